@@ -52,7 +52,7 @@
 TARGET_CRATES := libc std flate arena term \
                  serialize getopts collections test rand \
                  log graphviz core rbml alloc \
-                 unicode rustc_bitflags
+                 unicode rustc_bitflags alloc_system alloc_jemalloc
 RUSTC_CRATES := rustc rustc_typeck rustc_borrowck rustc_resolve rustc_driver \
                 rustc_trans rustc_back rustc_llvm rustc_privacy rustc_lint
 HOST_CRATES := syntax $(RUSTC_CRATES) rustdoc fmt_macros
@@ -62,7 +62,9 @@ TOOLS := compiletest rustdoc rustc rustbook
 DEPS_core :=
 DEPS_libc := core
 DEPS_unicode := core
-DEPS_alloc := core libc native:jemalloc
+DEPS_alloc := core libc alloc_system alloc_jemalloc
+DEPS_alloc_system := core libc
+DEPS_alloc_jemalloc := core libc native:jemalloc
 DEPS_std := core libc rand alloc collections unicode \
 	native:rust_builtin native:backtrace native:rustrt_native \
 	rustc_bitflags
@@ -117,6 +119,16 @@ ONLY_RLIB_rand := 1
 ONLY_RLIB_collections := 1
 ONLY_RLIB_unicode := 1
 ONLY_RLIB_rustc_bitflags := 1
+ONLY_RLIB_alloc_system := 1
+ONLY_RLIB_alloc_jemalloc := 1
+
+# jemalloc isn't built on every platform, in which case executables default to
+# the system allocator as well.
+ifdef CFG_DISABLE_JEMALLOC
+TARGET_CRATES := $(filter-out alloc_jemalloc,$(TARGET_CRATES))
+DEPS_alloc := $(filter-out alloc_jemalloc,$(DEPS_alloc))
+RUSTFLAGS_rustc_back := --cfg disable_jemalloc
+endif
 
 ################################################################################
 # You should not need to edit below this line
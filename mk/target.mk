@@ -21,6 +21,11 @@ RUST_LIB_FLAGS_ST0 += -W warnings
 RUST_LIB_FLAGS_ST1 += -D warnings
 RUST_LIB_FLAGS_ST2 += -D warnings
 
+# The libraries predate the clone_on_copy lint and still call `clone` on Copy
+# values in places; allow it until those are cleaned up.
+RUST_LIB_FLAGS_ST1 += -A clone-on-copy
+RUST_LIB_FLAGS_ST2 += -A clone-on-copy
+
 # Macro that generates the full list of dependencies for a crate at a particular
 # stage/target/host tuple.
 #
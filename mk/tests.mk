@@ -60,6 +60,8 @@ endif
 
 ifdef VERBOSE
   CTEST_TESTARGS += --verbose
+else
+  CTEST_TESTARGS += --quiet
 endif
 
 # Setting locale ensures that gdb's output remains consistent.
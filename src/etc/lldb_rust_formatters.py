@@ -108,19 +108,30 @@ def print_enum_val(val, internal_dict):
         # This is either an enum with just one variant, or it is an Option-like
         # enum where the discriminant is encoded in a non-nullable pointer
         # field. We find out which one it is by looking at the member name of
-        # the sole union variant. If it starts with "RUST$ENCODED$ENUM$" then
+        # the sole union variant. If it starts with "RUST$ENCODED$" then
         # we have an Option-like enum.
         first_variant_name = val.GetChildAtIndex(0).GetName()
-        if first_variant_name and first_variant_name.startswith("RUST$ENCODED$ENUM$"):
+        if first_variant_name and first_variant_name.startswith("RUST$ENCODED$"):
 
             # This is an Option-like enum. The position of the discriminator field is
             # encoded in the name which has the format:
             #  RUST$ENCODED$ENUM$<index of discriminator field>$<name of null variant>
+            # or, if the null variant is marked by a value other than zero:
+            #  RUST$ENCODED$NICHE$<value>$<index of discriminator field>$<name of null variant>
             last_separator_index = first_variant_name.rfind("$")
             if last_separator_index == -1:
                 return "<invalid enum encoding: %s>" % first_variant_name
 
-            start_index = len("RUST$ENCODED$ENUM$")
+            niche_value = 0
+            if first_variant_name.startswith("RUST$ENCODED$NICHE$"):
+                value_index = len("RUST$ENCODED$NICHE$")
+                try:
+                    start_index = first_variant_name.index("$", value_index) + 1
+                    niche_value = int(first_variant_name[value_index:start_index - 1])
+                except:
+                    return "<invalid enum encoding: %s>" % first_variant_name
+            else:
+                start_index = len("RUST$ENCODED$ENUM$")
 
             # Extract indices of the discriminator field
             try:
@@ -139,7 +150,7 @@ def print_enum_val(val, internal_dict):
             if disr_val.GetType().GetTypeClass() == lldb.eTypeClassStruct:
                 disr_val = disr_val.GetChildAtIndex(0)
 
-            if disr_val.GetValueAsUnsigned() == 0:
+            if disr_val.GetValueAsUnsigned() == niche_value:
                 # Null case: Print the name of the null-variant
                 null_variant_name = first_variant_name[last_separator_index + 1:]
                 return null_variant_name
@@ -239,6 +239,90 @@ pub fn weak_count<T>(this: &Arc<T>) -> usize { this.inner().weak.load(SeqCst) -
 #[unstable(feature = "alloc")]
 pub fn strong_count<T>(this: &Arc<T>) -> usize { this.inner().strong.load(SeqCst) }
 
+/// Unwraps the contained value if the `Arc<T>` is the only strong reference
+/// to it.
+///
+/// If other `Arc<T>` values share the same inner value, an `Err` is returned
+/// with the same `Arc<T>`. Any `Weak<T>` pointers will fail to upgrade once
+/// the value has been taken out.
+///
+/// # Examples
+///
+/// ```
+/// # #![feature(alloc)]
+/// extern crate alloc;
+/// # fn main() {
+/// use alloc::arc::{self, Arc};
+///
+/// let x = Arc::new(3);
+/// assert_eq!(arc::try_unwrap(x), Ok(3));
+///
+/// let x = Arc::new(4);
+/// let _y = x.clone();
+/// assert_eq!(arc::try_unwrap(x), Err(Arc::new(4)));
+/// # }
+/// ```
+#[inline]
+#[unstable(feature = "alloc")]
+pub fn try_unwrap<T>(this: Arc<T>) -> Result<T, Arc<T>> {
+    // See `drop` for why all these atomics are like this
+    if this.inner().strong.compare_and_swap(1, 0, Release) != 1 { return Err(this) }
+
+    atomic::fence(Acquire);
+
+    unsafe {
+        let ptr = *this._ptr;
+
+        let elem = ptr::read(&this.inner().data);
+
+        // Remove the implicit "strong weak" pointer now that we've taken the
+        // data out.
+        if this.inner().weak.fetch_sub(1, Release) == 1 {
+            atomic::fence(Acquire);
+            deallocate(ptr as *mut u8, size_of::<ArcInner<T>>(), min_align_of::<ArcInner<T>>())
+        }
+
+        mem::forget(this);
+        Ok(elem)
+    }
+}
+
+/// Returns a mutable reference to the contained value if the `Arc<T>` is
+/// unique, that is if there are no other `Arc<T>` or `Weak<T>` pointers to it.
+///
+/// Returns `None` if the `Arc<T>` is not unique.
+///
+/// # Examples
+///
+/// ```
+/// # #![feature(alloc)]
+/// extern crate alloc;
+/// # fn main() {
+/// use alloc::arc::{self, Arc};
+///
+/// let mut x = Arc::new(3);
+/// *arc::get_mut(&mut x).unwrap() = 4;
+/// assert_eq!(*x, 4);
+///
+/// let _y = x.clone();
+/// assert!(arc::get_mut(&mut x).is_none());
+/// # }
+/// ```
+#[inline]
+#[unstable(feature = "alloc")]
+pub fn get_mut<T>(this: &mut Arc<T>) -> Option<&mut T> {
+    // Our own strong reference also counts as a weak one, see `make_unique`.
+    if this.inner().strong.load(SeqCst) == 1 && this.inner().weak.load(SeqCst) == 1 {
+        // This unsafety is ok because we're guaranteed that the pointer
+        // returned is the *only* pointer that will ever be returned to T, for
+        // the same reasons as in `make_unique`.
+        let inner = unsafe { &mut **this._ptr };
+        Some(&mut inner.data)
+    } else {
+        None
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T> Clone for Arc<T> {
     /// Makes a clone of the `Arc<T>`.
@@ -691,6 +775,32 @@ mod tests {
         assert_eq!((*arc_v)[4], 5);
     }
 
+    #[test]
+    fn try_unwrap() {
+        let x = Arc::new(3);
+        assert_eq!(super::try_unwrap(x), Ok(3));
+        let x = Arc::new(4);
+        let _y = x.clone();
+        assert_eq!(super::try_unwrap(x), Err(Arc::new(4)));
+        let x = Arc::new(5);
+        let w = x.downgrade();
+        assert_eq!(super::try_unwrap(x), Ok(5));
+        assert!(w.upgrade().is_none());
+    }
+
+    #[test]
+    fn get_mut() {
+        let mut x = Arc::new(3);
+        *super::get_mut(&mut x).unwrap() = 4;
+        assert_eq!(*x, 4);
+        let y = x.clone();
+        assert!(super::get_mut(&mut x).is_none());
+        drop(y);
+        assert!(super::get_mut(&mut x).is_some());
+        let _w = x.downgrade();
+        assert!(super::get_mut(&mut x).is_none());
+    }
+
     #[test]
     fn test_cowarc_clone_make_unique() {
         let mut cow0 = Arc::new(75);
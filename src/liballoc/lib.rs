@@ -54,7 +54,12 @@
 //! ## Heap interfaces
 //!
 //! The [`heap`](heap/index.html) module defines the low-level interface to the
-//! default global allocator. It is not compatible with the libc allocator API.
+//! global allocator. It is not compatible with the libc allocator API.
+//!
+//! The allocator itself is not part of this library: it only refers to the
+//! `__rust_allocate` family of symbols, and exactly one crate tagged
+//! `#![allocator]` (such as `alloc_jemalloc` or `alloc_system`) defining them
+//! is linked into every final artifact.
 
 // Do not remove on snapshot creation. Needed for bootstrap. (Issue #22364)
 #![cfg_attr(stage0, feature(custom_attribute))]
@@ -71,23 +76,29 @@
 #![feature(no_std)]
 #![no_std]
 #![feature(allocator)]
+#![cfg_attr(not(stage0), feature(needs_allocator))]
+#![cfg_attr(not(stage0), needs_allocator)]
 #![feature(lang_items, unsafe_destructor)]
 #![feature(box_syntax)]
 #![feature(optin_builtin_traits)]
+#![feature(thread_local)]
 #![feature(unboxed_closures)]
 #![feature(unsafe_no_drop_flag)]
 #![feature(core)]
 #![feature(unique)]
 #![cfg_attr(test, feature(test, alloc, rustc_private))]
-#![cfg_attr(all(not(feature = "external_funcs"), not(feature = "external_crate")),
-            feature(libc))]
 
 
-#[macro_use]
+// Test builds get these macros from std instead.
+#[cfg_attr(not(test), macro_use)]
 extern crate core;
 
-#[cfg(all(not(feature = "external_funcs"), not(feature = "external_crate")))]
-extern crate libc;
+// The stage0 compiler doesn't inject an allocator crate, so link one in
+// explicitly when bootstrapping.
+#[cfg(all(stage0, jemalloc))]
+extern crate alloc_jemalloc;
+#[cfg(all(stage0, not(jemalloc)))]
+extern crate alloc_system;
 
 // Allow testing this library
 
@@ -112,16 +123,9 @@ mod boxed { pub use std::boxed::{Box, HEAP}; }
 mod boxed_test;
 pub mod arc;
 pub mod rc;
+pub mod oom;
 
-/// Common out-of-memory routine
-#[cold]
-#[inline(never)]
-pub fn oom() -> ! {
-    // FIXME(#14674): This really needs to do something other than just abort
-    //                here, but any printing done must be *guaranteed* to not
-    //                allocate.
-    unsafe { core::intrinsics::abort() }
-}
+pub use oom::oom;
 
 // FIXME(#14344): When linking liballoc with libstd, this library will be linked
 //                as an rlib (it only exists as an rlib). It turns out that an
@@ -0,0 +1,41 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Allocation failure handling
+//!
+//! When an allocation fails, `oom()` first calls the handler registered with
+//! `set_oom_handler`, if any, and then aborts the process. A handler gives
+//! the program a chance to report the failure, or to unwind or exit by some
+//! other means instead of aborting. Handlers must not allocate.
+
+use core::atomic::{AtomicUsize, ATOMIC_USIZE_INIT, Ordering};
+use core::mem;
+
+static HANDLER: AtomicUsize = ATOMIC_USIZE_INIT;
+
+/// Registers `handler` to be called whenever an allocation fails, replacing
+/// any handler registered previously.
+pub fn set_oom_handler(handler: fn()) {
+    HANDLER.store(handler as usize, Ordering::SeqCst);
+}
+
+/// Common out-of-memory routine
+#[cold]
+#[inline(never)]
+pub fn oom() -> ! {
+    let handler = HANDLER.load(Ordering::SeqCst);
+    if handler != 0 {
+        let handler: fn() = unsafe { mem::transmute(handler) };
+        handler();
+    }
+    // FIXME(#14674): The default handler should report the failure, but any
+    //                printing done must be *guaranteed* to not allocate.
+    unsafe { ::core::intrinsics::abort() }
+}
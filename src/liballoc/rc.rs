@@ -410,22 +410,92 @@ impl<T> Drop for Rc<T> {
             if !ptr.is_null() {
                 self.dec_strong();
                 if self.strong() == 0 {
-                    ptr::read(&**self); // destroy the contained object
+                    deferred::destroy(ptr as *mut u8, destroy_value::<T>);
+                }
+            }
+        }
+    }
+}
 
-                    // remove the implicit "strong weak" pointer now that we've
-                    // destroyed the contents.
-                    self.dec_weak();
+/// Destroys the contents of the `RcBox<T>` at `ptr`, whose strong count has
+/// dropped to zero, and frees the box unless `Weak<T>` pointers remain.
+unsafe fn destroy_value<T>(ptr: *mut u8) {
+    let ptr = ptr as *mut RcBox<T>;
+    ptr::read(&(*ptr).value); // destroy the contained object
 
-                    if self.weak() == 0 {
-                        deallocate(ptr as *mut u8, size_of::<RcBox<T>>(),
-                                   min_align_of::<RcBox<T>>())
-                    }
-                }
+    // remove the implicit "strong weak" pointer now that we've destroyed the
+    // contents.
+    let weak = (*ptr).weak.get() - 1;
+    (*ptr).weak.set(weak);
+
+    if weak == 0 {
+        deallocate(ptr as *mut u8, size_of::<RcBox<T>>(), min_align_of::<RcBox<T>>())
+    }
+}
+
+/// Destroying a value may drop the last `Rc` to another value, which destroys
+/// that one in turn, and so on: a long `Rc`-linked list would naively be freed
+/// by a recursion as deep as the list, overflowing the stack. Instead, once
+/// `MAX_DEPTH` destructions are nested, further values whose strong count hits
+/// zero are queued, and the outermost destruction works through the queue
+/// after its own value is gone. Queued values already have a strong count of
+/// zero, so `Weak::upgrade` fails on them as usual.
+#[cfg(all(any(target_os = "macos", target_os = "linux"), not(target_arch = "aarch64")))]
+mod deferred {
+    use super::boxed::{self, Box};
+    use core::ops::Drop;
+
+    const MAX_DEPTH: usize = 32;
+
+    struct Deferred {
+        next: *mut Deferred,
+        data: *mut u8,
+        destroy: unsafe fn(*mut u8),
+    }
+
+    #[thread_local]
+    static mut DEPTH: usize = 0;
+    #[thread_local]
+    static mut QUEUE: *mut Deferred = 0 as *mut Deferred;
+
+    struct Nested;
+
+    impl Drop for Nested {
+        fn drop(&mut self) {
+            unsafe { DEPTH -= 1; }
+        }
+    }
+
+    /// Calls `destroy(data)`, or queues the call if destructions are already
+    /// nested too deeply on this thread.
+    pub unsafe fn destroy(data: *mut u8, destroy: unsafe fn(*mut u8)) {
+        if DEPTH >= MAX_DEPTH {
+            QUEUE = boxed::into_raw(box Deferred { next: QUEUE, data: data, destroy: destroy });
+            return;
+        }
+
+        DEPTH += 1;
+        let _nested = Nested;
+        destroy(data);
+        if DEPTH == 1 {
+            while !QUEUE.is_null() {
+                let next = Box::from_raw(QUEUE);
+                QUEUE = next.next;
+                (next.destroy)(next.data);
             }
         }
     }
 }
 
+// Without `#[thread_local]` there is nowhere to keep the queue, so values are
+// destroyed in place.
+#[cfg(not(all(any(target_os = "macos", target_os = "linux"), not(target_arch = "aarch64"))))]
+mod deferred {
+    pub unsafe fn destroy(data: *mut u8, destroy: unsafe fn(*mut u8)) {
+        destroy(data)
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T> Clone for Rc<T> {
 
@@ -954,6 +1024,26 @@ mod tests {
         assert!(super::get_mut(&mut x).is_none());
     }
 
+    #[test]
+    fn drop_long_list() {
+        struct Node {
+            next: Option<Rc<Node>>,
+            last: Option<Weak<Node>>,
+        }
+
+        let first = Rc::new(Node { next: None, last: None });
+        let mut head = first.clone();
+        for _ in 0..10_000 {
+            head = Rc::new(Node { next: Some(head), last: Some(first.downgrade()) });
+        }
+        drop(first);
+        // The first node is only destroyed from the queue of deferred values,
+        // long after its strong count hit zero.
+        let weak = head.last.clone().unwrap();
+        drop(head);
+        assert!(weak.upgrade().is_none());
+    }
+
     #[test]
     fn test_cowrc_clone_make_unique() {
         let mut cow0 = Rc::new(75);
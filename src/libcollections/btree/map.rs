@@ -1379,6 +1379,16 @@ macro_rules! range_impl {
             let mut traversals = VecDeque::new();
             let (root, min, max) = ($root, $min, $max);
 
+            match (&min, &max) {
+                  (&Included(min_key), &Included(max_key))
+                | (&Included(min_key), &Excluded(max_key))
+                | (&Excluded(min_key), &Included(max_key))
+                | (&Excluded(min_key), &Excluded(max_key)) if min_key > max_key => {
+                    panic!("range start is greater than range end in BTreeMap")
+                }
+                _ => {}
+            }
+
             let mut leftmost = None;
             let mut rightmost = None;
 
@@ -1505,6 +1515,10 @@ impl<K: Ord, V> BTreeMap<K, V> {
     /// infinity", and if max is `Unbounded`, then it will be treated as "positive infinity".
     /// Thus range(Unbounded, Unbounded) will yield the whole collection.
     ///
+    /// # Panics
+    ///
+    /// Panics if min is greater than max.
+    ///
     /// # Examples
     ///
     /// ```
@@ -1532,6 +1546,10 @@ impl<K: Ord, V> BTreeMap<K, V> {
     /// infinity", and if max is `Unbounded`, then it will be treated as "positive infinity".
     /// Thus range(Unbounded, Unbounded) will yield the whole collection.
     ///
+    /// # Panics
+    ///
+    /// Panics if min is greater than max.
+    ///
     /// # Examples
     ///
     /// ```
@@ -1423,16 +1423,19 @@ pub enum TraversalItem<K, V, E> {
 }
 
 /// A traversal over a node's entries and edges
+#[cfg_attr(not(stage0), allow(private_in_public))]
 pub type Traversal<'a, K, V> = AbsTraversal<ElemsAndEdges<Zip<slice::Iter<'a, K>,
                                                               slice::Iter<'a, V>>,
                                                           slice::Iter<'a, Node<K, V>>>>;
 
 /// A mutable traversal over a node's entries and edges
+#[cfg_attr(not(stage0), allow(private_in_public))]
 pub type MutTraversal<'a, K, V> = AbsTraversal<ElemsAndEdges<Zip<slice::Iter<'a, K>,
                                                                  slice::IterMut<'a, V>>,
                                                              slice::IterMut<'a, Node<K, V>>>>;
 
 /// An owning traversal over a node's entries and edges
+#[cfg_attr(not(stage0), allow(private_in_public))]
 pub type MoveTraversal<K, V> = AbsTraversal<MoveTraversalImpl<K, V>>;
 
 
@@ -44,7 +44,8 @@
 #![feature(no_std)]
 #![no_std]
 
-#[macro_use]
+// Test builds get these macros from std instead.
+#[cfg_attr(not(test), macro_use)]
 extern crate core;
 
 extern crate unicode;
@@ -238,7 +238,7 @@ impl<T> [T] {
     /// elements.
     ///
     /// This sort is `O(n log n)` worst-case and stable, but allocates
-    /// approximately `2 * n`, where `n` is the length of `self`.
+    /// approximately `n / 2`, where `n` is the length of `self`.
     ///
     /// # Examples
     ///
@@ -257,6 +257,75 @@ impl<T> [T] {
         merge_sort(self, compare)
     }
 
+    /// Sorts the slice, in place, by the key `f` extracts from each element.
+    ///
+    /// This sort is stable and behaves like `sort_by`. `f` is called twice
+    /// for every comparison.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #![feature(collections)]
+    /// let mut v = [-5i32, 4, 1, -3, 2];
+    ///
+    /// v.sort_by_key(|k| k.abs());
+    /// assert!(v == [1, 2, -3, 4, -5]);
+    /// ```
+    #[unstable(feature = "collections", reason = "recently added")]
+    #[inline]
+    pub fn sort_by_key<B, F>(&mut self, mut f: F) where F: FnMut(&T) -> B, B: Ord {
+        merge_sort(self, |a, b| f(a).cmp(&f(b)))
+    }
+
+    /// Sorts the slice, in place, using `compare` to compare elements, but
+    /// may not preserve the order of equal elements.
+    ///
+    /// This sort is `O(n log n)` worst-case and does not allocate. It is
+    /// usually faster than `sort_by`.
+    ///
+    /// # Panics
+    ///
+    /// If `compare` panics, the slice is left in an unspecified order, but
+    /// still holds each of its elements exactly once.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #![feature(collections)]
+    /// let mut v = [5, 4, 1, 3, 2];
+    /// v.sort_unstable_by(|a, b| a.cmp(b));
+    /// assert!(v == [1, 2, 3, 4, 5]);
+    ///
+    /// // reverse sorting
+    /// v.sort_unstable_by(|a, b| b.cmp(a));
+    /// assert!(v == [5, 4, 3, 2, 1]);
+    /// ```
+    #[unstable(feature = "collections", reason = "recently added")]
+    #[inline]
+    pub fn sort_unstable_by<F>(&mut self, compare: F) where F: FnMut(&T, &T) -> Ordering {
+        core_slice::SliceExt::sort_unstable_by(self, compare)
+    }
+
+    /// Sorts the slice, in place, by the key `f` extracts from each element,
+    /// but may not preserve the order of equal elements.
+    ///
+    /// See `sort_unstable_by`. `f` is called twice for every comparison.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #![feature(collections)]
+    /// let mut v = [-5i32, 4, 1, -3, 2];
+    ///
+    /// v.sort_unstable_by_key(|k| k.abs());
+    /// assert!(v == [1, 2, -3, 4, -5]);
+    /// ```
+    #[unstable(feature = "collections", reason = "recently added")]
+    #[inline]
+    pub fn sort_unstable_by_key<B, F>(&mut self, mut f: F) where F: FnMut(&T) -> B, B: Ord {
+        core_slice::SliceExt::sort_unstable_by(self, |a, b| f(a).cmp(&f(b)))
+    }
+
     /// Consumes `src` and moves as many elements as it can into `self`
     /// from the range [start,end).
     ///
@@ -914,6 +983,26 @@ impl<T> [T] {
         self.sort_by(|a, b| a.cmp(b))
     }
 
+    /// Sorts the slice, in place, but may not preserve the order of equal
+    /// elements.
+    ///
+    /// This is equivalent to `self.sort_unstable_by(|a, b| a.cmp(b))`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #![feature(collections)]
+    /// let mut v = [-5, 4, 1, -3, 2];
+    ///
+    /// v.sort_unstable();
+    /// assert!(v == [-5, -3, 1, 2, 4]);
+    /// ```
+    #[unstable(feature = "collections", reason = "recently added")]
+    #[inline]
+    pub fn sort_unstable(&mut self) where T: Ord {
+        self.sort_unstable_by(|a, b| a.cmp(b))
+    }
+
     /// Binary search a sorted slice for a given element.
     ///
     /// If the value is found then `Ok` is returned, containing the
@@ -1297,7 +1386,7 @@ impl<T: Clone> Iterator for Permutations<T> {
 // Sorting
 ////////////////////////////////////////////////////////////////////////////////
 
-fn insertion_sort<T, F>(v: &mut [T], mut compare: F) where F: FnMut(&T, &T) -> Ordering {
+fn insertion_sort<T, F>(v: &mut [T], compare: &mut F) where F: FnMut(&T, &T) -> Ordering {
     let len = v.len() as isize;
     let buf_v = v.as_mut_ptr();
 
@@ -1314,7 +1403,7 @@ fn insertion_sort<T, F>(v: &mut [T], mut compare: F) where F: FnMut(&T, &T) -> O
 
             // 0 <= j - 1 < len, so .offset(j - 1) is in bounds.
             while j > 0 &&
-                    compare(&*read_ptr, &*buf_v.offset(j - 1)) == Less {
+                    (*compare)(&*read_ptr, &*buf_v.offset(j - 1)) == Less {
                 j -= 1;
             }
 
@@ -1355,139 +1444,111 @@ fn merge_sort<T, F>(v: &mut [T], mut compare: F) where F: FnMut(&T, &T) -> Order
 
     // short vectors get sorted in-place via insertion sort to avoid allocations
     if len <= insertion {
-        insertion_sort(v, compare);
+        insertion_sort(v, &mut compare);
         return;
     }
 
-    // allocate some memory to use as scratch memory, we keep the
-    // length 0 so we can keep shallow copies of the contents of `v`
-    // without risking the dtors running on an object twice if
-    // `compare` panics.
-    let mut working_space = Vec::with_capacity(2 * len);
-    // these both are buffers of length `len`.
-    let mut buf_dat = working_space.as_mut_ptr();
-    let mut buf_tmp = unsafe {buf_dat.offset(len as isize)};
-
-    // length `len`.
-    let buf_v = v.as_ptr();
-
-    // step 1. sort short runs with insertion sort. This takes the
-    // values from `v` and sorts them into `buf_dat`, leaving that
-    // with sorted runs of length INSERTION.
-
-    // We could hardcode the sorting comparisons here, and we could
-    // manipulate/step the pointers themselves, rather than repeatedly
-    // .offset-ing.
-    for start in (0.. len).step_by(insertion) {
-        // start <= i < len;
-        for i in start..cmp::min(start + insertion, len) {
-            // j satisfies: start <= j <= i;
-            let mut j = i as isize;
-            unsafe {
-                // `i` is in bounds.
-                let read_ptr = buf_v.offset(i as isize);
-
-                // find where to insert, we need to do strict <,
-                // rather than <=, to maintain stability.
-
-                // start <= j - 1 < len, so .offset(j - 1) is in
-                // bounds.
-                while j > start as isize &&
-                        compare(&*read_ptr, &*buf_dat.offset(j - 1)) == Less {
-                    j -= 1;
-                }
-
-                // shift everything to the right, to make space to
-                // insert this value.
+    // zero-sized values are all alike, so any order is a sorted one.
+    if size_of::<T>() == 0 {
+        return;
+    }
 
-                // j + 1 could be `len` (for the last `i`), but in
-                // that case, `i == j` so we don't copy. The
-                // `.offset(j)` is always in bounds.
-                ptr::copy(buf_dat.offset(j + 1),
-                          &*buf_dat.offset(j),
-                          i - j as usize);
-                ptr::copy_nonoverlapping(buf_dat.offset(j), read_ptr, 1);
-            }
-        }
+    // step 1. sort short runs of length `insertion` in place.
+    for start in (0..len).step_by(insertion) {
+        insertion_sort(&mut v[start..cmp::min(start + insertion, len)], &mut compare);
     }
 
-    // step 2. merge the sorted runs.
+    // step 2. merge the sorted runs, pairwise. Only the shorter run of each
+    // pair is moved out of `v`, and it is never longer than `len / 2`, so
+    // that is all the scratch memory needed. We keep the length of the
+    // buffer 0 so that it only ever holds shallow copies of the contents
+    // of `v`, and the dtors can't run twice if `compare` panics.
+    let mut working_space = Vec::with_capacity(len / 2);
+    let buf = working_space.as_mut_ptr();
+
     let mut width = insertion;
     while width < len {
-        // merge the sorted runs of length `width` in `buf_dat` two at
-        // a time, placing the result in `buf_tmp`.
-
-        // 0 <= start <= len.
         for start in (0..len).step_by(2 * width) {
-            // manipulate pointers directly for speed (rather than
-            // using a `for` loop with `range` and `.offset` inside
-            // that loop).
-            unsafe {
-                // the end of the first run & start of the
-                // second. Offset of `len` is defined, since this is
-                // precisely one byte past the end of the object.
-                let right_start = buf_dat.offset(cmp::min(start + width, len) as isize);
-                // end of the second. Similar reasoning to the above re safety.
-                let right_end_idx = cmp::min(start + 2 * width, len);
-                let right_end = buf_dat.offset(right_end_idx as isize);
-
-                // the pointers to the elements under consideration
-                // from the two runs.
-
-                // both of these are in bounds.
-                let mut left = buf_dat.offset(start as isize);
-                let mut right = right_start;
-
-                // where we're putting the results, it is a run of
-                // length `2*width`, so we step it once for each step
-                // of either `left` or `right`.  `buf_tmp` has length
-                // `len`, so these are in bounds.
-                let mut out = buf_tmp.offset(start as isize);
-                let out_end = buf_tmp.offset(right_end_idx as isize);
-
-                while out < out_end {
-                    // Either the left or the right run are exhausted,
-                    // so just copy the remainder from the other run
-                    // and move on; this gives a huge speed-up (order
-                    // of 25%) for mostly sorted vectors (the best
-                    // case).
-                    if left == right_start {
-                        // the number remaining in this run.
-                        let elems = (right_end as usize - right as usize) / mem::size_of::<T>();
-                        ptr::copy_nonoverlapping(out, &*right, elems);
-                        break;
-                    } else if right == right_end {
-                        let elems = (right_start as usize - left as usize) / mem::size_of::<T>();
-                        ptr::copy_nonoverlapping(out, &*left, elems);
-                        break;
-                    }
-
-                    // check which side is smaller, and that's the
-                    // next element for the new run.
-
-                    // `left < right_start` and `right < right_end`,
-                    // so these are valid.
-                    let to_copy = if compare(&*left, &*right) == Greater {
-                        step(&mut right)
-                    } else {
-                        step(&mut left)
-                    };
-                    ptr::copy_nonoverlapping(out, &*to_copy, 1);
-                    step(&mut out);
-                }
+            let mid = start + width;
+            if mid >= len {
+                break;
             }
-        }
+            let end = cmp::min(mid + width, len);
 
-        mem::swap(&mut buf_dat, &mut buf_tmp);
+            // runs that are in order already need no merging, which makes
+            // sorting mostly sorted vectors (the best case) a lot faster.
+            if compare(&v[mid - 1], &v[mid]) != Greater {
+                continue;
+            }
 
+            unsafe {
+                merge(&mut v[start..end], mid - start, buf, &mut compare);
+            }
+        }
         width *= 2;
     }
+}
 
-    // write the result to `v` in one go, so that there are never two copies
-    // of the same object in `v`.
-    unsafe {
-        ptr::copy_nonoverlapping(v.as_mut_ptr(), &*buf_dat, len);
+/// Merges the sorted runs `v[..mid]` and `v[mid..]` into one, using `buf` as
+/// temporary storage for the shorter of them.
+///
+/// `buf` must have room for `min(mid, v.len() - mid)` elements, and `T` must
+/// not be zero-sized.
+unsafe fn merge<T, F>(v: &mut [T], mid: usize, buf: *mut T, compare: &mut F)
+                      where F: FnMut(&T, &T) -> Ordering {
+    let len = v.len();
+    let v = v.as_mut_ptr();
+    let v_mid = v.offset(mid as isize);
+    let v_end = v.offset(len as isize);
+
+    // While merging, the shorter run lives in `buf`, and what is left of it
+    // is always exactly as long as the gap in `v` between the elements merged
+    // so far and the rest of the longer run. `hole` tracks both, so that if
+    // `compare` panics the remaining elements get copied back into the gap
+    // and `v` holds every element once again.
+    let mut hole;
+
+    if mid <= len - mid {
+        // The left run is shorter: move it out and merge forwards.
+        ptr::copy_nonoverlapping(buf, v, mid);
+        hole = MergeHole { start: buf, end: buf.offset(mid as isize), dest: v };
+
+        let left = &mut hole.start;
+        let mut right = v_mid;
+        let out = &mut hole.dest;
+
+        while *left < hole.end && right < v_end {
+            // take from the right run only if it is strictly smaller,
+            // to maintain stability.
+            let to_copy = if (*compare)(&*right, &**left) == Less {
+                step(&mut right)
+            } else {
+                step(left)
+            };
+            ptr::copy_nonoverlapping(step(out), to_copy, 1);
+        }
+    } else {
+        // The right run is shorter: move it out and merge backwards.
+        ptr::copy_nonoverlapping(buf, v_mid, len - mid);
+        hole = MergeHole { start: buf, end: buf.offset((len - mid) as isize), dest: v_mid };
+
+        let left = &mut hole.dest;
+        let right = &mut hole.end;
+        let mut out = v_end;
+
+        while v < *left && buf < *right {
+            // take from the left run only if it is strictly greater,
+            // to maintain stability.
+            let to_copy = if (*compare)(&*right.offset(-1), &*left.offset(-1)) == Less {
+                step_back(left)
+            } else {
+                step_back(right)
+            };
+            ptr::copy_nonoverlapping(step_back(&mut out), to_copy, 1);
+        }
     }
+    // `hole` is dropped here, copying whatever is left of the shorter run
+    // into place.
 
     // increment the pointer, returning the old pointer.
     #[inline(always)]
@@ -1496,4 +1557,28 @@ fn merge_sort<T, F>(v: &mut [T], mut compare: F) where F: FnMut(&T, &T) -> Order
         *ptr = ptr.offset(1);
         old
     }
+
+    // decrement the pointer, returning the new pointer.
+    #[inline(always)]
+    unsafe fn step_back<T>(ptr: &mut *mut T) -> *mut T {
+        *ptr = ptr.offset(-1);
+        *ptr
+    }
+}
+
+/// The not yet merged elements `start..end` of the shorter run of a merge,
+/// which belong at `dest` in the slice being sorted.
+struct MergeHole<T> {
+    start: *mut T,
+    end: *mut T,
+    dest: *mut T,
+}
+
+#[unsafe_destructor]
+impl<T> Drop for MergeHole<T> {
+    fn drop(&mut self) {
+        // `T` is not zero-sized, see `merge`.
+        let len = (self.end as usize - self.start as usize) / mem::size_of::<T>();
+        unsafe { ptr::copy_nonoverlapping(self.dest, self.start, len); }
+    }
 }
@@ -16,6 +16,7 @@
 
 use core::prelude::*;
 
+use alloc::boxed::Box;
 use core::default::Default;
 use core::error::Error;
 use core::fmt;
@@ -31,7 +32,7 @@ use unicode::str::Utf16Item;
 
 use borrow::{Cow, IntoCow};
 use str::{self, FromStr, Utf8Error};
-use vec::{DerefVec, Vec, as_vec};
+use vec::{DerefVec, ReserveError, Vec, as_vec};
 
 /// A growable string stored as a UTF-8 encoded buffer.
 #[derive(Clone, PartialOrd, Eq, Ord)]
@@ -441,6 +442,35 @@ impl String {
         self.vec.reserve_exact(additional)
     }
 
+    /// Tries to reserve capacity for at least `additional` more bytes to be
+    /// inserted in the given `String`, reporting failure to the caller
+    /// instead of panicking or calling the out-of-memory handler.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![feature(collections)]
+    /// let mut s = String::new();
+    /// assert!(s.try_reserve(10).is_ok());
+    /// assert!(s.capacity() >= 10);
+    /// ```
+    #[inline]
+    #[unstable(feature = "collections",
+               reason = "fallible allocation is still experimental")]
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), ReserveError> {
+        self.vec.try_reserve(additional)
+    }
+
+    /// Tries to reserve the minimum capacity for exactly `additional` more
+    /// bytes to be inserted in the given `String`, reporting failure to the
+    /// caller instead of panicking or calling the out-of-memory handler.
+    #[inline]
+    #[unstable(feature = "collections",
+               reason = "fallible allocation is still experimental")]
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), ReserveError> {
+        self.vec.try_reserve_exact(additional)
+    }
+
     /// Shrinks the capacity of this string buffer to match its length.
     ///
     /// # Examples
@@ -718,6 +748,8 @@ impl fmt::Display for FromUtf8Error {
 #[stable(feature = "rust1", since = "1.0.0")]
 impl Error for FromUtf8Error {
     fn description(&self) -> &str { "invalid utf-8" }
+
+    fn cause(&self) -> Option<&Error> { Some(&self.error) }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -732,6 +764,53 @@ impl Error for FromUtf16Error {
     fn description(&self) -> &str { "invalid utf-16" }
 }
 
+/// The error a message is boxed up as when converted into a `Box<Error>`.
+struct StringError(String);
+
+impl Error for StringError {
+    fn description(&self) -> &str { &self.0 }
+}
+
+impl fmt::Display for StringError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Debug for StringError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+#[stable(feature = "rust1", since = "1.0.0")]
+impl From<String> for Box<Error> {
+    fn from(err: String) -> Box<Error> {
+        Box::new(StringError(err))
+    }
+}
+
+#[stable(feature = "rust1", since = "1.0.0")]
+impl From<String> for Box<Error + Send> {
+    fn from(err: String) -> Box<Error + Send> {
+        Box::new(StringError(err))
+    }
+}
+
+#[stable(feature = "rust1", since = "1.0.0")]
+impl<'a, 'b> From<&'b str> for Box<Error + 'a> {
+    fn from(err: &'b str) -> Box<Error + 'a> {
+        Box::new(StringError(String::from_str(err)))
+    }
+}
+
+#[stable(feature = "rust1", since = "1.0.0")]
+impl<'a, 'b> From<&'b str> for Box<Error + Send + 'a> {
+    fn from(err: &'b str) -> Box<Error + Send + 'a> {
+        Box::new(StringError(String::from_str(err)))
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl FromIterator<char> for String {
     fn from_iter<I: IntoIterator<Item=char>>(iter: I) -> String {
@@ -149,6 +149,17 @@ pub struct Vec<T> {
 unsafe impl<T: Send> Send for Vec<T> { }
 unsafe impl<T: Sync> Sync for Vec<T> { }
 
+/// The error returned when a vector could not reserve the requested capacity.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[unstable(feature = "collections",
+           reason = "fallible allocation is still experimental")]
+pub enum ReserveError {
+    /// The requested capacity does not fit in a `usize` worth of bytes.
+    CapacityOverflow,
+    /// The allocator could not provide the requested memory.
+    AllocationFailure,
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Inherent methods
 ////////////////////////////////////////////////////////////////////////////////
@@ -341,6 +352,58 @@ impl<T> Vec<T> {
         }
     }
 
+    /// Tries to reserve capacity for at least `additional` more elements to be
+    /// inserted in the given `Vec<T>`, like `reserve` does.
+    ///
+    /// Unlike `reserve`, this reports failure to the caller instead of
+    /// panicking or calling the out-of-memory handler. The vector is left
+    /// unchanged if it could not grow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![feature(collections)]
+    /// use std::usize;
+    ///
+    /// let mut vec = vec![1];
+    /// assert!(vec.try_reserve(10).is_ok());
+    /// assert!(vec.capacity() >= 11);
+    /// assert!(vec.try_reserve(usize::MAX).is_err());
+    /// ```
+    #[unstable(feature = "collections",
+               reason = "fallible allocation is still experimental")]
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), ReserveError> {
+        if self.cap - self.len < additional {
+            match self.len.checked_add(additional)
+                          .and_then(|cap| cap.checked_next_power_of_two()) {
+                None => Err(ReserveError::CapacityOverflow),
+                Some(new_cap) => self.try_grow_capacity(new_cap)
+            }
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Tries to reserve the minimum capacity for exactly `additional` more
+    /// elements to be inserted in the given `Vec<T>`, like `reserve_exact`
+    /// does.
+    ///
+    /// Unlike `reserve_exact`, this reports failure to the caller instead of
+    /// panicking or calling the out-of-memory handler. The vector is left
+    /// unchanged if it could not grow.
+    #[unstable(feature = "collections",
+               reason = "fallible allocation is still experimental")]
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), ReserveError> {
+        if self.cap - self.len < additional {
+            match self.len.checked_add(additional) {
+                None => Err(ReserveError::CapacityOverflow),
+                Some(new_cap) => self.try_grow_capacity(new_cap)
+            }
+        } else {
+            Ok(())
+        }
+    }
+
     /// Shrinks the capacity of the vector as much as possible.
     ///
     /// It will drop down as close as possible to the length but the allocator
@@ -1230,18 +1293,31 @@ impl<T> Vec<T> {
     /// If the capacity for `self` is already equal to or greater than the
     /// requested capacity, then no action is taken.
     fn grow_capacity(&mut self, capacity: usize) {
-        if mem::size_of::<T>() == 0 { return }
+        match self.try_grow_capacity(capacity) {
+            Ok(()) => {}
+            Err(ReserveError::CapacityOverflow) => panic!("capacity overflow"),
+            Err(ReserveError::AllocationFailure) => ::alloc::oom(),
+        }
+    }
+
+    /// Like `grow_capacity`, but leaves the vector untouched and returns an
+    /// error if it cannot grow.
+    fn try_grow_capacity(&mut self, capacity: usize) -> Result<(), ReserveError> {
+        if mem::size_of::<T>() == 0 { return Ok(()) }
 
         if capacity > self.cap {
-            let size = capacity.checked_mul(mem::size_of::<T>())
-                               .expect("capacity overflow");
+            let size = match capacity.checked_mul(mem::size_of::<T>()) {
+                Some(size) => size,
+                None => return Err(ReserveError::CapacityOverflow)
+            };
             unsafe {
                 let ptr = alloc_or_realloc(*self.ptr, self.cap * mem::size_of::<T>(), size);
-                if ptr.is_null() { ::alloc::oom() }
+                if ptr.is_null() { return Err(ReserveError::AllocationFailure) }
                 self.ptr = Unique::new(ptr);
             }
             self.cap = capacity;
         }
+        Ok(())
     }
 }
 
@@ -1720,13 +1796,6 @@ impl<T> AsRef<Vec<T>> for Vec<T> {
     }
 }
 
-#[stable(feature = "rust1", since = "1.0.0")]
-impl<T> Into<Vec<T>> for Vec<T> {
-    fn into(self) -> Vec<T> {
-        self
-    }
-}
-
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T> AsRef<[T]> for Vec<T> {
     fn as_ref(&self) -> &[T] {
@@ -198,6 +198,102 @@ fn test_range() {
     }
 }
 
+#[test]
+fn test_range_empty() {
+    let map: BTreeMap<u32, u32> = BTreeMap::new();
+    assert_eq!(map.range(Unbounded, Unbounded).next(), None);
+    assert_eq!(map.range(Included(&1), Excluded(&2)).next(), None);
+
+    let map: BTreeMap<_, _> = (0..100).map(|i| (i * 10, i)).collect();
+    assert_eq!(map.range(Excluded(&10), Excluded(&20)).next(), None);
+    assert_eq!(map.range(Included(&11), Included(&19)).next(), None);
+    assert_eq!(map.range(Included(&20), Excluded(&20)).next(), None);
+    assert_eq!(map.range(Excluded(&20), Included(&20)).next(), None);
+    assert_eq!(map.range(Excluded(&990), Unbounded).next(), None);
+    assert_eq!(map.range(Unbounded, Excluded(&0)).next_back(), None);
+}
+
+#[test]
+fn test_range_single() {
+    let map: BTreeMap<_, _> = (0..100).map(|i| (i * 10, i)).collect();
+
+    let mut kvs = map.range(Included(&500), Included(&500));
+    assert_eq!(kvs.next(), Some((&500, &50)));
+    assert_eq!(kvs.next(), None);
+
+    let mut kvs = map.range(Excluded(&490), Excluded(&510));
+    assert_eq!(kvs.next_back(), Some((&500, &50)));
+    assert_eq!(kvs.next_back(), None);
+    assert_eq!(kvs.next(), None);
+}
+
+#[test]
+#[should_panic]
+fn test_range_inverted() {
+    let map: BTreeMap<_, _> = (0..10).map(|i| (i, i)).collect();
+    map.range(Included(&6), Included(&5));
+}
+
+#[test]
+fn test_range_random() {
+    use std::rand::{Rng, weak_rng};
+
+    fn contains(min: Bound<&u32>, max: Bound<&u32>, k: u32) -> bool {
+        (match min { Included(&m) => k >= m, Excluded(&m) => k > m, Unbounded => true }) &&
+        (match max { Included(&m) => k <= m, Excluded(&m) => k < m, Unbounded => true })
+    }
+
+    fn bound<'a, R: Rng>(rng: &mut R, key: &'a u32) -> Bound<&'a u32> {
+        match rng.gen_range(0, 3) {
+            0 => Included(key),
+            1 => Excluded(key),
+            _ => Unbounded,
+        }
+    }
+
+    let mut rng = weak_rng();
+    let mut map = BTreeMap::new();
+    let mut model = Vec::new();
+    for _ in 0..500 {
+        let k = rng.gen_range(0, 1000);
+        if map.insert(k, k * 2).is_none() {
+            model.push(k);
+        }
+    }
+    model.sort();
+
+    for _ in 0..1000 {
+        let a = rng.gen_range(0, 1000);
+        let b = rng.gen_range(a, 1000);
+        let (min, max) = (bound(&mut rng, &a), bound(&mut rng, &b));
+        let expected: Vec<_> = model.iter().cloned()
+                                    .filter(|&k| contains(min, max, k))
+                                    .map(|k| (k, k * 2))
+                                    .collect();
+
+        let forward: Vec<_> = map.range(min, max).map(|(&k, &v)| (k, v)).collect();
+        assert_eq!(forward, expected);
+        let mut backward: Vec<_> = map.range(min, max).rev().map(|(&k, &v)| (k, v)).collect();
+        backward.reverse();
+        assert_eq!(backward, expected);
+    }
+}
+
+#[test]
+fn test_borrowed_lookup() {
+    let mut map = BTreeMap::new();
+    map.insert("one".to_string(), 1);
+    map.insert("two".to_string(), 2);
+
+    assert_eq!(map.get("one"), Some(&1));
+    assert!(map.contains_key("two"));
+    assert_eq!(map.get("three"), None);
+    *map.get_mut("two").unwrap() += 10;
+    assert_eq!(map["two"], 12);
+    assert_eq!(map.remove("one"), Some(1));
+    assert_eq!(map.len(), 1);
+}
+
 #[test]
 fn test_entry(){
     let xs = [(1, 10), (2, 20), (3, 30), (4, 40), (5, 50), (6, 60)];
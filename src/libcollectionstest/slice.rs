@@ -597,6 +597,71 @@ fn test_sort_stability() {
     }
 }
 
+#[test]
+fn test_sort_stability_large() {
+    // long enough for the merging to kick in
+    for &len in &[100, 257, 1000] {
+        let mut counts = [0; 10];
+        let mut v: Vec<_> = (0..len).map(|_| {
+                let n = thread_rng().gen::<usize>() % 10;
+                counts[n] += 1;
+                (n, counts[n])
+            }).collect();
+        v.sort_by(|&(a,_), &(b,_)| a.cmp(&b));
+        assert!(v.windows(2).all(|w| w[0] <= w[1]));
+    }
+}
+
+#[test]
+fn test_sort_unstable() {
+    let mut rng = thread_rng();
+    for &len in &[0, 1, 2, 5, 20, 21, 49, 50, 100, 1000, 10000] {
+        for &modulus in &[5, 100, 1 << 30] {
+            let v: Vec<i32> = rng.gen_iter::<i32>().take(len).map(|x| x % modulus).collect();
+
+            // a few shapes that commonly trip up quicksort
+            let mut sorted = v.clone();
+            sorted.sort();
+            let mut reversed = sorted.clone();
+            reversed.reverse();
+            let sawtooth: Vec<i32> = (0..len as i32).map(|i| i % 7).collect();
+            let mut nearly_sorted = sorted.clone();
+            if len > 1 {
+                nearly_sorted.swap(0, len - 1);
+            }
+
+            for input in &[v, sorted, reversed, sawtooth, nearly_sorted] {
+                let mut expected = input.clone();
+                expected.sort();
+
+                let mut v1 = input.clone();
+                v1.sort_unstable();
+                assert_eq!(v1, expected);
+
+                let mut v2 = input.clone();
+                v2.sort_unstable_by(|a, b| b.cmp(a));
+                expected.reverse();
+                assert_eq!(v2, expected);
+            }
+        }
+    }
+
+    let mut v = [(); 100];
+    v.sort_unstable();
+}
+
+#[test]
+fn test_sort_by_key() {
+    let mut v: Vec<_> = (0..200).map(|i| (i * 37 % 10, i)).collect();
+    v.sort_by_key(|&(k, _)| k);
+    assert!(v.windows(2).all(|w| w[0] <= w[1]));
+
+    let mut calls = 0;
+    v.sort_unstable_by_key(|&(_, i)| { calls += 1; -i });
+    assert!(calls > 0);
+    assert!(v.windows(2).all(|w| w[0].1 > w[1].1));
+}
+
 #[test]
 fn test_concat() {
     let v: [Vec<i32>; 0] = [];
@@ -1525,6 +1590,55 @@ mod bench {
         b.bytes = (v.len() * mem::size_of_val(&v[0])) as u64;
     }
 
+    #[bench]
+    fn sort_unstable_random_small(b: &mut Bencher) {
+        let mut rng = weak_rng();
+        b.iter(|| {
+            let mut v: Vec<_> = rng.gen_iter::<u64>().take(5).collect();
+            v.sort_unstable();
+        });
+        b.bytes = 5 * mem::size_of::<u64>() as u64;
+    }
+
+    #[bench]
+    fn sort_unstable_random_medium(b: &mut Bencher) {
+        let mut rng = weak_rng();
+        b.iter(|| {
+            let mut v: Vec<_> = rng.gen_iter::<u64>().take(100).collect();
+            v.sort_unstable();
+        });
+        b.bytes = 100 * mem::size_of::<u64>() as u64;
+    }
+
+    #[bench]
+    fn sort_unstable_random_large(b: &mut Bencher) {
+        let mut rng = weak_rng();
+        b.iter(|| {
+            let mut v: Vec<_> = rng.gen_iter::<u64>().take(10000).collect();
+            v.sort_unstable();
+        });
+        b.bytes = 10000 * mem::size_of::<u64>() as u64;
+    }
+
+    #[bench]
+    fn sort_unstable_sorted(b: &mut Bencher) {
+        let mut v: Vec<_> = (0..10000).collect();
+        b.iter(|| {
+            v.sort_unstable();
+        });
+        b.bytes = (v.len() * mem::size_of_val(&v[0])) as u64;
+    }
+
+    #[bench]
+    fn sort_by_key_random_large(b: &mut Bencher) {
+        let mut rng = weak_rng();
+        b.iter(|| {
+            let mut v: Vec<_> = rng.gen_iter::<(u32, u64)>().take(10000).collect();
+            v.sort_by_key(|&(k, _)| k);
+        });
+        b.bytes = 10000 * mem::size_of::<(u32, u64)>() as u64;
+    }
+
     type BigSortable = (u64, u64, u64, u64);
 
     #[bench]
@@ -97,6 +97,27 @@ fn test_reserve() {
     assert!(v.capacity() >= 33)
 }
 
+#[test]
+fn test_try_reserve() {
+    use std::usize;
+    use std::vec::ReserveError;
+
+    let mut v: Vec<u32> = Vec::new();
+    assert_eq!(v.try_reserve(2), Ok(()));
+    assert!(v.capacity() >= 2);
+
+    v.push(1);
+    assert_eq!(v.try_reserve(usize::MAX), Err(ReserveError::CapacityOverflow));
+    assert_eq!(v.try_reserve_exact(usize::MAX / 2), Err(ReserveError::CapacityOverflow));
+    assert_eq!(v, [1]);
+
+    let mut v: Vec<u8> = vec![1, 2, 3];
+    assert_eq!(v.try_reserve_exact(usize::MAX / 2), Err(ReserveError::AllocationFailure));
+    assert_eq!(v, [1, 2, 3]);
+    assert_eq!(v.try_reserve_exact(10), Ok(()));
+    assert!(v.capacity() >= 13);
+}
+
 #[test]
 fn test_extend() {
     let mut v = Vec::new();
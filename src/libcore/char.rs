@@ -15,11 +15,15 @@
 #![allow(non_snake_case)]
 #![doc(primitive = "char")]
 
+use error::Error;
+use fmt;
 use iter::Iterator;
 use mem::transmute;
 use option::Option::{None, Some};
 use option::Option;
+use result::Result::{self, Ok, Err};
 use slice::SliceExt;
+use str::{FromStr, StrExt};
 
 // UTF-8 ranges and tags for encoding characters
 const TAG_CONT: u8    = 0b1000_0000;
@@ -272,6 +276,50 @@ pub fn encode_utf16_raw(mut ch: u32, dst: &mut [u16]) -> Option<usize> {
     }
 }
 
+#[stable(feature = "rust1", since = "1.0.0")]
+impl FromStr for char {
+    type Err = ParseCharError;
+
+    /// Parse a `char` from a string holding exactly one character.
+    #[inline]
+    fn from_str(s: &str) -> Result<char, ParseCharError> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (None, _) => Err(ParseCharError { kind: CharErrorKind::EmptyString }),
+            (Some(c), None) => Ok(c),
+            _ => Err(ParseCharError { kind: CharErrorKind::TooManyChars }),
+        }
+    }
+}
+
+/// An error returned when parsing a `char` from a string fails.
+#[derive(Debug, Clone, PartialEq)]
+#[unstable(feature = "core", reason = "recently added")]
+pub struct ParseCharError { kind: CharErrorKind }
+
+#[derive(Debug, Clone, PartialEq)]
+enum CharErrorKind {
+    EmptyString,
+    TooManyChars,
+}
+
+#[stable(feature = "rust1", since = "1.0.0")]
+impl fmt::Display for ParseCharError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.description().fmt(f)
+    }
+}
+
+#[stable(feature = "rust1", since = "1.0.0")]
+impl Error for ParseCharError {
+    fn description(&self) -> &str {
+        match self.kind {
+            CharErrorKind::EmptyString => "cannot parse char from empty string",
+            CharErrorKind::TooManyChars => "too many characters in string",
+        }
+    }
+}
+
 /// An iterator over the characters that represent a `char`, as escaped by
 /// Rust's unicode escaping rules.
 #[derive(Clone)]
@@ -14,13 +14,13 @@
 //!
 //! `Error` is a trait representing the basic expectations for error values,
 //! i.e. values of type `E` in `Result<T, E>`. At a minimum, errors must provide
-//! a description, but they may optionally provide additional detail (via
-//! `Display`) and cause chain information:
+//! a description and be printable (via `Debug` and `Display`), but they may
+//! optionally provide cause chain information:
 //!
 //! ```
-//! use std::fmt::Display;
+//! use std::fmt::{Debug, Display};
 //!
-//! trait Error: Display {
+//! trait Error: Debug + Display {
 //!     fn description(&self) -> &str;
 //!
 //!     fn cause(&self) -> Option<&Error> { None }
@@ -34,22 +34,22 @@
 //! particular implementation, but also reveal some of its implementation for
 //! debugging via `cause` chains.
 //!
-//! # The `FromError` trait
+//! The trait is object safe, and `Box<Error + Send>` makes for a universal
+//! error type: any `Error + Send` type, as well as `String` and `&str`
+//! messages, can be converted into one with `From`, so `try!` can be used on
+//! them in functions returning `Result<T, Box<Error + Send>>`.
 //!
-//! `FromError` is a simple trait that expresses conversions between different
-//! error types. To provide maximum flexibility, it does not require either of
-//! the types to actually implement the `Error` trait, although this will be the
-//! common case.
+//! # Converting errors
 //!
-//! The main use of this trait is in the `try!` macro, which uses it to
-//! automatically convert a given error to the error specified in a function's
-//! return type.
+//! The `try!` macro converts the error it returns early with to the error
+//! type of the enclosing function using the `From` trait. To provide maximum
+//! flexibility, conversions don't require either of the types to actually
+//! implement the `Error` trait, although this will be the common case.
 //!
 //! For example,
 //!
 //! ```
 //! # #![feature(os, old_io, old_path)]
-//! use std::error::FromError;
 //! use std::old_io::{File, IoError};
 //! use std::os::{MemoryMap, MapError};
 //! use std::old_path::Path;
@@ -59,14 +59,14 @@
 //!     Map(MapError)
 //! }
 //!
-//! impl FromError<IoError> for MyError {
-//!     fn from_error(err: IoError) -> MyError {
+//! impl From<IoError> for MyError {
+//!     fn from(err: IoError) -> MyError {
 //!         MyError::Io(err)
 //!     }
 //! }
 //!
-//! impl FromError<MapError> for MyError {
-//!     fn from_error(err: MapError) -> MyError {
+//! impl From<MapError> for MyError {
+//!     fn from(err: MapError) -> MyError {
 //!         MyError::Map(err)
 //!     }
 //! }
@@ -86,8 +86,11 @@ use prelude::*;
 use fmt::{Debug, Display};
 
 /// Base functionality for all errors in Rust.
+///
+/// Errors need not be `Send`; use `Box<Error + Send>` where boxed errors
+/// must cross threads.
 #[stable(feature = "rust1", since = "1.0.0")]
-pub trait Error: Debug + Display + Send {
+pub trait Error: Debug + Display {
     /// A short description of the error.
     ///
     /// The description should not contain newlines or sentence-ending
@@ -100,19 +103,3 @@ pub trait Error: Debug + Display + Send {
     #[stable(feature = "rust1", since = "1.0.0")]
     fn cause(&self) -> Option<&Error> { None }
 }
-
-/// A trait for types that can be converted from a given error type `E`.
-#[stable(feature = "rust1", since = "1.0.0")]
-pub trait FromError<E> {
-    /// Perform the conversion.
-    #[stable(feature = "rust1", since = "1.0.0")]
-    fn from_error(err: E) -> Self;
-}
-
-// Any type is convertable from itself
-#[stable(feature = "rust1", since = "1.0.0")]
-impl<E> FromError<E> for E {
-    fn from_error(err: E) -> E {
-        err
-    }
-}
@@ -368,6 +368,22 @@ extern "rust-intrinsic" {
     /// Perform a volatile store to the `dst` pointer.
     pub fn volatile_store<T>(dst: *mut T, val: T);
 
+    /// Hints to the compiler that `b` is expected to be `true`, so that the
+    /// code for that case is laid out as the fall-through path. Returns `b`.
+    #[cfg(not(stage0))]
+    pub fn likely(b: bool) -> bool;
+    /// Hints to the compiler that `b` is expected to be `false`, so that the
+    /// code for that case is laid out as the fall-through path. Returns `b`.
+    #[cfg(not(stage0))]
+    pub fn unlikely(b: bool) -> bool;
+
+    /// Perform a load from the `src` pointer, which need not be aligned.
+    #[cfg(not(stage0))]
+    pub fn unaligned_load<T>(src: *const T) -> T;
+    /// Perform a store to the `dst` pointer, which need not be aligned.
+    #[cfg(not(stage0))]
+    pub fn unaligned_store<T>(dst: *mut T, val: T);
+
     /// Returns the square root of an `f32`
     pub fn sqrtf32(x: f32) -> f32;
     /// Returns the square root of an `f64`
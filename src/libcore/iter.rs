@@ -121,6 +121,7 @@ pub trait FromIterator<A> {
 }
 
 /// Conversion into an `Iterator`
+#[lang = "into_iterator"]
 #[stable(feature = "rust1", since = "1.0.0")]
 pub trait IntoIterator {
     /// The type of the elements being iterated
@@ -2813,6 +2814,60 @@ impl<A: Int> DoubleEndedIterator for ::ops::Range<A> {
     }
 }
 
+#[unstable(feature = "inclusive_range",
+           reason = "recently added, follows the inclusive range syntax")]
+impl<A: Int> Iterator for ::ops::RangeInclusive<A> {
+    type Item = A;
+
+    #[inline]
+    fn next(&mut self) -> Option<A> {
+        if self.start < self.end {
+            let result = self.start;
+            self.start = self.start + Int::one();
+            Some(result)
+        } else if self.start == self.end {
+            // `end` may be the largest value of `A`, so make the range empty
+            // without stepping past it.
+            let result = self.start;
+            self.start = Int::one();
+            self.end = Int::zero();
+            Some(result)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.start > self.end {
+            (0, Some(0))
+        } else {
+            let length = (self.end - self.start).to_usize().and_then(|n| n.checked_add(1));
+            (length.unwrap_or(usize::MAX), length)
+        }
+    }
+}
+
+#[unstable(feature = "inclusive_range",
+           reason = "recently added, follows the inclusive range syntax")]
+impl<A: Int> DoubleEndedIterator for ::ops::RangeInclusive<A> {
+    #[inline]
+    fn next_back(&mut self) -> Option<A> {
+        if self.start < self.end {
+            let result = self.end;
+            self.end = self.end - Int::one();
+            Some(result)
+        } else if self.start == self.end {
+            let result = self.end;
+            self.start = Int::one();
+            self.end = Int::zero();
+            Some(result)
+        } else {
+            None
+        }
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<A: Int> Iterator for ::ops::RangeFrom<A> {
     type Item = A;
@@ -2862,6 +2917,7 @@ type IterateState<T, F> = (F, Option<T>, bool);
 /// An iterator that repeatedly applies a given function, starting
 /// from a given seed value.
 #[unstable(feature = "core")]
+#[cfg_attr(not(stage0), allow(private_in_public))]
 pub type Iterate<T, F> = Unfold<IterateState<T, F>, fn(&mut IterateState<T, F>) -> Option<T>>;
 
 /// Create a new iterator that produces an infinite sequence of
@@ -2770,10 +2770,11 @@ macro_rules! from_str_radix_int_impl {
                     Some(('-', src)) if is_signed_ty => {
                         // The number is negative
                         let mut result = 0;
-                        for c in src.chars() {
+                        for (i, c) in src.char_indices() {
                             let x = match c.to_digit(radix) {
                                 Some(x) => x,
-                                None => return Err(PIE { kind: InvalidDigit }),
+                                // Count the `-` too.
+                                None => return Err(PIE { kind: InvalidDigit(i + 1) }),
                             };
                             result = match result.checked_mul(radix as $T) {
                                 Some(result) => result,
@@ -2789,10 +2790,10 @@ macro_rules! from_str_radix_int_impl {
                     Some((_, _)) => {
                         // The number is signed
                         let mut result = 0;
-                        for c in src.chars() {
+                        for (i, c) in src.char_indices() {
                             let x = match c.to_digit(radix) {
                                 Some(x) => x,
-                                None => return Err(PIE { kind: InvalidDigit }),
+                                None => return Err(PIE { kind: InvalidDigit(i) }),
                             };
                             result = match result.checked_mul(radix as $T) {
                                 Some(result) => result,
@@ -2827,18 +2828,36 @@ from_str_radix_int_impl! { u64 }
 #[stable(feature = "rust1", since = "1.0.0")]
 pub struct ParseIntError { kind: IntErrorKind }
 
+/// The reasons parsing an integer can fail for.
 #[derive(Debug, Clone, PartialEq)]
-enum IntErrorKind {
+#[unstable(feature = "core", reason = "recently added")]
+pub enum IntErrorKind {
+    /// The string is empty, or only holds a sign.
     Empty,
-    InvalidDigit,
+    /// The string holds a character that isn't a digit of the radix, at the
+    /// given byte position.
+    InvalidDigit(usize),
+    /// The number is too large for the type.
     Overflow,
+    /// The number is too small for the type.
     Underflow,
 }
 
+impl ParseIntError {
+    /// Why parsing failed.
+    #[unstable(feature = "core", reason = "recently added")]
+    pub fn kind(&self) -> &IntErrorKind { &self.kind }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl fmt::Display for ParseIntError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.description().fmt(f)
+        match self.kind {
+            IntErrorKind::InvalidDigit(pos) => {
+                write!(f, "{} at byte {}", self.description(), pos)
+            }
+            _ => self.description().fmt(f)
+        }
     }
 }
 
@@ -2847,7 +2866,7 @@ impl Error for ParseIntError {
     fn description(&self) -> &str {
         match self.kind {
             IntErrorKind::Empty => "cannot parse integer from empty string",
-            IntErrorKind::InvalidDigit => "invalid digit found in string",
+            IntErrorKind::InvalidDigit(_) => "invalid digit found in string",
             IntErrorKind::Overflow => "number too large to fit in target type",
             IntErrorKind::Underflow => "number too small to fit in target type",
         }
@@ -2859,12 +2878,22 @@ impl Error for ParseIntError {
 #[stable(feature = "rust1", since = "1.0.0")]
 pub struct ParseFloatError { kind: FloatErrorKind }
 
+/// The reasons parsing a float can fail for.
 #[derive(Debug, Clone, PartialEq)]
-enum FloatErrorKind {
+#[unstable(feature = "core", reason = "recently added")]
+pub enum FloatErrorKind {
+    /// The string is empty.
     Empty,
+    /// The string isn't a valid float literal.
     Invalid,
 }
 
+impl ParseFloatError {
+    /// Why parsing failed.
+    #[unstable(feature = "core", reason = "recently added")]
+    pub fn kind(&self) -> &FloatErrorKind { &self.kind }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl fmt::Display for ParseFloatError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -1041,6 +1041,31 @@ impl<Idx: fmt::Debug> fmt::Debug for RangeTo<Idx> {
     }
 }
 
+/// A range which is bounded at both ends and includes its upper bound,
+/// written `start...end`.
+///
+/// The range is empty once `start > end`. Iterating over it leaves it as
+/// `1...0` once the last element was yielded, so that even ranges ending at
+/// the maximum value of their type never overflow.
+#[derive(Clone, PartialEq, Eq)]
+#[lang="range_inclusive"]
+#[unstable(feature = "inclusive_range",
+           reason = "recently added, follows the inclusive range syntax")]
+pub struct RangeInclusive<Idx> {
+    /// The lower bound of the range (inclusive).
+    pub start: Idx,
+    /// The upper bound of the range (inclusive).
+    pub end: Idx,
+}
+
+#[unstable(feature = "inclusive_range",
+           reason = "recently added, follows the inclusive range syntax")]
+impl<Idx: fmt::Debug> fmt::Debug for RangeInclusive<Idx> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{:?}...{:?}", self.start, self.end)
+    }
+}
+
 
 /// The `Deref` trait is used to specify the functionality of dereferencing
 /// operations like `*v`.
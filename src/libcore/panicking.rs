@@ -34,6 +34,7 @@ use fmt;
 
 #[cold] #[inline(never)] // this is the slow path, always
 #[lang="panic"]
+#[cfg_attr(not(stage0), no_instrument_function)]
 pub fn panic(expr_file_line: &(&'static str, &'static str, u32)) -> ! {
     // Use Arguments::new_v1 instead of format_args!("{}", expr) to potentially
     // reduce size overhead. The format_args! macro uses str's Display trait to
@@ -47,6 +48,7 @@ pub fn panic(expr_file_line: &(&'static str, &'static str, u32)) -> ! {
 
 #[cold] #[inline(never)]
 #[lang="panic_bounds_check"]
+#[cfg_attr(not(stage0), no_instrument_function)]
 fn panic_bounds_check(file_line: &(&'static str, u32),
                      index: usize, len: usize) -> ! {
     panic_fmt(format_args!("index out of bounds: the len is {} but the index is {}",
@@ -54,6 +56,7 @@ fn panic_bounds_check(file_line: &(&'static str, u32),
 }
 
 #[cold] #[inline(never)]
+#[cfg_attr(not(stage0), no_instrument_function)]
 pub fn panic_fmt(fmt: fmt::Arguments, file_line: &(&'static str, u32)) -> ! {
     #[allow(improper_ctypes)]
     extern {
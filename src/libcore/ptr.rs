@@ -247,6 +247,61 @@ pub unsafe fn write<T>(dst: *mut T, src: T) {
     intrinsics::move_val_init(&mut *dst, src)
 }
 
+/// Performs a volatile read of the value from `src` without moving it.
+///
+/// Volatile accesses are never elided, merged with each other or reordered
+/// with respect to other volatile accesses by the compiler, which makes them
+/// suitable for memory-mapped I/O.
+///
+/// # Safety
+///
+/// This is unsafe for the same reasons that `read` is unsafe.
+#[inline]
+#[unstable(feature = "core",
+           reason = "recently added, may be folded into a volatile cell type")]
+pub unsafe fn read_volatile<T>(src: *const T) -> T {
+    intrinsics::volatile_load(src)
+}
+
+/// Performs a volatile write of `src` to `dst` without reading or dropping
+/// the old value.
+///
+/// # Safety
+///
+/// This is unsafe for the same reasons that `write` is unsafe.
+#[inline]
+#[unstable(feature = "core",
+           reason = "recently added, may be folded into a volatile cell type")]
+pub unsafe fn write_volatile<T>(dst: *mut T, src: T) {
+    intrinsics::volatile_store(dst, src)
+}
+
+/// Reads the value from `src`, which need not be aligned for `T`, without
+/// moving it.
+///
+/// # Safety
+///
+/// This is unsafe for the same reasons that `read` is unsafe.
+#[cfg(not(stage0))]
+#[inline]
+#[unstable(feature = "core", reason = "recently added")]
+pub unsafe fn read_unaligned<T>(src: *const T) -> T {
+    intrinsics::unaligned_load(src)
+}
+
+/// Writes `src` to `dst`, which need not be aligned for `T`, without reading
+/// or dropping the old value.
+///
+/// # Safety
+///
+/// This is unsafe for the same reasons that `write` is unsafe.
+#[cfg(not(stage0))]
+#[inline]
+#[unstable(feature = "core", reason = "recently added")]
+pub unsafe fn write_unaligned<T>(dst: *mut T, src: T) {
+    intrinsics::unaligned_store(dst, src)
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 #[lang = "const_ptr"]
 impl<T: ?Sized> *const T {
@@ -393,39 +448,92 @@ impl<T: ?Sized> Clone for *mut T {
     }
 }
 
-// Equality for extern "C" fn pointers
-mod externfnpointers {
-    use mem;
-    use cmp::PartialEq;
+// Impls for function pointers. A function pointer is a plain address, so all
+// of these compare, hash and print that address.
+//
+// FIXME: these only cover pointer types without late-bound regions; a
+// pointer such as `for<'a> fn(&'a u8)` has to be coerced to an instance
+// like `fn(&'static u8)` first.
+mod fnptrs {
+    use cmp::{PartialEq, Eq, PartialOrd, Ord, Ordering};
+    use fmt;
+    use hash::{Hash, Hasher};
+    use option::Option;
+
+    macro_rules! fnptr_impls_safety_abi {
+        ($FnTy: ty, $($Arg: ident),*) => {
+            #[stable(feature = "fnptr_impls", since = "1.0.0")]
+            impl<Ret, $($Arg),*> PartialEq for $FnTy {
+                #[inline]
+                fn eq(&self, other: &$FnTy) -> bool {
+                    *self as usize == *other as usize
+                }
+            }
 
-    #[stable(feature = "rust1", since = "1.0.0")]
-    impl<_R> PartialEq for extern "C" fn() -> _R {
-        #[inline]
-        fn eq(&self, other: &extern "C" fn() -> _R) -> bool {
-            let self_: *const () = unsafe { mem::transmute(*self) };
-            let other_: *const () = unsafe { mem::transmute(*other) };
-            self_ == other_
-        }
-    }
-    macro_rules! fnptreq {
-        ($($p:ident),*) => {
-            #[stable(feature = "rust1", since = "1.0.0")]
-            impl<_R,$($p),*> PartialEq for extern "C" fn($($p),*) -> _R {
+            #[stable(feature = "fnptr_impls", since = "1.0.0")]
+            impl<Ret, $($Arg),*> Eq for $FnTy {}
+
+            #[stable(feature = "fnptr_impls", since = "1.0.0")]
+            impl<Ret, $($Arg),*> PartialOrd for $FnTy {
+                #[inline]
+                fn partial_cmp(&self, other: &$FnTy) -> Option<Ordering> {
+                    (*self as usize).partial_cmp(&(*other as usize))
+                }
+            }
+
+            #[stable(feature = "fnptr_impls", since = "1.0.0")]
+            impl<Ret, $($Arg),*> Ord for $FnTy {
                 #[inline]
-                fn eq(&self, other: &extern "C" fn($($p),*) -> _R) -> bool {
-                    let self_: *const () = unsafe { mem::transmute(*self) };
+                fn cmp(&self, other: &$FnTy) -> Ordering {
+                    (*self as usize).cmp(&(*other as usize))
+                }
+            }
 
-                    let other_: *const () = unsafe { mem::transmute(*other) };
-                    self_ == other_
+            #[stable(feature = "fnptr_impls", since = "1.0.0")]
+            impl<Ret, $($Arg),*> Hash for $FnTy {
+                fn hash<HH: Hasher>(&self, state: &mut HH) {
+                    state.write_usize(*self as usize)
                 }
             }
+
+            #[stable(feature = "fnptr_impls", since = "1.0.0")]
+            impl<Ret, $($Arg),*> fmt::Pointer for $FnTy {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    fmt::Pointer::fmt(&(*self as *const ()), f)
+                }
+            }
+
+            #[stable(feature = "fnptr_impls", since = "1.0.0")]
+            impl<Ret, $($Arg),*> fmt::Debug for $FnTy {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    fmt::Pointer::fmt(&(*self as *const ()), f)
+                }
+            }
+        }
+    }
+
+    macro_rules! fnptr_impls_args {
+        ($($Arg: ident),*) => {
+            fnptr_impls_safety_abi! { extern "Rust" fn($($Arg),*) -> Ret, $($Arg),* }
+            fnptr_impls_safety_abi! { extern "C" fn($($Arg),*) -> Ret, $($Arg),* }
+            fnptr_impls_safety_abi! { unsafe extern "Rust" fn($($Arg),*) -> Ret, $($Arg),* }
+            fnptr_impls_safety_abi! { unsafe extern "C" fn($($Arg),*) -> Ret, $($Arg),* }
         }
     }
-    fnptreq! { A }
-    fnptreq! { A,B }
-    fnptreq! { A,B,C }
-    fnptreq! { A,B,C,D }
-    fnptreq! { A,B,C,D,E }
+
+    fnptr_impls_args! { }
+    fnptr_impls_args! { A }
+    fnptr_impls_args! { A, B }
+    fnptr_impls_args! { A, B, C }
+    fnptr_impls_args! { A, B, C, D }
+    fnptr_impls_args! { A, B, C, D, E }
+    fnptr_impls_args! { A, B, C, D, E, F }
+    fnptr_impls_args! { A, B, C, D, E, F, G }
+    fnptr_impls_args! { A, B, C, D, E, F, G, H }
+    fnptr_impls_args! { A, B, C, D, E, F, G, H, I }
+    fnptr_impls_args! { A, B, C, D, E, F, G, H, I, J }
+    fnptr_impls_args! { A, B, C, D, E, F, G, H, I, J, K }
+    fnptr_impls_args! { A, B, C, D, E, F, G, H, I, J, K, L }
 }
 
 // Comparison for pointers
@@ -57,6 +57,38 @@ use raw::Repr;
 use raw::Slice as RawSlice;
 
 
+#[inline(always)]
+fn size_from_ptr<T>(_: *const T) -> usize {
+    mem::size_of::<T>()
+}
+
+// Steps an iterator pointer by `$by` elements. Zero-sized elements have no
+// address of their own, so for those the pointer is just used as a counter.
+macro_rules! slice_offset {
+    ($ptr:expr, $by:expr) => {{
+        let ptr = $ptr;
+        if size_from_ptr(ptr) == 0 {
+            transmute(ptr as isize + $by)
+        } else {
+            ptr.offset($by)
+        }
+    }};
+}
+
+// Turns an iterator pointer into a reference to its element, using a
+// non-null dangling address for zero-sized elements.
+macro_rules! slice_ref {
+    ($ptr:expr) => {{
+        let ptr = $ptr;
+        if size_from_ptr(ptr) == 0 {
+            &mut *(1 as *mut _)
+        } else {
+            transmute(ptr)
+        }
+    }};
+}
+
+
 //
 // Extension traits
 //
@@ -104,6 +136,8 @@ pub trait SliceExt {
     fn swap(&mut self, a: usize, b: usize);
     fn split_at_mut<'a>(&'a mut self, mid: usize) -> (&'a mut [Self::Item], &'a mut [Self::Item]);
     fn reverse(&mut self);
+    fn sort_unstable_by<F>(&mut self, compare: F)
+                           where F: FnMut(&Self::Item, &Self::Item) -> Ordering;
     unsafe fn get_unchecked_mut<'a>(&'a mut self, index: usize) -> &'a mut Self::Item;
     fn as_mut_ptr(&mut self) -> *mut Self::Item;
 
@@ -138,15 +172,9 @@ impl<T> SliceExt for [T] {
         unsafe {
             let p = self.as_ptr();
             assume(!p.is_null());
-            if mem::size_of::<T>() == 0 {
-                Iter {ptr: p,
-                      end: (p as usize + self.len()) as *const T,
-                      _marker: marker::PhantomData}
-            } else {
-                Iter {ptr: p,
-                      end: p.offset(self.len() as isize),
-                      _marker: marker::PhantomData}
-            }
+            Iter {ptr: p,
+                  end: slice_offset!(p, self.len() as isize),
+                  _marker: marker::PhantomData}
         }
     }
 
@@ -290,15 +318,9 @@ impl<T> SliceExt for [T] {
         unsafe {
             let p = self.as_mut_ptr();
             assume(!p.is_null());
-            if mem::size_of::<T>() == 0 {
-                IterMut {ptr: p,
-                         end: (p as usize + self.len()) as *mut T,
-                         _marker: marker::PhantomData}
-            } else {
-                IterMut {ptr: p,
-                         end: p.offset(self.len() as isize),
-                         _marker: marker::PhantomData}
-            }
+            IterMut {ptr: p,
+                     end: slice_offset!(p, self.len() as isize),
+                     _marker: marker::PhantomData}
         }
     }
 
@@ -387,6 +409,12 @@ impl<T> SliceExt for [T] {
         }
     }
 
+    #[unstable(feature = "core")]
+    fn sort_unstable_by<F>(&mut self, mut compare: F)
+                           where F: FnMut(&T, &T) -> Ordering {
+        quicksort(self, &mut |a: &T, b: &T| compare(a, b) == Less)
+    }
+
     #[inline]
     unsafe fn get_unchecked_mut(&mut self, index: usize) -> &mut T {
         transmute((self.repr().data as *mut T).offset(index as isize))
@@ -503,20 +531,39 @@ impl<T> SliceExt for [T] {
     }
 }
 
+#[cold] #[inline(never)]
+fn index_len_fail(index: usize, len: usize) -> ! {
+    panic!("index out of bounds: the len is {} but the index is {}", len, index);
+}
+
+#[cold] #[inline(never)]
+fn slice_index_len_fail(index: usize, len: usize) -> ! {
+    panic!("range end index {} out of range for slice of length {}", index, len);
+}
+
+#[cold] #[inline(never)]
+fn slice_index_order_fail(index: usize, end: usize) -> ! {
+    panic!("slice index starts at {} but ends at {}", index, end);
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T> ops::Index<usize> for [T] {
     type Output = T;
 
     #[cfg(stage0)]
     fn index(&self, &index: &usize) -> &T {
-        assert!(index < self.len());
+        if index >= self.len() {
+            index_len_fail(index, self.len());
+        }
 
         unsafe { mem::transmute(self.repr().data.offset(index as isize)) }
     }
 
     #[cfg(not(stage0))]
     fn index(&self, index: usize) -> &T {
-        assert!(index < self.len());
+        if index >= self.len() {
+            index_len_fail(index, self.len());
+        }
 
         unsafe { mem::transmute(self.repr().data.offset(index as isize)) }
     }
@@ -527,7 +574,9 @@ impl<T> ops::IndexMut<usize> for [T] {
     #[cfg(stage0)]
     #[inline]
     fn index_mut(&mut self, &index: &usize) -> &mut T {
-        assert!(index < self.len());
+        if index >= self.len() {
+            index_len_fail(index, self.len());
+        }
 
         unsafe { mem::transmute(self.repr().data.offset(index as isize)) }
     }
@@ -535,7 +584,9 @@ impl<T> ops::IndexMut<usize> for [T] {
     #[cfg(not(stage0))]
     #[inline]
     fn index_mut(&mut self, index: usize) -> &mut T {
-        assert!(index < self.len());
+        if index >= self.len() {
+            index_len_fail(index, self.len());
+        }
 
         unsafe { mem::transmute(self.repr().data.offset(index as isize)) }
     }
@@ -548,8 +599,11 @@ impl<T> ops::Index<ops::Range<usize>> for [T] {
     #[cfg(stage0)]
     #[inline]
     fn index(&self, index: &ops::Range<usize>) -> &[T] {
-        assert!(index.start <= index.end);
-        assert!(index.end <= self.len());
+        if index.start > index.end {
+            slice_index_order_fail(index.start, index.end);
+        } else if index.end > self.len() {
+            slice_index_len_fail(index.end, self.len());
+        }
         unsafe {
             from_raw_parts (
                 self.as_ptr().offset(index.start as isize),
@@ -561,8 +615,11 @@ impl<T> ops::Index<ops::Range<usize>> for [T] {
     #[cfg(not(stage0))]
     #[inline]
     fn index(&self, index: ops::Range<usize>) -> &[T] {
-        assert!(index.start <= index.end);
-        assert!(index.end <= self.len());
+        if index.start > index.end {
+            slice_index_order_fail(index.start, index.end);
+        } else if index.end > self.len() {
+            slice_index_len_fail(index.end, self.len());
+        }
         unsafe {
             from_raw_parts (
                 self.as_ptr().offset(index.start as isize),
@@ -625,8 +682,11 @@ impl<T> ops::IndexMut<ops::Range<usize>> for [T] {
     #[cfg(stage0)]
     #[inline]
     fn index_mut(&mut self, index: &ops::Range<usize>) -> &mut [T] {
-        assert!(index.start <= index.end);
-        assert!(index.end <= self.len());
+        if index.start > index.end {
+            slice_index_order_fail(index.start, index.end);
+        } else if index.end > self.len() {
+            slice_index_len_fail(index.end, self.len());
+        }
         unsafe {
             from_raw_parts_mut(
                 self.as_mut_ptr().offset(index.start as isize),
@@ -638,8 +698,11 @@ impl<T> ops::IndexMut<ops::Range<usize>> for [T] {
     #[cfg(not(stage0))]
     #[inline]
     fn index_mut(&mut self, index: ops::Range<usize>) -> &mut [T] {
-        assert!(index.start <= index.end);
-        assert!(index.end <= self.len());
+        if index.start > index.end {
+            slice_index_order_fail(index.start, index.end);
+        } else if index.end > self.len() {
+            slice_index_len_fail(index.end, self.len());
+        }
         unsafe {
             from_raw_parts_mut(
                 self.as_mut_ptr().offset(index.start as isize),
@@ -767,7 +830,10 @@ macro_rules! iterator {
         impl<'a, T> Iterator for $name<'a, T> {
             type Item = $elem;
 
-            #[inline]
+            // This is `inline(always)` so that loops over slices are reduced
+            // to a pointer comparison and increment before the optimizer has
+            // to reason about the `Option` that is returned.
+            #[inline(always)]
             fn next(&mut self) -> Option<$elem> {
                 // could be implemented with slices, but this avoids bounds checks
                 unsafe {
@@ -776,20 +842,9 @@ macro_rules! iterator {
                     if self.ptr == self.end {
                         None
                     } else {
-                        if mem::size_of::<T>() == 0 {
-                            // purposefully don't use 'ptr.offset' because for
-                            // vectors with 0-size elements this would return the
-                            // same pointer.
-                            self.ptr = transmute(self.ptr as usize + 1);
-
-                            // Use a non-null pointer value
-                            Some(&mut *(1 as *mut _))
-                        } else {
-                            let old = self.ptr;
-                            self.ptr = self.ptr.offset(1);
-
-                            Some(transmute(old))
-                        }
+                        let old = self.ptr;
+                        self.ptr = slice_offset!(self.ptr, 1);
+                        Some(slice_ref!(old))
                     }
                 }
             }
@@ -805,7 +860,7 @@ macro_rules! iterator {
 
         #[stable(feature = "rust1", since = "1.0.0")]
         impl<'a, T> DoubleEndedIterator for $name<'a, T> {
-            #[inline]
+            #[inline(always)]
             fn next_back(&mut self) -> Option<$elem> {
                 // could be implemented with slices, but this avoids bounds checks
                 unsafe {
@@ -814,17 +869,8 @@ macro_rules! iterator {
                     if self.end == self.ptr {
                         None
                     } else {
-                        if mem::size_of::<T>() == 0 {
-                            // See above for why 'ptr.offset' isn't used
-                            self.end = transmute(self.end as usize - 1);
-
-                            // Use a non-null pointer value
-                            Some(&mut *(1 as *mut _))
-                        } else {
-                            self.end = self.end.offset(-1);
-
-                            Some(transmute(self.end))
-                        }
+                        self.end = slice_offset!(self.end, -1);
+                        Some(slice_ref!(self.end))
                     }
                 }
             }
@@ -1729,6 +1775,314 @@ pub unsafe fn from_raw_mut_buf<'a, T>(p: &'a *mut T, len: usize) -> &'a mut [T]
     transmute(RawSlice { data: *p, len: len })
 }
 
+//
+// Sorting
+//
+
+// The unstable sort below is an introsort: a quicksort that falls back to
+// heapsort once too many partitions have come out badly unbalanced, so it is
+// `O(n log n)` in the worst case. Like pattern-defeating quicksort it also
+// guards against inputs that are common in practice: partitions that needed
+// no swaps are handed to a bounded insertion sort, as the slice may already
+// have been sorted; an unbalanced partition shuffles a few elements to break
+// up adversarial orderings; and runs of elements equal to an earlier pivot
+// are split off in a single pass.
+//
+// Elements are only ever moved by swapping them, so if a comparison panics
+// the slice is still a permutation of what it was.
+
+/// Sorts slices of at most this length with insertion sort.
+const MAX_INSERTION: usize = 20;
+/// Pivots are chosen as a median of medians for slices at least this long.
+const SHORTEST_MEDIAN_OF_MEDIANS: usize = 50;
+
+fn insertion_sort<T, F>(v: &mut [T], is_less: &mut F) where F: FnMut(&T, &T) -> bool {
+    for i in 1..v.len() {
+        shift_tail(&mut v[..i + 1], is_less);
+    }
+}
+
+/// Moves the last element left until it is no longer less than its
+/// neighbour.
+fn shift_tail<T, F>(v: &mut [T], is_less: &mut F) where F: FnMut(&T, &T) -> bool {
+    let mut i = v.len();
+    while i >= 2 && (*is_less)(&v[i - 1], &v[i - 2]) {
+        v.swap(i - 1, i - 2);
+        i -= 1;
+    }
+}
+
+/// Moves the first element right until no neighbour is less than it.
+fn shift_head<T, F>(v: &mut [T], is_less: &mut F) where F: FnMut(&T, &T) -> bool {
+    let mut i = 0;
+    while i + 1 < v.len() && (*is_less)(&v[i + 1], &v[i]) {
+        v.swap(i, i + 1);
+        i += 1;
+    }
+}
+
+/// Sorts `v` if it is nearly sorted, by fixing up a bounded number of
+/// out-of-order pairs. Returns whether the slice ended up sorted.
+fn partial_insertion_sort<T, F>(v: &mut [T], is_less: &mut F) -> bool
+                                where F: FnMut(&T, &T) -> bool {
+    const MAX_STEPS: usize = 5;
+    // Shifting costs a lot on short slices, which are cheap to partition.
+    const SHORTEST_SHIFTING: usize = 50;
+
+    let len = v.len();
+    let mut i = 1;
+    for _ in 0..MAX_STEPS {
+        while i < len && !(*is_less)(&v[i], &v[i - 1]) {
+            i += 1;
+        }
+        if i == len {
+            return true;
+        }
+        if len < SHORTEST_SHIFTING {
+            return false;
+        }
+        v.swap(i - 1, i);
+        shift_tail(&mut v[..i], is_less);
+        shift_head(&mut v[i..], is_less);
+    }
+    false
+}
+
+fn heapsort<T, F>(v: &mut [T], is_less: &mut F) where F: FnMut(&T, &T) -> bool {
+    // Restores the heap property below `node`, in a heap of `v.len()` elements.
+    fn sift_down<T, F>(v: &mut [T], mut node: usize, is_less: &mut F)
+                       where F: FnMut(&T, &T) -> bool {
+        loop {
+            let left = 2 * node + 1;
+            let right = left + 1;
+            let greater = if right < v.len() && (*is_less)(&v[left], &v[right]) {
+                right
+            } else {
+                left
+            };
+            if greater >= v.len() || !(*is_less)(&v[node], &v[greater]) {
+                break;
+            }
+            v.swap(node, greater);
+            node = greater;
+        }
+    }
+
+    for i in (0..v.len() / 2).rev() {
+        sift_down(v, i, is_less);
+    }
+    for end in (1..v.len()).rev() {
+        v.swap(0, end);
+        sift_down(&mut v[..end], 0, is_less);
+    }
+}
+
+/// Partitions `v` around `v[pivot]`, leaving the pivot at the index that is
+/// returned, everything less than it to its left and everything else to its
+/// right. Also returns whether `v` was already partitioned.
+fn partition<T, F>(v: &mut [T], pivot: usize, is_less: &mut F) -> (usize, bool)
+                   where F: FnMut(&T, &T) -> bool {
+    v.swap(0, pivot);
+    let mid = {
+        let (pivot, rest) = v.split_at_mut(1);
+        let pivot = &pivot[0];
+
+        let mut l = 0;
+        let mut r = rest.len();
+        while l < r && (*is_less)(&rest[l], pivot) {
+            l += 1;
+        }
+        while l < r && !(*is_less)(&rest[r - 1], pivot) {
+            r -= 1;
+        }
+        let was_partitioned = l >= r;
+
+        loop {
+            while l < r && (*is_less)(&rest[l], pivot) {
+                l += 1;
+            }
+            while l < r && !(*is_less)(&rest[r - 1], pivot) {
+                r -= 1;
+            }
+            if l >= r {
+                break;
+            }
+            r -= 1;
+            rest.swap(l, r);
+            l += 1;
+        }
+        (l, was_partitioned)
+    };
+    v.swap(0, mid.0);
+    mid
+}
+
+/// Moves `v[pivot]` and all the elements equal to it to the front of `v`,
+/// presuming nothing in `v` is less than it. Returns how many there are.
+fn partition_equal<T, F>(v: &mut [T], pivot: usize, is_less: &mut F) -> usize
+                         where F: FnMut(&T, &T) -> bool {
+    v.swap(0, pivot);
+    let (pivot, rest) = v.split_at_mut(1);
+    let pivot = &pivot[0];
+
+    let mut l = 0;
+    let mut r = rest.len();
+    loop {
+        while l < r && !(*is_less)(pivot, &rest[l]) {
+            l += 1;
+        }
+        while l < r && (*is_less)(pivot, &rest[r - 1]) {
+            r -= 1;
+        }
+        if l >= r {
+            break;
+        }
+        r -= 1;
+        rest.swap(l, r);
+        l += 1;
+    }
+    l + 1
+}
+
+/// Swaps a few elements around the middle of `v` with pseudo-random others.
+fn break_patterns<T>(v: &mut [T]) {
+    let len = v.len();
+    if len >= 8 {
+        // A fixed seed keeps sorting deterministic.
+        let mut random = len as u32;
+        let pos = len / 4 * 2;
+        for i in 0..3 {
+            random ^= random << 13;
+            random ^= random >> 17;
+            random ^= random << 5;
+            v.swap(pos - 1 + i, random as usize % len);
+        }
+    }
+}
+
+/// Picks a pivot, returning its index and whether `v` looks sorted already.
+/// A slice that looked reversed gets reversed first.
+fn choose_pivot<T, F>(v: &mut [T], is_less: &mut F) -> (usize, bool)
+                      where F: FnMut(&T, &T) -> bool {
+    const MAX_SWAPS: usize = 4 * 3;
+
+    let len = v.len();
+    let mut a = len / 4 * 1;
+    let mut b = len / 4 * 2;
+    let mut c = len / 4 * 3;
+    let mut swaps = 0;
+
+    if len >= 8 {
+        let v = &*v;
+        let mut sort2 = |a: &mut usize, b: &mut usize| {
+            if (*is_less)(&v[*b], &v[*a]) {
+                mem::swap(a, b);
+                swaps += 1;
+            }
+        };
+        let mut sort3 = |a: &mut usize, b: &mut usize, c: &mut usize| {
+            sort2(&mut *a, &mut *b);
+            sort2(&mut *b, &mut *c);
+            sort2(&mut *a, &mut *b);
+        };
+
+        if len >= SHORTEST_MEDIAN_OF_MEDIANS {
+            // Replaces `a` by the median of `a - 1`, `a` and `a + 1`.
+            let mut sort_adjacent = |a: &mut usize| {
+                let tmp = *a;
+                sort3(&mut (tmp - 1), a, &mut (tmp + 1));
+            };
+            sort_adjacent(&mut a);
+            sort_adjacent(&mut b);
+            sort_adjacent(&mut c);
+        }
+        sort3(&mut a, &mut b, &mut c);
+    }
+
+    if swaps < MAX_SWAPS {
+        (b, swaps == 0)
+    } else {
+        // Every comparison swapped: the slice is probably descending.
+        v.reverse();
+        (len - 1 - b, true)
+    }
+}
+
+fn recurse<'a, T, F>(mut v: &'a mut [T], is_less: &mut F, mut pred: Option<&'a T>,
+                     mut limit: usize)
+                     where F: FnMut(&T, &T) -> bool {
+    let mut was_balanced = true;
+    let mut was_partitioned = true;
+
+    loop {
+        let len = v.len();
+        if len <= MAX_INSERTION {
+            insertion_sort(v, is_less);
+            return;
+        }
+
+        // Too many bad pivots: fall back to the guaranteed `O(n log n)`.
+        if limit == 0 {
+            heapsort(v, is_less);
+            return;
+        }
+
+        if !was_balanced {
+            break_patterns(v);
+            limit -= 1;
+        }
+
+        let (pivot, likely_sorted) = choose_pivot(v, is_less);
+
+        if was_balanced && was_partitioned && likely_sorted {
+            if partial_insertion_sort(v, is_less) {
+                return;
+            }
+        }
+
+        // If the pivot equals the predecessor of this slice, which is the
+        // pivot it was split off by, nothing in `v` is less than it: take
+        // all the elements equal to it out in one go.
+        if let Some(p) = pred {
+            if !(*is_less)(p, &v[pivot]) {
+                let mid = partition_equal(v, pivot, is_less);
+                let tmp = v;
+                v = &mut tmp[mid..];
+                continue;
+            }
+        }
+
+        let (mid, partitioned) = partition(v, pivot, is_less);
+        was_balanced = cmp::min(mid, len - mid) >= len / 8;
+        was_partitioned = partitioned;
+
+        // Recurse into the shorter side only, to bound the stack depth.
+        let tmp = v;
+        let (left, right) = tmp.split_at_mut(mid);
+        let (pivot, right) = right.split_at_mut(1);
+        let pivot = &pivot[0];
+        if left.len() < right.len() {
+            recurse(left, is_less, pred, limit);
+            v = right;
+            pred = Some(pivot);
+        } else {
+            recurse(right, is_less, Some(pivot), limit);
+            v = left;
+        }
+    }
+}
+
+fn quicksort<T, F>(v: &mut [T], is_less: &mut F) where F: FnMut(&T, &T) -> bool {
+    // Allow about `log2(len)` unbalanced partitions before using heapsort.
+    let mut limit = 0;
+    let mut len = v.len();
+    while len > 0 {
+        limit += 1;
+        len >>= 1;
+    }
+    recurse(v, is_less, None, limit);
+}
+
 //
 // Submodules
 //
@@ -1538,11 +1538,28 @@ pub trait StrExt {
     fn parse<T: FromStr>(&self) -> Result<T, T::Err>;
 }
 
-#[inline(never)]
+#[cold] #[inline(never)]
 fn slice_error_fail(s: &str, begin: usize, end: usize) -> ! {
-    assert!(begin <= end);
-    panic!("index {} and/or {} in `{}` do not lie on character boundary",
-          begin, end, s);
+    if begin > end {
+        panic!("slice index starts at byte {} but ends at byte {} in `{}`",
+               begin, end, s);
+    }
+    let index = if !s.is_char_boundary(begin) { begin } else { end };
+    if index > s.len() {
+        panic!("byte index {} is out of bounds of `{}` ({} bytes long)",
+               index, s, s.len());
+    }
+    let mut before = index;
+    while !s.is_char_boundary(before) {
+        before -= 1;
+    }
+    let mut after = index;
+    while !s.is_char_boundary(after) {
+        after += 1;
+    }
+    panic!("byte index {} is not a char boundary of `{}`; \
+            the nearest boundaries are {} and {}",
+           index, s, before, after);
 }
 
 impl StrExt for str {
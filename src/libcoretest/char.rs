@@ -236,3 +236,15 @@ fn test_width() {
     assert_eq!('\u{300}'.width(false),Some(0));
     assert_eq!('\u{300}'.width(true),Some(0));
 }
+
+#[test]
+fn test_from_str() {
+    use core::error::Error;
+
+    assert_eq!("a".parse::<char>().ok(), Some('a'));
+    assert_eq!("中".parse::<char>().ok(), Some('中'));
+    assert_eq!("".parse::<char>().err().unwrap().description(),
+               "cannot parse char from empty string");
+    assert_eq!("ab".parse::<char>().err().unwrap().description(),
+               "too many characters in string");
+}
@@ -57,6 +57,7 @@ mod test {
     use core::option::Option::{Some, None};
     use core::num::Float;
     use core::num::from_str_radix;
+    use core::num::{IntErrorKind, FloatErrorKind};
 
     #[test]
     fn from_str_issue7588() {
@@ -125,4 +126,35 @@ mod test {
     fn test_int_from_minus_sign() {
         assert_eq!("-".parse::<i32>().ok(), None);
     }
+
+    #[test]
+    fn test_int_error_kinds() {
+        fn kind<T>(s: &str) -> IntErrorKind
+            where T: ::std::str::FromStr<Err=::core::num::ParseIntError>
+        {
+            s.parse::<T>().err().unwrap().kind().clone()
+        }
+        assert_eq!(kind::<i32>(""), IntErrorKind::Empty);
+        assert_eq!(kind::<i32>("-"), IntErrorKind::Empty);
+        assert_eq!(kind::<i32>("12x4"), IntErrorKind::InvalidDigit(2));
+        assert_eq!(kind::<i32>("-12x4"), IntErrorKind::InvalidDigit(3));
+        assert_eq!(kind::<u32>("-1"), IntErrorKind::InvalidDigit(0));
+        assert_eq!(kind::<u8>("256"), IntErrorKind::Overflow);
+        assert_eq!(kind::<i8>("128"), IntErrorKind::Overflow);
+        assert_eq!(kind::<i8>("-129"), IntErrorKind::Underflow);
+    }
+
+    #[test]
+    fn test_int_error_display() {
+        let err = "12x4".parse::<i32>().err().unwrap();
+        assert_eq!(err.to_string(), "invalid digit found in string at byte 2");
+        let err = "".parse::<i32>().err().unwrap();
+        assert_eq!(err.to_string(), "cannot parse integer from empty string");
+    }
+
+    #[test]
+    fn test_float_error_kinds() {
+        assert_eq!("".parse::<f64>().err().unwrap().kind(), &FloatErrorKind::Empty);
+        assert_eq!("1.2.3".parse::<f64>().err().unwrap().kind(), &FloatErrorKind::Invalid);
+    }
 }
@@ -190,8 +190,9 @@ enum Optval {
 pub struct Matches {
     /// Options that matched
     opts: Vec<Opt>,
-    /// Values of the Options that matched
-    vals: Vec<Vec<Optval>>,
+    /// Values of the Options that matched, with the position of the argument
+    /// they were given in
+    vals: Vec<Vec<(uint, Optval)>>,
     /// Free string fragments
     pub free: Vec<String>,
 }
@@ -291,7 +292,7 @@ impl OptGroup {
 impl Matches {
     fn opt_vals(&self, nm: &str) -> Vec<Optval> {
         match find_opt(&self.opts[..], Name::from_str(nm)) {
-            Some(id) => self.vals[id].clone(),
+            Some(id) => self.vals[id].iter().map(|&(_, ref v)| v.clone()).collect(),
             None => panic!("No option '{}' defined", nm)
         }
     }
@@ -353,6 +354,20 @@ impl Matches {
         acc
     }
 
+    /// Returns a vector of the arguments provided to all matches of the given
+    /// option, together with the positions they were given at.
+    ///
+    /// Used when the relative order of several options matters.
+    pub fn opt_strs_pos(&self, nm: &str) -> Vec<(uint, String)> {
+        match find_opt(&self.opts[..], Name::from_str(nm)) {
+            Some(id) => self.vals[id].iter().filter_map(|&(pos, ref v)| match *v {
+                Val(ref s) => Some((pos, s.clone())),
+                Given => None,
+            }).collect(),
+            None => panic!("No option '{}' defined", nm)
+        }
+    }
+
     /// Returns the string argument supplied to a matching option or `None`.
     pub fn opt_str(&self, nm: &str) -> Option<String> {
         let vals = self.opt_vals(nm);
@@ -587,13 +602,14 @@ pub fn getopts(args: &[String], optgrps: &[OptGroup]) -> Result {
     let opts: Vec<Opt> = optgrps.iter().map(|x| x.long_to_short()).collect();
     let n_opts = opts.len();
 
-    fn f(_x: uint) -> Vec<Optval> { return Vec::new(); }
+    fn f(_x: uint) -> Vec<(uint, Optval)> { return Vec::new(); }
 
     let mut vals: Vec<_> = (0..n_opts).map(f).collect();
     let mut free: Vec<String> = Vec::new();
     let l = args.len();
     let mut i = 0;
     while i < l {
+        let pos = i;
         let cur = args[i].clone();
         let curlen = cur.len();
         if !is_arg(&cur[..]) {
@@ -663,33 +679,33 @@ pub fn getopts(args: &[String], optgrps: &[OptGroup]) -> Result {
                         return Err(UnexpectedArgument(nm.to_string()));
                     }
                     let v = &mut vals[optid];
-                    v.push(Given);
+                    v.push((pos, Given));
                   }
                   Maybe => {
                     if !i_arg.is_none() {
                         let v = &mut vals[optid];
-                        v.push(Val((i_arg.clone())
-                            .unwrap()));
+                        v.push((pos, Val((i_arg.clone())
+                            .unwrap())));
                     } else if name_pos < names.len() || i + 1 == l ||
                             is_arg(&args[i + 1][..]) {
                         let v = &mut vals[optid];
-                        v.push(Given);
+                        v.push((pos, Given));
                     } else {
                         i += 1;
                         let v = &mut vals[optid];
-                        v.push(Val(args[i].clone()));
+                        v.push((pos, Val(args[i].clone())));
                     }
                   }
                   Yes => {
                     if !i_arg.is_none() {
                         let v = &mut vals[optid];
-                        v.push(Val(i_arg.clone().unwrap()));
+                        v.push((pos, Val(i_arg.clone().unwrap())));
                     } else if i + 1 == l {
                         return Err(ArgumentMissing(nm.to_string()));
                     } else {
                         i += 1;
                         let v = &mut vals[optid];
-                        v.push(Val(args[i].clone()));
+                        v.push((pos, Val(args[i].clone())));
                     }
                   }
                 }
@@ -1343,6 +1359,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_optmulti_positions() {
+        let args = vec!("-a".to_string(), "1".to_string(), "-b2".to_string(),
+                        "--alpha=3".to_string());
+        let opts = vec!(optmulti("a", "alpha", "first", "A"),
+                        optmulti("b", "beta", "second", "B"));
+        match getopts(&args, &opts) {
+          Ok(ref m) => {
+              assert_eq!(m.opt_strs_pos("a"),
+                         vec!((0, "1".to_string()), (3, "3".to_string())));
+              assert_eq!(m.opt_strs_pos("beta"), vec!((2, "2".to_string())));
+          }
+          _ => panic!()
+        }
+    }
+
     #[test]
     fn test_unrecognized_option() {
         let long_args = vec!("--untest".to_string());
@@ -38,7 +38,8 @@
 
 #![allow(deprecated)]
 
-#[macro_use]
+// Test builds get these macros from std instead.
+#[cfg_attr(not(test), macro_use)]
 extern crate core;
 
 #[cfg(test)] #[macro_use] extern crate std;
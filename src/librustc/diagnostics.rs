@@ -54,7 +54,6 @@ register_long_diagnostics! {
 register_diagnostics! {
     E0002,
     E0007,
-    E0008,
     E0009,
     E0010,
     E0011,
@@ -71,8 +70,10 @@ register_diagnostics! {
     E0109,
     E0110,
     E0133,
-    E0134,
-    E0135,
+    // E0134 and E0135 ("modification of string types is not allowed") are
+    // no longer emitted: indexing a string now always yields an immutable
+    // place, so writes and mutable borrows through it are rejected by
+    // borrowck as "cannot assign to/borrow immutable indexed content".
     E0136,
     E0137,
     E0138,
@@ -128,7 +129,17 @@ register_diagnostics! {
     E0313, // lifetime of borrowed pointer outlives lifetime of captured variable
     E0314, // closure outlives stack frame
     E0315, // cannot invoke closure outside of its lifetime
-    E0316 // nested quantification of lifetimes
+    E0316, // nested quantification of lifetimes
+    E0331, // atomic intrinsic used with a non-integer, non-pointer type
+    E0332, // recursive `#[inline(always)]` function
+    E0333, // cannot move out of a by-move binding in a pattern guard
+    E0334, // higher-ranked requirement only met for a specific lifetime
+    E0336, // malformed macro work limit attribute
+    E0337, // negative array length or repeat count
+    E0338, // array length or repeat count does not fit in the target's `usize`
+    E0339, // malformed function expression limit attribute
+    E0340, // unknown Windows subsystem
+    E0341  // `windows_subsystem` on a crate that is not only an executable
 }
 
 __build_diagnostic_array! { DIAGNOSTICS }
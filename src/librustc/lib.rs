@@ -36,7 +36,6 @@
 #![feature(quote)]
 #![feature(rustc_diagnostic_macros)]
 #![feature(rustc_private)]
-#![feature(unsafe_destructor)]
 #![feature(staged_api)]
 #![feature(std_misc)]
 #![feature(io)]
@@ -91,11 +90,13 @@ pub mod middle {
     pub mod astencode;
     pub mod cfg;
     pub mod check_const;
+    pub mod check_inline_recursion;
     pub mod check_static_recursion;
     pub mod check_loop;
     pub mod check_match;
     pub mod check_rvalues;
     pub mod const_eval;
+    pub mod crate_stats;
     pub mod dataflow;
     pub mod dead;
     pub mod def;
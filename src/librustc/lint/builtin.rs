@@ -14,7 +14,7 @@
 //! compiler code, rather than using their own custom pass. Those
 //! lints are all available in `rustc_lint::builtin`.
 
-use lint::{LintPass, LintArray};
+use lint::{LintPass, LateLintPass, LintArray};
 
 declare_lint! {
     pub UNUSED_IMPORTS,
@@ -100,6 +100,30 @@ declare_lint! {
     "detects transmutes of fat pointers"
 }
 
+declare_lint! {
+    pub OVERLAPPING_PATTERNS,
+    Allow,
+    "detects range patterns that overlap with a previous pattern of the same match"
+}
+
+declare_lint! {
+    pub ILL_FORMED_SIGNATURES,
+    Warn,
+    "detects method signatures using types that do not meet their own bounds"
+}
+
+declare_lint! {
+    pub SHADOWED_LIFETIMES,
+    Warn,
+    "detects lifetime parameters that shadow a lifetime already in scope"
+}
+
+declare_lint! {
+    pub PRIVATE_IN_PUBLIC,
+    Warn,
+    "detects private types and traits in the signatures of exported items"
+}
+
 /// Does nothing as a lint pass, but registers some `Lint`s
 /// which are used by other parts of the compiler.
 #[derive(Copy)]
@@ -121,7 +145,13 @@ impl LintPass for HardwiredLints {
             STABLE_FEATURES,
             UNKNOWN_CRATE_TYPES,
             VARIANT_SIZE_DIFFERENCES,
-            FAT_PTR_TRANSMUTES
+            FAT_PTR_TRANSMUTES,
+            OVERLAPPING_PATTERNS,
+            ILL_FORMED_SIGNATURES,
+            SHADOWED_LIFETIMES,
+            PRIVATE_IN_PUBLIC
         )
     }
 }
+
+impl LateLintPass for HardwiredLints {}
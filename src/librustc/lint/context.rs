@@ -10,14 +10,15 @@
 
 //! Implementation of lint checking.
 //!
-//! The lint checking is mostly consolidated into one pass which runs just
-//! before translation to LLVM bytecode. Throughout compilation, lint warnings
+//! The lint checking is mostly consolidated into two passes: an early one
+//! which runs on the AST right after expansion, and a late one which runs
+//! just before translation to LLVM bytecode. Throughout compilation, lint warnings
 //! can be added via the `add_lint` method on the Session structure. This
 //! requires a span and an id of the node that the lint is being added to. The
 //! lint isn't actually emitted at that time because it is unknown what the
 //! actual lint level at that location is.
 //!
-//! To actually emit lint warnings/errors, a separate pass is used just before
+//! To actually emit lint warnings/errors, the late pass is used just before
 //! translation. A context keeps track of the current state of all lint levels.
 //! Upon entering a node of the ast which can modify the lint settings, the
 //! previous lint state is pushed onto a stack and the ast is then recursed
@@ -30,11 +31,13 @@ use middle::ty::{self, Ty};
 use session::{early_error, Session};
 use session::config::UnstableFeatures;
 use lint::{Level, LevelSource, Lint, LintId, LintArray, LintPass, LintPassObject};
+use lint::{EarlyLintPassObject, LateLintPassObject, LateLintPass};
 use lint::{Default, CommandLine, Node, Allow, Warn, Deny, Forbid, ReleaseChannel};
 use lint::builtin;
-use util::nodemap::FnvHashMap;
+use util::nodemap::{FnvHashMap, FnvHashSet};
 
 use std::cell::RefCell;
+use std::cmp;
 use std::mem;
 use syntax::ast_util::IdVisitingOperation;
 use syntax::attr::AttrMetaMethods;
@@ -46,7 +49,7 @@ use syntax::{ast, ast_util, visit};
 
 /// Information about the registered lints.
 ///
-/// This is basically the subset of `Context` that we can
+/// This is basically the subset of `LateContext` that we can
 /// build early in the compile pipeline.
 pub struct LintStore {
     /// Registered lints. The bool is true if the lint was
@@ -56,7 +59,8 @@ pub struct LintStore {
     /// Trait objects for each lint pass.
     /// This is only `None` while iterating over the objects. See the definition
     /// of run_lints.
-    passes: Option<Vec<LintPassObject>>,
+    early_passes: Option<Vec<EarlyLintPassObject>>,
+    late_passes: Option<Vec<LateLintPassObject>>,
 
     /// Lints indexed by name.
     by_name: FnvHashMap<String, TargetLint>,
@@ -64,9 +68,26 @@ pub struct LintStore {
     /// Current levels of each lint, and where they were set.
     levels: FnvHashMap<LintId, LevelSource>,
 
+    /// Lints given a level on the command line after `warnings` was. Their
+    /// level is not changed by the level of `warnings` while it still comes
+    /// from the command line.
+    after_warnings: FnvHashSet<LintId>,
+
     /// Map of registered lint groups to what lints they expand to. The bool
     /// is true if the lint group was added by a plugin.
     lint_groups: FnvHashMap<&'static str, (Vec<LintId>, bool)>,
+
+    /// Lints found by the early pass at deny or forbid level, which are only
+    /// emitted by the late pass. See `EarlyContext::emit_lint`.
+    deferred: Vec<DeferredLint>,
+}
+
+/// A lint found by the early pass, whose level has already been determined.
+struct DeferredLint {
+    lint: &'static Lint,
+    lvlsrc: LevelSource,
+    span: Option<Span>,
+    msg: String,
 }
 
 /// The targed of the `by_name` map, which accounts for renaming/deprecation.
@@ -97,10 +118,13 @@ impl LintStore {
     pub fn new() -> LintStore {
         LintStore {
             lints: vec!(),
-            passes: Some(vec!()),
+            early_passes: Some(vec!()),
+            late_passes: Some(vec!()),
             by_name: FnvHashMap(),
             levels: FnvHashMap(),
+            after_warnings: FnvHashSet(),
             lint_groups: FnvHashMap(),
+            deferred: vec!(),
         }
     }
 
@@ -114,9 +138,29 @@ impl LintStore {
                                               v.1)).collect()
     }
 
+    pub fn register_early_pass(&mut self, sess: Option<&Session>,
+                               from_plugin: bool, pass: EarlyLintPassObject) {
+        self.register_lints(sess, from_plugin, pass.get_lints());
+        self.early_passes.as_mut().unwrap().push(pass);
+    }
+
+    pub fn register_late_pass(&mut self, sess: Option<&Session>,
+                              from_plugin: bool, pass: LateLintPassObject) {
+        self.register_lints(sess, from_plugin, pass.get_lints());
+        self.late_passes.as_mut().unwrap().push(pass);
+    }
+
     pub fn register_pass(&mut self, sess: Option<&Session>,
                          from_plugin: bool, pass: LintPassObject) {
-        for &lint in pass.get_lints() {
+        match pass {
+            LintPassObject::Early(pass) => self.register_early_pass(sess, from_plugin, pass),
+            LintPassObject::Late(pass) => self.register_late_pass(sess, from_plugin, pass),
+        }
+    }
+
+    fn register_lints(&mut self, sess: Option<&Session>,
+                      from_plugin: bool, lints: LintArray) {
+        for &lint in lints {
             self.lints.push((*lint, from_plugin));
 
             let id = LintId::of(*lint);
@@ -137,7 +181,6 @@ impl LintStore {
                 self.levels.insert(id, (lint.default_level, Default));
             }
         }
-        self.passes.as_mut().unwrap().push(pass);
     }
 
     pub fn register_group(&mut self, sess: Option<&Session>,
@@ -167,8 +210,9 @@ impl LintStore {
         self.by_name.insert(old_name.to_string(), Renamed(new_name.to_string(), target));
     }
 
-    #[allow(unused_variables)]
-    fn find_lint(&self, lint_name: &str, sess: &Session, span: Option<Span>)
+    /// Look up a lint by name. Uses of renamed lints are warned about
+    /// through `sess`, if given.
+    fn find_lint(&self, lint_name: &str, sess: Option<&Session>, span: Option<Span>)
                  -> Option<LintId>
     {
         match self.by_name.get(lint_name) {
@@ -176,9 +220,10 @@ impl LintStore {
             Some(&Renamed(ref new_name, lint_id)) => {
                 let warning = format!("lint {} has been renamed to {}",
                                       lint_name, new_name);
-                match span {
-                    Some(span) => sess.span_warn(span, &warning[..]),
-                    None => sess.warn(&warning[..]),
+                match (sess, span) {
+                    (Some(sess), Some(span)) => sess.span_warn(span, &warning[..]),
+                    (Some(sess), None) => sess.warn(&warning[..]),
+                    (None, _) => {}
                 };
                 Some(lint_id)
             }
@@ -186,20 +231,37 @@ impl LintStore {
         }
     }
 
+    /// Applies the `-A`/`-W`/`-D`/`-F` flags in the order they were given, so
+    /// a later flag overrides an earlier one for the same lint. In particular
+    /// `-D warnings -W unused` keeps the `unused` lints as warnings.
     pub fn process_command_line(&mut self, sess: &Session) {
+        let warnings = LintId::of(builtin::WARNINGS);
+        let mut seen_warnings = false;
         for &(ref lint_name, level) in &sess.opts.lint_opts {
-            match self.find_lint(&lint_name[..], sess, None) {
-                Some(lint_id) => self.set_level(lint_id, (level, CommandLine)),
+            match self.find_lint(&lint_name[..], Some(sess), None) {
+                Some(lint_id) if lint_id == warnings => {
+                    seen_warnings = true;
+                    self.after_warnings.clear();
+                    self.set_level(lint_id, (level, CommandLine));
+                }
+                Some(lint_id) => {
+                    if seen_warnings {
+                        self.after_warnings.insert(lint_id);
+                    }
+                    self.set_level(lint_id, (level, CommandLine))
+                }
                 None => {
                     match self.lint_groups.iter().map(|(&x, pair)| (x, pair.0.clone()))
                                                  .collect::<FnvHashMap<&'static str,
                                                                        Vec<LintId>>>()
                                                  .get(&lint_name[..]) {
                         Some(v) => {
-                            v.iter()
-                             .map(|lint_id: &LintId|
-                                     self.set_level(*lint_id, (level, CommandLine)))
-                             .collect::<Vec<()>>();
+                            for &lint_id in v {
+                                if seen_warnings {
+                                    self.after_warnings.insert(lint_id);
+                                }
+                                self.set_level(lint_id, (level, CommandLine));
+                            }
                         }
                         None => sess.err(&format!("unknown {} flag: {}",
                                                  level.as_str(), lint_name)),
@@ -227,8 +289,8 @@ impl LintStore {
     }
 }
 
-/// Context for lint checking.
-pub struct Context<'a, 'tcx: 'a> {
+/// Context for lint checking after type checking.
+pub struct LateContext<'a, 'tcx: 'a> {
     /// Type context we're checking in.
     pub tcx: &'a ty::ctxt<'tcx>,
 
@@ -251,15 +313,36 @@ pub struct Context<'a, 'tcx: 'a> {
     node_levels: RefCell<FnvHashMap<(ast::NodeId, LintId), LevelSource>>,
 }
 
+/// Context for lint checking of the AST, after expansion, before lowering to
+/// typed form.
+pub struct EarlyContext<'a> {
+    /// Compiler session.
+    pub sess: &'a Session,
+
+    /// The crate being checked.
+    pub krate: &'a ast::Crate,
+
+    /// The store of registered lints.
+    lints: LintStore,
+
+    /// When recursing into an attributed node of the ast which modifies lint
+    /// levels, this stack keeps track of the previous lint levels of whatever
+    /// was modified.
+    level_stack: Vec<(LintId, LevelSource)>,
+
+    /// Lints at deny or forbid level, handed over to the late pass.
+    deferred: RefCell<Vec<DeferredLint>>,
+}
+
 /// Convenience macro for calling a `LintPass` method on every pass in the context.
-macro_rules! run_lints { ($cx:expr, $f:ident, $($args:expr),*) => ({
+macro_rules! run_lints { ($cx:expr, $f:ident, $ps:ident, $($args:expr),*) => ({
     // Move the vector of passes out of `$cx` so that we can
     // iterate over it mutably while passing `$cx` to the methods.
-    let mut passes = $cx.lints.passes.take().unwrap();
+    let mut passes = $cx.lints.$ps.take().unwrap();
     for obj in &mut passes {
         obj.$f($cx, $($args),*);
     }
-    $cx.lints.passes = Some(passes);
+    $cx.lints.$ps = Some(passes);
 }) }
 
 /// Parse the lint attributes into a vector, with `Err`s for malformed lint
@@ -297,13 +380,17 @@ pub fn gather_attrs(attrs: &[ast::Attribute])
 /// Emit a lint as a warning or an error (or not at all)
 /// according to `level`.
 ///
-/// This lives outside of `Context` so it can be used by checks
+/// This lives outside of the contexts so it can be used by checks
 /// in trans that run after the main lint pass is finished. Most
 /// lints elsewhere in the compiler should call
 /// `Session::add_lint()` instead.
 pub fn raw_emit_lint(sess: &Session, lint: &'static Lint,
                      lvlsrc: LevelSource, span: Option<Span>, msg: &str) {
     let (mut level, source) = lvlsrc;
+    // `--cap-lints` applies after every other way of setting a level.
+    if let Some(cap) = sess.opts.lint_cap {
+        level = cmp::min(level, cap);
+    }
     if level == Allow { return }
 
     let name = lint.name_lower();
@@ -353,55 +440,57 @@ pub fn raw_emit_lint(sess: &Session, lint: &'static Lint,
     }
 }
 
-impl<'a, 'tcx> Context<'a, 'tcx> {
-    fn new(tcx: &'a ty::ctxt<'tcx>,
-           krate: &'a ast::Crate,
-           exported_items: &'a ExportedItems) -> Context<'a, 'tcx> {
-        // We want to own the lint store, so move it out of the session.
-        let lint_store = mem::replace(&mut *tcx.sess.lint_store.borrow_mut(),
-                                      LintStore::new());
-
-        Context {
-            tcx: tcx,
-            krate: krate,
-            exported_items: exported_items,
-            lints: lint_store,
-            level_stack: vec![],
-            node_levels: RefCell::new(FnvHashMap()),
-        }
-    }
-
+/// The functionality shared by the early and late lint contexts.
+pub trait LintContext: Sized {
     /// Get the overall compiler `Session` object.
-    pub fn sess(&'a self) -> &'a Session {
-        &self.tcx.sess
+    fn sess(&self) -> &Session;
+    fn lints(&self) -> &LintStore;
+    fn mut_lints(&mut self) -> &mut LintStore;
+    fn level_stack(&mut self) -> &mut Vec<(LintId, LevelSource)>;
+    fn enter_attrs(&mut self, attrs: &[ast::Attribute]);
+    fn exit_attrs(&mut self, attrs: &[ast::Attribute]);
+
+    /// Whether problems with the lint attributes themselves (malformed or
+    /// unknown lints, overruled `forbid`s) are reported by this context.
+    /// Both passes read the attributes, but only one of them reports them.
+    fn reports_attr_errors(&self) -> bool;
+
+    /// Emit a lint whose level has already been looked up.
+    fn emit_lint(&self, lint: &'static Lint, lvlsrc: LevelSource,
+                 span: Option<Span>, msg: &str) {
+        raw_emit_lint(self.sess(), lint, lvlsrc, span, msg);
     }
 
     /// Get the level of `lint` at the current position of the lint
     /// traversal.
-    pub fn current_level(&self, lint: &'static Lint) -> Level {
-        self.lints.levels.get(&LintId::of(lint)).map_or(Allow, |&(lvl, _)| lvl)
+    fn current_level(&self, lint: &'static Lint) -> Level {
+        self.lints().levels.get(&LintId::of(lint)).map_or(Allow, |&(lvl, _)| lvl)
     }
 
     fn lookup_and_emit(&self, lint: &'static Lint, span: Option<Span>, msg: &str) {
-        let (level, src) = match self.lints.levels.get(&LintId::of(lint)) {
+        let lint_id = LintId::of(lint);
+        let (level, src) = match self.lints().levels.get(&lint_id) {
             None => return,
+            Some(&(Warn, CommandLine)) if self.lints().after_warnings.contains(&lint_id) => {
+                (Warn, CommandLine)
+            }
             Some(&(Warn, src)) => {
                 let lint_id = LintId::of(builtin::WARNINGS);
-                (self.lints.get_level_source(lint_id).0, src)
+                (self.lints().get_level_source(lint_id).0, src)
             }
             Some(&pair) => pair,
         };
 
-        raw_emit_lint(&self.tcx.sess, lint, (level, src), span, msg);
+        self.emit_lint(lint, (level, src), span, msg);
     }
 
     /// Emit a lint at the appropriate level, with no associated span.
-    pub fn lint(&self, lint: &'static Lint, msg: &str) {
+    fn lint(&self, lint: &'static Lint, msg: &str) {
         self.lookup_and_emit(lint, None, msg);
     }
 
     /// Emit a lint at the appropriate level, for a particular span.
-    pub fn span_lint(&self, lint: &'static Lint, span: Span, msg: &str) {
+    fn span_lint(&self, lint: &'static Lint, span: Span, msg: &str) {
         self.lookup_and_emit(lint, Some(span), msg);
     }
 
@@ -411,33 +500,39 @@ impl<'a, 'tcx> Context<'a, 'tcx> {
     fn with_lint_attrs<F>(&mut self,
                           attrs: &[ast::Attribute],
                           f: F) where
-        F: FnOnce(&mut Context),
+        F: FnOnce(&mut Self),
     {
         // Parse all of the lint attributes, and then add them all to the
         // current dictionary of lint information. Along the way, keep a history
         // of what we changed so we can roll everything back after invoking the
         // specified closure
         let mut pushed = 0;
+        let report = self.reports_attr_errors();
 
         for result in gather_attrs(attrs) {
             let v = match result {
                 Err(span) => {
-                    self.tcx.sess.span_err(span, "malformed lint attribute");
+                    if report {
+                        self.sess().span_err(span, "malformed lint attribute");
+                    }
                     continue;
                 }
                 Ok((lint_name, level, span)) => {
-                    match self.lints.find_lint(&lint_name, &self.tcx.sess, Some(span)) {
+                    let sess = if report { Some(self.sess()) } else { None };
+                    match self.lints().find_lint(&lint_name, sess, Some(span)) {
                         Some(lint_id) => vec![(lint_id, level, span)],
                         None => {
-                            match self.lints.lint_groups.get(&lint_name[..]) {
+                            match self.lints().lint_groups.get(&lint_name[..]) {
                                 Some(&(ref v, _)) => v.iter()
                                                       .map(|lint_id: &LintId|
                                                            (*lint_id, level, span))
                                                       .collect(),
                                 None => {
-                                    self.span_lint(builtin::UNKNOWN_LINTS, span,
-                                                   &format!("unknown `{}` attribute: `{}`",
-                                                            level.as_str(), lint_name));
+                                    if report {
+                                        self.span_lint(builtin::UNKNOWN_LINTS, span,
+                                                       &format!("unknown `{}` attribute: `{}`",
+                                                                level.as_str(), lint_name));
+                                    }
                                     continue;
                                 }
                             }
@@ -447,35 +542,73 @@ impl<'a, 'tcx> Context<'a, 'tcx> {
             };
 
             for (lint_id, level, span) in v {
-                let now = self.lints.get_level_source(lint_id).0;
+                let (now, src) = self.lints().get_level_source(lint_id);
                 if now == Forbid && level != Forbid {
-                    let lint_name = lint_id.as_str();
-                    self.tcx.sess.span_err(span,
-                                           &format!("{}({}) overruled by outer forbid({})",
-                                                   level.as_str(), lint_name,
-                                                   lint_name));
+                    if report {
+                        let lint_name = lint_id.as_str();
+                        self.sess().span_err(span,
+                                             &format!("{}({}) overruled by outer forbid({})",
+                                                     level.as_str(), lint_name,
+                                                     lint_name));
+                    }
                 } else if now != level {
-                    let src = self.lints.get_level_source(lint_id).1;
-                    self.level_stack.push((lint_id, (now, src)));
+                    self.level_stack().push((lint_id, (now, src)));
                     pushed += 1;
-                    self.lints.set_level(lint_id, (level, Node(span)));
+                    self.mut_lints().set_level(lint_id, (level, Node(span)));
                 }
             }
         }
 
-        run_lints!(self, enter_lint_attrs, attrs);
+        self.enter_attrs(attrs);
         f(self);
-        run_lints!(self, exit_lint_attrs, attrs);
+        self.exit_attrs(attrs);
 
         // rollback
         for _ in 0..pushed {
-            let (lint, lvlsrc) = self.level_stack.pop().unwrap();
-            self.lints.set_level(lint, lvlsrc);
+            let (lint, lvlsrc) = self.level_stack().pop().unwrap();
+            self.mut_lints().set_level(lint, lvlsrc);
+        }
+    }
+}
+
+impl<'a> EarlyContext<'a> {
+    fn new(sess: &'a Session,
+           krate: &'a ast::Crate) -> EarlyContext<'a> {
+        // We want to own the lint store, so move it out of the session. It is
+        // put back by `check_ast_crate`.
+        let lint_store = mem::replace(&mut *sess.lint_store.borrow_mut(),
+                                      LintStore::new());
+
+        EarlyContext {
+            sess: sess,
+            krate: krate,
+            lints: lint_store,
+            level_stack: vec![],
+            deferred: RefCell::new(vec![]),
+        }
+    }
+}
+
+impl<'a, 'tcx> LateContext<'a, 'tcx> {
+    fn new(tcx: &'a ty::ctxt<'tcx>,
+           krate: &'a ast::Crate,
+           exported_items: &'a ExportedItems) -> LateContext<'a, 'tcx> {
+        // We want to own the lint store, so move it out of the session.
+        let lint_store = mem::replace(&mut *tcx.sess.lint_store.borrow_mut(),
+                                      LintStore::new());
+
+        LateContext {
+            tcx: tcx,
+            krate: krate,
+            exported_items: exported_items,
+            lints: lint_store,
+            level_stack: vec![],
+            node_levels: RefCell::new(FnvHashMap()),
         }
     }
 
     fn visit_ids<F>(&mut self, f: F) where
-        F: FnOnce(&mut ast_util::IdVisitor<Context>)
+        F: FnOnce(&mut ast_util::IdVisitor<LateContext>)
     {
         let mut v = ast_util::IdVisitor {
             operation: self,
@@ -486,10 +619,87 @@ impl<'a, 'tcx> Context<'a, 'tcx> {
     }
 }
 
-impl<'a, 'tcx, 'v> Visitor<'v> for Context<'a, 'tcx> {
+impl<'a> LintContext for EarlyContext<'a> {
+    fn sess(&self) -> &Session {
+        self.sess
+    }
+
+    fn lints(&self) -> &LintStore {
+        &self.lints
+    }
+
+    fn mut_lints(&mut self) -> &mut LintStore {
+        &mut self.lints
+    }
+
+    fn level_stack(&mut self) -> &mut Vec<(LintId, LevelSource)> {
+        &mut self.level_stack
+    }
+
+    fn enter_attrs(&mut self, attrs: &[ast::Attribute]) {
+        run_lints!(self, enter_lint_attrs, early_passes, attrs);
+    }
+
+    fn exit_attrs(&mut self, attrs: &[ast::Attribute]) {
+        run_lints!(self, exit_lint_attrs, early_passes, attrs);
+    }
+
+    fn reports_attr_errors(&self) -> bool {
+        false
+    }
+
+    /// Errors are held back and emitted by the late pass instead, so that
+    /// they don't stop compilation before the rest of the analysis has had a
+    /// chance to report its own errors.
+    fn emit_lint(&self, lint: &'static Lint, lvlsrc: LevelSource,
+                 span: Option<Span>, msg: &str) {
+        if lvlsrc.0 >= Deny {
+            self.deferred.borrow_mut().push(DeferredLint {
+                lint: lint,
+                lvlsrc: lvlsrc,
+                span: span,
+                msg: msg.to_string(),
+            });
+        } else {
+            raw_emit_lint(self.sess, lint, lvlsrc, span, msg);
+        }
+    }
+}
+
+impl<'a, 'tcx> LintContext for LateContext<'a, 'tcx> {
+    fn sess(&self) -> &Session {
+        &self.tcx.sess
+    }
+
+    fn lints(&self) -> &LintStore {
+        &self.lints
+    }
+
+    fn mut_lints(&mut self) -> &mut LintStore {
+        &mut self.lints
+    }
+
+    fn level_stack(&mut self) -> &mut Vec<(LintId, LevelSource)> {
+        &mut self.level_stack
+    }
+
+    fn enter_attrs(&mut self, attrs: &[ast::Attribute]) {
+        run_lints!(self, enter_lint_attrs, late_passes, attrs);
+    }
+
+    fn exit_attrs(&mut self, attrs: &[ast::Attribute]) {
+        run_lints!(self, exit_lint_attrs, late_passes, attrs);
+    }
+
+    fn reports_attr_errors(&self) -> bool {
+        true
+    }
+}
+
+impl<'a, 'tcx, 'v> Visitor<'v> for LateContext<'a, 'tcx> {
     fn visit_item(&mut self, it: &ast::Item) {
         self.with_lint_attrs(&it.attrs, |cx| {
-            run_lints!(cx, check_item, it);
+            run_lints!(cx, check_item, late_passes, it);
             cx.visit_ids(|v| v.visit_item(it));
             visit::walk_item(cx, it);
         })
@@ -497,29 +707,29 @@ impl<'a, 'tcx, 'v> Visitor<'v> for Context<'a, 'tcx> {
 
     fn visit_foreign_item(&mut self, it: &ast::ForeignItem) {
         self.with_lint_attrs(&it.attrs, |cx| {
-            run_lints!(cx, check_foreign_item, it);
+            run_lints!(cx, check_foreign_item, late_passes, it);
             visit::walk_foreign_item(cx, it);
         })
     }
 
     fn visit_pat(&mut self, p: &ast::Pat) {
-        run_lints!(self, check_pat, p);
+        run_lints!(self, check_pat, late_passes, p);
         visit::walk_pat(self, p);
     }
 
     fn visit_expr(&mut self, e: &ast::Expr) {
-        run_lints!(self, check_expr, e);
+        run_lints!(self, check_expr, late_passes, e);
         visit::walk_expr(self, e);
     }
 
     fn visit_stmt(&mut self, s: &ast::Stmt) {
-        run_lints!(self, check_stmt, s);
+        run_lints!(self, check_stmt, late_passes, s);
         visit::walk_stmt(self, s);
     }
 
     fn visit_fn(&mut self, fk: FnKind<'v>, decl: &'v ast::FnDecl,
                 body: &'v ast::Block, span: Span, id: ast::NodeId) {
-        run_lints!(self, check_fn, fk, decl, body, span, id);
+        run_lints!(self, check_fn, late_passes, fk, decl, body, span, id);
         visit::walk_fn(self, fk, decl, body, span);
     }
 
@@ -528,72 +738,72 @@ impl<'a, 'tcx, 'v> Visitor<'v> for Context<'a, 'tcx> {
                         ident: ast::Ident,
                         g: &ast::Generics,
                         id: ast::NodeId) {
-        run_lints!(self, check_struct_def, s, ident, g, id);
+        run_lints!(self, check_struct_def, late_passes, s, ident, g, id);
         visit::walk_struct_def(self, s);
-        run_lints!(self, check_struct_def_post, s, ident, g, id);
+        run_lints!(self, check_struct_def_post, late_passes, s, ident, g, id);
     }
 
     fn visit_struct_field(&mut self, s: &ast::StructField) {
         self.with_lint_attrs(&s.node.attrs, |cx| {
-            run_lints!(cx, check_struct_field, s);
+            run_lints!(cx, check_struct_field, late_passes, s);
             visit::walk_struct_field(cx, s);
         })
     }
 
     fn visit_variant(&mut self, v: &ast::Variant, g: &ast::Generics) {
         self.with_lint_attrs(&v.node.attrs, |cx| {
-            run_lints!(cx, check_variant, v, g);
+            run_lints!(cx, check_variant, late_passes, v, g);
             visit::walk_variant(cx, v, g);
-            run_lints!(cx, check_variant_post, v, g);
+            run_lints!(cx, check_variant_post, late_passes, v, g);
         })
     }
 
     fn visit_ty(&mut self, t: &ast::Ty) {
-        run_lints!(self, check_ty, t);
+        run_lints!(self, check_ty, late_passes, t);
         visit::walk_ty(self, t);
     }
 
     fn visit_ident(&mut self, sp: Span, id: ast::Ident) {
-        run_lints!(self, check_ident, sp, id);
+        run_lints!(self, check_ident, late_passes, sp, id);
     }
 
     fn visit_mod(&mut self, m: &ast::Mod, s: Span, n: ast::NodeId) {
-        run_lints!(self, check_mod, m, s, n);
+        run_lints!(self, check_mod, late_passes, m, s, n);
         visit::walk_mod(self, m);
     }
 
     fn visit_local(&mut self, l: &ast::Local) {
-        run_lints!(self, check_local, l);
+        run_lints!(self, check_local, late_passes, l);
         visit::walk_local(self, l);
     }
 
     fn visit_block(&mut self, b: &ast::Block) {
-        run_lints!(self, check_block, b);
+        run_lints!(self, check_block, late_passes, b);
         visit::walk_block(self, b);
     }
 
     fn visit_arm(&mut self, a: &ast::Arm) {
-        run_lints!(self, check_arm, a);
+        run_lints!(self, check_arm, late_passes, a);
         visit::walk_arm(self, a);
     }
 
     fn visit_decl(&mut self, d: &ast::Decl) {
-        run_lints!(self, check_decl, d);
+        run_lints!(self, check_decl, late_passes, d);
         visit::walk_decl(self, d);
     }
 
     fn visit_expr_post(&mut self, e: &ast::Expr) {
-        run_lints!(self, check_expr_post, e);
+        run_lints!(self, check_expr_post, late_passes, e);
     }
 
     fn visit_generics(&mut self, g: &ast::Generics) {
-        run_lints!(self, check_generics, g);
+        run_lints!(self, check_generics, late_passes, g);
         visit::walk_generics(self, g);
     }
 
     fn visit_trait_item(&mut self, trait_item: &ast::TraitItem) {
         self.with_lint_attrs(&trait_item.attrs, |cx| {
-            run_lints!(cx, check_trait_item, trait_item);
+            run_lints!(cx, check_trait_item, late_passes, trait_item);
             cx.visit_ids(|v| v.visit_trait_item(trait_item));
             visit::walk_trait_item(cx, trait_item);
         });
@@ -601,59 +811,215 @@ impl<'a, 'tcx, 'v> Visitor<'v> for Context<'a, 'tcx> {
 
     fn visit_impl_item(&mut self, impl_item: &ast::ImplItem) {
         self.with_lint_attrs(&impl_item.attrs, |cx| {
-            run_lints!(cx, check_impl_item, impl_item);
+            run_lints!(cx, check_impl_item, late_passes, impl_item);
             cx.visit_ids(|v| v.visit_impl_item(impl_item));
             visit::walk_impl_item(cx, impl_item);
         });
     }
 
     fn visit_opt_lifetime_ref(&mut self, sp: Span, lt: &Option<ast::Lifetime>) {
-        run_lints!(self, check_opt_lifetime_ref, sp, lt);
+        run_lints!(self, check_opt_lifetime_ref, late_passes, sp, lt);
     }
 
     fn visit_lifetime_ref(&mut self, lt: &ast::Lifetime) {
-        run_lints!(self, check_lifetime_ref, lt);
+        run_lints!(self, check_lifetime_ref, late_passes, lt);
     }
 
     fn visit_lifetime_def(&mut self, lt: &ast::LifetimeDef) {
-        run_lints!(self, check_lifetime_def, lt);
+        run_lints!(self, check_lifetime_def, late_passes, lt);
     }
 
     fn visit_explicit_self(&mut self, es: &ast::ExplicitSelf) {
-        run_lints!(self, check_explicit_self, es);
+        run_lints!(self, check_explicit_self, late_passes, es);
         visit::walk_explicit_self(self, es);
     }
 
     fn visit_mac(&mut self, mac: &ast::Mac) {
-        run_lints!(self, check_mac, mac);
+        run_lints!(self, check_mac, late_passes, mac);
         visit::walk_mac(self, mac);
     }
 
     fn visit_path(&mut self, p: &ast::Path, id: ast::NodeId) {
-        run_lints!(self, check_path, p, id);
+        run_lints!(self, check_path, late_passes, p, id);
         visit::walk_path(self, p);
     }
 
     fn visit_attribute(&mut self, attr: &ast::Attribute) {
-        run_lints!(self, check_attribute, attr);
+        run_lints!(self, check_attribute, late_passes, attr);
+    }
+}
+
+impl<'a, 'v> Visitor<'v> for EarlyContext<'a> {
+    fn visit_item(&mut self, it: &ast::Item) {
+        self.with_lint_attrs(&it.attrs, |cx| {
+            run_lints!(cx, check_item, early_passes, it);
+            visit::walk_item(cx, it);
+        })
+    }
+
+    fn visit_foreign_item(&mut self, it: &ast::ForeignItem) {
+        self.with_lint_attrs(&it.attrs, |cx| {
+            run_lints!(cx, check_foreign_item, early_passes, it);
+            visit::walk_foreign_item(cx, it);
+        })
+    }
+
+    fn visit_pat(&mut self, p: &ast::Pat) {
+        run_lints!(self, check_pat, early_passes, p);
+        visit::walk_pat(self, p);
+    }
+
+    fn visit_expr(&mut self, e: &ast::Expr) {
+        run_lints!(self, check_expr, early_passes, e);
+        visit::walk_expr(self, e);
+    }
+
+    fn visit_stmt(&mut self, s: &ast::Stmt) {
+        run_lints!(self, check_stmt, early_passes, s);
+        visit::walk_stmt(self, s);
+    }
+
+    fn visit_fn(&mut self, fk: FnKind<'v>, decl: &'v ast::FnDecl,
+                body: &'v ast::Block, span: Span, id: ast::NodeId) {
+        run_lints!(self, check_fn, early_passes, fk, decl, body, span, id);
+        visit::walk_fn(self, fk, decl, body, span);
+    }
+
+    fn visit_struct_def(&mut self,
+                        s: &ast::StructDef,
+                        ident: ast::Ident,
+                        g: &ast::Generics,
+                        id: ast::NodeId) {
+        run_lints!(self, check_struct_def, early_passes, s, ident, g, id);
+        visit::walk_struct_def(self, s);
+        run_lints!(self, check_struct_def_post, early_passes, s, ident, g, id);
+    }
+
+    fn visit_struct_field(&mut self, s: &ast::StructField) {
+        self.with_lint_attrs(&s.node.attrs, |cx| {
+            run_lints!(cx, check_struct_field, early_passes, s);
+            visit::walk_struct_field(cx, s);
+        })
+    }
+
+    fn visit_variant(&mut self, v: &ast::Variant, g: &ast::Generics) {
+        self.with_lint_attrs(&v.node.attrs, |cx| {
+            run_lints!(cx, check_variant, early_passes, v, g);
+            visit::walk_variant(cx, v, g);
+            run_lints!(cx, check_variant_post, early_passes, v, g);
+        })
+    }
+
+    fn visit_ty(&mut self, t: &ast::Ty) {
+        run_lints!(self, check_ty, early_passes, t);
+        visit::walk_ty(self, t);
+    }
+
+    fn visit_ident(&mut self, sp: Span, id: ast::Ident) {
+        run_lints!(self, check_ident, early_passes, sp, id);
+    }
+
+    fn visit_mod(&mut self, m: &ast::Mod, s: Span, n: ast::NodeId) {
+        run_lints!(self, check_mod, early_passes, m, s, n);
+        visit::walk_mod(self, m);
+    }
+
+    fn visit_local(&mut self, l: &ast::Local) {
+        run_lints!(self, check_local, early_passes, l);
+        visit::walk_local(self, l);
+    }
+
+    fn visit_block(&mut self, b: &ast::Block) {
+        run_lints!(self, check_block, early_passes, b);
+        visit::walk_block(self, b);
+    }
+
+    fn visit_arm(&mut self, a: &ast::Arm) {
+        run_lints!(self, check_arm, early_passes, a);
+        visit::walk_arm(self, a);
+    }
+
+    fn visit_decl(&mut self, d: &ast::Decl) {
+        run_lints!(self, check_decl, early_passes, d);
+        visit::walk_decl(self, d);
+    }
+
+    fn visit_expr_post(&mut self, e: &ast::Expr) {
+        run_lints!(self, check_expr_post, early_passes, e);
+    }
+
+    fn visit_generics(&mut self, g: &ast::Generics) {
+        run_lints!(self, check_generics, early_passes, g);
+        visit::walk_generics(self, g);
+    }
+
+    fn visit_trait_item(&mut self, trait_item: &ast::TraitItem) {
+        self.with_lint_attrs(&trait_item.attrs, |cx| {
+            run_lints!(cx, check_trait_item, early_passes, trait_item);
+            visit::walk_trait_item(cx, trait_item);
+        });
+    }
+
+    fn visit_impl_item(&mut self, impl_item: &ast::ImplItem) {
+        self.with_lint_attrs(&impl_item.attrs, |cx| {
+            run_lints!(cx, check_impl_item, early_passes, impl_item);
+            visit::walk_impl_item(cx, impl_item);
+        });
+    }
+
+    fn visit_opt_lifetime_ref(&mut self, sp: Span, lt: &Option<ast::Lifetime>) {
+        run_lints!(self, check_opt_lifetime_ref, early_passes, sp, lt);
+    }
+
+    fn visit_lifetime_ref(&mut self, lt: &ast::Lifetime) {
+        run_lints!(self, check_lifetime_ref, early_passes, lt);
+    }
+
+    fn visit_lifetime_def(&mut self, lt: &ast::LifetimeDef) {
+        run_lints!(self, check_lifetime_def, early_passes, lt);
+    }
+
+    fn visit_explicit_self(&mut self, es: &ast::ExplicitSelf) {
+        run_lints!(self, check_explicit_self, early_passes, es);
+        visit::walk_explicit_self(self, es);
+    }
+
+    fn visit_mac(&mut self, mac: &ast::Mac) {
+        run_lints!(self, check_mac, early_passes, mac);
+        visit::walk_mac(self, mac);
+    }
+
+    fn visit_path(&mut self, p: &ast::Path, id: ast::NodeId) {
+        run_lints!(self, check_path, early_passes, p, id);
+        visit::walk_path(self, p);
+    }
+
+    fn visit_attribute(&mut self, attr: &ast::Attribute) {
+        run_lints!(self, check_attribute, early_passes, attr);
     }
 }
 
 // Output any lints that were previously added to the session.
-impl<'a, 'tcx> IdVisitingOperation for Context<'a, 'tcx> {
+impl<'a, 'tcx> IdVisitingOperation for LateContext<'a, 'tcx> {
     fn visit_id(&mut self, id: ast::NodeId) {
         match self.tcx.sess.lints.borrow_mut().remove(&id) {
             None => {}
             Some(lints) => {
-                for (lint_id, span, msg) in lints {
-                    self.span_lint(lint_id.lint, span, &msg[..])
+                for buffered in lints {
+                    let lint = buffered.lint_id.lint;
+                    self.span_lint(lint, buffered.span, &buffered.msg[..]);
+                    if self.current_level(lint) != Allow {
+                        for (span, note) in buffered.notes {
+                            self.sess().span_note(span, &note[..]);
+                        }
+                    }
                 }
             }
         }
     }
 }
 
-// This lint pass is defined here because it touches parts of the `Context`
+// This lint pass is defined here because it touches parts of the `LateContext`
 // that we don't want to expose. It records the lint level at certain AST
 // nodes, so that the variant size difference check in trans can call
 // `raw_emit_lint`.
@@ -664,8 +1030,10 @@ impl LintPass for GatherNodeLevels {
     fn get_lints(&self) -> LintArray {
         lint_array!()
     }
+}
 
-    fn check_item(&mut self, cx: &Context, it: &ast::Item) {
+impl LateLintPass for GatherNodeLevels {
+    fn check_item(&mut self, cx: &LateContext, it: &ast::Item) {
         match it.node {
             ast::ItemEnum(..) => {
                 let lint_id = LintId::of(builtin::VARIANT_SIZE_DIFFERENCES);
@@ -688,12 +1056,14 @@ impl LintPass for GatherNodeLevels {
 /// Consumes the `lint_store` field of the `Session`.
 pub fn check_crate(tcx: &ty::ctxt,
                    exported_items: &ExportedItems) {
-
-    // If this is a feature-staged build of rustc then flip several lints to 'forbid'
-    tcx.sess.lint_store.borrow_mut().maybe_stage_features(&tcx.sess);
-
     let krate = tcx.map.krate();
-    let mut cx = Context::new(tcx, krate, exported_items);
+    let mut cx = LateContext::new(tcx, krate, exported_items);
+
+    // Emit the errors the early pass held back.
+    for deferred in mem::replace(&mut cx.lints.deferred, vec![]) {
+        raw_emit_lint(&tcx.sess, deferred.lint, deferred.lvlsrc,
+                      deferred.span, &deferred.msg[..]);
+    }
 
     // Visit the whole crate.
     cx.with_lint_attrs(&krate.attrs, |cx| {
@@ -705,7 +1075,7 @@ pub fn check_crate(tcx: &ty::ctxt,
 
         // since the root module isn't visited as an item (because it isn't an
         // item), warn for it here.
-        run_lints!(cx, check_crate, krate);
+        run_lints!(cx, check_crate, late_passes, krate);
 
         visit::walk_crate(cx, krate);
     });
@@ -713,12 +1083,40 @@ pub fn check_crate(tcx: &ty::ctxt,
     // If we missed any lints added to the session, then there's a bug somewhere
     // in the iteration code.
     for (id, v) in &*tcx.sess.lints.borrow() {
-        for &(lint, span, ref msg) in v {
-            tcx.sess.span_bug(span,
+        for buffered in v {
+            tcx.sess.span_bug(buffered.span,
                               &format!("unprocessed lint {} at {}: {}",
-                                       lint.as_str(), tcx.map.node_to_string(*id), *msg))
+                                       buffered.lint_id.as_str(),
+                                       tcx.map.node_to_string(*id),
+                                       buffered.msg))
         }
     }
 
     *tcx.node_lint_levels.borrow_mut() = cx.node_levels.into_inner();
 }
+
+/// Run the early lint passes over a crate.
+///
+/// Borrows the `lint_store` field of the `Session` for the duration of the
+/// traversal.
+pub fn check_ast_crate(sess: &Session, krate: &ast::Crate) {
+    // If this is a feature-staged build of rustc then flip several lints to 'forbid'
+    sess.lint_store.borrow_mut().maybe_stage_features(sess);
+
+    let mut cx = EarlyContext::new(sess, krate);
+
+    // Visit the whole crate.
+    cx.with_lint_attrs(&krate.attrs, |cx| {
+        // since the root module isn't visited as an item (because it isn't an
+        // item), warn for it here.
+        run_lints!(cx, check_crate, early_passes, krate);
+
+        visit::walk_crate(cx, krate);
+    });
+
+    // Put the lint store back in the session, along with the errors that
+    // the late pass is to emit.
+    let EarlyContext { mut lints, deferred, .. } = cx;
+    lints.deferred = deferred.into_inner();
+    *sess.lint_store.borrow_mut() = lints;
+}
@@ -16,8 +16,10 @@
 //! other phases of the compiler, which are generally required to hold in order
 //! to compile the program at all.
 //!
-//! Most lints can be written as `LintPass` instances. These run just before
-//! translation to LLVM bytecode. The `LintPass`es built into rustc are defined
+//! Most lints can be written as `LintPass` instances. These come in two
+//! kinds: `EarlyLintPass`es run on the AST right after macro expansion, and
+//! `LateLintPass`es run just before translation to LLVM bytecode, with type
+//! information available. The `LintPass`es built into rustc are defined
 //! within `builtin.rs`, which has further comments on how to add such a lint.
 //! rustc can also load user-defined lint plugins via the plugin mechanism.
 //!
@@ -37,7 +39,8 @@ use syntax::codemap::Span;
 use syntax::visit::FnKind;
 use syntax::ast;
 
-pub use lint::context::{Context, LintStore, raw_emit_lint, check_crate, gather_attrs,
+pub use lint::context::{LateContext, EarlyContext, LintContext, LintStore,
+                        raw_emit_lint, check_crate, check_ast_crate, gather_attrs,
                         GatherNodeLevels};
 
 /// Specification of a single lint.
@@ -111,12 +114,8 @@ pub type LintArray = &'static [&'static &'static Lint];
 
 /// Trait for types providing lint checks.
 ///
-/// Each `check` method checks a single syntax node, and should not
-/// invoke methods recursively (unlike `Visitor`). By default they
-/// do nothing.
-//
-// FIXME: eliminate the duplication with `Visitor`. But this also
-// contains a few lint-specific methods with no equivalent in `Visitor`.
+/// The checks themselves are provided by the `EarlyLintPass` and
+/// `LateLintPass` traits, depending on when the pass runs.
 pub trait LintPass {
     /// Get descriptions of the lints this `LintPass` object can emit.
     ///
@@ -125,51 +124,118 @@ pub trait LintPass {
     /// parts of the compiler. If you want enforced access restrictions for your
     /// `Lint`, make it a private `static` item in its own module.
     fn get_lints(&self) -> LintArray;
+}
+
+/// Trait for lint passes that run after type checking.
+///
+/// Each `check` method checks a single syntax node, and should not
+/// invoke methods recursively (unlike `Visitor`). By default they
+/// do nothing.
+//
+// FIXME: eliminate the duplication with `Visitor`. But this also
+// contains a few lint-specific methods with no equivalent in `Visitor`.
+pub trait LateLintPass: LintPass {
+    fn check_crate(&mut self, _: &LateContext, _: &ast::Crate) { }
+    fn check_ident(&mut self, _: &LateContext, _: Span, _: ast::Ident) { }
+    fn check_mod(&mut self, _: &LateContext, _: &ast::Mod, _: Span, _: ast::NodeId) { }
+    fn check_foreign_item(&mut self, _: &LateContext, _: &ast::ForeignItem) { }
+    fn check_item(&mut self, _: &LateContext, _: &ast::Item) { }
+    fn check_local(&mut self, _: &LateContext, _: &ast::Local) { }
+    fn check_block(&mut self, _: &LateContext, _: &ast::Block) { }
+    fn check_stmt(&mut self, _: &LateContext, _: &ast::Stmt) { }
+    fn check_arm(&mut self, _: &LateContext, _: &ast::Arm) { }
+    fn check_pat(&mut self, _: &LateContext, _: &ast::Pat) { }
+    fn check_decl(&mut self, _: &LateContext, _: &ast::Decl) { }
+    fn check_expr(&mut self, _: &LateContext, _: &ast::Expr) { }
+    fn check_expr_post(&mut self, _: &LateContext, _: &ast::Expr) { }
+    fn check_ty(&mut self, _: &LateContext, _: &ast::Ty) { }
+    fn check_generics(&mut self, _: &LateContext, _: &ast::Generics) { }
+    fn check_fn(&mut self, _: &LateContext,
+        _: FnKind, _: &ast::FnDecl, _: &ast::Block, _: Span, _: ast::NodeId) { }
+    fn check_trait_item(&mut self, _: &LateContext, _: &ast::TraitItem) { }
+    fn check_impl_item(&mut self, _: &LateContext, _: &ast::ImplItem) { }
+    fn check_struct_def(&mut self, _: &LateContext,
+        _: &ast::StructDef, _: ast::Ident, _: &ast::Generics, _: ast::NodeId) { }
+    fn check_struct_def_post(&mut self, _: &LateContext,
+        _: &ast::StructDef, _: ast::Ident, _: &ast::Generics, _: ast::NodeId) { }
+    fn check_struct_field(&mut self, _: &LateContext, _: &ast::StructField) { }
+    fn check_variant(&mut self, _: &LateContext, _: &ast::Variant, _: &ast::Generics) { }
+    fn check_variant_post(&mut self, _: &LateContext, _: &ast::Variant, _: &ast::Generics) { }
+    fn check_opt_lifetime_ref(&mut self, _: &LateContext, _: Span, _: &Option<ast::Lifetime>) { }
+    fn check_lifetime_ref(&mut self, _: &LateContext, _: &ast::Lifetime) { }
+    fn check_lifetime_def(&mut self, _: &LateContext, _: &ast::LifetimeDef) { }
+    fn check_explicit_self(&mut self, _: &LateContext, _: &ast::ExplicitSelf) { }
+    fn check_mac(&mut self, _: &LateContext, _: &ast::Mac) { }
+    fn check_path(&mut self, _: &LateContext, _: &ast::Path, _: ast::NodeId) { }
+    fn check_attribute(&mut self, _: &LateContext, _: &ast::Attribute) { }
+
+    /// Called when entering a syntax node that can have lint attributes such
+    /// as `#[allow(...)]`. Called with *all* the attributes of that node.
+    fn enter_lint_attrs(&mut self, _: &LateContext, _: &[ast::Attribute]) { }
 
-    fn check_crate(&mut self, _: &Context, _: &ast::Crate) { }
-    fn check_ident(&mut self, _: &Context, _: Span, _: ast::Ident) { }
-    fn check_mod(&mut self, _: &Context, _: &ast::Mod, _: Span, _: ast::NodeId) { }
-    fn check_foreign_item(&mut self, _: &Context, _: &ast::ForeignItem) { }
-    fn check_item(&mut self, _: &Context, _: &ast::Item) { }
-    fn check_local(&mut self, _: &Context, _: &ast::Local) { }
-    fn check_block(&mut self, _: &Context, _: &ast::Block) { }
-    fn check_stmt(&mut self, _: &Context, _: &ast::Stmt) { }
-    fn check_arm(&mut self, _: &Context, _: &ast::Arm) { }
-    fn check_pat(&mut self, _: &Context, _: &ast::Pat) { }
-    fn check_decl(&mut self, _: &Context, _: &ast::Decl) { }
-    fn check_expr(&mut self, _: &Context, _: &ast::Expr) { }
-    fn check_expr_post(&mut self, _: &Context, _: &ast::Expr) { }
-    fn check_ty(&mut self, _: &Context, _: &ast::Ty) { }
-    fn check_generics(&mut self, _: &Context, _: &ast::Generics) { }
-    fn check_fn(&mut self, _: &Context,
+    /// Counterpart to `enter_lint_attrs`.
+    fn exit_lint_attrs(&mut self, _: &LateContext, _: &[ast::Attribute]) { }
+}
+
+/// Trait for lint passes that run on the AST right after expansion.
+///
+/// These passes have no access to type information, and run even when
+/// resolution or type checking fails. The `check` methods mirror those of
+/// `LateLintPass`.
+pub trait EarlyLintPass: LintPass {
+    fn check_crate(&mut self, _: &EarlyContext, _: &ast::Crate) { }
+    fn check_ident(&mut self, _: &EarlyContext, _: Span, _: ast::Ident) { }
+    fn check_mod(&mut self, _: &EarlyContext, _: &ast::Mod, _: Span, _: ast::NodeId) { }
+    fn check_foreign_item(&mut self, _: &EarlyContext, _: &ast::ForeignItem) { }
+    fn check_item(&mut self, _: &EarlyContext, _: &ast::Item) { }
+    fn check_local(&mut self, _: &EarlyContext, _: &ast::Local) { }
+    fn check_block(&mut self, _: &EarlyContext, _: &ast::Block) { }
+    fn check_stmt(&mut self, _: &EarlyContext, _: &ast::Stmt) { }
+    fn check_arm(&mut self, _: &EarlyContext, _: &ast::Arm) { }
+    fn check_pat(&mut self, _: &EarlyContext, _: &ast::Pat) { }
+    fn check_decl(&mut self, _: &EarlyContext, _: &ast::Decl) { }
+    fn check_expr(&mut self, _: &EarlyContext, _: &ast::Expr) { }
+    fn check_expr_post(&mut self, _: &EarlyContext, _: &ast::Expr) { }
+    fn check_ty(&mut self, _: &EarlyContext, _: &ast::Ty) { }
+    fn check_generics(&mut self, _: &EarlyContext, _: &ast::Generics) { }
+    fn check_fn(&mut self, _: &EarlyContext,
         _: FnKind, _: &ast::FnDecl, _: &ast::Block, _: Span, _: ast::NodeId) { }
-    fn check_trait_item(&mut self, _: &Context, _: &ast::TraitItem) { }
-    fn check_impl_item(&mut self, _: &Context, _: &ast::ImplItem) { }
-    fn check_struct_def(&mut self, _: &Context,
+    fn check_trait_item(&mut self, _: &EarlyContext, _: &ast::TraitItem) { }
+    fn check_impl_item(&mut self, _: &EarlyContext, _: &ast::ImplItem) { }
+    fn check_struct_def(&mut self, _: &EarlyContext,
         _: &ast::StructDef, _: ast::Ident, _: &ast::Generics, _: ast::NodeId) { }
-    fn check_struct_def_post(&mut self, _: &Context,
+    fn check_struct_def_post(&mut self, _: &EarlyContext,
         _: &ast::StructDef, _: ast::Ident, _: &ast::Generics, _: ast::NodeId) { }
-    fn check_struct_field(&mut self, _: &Context, _: &ast::StructField) { }
-    fn check_variant(&mut self, _: &Context, _: &ast::Variant, _: &ast::Generics) { }
-    fn check_variant_post(&mut self, _: &Context, _: &ast::Variant, _: &ast::Generics) { }
-    fn check_opt_lifetime_ref(&mut self, _: &Context, _: Span, _: &Option<ast::Lifetime>) { }
-    fn check_lifetime_ref(&mut self, _: &Context, _: &ast::Lifetime) { }
-    fn check_lifetime_def(&mut self, _: &Context, _: &ast::LifetimeDef) { }
-    fn check_explicit_self(&mut self, _: &Context, _: &ast::ExplicitSelf) { }
-    fn check_mac(&mut self, _: &Context, _: &ast::Mac) { }
-    fn check_path(&mut self, _: &Context, _: &ast::Path, _: ast::NodeId) { }
-    fn check_attribute(&mut self, _: &Context, _: &ast::Attribute) { }
+    fn check_struct_field(&mut self, _: &EarlyContext, _: &ast::StructField) { }
+    fn check_variant(&mut self, _: &EarlyContext, _: &ast::Variant, _: &ast::Generics) { }
+    fn check_variant_post(&mut self, _: &EarlyContext, _: &ast::Variant, _: &ast::Generics) { }
+    fn check_opt_lifetime_ref(&mut self, _: &EarlyContext, _: Span, _: &Option<ast::Lifetime>) { }
+    fn check_lifetime_ref(&mut self, _: &EarlyContext, _: &ast::Lifetime) { }
+    fn check_lifetime_def(&mut self, _: &EarlyContext, _: &ast::LifetimeDef) { }
+    fn check_explicit_self(&mut self, _: &EarlyContext, _: &ast::ExplicitSelf) { }
+    fn check_mac(&mut self, _: &EarlyContext, _: &ast::Mac) { }
+    fn check_path(&mut self, _: &EarlyContext, _: &ast::Path, _: ast::NodeId) { }
+    fn check_attribute(&mut self, _: &EarlyContext, _: &ast::Attribute) { }
 
     /// Called when entering a syntax node that can have lint attributes such
     /// as `#[allow(...)]`. Called with *all* the attributes of that node.
-    fn enter_lint_attrs(&mut self, _: &Context, _: &[ast::Attribute]) { }
+    fn enter_lint_attrs(&mut self, _: &EarlyContext, _: &[ast::Attribute]) { }
 
     /// Counterpart to `enter_lint_attrs`.
-    fn exit_lint_attrs(&mut self, _: &Context, _: &[ast::Attribute]) { }
+    fn exit_lint_attrs(&mut self, _: &EarlyContext, _: &[ast::Attribute]) { }
 }
 
-/// A lint pass boxed up as a trait object.
-pub type LintPassObject = Box<LintPass + 'static>;
+/// An early lint pass boxed up as a trait object.
+pub type EarlyLintPassObject = Box<EarlyLintPass + 'static>;
+
+/// A late lint pass boxed up as a trait object.
+pub type LateLintPassObject = Box<LateLintPass + 'static>;
+
+/// A lint pass of either kind, as registered by plugins.
+pub enum LintPassObject {
+    Early(EarlyLintPassObject),
+    Late(LateLintPassObject),
+}
 
 /// Identifies a lint known to the compiler.
 #[derive(Clone, Copy)]
@@ -207,6 +273,16 @@ impl LintId {
     }
 }
 
+/// A lint found before the lint pass runs (see `Session::add_lint`), to be
+/// emitted once the lint levels at its node are known.
+pub struct BufferedLint {
+    pub lint_id: LintId,
+    pub span: Span,
+    pub msg: String,
+    /// Notes to attach to the lint, if it is emitted at all.
+    pub notes: Vec<(Span, String)>,
+}
+
 /// Setting for how to handle a lint.
 #[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug)]
 pub enum Level {
@@ -78,6 +78,7 @@ pub const tag_crate_crate_name: uint = 0x104; // top-level only
 
 pub const tag_crate_dep_crate_name: uint = 0x36;
 pub const tag_crate_dep_hash: uint = 0x37;
+pub const tag_crate_dep_explicitly_linked: uint = 0xa5;
 
 pub const tag_mod_impl: uint = 0x38;
 
@@ -256,3 +257,15 @@ pub const tag_codemap_filemap: uint = 0xa2;
 pub const tag_item_super_predicates: uint = 0xa3;
 
 pub const tag_defaulted_trait: uint = 0xa4;
+
+pub const tag_items_data_item_deprecation: uint = 0xa6;
+
+pub const tag_unmangled_symbols: uint = 0x10f; // top-level only
+pub const tag_unmangled_symbol: uint = 0xa7;
+
+/// The version of the compiler which is stamped into the header of all
+/// metadata it encodes. The loader rejects crates carrying any other stamp,
+/// as their rbml is not guaranteed to be decodable by this compiler.
+pub fn rustc_version() -> &'static str {
+    option_env!("CFG_VERSION").unwrap_or("unknown version")
+}
@@ -22,6 +22,7 @@ use metadata::loader;
 use metadata::loader::CratePaths;
 
 use std::path::{Path, PathBuf};
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 use syntax::ast;
 use syntax::abi;
@@ -161,6 +162,7 @@ impl<'a> CrateReader<'a> {
     pub fn read_crates(&mut self, krate: &ast::Crate) {
         self.process_crate(krate);
         visit::walk_crate(self, krate);
+        self.inject_allocator_crate(krate);
 
         if log_enabled!(log::DEBUG) {
             dump_crates(&self.sess.cstore);
@@ -180,6 +182,99 @@ impl<'a> CrateReader<'a> {
         }
     }
 
+    fn inject_allocator_crate(&mut self, krate: &ast::Crate) {
+        // An allocator crate provides the allocation symbols itself, so it
+        // never needs another one injected.
+        if attr::contains_name(&krate.attrs, "allocator") {
+            return
+        }
+
+        // Only link an allocator if some crate calls into one, and don't
+        // inject one if an allocator was already requested through `extern
+        // crate` (here or upstream).
+        let mut needs_allocator = attr::contains_name(&krate.attrs,
+                                                      "needs_allocator");
+        let mut found_required_allocator = false;
+        let mut allocators = Vec::new();
+        self.sess.cstore.iter_crate_data(|cnum, data| {
+            needs_allocator = needs_allocator || data.needs_allocator();
+            if data.is_allocator() {
+                allocators.push(cnum);
+                found_required_allocator = found_required_allocator ||
+                                           data.explicitly_linked.get();
+            }
+        });
+        for &cnum in &allocators {
+            self.inject_allocator_dependency(cnum);
+        }
+        if !needs_allocator || found_required_allocator { return }
+
+        // Rlibs are just object files, only final artifacts need an allocator.
+        let mut need_lib_alloc = false;
+        let mut need_exe_alloc = false;
+        for ct in &*self.sess.crate_types.borrow() {
+            match *ct {
+                config::CrateTypeExecutable => need_exe_alloc = true,
+                config::CrateTypeDylib |
+                config::CrateTypeStaticlib => need_lib_alloc = true,
+                config::CrateTypeRlib => {}
+            }
+        }
+        if !need_lib_alloc && !need_exe_alloc { return }
+
+        // Libraries are likely to be embedded in a non-Rust program, so they
+        // default to the system allocator. A dylib built with `-C
+        // prefer-dynamic` is however a Rust dylib meant to be used from Rust,
+        // so it gets the same allocator as executables.
+        let name = if need_lib_alloc && !self.sess.opts.cg.prefer_dynamic {
+            self.sess.target.target.options.lib_allocation_crate.clone()
+        } else {
+            self.sess.target.target.options.exe_allocation_crate.clone()
+        };
+        let (cnum, data, _) = self.resolve_crate(&None, &name, &name, None,
+                                                 codemap::DUMMY_SP,
+                                                 PathKind::Crate, false);
+        if !data.is_allocator() {
+            self.sess.err(&format!("the allocator crate `{}` is not tagged \
+                                    with #![allocator]", data.name()));
+        }
+        self.sess.injected_allocator.set(Some(cnum));
+        self.inject_allocator_dependency(cnum);
+    }
+
+    fn inject_allocator_dependency(&self, allocator: ast::CrateNum) {
+        // An allocator may not itself depend on the crates it serves, or the
+        // dependency injected below would be circular.
+        validate(self, allocator, allocator);
+
+        // Crates tagged `#![needs_allocator]` refer to the allocator's symbols
+        // without depending on it, so record a dependency on it to get it
+        // ordered after them on the linker command line.
+        self.sess.cstore.iter_crate_data(|cnum, data| {
+            if !data.needs_allocator() { return }
+            debug!("injecting a dependency from {} to {}", cnum, allocator);
+            let mut cnum_map = data.cnum_map.borrow_mut();
+            if cnum_map.values().any(|&dep| dep == allocator) { return }
+            let remote_cnum = cnum_map.len() + 1;
+            cnum_map.insert(remote_cnum as ast::CrateNum, allocator);
+        });
+
+        fn validate(me: &CrateReader, krate: ast::CrateNum,
+                    allocator: ast::CrateNum) {
+            let data = me.sess.cstore.get_crate_data(krate);
+            if data.needs_allocator() {
+                let alloc = me.sess.cstore.get_crate_data(allocator);
+                me.sess.err(&format!("the allocator crate `{}` cannot depend \
+                                      on a crate that needs an allocator, but \
+                                      it depends on `{}`", alloc.name(),
+                                     data.name()));
+            }
+            for (_, &dep) in &*data.cnum_map.borrow() {
+                validate(me, dep, allocator);
+            }
+        }
+    }
+
     fn extract_crate_info(&self, i: &ast::Item) -> Option<CrateInfo> {
         match i.node {
             ast::ItemExternCrate(ref path_opt) => {
@@ -219,7 +314,8 @@ impl<'a> CrateReader<'a> {
                                                               &info.name,
                                                               None,
                                                               i.span,
-                                                              PathKind::Crate);
+                                                              PathKind::Crate,
+                                                              true);
                         self.sess.cstore.add_extern_mod_stmt_cnum(info.id, cnum);
                     }
                     None => ()
@@ -362,7 +458,8 @@ impl<'a> CrateReader<'a> {
                       ident: &str,
                       name: &str,
                       span: Span,
-                      lib: loader::Library)
+                      lib: loader::Library,
+                      explicitly_linked: bool)
                       -> (ast::CrateNum, Rc<cstore::crate_metadata>,
                           cstore::CrateSource) {
         // Claim this crate number and cache it
@@ -390,10 +487,11 @@ impl<'a> CrateReader<'a> {
         let cmeta = Rc::new( cstore::crate_metadata {
             name: name.to_string(),
             data: metadata,
-            cnum_map: cnum_map,
+            cnum_map: RefCell::new(cnum_map),
             cnum: cnum,
             codemap_import_info: codemap_import_info,
             span: span,
+            explicitly_linked: Cell::new(explicitly_linked),
         });
 
         let source = cstore::CrateSource {
@@ -413,7 +511,8 @@ impl<'a> CrateReader<'a> {
                      name: &str,
                      hash: Option<&Svh>,
                      span: Span,
-                     kind: PathKind)
+                     kind: PathKind,
+                     explicitly_linked: bool)
                          -> (ast::CrateNum, Rc<cstore::crate_metadata>,
                              cstore::CrateSource) {
         match self.existing_match(name, hash, kind) {
@@ -431,14 +530,21 @@ impl<'a> CrateReader<'a> {
                     rejected_via_hash: vec!(),
                     rejected_via_triple: vec!(),
                     rejected_via_kind: vec!(),
+                    rejected_via_version: vec!(),
+                    rejected_via_metadata: vec!(),
                     should_match_name: true,
                 };
                 let library = load_ctxt.load_library_crate();
-                self.register_crate(root, ident, name, span, library)
+                self.register_crate(root, ident, name, span, library,
+                                    explicitly_linked)
+            }
+            Some(cnum) => {
+                let data = self.sess.cstore.get_crate_data(cnum);
+                if explicitly_linked {
+                    data.explicitly_linked.set(true);
+                }
+                (cnum, data, self.sess.cstore.get_used_crate_source(cnum).unwrap())
             }
-            Some(cnum) => (cnum,
-                           self.sess.cstore.get_crate_data(cnum),
-                           self.sess.cstore.get_used_crate_source(cnum).unwrap())
         }
     }
 
@@ -457,7 +563,8 @@ impl<'a> CrateReader<'a> {
                                                    &dep.name,
                                                    Some(&dep.hash),
                                                    span,
-                                                   PathKind::Dependency);
+                                                   PathKind::Dependency,
+                                                   dep.explicitly_linked);
             (dep.cnum, local_cnum)
         }).collect()
     }
@@ -482,6 +589,8 @@ impl<'a> CrateReader<'a> {
             rejected_via_hash: vec!(),
             rejected_via_triple: vec!(),
             rejected_via_kind: vec!(),
+            rejected_via_version: vec!(),
+            rejected_via_metadata: vec!(),
             should_match_name: true,
         };
         let library = match load_ctxt.maybe_load_library_crate() {
@@ -507,7 +616,7 @@ impl<'a> CrateReader<'a> {
         let metadata = if register {
             // Register crate now to avoid double-reading metadata
             let (_, cmd, _) = self.register_crate(&None, &info.ident,
-                                &info.name, span, library);
+                                &info.name, span, library, false);
             PMDSource::Registered(cmd)
         } else {
             // Not registering the crate; just hold on to the metadata
@@ -372,11 +372,25 @@ pub fn get_reachable_extern_fns(cstore: &cstore::CStore, cnum: ast::CrateNum)
     decoder::get_reachable_extern_fns(&*cdata)
 }
 
+/// The names of the `#[no_mangle]` and `#[export_name]` symbols the crate
+/// `cnum` defines.
+pub fn get_unmangled_symbols(cstore: &cstore::CStore, cnum: ast::CrateNum) -> Vec<String> {
+    let cdata = cstore.get_crate_data(cnum);
+    decoder::get_unmangled_symbols(&*cdata)
+}
+
 pub fn is_typedef(cstore: &cstore::CStore, did: ast::DefId) -> bool {
     let cdata = cstore.get_crate_data(did.krate);
     decoder::is_typedef(&*cdata, did.node)
 }
 
+pub fn get_item_visibility(cstore: &cstore::CStore,
+                           def: ast::DefId)
+                           -> ast::Visibility {
+    let cdata = cstore.get_crate_data(def.krate);
+    decoder::get_item_visibility(&*cdata, def.node)
+}
+
 pub fn get_stability(cstore: &cstore::CStore,
                      def: ast::DefId)
                      -> Option<attr::Stability> {
@@ -384,6 +398,13 @@ pub fn get_stability(cstore: &cstore::CStore,
     decoder::get_stability(&*cdata, def.node)
 }
 
+pub fn get_deprecation(cstore: &cstore::CStore,
+                       def: ast::DefId)
+                       -> Option<attr::Deprecation> {
+    let cdata = cstore.get_crate_data(def.krate);
+    decoder::get_deprecation(&*cdata, def.node)
+}
+
 pub fn is_staged_api(cstore: &cstore::CStore, def: ast::DefId) -> bool {
     let cdata = cstore.get_crate_data(def.krate);
     let attrs = decoder::get_crate_attributes(cdata.data());
@@ -23,11 +23,13 @@ use metadata::loader;
 use session::search_paths::PathKind;
 use util::nodemap::{FnvHashMap, NodeMap};
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 use std::path::PathBuf;
+use std::str;
 use flate::Bytes;
 use syntax::ast;
+use syntax::attr;
 use syntax::codemap;
 use syntax::parse::token::IdentInterner;
 
@@ -56,10 +58,13 @@ pub struct ImportedFileMap {
 pub struct crate_metadata {
     pub name: String,
     pub data: MetadataBlob,
-    pub cnum_map: cnum_map,
+    pub cnum_map: RefCell<cnum_map>,
     pub cnum: ast::CrateNum,
     pub codemap_import_info: Vec<ImportedFileMap>,
     pub span: codemap::Span,
+    /// Whether this crate was reached through `extern crate` items (in this
+    /// crate or upstream), as opposed to only being injected by the compiler.
+    pub explicitly_linked: Cell<bool>,
 }
 
 #[derive(Copy, Debug, PartialEq, Clone)]
@@ -179,7 +184,7 @@ impl CStore {
                  ordering: &mut Vec<ast::CrateNum>) {
             if ordering.contains(&cnum) { return }
             let meta = cstore.get_crate_data(cnum);
-            for (_, &dep) in &meta.cnum_map {
+            for (_, &dep) in &*meta.cnum_map.borrow() {
                 visit(cstore, dep, ordering);
             }
             ordering.push(cnum);
@@ -238,10 +243,49 @@ impl crate_metadata {
     pub fn data<'a>(&'a self) -> &'a [u8] { self.data.as_slice() }
     pub fn name(&self) -> String { decoder::get_crate_name(self.data()) }
     pub fn hash(&self) -> Svh { decoder::get_crate_hash(self.data()) }
+
+    /// Whether this crate is tagged `#![allocator]`, i.e. it defines the
+    /// `__rust_allocate` family of symbols.
+    pub fn is_allocator(&self) -> bool {
+        let attrs = decoder::get_crate_attributes(self.data());
+        attr::contains_name(&attrs, "allocator")
+    }
+
+    /// Whether this crate is tagged `#![needs_allocator]`, i.e. it calls the
+    /// `__rust_allocate` family of symbols and one allocator crate must be
+    /// linked into the final artifact.
+    pub fn needs_allocator(&self) -> bool {
+        let attrs = decoder::get_crate_attributes(self.data());
+        attr::contains_name(&attrs, "needs_allocator")
+    }
 }
 
 impl MetadataBlob {
+    /// The rbml of this crate's metadata, without the header written by
+    /// `encoder::encode_metadata`.
     pub fn as_slice<'a>(&'a self) -> &'a [u8] {
+        let slice = self.contents();
+        match slice.first() {
+            Some(&vlen) if 1 + vlen as uint <= slice.len() => {
+                &slice[1 + vlen as uint..]
+            }
+            _ => &[] // corrupt metadata
+        }
+    }
+
+    /// The version of the compiler which produced this metadata, or `None`
+    /// if it is unreadable.
+    pub fn rustc_version<'a>(&'a self) -> Option<&'a str> {
+        let slice = self.contents();
+        match slice.first() {
+            Some(&vlen) if 1 + vlen as uint <= slice.len() => {
+                str::from_utf8(&slice[1..1 + vlen as uint]).ok()
+            }
+            _ => None
+        }
+    }
+
+    fn contents<'a>(&'a self) -> &'a [u8] {
         let slice = match *self {
             MetadataVec(ref vec) => &vec[..],
             MetadataArchive(ref ar) => ar.as_slice(),
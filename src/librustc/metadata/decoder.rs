@@ -169,6 +169,7 @@ fn item_visibility(item: rbml::Doc) -> ast::Visibility {
         Some(visibility_doc) => {
             match reader::doc_as_u8(visibility_doc) as char {
                 'y' => ast::Public,
+                'c' => ast::PubCrate,
                 'i' => ast::Inherited,
                 _ => panic!("unknown visibility character")
             }
@@ -437,6 +438,10 @@ pub fn get_type<'tcx>(cdata: Cmd, id: ast::NodeId, tcx: &ty::ctxt<'tcx>)
     }
 }
 
+pub fn get_item_visibility(cdata: Cmd, id: ast::NodeId) -> ast::Visibility {
+    item_visibility(lookup_item(id, cdata.data()))
+}
+
 pub fn get_stability(cdata: Cmd, id: ast::NodeId) -> Option<attr::Stability> {
     let item = lookup_item(id, cdata.data());
     reader::maybe_get_doc(item, tag_items_data_item_stability).map(|doc| {
@@ -445,6 +450,14 @@ pub fn get_stability(cdata: Cmd, id: ast::NodeId) -> Option<attr::Stability> {
     })
 }
 
+pub fn get_deprecation(cdata: Cmd, id: ast::NodeId) -> Option<attr::Deprecation> {
+    let item = lookup_item(id, cdata.data());
+    reader::maybe_get_doc(item, tag_items_data_item_deprecation).map(|doc| {
+        let mut decoder = reader::Decoder::new(doc);
+        Decodable::decode(&mut decoder).unwrap()
+    })
+}
+
 pub fn get_repr_attrs(cdata: Cmd, id: ast::NodeId) -> Vec<attr::ReprAttr> {
     let item = lookup_item(id, cdata.data());
     match reader::maybe_get_doc(item, tag_items_data_item_repr).map(|doc| {
@@ -1187,6 +1200,7 @@ pub struct CrateDep {
     pub cnum: ast::CrateNum,
     pub name: String,
     pub hash: Svh,
+    pub explicitly_linked: bool,
 }
 
 pub fn get_crate_deps(data: &[u8]) -> Vec<CrateDep> {
@@ -1201,10 +1215,13 @@ pub fn get_crate_deps(data: &[u8]) -> Vec<CrateDep> {
     reader::tagged_docs(depsdoc, tag_crate_dep, |depdoc| {
         let name = docstr(depdoc, tag_crate_dep_crate_name);
         let hash = Svh::new(&docstr(depdoc, tag_crate_dep_hash));
+        let doc = reader::get_doc(depdoc, tag_crate_dep_explicitly_linked);
+        let explicitly_linked = reader::doc_as_u8(doc) != 0;
         deps.push(CrateDep {
             cnum: crate_num,
             name: name,
             hash: hash,
+            explicitly_linked: explicitly_linked,
         });
         crate_num += 1;
         true
@@ -1268,7 +1285,7 @@ pub fn translate_def_id(cdata: Cmd, did: ast::DefId) -> ast::DefId {
         return ast::DefId { krate: cdata.cnum, node: did.node };
     }
 
-    match cdata.cnum_map.get(&did.krate) {
+    match cdata.cnum_map.borrow().get(&did.krate) {
         Some(&n) => {
             ast::DefId {
                 krate: n,
@@ -1387,7 +1404,7 @@ pub fn get_dylib_dependency_formats(cdata: Cmd)
         let cnum = spec.split(':').nth(0).unwrap();
         let link = spec.split(':').nth(1).unwrap();
         let cnum: ast::CrateNum = cnum.parse().unwrap();
-        let cnum = match cdata.cnum_map.get(&cnum) {
+        let cnum = match cdata.cnum_map.borrow().get(&cnum) {
             Some(&n) => n,
             None => panic!("didn't find a crate in the cnum_map")
         };
@@ -1443,6 +1460,17 @@ pub fn get_reachable_extern_fns(cdata: Cmd) -> Vec<ast::DefId> {
     return ret;
 }
 
+pub fn get_unmangled_symbols(cdata: Cmd) -> Vec<String> {
+    let mut ret = Vec::new();
+    let symbols = reader::get_doc(rbml::Doc::new(cdata.data()),
+                                  tag_unmangled_symbols);
+    reader::tagged_docs(symbols, tag_unmangled_symbol, |doc| {
+        ret.push(doc.as_str_slice().to_string());
+        true
+    });
+    ret
+}
+
 pub fn is_typedef(cdata: Cmd, id: ast::NodeId) -> bool {
     let item_doc = lookup_item(id, cdata.data());
     match item_family(item_doc) {
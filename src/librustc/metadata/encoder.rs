@@ -24,6 +24,7 @@ use metadata::tyencode;
 use middle::def;
 use middle::ty::{lookup_item_type};
 use middle::ty::{self, Ty};
+use middle::reachable;
 use middle::stability;
 use util::nodemap::{FnvHashMap, NodeMap, NodeSet};
 
@@ -311,7 +312,9 @@ fn encode_enum_variant_info(ecx: &EncodeContext,
         encode_repr_attrs(rbml_w, ecx, &variant.node.attrs);
 
         let stab = stability::lookup(ecx.tcx, ast_util::local_def(variant.node.id));
+        let depr = stability::lookup_deprecation(ecx.tcx, ast_util::local_def(variant.node.id));
         encode_stability(rbml_w, stab);
+        encode_deprecation(rbml_w, depr);
 
         match variant.node.kind {
             ast::TupleVariantKind(_) => {},
@@ -554,7 +557,9 @@ fn encode_info_for_mod(ecx: &EncodeContext,
     encode_visibility(rbml_w, vis);
 
     let stab = stability::lookup(ecx.tcx, ast_util::local_def(id));
+    let depr = stability::lookup_deprecation(ecx.tcx, ast_util::local_def(id));
     encode_stability(rbml_w, stab);
+    encode_deprecation(rbml_w, depr);
 
     // Encode the reexports of this module, if this module is public.
     if vis == ast::Public {
@@ -570,13 +575,15 @@ fn encode_struct_field_family(rbml_w: &mut Encoder,
                               visibility: ast::Visibility) {
     encode_family(rbml_w, match visibility {
         ast::Public => 'g',
-        ast::Inherited => 'N'
+        // Crate-visible fields are private to other crates.
+        ast::PubCrate | ast::Inherited => 'N'
     });
 }
 
 fn encode_visibility(rbml_w: &mut Encoder, visibility: ast::Visibility) {
     let ch = match visibility {
         ast::Public => 'y',
+        ast::PubCrate => 'c',
         ast::Inherited => 'i',
     };
     rbml_w.wr_tagged_u8(tag_items_data_item_visibility, ch as u8);
@@ -658,7 +665,9 @@ fn encode_info_for_struct(ecx: &EncodeContext,
         encode_def_id(rbml_w, local_def(id));
 
         let stab = stability::lookup(ecx.tcx, field.id);
+        let depr = stability::lookup_deprecation(ecx.tcx, field.id);
         encode_stability(rbml_w, stab);
+        encode_deprecation(rbml_w, depr);
 
         rbml_w.end_tag();
     }
@@ -670,7 +679,8 @@ fn encode_info_for_struct_ctor(ecx: &EncodeContext,
                                name: ast::Ident,
                                ctor_id: NodeId,
                                index: &mut Vec<entry<i64>>,
-                               struct_id: NodeId) {
+                               struct_id: NodeId,
+                               vis: ast::Visibility) {
     index.push(entry {
         val: ctor_id as i64,
         pos: rbml_w.mark_stable_position(),
@@ -689,7 +699,13 @@ fn encode_info_for_struct_ctor(ecx: &EncodeContext,
     }
 
     let stab = stability::lookup(ecx.tcx, ast_util::local_def(ctor_id));
+    let depr = stability::lookup_deprecation(ecx.tcx, ast_util::local_def(ctor_id));
     encode_stability(rbml_w, stab);
+    encode_deprecation(rbml_w, depr);
+
+    // the constructor is exactly as visible as the struct itself; without this
+    // the ctor of a private tuple struct would be decoded as public
+    encode_visibility(rbml_w, vis);
 
     // indicate that this is a tuple struct ctor, because downstream users will normally want
     // the tuple struct definition, but without this there is no way for them to tell that
@@ -820,7 +836,9 @@ fn encode_info_for_method<'a, 'tcx>(ecx: &EncodeContext<'a, 'tcx>,
     encode_item_sort(rbml_w, 'r');
 
     let stab = stability::lookup(ecx.tcx, m.def_id);
+    let depr = stability::lookup_deprecation(ecx.tcx, m.def_id);
     encode_stability(rbml_w, stab);
+    encode_deprecation(rbml_w, depr);
 
     // The type for methods gets encoded twice, which is unfortunate.
     encode_bounds_and_type_for_item(rbml_w, ecx, m.def_id.local_id());
@@ -868,7 +886,9 @@ fn encode_info_for_associated_type(ecx: &EncodeContext,
     encode_bounds_and_type_for_item(rbml_w, ecx, associated_type.def_id.local_id());
 
     let stab = stability::lookup(ecx.tcx, associated_type.def_id);
+    let depr = stability::lookup_deprecation(ecx.tcx, associated_type.def_id);
     encode_stability(rbml_w, stab);
+    encode_deprecation(rbml_w, depr);
 
     let elem = ast_map::PathName(associated_type.name);
     encode_path(rbml_w, impl_path.chain(Some(elem).into_iter()));
@@ -961,6 +981,14 @@ fn encode_stability(rbml_w: &mut Encoder, stab_opt: Option<attr::Stability>) {
     });
 }
 
+fn encode_deprecation(rbml_w: &mut Encoder, depr_opt: Option<attr::Deprecation>) {
+    depr_opt.map(|depr| {
+        rbml_w.start_tag(tag_items_data_item_deprecation);
+        depr.encode(rbml_w).unwrap();
+        rbml_w.end_tag();
+    });
+}
+
 fn encode_info_for_item(ecx: &EncodeContext,
                         rbml_w: &mut Encoder,
                         item: &ast::Item,
@@ -982,6 +1010,7 @@ fn encode_info_for_item(ecx: &EncodeContext,
 
     let def_id = local_def(item.id);
     let stab = stability::lookup(tcx, ast_util::local_def(item.id));
+    let depr = stability::lookup_deprecation(tcx, ast_util::local_def(item.id));
 
     match item.node {
       ast::ItemStatic(_, m, _) => {
@@ -999,6 +1028,7 @@ fn encode_info_for_item(ecx: &EncodeContext,
         encode_path(rbml_w, path);
         encode_visibility(rbml_w, vis);
         encode_stability(rbml_w, stab);
+        encode_deprecation(rbml_w, depr);
         encode_attributes(rbml_w, &item.attrs);
         rbml_w.end_tag();
       }
@@ -1014,6 +1044,7 @@ fn encode_info_for_item(ecx: &EncodeContext,
         encode_inlined_item(ecx, rbml_w, IIItemRef(item));
         encode_visibility(rbml_w, vis);
         encode_stability(rbml_w, stab);
+        encode_deprecation(rbml_w, depr);
         rbml_w.end_tag();
       }
       ast::ItemFn(ref decl, _, _, ref generics, _) => {
@@ -1034,6 +1065,7 @@ fn encode_info_for_item(ecx: &EncodeContext,
         }
         encode_visibility(rbml_w, vis);
         encode_stability(rbml_w, stab);
+        encode_deprecation(rbml_w, depr);
         encode_method_argument_names(rbml_w, &**decl);
         rbml_w.end_tag();
       }
@@ -1063,6 +1095,7 @@ fn encode_info_for_item(ecx: &EncodeContext,
         }
         encode_visibility(rbml_w, vis);
         encode_stability(rbml_w, stab);
+        encode_deprecation(rbml_w, depr);
         rbml_w.end_tag();
       }
       ast::ItemTy(..) => {
@@ -1075,6 +1108,7 @@ fn encode_info_for_item(ecx: &EncodeContext,
         encode_path(rbml_w, path);
         encode_visibility(rbml_w, vis);
         encode_stability(rbml_w, stab);
+        encode_deprecation(rbml_w, depr);
         rbml_w.end_tag();
       }
       ast::ItemEnum(ref enum_definition, _) => {
@@ -1099,6 +1133,7 @@ fn encode_info_for_item(ecx: &EncodeContext,
 
         encode_visibility(rbml_w, vis);
         encode_stability(rbml_w, stab);
+        encode_deprecation(rbml_w, depr);
         rbml_w.end_tag();
 
         encode_enum_variant_info(ecx,
@@ -1133,6 +1168,7 @@ fn encode_info_for_item(ecx: &EncodeContext,
         encode_attributes(rbml_w, &item.attrs);
         encode_path(rbml_w, path.clone());
         encode_stability(rbml_w, stab);
+        encode_deprecation(rbml_w, depr);
         encode_visibility(rbml_w, vis);
         encode_repr_attrs(rbml_w, ecx, &item.attrs);
 
@@ -1154,7 +1190,7 @@ fn encode_info_for_item(ecx: &EncodeContext,
         match struct_def.ctor_id {
             Some(ctor_id) => {
                 encode_info_for_struct_ctor(ecx, rbml_w, item.ident,
-                                            ctor_id, index, def_id.node);
+                                            ctor_id, index, def_id.node, vis);
             }
             None => {}
         }
@@ -1213,6 +1249,7 @@ fn encode_info_for_item(ecx: &EncodeContext,
         }
         encode_path(rbml_w, path.clone());
         encode_stability(rbml_w, stab);
+        encode_deprecation(rbml_w, depr);
         rbml_w.end_tag();
 
         // Iterate down the trait items, emitting them. We rely on the
@@ -1273,6 +1310,7 @@ fn encode_info_for_item(ecx: &EncodeContext,
         encode_attributes(rbml_w, &item.attrs);
         encode_visibility(rbml_w, vis);
         encode_stability(rbml_w, stab);
+        encode_deprecation(rbml_w, depr);
         for &method_def_id in &*ty::trait_item_def_ids(tcx, def_id) {
             rbml_w.start_tag(tag_item_trait_item);
             match method_def_id {
@@ -1315,7 +1353,9 @@ fn encode_info_for_item(ecx: &EncodeContext,
             encode_parent_item(rbml_w, def_id);
 
             let stab = stability::lookup(tcx, item_def_id.def_id());
+            let depr = stability::lookup_deprecation(tcx, item_def_id.def_id());
             encode_stability(rbml_w, stab);
+            encode_deprecation(rbml_w, depr);
 
             let trait_item_type =
                 ty::impl_or_trait_item(tcx, item_def_id.def_id());
@@ -1422,7 +1462,9 @@ fn encode_info_for_foreign_item(ecx: &EncodeContext,
         }
         encode_attributes(rbml_w, &*nitem.attrs);
         let stab = stability::lookup(ecx.tcx, ast_util::local_def(nitem.id));
+        let depr = stability::lookup_deprecation(ecx.tcx, ast_util::local_def(nitem.id));
         encode_stability(rbml_w, stab);
+        encode_deprecation(rbml_w, depr);
         encode_symbol(ecx, rbml_w, nitem.id);
         encode_method_argument_names(rbml_w, &*fndecl);
       }
@@ -1435,7 +1477,9 @@ fn encode_info_for_foreign_item(ecx: &EncodeContext,
         encode_bounds_and_type_for_item(rbml_w, ecx, nitem.id);
         encode_attributes(rbml_w, &*nitem.attrs);
         let stab = stability::lookup(ecx.tcx, ast_util::local_def(nitem.id));
+        let depr = stability::lookup_deprecation(ecx.tcx, ast_util::local_def(nitem.id));
         encode_stability(rbml_w, stab);
+        encode_deprecation(rbml_w, depr);
         encode_symbol(ecx, rbml_w, nitem.id);
         encode_name(rbml_w, nitem.ident.name);
       }
@@ -1669,6 +1713,7 @@ fn encode_crate_deps(rbml_w: &mut Encoder, cstore: &cstore::CStore) {
                 cnum: key,
                 name: decoder::get_crate_name(val.data()),
                 hash: decoder::get_crate_hash(val.data()),
+                explicitly_linked: val.explicitly_linked.get(),
             };
             deps.push(dep);
         });
@@ -1883,11 +1928,14 @@ fn encode_misc_info(ecx: &EncodeContext,
 fn encode_reachable_extern_fns(ecx: &EncodeContext, rbml_w: &mut Encoder) {
     rbml_w.start_tag(tag_reachable_extern_fns);
 
-    for id in ecx.reachable {
-        if let Some(ast_map::NodeItem(i)) = ecx.tcx.map.find(*id) {
+    // Sort so that the output doesn't depend on the set's iteration order.
+    let mut reachable: Vec<_> = ecx.reachable.iter().cloned().collect();
+    reachable.sort();
+    for id in reachable {
+        if let Some(ast_map::NodeItem(i)) = ecx.tcx.map.find(id) {
             if let ast::ItemFn(_, _, abi, ref generics, _) = i.node {
                 if abi != abi::Rust && !generics.is_type_parameterized() {
-                    rbml_w.wr_tagged_u32(tag_reachable_extern_fn_id, *id);
+                    rbml_w.wr_tagged_u32(tag_reachable_extern_fn_id, id);
                 }
             }
         }
@@ -1896,11 +1944,21 @@ fn encode_reachable_extern_fns(ecx: &EncodeContext, rbml_w: &mut Encoder) {
     rbml_w.end_tag();
 }
 
+fn encode_unmangled_symbols(ecx: &EncodeContext, rbml_w: &mut Encoder) {
+    rbml_w.start_tag(tag_unmangled_symbols);
+    for (name, _) in reachable::unmangled_symbols(ecx.tcx, ecx.reachable) {
+        rbml_w.wr_tagged_str(tag_unmangled_symbol, &name);
+    }
+    rbml_w.end_tag();
+}
+
 fn encode_crate_dep(rbml_w: &mut Encoder,
                     dep: decoder::CrateDep) {
     rbml_w.start_tag(tag_crate_dep);
     rbml_w.wr_tagged_str(tag_crate_dep_crate_name, &dep.name);
     rbml_w.wr_tagged_str(tag_crate_dep_hash, dep.hash.as_str());
+    rbml_w.wr_tagged_u8(tag_crate_dep_explicitly_linked,
+                        dep.explicitly_linked as u8);
     rbml_w.end_tag();
 }
 
@@ -1936,7 +1994,7 @@ fn encode_dylib_dependency_formats(rbml_w: &mut Encoder, ecx: &EncodeContext) {
 
 // NB: Increment this as you change the metadata encoding version.
 #[allow(non_upper_case_globals)]
-pub const metadata_encoding_version : &'static [u8] = &[b'r', b'u', b's', b't', 0, 0, 0, 2 ];
+pub const metadata_encoding_version : &'static [u8] = &[b'r', b'u', b's', b't', 0, 0, 0, 3 ];
 
 pub fn encode_metadata(parms: EncodeParams, krate: &ast::Crate) -> Vec<u8> {
     let mut wr = Cursor::new(Vec::new());
@@ -1967,6 +2025,19 @@ pub fn encode_metadata(parms: EncodeParams, krate: &ast::Crate) -> Vec<u8> {
     // the length of the metadata to the start of the metadata. Later on this
     // will allow us to slice the metadata to the precise length that we just
     // generated regardless of trailing bytes that end up in it.
+    //
+    // Right after the length comes the version of the compiler which encoded
+    // this crate, prefixed by its own one-byte length. It sits at a fixed
+    // offset so the loader can cheaply reject crates produced by another
+    // compiler before looking at any of the rbml, which may well have been
+    // laid out differently.
+    let version = rustc_version().as_bytes();
+    assert!(version.len() <= 255);
+    let mut header = vec![version.len() as u8];
+    header.extend(version.iter().cloned());
+    header.extend(v.into_iter());
+    let mut v = header;
+
     let len = v.len() as u32;
     v.insert(0, (len >>  0) as u8);
     v.insert(0, (len >>  8) as u8);
@@ -2084,6 +2155,7 @@ fn encode_metadata_inner(wr: &mut Cursor<Vec<u8>>,
     i = rbml_w.writer.seek(SeekFrom::Current(0)).unwrap();
     encode_misc_info(&ecx, krate, &mut rbml_w);
     encode_reachable_extern_fns(&ecx, &mut rbml_w);
+    encode_unmangled_symbols(&ecx, &mut rbml_w);
     stats.misc_bytes = rbml_w.writer.seek(SeekFrom::Current(0)).unwrap() - i;
 
     // Encode and index the items.
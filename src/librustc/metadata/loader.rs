@@ -219,6 +219,7 @@ use session::search_paths::PathKind;
 use llvm;
 use llvm::{False, ObjectFile, mk_section_iter};
 use llvm::archive_ro::ArchiveRO;
+use metadata::common::rustc_version;
 use metadata::cstore::{MetadataBlob, MetadataVec, MetadataArchive};
 use metadata::decoder;
 use metadata::encoder;
@@ -259,6 +260,8 @@ pub struct Context<'a> {
     pub rejected_via_hash: Vec<CrateMismatch>,
     pub rejected_via_triple: Vec<CrateMismatch>,
     pub rejected_via_kind: Vec<CrateMismatch>,
+    pub rejected_via_version: Vec<CrateMismatch>,
+    pub rejected_via_metadata: Vec<CrateMismatch>,
     pub should_match_name: bool,
 }
 
@@ -307,7 +310,12 @@ impl<'a> Context<'a> {
     }
 
     pub fn report_load_errs(&mut self) {
-        let message = if self.rejected_via_hash.len() > 0 {
+        let message = if self.rejected_via_version.len() > 0 {
+            format!("crate `{}` was compiled by {} but this is rustc {}; \
+                     recompile the dependency",
+                    self.ident, self.rejected_via_version[0].got,
+                    rustc_version())
+        } else if self.rejected_via_hash.len() > 0 {
             format!("found possibly newer version of crate `{}`",
                     self.ident)
         } else if self.rejected_via_triple.len() > 0 {
@@ -325,6 +333,14 @@ impl<'a> Context<'a> {
         };
         self.sess.span_err(self.span, &message[..]);
 
+        if self.rejected_via_version.len() > 0 {
+            let mismatches = self.rejected_via_version.iter();
+            for (i, &CrateMismatch{ ref path, ref got }) in mismatches.enumerate() {
+                self.sess.fileline_note(self.span,
+                    &format!("crate `{}` path #{}, compiled by {}: {}",
+                            self.ident, i+1, got, path.display()));
+            }
+        }
         if self.rejected_via_triple.len() > 0 {
             let mismatches = self.rejected_via_triple.iter();
             for (i, &CrateMismatch{ ref path, ref got }) in mismatches.enumerate() {
@@ -363,9 +379,58 @@ impl<'a> Context<'a> {
                                                  self.ident, i+1, path.display()));
             }
         }
+        if self.rejected_via_metadata.len() > 0 {
+            let mismatches = self.rejected_via_metadata.iter();
+            for (i, &CrateMismatch { ref got, .. }) in mismatches.enumerate() {
+                self.sess.fileline_note(self.span,
+                    &format!("crate `{}` path #{}, rejected: {}",
+                            self.ident, i+1, got));
+            }
+        }
+        self.note_search_paths();
+        if self.crate_name.contains("-") {
+            self.sess.fileline_help(self.span,
+                &format!("library file names use underscores in place of hyphens, \
+                          perhaps you meant `extern crate {}`?",
+                        self.crate_name.replace("-", "_")));
+        }
         self.sess.abort_if_errors();
     }
 
+    // Lists every directory the crate was searched for in, along with the
+    // file names which were tried in each of them.
+    fn note_search_paths(&self) {
+        if self.hash.is_none() && self.sess.opts.externs.contains_key(self.crate_name) {
+            // Only the `--extern` locations were considered.
+            return
+        }
+        let (ref prefix, ref suffix) = self.dylibname();
+        self.sess.fileline_note(self.span,
+            &format!("searched for `lib{}*.rlib` and `{}{}*{}` in:",
+                    self.crate_name, prefix, self.crate_name, suffix));
+        let sysroot = self.filesearch.get_lib_path();
+        self.filesearch.for_each_lib_search_path(|path, kind| {
+            let origin = if path == &*sysroot {
+                "sysroot"
+            } else if self.filesearch.search_paths.iter(self.filesearch.kind)
+                                                  .any(|(p, _)| p == path) {
+                match kind {
+                    PathKind::Native => "-L native",
+                    PathKind::Crate => "-L crate",
+                    PathKind::Dependency => "-L dependency",
+                    PathKind::Framework => "-L framework",
+                    PathKind::ExternFlag => "--extern",
+                    PathKind::All => "-L all",
+                }
+            } else {
+                "RUST_PATH"
+            };
+            self.sess.fileline_note(self.span,
+                &format!("    {} ({})", path.display(), origin));
+            FileDoesntMatch
+        });
+    }
+
     fn find_library_crate(&mut self) -> Option<Library> {
         // If an SVH is specified, then this is a transitive dependency that
         // must be loaded via -L plus some filtering.
@@ -529,6 +594,10 @@ impl<'a> Context<'a> {
             let metadata = match get_metadata_section(self.target.options.is_like_osx,
                                                       &lib) {
                 Ok(blob) => {
+                    if !self.version_matches(&blob, &lib) {
+                        info!("metadata version mismatch");
+                        continue
+                    }
                     if self.crate_matches(blob.as_slice(), &lib) {
                         blob
                     } else {
@@ -536,8 +605,12 @@ impl<'a> Context<'a> {
                         continue
                     }
                 }
-                Err(_) => {
-                    info!("no metadata found");
+                Err(err) => {
+                    info!("no metadata found: {}", err);
+                    self.rejected_via_metadata.push(CrateMismatch {
+                        path: lib.clone(),
+                        got: err,
+                    });
                     continue
                 }
             };
@@ -567,6 +640,32 @@ impl<'a> Context<'a> {
         return if error > 0 {None} else {ret}
     }
 
+    // Checks the compiler version stamped into the header of the metadata.
+    // This happens before anything else is decoded: the rbml written by a
+    // different compiler can't be trusted to mean the same thing.
+    fn version_matches(&mut self, blob: &MetadataBlob, libpath: &Path) -> bool {
+        match blob.rustc_version() {
+            Some(version) if version == rustc_version() => true,
+            Some(version) => {
+                info!("Rejecting via rustc version: expected {} got {}",
+                      rustc_version(), version);
+                self.rejected_via_version.push(CrateMismatch {
+                    path: libpath.to_path_buf(),
+                    got: format!("rustc {}", version),
+                });
+                false
+            }
+            None => {
+                info!("Rejecting via lack of rustc version");
+                self.rejected_via_metadata.push(CrateMismatch {
+                    path: libpath.to_path_buf(),
+                    got: format!("corrupt metadata: '{}'", libpath.display()),
+                });
+                false
+            }
+        }
+    }
+
     fn crate_matches(&mut self, crate_data: &[u8], libpath: &Path) -> bool {
         if self.should_match_name {
             match decoder::maybe_get_crate_name(crate_data) {
@@ -577,6 +676,10 @@ impl<'a> Context<'a> {
         let hash = match decoder::maybe_get_crate_hash(crate_data) {
             Some(hash) => hash, None => {
                 info!("Rejecting via lack of crate hash");
+                self.rejected_via_metadata.push(CrateMismatch {
+                    path: libpath.to_path_buf(),
+                    got: format!("corrupt metadata: '{}'", libpath.display()),
+                });
                 return false;
             }
         };
@@ -12,11 +12,13 @@
 
 use session::Session;
 use metadata::creader::CrateReader;
+use util::lev_distance::lev_distance;
 
-use std::collections::{HashSet, HashMap};
+use std::cmp;
+use std::collections::{HashMap, HashSet};
 use syntax::ast;
 use syntax::attr;
-use syntax::codemap::Span;
+use syntax::codemap::{Span, DUMMY_SP};
 use syntax::parse::token;
 use syntax::visit;
 use syntax::visit::Visitor;
@@ -27,6 +29,11 @@ struct MacroLoader<'a> {
     span_whitelist: HashSet<Span>,
     reader: CrateReader<'a>,
     macros: Vec<ast::MacroDef>,
+
+    // For each macro imported so far: the crate it came from, where it was
+    // imported, and whether that was the `extern crate std` injected by the
+    // compiler, whose macros may be shadowed like the rest of the prelude.
+    imported: HashMap<token::InternedString, (ast::Name, Span, bool)>,
 }
 
 impl<'a> MacroLoader<'a> {
@@ -36,6 +43,7 @@ impl<'a> MacroLoader<'a> {
             span_whitelist: HashSet::new(),
             reader: CrateReader::new(sess),
             macros: vec![],
+            imported: HashMap::new(),
         }
     }
 }
@@ -58,7 +66,18 @@ pub fn read_macro_defs(sess: &Session, krate: &ast::Crate) -> Vec<ast::MacroDef>
     loader.macros
 }
 
-pub type MacroSelection = HashMap<token::InternedString, Span>;
+/// The macros named by `#[macro_use(...)]` or `#[macro_reexport(...)]`, in
+/// the order they are written, so that errors about them come out in that
+/// order too.
+pub type MacroSelection = Vec<(token::InternedString, Span)>;
+
+fn selects(sel: &MacroSelection, name: &token::InternedString) -> bool {
+    sel.iter().any(|&(ref selected, _)| selected == name)
+}
+
+fn selection_span(sel: &MacroSelection, name: &token::InternedString) -> Option<Span> {
+    sel.iter().find(|&&(ref selected, _)| selected == name).map(|&(_, span)| span)
+}
 
 // note that macros aren't expanded yet, and therefore macros can't add macro imports.
 impl<'a, 'v> Visitor<'v> for MacroLoader<'a> {
@@ -73,8 +92,8 @@ impl<'a, 'v> Visitor<'v> for MacroLoader<'a> {
         }
 
         // Parse the attributes relating to macros.
-        let mut import = Some(HashMap::new());  // None => load all
-        let mut reexport = HashMap::new();
+        let mut import = Some(Vec::new());  // None => load all
+        let mut reexport = Vec::new();
 
         for attr in &item.attrs {
             let mut used = true;
@@ -97,7 +116,7 @@ impl<'a, 'v> Visitor<'v> for MacroLoader<'a> {
                     if let (Some(sel), Some(names)) = (import.as_mut(), names) {
                         for attr in names {
                             if let ast::MetaWord(ref name) = attr.node {
-                                sel.insert(name.clone(), attr.span);
+                                sel.push((name.clone(), attr.span));
                             } else {
                                 self.sess.span_err(attr.span, "bad macro import");
                             }
@@ -115,7 +134,7 @@ impl<'a, 'v> Visitor<'v> for MacroLoader<'a> {
 
                     for attr in names {
                         if let ast::MetaWord(ref name) = attr.node {
-                            reexport.insert(name.clone(), attr.span);
+                            reexport.push((name.clone(), attr.span));
                         } else {
                             self.sess.span_err(attr.span, "bad macro reexport");
                         }
@@ -154,6 +173,12 @@ impl<'a> MacroLoader<'a> {
             return;
         }
 
+        let krate = match vi.node {
+            ast::ItemExternCrate(Some(name)) => name,
+            _ => vi.ident.name,
+        };
+        let injected = vi.span == DUMMY_SP;
+
         let macros = self.reader.read_exported_macros(vi);
         let mut seen = HashSet::new();
 
@@ -163,26 +188,81 @@ impl<'a> MacroLoader<'a> {
 
             def.use_locally = match import.as_ref() {
                 None => true,
-                Some(sel) => sel.contains_key(&name),
+                Some(sel) => selects(sel, &name),
             };
-            def.export = reexport.contains_key(&name);
+            def.export = selects(&reexport, &name);
             def.allow_internal_unstable = attr::contains_name(&def.attrs,
                                                               "allow_internal_unstable");
+            if def.use_locally {
+                let span = import.as_ref().and_then(|sel| selection_span(sel, &name))
+                                 .unwrap_or(vi.span);
+                self.check_collision(&name, krate, span, injected);
+            }
             debug!("load_macros: loaded: {:?}", def);
             self.macros.push(def);
         }
 
         if let Some(sel) = import.as_ref() {
-            for (name, span) in sel.iter() {
-                if !seen.contains(name) {
-                    self.sess.span_err(*span, "imported macro not found");
-                }
+            self.check_selected(krate, sel, &seen, "imported");
+        }
+        self.check_selected(krate, &reexport, &seen, "reexported");
+    }
+
+    /// Reports a macro imported from two crates, rather than letting the
+    /// later import silently shadow the earlier one.
+    fn check_collision(&mut self,
+                       name: &token::InternedString,
+                       krate: ast::Name,
+                       span: Span,
+                       injected: bool) {
+        if let Some(&(first_krate, first_span, first_injected)) = self.imported.get(name) {
+            if !first_injected && !injected {
+                self.sess.span_err(span, &format!("macro `{}` is imported from both `{}` \
+                                                   and `{}`",
+                                                  name,
+                                                  token::get_name(first_krate),
+                                                  token::get_name(krate)));
+                self.sess.span_note(first_span, &format!("`{}` is first imported from `{}` \
+                                                          here",
+                                                         name,
+                                                         token::get_name(first_krate)));
             }
         }
+        self.imported.insert(name.clone(), (krate, span, injected));
+    }
+
+    /// Reports the names in `sel` that `krate` does not export, along with
+    /// the names it does.
+    fn check_selected(&self,
+                      krate: ast::Name,
+                      sel: &MacroSelection,
+                      exported: &HashSet<token::InternedString>,
+                      what: &str) {
+        let mut exported: Vec<_> = exported.iter().collect();
+        exported.sort();
+
+        for &(ref name, span) in sel {
+            if exported.contains(&name) {
+                continue;
+            }
+            self.sess.span_err(span, &format!("{} macro not found", what));
+
+            let closest = exported.iter()
+                                  .map(|&other| (lev_distance(name, other), other))
+                                  .filter(|&(dist, _)| dist <= cmp::max(name.len() / 3, 1))
+                                  .min_by(|&(dist, _)| dist);
+            if let Some((_, other)) = closest {
+                self.sess.span_help(span, &format!("did you mean `{}`?", other));
+            }
 
-        for (name, span) in reexport.iter() {
-            if !seen.contains(name) {
-                self.sess.span_err(*span, "reexported macro not found");
+            if exported.is_empty() {
+                self.sess.fileline_note(span, &format!("`{}` exports no macros",
+                                                       token::get_name(krate)));
+            } else {
+                let names: Vec<_> = exported.iter().map(|n| format!("`{}`", n)).collect();
+                self.sess.fileline_note(span, &format!("`{}` exports {}",
+                                                       token::get_name(krate),
+                                                       names.connect(", ")));
             }
         }
     }
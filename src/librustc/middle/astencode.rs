@@ -471,6 +471,7 @@ impl tr for def::Def {
           def::DefAssociatedTy(trait_did, did) =>
               def::DefAssociatedTy(trait_did.tr(dcx), did.tr(dcx)),
           def::DefPrimTy(p) => def::DefPrimTy(p),
+          def::DefErr => def::DefErr,
           def::DefTyParam(s, index, def_id, n) => def::DefTyParam(s, index, def_id.tr(dcx), n),
           def::DefUse(did) => def::DefUse(did.tr(dcx)),
           def::DefUpvar(nid1, nid2) => {
@@ -1026,6 +1027,12 @@ impl<'a, 'tcx> rbml_writer_helpers<'tcx> for Encoder<'a> {
                             |this| Ok(this.emit_auto_deref_ref(ecx, auto_deref_ref)))
                     })
                 }
+
+                ty::AdjustClosureFnPointer(fn_ty) => {
+                    this.emit_enum_variant("AdjustClosureFnPointer", 4, 1, |this| {
+                        this.emit_enum_variant_arg(0, |this| Ok(this.emit_ty(ecx, fn_ty)))
+                    })
+                }
             }
         });
     }
@@ -1655,6 +1662,13 @@ impl<'a, 'tcx> rbml_decoder_decoder_helpers<'tcx> for reader::Decoder<'a> {
 
                         ty::AdjustDerefRef(auto_deref_ref)
                     }
+                    4 => {
+                        let fn_ty: Ty<'tcx> =
+                            this.read_enum_variant_arg(0,
+                                |this| Ok(this.read_ty(dcx))).unwrap();
+
+                        ty::AdjustClosureFnPointer(fn_ty)
+                    }
                     _ => panic!("bad enum variant for ty::AutoAdjustment")
                 })
             })
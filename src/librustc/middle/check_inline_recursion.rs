@@ -0,0 +1,153 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// This compiler pass detects `#[inline(always)]` functions that call
+// themselves, either directly or through other `#[inline(always)]`
+// functions. Such a request can never be honoured, and LLVM reports it
+// with an unhelpful message long after the fact.
+
+use session::Session;
+use middle::def::{DefFn, DefMethod, DefMap};
+
+use syntax::ast;
+use syntax::{ast_util, ast_map};
+use syntax::attr::{self, InlineAttr};
+use syntax::codemap::Span;
+use syntax::parse::token;
+use syntax::visit::Visitor;
+use syntax::visit;
+
+struct CheckCrateVisitor<'a, 'ast: 'a> {
+    sess: &'a Session,
+    def_map: &'a DefMap,
+    ast_map: &'a ast_map::Map<'ast>
+}
+
+impl<'v, 'a, 'ast> Visitor<'v> for CheckCrateVisitor<'a, 'ast> {
+    fn visit_item(&mut self, i: &ast::Item) {
+        if let Some(body) = inline_always_body(self.ast_map, i.id) {
+            check_fn_recursion(self, i.id, i.span, body);
+        }
+        visit::walk_item(self, i)
+    }
+
+    fn visit_impl_item(&mut self, ii: &ast::ImplItem) {
+        if let Some(body) = inline_always_body(self.ast_map, ii.id) {
+            check_fn_recursion(self, ii.id, ii.span, body);
+        }
+        visit::walk_impl_item(self, ii)
+    }
+}
+
+pub fn check_crate<'ast>(sess: &Session,
+                         krate: &ast::Crate,
+                         def_map: &DefMap,
+                         ast_map: &ast_map::Map<'ast>) {
+    let errors = sess.err_count();
+    let mut visitor = CheckCrateVisitor {
+        sess: sess,
+        def_map: def_map,
+        ast_map: ast_map
+    };
+    visit::walk_crate(&mut visitor, krate);
+    sess.abort_if_new_errors(errors);
+}
+
+/// Returns the body of the function or method `id` if it is marked
+/// `#[inline(always)]`.
+fn inline_always_body<'ast>(ast_map: &ast_map::Map<'ast>, id: ast::NodeId)
+                            -> Option<&'ast ast::Block> {
+    let (attrs, body) = match ast_map.find(id) {
+        Some(ast_map::NodeItem(item)) => match item.node {
+            ast::ItemFn(_, _, _, _, ref body) => (&item.attrs, &**body),
+            _ => return None
+        },
+        Some(ast_map::NodeImplItem(impl_item)) => match impl_item.node {
+            ast::MethodImplItem(_, ref body) => (&impl_item.attrs, &**body),
+            _ => return None
+        },
+        _ => return None
+    };
+    match attr::find_inline_attr(None, attrs) {
+        InlineAttr::Always => Some(body),
+        _ => None
+    }
+}
+
+struct CheckFnRecursionVisitor<'a, 'ast: 'a> {
+    root_id: ast::NodeId,
+    root_span: Span,
+    sess: &'a Session,
+    ast_map: &'a ast_map::Map<'ast>,
+    def_map: &'a DefMap,
+    idstack: Vec<ast::NodeId>
+}
+
+// Make sure an `#[inline(always)]` function doesn't end up calling itself
+// through a chain of functions that all have to be inlined.
+fn check_fn_recursion<'a, 'ast>(v: &CheckCrateVisitor<'a, 'ast>,
+                                id: ast::NodeId,
+                                span: Span,
+                                body: &ast::Block) {
+    let mut visitor = CheckFnRecursionVisitor {
+        root_id: id,
+        root_span: span,
+        sess: v.sess,
+        ast_map: v.ast_map,
+        def_map: v.def_map,
+        idstack: vec![id]
+    };
+    visitor.visit_block(body);
+}
+
+impl<'a, 'ast> CheckFnRecursionVisitor<'a, 'ast> {
+    fn visit_callee(&mut self, id: ast::NodeId) {
+        if id == self.root_id {
+            // Every function on a cycle would find it; only report it for
+            // the first of them.
+            if self.idstack.iter().all(|&x| x >= self.root_id) {
+                let cycle = self.idstack.iter().chain(Some(id).iter()).map(|&x| {
+                    format!("`{}`", token::get_name(self.ast_map.get_path_elem(x).name()))
+                }).collect::<Vec<_>>().connect(" -> ");
+                span_err!(self.sess, self.root_span, E0332,
+                          "`#[inline(always)]` function is recursive: {}", cycle);
+            }
+            return;
+        }
+        if self.idstack.contains(&id) {
+            return;
+        }
+        if let Some(body) = inline_always_body(self.ast_map, id) {
+            self.idstack.push(id);
+            self.visit_block(body);
+            self.idstack.pop();
+        }
+    }
+}
+
+impl<'a, 'ast, 'v> Visitor<'v> for CheckFnRecursionVisitor<'a, 'ast> {
+    // Nested items are checked on their own.
+    fn visit_item(&mut self, _: &ast::Item) {}
+
+    fn visit_expr(&mut self, e: &ast::Expr) {
+        if let ast::ExprCall(ref callee, _) = e.node {
+            if let ast::ExprPath(..) = callee.node {
+                match self.def_map.borrow().get(&callee.id).map(|d| d.full_def()) {
+                    Some(DefFn(def_id, _)) |
+                    Some(DefMethod(def_id, _)) if ast_util::is_local(def_id) => {
+                        self.visit_callee(def_id.node)
+                    }
+                    _ => ()
+                }
+            }
+        }
+        visit::walk_expr(self, e);
+    }
+}
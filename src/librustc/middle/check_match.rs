@@ -12,7 +12,9 @@ pub use self::Constructor::*;
 use self::Usefulness::*;
 use self::WitnessPreference::*;
 
-use middle::const_eval::{compare_const_vals, const_bool, const_float, const_val};
+use lint;
+use middle::const_eval::{compare_const_vals, const_bool, const_float, const_int, const_uint};
+use middle::const_eval::const_val;
 use middle::const_eval::{eval_const_expr, eval_const_expr_partial};
 use middle::const_eval::{const_expr_to_pat, lookup_const_by_id};
 use middle::def::*;
@@ -21,10 +23,11 @@ use middle::expr_use_visitor::{JustWrite, LoanCause, MutateMode};
 use middle::expr_use_visitor::{WriteAndRead};
 use middle::expr_use_visitor as euv;
 use middle::mem_categorization::cmt;
+use middle::mem_categorization as mc;
 use middle::pat_util::*;
 use middle::ty::*;
 use middle::ty;
-use std::cmp::Ordering;
+use std::cmp::{self, Ordering};
 use std::fmt;
 use std::iter::{range_inclusive, AdditiveIterator, FromIterator, IntoIterator, repeat};
 use std::slice;
@@ -161,14 +164,13 @@ fn check_expr(cx: &mut MatchCheckCtxt, ex: &ast::Expr) {
         ast::ExprMatch(ref scrut, ref arms, source) => {
             for arm in arms {
                 // First, check legality of move bindings.
-                check_legality_of_move_bindings(cx,
-                                                arm.guard.is_some(),
-                                                &arm.pats);
+                check_legality_of_move_bindings(cx, &arm.pats);
 
                 // Second, if there is a guard on each arm, make sure it isn't
-                // assigning or borrowing anything mutably.
+                // assigning, borrowing anything mutably or moving out of the
+                // arm's by-move bindings.
                 match arm.guard {
-                    Some(ref guard) => check_for_mutation_in_guard(cx, &**guard),
+                    Some(ref guard) => check_for_mutation_in_guard(cx, &arm.pats, &**guard),
                     None => {}
                 }
             }
@@ -292,6 +294,7 @@ fn check_arms(cx: &MatchCheckCtxt,
               arms: &[(Vec<P<Pat>>, Option<&ast::Expr>)],
               source: ast::MatchSource) {
     let mut seen = Matrix(vec![]);
+    let mut seen_ranges = vec![];
     let mut printed_if_let_err = false;
     for &(ref pats, guard) in arms {
         for pat in pats {
@@ -335,7 +338,8 @@ fn check_arms(cx: &MatchCheckCtxt,
                         },
                     }
                 }
-                Useful => (),
+                Useful => check_for_overlapping_ranges(cx, pat, guard.is_some(),
+                                                       &mut seen_ranges),
                 UsefulWithWitness(_) => unreachable!()
             }
             if guard.is_none() {
@@ -347,6 +351,42 @@ fn check_arms(cx: &MatchCheckCtxt,
     }
 }
 
+/// Lints a literal or range pattern of integer or `char` type that overlaps
+/// with a previous one, where at least one of the two is a range. `seen`
+/// holds the encoded bounds, span and range-ness of the previous unguarded
+/// patterns.
+fn check_for_overlapping_ranges(cx: &MatchCheckCtxt, pat: &Pat, guarded: bool,
+                                seen: &mut Vec<(u64, u64, Span, bool)>) {
+    let raw = raw_pat(pat);
+    let (ctor, is_range) = match raw.node {
+        ast::PatLit(ref expr) => (ConstantValue(eval_const_expr(cx.tcx, &**expr)), false),
+        ast::PatRange(ref lo, ref hi) => {
+            (ConstantRange(eval_const_expr(cx.tcx, &**lo), eval_const_expr(cx.tcx, &**hi)),
+             true)
+        }
+        _ => return
+    };
+    let range = IntDomain::of(ty::pat_ty(cx.tcx, raw)).and_then(|domain| {
+        domain.constructor_range(&ctor)
+    });
+    let (lo, hi) = match range {
+        Some(range) => range,
+        None => return
+    };
+
+    let overlapped = seen.iter().find(|&&(seen_lo, seen_hi, _, seen_is_range)| {
+        (is_range || seen_is_range) && lo <= seen_hi && seen_lo <= hi
+    });
+    if let Some(&(_, _, seen_span, _)) = overlapped {
+        cx.tcx.sess.add_lint(lint::builtin::OVERLAPPING_PATTERNS, pat.id, pat.span,
+                             format!("this pattern overlaps with the pattern at {}",
+                                     cx.tcx.sess.codemap().span_to_string(seen_span)));
+    }
+    if !guarded {
+        seen.push((lo, hi, pat.span, is_range));
+    }
+}
+
 fn raw_pat<'a>(p: &'a Pat) -> &'a Pat {
     match p.node {
         ast::PatIdent(_, _, Some(ref s)) => raw_pat(&**s),
@@ -616,6 +656,171 @@ fn all_constructors(cx: &MatchCheckCtxt, left_ty: Ty,
     }
 }
 
+/// The values of an integer or `char` type that literal and range patterns
+/// can be checked against, encoded as offsets from the type's minimum value
+/// so that signed and unsigned types can be handled alike.
+#[derive(Copy)]
+struct IntDomain {
+    signed: bool,
+    /// The largest encoded value.
+    max: u64,
+    /// Whether this is `char`, whose surrogate range isn't made of values.
+    is_char: bool,
+}
+
+/// The surrogate code points, which are not valid `char`s.
+const CHAR_SURROGATES: (u64, u64) = (0xD800, 0xDFFF);
+
+impl IntDomain {
+    /// The domain of `ty`, if it has one. Pointer-sized integers have none,
+    /// since their range depends on the target.
+    fn of(ty: Ty) -> Option<IntDomain> {
+        let (signed, bits) = match ty.sty {
+            ty::ty_int(ast::TyI8) => (true, 8),
+            ty::ty_int(ast::TyI16) => (true, 16),
+            ty::ty_int(ast::TyI32) => (true, 32),
+            ty::ty_int(ast::TyI64) => (true, 64),
+            ty::ty_uint(ast::TyU8) => (false, 8),
+            ty::ty_uint(ast::TyU16) => (false, 16),
+            ty::ty_uint(ast::TyU32) => (false, 32),
+            ty::ty_uint(ast::TyU64) => (false, 64),
+            ty::ty_char => {
+                return Some(IntDomain { signed: false, max: 0x10FFFF, is_char: true });
+            }
+            _ => return None
+        };
+        let max = if bits == 64 { !0 } else { (1 << bits) - 1 };
+        Some(IntDomain { signed: signed, max: max, is_char: false })
+    }
+
+    fn bias(&self) -> u64 {
+        if self.signed { (self.max >> 1) + 1 } else { 0 }
+    }
+
+    fn encode(&self, value: &const_val) -> Option<u64> {
+        // As in `compare_int_or_const_vals`, signed values of an unsigned
+        // type come from unsuffixed literals and may have wrapped around.
+        let raw = match *value {
+            const_int(v) => v as u64,
+            const_uint(v) if !self.signed || (v as i64) >= 0 => v,
+            _ => return None
+        };
+        let encoded = raw.wrapping_add(self.bias());
+        if encoded <= self.max { Some(encoded) } else { None }
+    }
+
+    fn decode(&self, encoded: u64) -> const_val {
+        if self.signed {
+            const_int(encoded.wrapping_sub(self.bias()) as i64)
+        } else {
+            const_uint(encoded)
+        }
+    }
+
+    /// The encoded bounds of a literal or range constructor.
+    fn constructor_range(&self, ctor: &Constructor) -> Option<(u64, u64)> {
+        let (lo, hi) = match *ctor {
+            ConstantValue(ref value) => (value, value),
+            ConstantRange(ref lo, ref hi) => (lo, hi),
+            _ => return None
+        };
+        match (self.encode(lo), self.encode(hi)) {
+            (Some(lo), Some(hi)) if lo <= hi => Some((lo, hi)),
+            _ => None
+        }
+    }
+}
+
+/// The encoded ranges of values covered by the literal and range patterns
+/// heading `rows`.
+fn head_int_ranges(cx: &MatchCheckCtxt, rows: &[Vec<&Pat>],
+                   domain: &IntDomain) -> Vec<(u64, u64)> {
+    rows.iter().filter_map(|row| {
+        match raw_pat(row[0]).node {
+            ast::PatLit(ref expr) => {
+                let value = eval_const_expr(cx.tcx, &**expr);
+                domain.constructor_range(&ConstantValue(value))
+            }
+            ast::PatRange(ref lo, ref hi) => {
+                let lo = eval_const_expr(cx.tcx, &**lo);
+                let hi = eval_const_expr(cx.tcx, &**hi);
+                domain.constructor_range(&ConstantRange(lo, hi))
+            }
+            _ => None
+        }
+    }).collect()
+}
+
+/// Whether the literal and range patterns heading `rows` together match
+/// every value of `left_ty`, making a wildcard in this column redundant.
+fn covers_int_domain(cx: &MatchCheckCtxt, rows: &[Vec<&Pat>], left_ty: Ty) -> bool {
+    let domain = match IntDomain::of(left_ty) {
+        Some(domain) => domain,
+        None => return false
+    };
+    let mut ranges = head_int_ranges(cx, rows, &domain);
+    if domain.is_char {
+        ranges.push(CHAR_SURROGATES);
+    }
+    ranges.sort();
+
+    // The smallest value not known to be covered yet.
+    let mut next = 0;
+    for &(lo, hi) in &ranges {
+        if lo > next {
+            return false;
+        }
+        if hi == domain.max {
+            return true;
+        }
+        next = cmp::max(next, hi + 1);
+    }
+    false
+}
+
+/// Splits the values matched by `ctor` (or, without it, all the values of
+/// `left_ty`) at the bounds of the literal and range patterns heading `rows`,
+/// so that each of the resulting range constructors lies either entirely
+/// inside or entirely outside each of those patterns. Returns `None` if
+/// `left_ty` or `ctor` isn't made of integer or `char` values.
+fn split_int_constructors(cx: &MatchCheckCtxt, rows: &[Vec<&Pat>], left_ty: Ty,
+                          ctor: Option<&Constructor>) -> Option<Vec<Constructor>> {
+    let domain = match IntDomain::of(left_ty) {
+        Some(domain) => domain,
+        None => return None
+    };
+    let (lo, hi) = match ctor {
+        Some(ctor) => match domain.constructor_range(ctor) {
+            Some(range) => range,
+            None => return None
+        },
+        None => (0, domain.max)
+    };
+
+    let mut ranges = head_int_ranges(cx, rows, &domain);
+    if domain.is_char {
+        ranges.push(CHAR_SURROGATES);
+    }
+    let mut starts = vec![lo];
+    for &(r_lo, r_hi) in &ranges {
+        if r_lo > lo && r_lo <= hi {
+            starts.push(r_lo);
+        }
+        if r_hi >= lo && r_hi < hi {
+            starts.push(r_hi + 1);
+        }
+    }
+    starts.sort();
+    starts.dedup();
+
+    let ends = starts.iter().skip(1).map(|&start| start - 1).chain(Some(hi).into_iter());
+    Some(starts.iter().zip(ends).filter(|&(&start, _)| {
+        !domain.is_char || start < CHAR_SURROGATES.0 || start > CHAR_SURROGATES.1
+    }).map(|(&start, end)| {
+        ConstantRange(domain.decode(start), domain.decode(end))
+    }).collect())
+}
+
 // Algorithm from http://moscova.inria.fr/~maranget/papers/warn/index.html
 //
 // Whether a vector `v` of patterns is 'useful' in relation to a set of such
@@ -669,9 +874,16 @@ fn is_useful(cx: &MatchCheckCtxt,
 
     let constructors = pat_constructors(cx, v[0], left_ty, max_slice_length);
     if constructors.is_empty() {
-        match missing_constructor(cx, matrix, left_ty, max_slice_length) {
+        let missing = if covers_int_domain(cx, rows, left_ty) {
+            None
+        } else {
+            missing_constructor(cx, matrix, left_ty, max_slice_length)
+        };
+        match missing {
             None => {
-                all_constructors(cx, left_ty, max_slice_length).into_iter().map(|c| {
+                let all = split_int_constructors(cx, rows, left_ty, None)
+                    .unwrap_or_else(|| all_constructors(cx, left_ty, max_slice_length));
+                all.into_iter().map(|c| {
                     match is_useful_specialized(cx, matrix, v, c.clone(), left_ty, witness) {
                         UsefulWithWitness(pats) => UsefulWithWitness({
                             let arity = constructor_arity(cx, &c, left_ty);
@@ -712,7 +924,9 @@ fn is_useful(cx: &MatchCheckCtxt,
             }
         }
     } else {
-        constructors.into_iter().map(|c|
+        constructors.into_iter().flat_map(|c| {
+            split_int_constructors(cx, rows, left_ty, Some(&c)).unwrap_or(vec![c]).into_iter()
+        }).map(|c|
             is_useful_specialized(cx, matrix, v, c.clone(), left_ty, witness)
         ).find(|result| result != &NotUseful).unwrap_or(NotUseful)
     }
@@ -823,6 +1037,17 @@ pub fn constructor_arity(cx: &MatchCheckCtxt, ctor: &Constructor, ty: Ty) -> uin
     }
 }
 
+/// Like `compare_const_vals`, but also compares signed and unsigned integer
+/// values with each other: unsuffixed literals evaluate to signed values even
+/// in patterns of unsigned type, wrapping around above `i64::MAX`.
+fn compare_int_or_const_vals(a: &const_val, b: &const_val) -> Option<Ordering> {
+    match (a, b) {
+        (&const_int(a), &const_uint(b)) => Some((a as u64).cmp(&b)),
+        (&const_uint(_), &const_int(_)) => compare_int_or_const_vals(b, a).map(|o| o.reverse()),
+        _ => compare_const_vals(a, b)
+    }
+}
+
 fn range_covered_by_constructor(ctor: &Constructor,
                                 from: &const_val, to: &const_val) -> Option<bool> {
     let (c_from, c_to) = match *ctor {
@@ -831,8 +1056,8 @@ fn range_covered_by_constructor(ctor: &Constructor,
         Single                          => return Some(true),
         _                               => unreachable!()
     };
-    let cmp_from = compare_const_vals(c_from, from);
-    let cmp_to = compare_const_vals(c_to, to);
+    let cmp_from = compare_int_or_const_vals(c_from, from);
+    let cmp_to = compare_int_or_const_vals(c_to, to);
     match (cmp_from, cmp_to) {
         (Some(cmp_from), Some(cmp_to)) => {
             Some(cmp_from != Ordering::Less && cmp_to != Ordering::Greater)
@@ -1018,7 +1243,7 @@ fn check_local(cx: &mut MatchCheckCtxt, loc: &ast::Local) {
     });
 
     // Check legality of move bindings and `@` patterns.
-    check_legality_of_move_bindings(cx, false, slice::ref_slice(&loc.pat));
+    check_legality_of_move_bindings(cx, slice::ref_slice(&loc.pat));
     check_legality_of_bindings_in_at_patterns(cx, &*loc.pat);
 }
 
@@ -1042,7 +1267,7 @@ fn check_fn(cx: &mut MatchCheckCtxt,
                 pat_to_string(pat)
             );
         });
-        check_legality_of_move_bindings(cx, false, slice::ref_slice(&input.pat));
+        check_legality_of_move_bindings(cx, slice::ref_slice(&input.pat));
         check_legality_of_bindings_in_at_patterns(cx, &*input.pat);
     }
 }
@@ -1063,7 +1288,6 @@ fn is_refutable<A, F>(cx: &MatchCheckCtxt, pat: &Pat, refutable: F) -> Option<A>
 
 // Legality of move bindings checking
 fn check_legality_of_move_bindings(cx: &MatchCheckCtxt,
-                                   has_guard: bool,
                                    pats: &[P<Pat>]) {
     let tcx = cx.tcx;
     let def_map = &tcx.def_map;
@@ -1086,8 +1310,26 @@ fn check_legality_of_move_bindings(cx: &MatchCheckCtxt,
         // x @ Foo(..) is legal, but x @ Foo(y) isn't.
         if sub.map_or(false, |p| pat_contains_bindings(def_map, &*p)) {
             span_err!(cx.tcx.sess, p.span, E0007, "cannot bind by-move with sub-bindings");
-        } else if has_guard {
-            span_err!(cx.tcx.sess, p.span, E0008, "cannot bind by-move into a pattern guard");
+            let sub = sub.unwrap();
+            let mut sub_binding_span = None;
+            let mut all_shared = true;
+            ast_util::walk_pat(sub, |p| {
+                if pat_is_binding(def_map, p) {
+                    if sub_binding_span.is_none() {
+                        sub_binding_span = Some(p.span);
+                    }
+                    all_shared &= is_shared_sub_binding(cx, p);
+                }
+                true
+            });
+            span_note!(cx.tcx.sess, sub_binding_span.unwrap(), "sub-binding occurs here");
+            if all_shared {
+                if let ast::PatIdent(_, ref ident, _) = p.node {
+                    span_help!(cx.tcx.sess, p.span,
+                        "bind `{}` by reference instead: `ref {} @ ...`",
+                        token::get_ident(ident.node), token::get_ident(ident.node));
+                }
+            }
         } else if by_ref_span.is_some() {
             span_err!(cx.tcx.sess, p.span, E0009,
                 "cannot bind by-move and by-ref in the same pattern");
@@ -1122,12 +1364,28 @@ fn check_legality_of_move_bindings(cx: &MatchCheckCtxt,
     }
 }
 
-/// Ensures that a pattern guard doesn't borrow by mutable reference or
-/// assign.
+/// Ensures that a pattern guard doesn't borrow by mutable reference,
+/// assign, or move out of one of the arm's by-move bindings. Until the guard
+/// has succeeded, those bindings still refer to the matched value in place,
+/// which a later arm may go on to use.
 fn check_for_mutation_in_guard<'a, 'tcx>(cx: &'a MatchCheckCtxt<'a, 'tcx>,
+                                         pats: &[P<Pat>],
                                          guard: &ast::Expr) {
+    let mut by_move_bindings = FnvHashMap();
+    for pat in pats {
+        pat_bindings(&cx.tcx.def_map, &**pat, |bm, id, span, path| {
+            if let ast::BindByValue(_) = bm {
+                let pat_ty = ty::node_id_to_type(cx.tcx, id);
+                if ty::type_moves_by_default(&cx.param_env, span, pat_ty) {
+                    by_move_bindings.insert(id, path.node);
+                }
+            }
+        })
+    }
+
     let mut checker = MutationChecker {
         cx: cx,
+        by_move_bindings: by_move_bindings,
     };
     let mut visitor = ExprUseVisitor::new(&mut checker,
                                           &checker.cx.param_env);
@@ -1136,11 +1394,34 @@ fn check_for_mutation_in_guard<'a, 'tcx>(cx: &'a MatchCheckCtxt<'a, 'tcx>,
 
 struct MutationChecker<'a, 'tcx: 'a> {
     cx: &'a MatchCheckCtxt<'a, 'tcx>,
+    /// The by-move bindings of the guarded arm, and their names.
+    by_move_bindings: FnvHashMap<NodeId, ast::Ident>,
+}
+
+impl<'a, 'tcx> MutationChecker<'a, 'tcx> {
+    /// The by-move binding that `cmt` is owned by, if any.
+    fn owning_binding(&self, cmt: &cmt) -> Option<ast::Ident> {
+        match cmt.cat {
+            mc::cat_local(id) => self.by_move_bindings.get(&id).cloned(),
+            mc::cat_interior(ref base, _) |
+            mc::cat_downcast(ref base, _) |
+            mc::cat_deref(ref base, _, mc::Unique) => self.owning_binding(base),
+            _ => None
+        }
+    }
 }
 
 impl<'a, 'tcx> Delegate<'tcx> for MutationChecker<'a, 'tcx> {
     fn matched_pat(&mut self, _: &Pat, _: cmt, _: euv::MatchMode) {}
-    fn consume(&mut self, _: NodeId, _: Span, _: cmt, _: ConsumeMode) {}
+    fn consume(&mut self, _: NodeId, span: Span, cmt: cmt, mode: ConsumeMode) {
+        if let euv::Move(_) = mode {
+            if let Some(ident) = self.owning_binding(&cmt) {
+                span_err!(self.cx.tcx.sess, span, E0333,
+                          "cannot move out of `{}` in a pattern guard",
+                          token::get_ident(ident));
+            }
+        }
+    }
     fn consume_pat(&mut self, _: &Pat, _: cmt, _: ConsumeMode) {}
     fn borrow(&mut self,
               _: NodeId,
@@ -1168,32 +1449,70 @@ impl<'a, 'tcx> Delegate<'tcx> for MutationChecker<'a, 'tcx> {
     }
 }
 
-/// Forbids bindings in `@` patterns. This is necessary for memory safety,
-/// because of the way rvalues are handled in the borrow check. (See issue
-/// #14587.)
+/// Whether `pat`, a binding found under `ref x @ ...`, may coexist with the
+/// shared borrow of `x`: it must be a shared borrow itself, or a copy.
+fn is_shared_sub_binding(cx: &MatchCheckCtxt, pat: &Pat) -> bool {
+    match pat.node {
+        ast::PatIdent(ast::BindByRef(ast::MutImmutable), _, _) => true,
+        ast::PatIdent(ast::BindByValue(_), _, _) => {
+            let pat_ty = ty::node_id_to_type(cx.tcx, pat.id);
+            !ty::type_moves_by_default(&cx.param_env, pat.span, pat_ty)
+        }
+        _ => false
+    }
+}
+
+/// Forbids bindings in `@` patterns, except for shared borrows and copies
+/// under a `ref x @ ...` binding, which only ever alias `x`'s referent
+/// immutably. Anything else would be unsound because of the way rvalues
+/// are handled in the borrow check. (See issue #14587.)
 fn check_legality_of_bindings_in_at_patterns(cx: &MatchCheckCtxt, pat: &Pat) {
-    AtBindingPatternVisitor { cx: cx, bindings_allowed: true }.visit_pat(pat);
+    AtBindingPatternVisitor { cx: cx, outer_binding: None }.visit_pat(pat);
+}
+
+/// The kind of `@` binding an `AtBindingPatternVisitor` is underneath.
+#[derive(Copy, PartialEq)]
+enum OuterBinding {
+    /// `ref x @ ...`: shared borrows and copies are allowed below.
+    SharedRef,
+    /// `x @ ...` of a type that isn't `Copy`, already reported as E0007.
+    Move,
+    /// Anything else: no bindings are allowed below.
+    Other,
 }
 
 struct AtBindingPatternVisitor<'a, 'b:'a, 'tcx:'b> {
     cx: &'a MatchCheckCtxt<'b, 'tcx>,
-    bindings_allowed: bool
+    outer_binding: Option<OuterBinding>,
 }
 
 impl<'a, 'b, 'tcx, 'v> Visitor<'v> for AtBindingPatternVisitor<'a, 'b, 'tcx> {
     fn visit_pat(&mut self, pat: &Pat) {
-        if !self.bindings_allowed && pat_is_binding(&self.cx.tcx.def_map, pat) {
-            span_err!(self.cx.tcx.sess, pat.span, E0303,
-                                      "pattern bindings are not allowed \
-                                       after an `@`");
+        if pat_is_binding(&self.cx.tcx.def_map, pat) {
+            let legal = match self.outer_binding {
+                None | Some(OuterBinding::Move) => true,
+                Some(OuterBinding::SharedRef) => is_shared_sub_binding(self.cx, pat),
+                Some(OuterBinding::Other) => false,
+            };
+            if !legal {
+                span_err!(self.cx.tcx.sess, pat.span, E0303,
+                                          "pattern bindings are not allowed \
+                                           after an `@`");
+            }
         }
 
         match pat.node {
-            ast::PatIdent(_, _, Some(_)) => {
-                let bindings_were_allowed = self.bindings_allowed;
-                self.bindings_allowed = false;
+            ast::PatIdent(bm, _, Some(_)) => {
+                let outer_binding = self.outer_binding;
+                self.outer_binding = Some(match bm {
+                    ast::BindByRef(ast::MutImmutable) => OuterBinding::SharedRef,
+                    ast::BindByValue(_) if !is_shared_sub_binding(self.cx, pat) => {
+                        OuterBinding::Move
+                    }
+                    _ => OuterBinding::Other,
+                });
                 visit::walk_pat(self, pat);
-                self.bindings_allowed = bindings_were_allowed;
+                self.outer_binding = outer_binding;
             }
             _ => visit::walk_pat(self, pat),
         }
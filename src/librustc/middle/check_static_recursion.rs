@@ -8,21 +8,27 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-// This compiler pass detects static items that refer to themselves
-// recursively.
+// This compiler pass detects static and constant items, and enum
+// discriminants, whose values depend on themselves.
 
 use session::Session;
-use middle::def::{DefStatic, DefConst, DefMap};
+use middle::def::{DefStatic, DefConst, DefVariant, DefMap};
+use util::nodemap::NodeSet;
 
 use syntax::ast;
 use syntax::{ast_util, ast_map};
+use syntax::codemap::Span;
+use syntax::parse::token;
 use syntax::visit::Visitor;
 use syntax::visit;
 
 struct CheckCrateVisitor<'a, 'ast: 'a> {
     sess: &'a Session,
     def_map: &'a DefMap,
-    ast_map: &'a ast_map::Map<'ast>
+    ast_map: &'a ast_map::Map<'ast>,
+    // Every node that is part of a cycle that has already been reported, so
+    // that each cycle is only reported once.
+    reported: NodeSet,
 }
 
 impl<'v, 'a, 'ast> Visitor<'v> for CheckCrateVisitor<'a, 'ast> {
@@ -35,61 +41,92 @@ pub fn check_crate<'ast>(sess: &Session,
                          krate: &ast::Crate,
                          def_map: &DefMap,
                          ast_map: &ast_map::Map<'ast>) {
+    let errors = sess.err_count();
     let mut visitor = CheckCrateVisitor {
         sess: sess,
         def_map: def_map,
-        ast_map: ast_map
+        ast_map: ast_map,
+        reported: NodeSet(),
     };
     visit::walk_crate(&mut visitor, krate);
-    sess.abort_if_errors();
+    sess.abort_if_new_errors(errors);
 }
 
 fn check_item(v: &mut CheckCrateVisitor, it: &ast::Item) {
     match it.node {
         ast::ItemStatic(_, _, ref ex) |
         ast::ItemConst(_, ref ex) => {
-            check_item_recursion(v.sess, v.ast_map, v.def_map, it);
+            check_item_recursion(v.sess, v.ast_map, v.def_map, &mut v.reported, it.id);
             visit::walk_expr(v, &**ex)
         },
+        ast::ItemEnum(ref enum_def, _) => {
+            for variant in &enum_def.variants {
+                if variant.node.disr_expr.is_some() {
+                    check_item_recursion(v.sess, v.ast_map, v.def_map, &mut v.reported,
+                                         variant.node.id);
+                }
+            }
+            visit::walk_item(v, it)
+        }
         _ => visit::walk_item(v, it)
     }
 }
 
 struct CheckItemRecursionVisitor<'a, 'ast: 'a> {
-    root_it: &'a ast::Item,
     sess: &'a Session,
     ast_map: &'a ast_map::Map<'ast>,
     def_map: &'a DefMap,
+    reported: &'a mut NodeSet,
     idstack: Vec<ast::NodeId>
 }
 
-// Make sure a const item doesn't recursively refer to itself
+// Make sure a const item, static item or enum discriminant doesn't
+// recursively refer to itself
 // FIXME: Should use the dependency graph when it's available (#1356)
 pub fn check_item_recursion<'a>(sess: &'a Session,
                                 ast_map: &'a ast_map::Map,
                                 def_map: &'a DefMap,
-                                it: &'a ast::Item) {
+                                reported: &'a mut NodeSet,
+                                id: ast::NodeId) {
 
     let mut visitor = CheckItemRecursionVisitor {
-        root_it: it,
         sess: sess,
         ast_map: ast_map,
         def_map: def_map,
+        reported: reported,
         idstack: Vec::new()
     };
-    visitor.visit_item(it);
+    visitor.visit_node(id);
 }
 
-impl<'a, 'ast, 'v> Visitor<'v> for CheckItemRecursionVisitor<'a, 'ast> {
-    fn visit_item(&mut self, it: &ast::Item) {
-        if self.idstack.iter().any(|x| x == &(it.id)) {
-            span_err!(self.sess, self.root_it.span, E0265, "recursive constant");
+impl<'a, 'ast> CheckItemRecursionVisitor<'a, 'ast> {
+    fn visit_node(&mut self, id: ast::NodeId) {
+        if let Some(pos) = self.idstack.iter().position(|x| *x == id) {
+            let cycle = &self.idstack[pos..];
+            if !cycle.iter().any(|id| self.reported.contains(id)) {
+                report_cycle(self.sess, self.ast_map, cycle);
+                self.reported.extend(cycle.iter().cloned());
+            }
             return;
         }
-        self.idstack.push(it.id);
-        visit::walk_item(self, it);
+        self.idstack.push(id);
+        match self.ast_map.get(id) {
+            ast_map::NodeItem(item) => visit::walk_item(self, item),
+            ast_map::NodeVariant(variant) => {
+                if let Some(ref ex) = variant.node.disr_expr {
+                    self.visit_expr(&**ex);
+                }
+            }
+            _ => {}
+        }
         self.idstack.pop();
     }
+}
+
+impl<'a, 'ast, 'v> Visitor<'v> for CheckItemRecursionVisitor<'a, 'ast> {
+    fn visit_item(&mut self, it: &ast::Item) {
+        self.visit_node(it.id);
+    }
 
     fn visit_expr(&mut self, e: &ast::Expr) {
         match e.node {
@@ -99,8 +136,8 @@ impl<'a, 'ast, 'v> Visitor<'v> for CheckItemRecursionVisitor<'a, 'ast> {
                     Some(DefConst(def_id)) if
                             ast_util::is_local(def_id) => {
                         match self.ast_map.get(def_id.node) {
-                          ast_map::NodeItem(item) =>
-                            self.visit_item(item),
+                          ast_map::NodeItem(_) =>
+                            self.visit_node(def_id.node),
                           ast_map::NodeForeignItem(_) => {},
                           _ => {
                             span_err!(self.sess, e.span, E0266,
@@ -110,6 +147,10 @@ impl<'a, 'ast, 'v> Visitor<'v> for CheckItemRecursionVisitor<'a, 'ast> {
                           },
                         }
                     }
+                    Some(DefVariant(enum_def, variant_def, _)) if
+                            ast_util::is_local(enum_def) => {
+                        self.visit_node(variant_def.node);
+                    }
                     _ => ()
                 }
             },
@@ -118,3 +159,31 @@ impl<'a, 'ast, 'v> Visitor<'v> for CheckItemRecursionVisitor<'a, 'ast> {
         visit::walk_expr(self, e);
     }
 }
+
+fn describe_node(ast_map: &ast_map::Map, id: ast::NodeId) -> (Span, String) {
+    match ast_map.get(id) {
+        ast_map::NodeItem(item) => (item.span, token::get_ident(item.ident).to_string()),
+        ast_map::NodeVariant(variant) => {
+            let enum_name = ast_map.get_path_elem(ast_map.get_parent(id)).name();
+            (variant.span, format!("{}::{}", token::get_name(enum_name),
+                                   token::get_ident(variant.node.name)))
+        }
+        _ => (ast_map.span(id), ast_map.node_to_string(id))
+    }
+}
+
+/// Reports a cycle between the constants, statics and enum discriminants in
+/// `cycle`: each one requires the value of the next, and the last one
+/// requires the value of the first.
+pub fn report_cycle(sess: &Session, ast_map: &ast_map::Map, cycle: &[ast::NodeId]) {
+    assert!(!cycle.is_empty());
+    let (span, name) = describe_node(ast_map, cycle[0]);
+    span_err!(sess, span, E0265, "cycle detected when computing value of `{}`", name);
+    for &id in &cycle[1..] {
+        let (span, name) = describe_node(ast_map, id);
+        sess.span_note(span, &format!("...which then requires computing value of `{}`...",
+                                      name));
+    }
+    sess.span_note(span, &format!("...which then again requires computing value of `{}`, \
+                                   completing the cycle", name));
+}
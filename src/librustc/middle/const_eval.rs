@@ -14,7 +14,7 @@
 pub use self::const_val::*;
 
 use metadata::csearch;
-use middle::{astencode, def};
+use middle::{astencode, check_static_recursion, def};
 use middle::pat_util::def_to_path;
 use middle::ty::{self, Ty};
 use middle::astconv_util::ast_ty_to_prim_ty;
@@ -244,6 +244,8 @@ pub enum ErrKind {
     DivideWithOverflow,
     ModuloByZero,
     ModuloWithOverflow,
+    ShiftLeftWithOverflow,
+    ShiftRightWithOverflow,
     MissingStructField,
     NonConstPath,
     ExpectedConstTuple,
@@ -285,6 +287,8 @@ impl ConstEvalErr {
             DivideWithOverflow   => "attempted to divide with overflow".into_cow(),
             ModuloByZero         => "attempted remainder with a divisor of zero".into_cow(),
             ModuloWithOverflow   => "attempted remainder with overflow".into_cow(),
+            ShiftLeftWithOverflow => "attempted to shift left with overflow".into_cow(),
+            ShiftRightWithOverflow => "attempted to shift right with overflow".into_cow(),
             MissingStructField  => "nonexistent struct field".into_cow(),
             NonConstPath        => "non-constant path in constant expr".into_cow(),
             ExpectedConstTuple => "expected constant tuple".into_cow(),
@@ -333,6 +337,43 @@ fn checked_mul_uint(e: &Expr, a: u64, b: u64) -> Result<const_val, ConstEvalErr>
     if !oflo { Ok(const_uint(ret)) } else { signal!(e, MuluWithOverflow(a, b)) }
 }
 
+/// Checks that the shift amount `b` (`None` if it is negative) is less than
+/// the bit width of the shifted type. That is the type of `e` if it has been
+/// typechecked, then the suffix of a literal `lhs`, then the hint `ety`, and
+/// 64 bits if none of those are known.
+fn checked_shift_amount<'tcx>(tcx: &ty::ctxt<'tcx>, e: &Expr, lhs: &Expr,
+                              ety: Option<Ty<'tcx>>, op: ast::BinOp_, b: Option<u64>)
+                              -> Result<uint, ConstEvalErr> {
+    let suffix = match lhs.node {
+        ast::ExprLit(ref lit) => match lit.node {
+            ast::LitInt(_, ast::SignedIntLit(t, _)) => Some(ty::mk_mach_int(tcx, t)),
+            ast::LitInt(_, ast::UnsignedIntLit(t)) => Some(ty::mk_mach_uint(tcx, t)),
+            _ => None
+        },
+        _ => None
+    };
+    let int_ty = ty::expr_ty_opt(tcx, e).or(suffix).or(ety);
+    let bits = match int_ty.map(|ty| &ty.sty) {
+        Some(&ty::ty_int(ast::TyIs(_))) => match tcx.sess.target.int_type {
+            ast::TyI32 => 32,
+            _ => 64,
+        },
+        Some(&ty::ty_uint(ast::TyUs(_))) => match tcx.sess.target.uint_type {
+            ast::TyU32 => 32,
+            _ => 64,
+        },
+        Some(&ty::ty_int(ast::TyI8)) | Some(&ty::ty_uint(ast::TyU8)) => 8,
+        Some(&ty::ty_int(ast::TyI16)) | Some(&ty::ty_uint(ast::TyU16)) => 16,
+        Some(&ty::ty_int(ast::TyI32)) | Some(&ty::ty_uint(ast::TyU32)) => 32,
+        _ => 64,
+    };
+    match b {
+        Some(b) if b < bits => Ok(b as uint),
+        _ if op == ast::BiShl => signal!(e, ShiftLeftWithOverflow),
+        _ => signal!(e, ShiftRightWithOverflow),
+    }
+}
+
 
 pub fn eval_const_expr_partial<'tcx>(tcx: &ty::ctxt<'tcx>,
                                      e: &Expr,
@@ -368,6 +409,7 @@ pub fn eval_const_expr_partial<'tcx>(tcx: &ty::ctxt<'tcx>,
         }
       }
       ast::ExprBinary(op, ref a, ref b) => {
+        let lhs = &**a;
         let b_ty = match op.node {
             ast::BiShl | ast::BiShr => Some(tcx.types.uint),
             _ => ety
@@ -434,8 +476,11 @@ pub fn eval_const_expr_partial<'tcx>(tcx: &ty::ctxt<'tcx>,
               ast::BiAnd | ast::BiBitAnd => const_int(a & b),
               ast::BiOr | ast::BiBitOr => const_int(a | b),
               ast::BiBitXor => const_int(a ^ b),
-              ast::BiShl => const_int(a << b as uint),
-              ast::BiShr => const_int(a >> b as uint),
+              ast::BiShl | ast::BiShr => {
+                  let b = if b < 0 { None } else { Some(b as u64) };
+                  let b = try!(checked_shift_amount(tcx, e, lhs, ety, op.node, b));
+                  if op.node == ast::BiShl { const_int(a << b) } else { const_int(a >> b) }
+              }
               ast::BiEq => fromb(a == b),
               ast::BiLt => fromb(a < b),
               ast::BiLe => fromb(a <= b),
@@ -456,8 +501,10 @@ pub fn eval_const_expr_partial<'tcx>(tcx: &ty::ctxt<'tcx>,
               ast::BiAnd | ast::BiBitAnd => const_uint(a & b),
               ast::BiOr | ast::BiBitOr => const_uint(a | b),
               ast::BiBitXor => const_uint(a ^ b),
-              ast::BiShl => const_uint(a << b as uint),
-              ast::BiShr => const_uint(a >> b as uint),
+              ast::BiShl | ast::BiShr => {
+                  let b = try!(checked_shift_amount(tcx, e, lhs, ety, op.node, Some(b)));
+                  if op.node == ast::BiShl { const_uint(a << b) } else { const_uint(a >> b) }
+              }
               ast::BiEq => fromb(a == b),
               ast::BiLt => fromb(a < b),
               ast::BiLe => fromb(a <= b),
@@ -469,15 +516,20 @@ pub fn eval_const_expr_partial<'tcx>(tcx: &ty::ctxt<'tcx>,
           // shifts can have any integral type as their rhs
           (const_int(a), const_uint(b)) => {
             match op.node {
-              ast::BiShl => const_int(a << b as uint),
-              ast::BiShr => const_int(a >> b as uint),
+              ast::BiShl | ast::BiShr => {
+                  let b = try!(checked_shift_amount(tcx, e, lhs, ety, op.node, Some(b)));
+                  if op.node == ast::BiShl { const_int(a << b) } else { const_int(a >> b) }
+              }
               _ => signal!(e, InvalidOpForIntUint(op.node)),
             }
           }
           (const_uint(a), const_int(b)) => {
             match op.node {
-              ast::BiShl => const_uint(a << b as uint),
-              ast::BiShr => const_uint(a >> b as uint),
+              ast::BiShl | ast::BiShr => {
+                  let b = if b < 0 { None } else { Some(b as u64) };
+                  let b = try!(checked_shift_amount(tcx, e, lhs, ety, op.node, b));
+                  if op.node == ast::BiShl { const_uint(a << b) } else { const_uint(a >> b) }
+              }
               _ => signal!(e, InvalidOpForUintInt(op.node)),
             }
           }
@@ -542,7 +594,22 @@ pub fn eval_const_expr_partial<'tcx>(tcx: &ty::ctxt<'tcx>,
               None => signal!(e, NonConstPath)
           };
           let ety = ety.or_else(|| const_ty.and_then(|ty| ast_ty_to_prim_ty(tcx, ty)));
-          try!(eval_const_expr_partial(tcx, const_expr, ety))
+          let in_progress = match opt_def {
+              Some(def::DefConst(def_id)) if ast_util::is_local(def_id) => Some(def_id.node),
+              Some(def::DefVariant(enum_def, variant_def, _)) if ast_util::is_local(enum_def) => {
+                  Some(variant_def.node)
+              }
+              _ => None
+          };
+          match in_progress {
+              Some(id) => {
+                  enter_const_eval(tcx, id);
+                  let result = eval_const_expr_partial(tcx, const_expr, ety);
+                  tcx.const_eval_stack.borrow_mut().pop();
+                  try!(result)
+              }
+              None => try!(eval_const_expr_partial(tcx, const_expr, ety))
+          }
       }
       ast::ExprLit(ref lit) => {
           lit_to_const(&**lit, ety)
@@ -607,6 +674,20 @@ pub fn eval_const_expr_partial<'tcx>(tcx: &ty::ctxt<'tcx>,
     Ok(result)
 }
 
+/// Records that the value of the local constant or enum variant `id` is being
+/// computed. If it already is, its value depends on itself: the cycle is
+/// reported and compilation stops, since evaluating it would never finish.
+fn enter_const_eval(tcx: &ty::ctxt, id: ast::NodeId) {
+    {
+        let stack = tcx.const_eval_stack.borrow();
+        if let Some(pos) = stack.iter().position(|x| *x == id) {
+            check_static_recursion::report_cycle(&tcx.sess, &tcx.map, &stack[pos..]);
+            tcx.sess.abort_if_errors();
+        }
+    }
+    tcx.const_eval_stack.borrow_mut().push(id);
+}
+
 fn cast_const(val: const_val, ty: Ty) -> Result<const_val, ErrKind> {
     macro_rules! define_casts {
         ($($ty_pat:pat => (
@@ -0,0 +1,172 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Statistics about the shape of a crate after resolution, printed with
+//! `-Z crate-stats`. They are meant to help the authors of code generators
+//! and macro-heavy crates notice when they are producing far more code than
+//! they expect. The size of a single function body is also what the
+//! `large_fn_bodies` lint measures.
+
+use session::Session;
+
+use syntax::ast;
+use syntax::ast_map;
+use syntax::codemap::{Span, DUMMY_SP};
+use syntax::visit::{self, Visitor};
+
+/// The size of a function body. Closures count towards the body they appear
+/// in, but nested items do not.
+#[derive(Copy)]
+pub struct BodySize {
+    /// The number of expressions in the body.
+    pub exprs: uint,
+    /// How deeply the expressions of the body nest.
+    pub max_depth: uint,
+    /// The most deeply nested expression.
+    pub deepest: Span,
+    depth: uint,
+}
+
+impl<'v> Visitor<'v> for BodySize {
+    fn visit_expr(&mut self, e: &ast::Expr) {
+        self.exprs += 1;
+        self.depth += 1;
+        if self.depth > self.max_depth {
+            self.max_depth = self.depth;
+            self.deepest = e.span;
+        }
+        visit::walk_expr(self, e);
+        self.depth -= 1;
+    }
+
+    fn visit_item(&mut self, _: &ast::Item) {
+        // Nested items are bodies of their own.
+    }
+}
+
+pub fn body_size(body: &ast::Block) -> BodySize {
+    let mut size = BodySize { exprs: 0, max_depth: 0, deepest: DUMMY_SP, depth: 0 };
+    visit::walk_block(&mut size, body);
+    size
+}
+
+/// The span of the outermost macro invocation `span` was expanded from, or
+/// `span` itself if it was written by hand.
+pub fn outermost_call_site(sess: &Session, span: Span) -> Span {
+    let mut span = span;
+    loop {
+        let call_site = sess.codemap().with_expn_info(span.expn_id, |info| {
+            info.map(|info| info.call_site)
+        });
+        match call_site {
+            Some(call_site) => span = call_site,
+            None => return span,
+        }
+    }
+}
+
+struct StatsVisitor<'a, 'ast: 'a> {
+    ast_map: &'a ast_map::Map<'ast>,
+
+    fns: uint,
+    impls: uint,
+    methods: uint,
+    statics: uint,
+    generic_fns: uint,
+
+    // Whether the methods being visited belong to a trait or to a generic
+    // impl, and so are instantiated once per use just like generic functions.
+    in_generic_container: bool,
+
+    largest: Option<(ast::NodeId, uint)>,
+    deepest: Option<(uint, Span)>,
+}
+
+impl<'a, 'ast, 'v> Visitor<'v> for StatsVisitor<'a, 'ast> {
+    fn visit_item(&mut self, i: &'v ast::Item) {
+        let was_generic = self.in_generic_container;
+        match i.node {
+            ast::ItemImpl(_, _, ref generics, _, _, _) => {
+                self.impls += 1;
+                self.in_generic_container = generics.is_type_parameterized();
+            }
+            ast::ItemTrait(..) => self.in_generic_container = true,
+            ast::ItemStatic(..) => self.statics += 1,
+            _ => {}
+        }
+        visit::walk_item(self, i);
+        self.in_generic_container = was_generic;
+    }
+
+    fn visit_fn(&mut self, fk: visit::FnKind<'v>, fd: &'v ast::FnDecl,
+                b: &'v ast::Block, s: Span, id: ast::NodeId) {
+        let generic = match fk {
+            visit::FkItemFn(_, generics, _, _) => {
+                self.fns += 1;
+                generics.is_type_parameterized()
+            }
+            visit::FkMethod(_, sig) => {
+                self.methods += 1;
+                self.in_generic_container || sig.generics.is_type_parameterized()
+            }
+            visit::FkFnBlock => {
+                // Part of the body of the function it appears in.
+                return visit::walk_fn(self, fk, fd, b, s);
+            }
+        };
+        if generic {
+            self.generic_fns += 1;
+        }
+
+        let size = body_size(b);
+        if self.largest.map_or(true, |(_, exprs)| size.exprs > exprs) {
+            self.largest = Some((id, size.exprs));
+        }
+        if self.deepest.map_or(true, |(depth, _)| size.max_depth > depth) {
+            self.deepest = Some((size.max_depth, size.deepest));
+        }
+
+        // A nested function is not part of the container's methods.
+        let was_generic = self.in_generic_container;
+        self.in_generic_container = false;
+        visit::walk_fn(self, fk, fd, b, s);
+        self.in_generic_container = was_generic;
+    }
+}
+
+pub fn print_crate_stats(sess: &Session, krate: &ast::Crate, ast_map: &ast_map::Map) {
+    let mut visitor = StatsVisitor {
+        ast_map: ast_map,
+        fns: 0,
+        impls: 0,
+        methods: 0,
+        statics: 0,
+        generic_fns: 0,
+        in_generic_container: false,
+        largest: None,
+        deepest: None,
+    };
+    visit::walk_crate(&mut visitor, krate);
+
+    println!("--- crate stats ---");
+    println!("fns: {}", visitor.fns);
+    println!("impls: {}", visitor.impls);
+    println!("methods: {}", visitor.methods);
+    println!("statics: {}", visitor.statics);
+    println!("generic fns and methods: {}", visitor.generic_fns);
+    if let Some((id, exprs)) = visitor.largest {
+        println!("largest fn body: `{}`, {} expressions",
+                 visitor.ast_map.path_to_string(id), exprs);
+    }
+    if let Some((depth, span)) = visitor.deepest {
+        println!("deepest expression nesting: {}, at {}",
+                 depth, sess.codemap().span_to_string(span));
+    }
+}
@@ -333,11 +333,15 @@ fn has_allow_dead_code_or_lang_attr(attrs: &[ast::Attribute]) -> bool {
 //           warning for both `f` and `g`.
 //     * Item annotated with #[lang=".."]
 //         - This is because lang items are always callable from elsewhere.
+//     * Item annotated with #[test] or #[bench], when building with --test
+//         - These are called by the test harness, so everything they use
+//           is live even if the harness itself is not the entry point.
 //   or
 //   2) We are not sure to be live or not
 //     * Implementation of a trait method
 struct LifeSeeder {
-    worklist: Vec<ast::NodeId>
+    worklist: Vec<ast::NodeId>,
+    test_harness: bool,
 }
 
 impl<'v> Visitor<'v> for LifeSeeder {
@@ -345,6 +349,9 @@ impl<'v> Visitor<'v> for LifeSeeder {
         let allow_dead_code = has_allow_dead_code_or_lang_attr(&item.attrs);
         if allow_dead_code {
             self.worklist.push(item.id);
+        } else if self.test_harness && (attr::contains_name(&item.attrs, "test") ||
+                                        attr::contains_name(&item.attrs, "bench")) {
+            self.worklist.push(item.id);
         }
         match item.node {
             ast::ItemEnum(ref enum_def, _) if allow_dead_code => {
@@ -408,7 +415,8 @@ fn create_and_seed_worklist(tcx: &ty::ctxt,
 
     // Seed implemented trait methods
     let mut life_seeder = LifeSeeder {
-        worklist: worklist
+        worklist: worklist,
+        test_harness: tcx.sess.opts.test,
     };
     visit::walk_crate(&mut life_seeder, krate);
 
@@ -50,6 +50,10 @@ pub enum Def {
     DefRegion(ast::NodeId),
     DefLabel(ast::NodeId),
     DefMethod(ast::DefId /* method */, MethodProvenance),
+
+    /// Stands in for a path that resolution reported an error for, so that
+    /// later passes can carry on under `-Z keep-going`.
+    DefErr,
 }
 
 /// The result of resolving a path.
@@ -139,7 +143,8 @@ impl Def {
                 local_def(id)
             }
 
-            DefPrimTy(_) => panic!("attempted .def_id() on DefPrimTy")
+            DefPrimTy(_) => panic!("attempted .def_id() on DefPrimTy"),
+            DefErr => panic!("attempted .def_id() on DefErr")
         }
     }
 
@@ -60,8 +60,14 @@
 //! particular library (other than a global dynamic/static switch).
 //! Additionally, the algorithm is geared towards finding *any* solution rather
 //! than finding a number of solutions (there are normally quite a few).
+//!
+//! Allocator crates (tagged `#![allocator]`) are special in that exactly one
+//! of them must end up in the final artifact. Allocators which were only
+//! injected into an upstream crate's compilation are not linked, and the one
+//! injected for this compilation is only linked if no other allocator was.
 
 use syntax::ast;
+use syntax::codemap::DUMMY_SP;
 
 use session;
 use session::config;
@@ -117,8 +123,8 @@ fn calculate_type(sess: &session::Session,
             sess.cstore.iter_crate_data(|cnum, data| {
                 let src = sess.cstore.get_used_crate_source(cnum).unwrap();
                 if src.rlib.is_some() { return }
-                sess.err(&format!("dependency `{}` not found in rlib format",
-                                 data.name));
+                crate_err(sess, data, &format!("dependency `{}` not found in rlib format",
+                                               data.name));
             });
             return Vec::new();
         }
@@ -168,7 +174,8 @@ fn calculate_type(sess: &session::Session,
     // static libraries.
     sess.cstore.iter_crate_data(|cnum, data| {
         let src = sess.cstore.get_used_crate_source(cnum).unwrap();
-        if src.dylib.is_none() && !formats.contains_key(&cnum) {
+        if src.dylib.is_none() && !formats.contains_key(&cnum) &&
+           !is_unrequested_allocator(sess, cnum) {
             assert!(src.rlib.is_some());
             debug!("adding staticlib: {}", data.name);
             add_library(sess, cnum, cstore::RequireStatic, &mut formats);
@@ -176,6 +183,9 @@ fn calculate_type(sess: &session::Session,
         }
     });
 
+    activate_allocator(sess, &mut ret, &formats);
+    verify_allocators(sess, &ret, &formats);
+
     // When dylib B links to dylib A, then when using B we must also link to A.
     // It could be the case, however, that the rlib for A is present (hence we
     // found metadata), but the dylib for A has since been removed.
@@ -191,13 +201,14 @@ fn calculate_type(sess: &session::Session,
             Some(cstore::RequireDynamic) if src.dylib.is_some() => continue,
             Some(kind) => {
                 let data = sess.cstore.get_crate_data(cnum + 1);
-                sess.err(&format!("crate `{}` required to be available in {}, \
-                                  but it was not available in this form",
-                                 data.name,
-                                 match kind {
-                                     cstore::RequireStatic => "rlib",
-                                     cstore::RequireDynamic => "dylib",
-                                 }));
+                crate_err(sess, &data,
+                          &format!("crate `{}` required to be available in {}, \
+                                    but it was not available in this form",
+                                   data.name,
+                                   match kind {
+                                       cstore::RequireStatic => "rlib",
+                                       cstore::RequireDynamic => "dylib",
+                                   }));
             }
         }
     }
@@ -220,9 +231,10 @@ fn add_library(sess: &session::Session,
             // can be refined over time.
             if link2 != link || link == cstore::RequireStatic {
                 let data = sess.cstore.get_crate_data(cnum);
-                sess.err(&format!("cannot satisfy dependencies so `{}` only \
-                                  shows up once",
-                                 data.name));
+                crate_err(sess, &data,
+                          &format!("cannot satisfy dependencies so `{}` only \
+                                    shows up once",
+                                   data.name));
                 sess.help("having upstream crates all available in one format \
                            will likely make this go away");
             }
@@ -231,11 +243,84 @@ fn add_library(sess: &session::Session,
     }
 }
 
+/// Report `msg` at the `extern crate` that loaded `data`, if it has a span.
+/// Crates the compiler injected itself have none.
+fn crate_err(sess: &session::Session, data: &cstore::crate_metadata, msg: &str) {
+    if data.span == DUMMY_SP {
+        sess.err(msg);
+    } else {
+        sess.span_err(data.span, msg);
+    }
+}
+
 fn attempt_static(sess: &session::Session) -> Option<DependencyList> {
     let crates = sess.cstore.get_used_crates(cstore::RequireStatic);
-    if crates.iter().by_ref().all(|&(_, ref p)| p.is_some()) {
-        Some(crates.into_iter().map(|_| Some(cstore::RequireStatic)).collect())
-    } else {
-        None
+    if !crates.iter().by_ref().all(|&(_, ref p)| p.is_some()) {
+        return None
+    }
+
+    let mut ret = (1..sess.cstore.next_crate_num()).map(|cnum| {
+        if is_unrequested_allocator(sess, cnum) {
+            None
+        } else {
+            Some(cstore::RequireStatic)
+        }
+    }).collect::<Vec<_>>();
+    let included = FnvHashMap();
+    activate_allocator(sess, &mut ret, &included);
+    verify_allocators(sess, &ret, &included);
+    Some(ret)
+}
+
+// An allocator crate which is only in the crate graph because it was injected
+// into some compilation (possibly an upstream one) is not linked on its own.
+fn is_unrequested_allocator(sess: &session::Session, cnum: ast::CrateNum) -> bool {
+    let data = sess.cstore.get_crate_data(cnum);
+    data.is_allocator() && !data.explicitly_linked.get()
+}
+
+// Whether the crate `cnum` ends up in the output, either linked directly or
+// through one of the dylibs that are linked.
+fn is_linked(list: &DependencyList,
+             included: &FnvHashMap<ast::CrateNum, cstore::LinkagePreference>,
+             cnum: ast::CrateNum) -> bool {
+    list[cnum as uint - 1].is_some() || included.contains_key(&cnum)
+}
+
+fn activate_allocator(sess: &session::Session,
+                      list: &mut DependencyList,
+                      included: &FnvHashMap<ast::CrateNum, cstore::LinkagePreference>) {
+    let cnum = match sess.injected_allocator.get() {
+        Some(cnum) => cnum,
+        None => return,
+    };
+    let mut allocator_found = false;
+    sess.cstore.iter_crate_data(|other, data| {
+        if data.is_allocator() && is_linked(list, included, other) {
+            allocator_found = true;
+        }
+    });
+    if !allocator_found {
+        list[cnum as uint - 1] = Some(cstore::RequireStatic);
+    }
+}
+
+fn verify_allocators(sess: &session::Session,
+                     list: &DependencyList,
+                     included: &FnvHashMap<ast::CrateNum, cstore::LinkagePreference>) {
+    let mut allocator: Option<String> = None;
+    for cnum in 1..sess.cstore.next_crate_num() {
+        let data = sess.cstore.get_crate_data(cnum);
+        if !data.is_allocator() || !is_linked(list, included, cnum) {
+            continue
+        }
+        match allocator {
+            Some(ref prev) => {
+                crate_err(sess, &data,
+                          &format!("cannot link together two allocators: `{}` \
+                                    and `{}`", prev, data.name));
+            }
+            None => allocator = Some(data.name.clone()),
+        }
     }
 }
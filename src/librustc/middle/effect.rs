@@ -13,6 +13,7 @@
 use self::UnsafeContext::*;
 
 use middle::def;
+use middle::pat_util;
 use middle::ty::{self, Ty};
 use middle::ty::MethodCall;
 use util::ppaux;
@@ -25,13 +26,20 @@ use syntax::visit::Visitor;
 #[derive(Copy, PartialEq)]
 enum UnsafeContext {
     SafeContext,
-    UnsafeFn,
+    UnsafeFn(ast::NodeId),
     UnsafeBlock(ast::NodeId),
+    // An `unsafe` block directly inside an unsafe fn (the first id), which
+    // is only needed under the `unsafe_op_in_unsafe_fn` lint.
+    UnsafeBlockInUnsafeFn(ast::NodeId, ast::NodeId),
 }
 
 fn type_is_unsafe_function(ty: Ty) -> bool {
     match ty.sty {
         ty::ty_bare_fn(_, ref f) => f.unsafety == ast::Unsafety::Unsafe,
+        // Callees are autoderef'd, so calling through a pointer to an
+        // unsafe fn is still a call to an unsafe fn.
+        ty::ty_rptr(_, ty::mt { ty, .. }) | ty::ty_ptr(ty::mt { ty, .. }) |
+        ty::ty_uniq(ty) => type_is_unsafe_function(ty),
         _ => false,
     }
 }
@@ -45,46 +53,84 @@ struct EffectCheckVisitor<'a, 'tcx: 'a> {
 
 impl<'a, 'tcx> EffectCheckVisitor<'a, 'tcx> {
     fn require_unsafe(&mut self, span: Span, description: &str) {
+        self.require_unsafe_noted(span, description, None)
+    }
+
+    /// Like `require_unsafe`, with a note explaining what made the
+    /// operation unsafe attached to the error, if any.
+    fn require_unsafe_noted(&mut self, span: Span, description: &str,
+                            note: Option<(Span, String)>) {
         match self.unsafe_context {
             SafeContext => {
                 // Report an error.
                 span_err!(self.tcx.sess, span, E0133,
                           "{} requires unsafe function or block",
                           description);
+                if let Some((note_span, note)) = note {
+                    self.tcx.sess.span_note(note_span, &note);
+                }
             }
             UnsafeBlock(block_id) => {
                 // OK, but record this.
                 debug!("effect: recording unsafe block as used: {}", block_id);
                 self.tcx.used_unsafe.borrow_mut().insert(block_id);
             }
-            UnsafeFn => {}
+            UnsafeFn(fn_id) => {
+                // OK, but the `unsafe_op_in_unsafe_fn` lint may still want
+                // an explicit `unsafe` block around this. Either way the
+                // function needed to be unsafe.
+                self.tcx.used_unsafe.borrow_mut().insert(fn_id);
+                let mut ops = self.tcx.unsafe_ops_in_unsafe_fn.borrow_mut();
+                let op = (span, description.to_string());
+                match ops.get_mut(&fn_id) {
+                    Some(fn_ops) => { fn_ops.push(op); return; }
+                    None => {}
+                }
+                ops.insert(fn_id, vec![op]);
+            }
+            UnsafeBlockInUnsafeFn(fn_id, block_id) => {
+                debug!("effect: recording unsafe block in unsafe fn as used: {}", block_id);
+                self.tcx.used_unsafe.borrow_mut().insert(fn_id);
+                self.tcx.used_unsafe_in_unsafe_fn.borrow_mut().insert(block_id);
+            }
         }
     }
 
-    fn check_str_index(&mut self, e: &ast::Expr) {
-        let base_type = match e.node {
-            ast::ExprIndex(ref base, _) => ty::node_id_to_type(self.tcx, base.id),
-            _ => return
-        };
-        debug!("effect: checking index with base type {}",
-                ppaux::ty_to_string(self.tcx, base_type));
-        match base_type.sty {
-            ty::ty_uniq(ty) | ty::ty_rptr(_, ty::mt{ty, ..}) => if ty::ty_str == ty.sty {
-                span_err!(self.tcx.sess, e.span, E0134,
-                          "modification of string types is not allowed");
-            },
-            ty::ty_str => {
-                span_err!(self.tcx.sess, e.span, E0135,
-                          "modification of string types is not allowed");
+    fn is_packed(&self, ty: Ty<'tcx>) -> bool {
+        match ty.sty {
+            ty::ty_struct(did, _) => ty::lookup_packed(self.tcx, did),
+            _ => false,
+        }
+    }
+
+    /// Whether `e` is a place which lies inside a `#[repr(packed)]` struct,
+    /// and so is not necessarily aligned for its type.
+    fn is_packed_place(&self, e: &ast::Expr) -> bool {
+        match e.node {
+            ast::ExprField(ref base, _) | ast::ExprTupField(ref base, _) => {
+                let base_ty = ty::expr_ty_adjusted(self.tcx, &**base);
+                if self.is_packed(base_ty) {
+                    return true;
+                }
+                // If the base wasn't autoderef'd, it is stored inline in its
+                // own parent and so is only as aligned as that is.
+                base_ty == ty::expr_ty(self.tcx, &**base) && self.is_packed_place(&**base)
             }
-            _ => {}
+            ast::ExprParen(ref e) => self.is_packed_place(&**e),
+            _ => false,
+        }
+    }
+
+    fn check_packed_borrow(&mut self, span: Span, place: &ast::Expr) {
+        if self.is_packed_place(place) {
+            self.require_unsafe(span, "borrow of packed field");
         }
     }
 }
 
 impl<'a, 'tcx, 'v> Visitor<'v> for EffectCheckVisitor<'a, 'tcx> {
     fn visit_fn(&mut self, fn_kind: visit::FnKind<'v>, fn_decl: &'v ast::FnDecl,
-                block: &'v ast::Block, span: Span, _: ast::NodeId) {
+                block: &'v ast::Block, span: Span, id: ast::NodeId) {
 
         let (is_item_fn, is_unsafe_fn) = match fn_kind {
             visit::FkItemFn(_, _, fn_style, _) =>
@@ -96,7 +142,7 @@ impl<'a, 'tcx, 'v> Visitor<'v> for EffectCheckVisitor<'a, 'tcx> {
 
         let old_unsafe_context = self.unsafe_context;
         if is_unsafe_fn {
-            self.unsafe_context = UnsafeFn
+            self.unsafe_context = UnsafeFn(id)
         } else if is_item_fn {
             self.unsafe_context = SafeContext
         }
@@ -113,8 +159,9 @@ impl<'a, 'tcx, 'v> Visitor<'v> for EffectCheckVisitor<'a, 'tcx> {
             ast::UnsafeBlock(source) => {
                 // By default only the outermost `unsafe` block is
                 // "used" and so nested unsafe blocks are pointless
-                // (the inner ones are unnecessary and we actually
-                // warn about them). As such, there are two cases when
+                // (the inner ones are unnecessary and the `unused_unsafe`
+                // lint warns about any block left out of `used_unsafe`).
+                // As such, there are two cases when
                 // we need to create a new context, when we're
                 // - outside `unsafe` and found a `unsafe` block
                 //   (normal case)
@@ -126,8 +173,14 @@ impl<'a, 'tcx, 'v> Visitor<'v> for EffectCheckVisitor<'a, 'tcx> {
                 // external blocks (e.g. `unsafe { println("") }`,
                 // expands to `unsafe { ... unsafe { ... } }` where
                 // the inner one is compiler generated).
+                //
+                // Directly inside an unsafe fn, a user-written block is
+                // not needed either, unless `unsafe_op_in_unsafe_fn` is
+                // enabled, so it is tracked separately.
                 if self.unsafe_context == SafeContext || source == ast::CompilerGenerated {
                     self.unsafe_context = UnsafeBlock(block.id)
+                } else if let UnsafeFn(fn_id) = self.unsafe_context {
+                    self.unsafe_context = UnsafeBlockInUnsafeFn(fn_id, block.id)
                 }
             }
         }
@@ -138,6 +191,17 @@ impl<'a, 'tcx, 'v> Visitor<'v> for EffectCheckVisitor<'a, 'tcx> {
     }
 
     fn visit_expr(&mut self, expr: &ast::Expr) {
+        // Method receivers are borrowed implicitly.
+        let autoref = match self.tcx.adjustments.borrow().get(&expr.id) {
+            Some(&ty::AdjustDerefRef(ty::AutoDerefRef {
+                autoderefs: 0, autoref: Some(ty::AutoPtr(..))
+            })) => true,
+            _ => false,
+        };
+        if autoref {
+            self.check_packed_borrow(expr.span, expr);
+        }
+
         match expr.node {
             ast::ExprMethodCall(_, _, _) => {
                 let method_call = MethodCall::expr(expr.id);
@@ -154,7 +218,16 @@ impl<'a, 'tcx, 'v> Visitor<'v> for EffectCheckVisitor<'a, 'tcx> {
                 debug!("effect: call case, base type is {}",
                        ppaux::ty_to_string(self.tcx, base_type));
                 if type_is_unsafe_function(base_type) {
-                    self.require_unsafe(expr.span, "call to unsafe function")
+                    // Reading the callee (e.g. out of a `static mut` table)
+                    // is checked on its own; this is about the callee.
+                    let kind = match base_type.sty {
+                        ty::ty_bare_fn(..) => "is an unsafe function",
+                        _ => "points to an unsafe function",
+                    };
+                    let note = format!("the callee has type `{}`, which {}",
+                                       ppaux::ty_to_string(self.tcx, base_type), kind);
+                    self.require_unsafe_noted(expr.span, "call to unsafe function",
+                                              Some((base.span, note)))
                 }
             }
             ast::ExprUnary(ast::UnDeref, ref base) => {
@@ -165,11 +238,14 @@ impl<'a, 'tcx, 'v> Visitor<'v> for EffectCheckVisitor<'a, 'tcx> {
                     self.require_unsafe(expr.span, "dereference of unsafe pointer")
                 }
             }
-            ast::ExprAssign(ref base, _) | ast::ExprAssignOp(_, ref base, _) => {
-                self.check_str_index(&**base);
+            ast::ExprAddrOf(_, ref base) => {
+                self.check_packed_borrow(expr.span, &**base);
             }
-            ast::ExprAddrOf(ast::MutMutable, ref base) => {
-                self.check_str_index(&**base);
+            ast::ExprMatch(ref discr, ref arms, _) => {
+                let tcx = self.tcx;
+                if arms.iter().any(|arm| pat_util::arm_contains_ref_binding(&tcx.def_map, arm)) {
+                    self.check_packed_borrow(discr.span, &**discr);
+                }
             }
             ast::ExprInlineAsm(..) => {
                 self.require_unsafe(expr.span, "use of inline assembly");
@@ -184,6 +260,40 @@ impl<'a, 'tcx, 'v> Visitor<'v> for EffectCheckVisitor<'a, 'tcx> {
 
         visit::walk_expr(self, expr);
     }
+
+    fn visit_local(&mut self, local: &ast::Local) {
+        if let Some(ref init) = local.init {
+            if pat_util::pat_contains_ref_binding(&self.tcx.def_map, &*local.pat) {
+                self.check_packed_borrow(init.span, &**init);
+            }
+        }
+
+        visit::walk_local(self, local);
+    }
+
+    fn visit_pat(&mut self, pat: &ast::Pat) {
+        // `ref` bindings to the fields of a packed struct pattern borrow them.
+        // FIXME: `#[derive]` matches on `self` by reference even for packed
+        // structs; those borrows are let through rather than having every
+        // derived impl on a packed struct fail to compile.
+        let tcx = self.tcx;
+        let borrows = |fpat: &ast::Pat| {
+            pat_util::pat_contains_ref_binding(&tcx.def_map, fpat) &&
+                !tcx.sess.codemap().with_expn_info(fpat.span.expn_id, |info| {
+                    info.map_or(false, |info| info.callee.name.starts_with("derive("))
+                })
+        };
+        let fields_borrowed = match pat.node {
+            ast::PatStruct(_, ref fields, _) => fields.iter().any(|f| borrows(&*f.node.pat)),
+            ast::PatEnum(_, Some(ref fields)) => fields.iter().any(|f| borrows(&**f)),
+            _ => false,
+        };
+        if fields_borrowed && self.is_packed(ty::node_id_to_type(self.tcx, pat.id)) {
+            self.require_unsafe(pat.span, "borrow of packed field");
+        }
+
+        visit::walk_pat(self, pat);
+    }
 }
 
 pub fn check_crate(tcx: &ty::ctxt) {
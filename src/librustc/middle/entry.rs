@@ -10,9 +10,9 @@
 
 
 use session::{config, Session};
-use syntax::ast::{Name, NodeId, Item, ItemFn};
+use syntax::ast::{self, Name, NodeId, Item, ItemFn};
 use syntax::ast_map;
-use syntax::attr;
+use syntax::attr::{self, AttrMetaMethods};
 use syntax::codemap::Span;
 use syntax::parse::token;
 use syntax::visit;
@@ -46,6 +46,45 @@ impl<'a, 'ast, 'v> Visitor<'v> for EntryContext<'a, 'ast> {
     }
 }
 
+/// Records the subsystem requested with `#![windows_subsystem = "..."]`,
+/// which `back::link` passes on to the linker.
+pub fn find_windows_subsystem(session: &Session, krate: &ast::Crate) {
+    for attr in &krate.attrs {
+        if !attr.check_name("windows_subsystem") {
+            continue;
+        }
+
+        let subsystem = match attr.value_str() {
+            Some(ref s) if *s == "windows" || *s == "console" => s.to_string(),
+            _ => {
+                span_err!(session, attr.span, E0340,
+                          "invalid windows subsystem, expected \
+                           #![windows_subsystem = \"windows\"] or \
+                           #![windows_subsystem = \"console\"]");
+                return
+            }
+        };
+
+        let crate_types = session.crate_types.borrow();
+        if let Some(ty) = crate_types.iter().find(|&&ty| ty != config::CrateTypeExecutable) {
+            span_err!(session, attr.span, E0341,
+                      "`#![windows_subsystem]` only applies to executables, not to \
+                       crate type `{}`", ty);
+            return
+        }
+
+        if !session.target.target.options.is_like_windows {
+            session.span_warn(attr.span, &format!("`windows_subsystem` has no effect \
+                                                   when targeting `{}`",
+                                                  session.target.target.target_os));
+            return
+        }
+
+        *session.windows_subsystem.borrow_mut() = Some(subsystem);
+        return
+    }
+}
+
 pub fn find_entry_point(session: &Session, ast_map: &ast_map::Map) {
     let any_exe = session.crate_types.borrow().iter().any(|ty| {
         *ty == config::CrateTypeExecutable
@@ -109,6 +109,10 @@ pub trait ErrorReporting<'tcx> {
                                      trace: TypeTrace<'tcx>,
                                      terr: &ty::type_err<'tcx>);
 
+    fn note_object_lifetime_defaults(&self,
+                                     trace: &TypeTrace<'tcx>,
+                                     terr: &ty::type_err<'tcx>);
+
     fn values_str(&self, values: &ValuePairs<'tcx>) -> Option<String>;
 
     fn expected_found_str<T: UserString<'tcx> + Resolvable<'tcx>>(
@@ -387,8 +391,54 @@ impl<'a, 'tcx> ErrorReporting<'tcx> for InferCtxt<'a, 'tcx> {
     fn report_and_explain_type_error(&self,
                                      trace: TypeTrace<'tcx>,
                                      terr: &ty::type_err<'tcx>) {
-        self.report_type_error(trace, terr);
+        self.report_type_error(trace.clone(), terr);
         ty::note_and_explain_type_err(self.tcx, terr);
+        self.note_object_lifetime_defaults(&trace, terr);
+    }
+
+    /// When a region error involves an object type whose lifetime bound
+    /// was left to default to `'static`, say so: otherwise the `'static`
+    /// in the error seems to come out of nowhere.
+    fn note_object_lifetime_defaults(&self,
+                                     trace: &TypeTrace<'tcx>,
+                                     terr: &ty::type_err<'tcx>) {
+        match *terr {
+            ty::terr_regions_does_not_outlive(..) |
+            ty::terr_regions_not_same(..) |
+            ty::terr_regions_no_overlap(..) => {}
+            _ => return
+        }
+
+        let exp_found = match trace.values {
+            infer::Types(ref exp_found) => exp_found,
+            _ => return
+        };
+
+        let defaults = self.tcx.static_object_lifetime_defaults.borrow();
+        let mut traits = Vec::new();
+        for &ty in [exp_found.expected, exp_found.found].iter() {
+            let ty = self.resolve_type_vars_if_possible(&ty);
+            for t in ty.walk() {
+                if let ty::ty_trait(ref data) = t.sty {
+                    let def_id = data.principal_def_id();
+                    if data.bounds.region_bound == ty::ReStatic &&
+                       defaults.contains(&def_id) &&
+                       !traits.contains(&def_id) {
+                        traits.push(def_id);
+                    }
+                }
+            }
+        }
+
+        for &def_id in &traits {
+            let name = ty::item_path_str(self.tcx, def_id);
+            self.tcx.sess.span_note(
+                trace.origin.span(),
+                &format!("an object type `{}` written without a lifetime bound defaults \
+                          to `{} + 'static` here; write an explicit bound such as \
+                          `{} + 'a` if a shorter lifetime is intended",
+                         name, name, name));
+        }
     }
 
     /// Returns a string of the form "expected `{}`, found `{}`", or None if this is a derived
@@ -20,8 +20,8 @@ use graphviz as dot;
 
 use middle::ty;
 use middle::region::CodeExtent;
-use super::Constraint;
-use middle::infer::SubregionOrigin;
+use super::{Constraint, VarValue};
+use middle::infer::{self, SubregionOrigin};
 use middle::infer::region_inference::RegionVarBindings;
 use util::nodemap::{FnvHashMap, FnvHashSet};
 use util::ppaux::Repr;
@@ -34,6 +34,7 @@ use std::io;
 use std::io::prelude::*;
 use std::sync::atomic::{AtomicBool, Ordering, ATOMIC_BOOL_INIT};
 use syntax::ast;
+use syntax::ast_map;
 
 fn print_help_message() {
     println!("\
@@ -117,11 +118,97 @@ pub fn maybe_print_constraints_for<'a, 'tcx>(region_vars: &RegionVarBindings<'a,
     }
 }
 
+/// Implements `-Z dump-region-graph=FILTER`: writes the constraints of
+/// `subject_node`, along with the values inferred for each region variable,
+/// to `region-graph.PATH.NODE.dot` in the working directory if the item path
+/// of the subject contains `FILTER`.
+pub fn maybe_dump_region_graph<'a, 'tcx>(region_vars: &RegionVarBindings<'a, 'tcx>,
+                                         subject_node: ast::NodeId,
+                                         values: &[VarValue]) {
+    let tcx = region_vars.tcx;
+    let filter = match tcx.sess.opts.debugging_opts.dump_region_graph {
+        Some(ref filter) => filter,
+        None => return,
+    };
+
+    // The subject is usually a fn body; name it after the enclosing item.
+    let item = match tcx.map.find(subject_node) {
+        Some(ast_map::NodeBlock(..)) | Some(ast_map::NodeExpr(..)) => {
+            tcx.map.get_parent(subject_node)
+        }
+        _ => subject_node,
+    };
+    let path = tcx.map.path_to_string(item);
+    if !path.contains(&filter[..]) {
+        return;
+    }
+
+    let var_origins = region_vars.var_origins.borrow();
+    let labelled = var_origins.iter().zip(values.iter()).enumerate();
+    let var_labels: FnvHashMap<_, _> = labelled.map(|(i, (origin, value))| {
+        let vid = ty::RegionVid { index: i as u32 };
+        let snippet = tcx.sess.codemap().span_to_snippet(origin.span()).ok();
+        let snippet = snippet.as_ref().and_then(|s| s.lines().next()).unwrap_or("?");
+        let value = match *value {
+            VarValue::Value(r) => r.repr(tcx),
+            VarValue::NoValue => "(no value)".to_string(),
+            VarValue::ErrorValue => "ERROR".to_string(),
+        };
+        (vid, format!("{:?} `{}` = {}", vid, snippet, value))
+    }).collect();
+
+    let output_path = format!("region-graph.{}.{}.dot", path.replace("::", "-"), subject_node);
+    let constraints = &*region_vars.constraints.borrow();
+    let g = ConstraintGraph::new(tcx, format!("region_graph"), constraints, Some(var_labels));
+    let mut v = Vec::new();
+    dot::render(&g, &mut v).unwrap();
+    match File::create(&output_path).and_then(|mut f| f.write_all(&v)) {
+        Ok(()) => {}
+        Err(e) => {
+            tcx.sess.err(&format!("io error dumping region graph to `{}`: {}",
+                                  output_path, e))
+        }
+    }
+}
+
+/// The kind of a constraint's origin, without its spans and types.
+fn origin_kind(origin: &SubregionOrigin) -> &'static str {
+    match *origin {
+        infer::Subtype(..) => "Subtype",
+        infer::InfStackClosure(..) => "InfStackClosure",
+        infer::InvokeClosure(..) => "InvokeClosure",
+        infer::DerefPointer(..) => "DerefPointer",
+        infer::FreeVariable(..) => "FreeVariable",
+        infer::IndexSlice(..) => "IndexSlice",
+        infer::RelateObjectBound(..) => "RelateObjectBound",
+        infer::RelateParamBound(..) => "RelateParamBound",
+        infer::RelateRegionParamBound(..) => "RelateRegionParamBound",
+        infer::RelateDefaultParamBound(..) => "RelateDefaultParamBound",
+        infer::Reborrow(..) => "Reborrow",
+        infer::ReborrowUpvar(..) => "ReborrowUpvar",
+        infer::ReferenceOutlivesReferent(..) => "ReferenceOutlivesReferent",
+        infer::ExprTypeIsNotInScope(..) => "ExprTypeIsNotInScope",
+        infer::BindingTypeIsNotValidAtDecl(..) => "BindingTypeIsNotValidAtDecl",
+        infer::CallRcvr(..) => "CallRcvr",
+        infer::CallArg(..) => "CallArg",
+        infer::CallReturn(..) => "CallReturn",
+        infer::Operand(..) => "Operand",
+        infer::AddrOf(..) => "AddrOf",
+        infer::AutoBorrow(..) => "AutoBorrow",
+        infer::SafeDestructor(..) => "SafeDestructor",
+    }
+}
+
 struct ConstraintGraph<'a, 'tcx: 'a> {
     tcx: &'a ty::ctxt<'tcx>,
     graph_name: String,
     map: &'a FnvHashMap<Constraint, SubregionOrigin<'tcx>>,
     node_ids: FnvHashMap<Node, uint>,
+    // Labels for the region variables, set when dumping the graph of a
+    // single function with `-Z dump-region-graph`. Enclosing scope edges are
+    // left out of such graphs, and constraint edges only name their origin's
+    // kind.
+    var_labels: Option<FnvHashMap<ty::RegionVid, String>>,
 }
 
 #[derive(Clone, Hash, PartialEq, Eq, Debug, Copy)]
@@ -140,7 +227,9 @@ enum Edge {
 impl<'a, 'tcx> ConstraintGraph<'a, 'tcx> {
     fn new(tcx: &'a ty::ctxt<'tcx>,
            name: String,
-           map: &'a ConstraintMap<'tcx>) -> ConstraintGraph<'a, 'tcx> {
+           map: &'a ConstraintMap<'tcx>,
+           var_labels: Option<FnvHashMap<ty::RegionVid, String>>)
+           -> ConstraintGraph<'a, 'tcx> {
         let mut i = 0;
         let mut node_ids = FnvHashMap();
         {
@@ -156,16 +245,19 @@ impl<'a, 'tcx> ConstraintGraph<'a, 'tcx> {
                 add_node(n2);
             }
 
-            tcx.region_maps.each_encl_scope(|sub, sup| {
-                add_node(Node::Region(ty::ReScope(*sub)));
-                add_node(Node::Region(ty::ReScope(*sup)));
-            });
+            if var_labels.is_none() {
+                tcx.region_maps.each_encl_scope(|sub, sup| {
+                    add_node(Node::Region(ty::ReScope(*sub)));
+                    add_node(Node::Region(ty::ReScope(*sup)));
+                });
+            }
         }
 
         ConstraintGraph { tcx: tcx,
                           graph_name: name,
                           map: map,
-                          node_ids: node_ids }
+                          node_ids: node_ids,
+                          var_labels: var_labels }
     }
 }
 
@@ -188,14 +280,20 @@ impl<'a, 'tcx> dot::Labeller<'a, Node, Edge> for ConstraintGraph<'a, 'tcx> {
     }
     fn node_label(&self, n: &Node) -> dot::LabelText {
         match *n {
-            Node::RegionVid(n_vid) =>
-                dot::LabelText::label(format!("{:?}", n_vid)),
+            Node::RegionVid(n_vid) => {
+                match self.var_labels.as_ref().and_then(|labels| labels.get(&n_vid)) {
+                    Some(label) => dot::LabelText::label(label.clone()),
+                    None => dot::LabelText::label(format!("{:?}", n_vid)),
+                }
+            }
             Node::Region(n_rgn) =>
                 dot::LabelText::label(format!("{}", n_rgn.repr(self.tcx))),
         }
     }
     fn edge_label(&self, e: &Edge) -> dot::LabelText {
         match *e {
+            Edge::Constraint(ref c) if self.var_labels.is_some() =>
+                dot::LabelText::label(origin_kind(self.map.get(c).unwrap())),
             Edge::Constraint(ref c) =>
                 dot::LabelText::label(format!("{}", self.map.get(c).unwrap().repr(self.tcx))),
             Edge::EnclScope(..) =>
@@ -236,9 +334,11 @@ impl<'a, 'tcx> dot::GraphWalk<'a, Node, Edge> for ConstraintGraph<'a, 'tcx> {
     fn edges(&self) -> dot::Edges<Edge> {
         debug!("constraint graph has {} edges", self.map.len());
         let mut v : Vec<_> = self.map.keys().map(|e| Edge::Constraint(*e)).collect();
-        self.tcx.region_maps.each_encl_scope(|sub, sup| {
-            v.push(Edge::EnclScope(*sub, *sup))
-        });
+        if self.var_labels.is_none() {
+            self.tcx.region_maps.each_encl_scope(|sub, sup| {
+                v.push(Edge::EnclScope(*sub, *sup))
+            });
+        }
         debug!("region graph has {} edges", v.len());
         Cow::Owned(v)
     }
@@ -260,7 +360,7 @@ fn dump_region_constraints_to<'a, 'tcx:'a >(tcx: &'a ty::ctxt<'tcx>,
                                             map: &ConstraintMap<'tcx>,
                                             path: &str) -> io::Result<()> {
     debug!("dump_region_constraints map (len: {}) path: {}", map.len(), path);
-    let g = ConstraintGraph::new(tcx, format!("region_constraints"), map);
+    let g = ConstraintGraph::new(tcx, format!("region_constraints"), map, None);
     debug!("dump_region_constraints calling render");
     let mut v = Vec::new();
     dot::render(&g, &mut v).unwrap();
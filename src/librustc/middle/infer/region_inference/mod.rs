@@ -980,6 +980,7 @@ impl<'a, 'tcx> RegionVarBindings<'a, 'tcx> {
             self.extract_values_and_collect_conflicts(&var_data[..],
                                                       errors);
         self.collect_concrete_region_errors(&values, errors);
+        graphviz::maybe_dump_region_graph(self, subject, &values);
         values
     }
 
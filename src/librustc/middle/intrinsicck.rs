@@ -13,7 +13,7 @@ use middle::def::DefFn;
 use middle::subst::{Subst, Substs, EnumeratedItems};
 use middle::ty::{TransmuteRestriction, ctxt, ty_bare_fn};
 use middle::ty::{self, Ty};
-use util::ppaux::Repr;
+use util::ppaux::{Repr, ty_to_string};
 
 use syntax::abi::RustIntrinsic;
 use syntax::ast::DefId;
@@ -51,29 +51,52 @@ struct IntrinsicCheckingVisitor<'a, 'tcx: 'a> {
     dummy_unsized_ty: Ty<'tcx>,
 }
 
-impl<'a, 'tcx> IntrinsicCheckingVisitor<'a, 'tcx> {
-    fn def_id_is_transmute(&self, def_id: DefId) -> bool {
-        let intrinsic = match ty::lookup_item_type(self.tcx, def_id).ty.sty {
-            ty::ty_bare_fn(_, ref bfty) => bfty.abi == RustIntrinsic,
-            _ => return false
-        };
-        if def_id.krate == ast::LOCAL_CRATE {
-            match self.tcx.map.get(def_id.node) {
-                NodeForeignItem(ref item) if intrinsic => {
-                    token::get_ident(item.ident) ==
-                        token::intern_and_get_ident("transmute")
-                }
-                _ => false,
-            }
-        } else {
-            match csearch::get_item_path(self.tcx, def_id).last() {
-                Some(ref last) if intrinsic => {
-                    token::get_name(last.name()) ==
-                        token::intern_and_get_ident("transmute")
-                }
-                _ => false,
+/// Whether `ty` can be operated on by the `atomic_*` intrinsics: LLVM only
+/// supports atomic operations on integers and thin pointers.
+pub fn is_atomic_operand_type<'tcx>(ty: Ty<'tcx>) -> bool {
+    match ty.sty {
+        ty::ty_int(_) | ty::ty_uint(_) => true,
+        ty::ty_ptr(ref mt) => {
+            match mt.ty.sty {
+                ty::ty_str | ty::ty_vec(_, None) | ty::ty_trait(..) => false,
+                _ => true
             }
         }
+        _ => false
+    }
+}
+
+/// Returns the name of the intrinsic `def_id` refers to, if it is one.
+pub fn intrinsic_name(tcx: &ctxt, def_id: DefId) -> Option<token::InternedString> {
+    let intrinsic = match ty::lookup_item_type(tcx, def_id).ty.sty {
+        ty::ty_bare_fn(_, ref bfty) => bfty.abi == RustIntrinsic,
+        _ => return None
+    };
+    if !intrinsic {
+        return None;
+    }
+    if def_id.krate == ast::LOCAL_CRATE {
+        match tcx.map.get(def_id.node) {
+            NodeForeignItem(ref item) => Some(token::get_ident(item.ident)),
+            _ => None,
+        }
+    } else {
+        csearch::get_item_path(tcx, def_id).last().map(|last| token::get_name(last.name()))
+    }
+}
+
+impl<'a, 'tcx> IntrinsicCheckingVisitor<'a, 'tcx> {
+    fn check_atomic(&self, span: Span, name: &str, operand: Ty<'tcx>) {
+        // Operands involving type parameters can only be checked once
+        // they have been substituted, during trans.
+        if ty::type_has_params(operand) || ty::type_has_self(operand) {
+            return;
+        }
+        if !is_atomic_operand_type(operand) {
+            span_err!(self.tcx.sess, span, E0331,
+                      "`{}` can only operate on integer and raw pointer types, found `{}`",
+                      name, ty_to_string(self.tcx, operand));
+        }
     }
 
     fn check_transmute(&self, span: Span, from: Ty<'tcx>, to: Ty<'tcx>, id: ast::NodeId) {
@@ -252,24 +275,36 @@ impl<'a, 'tcx, 'v> Visitor<'v> for IntrinsicCheckingVisitor<'a, 'tcx> {
 
     fn visit_expr(&mut self, expr: &ast::Expr) {
         if let ast::ExprPath(..) = expr.node {
-            match ty::resolve_expr(self.tcx, expr) {
-                DefFn(did, _) if self.def_id_is_transmute(did) => {
-                    let typ = ty::node_id_to_type(self.tcx, expr.id);
-                    match typ.sty {
-                        ty_bare_fn(_, ref bare_fn_ty) if bare_fn_ty.abi == RustIntrinsic => {
-                            if let ty::FnConverging(to) = bare_fn_ty.sig.0.output {
-                                let from = bare_fn_ty.sig.0.inputs[0];
-                                self.check_transmute(expr.span, from, to, expr.id);
-                            }
-                        }
-                        _ => {
-                            self.tcx
-                                .sess
-                                .span_bug(expr.span, "transmute wasn't a bare fn?!");
+            let name = match ty::resolve_expr(self.tcx, expr) {
+                DefFn(did, _) => intrinsic_name(self.tcx, did),
+                _ => None
+            };
+            if let Some(name) = name {
+                let typ = ty::node_id_to_type(self.tcx, expr.id);
+                let sig = match typ.sty {
+                    ty_bare_fn(_, ref bare_fn_ty) if bare_fn_ty.abi == RustIntrinsic => {
+                        &bare_fn_ty.sig.0
+                    }
+                    _ => {
+                        self.tcx
+                            .sess
+                            .span_bug(expr.span, "intrinsic wasn't a bare fn?!");
+                    }
+                };
+                if &name[..] == "transmute" {
+                    if let ty::FnConverging(to) = sig.output {
+                        let from = sig.inputs[0];
+                        self.check_transmute(expr.span, from, to, expr.id);
+                    }
+                } else if name.starts_with("atomic_") && !name.starts_with("atomic_fence") {
+                    // Every atomic except `fence` takes a pointer to its
+                    // operand first.
+                    if let Some(ptr) = sig.inputs.get(0) {
+                        if let ty::ty_ptr(ref mt) = ptr.sty {
+                            self.check_atomic(expr.span, &name, mt.ty);
                         }
                     }
                 }
-                _ => {}
             }
         }
 
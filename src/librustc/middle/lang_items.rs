@@ -30,7 +30,7 @@ use util::nodemap::FnvHashMap;
 use syntax::ast;
 use syntax::ast_util::local_def;
 use syntax::attr::AttrMetaMethods;
-use syntax::codemap::{DUMMY_SP, Span};
+use syntax::codemap::Span;
 use syntax::parse::token::InternedString;
 use syntax::visit::Visitor;
 use syntax::visit;
@@ -195,10 +195,11 @@ impl<'a> LanguageItemCollector<'a> {
 
     pub fn collect_external_language_items(&mut self) {
         let crate_store = &self.session.cstore;
-        crate_store.iter_crate_data(|crate_number, _crate_metadata| {
+        crate_store.iter_crate_data(|crate_number, crate_metadata| {
             each_lang_item(crate_store, crate_number, |node_id, item_index| {
                 let def_id = ast::DefId { krate: crate_number, node: node_id };
-                self.collect_item(item_index, def_id, DUMMY_SP);
+                // Blame a duplicate on the `extern crate` that brought it in.
+                self.collect_item(item_index, def_id, crate_metadata.span);
                 true
             });
         })
@@ -282,6 +283,7 @@ lets_do_this! {
     RangeFromStructLangItem,         "range_from",              range_from_struct;
     RangeToStructLangItem,           "range_to",                range_to_struct;
     RangeFullStructLangItem,         "range_full",              range_full_struct;
+    RangeInclusiveStructLangItem,    "range_inclusive",         range_inclusive_struct;
 
     UnsafeCellTypeLangItem,          "unsafe_cell",             unsafe_cell_type;
 
@@ -292,6 +294,9 @@ lets_do_this! {
     FnMutTraitLangItem,              "fn_mut",                  fn_mut_trait;
     FnOnceTraitLangItem,             "fn_once",                 fn_once_trait;
 
+    IteratorTraitLangItem,           "iterator",                iterator_trait;
+    IntoIteratorTraitLangItem,       "into_iterator",           into_iterator_trait;
+
     EqTraitLangItem,                 "eq",                      eq_trait;
     OrdTraitLangItem,                "ord",                     ord_trait;
 
@@ -309,6 +314,7 @@ lets_do_this! {
     PanicFnLangItem,                 "panic",                   panic_fn;
     PanicBoundsCheckFnLangItem,      "panic_bounds_check",      panic_bounds_check_fn;
     PanicFmtLangItem,                "panic_fmt",               panic_fmt;
+    ForeignUnwindFnLangItem,         "foreign_unwind",          foreign_unwind_fn;
 
     ExchangeMallocFnLangItem,        "exchange_malloc",         exchange_malloc_fn;
     ExchangeFreeFnLangItem,          "exchange_free",           exchange_free_fn;
@@ -1105,8 +1105,12 @@ impl<'a, 'tcx> Liveness<'a, 'tcx> {
           ast::ExprAssign(ref l, ref r) => {
             // see comment on lvalues in
             // propagate_through_lvalue_components()
-            let succ = self.write_lvalue(&**l, succ, ACC_WRITE);
-            let succ = self.propagate_through_lvalue_components(&**l, succ);
+            let succ = if self.partially_written_local(&**l).is_some() {
+                self.propagate_through_partial_lvalue(&**l, succ)
+            } else {
+                let succ = self.write_lvalue(&**l, succ, ACC_WRITE);
+                self.propagate_through_lvalue_components(&**l, succ)
+            };
             self.propagate_through_expr(&**r, succ)
           }
 
@@ -1182,7 +1186,7 @@ impl<'a, 'tcx> Liveness<'a, 'tcx> {
             self.propagate_through_expr(&**l, r_succ)
           }
 
-          ast::ExprRange(ref e1, ref e2) => {
+          ast::ExprRange(ref e1, ref e2, _) => {
             let succ = e2.as_ref().map_or(succ, |e| self.propagate_through_expr(&**e, succ));
             e1.as_ref().map_or(succ, |e| self.propagate_through_expr(&**e, succ))
           }
@@ -1284,6 +1288,62 @@ impl<'a, 'tcx> Liveness<'a, 'tcx> {
         }
     }
 
+    /// If assigning to `expr` overwrites only part of a local variable held
+    /// by value, as in `x.f = ...`, `x.0.f = ...` or `x[i] = ...` for an
+    /// array `x`, returns the path to that variable. Locals whose destructor
+    /// could observe the write are excluded.
+    fn partially_written_local<'b>(&self, expr: &'b Expr) -> Option<&'b Expr> {
+        let tcx = self.ir.tcx;
+        let base = match expr.node {
+            ast::ExprField(ref base, _) | ast::ExprTupField(ref base, _) => &**base,
+            ast::ExprIndex(ref base, _) => {
+                // overloaded indexing is a method call on the base
+                let method_call = ty::MethodCall::expr(expr.id);
+                if tcx.method_map.borrow().contains_key(&method_call) {
+                    return None;
+                }
+                &**base
+            }
+            _ => return None
+        };
+        // writes through a pointer are not writes to the local
+        if tcx.adjustments.borrow().contains_key(&base.id) {
+            return None;
+        }
+        match ty::expr_ty(tcx, base).sty {
+            ty::ty_struct(did, _) if !ty::has_dtor(tcx, did) => {}
+            ty::ty_tup(_) | ty::ty_vec(_, Some(_)) => {}
+            _ => return None
+        }
+        match base.node {
+            ast::ExprPath(..) => match tcx.def_map.borrow().get(&base.id).unwrap().full_def() {
+                DefLocal(_) => Some(base),
+                _ => None
+            },
+            _ => self.partially_written_local(base)
+        }
+    }
+
+    // A partial write of a local neither reads it nor kills its earlier
+    // value: only the index expressions along the way are evaluated.
+    fn propagate_through_partial_lvalue(&mut self, expr: &Expr, succ: LiveNode)
+                                        -> LiveNode {
+        match expr.node {
+            ast::ExprField(ref base, _) | ast::ExprTupField(ref base, _) => {
+                self.propagate_through_partial_lvalue(&**base, succ)
+            }
+            ast::ExprIndex(ref base, ref idx) => {
+                let succ = self.propagate_through_expr(&**idx, succ);
+                self.propagate_through_partial_lvalue(&**base, succ)
+            }
+            _ => {
+                let ln = self.live_node(expr.id, expr.span);
+                self.init_from_succ(ln, succ);
+                ln
+            }
+        }
+    }
+
     // see comment on propagate_through_lvalue()
     fn write_lvalue(&mut self, expr: &Expr, succ: LiveNode, acc: u32)
                     -> LiveNode {
@@ -1434,7 +1494,10 @@ fn check_arm(this: &mut Liveness, arm: &ast::Arm) {
 fn check_expr(this: &mut Liveness, expr: &Expr) {
     match expr.node {
       ast::ExprAssign(ref l, ref r) => {
-        this.check_lvalue(&**l);
+        match this.partially_written_local(&**l) {
+            Some(local) => this.check_partial_lvalue(&**l, local),
+            None => this.check_lvalue(&**l),
+        }
         this.visit_expr(&**r);
 
         visit::walk_expr(this, expr);
@@ -1460,8 +1523,16 @@ fn check_expr(this: &mut Liveness, expr: &Expr) {
         visit::walk_expr(this, expr);
       }
 
+      ast::ExprMethodCall(ref ident, _, ref args) => {
+        if &token::get_ident(ident.node)[..] == "clone" && args.len() == 1 {
+            this.check_clone_receiver(expr, &*args[0]);
+        }
+
+        visit::walk_expr(this, expr);
+      }
+
       // no correctness conditions related to liveness
-      ast::ExprCall(..) | ast::ExprMethodCall(..) | ast::ExprIf(..) |
+      ast::ExprCall(..) | ast::ExprIf(..) |
       ast::ExprMatch(..) | ast::ExprWhile(..) | ast::ExprLoop(..) |
       ast::ExprIndex(..) | ast::ExprField(..) | ast::ExprTupField(..) |
       ast::ExprVec(..) | ast::ExprTup(..) | ast::ExprBinary(..) |
@@ -1541,13 +1612,21 @@ impl<'a, 'tcx> Liveness<'a, 'tcx> {
                         let last_stmt = body.stmts.first().unwrap();
                         let original_span = original_sp(self.ir.tcx.sess.codemap(),
                                                         last_stmt.span, sp);
-                        let span_semicolon = Span {
-                            lo: original_span.hi - BytePos(1),
-                            hi: original_span.hi,
-                            expn_id: original_span.expn_id
-                        };
-                        self.ir.tcx.sess.span_help(
-                            span_semicolon, "consider removing this semicolon:");
+                        let msg = "consider removing this semicolon:";
+                        match self.ir.tcx.sess.codemap().span_to_snippet(original_span) {
+                            Ok(ref stmt) if stmt.ends_with(";") => {
+                                let expr = stmt[..stmt.len() - 1].trim_right().to_string();
+                                self.ir.tcx.sess.span_suggestion(original_span, msg, expr);
+                            }
+                            _ => {
+                                let span_semicolon = Span {
+                                    lo: original_span.hi - BytePos(1),
+                                    hi: original_span.hi,
+                                    expn_id: original_span.expn_id
+                                };
+                                self.ir.tcx.sess.span_help(span_semicolon, msg);
+                            }
+                        }
                     }
                 }
             }
@@ -1584,6 +1663,35 @@ impl<'a, 'tcx> Liveness<'a, 'tcx> {
         }
     }
 
+    fn check_clone_receiver(&mut self, expr: &Expr, receiver: &Expr) {
+        if let ast::ExprPath(..) = receiver.node {
+            if let DefLocal(nid) = self.ir.tcx.def_map.borrow().get(&receiver.id)
+                                                               .unwrap()
+                                                               .full_def() {
+                // `x.clone()` where nothing reads `x` afterwards: moving `x`
+                // would have done. The `redundant_clone` lint decides whether
+                // that is really the case once types and borrows are known.
+                let ln = self.live_node(receiver.id, receiver.span);
+                let var = self.variable(nid, receiver.span);
+                if self.live_on_exit(ln, var).is_none() {
+                    self.ir.tcx.dead_clone_receivers.borrow_mut().insert(expr.id);
+                }
+            }
+        }
+    }
+
+    fn check_partial_lvalue(&mut self, expr: &Expr, local: &Expr) {
+        if let DefLocal(nid) = self.ir.tcx.def_map.borrow().get(&local.id)
+                                                           .unwrap()
+                                                           .full_def() {
+            // Nothing reads the variable after part of it is overwritten.
+            let ln = self.live_node(local.id, local.span);
+            let var = self.variable(nid, local.span);
+            self.warn_about_dead_assign(expr.span, expr.id, ln, var);
+        }
+        visit::walk_expr(self, expr);
+    }
+
     fn should_warn(&self, var: Variable) -> Option<String> {
         let name = self.ir.variable_name(var);
         if name.len() == 0 || name.as_bytes()[0] == ('_' as u8) {
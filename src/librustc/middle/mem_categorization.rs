@@ -429,7 +429,8 @@ impl<'t,'tcx,TYPER:Typer<'tcx>> MemCategorizationContext<'t,TYPER> {
             Some(adjustment) => {
                 match *adjustment {
                     ty::AdjustReifyFnPointer(..) |
-                    ty::AdjustUnsafeFnPointer(..) => {
+                    ty::AdjustUnsafeFnPointer(..) |
+                    ty::AdjustClosureFnPointer(..) => {
                         debug!("cat_expr(AdjustReifyFnPointer): {}",
                                expr.repr(self.tcx()));
                         // Convert a bare fn to a closure by adding NULL env.
@@ -522,7 +523,13 @@ impl<'t,'tcx,TYPER:Typer<'tcx>> MemCategorizationContext<'t,TYPER> {
                     // is an rvalue. That is what we will be
                     // dereferencing.
                     let base_cmt = self.cat_rvalue_node(expr.id(), expr.span(), ret_ty);
-                    self.cat_deref_common(expr, base_cmt, 1, elem_ty, Some(context), true)
+                    let cmt = try!(self.cat_deref_common(expr, base_cmt, 1, elem_ty,
+                                                         Some(context), true));
+
+                    // The first argument is `&Self` (or `&mut Self`).
+                    let self_ty = ty::ty_fn_sig(method_ty).input(0).0;
+                    let indexed_ty = ty::deref(self_ty, true).map_or(self_ty, |mt| mt.ty);
+                    Ok(str_contents_immutable(cmt, indexed_ty))
                 }
                 None => {
                     self.cat_index(expr, try!(self.cat_expr(&**base)), context)
@@ -583,7 +590,7 @@ impl<'t,'tcx,TYPER:Typer<'tcx>> MemCategorizationContext<'t,TYPER> {
           def::DefTrait(_) | def::DefTy(..) | def::DefPrimTy(_) |
           def::DefTyParam(..) | def::DefRegion(_) |
           def::DefLabel(_) | def::DefSelfTy(..) |
-          def::DefAssociatedTy(..) => {
+          def::DefAssociatedTy(..) | def::DefErr => {
               Ok(Rc::new(cmt_ {
                   id:id,
                   span:span,
@@ -1009,8 +1016,8 @@ impl<'t,'tcx,TYPER:Typer<'tcx>> MemCategorizationContext<'t,TYPER> {
         };
 
         let m = base_cmt.mutbl.inherit();
-        return Ok(interior(elt, base_cmt.clone(), base_cmt.ty,
-                           m, context, element_ty));
+        let cmt = interior(elt, base_cmt.clone(), base_cmt.ty, m, context, element_ty);
+        return Ok(str_contents_immutable(cmt, base_cmt.ty));
 
         fn interior<'tcx, N: ast_node>(elt: &N,
                                        of_cmt: cmt<'tcx>,
@@ -1602,6 +1609,18 @@ impl<'tcx> Repr<'tcx> for InteriorKind {
     }
 }
 
+/// Indexing into a string never yields a mutable place, however the string
+/// itself was reached: writing through it could break the UTF-8 invariant.
+/// Every mutation path (assignment, `&mut`, `ref mut` bindings) then gets the
+/// usual borrowck errors. `indexed_ty` is the type of the value being indexed.
+fn str_contents_immutable<'tcx>(cmt: cmt<'tcx>, indexed_ty: Ty<'tcx>) -> cmt<'tcx> {
+    if indexed_ty.sty == ty::ty_str && cmt.mutbl != McImmutable {
+        Rc::new(cmt_ { mutbl: McImmutable, ..(*cmt).clone() })
+    } else {
+        cmt
+    }
+}
+
 fn element_kind(t: Ty) -> ElementKind {
     match t.sty {
         ty::ty_rptr(_, ty::mt{ty, ..}) |
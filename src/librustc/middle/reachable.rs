@@ -27,6 +27,7 @@ use syntax::ast;
 use syntax::ast_map;
 use syntax::ast_util::is_local;
 use syntax::attr;
+use syntax::parse::token;
 use syntax::visit::Visitor;
 use syntax::visit;
 
@@ -104,6 +105,13 @@ impl<'a, 'tcx, 'v> Visitor<'v> for ReachableContext<'a, 'tcx> {
                     }
                 };
 
+                // A path to a trait method such as `Trait::method(x)` may
+                // end up calling any impl of that trait.
+                if let def::DefMethod(method_did, def::FromTrait(trait_did)) = def {
+                    let name = ty::impl_or_trait_item(self.tcx, method_did).name();
+                    self.mark_trait_impls_reachable(trait_did, name);
+                }
+
                 let def_id = def.def_id();
                 if is_local(def_id) {
                     if self.def_id_represents_local_inlined_item(def_id) {
@@ -126,21 +134,35 @@ impl<'a, 'tcx, 'v> Visitor<'v> for ReachableContext<'a, 'tcx> {
                     }
                 }
             }
-            ast::ExprMethodCall(..) => {
-                let method_call = ty::MethodCall::expr(expr.id);
-                match (*self.tcx.method_map.borrow()).get(&method_call).unwrap().origin {
-                    ty::MethodStatic(def_id) => {
-                        if is_local(def_id) {
-                            if self.def_id_represents_local_inlined_item(def_id) {
-                                self.worklist.push(def_id.node)
-                            }
-                            self.reachable_symbols.insert(def_id.node);
-                        }
-                    }
-                    _ => {}
+            _ => {}
+        }
+
+        // Method calls, as well as overloaded operators, indexing and
+        // derefs, are recorded in the method map.
+        let method_call = ty::MethodCall::expr(expr.id);
+        let origin = self.tcx.method_map.borrow().get(&method_call).map(|m| m.origin.clone());
+        match origin {
+            Some(ty::MethodStatic(def_id)) => {
+                self.mark_method_reachable(def_id);
+            }
+            Some(ty::MethodTypeParam(ref param)) => {
+                let trait_did = param.trait_ref.def_id;
+                let name = ty::trait_item(self.tcx, trait_did, param.method_num).name();
+                match param.impl_def_id {
+                    // The call was resolved to an impl on a concrete type...
+                    Some(impl_did) => self.mark_impl_method_reachable(impl_did, trait_did, name),
+                    // ...or it goes through a type parameter, in which case
+                    // the impl is only chosen when the caller is
+                    // monomorphized, possibly in another crate.
+                    None => self.mark_trait_impls_reachable(trait_did, name),
                 }
             }
-            _ => {}
+            Some(ty::MethodTraitObject(ref object)) => {
+                let trait_did = object.trait_ref.def_id;
+                let name = ty::trait_item(self.tcx, trait_did, object.method_num).name();
+                self.mark_trait_impls_reachable(trait_did, name);
+            }
+            Some(ty::MethodStaticClosure(_)) | None => {}
         }
 
         visit::walk_expr(self, expr)
@@ -221,6 +243,61 @@ impl<'a, 'tcx> ReachableContext<'a, 'tcx> {
         }
     }
 
+    // Marks a method that is called from a reachable item, scanning it in
+    // turn if it may be inlined into other crates.
+    fn mark_method_reachable(&mut self, def_id: ast::DefId) {
+        if is_local(def_id) {
+            if self.def_id_represents_local_inlined_item(def_id) {
+                self.worklist.push(def_id.node)
+            }
+            self.reachable_symbols.insert(def_id.node);
+        }
+    }
+
+    // Marks the method `name` of the impl `impl_did` of `trait_did`, or the
+    // trait's default method if the impl does not override it. The impl
+    // itself may be private; what matters is that the inlined caller will
+    // refer to the method's symbol from another crate.
+    fn mark_impl_method_reachable(&mut self, impl_did: ast::DefId,
+                                  trait_did: ast::DefId, name: ast::Name) {
+        if !is_local(impl_did) {
+            return
+        }
+        let tcx = self.tcx;
+        let impl_method = tcx.impl_items.borrow().get(&impl_did).and_then(|items| {
+            items.iter()
+                 .filter_map(|item| match *item {
+                     ty::MethodTraitItemId(did) => Some(did),
+                     ty::TypeTraitItemId(_) => None,
+                 })
+                 .find(|&did| ty::impl_or_trait_item(tcx, did).name() == name)
+        });
+        match impl_method {
+            Some(did) => self.mark_method_reachable(did),
+            None => {
+                let default = ty::trait_items(tcx, trait_did).iter()
+                                 .filter_map(|item| item.as_opt_method())
+                                 .find(|method| method.name == name)
+                                 .map(|method| method.def_id);
+                if let Some(did) = default {
+                    self.mark_method_reachable(did);
+                }
+            }
+        }
+    }
+
+    // Marks the method `name` of every local impl of `trait_did`, for calls
+    // whose impl is not known until monomorphization.
+    fn mark_trait_impls_reachable(&mut self, trait_did: ast::DefId, name: ast::Name) {
+        let impls = match self.tcx.trait_impls.borrow().get(&trait_did) {
+            Some(impls) => impls.borrow().clone(),
+            None => return,
+        };
+        for impl_did in impls {
+            self.mark_impl_method_reachable(impl_did, trait_did, name);
+        }
+    }
+
     // Step 2: Mark all symbols that the symbols on the worklist touch.
     fn propagate(&mut self) {
         let mut scanned = HashSet::new();
@@ -381,6 +458,53 @@ pub fn find_reachable(tcx: &ty::ctxt,
     // Step 3: Mark all destructors as reachable.
     reachable_context.mark_destructors_reachable();
 
+    if tcx.sess.opts.debugging_opts.print_reachable {
+        print_reachable(tcx, &reachable_context.reachable_symbols);
+    }
+
     // Return the set of reachable symbols.
     reachable_context.reachable_symbols
 }
+
+/// The reachable fns and statics whose symbol is not mangled, because of
+/// `#[no_mangle]` or `#[export_name]`, with the name they are exported under,
+/// sorted by node id. Items with an explicit `#[linkage]` are left out, as
+/// they may be meant to override or be overridden by another definition.
+pub fn unmangled_symbols(tcx: &ty::ctxt, reachable: &NodeSet) -> Vec<(String, ast::NodeId)> {
+    let mut ids: Vec<_> = reachable.iter().cloned().collect();
+    ids.sort();
+    ids.into_iter().filter_map(|id| {
+        let item = match tcx.map.find(id) {
+            Some(ast_map::NodeItem(item)) => item,
+            _ => return None
+        };
+        match item.node {
+            ast::ItemFn(_, _, _, ref generics, _) if !generics.is_type_parameterized() => {}
+            ast::ItemStatic(..) => {}
+            _ => return None
+        }
+        if attr::contains_name(&item.attrs, "linkage") {
+            return None;
+        }
+        match attr::first_attr_value_str_by_name(&item.attrs, "export_name") {
+            Some(name) => Some((name.to_string(), id)),
+            None if attr::contains_name(&item.attrs, "no_mangle") => {
+                Some((token::get_ident(item.ident).to_string(), id))
+            }
+            None => None
+        }
+    }).collect()
+}
+
+fn print_reachable(tcx: &ty::ctxt, reachable: &NodeSet) {
+    let mut paths: Vec<String> = reachable.iter().map(|&id| {
+        match tcx.map.find(id) {
+            Some(_) => tcx.map.path_to_string(id),
+            None => tcx.map.node_to_string(id),
+        }
+    }).collect();
+    paths.sort();
+    for path in &paths {
+        println!("print-reachable: {}", path);
+    }
+}
@@ -14,6 +14,11 @@
 // on how deeply they recurse to prevent stack overflow. Users can override
 // this via an attribute on the crate like `#![recursion_limit="22"]`. This pass
 // just peeks and looks for that attribute.
+//
+// The same goes for the amount of work `macro_rules!` matching may do on a
+// single invocation, set with `#![macro_work_limit="N"]`, and for the number
+// of expressions a function body may have before the `large_fn_bodies` lint
+// reports it, set with `#![fn_expr_limit="N"]`.
 
 use session::Session;
 use syntax::ast;
@@ -36,3 +41,39 @@ pub fn update_recursion_limit(sess: &Session, krate: &ast::Crate) {
                                   expected #![recursion_limit=\"N\"]");
     }
 }
+
+pub fn update_macro_work_limit(sess: &Session, krate: &ast::Crate) {
+    for attr in &krate.attrs {
+        if !attr.check_name("macro_work_limit") {
+            continue;
+        }
+
+        if let Some(s) = attr.value_str() {
+            if let Some(n) = s.parse().ok() {
+                sess.macro_work_limit.set(n);
+                return;
+            }
+        }
+
+        span_err!(sess, attr.span, E0336, "malformed macro work limit attribute, \
+                                  expected #![macro_work_limit=\"N\"]");
+    }
+}
+
+pub fn update_fn_expr_limit(sess: &Session, krate: &ast::Crate) {
+    for attr in &krate.attrs {
+        if !attr.check_name("fn_expr_limit") {
+            continue;
+        }
+
+        if let Some(s) = attr.value_str() {
+            if let Some(n) = s.parse().ok() {
+                sess.fn_expr_limit.set(n);
+                return;
+            }
+        }
+
+        span_err!(sess, attr.span, E0339, "malformed function expression limit attribute, \
+                                  expected #![fn_expr_limit=\"N\"]");
+    }
+}
@@ -18,6 +18,7 @@
 pub use self::DefRegion::*;
 use self::ScopeChain::*;
 
+use lint;
 use session::Session;
 use middle::def::{self, DefMap};
 use middle::region;
@@ -90,6 +91,7 @@ type Scope<'a> = &'a ScopeChain<'a>;
 static ROOT_SCOPE: ScopeChain<'static> = RootScope;
 
 pub fn krate(sess: &Session, krate: &ast::Crate, def_map: &DefMap) -> NamedRegionMap {
+    let errors = sess.err_count();
     let mut named_region_map = NodeMap();
     visit::walk_crate(&mut LifetimeContext {
         sess: sess,
@@ -98,7 +100,7 @@ pub fn krate(sess: &Session, krate: &ast::Crate, def_map: &DefMap) -> NamedRegio
         def_map: def_map,
         trait_ref_hack: false,
     }, krate);
-    sess.abort_if_errors();
+    sess.abort_if_new_errors(errors);
     named_region_map
 }
 
@@ -481,7 +483,7 @@ impl<'a> LifetimeContext<'a> {
                 }
             }
 
-            // It is a soft error to shadow a lifetime within a parent scope.
+            // Shadowing a lifetime of a parent scope is linted.
             self.check_lifetime_def_for_shadowing(old_scope, &lifetime_i.lifetime);
 
             for bound in &lifetime_i.bounds {
@@ -507,19 +509,16 @@ impl<'a> LifetimeContext<'a> {
                 EarlyScope(_, lifetimes, s) |
                 LateScope(lifetimes, s) => {
                     if let Some((_, lifetime_def)) = search_lifetimes(lifetimes, lifetime) {
-                        self.sess.span_warn(
+                        self.sess.add_lint_with_notes(
+                            lint::builtin::SHADOWED_LIFETIMES,
+                            lifetime.id,
                             lifetime.span,
-                            &format!("lifetime name `{}` shadows another \
+                            format!("lifetime name `{}` shadows another \
                                      lifetime name that is already in scope",
-                                     token::get_name(lifetime.name)));
-                        self.sess.span_note(
-                            lifetime_def.span,
-                            &format!("shadowed lifetime `{}` declared here",
-                                     token::get_name(lifetime.name)));
-                        self.sess.span_note(
-                            lifetime.span,
-                            "shadowed lifetimes are deprecated \
-                             and will become a hard error before 1.0");
+                                    token::get_name(lifetime.name)),
+                            vec![(lifetime_def.span,
+                                  format!("shadowed lifetime `{}` declared here",
+                                          token::get_name(lifetime.name)))]);
                         return;
                     }
 
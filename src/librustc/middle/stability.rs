@@ -24,7 +24,7 @@ use syntax::ast;
 use syntax::ast::{Attribute, Block, Crate, DefId, FnDecl, NodeId, Variant};
 use syntax::ast::{Item, Generics, StructField};
 use syntax::ast_util::is_local;
-use syntax::attr::{Stability, AttrMetaMethods};
+use syntax::attr::{Stability, Deprecation, AttrMetaMethods};
 use syntax::visit::{FnKind, Visitor};
 use syntax::feature_gate::emit_feature_err;
 use util::nodemap::{NodeMap, DefIdMap, FnvHashSet, FnvHashMap};
@@ -38,6 +38,9 @@ pub struct Index {
     staged_api: bool,
     // stability for crate-local items; unmarked stability == no entry
     local: NodeMap<Stability>,
+    // standalone `#[deprecated]` for crate-local items in crates that do
+    // not use the staged stability system
+    local_deprecation: NodeMap<Deprecation>,
     // cache for extern-crate items; unmarked stability == entry with None
     extern_cache: DefIdMap<Option<Stability>>
 }
@@ -47,6 +50,7 @@ struct Annotator<'a> {
     sess: &'a Session,
     index: &'a mut Index,
     parent: Option<Stability>,
+    parent_deprecation: Option<Deprecation>,
     export_map: &'a PublicItems,
 }
 
@@ -58,6 +62,20 @@ impl<'a> Annotator<'a> {
         F: FnOnce(&mut Annotator),
     {
         debug!("annotate(id = {:?}, attrs = {:?})", id, attrs);
+        if !self.index.staged_api {
+            // Outside of the staged stability system only `#[deprecated]`
+            // is meaningful; like stability, it is inherited lexically.
+            let depr = attr::find_deprecation(self.sess.diagnostic(), attrs, item_sp)
+                .or_else(|| if use_parent { self.parent_deprecation.clone() } else { None });
+            if let Some(ref depr) = depr {
+                self.index.local_deprecation.insert(id, depr.clone());
+            }
+            let parent = replace(&mut self.parent_deprecation, depr);
+            f(self);
+            self.parent_deprecation = parent;
+            return;
+        }
+
         match attr::find_stability(self.sess.diagnostic(), attrs, item_sp) {
             Some(stab) => {
                 debug!("annotate: found {:?}", stab);
@@ -157,13 +175,11 @@ impl<'a, 'v> Visitor<'v> for Annotator<'a> {
 impl Index {
     /// Construct the stability index for a crate being compiled.
     pub fn build(&mut self, sess: &Session, krate: &Crate, export_map: &PublicItems) {
-        if !self.staged_api {
-            return;
-        }
         let mut annotator = Annotator {
             sess: sess,
             index: self,
             parent: None,
+            parent_deprecation: None,
             export_map: export_map,
         };
         annotator.annotate(ast::CRATE_NODE_ID, true, &krate.attrs, krate.span,
@@ -186,6 +202,7 @@ impl Index {
         Index {
             staged_api: staged_api,
             local: NodeMap(),
+            local_deprecation: NodeMap(),
             extern_cache: DefIdMap()
         }
     }
@@ -221,11 +238,16 @@ struct Checker<'a, 'tcx: 'a> {
 }
 
 impl<'a, 'tcx> Checker<'a, 'tcx> {
-    fn check(&mut self, id: ast::DefId, span: Span, stab: &Option<Stability>) {
+    fn check(&mut self, id: ast::DefId, span: Span, stab: &Option<Stability>,
+             depr: &Option<Deprecation>) {
         // Only the cross-crate scenario matters when checking unstable APIs
         let cross_crate = !is_local(id);
         if !cross_crate { return }
 
+        // Items outside of the staged stability system are only ever
+        // deprecated, which is left to the lint.
+        if depr.is_some() { return }
+
         match *stab {
             Some(Stability { level: attr::Unstable, ref feature, ref reason, .. }) => {
                 self.used_features.insert(feature.clone(), attr::Unstable);
@@ -276,32 +298,32 @@ impl<'a, 'v, 'tcx> Visitor<'v> for Checker<'a, 'tcx> {
         if item.span == DUMMY_SP && item.ident.as_str() == "__test" { return }
 
         check_item(self.tcx, item, true,
-                   &mut |id, sp, stab| self.check(id, sp, stab));
+                   &mut |id, sp, stab, depr| self.check(id, sp, stab, depr));
         visit::walk_item(self, item);
     }
 
     fn visit_expr(&mut self, ex: &ast::Expr) {
         check_expr(self.tcx, ex,
-                   &mut |id, sp, stab| self.check(id, sp, stab));
+                   &mut |id, sp, stab, depr| self.check(id, sp, stab, depr));
         visit::walk_expr(self, ex);
     }
 
     fn visit_path(&mut self, path: &ast::Path, id: ast::NodeId) {
         check_path(self.tcx, path, id,
-                   &mut |id, sp, stab| self.check(id, sp, stab));
+                   &mut |id, sp, stab, depr| self.check(id, sp, stab, depr));
         visit::walk_path(self, path)
     }
 
     fn visit_pat(&mut self, pat: &ast::Pat) {
         check_pat(self.tcx, pat,
-                  &mut |id, sp, stab| self.check(id, sp, stab));
+                  &mut |id, sp, stab, depr| self.check(id, sp, stab, depr));
         visit::walk_pat(self, pat)
     }
 }
 
 /// Helper for discovering nodes to check for stability
 pub fn check_item(tcx: &ty::ctxt, item: &ast::Item, warn_about_defns: bool,
-                  cb: &mut FnMut(ast::DefId, Span, &Option<Stability>)) {
+                  cb: &mut FnMut(ast::DefId, Span, &Option<Stability>, &Option<Deprecation>)) {
     match item.node {
         ast::ItemExternCrate(_) => {
             // compiler-generated `extern crate` items have a dummy span.
@@ -338,7 +360,7 @@ pub fn check_item(tcx: &ty::ctxt, item: &ast::Item, warn_about_defns: bool,
 
 /// Helper for discovering nodes to check for stability
 pub fn check_expr(tcx: &ty::ctxt, e: &ast::Expr,
-                  cb: &mut FnMut(ast::DefId, Span, &Option<Stability>)) {
+                  cb: &mut FnMut(ast::DefId, Span, &Option<Stability>, &Option<Deprecation>)) {
     let span;
     let id = match e.node {
         ast::ExprMethodCall(i, _, _) => {
@@ -447,7 +469,7 @@ pub fn check_expr(tcx: &ty::ctxt, e: &ast::Expr,
 }
 
 pub fn check_path(tcx: &ty::ctxt, path: &ast::Path, id: ast::NodeId,
-                  cb: &mut FnMut(ast::DefId, Span, &Option<Stability>)) {
+                  cb: &mut FnMut(ast::DefId, Span, &Option<Stability>, &Option<Deprecation>)) {
     match tcx.def_map.borrow().get(&id).map(|d| d.full_def()) {
         Some(def::DefPrimTy(..)) => {}
         Some(def) => {
@@ -459,7 +481,7 @@ pub fn check_path(tcx: &ty::ctxt, path: &ast::Path, id: ast::NodeId,
 }
 
 pub fn check_pat(tcx: &ty::ctxt, pat: &ast::Pat,
-                 cb: &mut FnMut(ast::DefId, Span, &Option<Stability>)) {
+                 cb: &mut FnMut(ast::DefId, Span, &Option<Stability>, &Option<Deprecation>)) {
     debug!("check_pat(pat = {:?})", pat);
     if is_internal(tcx, pat.span) { return; }
 
@@ -500,11 +522,15 @@ pub fn check_pat(tcx: &ty::ctxt, pat: &ast::Pat,
 }
 
 fn maybe_do_stability_check(tcx: &ty::ctxt, id: ast::DefId, span: Span,
-                            cb: &mut FnMut(ast::DefId, Span, &Option<Stability>)) {
-    if !is_staged_api(tcx, id) { return  }
+                            cb: &mut FnMut(ast::DefId, Span, &Option<Stability>,
+                                           &Option<Deprecation>)) {
     if is_internal(tcx, span) { return }
-    let ref stability = lookup(tcx, id);
-    cb(id, span, stability);
+    if is_staged_api(tcx, id) {
+        let ref stability = lookup(tcx, id);
+        cb(id, span, stability, &None);
+    } else if let Some(deprecation) = lookup_deprecation(tcx, id) {
+        cb(id, span, &None, &Some(deprecation));
+    }
 }
 
 fn is_internal(tcx: &ty::ctxt, span: Span) -> bool {
@@ -564,9 +590,32 @@ pub fn lookup(tcx: &ty::ctxt, id: DefId) -> Option<Stability> {
     })
 }
 
+/// Lookup the standalone `#[deprecated]` attribute of an item from a crate
+/// that does not use the staged stability system, loading external crate
+/// metadata as necessary.
+pub fn lookup_deprecation(tcx: &ty::ctxt, id: DefId) -> Option<Deprecation> {
+    debug!("lookup_deprecation(id={})",
+           id.repr(tcx));
+
+    // implementations of a deprecated trait method are deprecated too
+    match ty::trait_item_of_item(tcx, id) {
+        Some(ty::MethodTraitItemId(trait_method_id)) if trait_method_id != id => {
+            return lookup_deprecation(tcx, trait_method_id)
+        }
+        _ => {}
+    }
+
+    if is_local(id) {
+        tcx.stability.borrow().local_deprecation.get(&id.node).cloned()
+    } else {
+        csearch::get_deprecation(&tcx.sess.cstore, id)
+    }
+}
+
 /// Given the list of enabled features that were not language features (i.e. that
 /// were expected to be library features), and the list of features used from
 /// libraries, identify activated features that don't exist and error about them.
+/// Also lints the language features that feature gating found no use of.
 pub fn check_unused_or_stable_features(sess: &Session,
                                        lib_features_used: &FnvHashMap<InternedString,
                                                                       attr::StabilityLevel>) {
@@ -603,4 +652,11 @@ pub fn check_unused_or_stable_features(sess: &Session,
                       span,
                       "unused or unknown feature".to_string());
     }
+
+    for (name, span) in sess.features.borrow().unused_lang_features() {
+        sess.add_lint(lint::builtin::UNUSED_FEATURES,
+                      ast::CRATE_NODE_ID,
+                      span,
+                      format!("unused feature `{}`", name));
+    }
 }
@@ -20,8 +20,9 @@ use util::ppaux::Repr;
 use std::fmt;
 use std::iter::IntoIterator;
 use std::slice::Iter;
-use std::vec::{Vec, IntoIter};
+use std::vec::Vec;
 use syntax::codemap::{Span, DUMMY_SP};
+use syntax::util::small_vector::{SmallVector, IntoIter};
 
 ///////////////////////////////////////////////////////////////////////////
 
@@ -226,9 +227,12 @@ pub struct VecPerParamSpace<T> {
     // AF(self) = (self.content[..self.type_limit],
     //             self.content[self.type_limit..self.self_limit],
     //             self.content[self.self_limit..])
+    //
+    // Most substitutions and predicate lists hold no more than a couple
+    // of entries, so the content is kept inline while it is that small.
     type_limit: uint,
     self_limit: uint,
-    content: Vec<T>,
+    content: SmallVector<T>,
 }
 
 /// The `split` function converts one `VecPerParamSpace` into this
@@ -263,7 +267,7 @@ impl<T> VecPerParamSpace<T> {
         VecPerParamSpace {
             type_limit: 0,
             self_limit: 0,
-            content: Vec::new()
+            content: SmallVector::zero()
         }
     }
 
@@ -279,9 +283,7 @@ impl<T> VecPerParamSpace<T> {
         let type_limit = t.len();
         let self_limit = type_limit + s.len();
 
-        let mut content = t;
-        content.extend(s.into_iter());
-        content.extend(f.into_iter());
+        let content = t.into_iter().chain(s.into_iter()).chain(f.into_iter()).collect();
 
         VecPerParamSpace {
             type_limit: type_limit,
@@ -290,7 +292,7 @@ impl<T> VecPerParamSpace<T> {
         }
     }
 
-    fn new_internal(content: Vec<T>, type_limit: uint, self_limit: uint)
+    fn new_internal(content: SmallVector<T>, type_limit: uint, self_limit: uint)
                     -> VecPerParamSpace<T>
     {
         VecPerParamSpace {
@@ -374,12 +376,12 @@ impl<T> VecPerParamSpace<T> {
 
     pub fn get_slice<'a>(&'a self, space: ParamSpace) -> &'a [T] {
         let (start, limit) = self.limits(space);
-        &self.content[start.. limit]
+        &self.content.as_slice()[start.. limit]
     }
 
     pub fn get_mut_slice<'a>(&'a mut self, space: ParamSpace) -> &'a mut [T] {
         let (start, limit) = self.limits(space);
-        &mut self.content[start.. limit]
+        &mut self.content.as_mut_slice()[start.. limit]
     }
 
     pub fn opt_get<'a>(&'a self,
@@ -407,11 +409,11 @@ impl<T> VecPerParamSpace<T> {
     }
 
     pub fn as_slice(&self) -> &[T] {
-        &self.content
+        self.content.as_slice()
     }
 
     pub fn into_vec(self) -> Vec<T> {
-        self.content
+        self.content.into_vec()
     }
 
     pub fn all_vecs<P>(&self, mut pred: P) -> bool where
@@ -535,7 +537,7 @@ impl<T> IntoIterator for VecPerParamSpace<T> {
     type IntoIter = IntoIter<T>;
 
     fn into_iter(self) -> IntoIter<T> {
-        self.into_vec().into_iter()
+        self.content.into_iter()
     }
 }
 
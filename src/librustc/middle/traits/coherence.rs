@@ -87,6 +87,65 @@ fn overlap(selcx: &mut SelectionContext,
     true
 }
 
+/// True if there exist types that satisfy the self types and where
+/// clauses of both of the two given inherent impls.
+pub fn overlapping_inherent_impls(infcx: &InferCtxt,
+                                  impl1_def_id: ast::DefId,
+                                  impl2_def_id: ast::DefId)
+                                  -> bool
+{
+    debug!("overlapping_inherent_impls(\
+           impl1_def_id={}, \
+           impl2_def_id={})",
+           impl1_def_id.repr(infcx.tcx),
+           impl2_def_id.repr(infcx.tcx));
+
+    let param_env = &ty::empty_parameter_environment(infcx.tcx);
+    let selcx = &mut SelectionContext::intercrate(infcx, param_env);
+    infcx.probe(|_| {
+        let (a_self_ty, a_obligations) = impl_self_ty_and_oblig(selcx, impl1_def_id);
+        let (b_self_ty, b_obligations) = impl_self_ty_and_oblig(selcx, impl2_def_id);
+
+        if let Err(_) = infer::mk_eqty(selcx.infcx(), true, infer::Misc(DUMMY_SP),
+                                       a_self_ty, b_self_ty) {
+            return false;
+        }
+
+        a_obligations.iter()
+                     .chain(b_obligations.iter())
+                     .all(|o| selcx.evaluate_obligation(o))
+    })
+}
+
+/// Like `impl_trait_ref_and_oblig`, but for an inherent impl: returns
+/// the self type with fresh variables substituted.
+fn impl_self_ty_and_oblig<'a,'tcx>(selcx: &mut SelectionContext<'a,'tcx>,
+                                   impl_def_id: ast::DefId)
+                                   -> (Ty<'tcx>, Vec<PredicateObligation<'tcx>>)
+{
+    let impl_substs =
+        &util::fresh_substs_for_impl(selcx.infcx(), DUMMY_SP, impl_def_id);
+    let self_ty =
+        ty::lookup_item_type(selcx.tcx(), impl_def_id).ty.subst(selcx.tcx(), impl_substs);
+    let Normalized { value: self_ty, obligations: normalization_obligations1 } =
+        project::normalize(selcx, ObligationCause::dummy(), &self_ty);
+
+    let predicates = ty::lookup_predicates(selcx.tcx(), impl_def_id);
+    let predicates = predicates.instantiate(selcx.tcx(), impl_substs);
+    let Normalized { value: predicates, obligations: normalization_obligations2 } =
+        project::normalize(selcx, ObligationCause::dummy(), &predicates);
+    let impl_obligations =
+        util::predicates_for_generics(selcx.tcx(), ObligationCause::dummy(), 0, &predicates);
+
+    let impl_obligations: Vec<_> =
+        impl_obligations.into_iter()
+        .chain(normalization_obligations1.into_iter())
+        .chain(normalization_obligations2.into_iter())
+        .collect();
+
+    (self_ty, impl_obligations)
+}
+
 /// Instantiate fresh variables for all bound parameters of the impl
 /// and return the impl trait ref with those variables substituted.
 fn impl_trait_ref_and_oblig<'a,'tcx>(selcx: &mut SelectionContext<'a,'tcx>,
@@ -17,15 +17,21 @@ use super::{
     OutputTypeParameterMismatch,
     PredicateObligation,
     SelectionError,
+    FieldOrigin,
+    UpvarOrigin,
+    UnknownOrigin,
 };
 
 use fmt_macros::{Parser, Piece, Position};
-use middle::infer::InferCtxt;
+use middle::infer::{self, InferCtxt};
+use middle::subst::{self, Subst};
 use middle::ty::{self, AsPredicate, ReferencesError, ToPolyTraitRef, TraitRef};
-use middle::ty_fold::TypeFoldable;
+use middle::ty_fold::{self, TypeFoldable};
 use std::collections::HashMap;
+use syntax::ast;
 use syntax::codemap::{DUMMY_SP, Span};
 use syntax::attr::{AttributeMethods, AttrMetaMethods};
+use syntax::parse::token;
 use util::ppaux::{Repr, UserString};
 
 pub fn report_fulfillment_errors<'a, 'tcx>(infcx: &InferCtxt<'a, 'tcx>,
@@ -37,6 +43,16 @@ pub fn report_fulfillment_errors<'a, 'tcx>(infcx: &InferCtxt<'a, 'tcx>,
 
 fn report_fulfillment_error<'a, 'tcx>(infcx: &InferCtxt<'a, 'tcx>,
                                       error: &FulfillmentError<'tcx>) {
+    // However it failed, an obligation of an impl method that does not hold
+    // under the trait method's bounds is a requirement the trait lacks.
+    if let ObligationCauseCode::CompareImplMethodObligation(trait_m_def_id) =
+            error.obligation.cause.code {
+        let predicate = infcx.resolve_type_vars_if_possible(&error.obligation.predicate);
+        report_extra_requirement(infcx.tcx, error.obligation.cause.span,
+                                 &predicate, trait_m_def_id);
+        return;
+    }
+
     match error.code {
         FulfillmentErrorCode::CodeSelectionError(ref e) => {
             report_selection_error(infcx, &error.obligation, e);
@@ -50,6 +66,23 @@ fn report_fulfillment_error<'a, 'tcx>(infcx: &InferCtxt<'a, 'tcx>,
     }
 }
 
+/// Reports that `predicate`, required by an impl method at `span`, is not
+/// implied by the bounds of the trait method `trait_m_def_id` it implements.
+pub fn report_extra_requirement<'tcx>(tcx: &ty::ctxt<'tcx>,
+                                      span: Span,
+                                      predicate: &ty::Predicate<'tcx>,
+                                      trait_m_def_id: ast::DefId) {
+    span_err!(tcx.sess, span, E0276,
+              "the requirement `{}` appears on the impl \
+               method but not on the corresponding trait method",
+              predicate.user_string(tcx));
+    if trait_m_def_id.krate == ast::LOCAL_CRATE {
+        if let Some(trait_span) = tcx.map.opt_span(trait_m_def_id.node) {
+            tcx.sess.span_note(trait_span, "the trait declaration is here");
+        }
+    }
+}
+
 pub fn report_projection_error<'a, 'tcx>(infcx: &InferCtxt<'a, 'tcx>,
                                          obligation: &PredicateObligation<'tcx>,
                                          error: &MismatchedProjectionTypes<'tcx>)
@@ -139,6 +172,27 @@ fn report_on_unimplemented<'a, 'tcx>(infcx: &InferCtxt<'a, 'tcx>,
     report
 }
 
+/// Every iterator is `IntoIterator`, so a type which is neither (e.g. the
+/// head of a `for` loop) fails to be an `Iterator` on behalf of the blanket
+/// impl. Point at `IntoIterator` instead in this case, which is what the user
+/// most likely wants to implement.
+fn into_iterator_note<'a, 'tcx>(infcx: &InferCtxt<'a, 'tcx>,
+                                code: &ObligationCauseCode<'tcx>,
+                                trait_ref: &TraitRef<'tcx>) -> Option<String> {
+    let lang_items = &infcx.tcx.lang_items;
+    if Some(trait_ref.def_id) != lang_items.iterator_trait() {
+        return None;
+    }
+    match *code {
+        ObligationCauseCode::ImplDerivedObligation(ref data)
+                if Some(data.parent_trait_ref.def_id()) == lang_items.into_iterator_trait() => {
+            Some(format!("`{}` is not an iterator; consider implementing `IntoIterator`",
+                         trait_ref.self_ty().user_string(infcx.tcx)))
+        }
+        _ => None
+    }
+}
+
 /// Reports that an overflow has occurred and halts compilation. We
 /// halt compilation unconditionally because it is important that
 /// overflows never be masked -- they basically represent computations
@@ -171,11 +225,9 @@ pub fn report_selection_error<'a, 'tcx>(infcx: &InferCtxt<'a, 'tcx>,
     match *error {
         SelectionError::Unimplemented => {
             match &obligation.cause.code {
-                &ObligationCauseCode::CompareImplMethodObligation => {
-                    span_err!(infcx.tcx.sess, obligation.cause.span, E0276,
-                            "the requirement `{}` appears on the impl \
-                            method but not on the corresponding trait method",
-                            obligation.predicate.user_string(infcx.tcx));;
+                &ObligationCauseCode::CompareImplMethodObligation(trait_m_def_id) => {
+                    report_extra_requirement(infcx.tcx, obligation.cause.span,
+                                             &obligation.predicate, trait_m_def_id);
                 }
                 _ => {
                     match obligation.predicate {
@@ -183,20 +235,46 @@ pub fn report_selection_error<'a, 'tcx>(infcx: &InferCtxt<'a, 'tcx>,
                             let trait_predicate =
                                 infcx.resolve_type_vars_if_possible(trait_predicate);
 
-                            if !trait_predicate.references_error() {
+                            if trait_predicate.references_error() {
+                                // Already reported.
+                            } else if let Some((impl_def_id, region)) =
+                                    find_too_specific_impl(infcx, obligation.cause.span,
+                                                           &trait_predicate) {
+                                let impl_span = if impl_def_id.krate == ast::LOCAL_CRATE {
+                                    Some(infcx.tcx.map.span(impl_def_id.node))
+                                } else {
+                                    None
+                                };
+                                report_too_specific_implementation(
+                                    infcx, obligation.cause.span,
+                                    trait_predicate.user_string(infcx.tcx),
+                                    region, impl_span.map(|sp| (sp, "implementation")));
+                                note_obligation_cause(infcx, obligation);
+                            } else {
                                 let trait_ref = trait_predicate.to_poly_trait_ref();
+                                let within = match outermost_builtin_derived_type(
+                                        infcx, &obligation.cause.code) {
+                                    Some(ty) => format!("within `{}`, ", ty.user_string(infcx.tcx)),
+                                    None => String::new(),
+                                };
                                 span_err!(infcx.tcx.sess, obligation.cause.span, E0277,
-                                        "the trait `{}` is not implemented for the type `{}`",
+                                        "{}the trait `{}` is not implemented for the type `{}`",
+                                        within,
                                         trait_ref.user_string(infcx.tcx),
                                         trait_ref.self_ty().user_string(infcx.tcx));
                                 // Check if it has a custom "#[rustc_on_unimplemented]"
                                 // error message, report with that message if it does
-                                let custom_note = report_on_unimplemented(infcx, &*trait_ref.0,
-                                                                          obligation.cause.span);
+                                let custom_note =
+                                    into_iterator_note(infcx, &obligation.cause.code,
+                                                       &*trait_ref.0).or_else(|| {
+                                        report_on_unimplemented(infcx, &*trait_ref.0,
+                                                                obligation.cause.span)
+                                    });
                                 if let Some(s) = custom_note {
                                     infcx.tcx.sess.span_note(obligation.cause.span,
                                                              &s);
                                 }
+                                note_obligation_cause(infcx, obligation);
                             }
                         }
 
@@ -235,7 +313,31 @@ pub fn report_selection_error<'a, 'tcx>(infcx: &InferCtxt<'a, 'tcx>,
         OutputTypeParameterMismatch(ref expected_trait_ref, ref actual_trait_ref, ref e) => {
             let expected_trait_ref = infcx.resolve_type_vars_if_possible(&*expected_trait_ref);
             let actual_trait_ref = infcx.resolve_type_vars_if_possible(&*actual_trait_ref);
-            if !ty::type_is_error(actual_trait_ref.self_ty()) {
+            let closure_span = match expected_trait_ref.self_ty().sty {
+                ty::ty_closure(def_id, _) if def_id.krate == ast::LOCAL_CRATE => {
+                    Some(infcx.tcx.map.span(def_id.node))
+                }
+                _ => None
+            };
+            // The closure's signature was inferred (or annotated) with a
+            // particular lifetime where a `for<'a>` bound needs one that
+            // holds for every lifetime.
+            let too_specific = match *e {
+                ty::terr_regions_insufficiently_polymorphic(_, region) |
+                ty::terr_regions_overly_polymorphic(_, region) => {
+                    closure_span.map(|span| (span, region))
+                }
+                _ => None
+            };
+            if ty::type_is_error(actual_trait_ref.self_ty()) {
+                // Already reported.
+            } else if let Some((closure_span, region)) = too_specific {
+                report_too_specific_implementation(
+                    infcx, obligation.cause.span,
+                    actual_trait_ref.as_predicate().user_string(infcx.tcx),
+                    region, Some((closure_span, "closure")));
+                note_obligation_cause(infcx, obligation);
+            } else {
                 span_err!(infcx.tcx.sess, obligation.cause.span, E0281,
                         "type mismatch: the type `{}` implements the trait `{}`, \
                         but the trait `{}` is required ({})",
@@ -249,6 +351,121 @@ pub fn report_selection_error<'a, 'tcx>(infcx: &InferCtxt<'a, 'tcx>,
     }
 }
 
+/// Reports a higher-ranked requirement (`for<'a> ...`) that failed only
+/// because the implementation that would have satisfied it is specific to
+/// one lifetime. `implementation` points at that impl or closure, if known.
+fn report_too_specific_implementation<'a, 'tcx>(infcx: &InferCtxt<'a, 'tcx>,
+                                                span: Span,
+                                                requirement: String,
+                                                region: ty::Region,
+                                                implementation: Option<(Span, &str)>)
+{
+    let lifetime = match region {
+        ty::ReStatic |
+        ty::ReEarlyBound(..) |
+        ty::ReFree(ty::FreeRegion { bound_region: ty::BrNamed(..), .. }) => {
+            format!("the specific lifetime `{}`", region.user_string(infcx.tcx))
+        }
+        _ => "a specific lifetime".to_string(),
+    };
+    span_err!(infcx.tcx.sess, span, E0334,
+              "the requirement `{}` is not satisfied because the implementation \
+               only applies to {}",
+              requirement, lifetime);
+    if let Some((impl_span, what)) = implementation {
+        infcx.tcx.sess.span_note(impl_span, &format!("the {} is here", what));
+    }
+}
+
+/// Given a higher-ranked trait predicate that could not be satisfied, looks
+/// for an impl of the trait that would satisfy it if its bound regions were
+/// instantiated with some particular lifetime. Returns that impl along with
+/// the lifetime its trait reference uses where the predicate has a bound
+/// region.
+fn find_too_specific_impl<'a, 'tcx>(infcx: &InferCtxt<'a, 'tcx>,
+                                    span: Span,
+                                    trait_predicate: &ty::PolyTraitPredicate<'tcx>)
+                                    -> Option<(ast::DefId, ty::Region)>
+{
+    let tcx = infcx.tcx;
+    if !ty::binds_late_bound_regions(tcx, trait_predicate) {
+        return None;
+    }
+
+    // For each region in the required trait reference, whether it is
+    // bound by the predicate's own `for<...>`.
+    let mut bound_positions = Vec::new();
+    ty_fold::fold_regions(tcx, &trait_predicate.0.trait_ref, |r, depth| {
+        bound_positions.push(match r {
+            ty::ReLateBound(debruijn, _) => debruijn.depth == depth,
+            _ => false,
+        });
+        r
+    });
+
+    let trait_def_id = trait_predicate.def_id();
+    ty::populate_implementations_for_trait_if_necessary(tcx, trait_def_id);
+    let impls = match tcx.trait_impls.borrow().get(&trait_def_id) {
+        Some(impls) => impls.borrow().clone(),
+        None => return None,
+    };
+
+    for impl_def_id in impls {
+        let impl_trait_ref = match ty::impl_trait_ref(tcx, impl_def_id) {
+            Some(trait_ref) => trait_ref,
+            None => continue,
+        };
+
+        // Keep the impl's lifetime parameters as they are, so that we can
+        // tell which of them lines up with a bound region below.
+        let generics = ty::lookup_item_type(tcx, impl_def_id).generics;
+        let substs = subst::Substs::new(generics.types.map(|_| infcx.next_ty_var()),
+                                        generics.regions.map(|d| d.to_early_bound_region()));
+        let impl_trait_ref = impl_trait_ref.subst(tcx, &substs);
+
+        let region = infcx.probe(|_| {
+            let (required_trait_ref, _) =
+                infcx.replace_late_bound_regions_with_fresh_var(
+                    span,
+                    infer::LateBoundRegionConversionTime::HigherRankedType,
+                    &trait_predicate.to_poly_trait_ref());
+            if infcx.sub_trait_refs(false, infer::Misc(span),
+                                    impl_trait_ref.clone(), required_trait_ref).is_err() {
+                return None;
+            }
+
+            let impl_regions =
+                ty_fold::collect_regions(tcx, &infcx.resolve_type_vars_if_possible(
+                    &impl_trait_ref));
+            if impl_regions.len() != bound_positions.len() {
+                return None;
+            }
+
+            bound_positions.iter()
+                           .zip(impl_regions.iter())
+                           .filter(|&(&bound, _)| bound)
+                           .map(|(_, &r)| r)
+                           .find(|&r| match r {
+                               // One of the required trait reference's own
+                               // regions, substituted for an impl type parameter.
+                               ty::ReInfer(..) => false,
+                               // A lifetime parameter of the impl only pins the
+                               // bound region down if the impl uses it elsewhere.
+                               ty::ReEarlyBound(..) => {
+                                   impl_regions.iter().filter(|&&r1| r1 == r).count() > 1
+                               }
+                               _ => true,
+                           })
+        });
+
+        if let Some(region) = region {
+            return Some((impl_def_id, region));
+        }
+    }
+
+    None
+}
+
 pub fn maybe_report_ambiguity<'a, 'tcx>(infcx: &InferCtxt<'a, 'tcx>,
                                         obligation: &PredicateObligation<'tcx>) {
     // Unable to successfully determine, probably means
@@ -323,6 +540,23 @@ pub fn maybe_report_ambiguity<'a, 'tcx>(infcx: &InferCtxt<'a, 'tcx>,
     }
 }
 
+/// If `code` was derived by breaking a type apart into its constituent
+/// types (as is done for `Send`, `Sync` and other defaulted traits),
+/// returns the outermost type of that decomposition, i.e. the type the
+/// user actually asked about.
+fn outermost_builtin_derived_type<'a, 'tcx>(infcx: &InferCtxt<'a, 'tcx>,
+                                            mut code: &ObligationCauseCode<'tcx>)
+                                            -> Option<ty::Ty<'tcx>>
+{
+    let mut outermost = None;
+    while let ObligationCauseCode::BuiltinDerivedObligation(ref data) = *code {
+        let parent_trait_ref = infcx.resolve_type_vars_if_possible(&data.parent_trait_ref);
+        outermost = Some(parent_trait_ref.0.self_ty());
+        code = &*data.parent_code;
+    }
+    outermost
+}
+
 fn note_obligation_cause<'a, 'tcx, T>(infcx: &InferCtxt<'a, 'tcx>,
                                       obligation: &Obligation<'tcx, T>)
     where T: UserString<'tcx>
@@ -407,9 +641,26 @@ fn note_obligation_cause_code<'a, 'tcx, T>(infcx: &InferCtxt<'a, 'tcx>,
         }
         ObligationCauseCode::BuiltinDerivedObligation(ref data) => {
             let parent_trait_ref = infcx.resolve_type_vars_if_possible(&data.parent_trait_ref);
-            span_note!(tcx.sess, cause_span,
-                       "required because it appears within the type `{}`",
-                       parent_trait_ref.0.self_ty().user_string(infcx.tcx));
+            let parent_ty = parent_trait_ref.0.self_ty().user_string(infcx.tcx);
+            match data.origin {
+                FieldOrigin(name) => {
+                    span_note!(tcx.sess, cause_span,
+                               "required because it appears within the field `{}` of `{}`",
+                               token::get_name(name),
+                               parent_ty);
+                }
+                UpvarOrigin(var_id) => {
+                    span_note!(tcx.sess, cause_span,
+                               "required because it appears within the closure's captured \
+                                variable `{}`",
+                               ty::local_var_name_str(tcx, var_id));
+                }
+                UnknownOrigin => {
+                    span_note!(tcx.sess, cause_span,
+                               "required because it appears within the type `{}`",
+                               parent_ty);
+                }
+            }
             let parent_predicate = parent_trait_ref.as_predicate();
             note_obligation_cause_code(infcx, &parent_predicate, cause_span, &*data.parent_code);
         }
@@ -422,7 +673,7 @@ fn note_obligation_cause_code<'a, 'tcx, T>(infcx: &InferCtxt<'a, 'tcx>,
             let parent_predicate = parent_trait_ref.as_predicate();
             note_obligation_cause_code(infcx, &parent_predicate, cause_span, &*data.parent_code);
         }
-        ObligationCauseCode::CompareImplMethodObligation => {
+        ObligationCauseCode::CompareImplMethodObligation(_) => {
             span_note!(tcx.sess, cause_span,
                       "the requirement `{}` appears on the impl method \
                       but not on the corresponding trait method",
@@ -14,6 +14,7 @@ pub use self::SelectionError::*;
 pub use self::FulfillmentErrorCode::*;
 pub use self::Vtable::*;
 pub use self::ObligationCauseCode::*;
+pub use self::ConstituentOrigin::*;
 
 use middle::subst;
 use middle::ty::{self, HasProjectionTypes, Ty};
@@ -26,10 +27,12 @@ use syntax::codemap::{Span, DUMMY_SP};
 use util::ppaux::Repr;
 
 pub use self::error_reporting::report_fulfillment_errors;
+pub use self::error_reporting::report_extra_requirement;
 pub use self::error_reporting::report_overflow_error;
 pub use self::error_reporting::suggest_new_overflow_limit;
 pub use self::coherence::orphan_check;
 pub use self::coherence::overlapping_impls;
+pub use self::coherence::overlapping_inherent_impls;
 pub use self::coherence::OrphanCheckErr;
 pub use self::fulfill::{FulfillmentContext, RegionObligation};
 pub use self::project::MismatchedProjectionTypes;
@@ -128,7 +131,9 @@ pub enum ObligationCauseCode<'tcx> {
 
     ImplDerivedObligation(DerivedObligationCause<'tcx>),
 
-    CompareImplMethodObligation,
+    /// An impl method must not require more than the trait method it
+    /// implements; the def-id is that of the trait method.
+    CompareImplMethodObligation(ast::DefId),
 }
 
 #[derive(Clone, PartialEq, Eq)]
@@ -140,7 +145,28 @@ pub struct DerivedObligationCause<'tcx> {
     parent_trait_ref: ty::PolyTraitRef<'tcx>,
 
     /// The parent trait had this cause
-    parent_code: Rc<ObligationCauseCode<'tcx>>
+    parent_code: Rc<ObligationCauseCode<'tcx>>,
+
+    /// Where the self type of the current obligation appears within
+    /// the self type of the parent obligation, if known.
+    origin: ConstituentOrigin,
+}
+
+/// Records how a constituent type was reached when a type is broken
+/// apart into its component types (e.g., for a defaulted trait like
+/// `Send`), so that errors can point out the offending field or
+/// captured variable.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ConstituentOrigin {
+    /// No more precise location is known (tuple elements, pointees
+    /// and so on).
+    UnknownOrigin,
+
+    /// The named field of a struct or struct-like enum variant.
+    FieldOrigin(ast::Name),
+
+    /// The variable with the given id, captured by a closure.
+    UpvarOrigin(ast::NodeId),
 }
 
 pub type Obligations<'tcx, O> = subst::VecPerParamSpace<Obligation<'tcx, O>>;
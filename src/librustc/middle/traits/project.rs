@@ -100,6 +100,99 @@ pub fn poly_project_and_unify_type<'cx,'tcx>(
     })
 }
 
+/// Normalizes a projection that refers to regions bound by an enclosing
+/// binder, such as `<F as FnOnce<(&'a str,)>>::Output` within
+/// `for<'a> fn(&'a str) -> ...`, using a where clause such as
+/// `for<'b> <F as FnOnce<(&'b str,)>>::Output == &'b str`. The where clause
+/// applies if its projection is the same as ours once its bound regions are
+/// renamed to ours, and the normalized type is renamed in the same way, so
+/// it refers to the same binder as the projection did.
+fn normalize_escaping_projection_from_env<'cx,'tcx>(
+    selcx: &SelectionContext<'cx,'tcx>,
+    data: &ty::ProjectionTy<'tcx>)
+    -> Option<Ty<'tcx>>
+{
+    let tcx = selcx.tcx();
+    let data_regions = match innermost_bound_regions(tcx, data) {
+        Some(regions) => regions,
+        None => return None,
+    };
+
+    let env_predicates = selcx.param_env().caller_bounds.clone();
+    for predicate in elaborate_predicates(tcx, env_predicates) {
+        let poly_projection = match predicate {
+            ty::Predicate::Projection(poly_projection) => poly_projection,
+            _ => continue,
+        };
+        if poly_projection.item_name() != data.item_name {
+            continue;
+        }
+
+        let env_regions = match innermost_bound_regions(tcx, &poly_projection.0.projection_ty) {
+            Some(regions) => regions,
+            None => continue,
+        };
+        if env_regions.len() != data_regions.len() {
+            continue;
+        }
+
+        let mut complete = true;
+        let renamed = ty_fold::fold_regions(tcx, &poly_projection.0, |r, depth| {
+            match r {
+                ty::ReLateBound(debruijn, br) if debruijn.depth == depth => {
+                    match env_regions.iter().position(|&env_br| env_br == br) {
+                        Some(i) => ty::ReLateBound(debruijn, data_regions[i]),
+                        None => {
+                            complete = false;
+                            r
+                        }
+                    }
+                }
+                _ => r,
+            }
+        });
+
+        if complete && renamed.projection_ty == *data {
+            debug!("normalize_escaping_projection_from_env: {} normalized to {}",
+                   data.repr(tcx), renamed.ty.repr(tcx));
+            return Some(renamed.ty);
+        }
+    }
+
+    None
+}
+
+/// Returns the regions bound by the binder immediately enclosing `value`, in
+/// the order they first appear, or `None` if `value` also refers to regions
+/// bound further out.
+fn innermost_bound_regions<'tcx, T>(tcx: &ty::ctxt<'tcx>, value: &T)
+                                    -> Option<Vec<ty::BoundRegion>>
+    where T : TypeFoldable<'tcx>
+{
+    let mut regions = Vec::new();
+    let mut refers_further_out = false;
+    ty_fold::fold_regions(tcx, value, |r, depth| {
+        match r {
+            ty::ReLateBound(debruijn, br) if debruijn.depth == depth => {
+                if !regions.contains(&br) {
+                    regions.push(br);
+                }
+            }
+            ty::ReLateBound(..) => {
+                refers_further_out = true;
+            }
+            _ => {}
+        }
+        r
+    });
+
+    if refers_further_out {
+        None
+    } else {
+        Some(regions)
+    }
+}
+
 /// Evaluates constraints of the form:
 ///
 ///     <T as Trait>::U == V
@@ -284,6 +377,17 @@ impl<'a,'b,'tcx> TypeFolder<'tcx> for AssociatedTypeNormalizer<'a,'b,'tcx> {
                 ty
             }
 
+            ty::ty_projection(ref data) => {
+                // We can still normalize a projection with bound regions
+                // in scope when a where clause covers it exactly, which is
+                // the common case of projecting out of an HRTB such as
+                // `F : for<'a> Fn(&'a str) -> &'a str`.
+                match normalize_escaping_projection_from_env(self.selcx, data) {
+                    Some(normalized_ty) => normalized_ty,
+                    None => ty,
+                }
+            }
+
             _ => {
                 ty
             }
@@ -17,7 +17,8 @@ use self::SelectionCandidate::*;
 use self::BuiltinBoundConditions::*;
 use self::EvaluationResult::*;
 
-use super::DerivedObligationCause;
+use super::{DerivedObligationCause, ConstituentOrigin};
+use super::{UnknownOrigin, FieldOrigin, UpvarOrigin};
 use super::project;
 use super::project::{normalize_with_depth, Normalized};
 use super::{PredicateObligation, TraitObligation, ObligationCause};
@@ -42,6 +43,7 @@ use std::cell::RefCell;
 use std::collections::hash_map::HashMap;
 use std::rc::Rc;
 use syntax::{abi, ast};
+use syntax::parse::token;
 use util::common::ErrorReported;
 use util::ppaux::Repr;
 
@@ -565,6 +567,13 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
             report_overflow_error(self.infcx(), &stack.obligation);
         }
 
+        // `Sized` obligations are very common and can usually be decided
+        // from the self type alone, so try that before freshening the
+        // obligation and assembling candidates.
+        if self.is_known_sized_obligation(&stack.obligation) {
+            return Ok(Some(BuiltinCandidate(ty::BoundSized)));
+        }
+
         // Check the cache. Note that we skolemize the trait-ref
         // separately rather than using `stack.fresh_trait_ref` -- this
         // is because we want the unbound variables to be replaced
@@ -598,6 +607,18 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
         candidate
     }
 
+    fn is_known_sized_obligation(&self, obligation: &TraitObligation<'tcx>) -> bool {
+        let tcx = self.tcx();
+        if tcx.lang_items.sized_trait() != Some(obligation.predicate.def_id()) {
+            return false;
+        }
+        let self_ty = self.infcx.shallow_resolve(obligation.predicate.0.self_ty());
+        let known = ty::known_to_be_sized(tcx, self_ty);
+        let (hits, misses) = tcx.sized_fast_path_stats.get();
+        tcx.sized_fast_path_stats.set(if known { (hits + 1, misses) } else { (hits, misses + 1) });
+        known
+    }
+
     fn candidate_from_obligation_no_cache<'o>(&mut self,
                                               stack: &TraitObligationStack<'o, 'tcx>)
                                               -> SelectionResult<'tcx, SelectionCandidate<'tcx>>
@@ -706,6 +727,15 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
         Ok(Some(candidate))
     }
 
+    fn is_negative_impl_candidate(&self, candidate: &SelectionCandidate<'tcx>) -> bool {
+        match *candidate {
+            ImplCandidate(def_id) => {
+                ty::trait_impl_polarity(self.tcx(), def_id) == Some(ast::ImplPolarity::Negative)
+            }
+            _ => false
+        }
+    }
+
     fn pick_candidate_cache(&self) -> &SelectionCache<'tcx> {
         // If there are any where-clauses in scope, then we always use
         // a cache local to this particular scope. Otherwise, we
@@ -846,6 +876,16 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
 
         self.assemble_candidates_from_projected_tys(obligation, &mut candidates);
         try!(self.assemble_candidates_from_caller_bounds(stack, &mut candidates));
+
+        // A negative impl only rules out the default impl when its
+        // where clauses actually hold. Something like `impl<T: Foo>
+        // !Send for Bar<T>` says nothing about `Bar<U>` when `U: Foo`
+        // does not hold, so in that case we drop the candidate here and
+        // let the default impl decide based on the constituent types.
+        candidates.vec.retain(|c| {
+            !self.is_negative_impl_candidate(c) || self.winnow_candidate(stack, c).may_apply()
+        });
+
         // Default implementations have lower priority, so we only
         // consider triggering a default if there is no other impl that can apply.
         if candidates.vec.len() == 0 {
@@ -1632,8 +1672,13 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
     /// Bar<i32> where struct Bar<T> { x: T, y: u32 } -> [i32, u32]
     /// Zed<i32> where enum Zed { A(T), B(u32) } -> [i32, u32]
     /// ```
-    fn constituent_types_for_ty(&self, t: Ty<'tcx>) -> Option<Vec<Ty<'tcx>>> {
-        match t.sty {
+    ///
+    /// Each constituent type is paired with the place it was found
+    /// in (a named field, a captured variable, ...) so that errors can
+    /// point the user at it.
+    fn constituent_types_for_ty(&self, t: Ty<'tcx>)
+                                -> Option<Vec<(Ty<'tcx>, ConstituentOrigin)>> {
+        return match t.sty {
             ty::ty_uint(_) |
             ty::ty_int(_) |
             ty::ty_bool |
@@ -1660,21 +1705,21 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
             }
 
             ty::ty_uniq(referent_ty) => {  // Box<T>
-                Some(vec![referent_ty])
+                Some(vec![(referent_ty, UnknownOrigin)])
             }
 
             ty::ty_ptr(ty::mt { ty: element_ty, ..}) |
             ty::ty_rptr(_, ty::mt { ty: element_ty, ..}) => {
-                Some(vec![element_ty])
+                Some(vec![(element_ty, UnknownOrigin)])
             },
 
             ty::ty_vec(element_ty, _) => {
-                Some(vec![element_ty])
+                Some(vec![(element_ty, UnknownOrigin)])
             }
 
             ty::ty_tup(ref tys) => {
                 // (T1, ..., Tn) -- meets any bound that all of T1...Tn meet
-                Some(tys.clone())
+                Some(tys.iter().map(|&ty| (ty, UnknownOrigin)).collect())
             }
 
             ty::ty_closure(def_id, substs) => {
@@ -1682,7 +1727,9 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
 
                 match self.closure_typer.closure_upvars(def_id, substs) {
                     Some(upvars) => {
-                        Some(upvars.iter().map(|c| c.ty).collect())
+                        Some(upvars.iter()
+                                   .map(|c| (c.ty, UpvarOrigin(c.def.def_id().node)))
+                                   .collect())
                     }
                     None => {
                         None
@@ -1694,21 +1741,37 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
             ty::ty_struct(def_id, substs)
                 if Some(def_id) == self.tcx().lang_items.phantom_data() =>
             {
-                Some(substs.types.get_slice(TypeSpace).to_vec())
+                Some(substs.types.get_slice(TypeSpace).iter()
+                     .map(|&ty| (ty, UnknownOrigin))
+                     .collect())
             }
 
             ty::ty_struct(def_id, substs) => {
                 Some(ty::struct_fields(self.tcx(), def_id, substs).iter()
-                     .map(|f| f.mt.ty)
+                     .map(|f| (f.mt.ty, field_origin(f.name)))
                      .collect())
             }
 
             ty::ty_enum(def_id, substs) => {
-                Some(ty::substd_enum_variants(self.tcx(), def_id, substs)
-                     .iter()
-                     .flat_map(|variant| variant.args.iter())
-                     .map(|&ty| ty)
-                     .collect())
+                let mut types = Vec::new();
+                for variant in &ty::substd_enum_variants(self.tcx(), def_id, substs) {
+                    for (i, &ty) in variant.args.iter().enumerate() {
+                        let origin = match variant.arg_names {
+                            Some(ref names) => field_origin(names[i].name),
+                            None => UnknownOrigin,
+                        };
+                        types.push((ty, origin));
+                    }
+                }
+                Some(types)
+            }
+        };
+
+        fn field_origin(name: ast::Name) -> ConstituentOrigin {
+            if name == token::special_names::unnamed_field {
+                UnknownOrigin
+            } else {
+                FieldOrigin(name)
             }
         }
     }
@@ -1716,22 +1779,25 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
     fn collect_predicates_for_types(&mut self,
                                     obligation: &TraitObligation<'tcx>,
                                     trait_def_id: ast::DefId,
-                                    types: Vec<Ty<'tcx>>) -> Vec<PredicateObligation<'tcx>> {
+                                    types: Vec<(Ty<'tcx>, ConstituentOrigin)>)
+                                    -> Vec<PredicateObligation<'tcx>> {
 
-        let derived_cause = match self.tcx().lang_items.to_builtin_kind(trait_def_id) {
-            Some(_) => {
-                self.derived_cause(obligation, BuiltinDerivedObligation)
-            },
-            None => {
-                self.derived_cause(obligation, ImplDerivedObligation)
-            }
-        };
+        let variant: fn(DerivedObligationCause<'tcx>) -> ObligationCauseCode<'tcx> =
+            match self.tcx().lang_items.to_builtin_kind(trait_def_id) {
+                Some(_) => BuiltinDerivedObligation,
+                None => ImplDerivedObligation,
+            };
+
+        let (types, origins): (Vec<_>, Vec<_>) = types.into_iter().unzip();
 
         let normalized = project::normalize_with_depth(self, obligation.cause.clone(),
                                                        obligation.recursion_depth + 1,
                                                        &types);
 
-        let obligations = normalized.value.iter().map(|&nested_ty| {
+        let nested = normalized.value.iter().cloned().zip(origins.into_iter());
+        let obligations = nested.map(|(nested_ty, origin)| {
+            let derived_cause = self.derived_cause(obligation, variant, origin);
+
             // the obligation might be higher-ranked, e.g. for<'a> &'a
             // int : Copy. In that case, we will wind up with
             // late-bound regions in the `nested` vector. So for each
@@ -1912,6 +1978,7 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
             }
         };
 
+        let nested = nested.into_iter().map(|ty| (ty, UnknownOrigin)).collect();
         let obligations = self.collect_predicates_for_types(obligation, trait_def, nested);
 
         let obligations = VecPerParamSpace::new(obligations, Vec::new(), Vec::new());
@@ -1953,7 +2020,7 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
     fn vtable_default_impl(&mut self,
                            obligation: &TraitObligation<'tcx>,
                            trait_def_id: ast::DefId,
-                           nested: Vec<Ty<'tcx>>)
+                           nested: Vec<(Ty<'tcx>, ConstituentOrigin)>)
                            -> VtableDefaultImplData<PredicateObligation<'tcx>>
     {
 
@@ -2466,7 +2533,8 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
     #[allow(unused_comparisons)]
     fn derived_cause(&self,
                      obligation: &TraitObligation<'tcx>,
-                     variant: fn(DerivedObligationCause<'tcx>) -> ObligationCauseCode<'tcx>)
+                     variant: fn(DerivedObligationCause<'tcx>) -> ObligationCauseCode<'tcx>,
+                     origin: ConstituentOrigin)
                      -> ObligationCause<'tcx>
     {
         /*!
@@ -2486,6 +2554,7 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
             let derived_cause = DerivedObligationCause {
                 parent_trait_ref: obligation.predicate.to_poly_trait_ref(),
                 parent_code: Rc::new(obligation.cause.code.clone()),
+                origin: origin,
             };
             ObligationCause::new(obligation.cause.span,
                                  obligation.cause.body_id,
@@ -75,6 +75,8 @@ use std::hash::{Hash, SipHasher, Hasher};
 use std::mem;
 use std::ops;
 use std::rc::Rc;
+use std::u32;
+use std::u64;
 use std::vec::IntoIter;
 use collections::enum_set::{EnumSet, CLike};
 use std::collections::{HashMap, HashSet};
@@ -283,6 +285,7 @@ pub enum Variance {
 pub enum AutoAdjustment<'tcx> {
     AdjustReifyFnPointer(ast::DefId), // go from a fn-item type to a fn-pointer type
     AdjustUnsafeFnPointer, // go from a safe fn pointer to an unsafe fn pointer
+    AdjustClosureFnPointer(Ty<'tcx>), // go from a non-capturing closure to the given fn pointer
     AdjustDerefRef(AutoDerefRef<'tcx>)
 }
 
@@ -776,11 +779,26 @@ pub struct ctxt<'tcx> {
     /// present in this set can be warned about.
     pub used_unsafe: RefCell<NodeSet>,
 
+    /// Unsafe operations in the body of each unsafe fn that are not inside
+    /// an `unsafe` block, with their descriptions. These are reported by the
+    /// `unsafe_op_in_unsafe_fn` lint.
+    pub unsafe_ops_in_unsafe_fn: RefCell<NodeMap<Vec<(Span, String)>>>,
+
+    /// Set of `unsafe` blocks inside unsafe fns that would be used if the
+    /// enclosing function did not already allow unsafe operations, i.e.
+    /// when the `unsafe_op_in_unsafe_fn` lint is enabled.
+    pub used_unsafe_in_unsafe_fn: RefCell<NodeSet>,
+
     /// Set of nodes which mark locals as mutable which end up getting used at
     /// some point. Local variable definitions not in this set can be warned
     /// about.
     pub used_mut_nodes: RefCell<NodeSet>,
 
+    /// Set of `x.clone()` method calls, where `x` is a local variable, after
+    /// which `x` is never used again. Filled in by liveness and read by the
+    /// `redundant_clone` lint.
+    pub dead_clone_receivers: RefCell<NodeSet>,
+
     /// The set of external nominal types whose implementations have been read.
     /// This is used for lazy resolution of methods.
     pub populated_external_types: RefCell<DefIdSet>,
@@ -792,6 +810,11 @@ pub struct ctxt<'tcx> {
     /// The set of external primitive inherent implementations that have been read.
     pub populated_external_primitive_impls: RefCell<DefIdSet>,
 
+    /// The principal traits of object types whose lifetime bound was not
+    /// written and defaulted to `'static`. Only used to explain region
+    /// errors involving such objects.
+    pub static_object_lifetime_defaults: RefCell<DefIdSet>,
+
     /// Borrows
     pub upvar_capture_map: RefCell<UpvarCaptureMap>,
 
@@ -800,6 +823,15 @@ pub struct ctxt<'tcx> {
     pub extern_const_statics: RefCell<DefIdMap<ast::NodeId>>,
     pub extern_const_variants: RefCell<DefIdMap<ast::NodeId>>,
 
+    /// The local constants and enum variants whose values const_eval is in
+    /// the middle of computing, innermost last. Used to detect cycles.
+    pub const_eval_stack: RefCell<Vec<ast::NodeId>>,
+
+    /// The casts whose operand got its numeric type from the cast itself.
+    /// Such casts are needed even when the types end up equal, so the
+    /// trivial cast lints ignore them.
+    pub disambiguating_casts: RefCell<NodeSet>,
+
     pub method_map: MethodMap<'tcx>,
 
     pub dependency_formats: RefCell<dependency_format::Dependencies>,
@@ -843,11 +875,23 @@ pub struct ctxt<'tcx> {
     /// results are dependent on the parameter environment.
     pub type_impls_sized_cache: RefCell<HashMap<Ty<'tcx>,bool>>,
 
+    /// How many `Sized` obligations trait selection decided through
+    /// `known_to_be_sized`, and how many it had to select normally.
+    pub sized_fast_path_stats: Cell<(uint, uint)>,
+
     /// Caches whether traits are object safe
     pub object_safety_cache: RefCell<DefIdMap<bool>>,
 
     /// Maps Expr NodeId's to their constant qualification.
     pub const_qualif_map: RefCell<NodeMap<check_const::ConstQualif>>,
+
+    /// The module that type paths in messages are printed relative to, if
+    /// any. See `with_path_printing_scope`.
+    pub path_printing_scope: Cell<Option<ast::NodeId>>,
+
+    /// Caches, for each module, the structs and enums it can name without
+    /// a path. A name that could mean more than one type maps to `None`.
+    pub type_names_in_module: RefCell<NodeMap<Rc<FnvHashMap<ast::Name, Option<DefId>>>>>,
 }
 
 // Flags that we track on types. These flags are propagated upwards
@@ -940,6 +984,8 @@ impl<'tcx> ctxt<'tcx> {
         println!("Substs interner: #{}", self.substs_interner.borrow().len());
         println!("BareFnTy interner: #{}", self.bare_fn_interner.borrow().len());
         println!("Region interner: #{}", self.region_interner.borrow().len());
+        let (hits, misses) = self.sized_fast_path_stats.get();
+        println!("Sized fast path: {} hits, {} misses", hits, misses);
     }
 }
 
@@ -2393,6 +2439,16 @@ impl<'a, 'tcx> ParameterEnvironment<'a, 'tcx> {
                     }
                 }
             }
+            Some(ast_map::NodeForeignItem(item)) => {
+                let def_id = ast_util::local_def(id);
+                let scheme = lookup_item_type(cx, def_id);
+                let predicates = lookup_predicates(cx, def_id);
+                construct_parameter_environment(cx,
+                                                item.span,
+                                                &scheme.generics,
+                                                &predicates,
+                                                id)
+            }
             Some(ast_map::NodeExpr(..)) => {
                 // This is a convenience to allow closures to work.
                 ParameterEnvironment::for_item(cx, cx.map.get_parent(id))
@@ -2599,13 +2655,19 @@ pub fn mk_ctxt<'tcx>(s: Session,
         inherent_impls: RefCell::new(DefIdMap()),
         impl_items: RefCell::new(DefIdMap()),
         used_unsafe: RefCell::new(NodeSet()),
+        unsafe_ops_in_unsafe_fn: RefCell::new(NodeMap()),
+        used_unsafe_in_unsafe_fn: RefCell::new(NodeSet()),
         used_mut_nodes: RefCell::new(NodeSet()),
+        dead_clone_receivers: RefCell::new(NodeSet()),
         populated_external_types: RefCell::new(DefIdSet()),
         populated_external_traits: RefCell::new(DefIdSet()),
         populated_external_primitive_impls: RefCell::new(DefIdSet()),
+        static_object_lifetime_defaults: RefCell::new(DefIdSet()),
         upvar_capture_map: RefCell::new(FnvHashMap()),
         extern_const_statics: RefCell::new(DefIdMap()),
         extern_const_variants: RefCell::new(DefIdMap()),
+        const_eval_stack: RefCell::new(Vec::new()),
+        disambiguating_casts: RefCell::new(NodeSet()),
         method_map: RefCell::new(FnvHashMap()),
         dependency_formats: RefCell::new(FnvHashMap()),
         closure_kinds: RefCell::new(DefIdMap()),
@@ -2618,8 +2680,11 @@ pub fn mk_ctxt<'tcx>(s: Session,
         repr_hint_cache: RefCell::new(DefIdMap()),
         type_impls_copy_cache: RefCell::new(HashMap::new()),
         type_impls_sized_cache: RefCell::new(HashMap::new()),
+        sized_fast_path_stats: Cell::new((0, 0)),
         object_safety_cache: RefCell::new(DefIdMap()),
         const_qualif_map: RefCell::new(NodeMap()),
+        path_printing_scope: Cell::new(None),
+        type_names_in_module: RefCell::new(NodeMap()),
    }
 }
 
@@ -3836,6 +3901,24 @@ pub fn type_is_sized<'a,'tcx>(param_env: &ParameterEnvironment<'a,'tcx>,
     type_impls_bound(param_env, &tcx.type_impls_sized_cache, ty, ty::BoundSized, span)
 }
 
+/// Whether `ty: Sized` is known to hold without consulting the parameter
+/// environment: either because `ty` is sized by construction, or because
+/// it mentions no type parameters, projections or inference variables and
+/// `type_is_sized` already proved it. Returns false when in doubt.
+pub fn known_to_be_sized<'tcx>(cx: &ctxt<'tcx>, ty: Ty<'tcx>) -> bool {
+    match ty.sty {
+        ty_bool | ty_char | ty_int(_) | ty_uint(_) | ty_float(_) |
+        ty_uniq(_) | ty_ptr(_) | ty_rptr(..) | ty_bare_fn(..) |
+        ty_closure(..) | ty_vec(_, Some(_)) => true,
+        ty_tup(ref tys) if tys.is_empty() => true,
+        _ => {
+            !type_has_params(ty) && !type_has_self(ty) &&
+                !type_needs_infer(ty) && !type_has_projection(ty) &&
+                cx.type_impls_sized_cache.borrow().get(&ty) == Some(&true)
+        }
+    }
+}
+
 pub fn is_ffi_safe<'tcx>(cx: &ctxt<'tcx>, ty: Ty<'tcx>) -> bool {
     !type_contents(cx, ty).intersects(TC::ReachesFfiUnsafe)
 }
@@ -4556,6 +4639,8 @@ pub fn adjust_ty<'tcx, F>(cx: &ctxt<'tcx>,
                     }
                }
 
+               AdjustClosureFnPointer(fn_ty) => fn_ty,
+
                 AdjustDerefRef(ref adj) => {
                     let mut adjusted_ty = unadjusted_ty;
 
@@ -4908,7 +4993,7 @@ pub fn ty_sort_string<'tcx>(cx: &ctxt<'tcx>, ty: Ty<'tcx>) -> String {
         }
         ty_tup(ref tys) if tys.is_empty() => ::util::ppaux::ty_to_string(cx, ty),
 
-        ty_enum(id, _) => format!("enum `{}`", item_path_str(cx, id)),
+        ty_enum(id, _) => format!("enum `{}`", type_path_str(cx, id)),
         ty_uniq(_) => "box".to_string(),
         ty_vec(_, Some(n)) => format!("array of {} elements", n),
         ty_vec(_, None) => "slice".to_string(),
@@ -4920,7 +5005,7 @@ pub fn ty_sort_string<'tcx>(cx: &ctxt<'tcx>, ty: Ty<'tcx>) -> String {
             format!("trait {}", item_path_str(cx, inner.principal_def_id()))
         }
         ty_struct(id, _) => {
-            format!("struct `{}`", item_path_str(cx, id))
+            format!("struct `{}`", type_path_str(cx, id))
         }
         ty_closure(..) => "closure".to_string(),
         ty_tup(_) => "tuple".to_string(),
@@ -5411,6 +5496,116 @@ pub fn item_path_str(cx: &ctxt, id: ast::DefId) -> String {
     with_path(cx, id, |path| ast_map::path_to_string(path)).to_string()
 }
 
+/// The path to print for the struct or enum `id` in a message: its name
+/// alone if that names it in the current path printing scope, and its full
+/// path otherwise or under `-Z verbose`.
+pub fn type_path_str(cx: &ctxt, id: ast::DefId) -> String {
+    match name_in_path_printing_scope(cx, id) {
+        Some(name) if !cx.sess.verbose() => token::get_name(name).to_string(),
+        _ => item_path_str(cx, id)
+    }
+}
+
+/// Runs `f` with the types in any messages it reports printed as they would
+/// be written in the module enclosing `id`: a struct or enum that module
+/// defines or imports by name is printed by its name alone.
+pub fn with_path_printing_scope<R, F>(cx: &ctxt, id: ast::NodeId, f: F) -> R where
+    F: FnOnce() -> R,
+{
+    let mut module = id;
+    loop {
+        if let Some(ast_map::NodeItem(&ast::Item { node: ast::ItemMod(_), .. })) =
+                cx.map.find(module) {
+            break;
+        }
+        let parent = cx.map.get_parent(module);
+        if parent == module {
+            module = ast::CRATE_NODE_ID;
+            break;
+        }
+        module = parent;
+    }
+    let old = cx.path_printing_scope.get();
+    cx.path_printing_scope.set(Some(module));
+    let result = f();
+    cx.path_printing_scope.set(old);
+    result
+}
+
+/// The name the struct or enum `id` can be referred to by in the current
+/// path printing scope, if it can be named there without a path. Glob
+/// imports are not considered.
+pub fn name_in_path_printing_scope(cx: &ctxt, id: ast::DefId) -> Option<ast::Name> {
+    let module = match cx.path_printing_scope.get() {
+        Some(module) => module,
+        None => return None
+    };
+    let names = type_names_in_module(cx, module);
+    names.iter().find(|&(_, did)| *did == Some(id)).map(|(&name, _)| name)
+}
+
+fn type_names_in_module(cx: &ctxt, module: ast::NodeId)
+                        -> Rc<FnvHashMap<ast::Name, Option<DefId>>> {
+    if let Some(names) = cx.type_names_in_module.borrow().get(&module) {
+        return names.clone();
+    }
+
+    let items = if module == ast::CRATE_NODE_ID {
+        &cx.map.krate().module.items[..]
+    } else {
+        match cx.map.get(module) {
+            ast_map::NodeItem(&ast::Item { node: ast::ItemMod(ref m), .. }) => &m.items[..],
+            _ => &[][..]
+        }
+    };
+
+    let imported_type = |id: ast::NodeId| {
+        match cx.def_map.borrow().get(&id).map(|resolution| resolution.base_def) {
+            Some(def::DefTy(did, _)) => Some(did),
+            _ => None
+        }
+    };
+
+    let mut names = FnvHashMap();
+    {
+        let mut add = |name: ast::Name, did: DefId| {
+            let entry = names.entry(name).get().unwrap_or_else(|v| v.insert(Some(did)));
+            if *entry != Some(did) {
+                *entry = None;
+            }
+        };
+        for item in items {
+            match item.node {
+                ast::ItemStruct(..) | ast::ItemEnum(..) => {
+                    add(item.ident.name, local_def(item.id));
+                }
+                ast::ItemUse(ref view_path) => match view_path.node {
+                    ast::ViewPathSimple(ident, _) => {
+                        if let Some(did) = imported_type(item.id) {
+                            add(ident.name, did);
+                        }
+                    }
+                    ast::ViewPathList(_, ref list) => {
+                        for path_item in list {
+                            if let ast::PathListIdent { name, id } = path_item.node {
+                                if let Some(did) = imported_type(id) {
+                                    add(name.name, did);
+                                }
+                            }
+                        }
+                    }
+                    ast::ViewPathGlob(_) => {}
+                },
+                _ => {}
+            }
+        }
+    }
+
+    let names = Rc::new(names);
+    cx.type_names_in_module.borrow_mut().insert(module, names.clone());
+    names
+}
+
 #[derive(Copy)]
 pub enum DtorKind {
     NoDtor,
@@ -5870,13 +6065,62 @@ pub fn is_binopable<'tcx>(cx: &ctxt<'tcx>, ty: Ty<'tcx>, op: ast::BinOp) -> bool
 }
 
 // Returns the repeat count for a repeating vector expression.
+/// The largest value of `usize` on the target.
+pub fn target_usize_max(tcx: &ctxt) -> u64 {
+    match &tcx.sess.target.target.target_pointer_width[..] {
+        "32" => u32::MAX as u64,
+        "64" => u64::MAX,
+        _ => unreachable!() // error handled by config::build_target_config
+    }
+}
+
+/// Interpret the integer `val`, the value of `expr`, as an array length or
+/// a repeat count (`what` says which). Negative values and values that do
+/// not fit in the target's `usize` are reported, and `None` returned.
+pub fn array_length_from_const(tcx: &ctxt,
+                               expr: &ast::Expr,
+                               val: &const_eval::const_val,
+                               what: &str)
+                               -> Option<uint> {
+    fn is_negation(expr: &ast::Expr) -> bool {
+        match expr.node {
+            ast::ExprParen(ref e) => is_negation(&**e),
+            ast::ExprUnary(ast::UnNeg, _) => true,
+            _ => false
+        }
+    }
+
+    let len = match *val {
+        const_eval::const_int(n) if n < 0 => None,
+        const_eval::const_int(n) => Some(n as u64),
+        // Lengths are evaluated as `usize`, so `-1` wraps around instead of
+        // coming out negative.
+        const_eval::const_uint(n) if n != 0 && is_negation(expr) => None,
+        const_eval::const_uint(n) => Some(n),
+        _ => tcx.sess.span_bug(expr.span, "array length is not an integer")
+    };
+    match len {
+        None => {
+            span_err!(tcx.sess, expr.span, E0337, "expected usize, found negative value");
+            None
+        }
+        Some(n) if n > target_usize_max(tcx) => {
+            span_err!(tcx.sess, expr.span, E0338,
+                      "{} `{}` does not fit in the target's `usize`", what, n);
+            None
+        }
+        Some(n) => Some(n as uint)
+    }
+}
+
 pub fn eval_repeat_count(tcx: &ctxt, count_expr: &ast::Expr) -> uint {
     match const_eval::eval_const_expr_partial(tcx, count_expr, Some(tcx.types.uint)) {
         Ok(val) => {
             let found = match val {
-                const_eval::const_uint(count) => return count as uint,
-                const_eval::const_int(count) if count >= 0 => return count as uint,
-                const_eval::const_int(_) => "negative integer",
+                const_eval::const_uint(_) | const_eval::const_int(_) => {
+                    return array_length_from_const(tcx, count_expr, &val, "repeat count")
+                        .unwrap_or(0);
+                }
                 const_eval::const_float(_) => "float",
                 const_eval::const_str(_) => "string",
                 const_eval::const_bool(_) => "boolean",
@@ -6716,6 +6960,7 @@ impl<'tcx> AutoAdjustment<'tcx> {
         match *self {
             AdjustReifyFnPointer(..) => false,
             AdjustUnsafeFnPointer(..) => false,
+            AdjustClosureFnPointer(..) => false,
             AdjustDerefRef(ref r) => r.is_identity(),
         }
     }
@@ -6868,6 +7113,9 @@ impl<'tcx> Repr<'tcx> for AutoAdjustment<'tcx> {
             AdjustUnsafeFnPointer => {
                 format!("AdjustUnsafeFnPointer")
             }
+            AdjustClosureFnPointer(fn_ty) => {
+                format!("AdjustClosureFnPointer({})", fn_ty.repr(tcx))
+            }
             AdjustDerefRef(ref data) => {
                 data.repr(tcx)
             }
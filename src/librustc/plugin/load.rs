@@ -33,6 +33,8 @@ pub type PluginRegistrarFun =
     fn(&mut Registry);
 
 pub struct PluginRegistrar {
+    /// Name of the plugin crate the registrar comes from.
+    pub name: String,
     pub fun: PluginRegistrarFun,
     pub args: Vec<P<ast::MetaItem>>,
 }
@@ -96,6 +98,7 @@ impl<'a> PluginLoader<'a> {
         if let Some((lib, symbol)) = registrar {
             let fun = self.dylink_registrar(span, lib, symbol);
             self.plugins.push(PluginRegistrar {
+                name: name.to_string(),
                 fun: fun,
                 args: args,
             });
@@ -17,12 +17,14 @@ use syntax::ext::base::{SyntaxExtension, NamedSyntaxExtension, NormalTT};
 use syntax::ext::base::{IdentTT, Decorator, Modifier, MultiModifier, MacroRulesTT};
 use syntax::ext::base::{MacroExpanderFn};
 use syntax::codemap::Span;
+use syntax::feature_gate::AttributeType;
 use syntax::parse::token;
 use syntax::ptr::P;
 use syntax::ast;
 
 use std::collections::HashMap;
 
+
 /// Structure used to register plugins.
 ///
 /// A plugin registrar function takes an `&mut Registry` and should call
@@ -42,14 +44,23 @@ pub struct Registry<'a> {
     #[doc(hidden)]
     pub krate_span: Span,
 
+    #[doc(hidden)]
+    pub plugin_name: String,
+
     #[doc(hidden)]
     pub syntax_exts: Vec<NamedSyntaxExtension>,
 
+    #[doc(hidden)]
+    pub syntax_ext_owners: HashMap<ast::Name, String>,
+
     #[doc(hidden)]
     pub lint_passes: Vec<LintPassObject>,
 
     #[doc(hidden)]
-    pub lint_groups: HashMap<&'static str, Vec<LintId>>,
+    pub lint_groups: Vec<(&'static str, Vec<LintId>)>,
+
+    #[doc(hidden)]
+    pub attributes: Vec<(String, AttributeType)>,
 }
 
 impl<'a> Registry<'a> {
@@ -59,9 +70,12 @@ impl<'a> Registry<'a> {
             sess: sess,
             args_hidden: None,
             krate_span: krate.span,
+            plugin_name: "rustc".to_string(),
             syntax_exts: vec!(),
+            syntax_ext_owners: HashMap::new(),
             lint_passes: vec!(),
-            lint_groups: HashMap::new(),
+            lint_groups: vec!(),
+            attributes: vec!(),
         }
     }
 
@@ -79,7 +93,20 @@ impl<'a> Registry<'a> {
     /// Register a syntax extension of any kind.
     ///
     /// This is the most general hook into `libsyntax`'s expansion behavior.
+    /// Each name can only be registered once across all loaded plugins.
     pub fn register_syntax_extension(&mut self, name: ast::Name, extension: SyntaxExtension) {
+        if let Some(owner) = self.syntax_ext_owners.get(&name) {
+            let msg = if *owner == self.plugin_name {
+                format!("syntax extension `{}` is registered twice by the `{}` plugin",
+                        token::get_name(name), owner)
+            } else {
+                format!("syntax extension `{}` is registered by both the `{}` and `{}` plugins",
+                        token::get_name(name), owner, self.plugin_name)
+            };
+            self.sess.err(&msg);
+            return;
+        }
+        self.syntax_ext_owners.insert(name, self.plugin_name.clone());
         self.syntax_exts.push((name, match extension {
             NormalTT(ext, _, allow_internal_unstable) => {
                 NormalTT(ext, Some(self.krate_span), allow_internal_unstable)
@@ -107,13 +134,37 @@ impl<'a> Registry<'a> {
                                        NormalTT(Box::new(expander), None, false));
     }
 
-    /// Register a compiler lint pass.
+    /// Register a compiler lint pass, either an early one running on the
+    /// AST (`LintPassObject::Early`) or a late one with access to type
+    /// information (`LintPassObject::Late`).
     pub fn register_lint_pass(&mut self, lint_pass: LintPassObject) {
         self.lint_passes.push(lint_pass);
     }
 
+    /// Register an attribute the plugin gives a meaning to, so it isn't
+    /// rejected as unknown.
+    ///
+    /// `Whitelisted` and `CrateLevel` attributes are never reported as
+    /// unused, and `CrateLevel` ones are only allowed as `#![name]` at the
+    /// crate root. `Normal` attributes may appear anywhere, and are reported
+    /// as unused unless the plugin marks them used (e.g. with `check_name`).
+    pub fn register_attribute(&mut self, name: String, ty: AttributeType) {
+        if let AttributeType::Gated(..) = ty {
+            self.sess.err(&format!("the `{}` plugin tried to register the gated \
+                                    attribute `{}`", self.plugin_name, name));
+            return;
+        }
+        self.attributes.push((name, ty));
+    }
+
     /// Register a lint group.
+    ///
+    /// Registering a group with the same name again replaces it.
     pub fn register_lint_group(&mut self, name: &'static str, to: Vec<&'static Lint>) {
-        self.lint_groups.insert(name, to.into_iter().map(|x| LintId::of(x)).collect());
+        let to = to.into_iter().map(|x| LintId::of(x)).collect();
+        match self.lint_groups.iter().position(|&(group, _)| group == name) {
+            Some(i) => self.lint_groups[i].1 = to,
+            None => self.lint_groups.push((name, to)),
+        }
     }
 }
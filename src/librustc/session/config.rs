@@ -63,14 +63,16 @@ pub enum DebugInfoLevel {
     FullDebugInfo,
 }
 
-#[derive(Clone, Copy, PartialEq, PartialOrd, Ord, Eq)]
+#[derive(Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Hash)]
 pub enum OutputType {
     OutputTypeBitcode,
     OutputTypeAssembly,
     OutputTypeLlvmAssembly,
+    OutputTypeUnoptLlvmAssembly,
     OutputTypeObject,
     OutputTypeExe,
     OutputTypeDepInfo,
+    OutputTypeExportedSymbols,
 }
 
 #[derive(Clone)]
@@ -84,8 +86,13 @@ pub struct Options {
     pub debug_assertions: bool,
     pub debuginfo: DebugInfoLevel,
     pub lint_opts: Vec<(String, lint::Level)>,
+    /// The most severe level any lint is reported at, from `--cap-lints`.
+    pub lint_cap: Option<lint::Level>,
     pub describe_lints: bool,
     pub output_types: Vec<OutputType>,
+    // Paths given explicitly for individual output types with
+    // `--emit kind=path`. These take precedence over `-o` and `--out-dir`.
+    pub output_paths: HashMap<OutputType, PathBuf>,
     // This was mutable for rustpkg, which updates search paths based on the
     // parsed code. It remains mutable in case its replacements wants to use
     // this.
@@ -140,13 +147,14 @@ pub enum PrintRequest {
     FileNames,
     Sysroot,
     CrateName,
+    TargetSpecJson,
 }
 
 pub enum Input {
     /// Load source from file
     File(PathBuf),
-    /// The string is the source
-    Str(String)
+    /// The string is the source, `name` is what the codemap calls it
+    Str { name: String, input: String }
 }
 
 impl Input {
@@ -154,7 +162,7 @@ impl Input {
         match *self {
             Input::File(ref ifile) => ifile.file_stem().unwrap()
                                            .to_str().unwrap().to_string(),
-            Input::Str(_) => "rust_out".to_string(),
+            Input::Str { .. } => "rust_out".to_string(),
         }
     }
 }
@@ -165,10 +173,17 @@ pub struct OutputFilenames {
     pub out_filestem: String,
     pub single_output_file: Option<PathBuf>,
     pub extra: String,
+    pub outputs: HashMap<OutputType, PathBuf>,
+    // The output requested with `-o -`. It is written to its usual path
+    // first and then streamed to stdout.
+    pub stdout_output: Option<OutputType>,
 }
 
 impl OutputFilenames {
     pub fn path(&self, flavor: OutputType) -> PathBuf {
+        if let Some(path) = self.outputs.get(&flavor) {
+            return path.clone();
+        }
         match self.single_output_file {
             Some(ref path) => return path.clone(),
             None => {}
@@ -182,8 +197,10 @@ impl OutputFilenames {
             OutputTypeBitcode => base.with_extension("bc"),
             OutputTypeAssembly => base.with_extension("s"),
             OutputTypeLlvmAssembly => base.with_extension("ll"),
+            OutputTypeUnoptLlvmAssembly => base.with_extension("unopt.ll"),
             OutputTypeObject => base.with_extension("o"),
             OutputTypeDepInfo => base.with_extension("d"),
+            OutputTypeExportedSymbols => base.with_extension("symbols"),
             OutputTypeExe => base,
         }
     }
@@ -218,8 +235,10 @@ pub fn basic_options() -> Options {
         optimize: No,
         debuginfo: NoDebugInfo,
         lint_opts: Vec::new(),
+        lint_cap: None,
         describe_lints: false,
         output_types: Vec::new(),
+        output_paths: HashMap::new(),
         search_paths: SearchPaths::new(),
         maybe_sysroot: None,
         target_triple: host_triple().to_string(),
@@ -263,6 +282,12 @@ pub enum CrateType {
     CrateTypeStaticlib,
 }
 
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PanicStrategy {
+    Unwind,
+    Abort,
+}
+
 #[derive(Clone)]
 pub enum Passes {
     SomePasses(Vec<String>),
@@ -312,7 +337,7 @@ macro_rules! options {
             let mut found = false;
             for &(candidate, setter, opt_type_desc, _) in $stat {
                 if option_to_lookup != candidate { continue }
-                if !setter(&mut op, value) {
+                if let Err(cause) = setter(&mut op, value) {
                     match (value, opt_type_desc) {
                         (Some(..), None) => {
                             early_error(&format!("{} option `{}` takes no \
@@ -325,10 +350,14 @@ macro_rules! options {
                                                 type_desc, $prefix))
                         }
                         (Some(value), Some(type_desc)) => {
+                            let cause = match cause {
+                                Some(cause) => format!(" ({})", cause),
+                                None => String::new(),
+                            };
                             early_error(&format!("incorrect value `{}` for {} \
-                                                 option `{}` - {} was expected",
+                                                 option `{}` - {} was expected{}",
                                                  value, $outputname,
-                                                 key, type_desc))
+                                                 key, type_desc, cause))
                         }
                         (None, None) => unreachable!()
                     }
@@ -344,7 +373,10 @@ macro_rules! options {
         return op;
     }
 
-    pub type $setter_name = fn(&mut $struct_name, v: Option<&str>) -> bool;
+    /// Sets an option from its command-line value. The error may carry the
+    /// reason the value was rejected.
+    pub type $setter_name = fn(&mut $struct_name, v: Option<&str>)
+                               -> Result<(), Option<String>>;
     pub const $stat: &'static [(&'static str, $setter_name,
                                      Option<&'static str>, &'static str)] =
         &[ $( (stringify!($opt), $mod_set::$opt, $mod_desc::$parse, $desc) ),* ];
@@ -363,26 +395,30 @@ macro_rules! options {
             Some("a space-separated list of passes, or `all`");
         pub const parse_opt_uint: Option<&'static str> =
             Some("a number");
+        pub const parse_panic_strategy: Option<&'static str> =
+            Some("either `unwind` or `abort`");
     }
 
     #[allow(dead_code)]
     mod $mod_set {
-        use super::{$struct_name, Passes, SomePasses, AllPasses};
+        use super::{$struct_name, Passes, SomePasses, AllPasses, PanicStrategy};
+
+        type SetResult = Result<(), Option<String>>;
 
         $(
-            pub fn $opt(cg: &mut $struct_name, v: Option<&str>) -> bool {
+            pub fn $opt(cg: &mut $struct_name, v: Option<&str>) -> SetResult {
                 $parse(&mut cg.$opt, v)
             }
         )*
 
-        fn parse_bool(slot: &mut bool, v: Option<&str>) -> bool {
+        fn parse_bool(slot: &mut bool, v: Option<&str>) -> SetResult {
             match v {
-                Some(..) => false,
-                None => { *slot = true; true }
+                Some(..) => Err(None),
+                None => { *slot = true; Ok(()) }
             }
         }
 
-        fn parse_opt_bool(slot: &mut Option<bool>, v: Option<&str>) -> bool {
+        fn parse_opt_bool(slot: &mut Option<bool>, v: Option<&str>) -> SetResult {
             match v {
                 Some(s) => {
                     match s {
@@ -392,85 +428,93 @@ macro_rules! options {
                         "y" | "yes" | "on" => {
                             *slot = Some(true);
                         }
-                        _ => { return false; }
+                        _ => { return Err(None); }
                     }
 
-                    true
+                    Ok(())
                 },
-                None => { *slot = Some(true); true }
+                None => { *slot = Some(true); Ok(()) }
             }
         }
 
-        fn parse_opt_string(slot: &mut Option<String>, v: Option<&str>) -> bool {
+        fn parse_opt_string(slot: &mut Option<String>, v: Option<&str>) -> SetResult {
             match v {
-                Some(s) => { *slot = Some(s.to_string()); true },
-                None => false,
+                Some(s) => { *slot = Some(s.to_string()); Ok(()) },
+                None => Err(None),
             }
         }
 
-        fn parse_string(slot: &mut String, v: Option<&str>) -> bool {
+        fn parse_string(slot: &mut String, v: Option<&str>) -> SetResult {
             match v {
-                Some(s) => { *slot = s.to_string(); true },
-                None => false,
+                Some(s) => { *slot = s.to_string(); Ok(()) },
+                None => Err(None),
             }
         }
 
         fn parse_list(slot: &mut Vec<String>, v: Option<&str>)
-                      -> bool {
+                      -> SetResult {
             match v {
                 Some(s) => {
                     for s in s.words() {
                         slot.push(s.to_string());
                     }
-                    true
+                    Ok(())
                 },
-                None => false,
+                None => Err(None),
             }
         }
 
         fn parse_opt_list(slot: &mut Option<Vec<String>>, v: Option<&str>)
-                      -> bool {
+                      -> SetResult {
             match v {
                 Some(s) => {
                     let v = s.words().map(|s| s.to_string()).collect();
                     *slot = Some(v);
-                    true
+                    Ok(())
                 },
-                None => false,
+                None => Err(None),
             }
         }
 
-        fn parse_uint(slot: &mut uint, v: Option<&str>) -> bool {
-            match v.and_then(|s| s.parse().ok()) {
-                Some(i) => { *slot = i; true },
-                None => false
+        fn parse_uint(slot: &mut uint, v: Option<&str>) -> SetResult {
+            match v.map(|s| s.parse()) {
+                Some(Ok(i)) => { *slot = i; Ok(()) },
+                Some(Err(e)) => Err(Some(e.to_string())),
+                None => Err(None)
             }
         }
 
-        fn parse_opt_uint(slot: &mut Option<uint>, v: Option<&str>) -> bool {
-            match v {
-                Some(s) => { *slot = s.parse().ok(); slot.is_some() }
-                None => { *slot = None; true }
+        fn parse_opt_uint(slot: &mut Option<uint>, v: Option<&str>) -> SetResult {
+            match v.map(|s| s.parse()) {
+                Some(Ok(i)) => { *slot = Some(i); Ok(()) }
+                Some(Err(e)) => { *slot = None; Err(Some(e.to_string())) }
+                None => { *slot = None; Ok(()) }
             }
         }
 
-        fn parse_passes(slot: &mut Passes, v: Option<&str>) -> bool {
+        fn parse_passes(slot: &mut Passes, v: Option<&str>) -> SetResult {
             match v {
                 Some("all") => {
                     *slot = AllPasses;
-                    true
+                    Ok(())
                 }
                 v => {
                     let mut passes = vec!();
-                    if parse_list(&mut passes, v) {
-                        *slot = SomePasses(passes);
-                        true
-                    } else {
-                        false
-                    }
+                    try!(parse_list(&mut passes, v));
+                    *slot = SomePasses(passes);
+                    Ok(())
                 }
             }
         }
+
+        fn parse_panic_strategy(slot: &mut PanicStrategy, v: Option<&str>) -> SetResult {
+            match v {
+                Some("unwind") => *slot = PanicStrategy::Unwind,
+                Some("abort") => *slot = PanicStrategy::Abort,
+                _ => return Err(None)
+            }
+            Ok(())
+        }
     }
 ) }
 
@@ -515,6 +559,10 @@ options! {CodegenOptions, CodegenSetter, basic_codegen_options,
          "choose the relocation model to use (llc -relocation-model for details)"),
     code_model: Option<String> = (None, parse_opt_string,
          "choose the code model to use (llc -code-model for details)"),
+    macosx_version_min: Option<String> = (None, parse_opt_string,
+         "oldest OS X version to deploy to (defaults to $MACOSX_DEPLOYMENT_TARGET)"),
+    ios_version_min: Option<String> = (None, parse_opt_string,
+         "oldest iOS version to deploy to (defaults to $IPHONEOS_DEPLOYMENT_TARGET)"),
     metadata: Vec<String> = (Vec::new(), parse_list,
          "metadata to mangle symbol names with"),
     extra_filename: String = ("".to_string(), parse_string,
@@ -532,6 +580,10 @@ options! {CodegenOptions, CodegenSetter, basic_codegen_options,
         "Optimize with possible levels 0-3"),
     debug_assertions: Option<bool> = (None, parse_opt_bool,
         "explicitly enable the cfg(debug_assertions) directive"),
+    default_hidden_visibility: Option<bool> = (None, parse_opt_bool,
+        "give symbols that aren't exported from the crate hidden visibility"),
+    panic: PanicStrategy = (PanicStrategy::Unwind, parse_panic_strategy,
+        "panic strategy: `unwind`, or `abort` to omit landing pads"),
 }
 
 
@@ -550,8 +602,10 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "gather trans statistics"),
     asm_comments: bool = (false, parse_bool,
         "generate comments into the assembly (may change behavior)"),
-    no_verify: bool = (false, parse_bool,
-        "skip LLVM verification"),
+    verify_llvm_ir: bool = (false, parse_bool,
+        "verify LLVM IR (always done by builds of rustc with debug assertions)"),
+    corrupt_llvm_ir: bool = (false, parse_bool,
+        "add invalid IR to each LLVM module before verifying it (for testing rustc)"),
     borrowck_stats: bool = (false, parse_bool,
         "gather borrowck statistics"),
     no_landing_pads: bool = (false, parse_bool,
@@ -562,20 +616,35 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "count the sizes of aggregate types"),
     meta_stats: bool = (false, parse_bool,
         "gather metadata statistics"),
+    crate_stats: bool = (false, parse_bool,
+        "print the number of items of each kind and the size of the largest function \
+         bodies after resolution"),
     print_link_args: bool = (false, parse_bool,
         "Print the arguments passed to the linker"),
     gc: bool = (false, parse_bool,
         "Garbage collect shared data (experimental)"),
     print_llvm_passes: bool = (false, parse_bool,
         "Prints the llvm optimization passes being run"),
+    print_vtables: bool = (false, parse_bool,
+        "Print where each slot of every vtable being translated comes from"),
+    report_dummy_spans: bool = (false, parse_bool,
+        "Print a backtrace for each error reported without a usable span"),
+    deterministic_ids: bool = (false, parse_bool,
+        "Check that node ids only depend on the expanded source of the crate"),
+    keep_going: bool = (false, parse_bool,
+        "Run as many analysis passes as possible despite errors from earlier ones"),
     ast_json: bool = (false, parse_bool,
         "Print the AST as JSON and halt"),
     ast_json_noexpand: bool = (false, parse_bool,
         "Print the pre-expansion AST as JSON and halt"),
     ls: bool = (false, parse_bool,
         "List the symbols defined by a library crate"),
+    demangle: bool = (false, parse_bool,
+        "Demangle the symbol names read from stdin, one per line, and exit"),
     save_analysis: bool = (false, parse_bool,
         "Write syntax and type analysis information in addition to normal output"),
+    save_types: Option<String> = (None, parse_opt_string,
+        "Write the type of every expression and pattern binding to the given file"),
     print_move_fragments: bool = (false, parse_bool,
         "Print out move-fragment data for every fn"),
     flowgraph_print_loans: bool = (false, parse_bool,
@@ -589,10 +658,13 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
     print_region_graph: bool = (false, parse_bool,
          "Prints region inference graph. \
           Use with RUST_REGION_GRAPH=help for more info"),
+    dump_region_graph: Option<String> = (None, parse_opt_string,
+         "dump the region constraints and inferred regions of each function whose \
+          path contains the given string to a .dot file"),
     parse_only: bool = (false, parse_bool,
           "Parse only; do not compile, assemble, or link"),
     no_trans: bool = (false, parse_bool,
-          "Run all passes except translation; no output"),
+          "Run all passes except translation; no output (the same as --check)"),
     treat_err_as_bug: bool = (false, parse_bool,
           "Treat all errors that occur as bugs"),
     no_analysis: bool = (false, parse_bool,
@@ -603,8 +675,29 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
           "Adds unstable command line options to rustc interface"),
     print_enum_sizes: bool = (false, parse_bool,
           "Print the size of enums and their variants"),
+    print_type_sizes: bool = (false, parse_bool,
+          "Print the size, alignment and field offsets of every translated struct and enum"),
+    print_reachable: bool = (false, parse_bool,
+          "Print the items that other crates may link against after reachability checking"),
     force_overflow_checks: Option<bool> = (None, parse_opt_bool,
           "Force overflow checks on or off"),
+    instrument_functions: bool = (false, parse_bool,
+          "Call `__rust_profile_enter` on entry to every function that isn't inlined"),
+    print_llvm_timings: bool = (false, parse_bool,
+          "Print how long LLVM's function passes took on the ten slowest functions \
+           of each codegen unit"),
+    llvm_time_limit: Option<uint> = (None, parse_opt_uint,
+          "Warn about each function that LLVM's function passes spend more than \
+           the given number of seconds on"),
+    future_wf_errors: bool = (false, parse_bool,
+          "Report types in method signatures that do not meet their own bounds as errors \
+           rather than through the `ill_formed_signatures` lint"),
+    private_in_public_errors: bool = (false, parse_bool,
+          "Report private types and traits in exported signatures as errors \
+           rather than through the `private_in_public` lint"),
+    remap_path_prefix: Option<String> = (None, parse_opt_string,
+          "Rewrite paths starting with FROM to start with TO in debuginfo and panic \
+           messages (FROM=TO)"),
 }
 
 pub fn default_lib_output() -> CrateType {
@@ -618,6 +711,8 @@ pub fn default_configuration(sess: &Session) -> ast::CrateConfig {
     let arch = &sess.target.target.arch;
     let wordsz = &sess.target.target.target_pointer_width;
     let os = &sess.target.target.target_os;
+    let env = &sess.target.target.options.target_env;
+    let vendor = &sess.target.target.options.target_vendor;
 
     let fam = match sess.target.target.options.is_like_windows {
         true  => InternedString::new("windows"),
@@ -632,18 +727,48 @@ pub fn default_configuration(sess: &Session) -> ast::CrateConfig {
          mk(InternedString::new("target_arch"), intern(arch)),
          mk(InternedString::new("target_endian"), intern(end)),
          mk(InternedString::new("target_pointer_width"),
-            intern(wordsz))
+            intern(wordsz)),
+         mk(InternedString::new("target_env"), intern(env)),
+         mk(InternedString::new("target_vendor"), intern(vendor)),
     ];
+    for feature in target_features(sess) {
+        ret.push(mk(InternedString::new("target_feature"), intern(&feature)));
+    }
     if sess.opts.debug_assertions {
         ret.push(attr::mk_word_item(InternedString::new("debug_assertions")));
     }
     return ret;
 }
 
+/// The features enabled by the target specification and `-C target-feature`,
+/// in the form `cfg(target_feature = "...")` matches against: `+sse2` is
+/// reported as `sse2`, and a later `-sse2` turns it off again.
+pub fn target_features(sess: &Session) -> Vec<String> {
+    let target = &sess.target.target.options.features;
+    let cmdline = &sess.opts.cg.target_feature;
+    let mut features: Vec<String> = Vec::new();
+    for feature in target.split(',').chain(cmdline.split(',')) {
+        let feature = feature.trim();
+        if feature.starts_with("-") {
+            features.retain(|f| *f != feature[1..]);
+            continue;
+        }
+        let name = if feature.starts_with("+") { &feature[1..] } else { feature };
+        if !name.is_empty() && !features.iter().any(|f| *f == name) {
+            features.push(name.to_string());
+        }
+    }
+    features
+}
+
+/// Add the word `name` to the configuration, unless it is already there.
+/// Only the bare word counts: `--cfg test="x"` is another value of the key
+/// and doesn't make `cfg(test)` hold.
 pub fn append_configuration(cfg: &mut ast::CrateConfig,
                             name: InternedString) {
-    if !cfg.iter().any(|mi| mi.name() == name) {
-        cfg.push(attr::mk_word_item(name))
+    let word = attr::mk_word_item(name);
+    if !attr::contains(cfg, &*word) {
+        cfg.push(word)
     }
 }
 
@@ -656,8 +781,14 @@ pub fn build_configuration(sess: &Session) -> ast::CrateConfig {
     if sess.opts.test {
         append_configuration(&mut user_cfg, InternedString::new("test"))
     }
-    let mut v = user_cfg.into_iter().collect::<Vec<_>>();
-    v.push_all(&default_cfg[..]);
+    // The configuration is a multimap, so a key given several times keeps
+    // all of its values; only entries repeated exactly are dropped.
+    let mut v: ast::CrateConfig = Vec::new();
+    for mi in user_cfg.into_iter().chain(default_cfg.into_iter()) {
+        if !attr::contains(&v, &*mi) {
+            v.push(mi);
+        }
+    }
     v
 }
 
@@ -777,11 +908,12 @@ pub fn rustc_short_optgroups() -> Vec<RustcOptGroup> {
         opt::opt("", "crate-name", "Specify the name of the crate being built",
                "NAME"),
         opt::multi("", "emit", "Comma separated list of types of output for \
-                              the compiler to emit",
-                 "[asm|llvm-bc|llvm-ir|obj|link|dep-info]"),
+                              the compiler to emit, each optionally followed \
+                              by `=PATH` to name its output file",
+                 "[asm|llvm-bc|llvm-ir|llvm-ir-unopt|obj|link|dep-info|exported-symbols][=PATH]"),
         opt::multi("", "print", "Comma separated list of compiler information to \
                                print on stdout",
-                 "[crate-name|file-names|sysroot]"),
+                 "[crate-name|file-names|sysroot|target-spec-json]"),
         opt::flag("g",  "",  "Equivalent to -C debuginfo=2"),
         opt::flag("O", "", "Equivalent to -C opt-level=2"),
         opt::opt("o", "", "Write output to <filename>", "FILENAME"),
@@ -789,7 +921,11 @@ pub fn rustc_short_optgroups() -> Vec<RustcOptGroup> {
                                 in <dir>", "DIR"),
         opt::opt("", "explain", "Provide a detailed explanation of an error \
                                message", "OPT"),
-        opt::flag("", "test", "Build a test harness"),
+        opt::flag("", "test", "Build a test harness, and with --crate-type \
+                               also the crate itself from the same parse"),
+        opt::flag("", "check", "Run the analysis passes, including lints, without \
+                                translating or linking the crate or writing \
+                                any output files"),
         opt::opt("", "target", "Target triple cpu-manufacturer-kernel[-os] \
                               to compile for (see chapter 3.4 of \
                               http://www.sourceware.org/autobook/
@@ -799,6 +935,9 @@ pub fn rustc_short_optgroups() -> Vec<RustcOptGroup> {
         opt::multi("A", "allow", "Set lint allowed", "OPT"),
         opt::multi("D", "deny", "Set lint denied", "OPT"),
         opt::multi("F", "forbid", "Set lint forbidden", "OPT"),
+        opt::opt("", "cap-lints", "Set the most restrictive lint level. \
+                                   More restrictive lints are capped at this level",
+                 "LEVEL"),
         opt::multi("C", "codegen", "Set a codegen option", "OPT[=VALUE]"),
         opt::flag("V", "version", "Print version info and exit"),
         opt::flag("v", "verbose", "Use verbose output"),
@@ -854,23 +993,56 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
     let crate_types = parse_crate_types_from_list(unparsed_crate_types)
         .unwrap_or_else(|e| early_error(&e[..]));
 
-    let mut lint_opts = vec!();
+    let mut lint_opts_pos = vec!();
     let mut describe_lints = false;
 
     for &level in &[lint::Allow, lint::Warn, lint::Deny, lint::Forbid] {
-        for lint_name in matches.opt_strs(level.as_str()) {
+        for (pos, lint_name) in matches.opt_strs_pos(level.as_str()) {
             if lint_name == "help" {
                 describe_lints = true;
             } else {
-                lint_opts.push((lint_name.replace("-", "_"), level));
+                lint_opts_pos.push((pos, lint_name.replace("-", "_"), level));
             }
         }
     }
 
+    // Lint flags apply in the order they were given, so that a later flag
+    // overrides an earlier one for the same lint.
+    lint_opts_pos.sort_by(|&(a, _, _), &(b, _, _)| a.cmp(&b));
+    let lint_opts: Vec<_> = lint_opts_pos.into_iter()
+                                         .map(|(_, name, level)| (name, level))
+                                         .collect();
+
+    let lint_cap = matches.opt_str("cap-lints").map(|cap| {
+        lint::Level::from_str(&cap).unwrap_or_else(|| {
+            early_error(&format!("unknown lint level: `{}`", cap))
+        })
+    });
+
     let debugging_opts = build_debugging_options(matches);
 
+    if let Some(ref mapping) = debugging_opts.remap_path_prefix {
+        if !mapping.contains("=") {
+            early_error(&format!("`-Z remap-path-prefix` expects FROM=TO, found `{}`",
+                                 mapping));
+        }
+    }
+
     let parse_only = debugging_opts.parse_only;
-    let no_trans = debugging_opts.no_trans;
+    // `--check` is the stable spelling of `-Z no-trans`: analysis runs to the
+    // end, including lints, and nothing is translated, linked or written.
+    let no_trans = matches.opt_present("check") || debugging_opts.no_trans;
+    if no_trans {
+        let flag = if matches.opt_present("check") { "--check" } else { "-Z no-trans" };
+        if matches.opt_present("o") {
+            early_error(&format!("`{}` produces no output files and cannot be combined \
+                                  with `-o`", flag));
+        }
+        if matches.opt_present("emit") {
+            early_error(&format!("`{}` produces no output files and cannot be combined \
+                                  with `--emit`", flag));
+        }
+    }
     let treat_err_as_bug = debugging_opts.treat_err_as_bug;
     let no_analysis = debugging_opts.no_analysis;
 
@@ -879,29 +1051,41 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
     }
 
     let mut output_types = Vec::new();
+    let mut output_paths = HashMap::new();
     if !debugging_opts.parse_only && !no_trans {
         let unparsed_output_types = matches.opt_strs("emit");
         for unparsed_output_type in &unparsed_output_types {
             for part in unparsed_output_type.split(',') {
-                let output_type = match part {
+                let mut parts = part.splitn(2, '=');
+                let kind = parts.next().unwrap();
+                let output_type = match kind {
                     "asm" => OutputTypeAssembly,
                     "llvm-ir" => OutputTypeLlvmAssembly,
+                    "llvm-ir-unopt" => OutputTypeUnoptLlvmAssembly,
                     "llvm-bc" => OutputTypeBitcode,
                     "obj" => OutputTypeObject,
                     "link" => OutputTypeExe,
                     "dep-info" => OutputTypeDepInfo,
+                    "exported-symbols" => OutputTypeExportedSymbols,
                     _ => {
                         early_error(&format!("unknown emission type: `{}`",
-                                            part))
+                                            kind))
                     }
                 };
+                if let Some(path) = parts.next() {
+                    if path.is_empty() {
+                        early_error(&format!("empty output path for emission type `{}`",
+                                            kind))
+                    }
+                    output_paths.insert(output_type, PathBuf::from(path));
+                }
                 output_types.push(output_type)
             }
         }
     };
     output_types.sort();
     output_types.dedup();
-    if output_types.len() == 0 {
+    if output_types.len() == 0 && !no_trans {
         output_types.push(OutputTypeExe);
     }
 
@@ -993,13 +1177,15 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
 
     let cfg = parse_cfgspecs(matches.opt_strs("cfg"));
     let test = matches.opt_present("test");
-    let write_dependency_info = (output_types.contains(&OutputTypeDepInfo), None);
+    let write_dependency_info = (output_types.contains(&OutputTypeDepInfo),
+                                 output_paths.get(&OutputTypeDepInfo).cloned());
 
     let prints = matches.opt_strs("print").into_iter().map(|s| {
         match &*s {
             "crate-name" => PrintRequest::CrateName,
             "file-names" => PrintRequest::FileNames,
             "sysroot" => PrintRequest::Sysroot,
+            "target-spec-json" => PrintRequest::TargetSpecJson,
             req => {
                 early_error(&format!("unknown print request `{}`", req))
             }
@@ -1051,8 +1237,10 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         optimize: opt_level,
         debuginfo: debuginfo,
         lint_opts: lint_opts,
+        lint_cap: lint_cap,
         describe_lints: describe_lints,
         output_types: output_types,
+        output_paths: output_paths,
         search_paths: search_paths,
         maybe_sysroot: sysroot_opt,
         target_triple: target,
@@ -1175,6 +1363,43 @@ mod test {
         assert!(test_items.next().is_none());
     }
 
+    // A valued `--cfg test="x"` doesn't stop --test from adding the word
+    // `test`, and repeated keys keep all of their values
+    #[test]
+    fn test_switch_implies_cfg_test_despite_valued_test() {
+        let matches =
+            &match getopts(&["--test".to_string(), "--cfg=test=\"x\"".to_string(),
+                             "--cfg=feature=\"a\"".to_string(),
+                             "--cfg=feature=\"b\"".to_string(),
+                             "--cfg=feature=\"a\"".to_string()],
+                           &optgroups()) {
+              Ok(m) => m,
+              Err(f) => {
+                panic!("test_switch_implies_cfg_test_despite_valued_test: {}", f)
+              }
+            };
+        let registry = diagnostics::registry::Registry::new(&[]);
+        let sessopts = build_session_options(matches);
+        let sess = build_session(sessopts, None, registry);
+        let cfg = build_configuration(&sess);
+        assert_eq!(cfg.iter().filter(|m| m.name() == "test").count(), 2);
+        assert!(cfg.iter().any(|m| m.name() == "test" && m.value_str().is_none()));
+        let features: Vec<_> = cfg.iter().filter(|m| m.name() == "feature")
+                                  .map(|m| m.value_str().unwrap()).collect();
+        assert_eq!(features.len(), 2);
+        assert_eq!(&features[0][..], "a");
+        assert_eq!(&features[1][..], "b");
+    }
+
+    // --check is -Z no-trans, and leaves no default output to write
+    #[test]
+    fn test_check_switch_requests_no_outputs() {
+        let matches = getopts(&["--check".to_string()], &optgroups()).unwrap();
+        let sessopts = build_session_options(&matches);
+        assert!(sessopts.no_trans);
+        assert!(sessopts.output_types.is_empty());
+    }
+
     #[test]
     fn test_can_print_warnings() {
         {
@@ -45,13 +45,15 @@ pub struct Session {
     pub entry_fn: RefCell<Option<(NodeId, codemap::Span)>>,
     pub entry_type: Cell<Option<config::EntryFnType>>,
     pub plugin_registrar_fn: Cell<Option<ast::NodeId>>,
+    // The allocator crate injected by the crate reader, if any
+    pub injected_allocator: Cell<Option<ast::CrateNum>>,
     pub default_sysroot: Option<PathBuf>,
     // The name of the root source file of the crate, in the local file system. The path is always
     // expected to be absolute. `None` means that there is no source file.
     pub local_crate_source_file: Option<PathBuf>,
     pub working_dir: PathBuf,
     pub lint_store: RefCell<lint::LintStore>,
-    pub lints: RefCell<NodeMap<Vec<(lint::LintId, codemap::Span, String)>>>,
+    pub lints: RefCell<NodeMap<Vec<lint::BufferedLint>>>,
     pub crate_types: RefCell<Vec<config::CrateType>>,
     pub crate_metadata: RefCell<Vec<String>>,
     pub features: RefCell<feature_gate::Features>,
@@ -60,6 +62,21 @@ pub struct Session {
     /// operations such as auto-dereference and monomorphization.
     pub recursion_limit: Cell<uint>,
 
+    /// Attributes registered by plugins, and where they may appear.
+    pub plugin_attributes: RefCell<Vec<(String, feature_gate::AttributeType)>>,
+
+    /// How many matcher positions `macro_rules!` may explore while matching
+    /// a single invocation before giving up.
+    pub macro_work_limit: Cell<uint>,
+
+    /// How many expressions a function body may contain before the
+    /// `large_fn_bodies` lint reports it.
+    pub fn_expr_limit: Cell<uint>,
+
+    /// The subsystem, from `#![windows_subsystem]`, that an executable for a
+    /// Windows target is linked for. `None` leaves it to the linker.
+    pub windows_subsystem: RefCell<Option<String>>,
+
     pub can_print_warnings: bool
 }
 
@@ -115,6 +132,13 @@ impl Session {
     pub fn abort_if_errors(&self) {
         self.diagnostic().handler().abort_if_errors()
     }
+    /// Abort if any errors were reported after `err_count()` returned
+    /// `errors_before`, ignoring the ones an earlier pass recovered from.
+    pub fn abort_if_new_errors(&self, errors_before: uint) {
+        if self.err_count() > errors_before {
+            self.abort_if_errors();
+        }
+    }
     pub fn span_warn(&self, sp: Span, msg: &str) {
         if self.can_print_warnings {
             self.diagnostic().span_warn(sp, msg)
@@ -151,6 +175,9 @@ impl Session {
     pub fn fileline_help(&self, sp: Span, msg: &str) {
         self.diagnostic().fileline_help(sp, msg)
     }
+    pub fn span_suggestion(&self, sp: Span, msg: &str, suggestion: String) {
+        self.diagnostic().span_suggestion(sp, msg, suggestion)
+    }
     pub fn note(&self, msg: &str) {
         self.diagnostic().handler().note(msg)
     }
@@ -180,13 +207,28 @@ impl Session {
                     id: ast::NodeId,
                     sp: Span,
                     msg: String) {
-        let lint_id = lint::LintId::of(lint);
+        self.add_lint_with_notes(lint, id, sp, msg, Vec::new())
+    }
+    /// Like `add_lint`, but with notes that are only shown if the lint
+    /// is emitted.
+    pub fn add_lint_with_notes(&self,
+                               lint: &'static lint::Lint,
+                               id: ast::NodeId,
+                               sp: Span,
+                               msg: String,
+                               notes: Vec<(Span, String)>) {
+        let buffered = lint::BufferedLint {
+            lint_id: lint::LintId::of(lint),
+            span: sp,
+            msg: msg,
+            notes: notes,
+        };
         let mut lints = self.lints.borrow_mut();
         match lints.get_mut(&id) {
-            Some(arr) => { arr.push((lint_id, sp, msg)); return; }
+            Some(arr) => { arr.push(buffered); return; }
             None => {}
         }
-        lints.insert(id, vec!((lint_id, sp, msg)));
+        lints.insert(id, vec!(buffered));
     }
     pub fn next_node_id(&self) -> ast::NodeId {
         self.parse_sess.next_node_id()
@@ -220,7 +262,9 @@ impl Session {
     pub fn trans_stats(&self) -> bool { self.opts.debugging_opts.trans_stats }
     pub fn meta_stats(&self) -> bool { self.opts.debugging_opts.meta_stats }
     pub fn asm_comments(&self) -> bool { self.opts.debugging_opts.asm_comments }
-    pub fn no_verify(&self) -> bool { self.opts.debugging_opts.no_verify }
+    pub fn verify_llvm_ir(&self) -> bool {
+        self.opts.debugging_opts.verify_llvm_ir || cfg!(debug_assertions)
+    }
     pub fn borrowck_stats(&self) -> bool { self.opts.debugging_opts.borrowck_stats }
     pub fn print_llvm_passes(&self) -> bool {
         self.opts.debugging_opts.print_llvm_passes
@@ -229,14 +273,20 @@ impl Session {
         self.opts.cg.lto
     }
     pub fn no_landing_pads(&self) -> bool {
-        self.opts.debugging_opts.no_landing_pads
+        self.opts.debugging_opts.no_landing_pads ||
+            self.opts.cg.panic == config::PanicStrategy::Abort
     }
+    pub fn keep_going(&self) -> bool { self.opts.debugging_opts.keep_going }
     pub fn unstable_options(&self) -> bool {
         self.opts.debugging_opts.unstable_options
     }
     pub fn print_enum_sizes(&self) -> bool {
         self.opts.debugging_opts.print_enum_sizes
     }
+    pub fn print_type_sizes(&self) -> bool {
+        self.opts.debugging_opts.print_type_sizes
+    }
+    pub fn crate_stats(&self) -> bool { self.opts.debugging_opts.crate_stats }
     pub fn sysroot<'a>(&'a self) -> &'a Path {
         match self.opts.maybe_sysroot {
             Some (ref sysroot) => sysroot,
@@ -257,6 +307,13 @@ impl Session {
             &self.opts.search_paths,
             kind)
     }
+    /// Applies `-Z remap-path-prefix` to a path that ends up in the output.
+    pub fn remap_path(&self, path: &str) -> String {
+        match self.opts.debugging_opts.remap_path_prefix {
+            Some(ref mapping) => codemap::remap_path_prefix(path, mapping),
+            None => path.to_string(),
+        }
+    }
 }
 
 fn split_msg_into_multilines(msg: &str) -> Option<String> {
@@ -353,6 +410,7 @@ pub fn build_session_(sopts: config::Options,
                 .fatal(&format!("Error loading host specification: {}", e));
     }
     };
+    span_diagnostic.handler().set_report_dummy_spans(sopts.debugging_opts.report_dummy_spans);
     let target_cfg = config::build_target_config(&sopts, &span_diagnostic);
     let p_s = parse::new_parse_sess_special_handler(span_diagnostic);
     let default_sysroot = match sopts.maybe_sysroot {
@@ -386,6 +444,7 @@ pub fn build_session_(sopts: config::Options,
         entry_fn: RefCell::new(None),
         entry_type: Cell::new(None),
         plugin_registrar_fn: Cell::new(None),
+        injected_allocator: Cell::new(None),
         default_sysroot: default_sysroot,
         local_crate_source_file: local_crate_source_file,
         working_dir: env::current_dir().unwrap(),
@@ -395,6 +454,10 @@ pub fn build_session_(sopts: config::Options,
         crate_metadata: RefCell::new(Vec::new()),
         features: RefCell::new(feature_gate::Features::new()),
         recursion_limit: Cell::new(64),
+        plugin_attributes: RefCell::new(Vec::new()),
+        macro_work_limit: Cell::new(10_000_000),
+        fn_expr_limit: Cell::new(100_000),
+        windows_subsystem: RefCell::new(None),
         can_print_warnings: can_print_warnings
     };
 
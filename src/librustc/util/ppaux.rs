@@ -392,7 +392,7 @@ pub fn ty_to_string<'tcx>(cx: &ctxt<'tcx>, typ: &ty::TyS<'tcx>) -> String {
             }
         }
         ty_enum(did, substs) | ty_struct(did, substs) => {
-            let base = ty::item_path_str(cx, did);
+            let base = ty::type_path_str(cx, did);
             parameterized(cx, &base, substs, did, &[],
                           || ty::lookup_item_type(cx, did).generics)
         }
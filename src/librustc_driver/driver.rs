@@ -24,6 +24,7 @@ use rustc_borrowck as borrowck;
 use rustc_resolve as resolve;
 use rustc_trans::back::link;
 use rustc_trans::back::write;
+use rustc_trans::save;
 use rustc_trans::trans;
 use rustc_typeck as typeck;
 use rustc_privacy;
@@ -31,6 +32,9 @@ use super::Compilation;
 
 use serialize::json;
 
+use libc;
+
+use std::cell::Cell;
 use std::env;
 use std::ffi::OsString;
 use std::fs;
@@ -52,12 +56,77 @@ pub fn compile_input(sess: Session,
                      output: &Option<PathBuf>,
                      addl_plugins: Option<Vec<String>>,
                      control: CompileController) {
+    let krate = phase_1_parse_input(&sess, cfg, input);
+    compile_parsed_input(sess, krate, input, outdir, output, addl_plugins, control, true);
+}
+
+/// Build the test harness for `input` and then, from the same parse, the
+/// library it describes. This is what `--test` with an explicit
+/// `--crate-type` does.
+///
+/// `lib_sess` must be a fresh session for the same input without `--test`.
+/// Its codemap is seeded with the source files of the parse so that the
+/// spans of the shared crate stay valid. Expansion and everything after it
+/// run once per build, since `#[cfg(test)]` makes the two crates differ.
+/// If `-o` is given it names the test executable, and the library is
+/// written next to it under its usual name.
+pub fn compile_test_and_library(sess: Session,
+                                lib_sess: Session,
+                                cfg: ast::CrateConfig,
+                                input: &Input,
+                                outdir: &Option<PathBuf>,
+                                output: &Option<PathBuf>,
+                                addl_plugins: Option<Vec<String>>,
+                                control: CompileController,
+                                lib_control: CompileController) {
+    assert!(sess.opts.test && !lib_sess.opts.test);
+
+    let krate = phase_1_parse_input(&sess, cfg, input);
+    let lib_krate = ast::Crate {
+        config: config::build_configuration(&lib_sess),
+        ..krate.clone()
+    };
+    for fm in &*sess.codemap().files.borrow() {
+        let src = fm.src.as_ref().expect("parsed file without source");
+        let copy = lib_sess.codemap().new_filemap(fm.name.clone(), (**src).clone());
+        *copy.lines.borrow_mut() = fm.lines.borrow().clone();
+        *copy.multibyte_chars.borrow_mut() =
+            fm.multibyte_chars.borrow().iter().map(|&c| c).collect();
+    }
+
+    compile_parsed_input(sess, krate, input, outdir, output, addl_plugins.clone(), control,
+                         false);
+
+    // Resolution of the test build discards the hygiene tables; the shared
+    // crate only contains unmarked identifiers, so starting over is sound.
+    syntax::ext::mtwt::reset_tables();
+
+    let lib_outdir = match *output {
+        Some(ref o) => o.parent().map(|p| p.to_path_buf()),
+        None => outdir.clone(),
+    };
+    compile_parsed_input(lib_sess, lib_krate, input, &lib_outdir, &None, addl_plugins,
+                         lib_control, true);
+}
+
+/// Run every phase after parsing on `krate`. The identifier interner is
+/// only discarded after translation if `discard_interner` is set; it must
+/// not be when another build will reuse identifiers from the same parse.
+fn compile_parsed_input(sess: Session,
+                        krate: ast::Crate,
+                        input: &Input,
+                        outdir: &Option<PathBuf>,
+                        output: &Option<PathBuf>,
+                        addl_plugins: Option<Vec<String>>,
+                        control: CompileController,
+                        discard_interner: bool) {
     macro_rules! controller_entry_point{($point: ident, $make_state: expr) => ({
-        {
-            let state = $make_state;
-            (control.$point.callback)(state);
-        }
-        if control.$point.stop == Compilation::Stop {
+        let stop = {
+            let mut state = $make_state;
+            (control.$point.callback)(&mut state);
+            state.stop
+        };
+        if control.$point.stop == Compilation::Stop || stop == Compilation::Stop {
             return;
         }
     })}
@@ -67,8 +136,6 @@ pub fn compile_input(sess: Session,
     // possible to keep the peak memory usage low
     let (outputs, trans, sess) = {
         let (outputs, expanded_crate, id) = {
-            let krate = phase_1_parse_input(&sess, cfg, input);
-
             controller_entry_point!(after_parse,
                                     CompileState::state_after_parse(input,
                                                                     &sess,
@@ -106,7 +173,7 @@ pub fn compile_input(sess: Session,
         let arenas = ty::CtxtArenas::new();
         let ast_map = assign_node_ids_and_map(&sess, &mut forest);
 
-        write_out_deps(&sess, input, &outputs, &id[..]);
+        write_out_deps(&sess, &outputs, &id[..]);
 
         controller_entry_point!(after_write_deps,
                                 CompileState::state_after_write_deps(input,
@@ -122,6 +189,10 @@ pub fn compile_input(sess: Session,
                                                    id,
                                                    control.make_glob_map);
 
+        // `--check` stops at the next entry point. Errors reported after the
+        // last check in the analysis passes must still fail the build.
+        analysis.ty_cx.sess.abort_if_errors();
+
         controller_entry_point!(after_analysis,
                                 CompileState::state_after_analysis(input,
                                                                    &analysis.ty_cx.sess,
@@ -142,7 +213,9 @@ pub fn compile_input(sess: Session,
         }
 
         // Discard interned strings as they are no longer required.
-        token::get_ident_interner().clear();
+        if discard_interner {
+            token::get_ident_interner().clear();
+        }
 
         (outputs, trans, tcx.sess)
     };
@@ -158,16 +231,21 @@ pub fn compile_input(sess: Session,
 }
 
 /// The name used for source code that doesn't originate in a file
-/// (e.g. source from stdin or a string)
+/// (e.g. source from a string)
 pub fn anon_src() -> String {
     "<anon>".to_string()
 }
 
+/// The name used for source code read from stdin
+pub fn stdin_src() -> String {
+    "<stdin>".to_string()
+}
+
 pub fn source_name(input: &Input) -> String {
     match *input {
         // FIXME (#9639): This needs to handle non-utf8 paths
         Input::File(ref ifile) => ifile.to_str().unwrap().to_string(),
-        Input::Str(_) => anon_src()
+        Input::Str { ref name, .. } => name.clone()
     }
 }
 
@@ -183,6 +261,9 @@ pub fn source_name(input: &Input) -> String {
 ///
 /// Note that if compilation is set to stop and a callback is provided for a
 /// given entry point, the callback is called before compilation is stopped.
+/// A callback may also stop compilation itself by setting `stop` on the
+/// `CompileState` it is given, e.g. once it has inspected the results of a
+/// phase and has no use for the rest.
 ///
 /// Expect more entry points to be added in the future.
 pub struct CompileController<'a> {
@@ -210,7 +291,7 @@ impl<'a> CompileController<'a> {
 
 pub struct PhaseController<'a> {
     pub stop: Compilation,
-    pub callback: Box<Fn(CompileState) -> () + 'a>,
+    pub callback: Box<Fn(&mut CompileState) -> () + 'a>,
 }
 
 impl<'a> PhaseController<'a> {
@@ -225,7 +306,11 @@ impl<'a> PhaseController<'a> {
 /// State that is passed to a callback. What state is available depends on when
 /// during compilation the callback is made. See the various constructor methods
 /// (`state_*`) in the impl to see which data is provided for any given entry point.
+/// After analysis the ast map is available as `tcx.map`.
 pub struct CompileState<'a, 'ast: 'a, 'tcx: 'a> {
+    /// Set by the callback to stop compilation after this entry point,
+    /// regardless of the `stop` setting of its `PhaseController`.
+    pub stop: Compilation,
     pub input: &'a Input,
     pub session: &'a Session,
     pub cfg: Option<&'a ast::CrateConfig>,
@@ -246,6 +331,7 @@ impl<'a, 'ast, 'tcx> CompileState<'a, 'ast, 'tcx> {
              out_dir: &'a Option<PathBuf>)
              -> CompileState<'a, 'ast, 'tcx> {
         CompileState {
+            stop: Compilation::Continue,
             input: input,
             session: session,
             out_dir: out_dir.as_ref().map(|s| &**s),
@@ -341,9 +427,9 @@ pub fn phase_1_parse_input(sess: &Session, cfg: ast::CrateConfig, input: &Input)
             Input::File(ref file) => {
                 parse::parse_crate_from_file(&(*file), cfg.clone(), &sess.parse_sess)
             }
-            Input::Str(ref src) => {
-                parse::parse_crate_from_source_str(anon_src().to_string(),
-                                                   src.to_string(),
+            Input::Str { ref name, ref input } => {
+                parse::parse_crate_from_source_str(name.clone(),
+                                                   input.clone(),
                                                    cfg.clone(),
                                                    &sess.parse_sess)
             }
@@ -385,6 +471,8 @@ pub fn phase_2_configure_and_expand(sess: &Session,
 
     time(time_passes, "recursion limit", (), |_| {
         middle::recursion_limit::update_recursion_limit(sess, &krate);
+        middle::recursion_limit::update_macro_work_limit(sess, &krate);
+        middle::recursion_limit::update_fn_expr_limit(sess, &krate);
     });
 
     // strip before expansion to allow macros to depend on
@@ -433,12 +521,14 @@ pub fn phase_2_configure_and_expand(sess: &Session,
         }
 
         for registrar in registrars {
+            registry.plugin_name = registrar.name;
             registry.args_hidden = Some(registrar.args);
             (registrar.fun)(&mut registry);
         }
     });
 
-    let Registry { syntax_exts, lint_passes, lint_groups, .. } = registry;
+    let Registry { syntax_exts, lint_passes, lint_groups, attributes, .. } = registry;
+    *sess.plugin_attributes.borrow_mut() = attributes;
 
     {
         let mut ls = sess.lint_store.borrow_mut();
@@ -480,6 +570,8 @@ pub fn phase_2_configure_and_expand(sess: &Session,
                 crate_name: crate_name.to_string(),
                 features: Some(&features),
                 recursion_limit: sess.recursion_limit.get(),
+                macro_work_limit: sess.macro_work_limit.get(),
+                remap_path_prefix: sess.opts.debugging_opts.remap_path_prefix.clone(),
             };
             let ret = syntax::ext::expand::expand_crate(&sess.parse_sess,
                                               cfg,
@@ -498,11 +590,13 @@ pub fn phase_2_configure_and_expand(sess: &Session,
     // much as possible (e.g. help the programmer avoid platform
     // specific differences)
     time(time_passes, "complete gated feature checking 1", (), |_| {
-        let features =
+        let mut features =
             syntax::feature_gate::check_crate(sess.codemap(),
                                               &sess.parse_sess.span_diagnostic,
                                               &krate,
+                                              &sess.plugin_attributes.borrow(),
                                               true);
+        features.note_uses_from(&sess.features.borrow());
         *sess.features.borrow_mut() = features;
         sess.abort_if_errors();
     });
@@ -529,11 +623,13 @@ pub fn phase_2_configure_and_expand(sess: &Session,
     // later, to make sure we've got everything (e.g. configuration
     // can insert new attributes via `cfg_attr`)
     time(time_passes, "complete gated feature checking 2", (), |_| {
-        let features =
+        let mut features =
             syntax::feature_gate::check_crate(sess.codemap(),
                                               &sess.parse_sess.span_diagnostic,
                                               &krate,
+                                              &sess.plugin_attributes.borrow(),
                                               false);
+        features.note_uses_from(&sess.features.borrow());
         *sess.features.borrow_mut() = features;
         sess.abort_if_errors();
     });
@@ -541,22 +637,46 @@ pub fn phase_2_configure_and_expand(sess: &Session,
     Some(krate)
 }
 
+/// Give every node of the expanded crate its id, in the order `map_crate`
+/// walks the crate. Nothing before this allocates ids, so the ids of a crate
+/// only depend on its expanded source, and are dense.
 pub fn assign_node_ids_and_map<'ast>(sess: &Session,
                                      forest: &'ast mut ast_map::Forest)
                                      -> ast_map::Map<'ast> {
     struct NodeIdAssigner<'a> {
-        sess: &'a Session
+        sess: &'a Session,
+        assigned: &'a Cell<ast::NodeId>,
     }
 
     impl<'a> ast_map::FoldOps for NodeIdAssigner<'a> {
         fn new_id(&self, old_id: ast::NodeId) -> ast::NodeId {
             assert_eq!(old_id, ast::DUMMY_NODE_ID);
+            self.assigned.set(self.assigned.get() + 1);
             self.sess.next_node_id()
         }
     }
 
+    let first_id = sess.reserve_node_ids(0);
+    let assigned = Cell::new(0);
     let map = time(sess.time_passes(), "assigning node ids and indexing ast", forest, |forest|
-                   ast_map::map_crate(forest, NodeIdAssigner { sess: sess }));
+                   ast_map::map_crate(forest, NodeIdAssigner {
+                       sess: sess,
+                       assigned: &assigned,
+                   }));
+
+    // Ids handed out before this pass, or by something other than it
+    // while it runs, would depend on more than the expanded source.
+    if sess.opts.debugging_opts.deterministic_ids {
+        if first_id != ast::CRATE_NODE_ID + 1 {
+            sess.bug(&format!("{} node ids were allocated before expansion finished",
+                              first_id - ast::CRATE_NODE_ID - 1));
+        }
+        let allocated = sess.reserve_node_ids(0) - first_id;
+        if allocated != assigned.get() {
+            sess.bug(&format!("{} node ids were allocated while assigning {}",
+                              allocated, assigned.get()));
+        }
+    }
 
     if sess.opts.debugging_opts.ast_json {
         println!("{}", json::as_json(map.krate()));
@@ -577,6 +697,11 @@ pub fn phase_3_run_analysis_passes<'tcx>(sess: Session,
     let time_passes = sess.time_passes();
     let krate = ast_map.krate();
 
+    // Lints that only need the AST are checked first, so that they are
+    // reported even if a later analysis pass aborts compilation.
+    time(time_passes, "early lint checks", (), |_|
+         lint::check_ast_crate(&sess, krate));
+
     time(time_passes, "external crate/lib resolution", (), |_|
          CrateReader::new(&sess).read_crates(krate));
 
@@ -607,6 +732,8 @@ pub fn phase_3_run_analysis_passes<'tcx>(sess: Session,
     time(time_passes, "looking for entry point", (),
          |_| middle::entry::find_entry_point(&sess, &ast_map));
 
+    middle::entry::find_windows_subsystem(&sess, krate);
+
     sess.plugin_registrar_fn.set(
         time(time_passes, "looking for plugin registrar", (), |_|
             plugin::build::find_plugin_registrar(
@@ -621,6 +748,13 @@ pub fn phase_3_run_analysis_passes<'tcx>(sess: Session,
     time(time_passes, "static item recursion checking", (), |_|
          middle::check_static_recursion::check_crate(&sess, krate, &def_map, &ast_map));
 
+    time(time_passes, "inline function recursion checking", (), |_|
+         middle::check_inline_recursion::check_crate(&sess, krate, &def_map, &ast_map));
+
+    if sess.crate_stats() {
+        middle::crate_stats::print_crate_stats(&sess, krate, &ast_map);
+    }
+
     let ty_cx = ty::mk_ctxt(sess,
                             arenas,
                             def_map,
@@ -694,6 +828,11 @@ pub fn phase_3_run_analysis_passes<'tcx>(sess: Session,
     // The above three passes generate errors w/o aborting
     ty_cx.sess.abort_if_errors();
 
+    if let Some(ref path) = ty_cx.sess.opts.debugging_opts.save_types {
+        time(time_passes, "saving types", (), |_|
+             save::types::dump_types(&ty_cx, krate, Path::new(path)));
+    }
+
     ty::CrateAnalysis {
         export_map: export_map,
         ty_cx: ty_cx,
@@ -744,7 +883,17 @@ pub fn phase_5_run_llvm_passes(sess: &Session,
                               outputs));
     }
 
+    if sess.opts.output_types.contains(&config::OutputTypeExportedSymbols) {
+        write::write_exported_symbols(sess, trans,
+                                      &outputs.path(config::OutputTypeExportedSymbols));
+    }
+
     sess.abort_if_errors();
+
+    match outputs.stdout_output {
+        Some(config::OutputTypeDepInfo) | None => {}
+        Some(output_type) => write::copy_output_to_stdout(sess, outputs, output_type),
+    }
 }
 
 /// Run the linker on any artifacts that resulted from the LLVM run.
@@ -773,7 +922,6 @@ fn escape_dep_filename(filename: &str) -> String {
 }
 
 fn write_out_deps(sess: &Session,
-                  input: &Input,
                   outputs: &OutputFilenames,
                   id: &str) {
 
@@ -794,19 +942,14 @@ fn write_out_deps(sess: &Session,
 
     // Write out dependency rules to the dep-info file if requested with
     // --dep-info
+    let to_stdout = outputs.stdout_output == Some(config::OutputTypeDepInfo);
     let deps_filename = match sess.opts.write_dependency_info {
+        (true, _) if to_stdout => PathBuf::from("<stdout>"),
         // Use filename from --dep-file argument if given
         (true, Some(ref filename)) => filename.clone(),
-        // Use default filename: crate source filename with extension replaced
-        // by ".d"
-        (true, None) => match *input {
-            Input::File(..) => outputs.with_extension("d"),
-            Input::Str(..) => {
-                sess.warn("can not write --dep-info without a filename \
-                           when compiling stdin.");
-                return
-            },
-        },
+        // Use default filename: output filestem (the crate source filename,
+        // or the crate name for stdin) with extension replaced by ".d"
+        (true, None) => outputs.with_extension("d"),
         _ => return,
     };
 
@@ -819,7 +962,11 @@ fn write_out_deps(sess: &Session,
                                    .filter(|fmap| !fmap.is_imported())
                                    .map(|fmap| escape_dep_filename(&fmap.name))
                                    .collect();
-        let mut file = try!(fs::File::create(&deps_filename));
+        let mut file: Box<Write> = if to_stdout {
+            box io::stdout()
+        } else {
+            box try!(fs::File::create(&deps_filename))
+        };
         for path in &out_filenames {
             try!(write!(&mut file,
                         "{}: {}\n\n", path.display(), files.connect(" ")));
@@ -921,6 +1068,13 @@ pub fn build_output_filenames(input: &Input,
                               sess: &Session)
                            -> OutputFilenames {
     match *ofile {
+        Some(ref out_file) if out_file.to_str() == Some("-") => {
+            let output_type = stdout_output_type(sess);
+            let mut outputs = build_output_filenames(input, odir, &None, attrs, sess);
+            outputs.stdout_output = Some(output_type);
+            outputs
+        }
+
         None => {
             // "-" as input file will cause the parser to read from stdin so we
             // have to make up a name
@@ -940,11 +1094,18 @@ pub fn build_output_filenames(input: &Input,
                 out_filestem: stem,
                 single_output_file: None,
                 extra: sess.opts.cg.extra_filename.clone(),
+                outputs: sess.opts.output_paths.clone(),
+                stdout_output: None,
             }
         }
 
         Some(ref out_file) => {
-            let ofile = if sess.opts.output_types.len() > 1 {
+            // `-o` names the one output that wasn't given a path with
+            // `--emit kind=path`.
+            let unnamed = sess.opts.output_types.iter().filter(|ty| {
+                !sess.opts.output_paths.contains_key(*ty)
+            }).count();
+            let ofile = if unnamed > 1 {
                 sess.warn("ignoring specified output filename because multiple \
                            outputs were requested");
                 None
@@ -963,7 +1124,51 @@ pub fn build_output_filenames(input: &Input,
                                       .to_str().unwrap().to_string(),
                 single_output_file: ofile,
                 extra: sess.opts.cg.extra_filename.clone(),
+                outputs: sess.opts.output_paths.clone(),
+                stdout_output: None,
             }
         }
     }
 }
+
+/// Checks that `-o -` is usable with the requested outputs and returns the one
+/// to write to stdout.
+fn stdout_output_type(sess: &Session) -> config::OutputType {
+    let unnamed: Vec<config::OutputType> = sess.opts.output_types.iter().cloned().filter(|ty| {
+        !sess.opts.output_paths.contains_key(ty)
+    }).collect();
+    if unnamed.len() != 1 {
+        sess.fatal("`-o -` requires exactly one output to be written to stdout");
+    }
+    match unnamed[0] {
+        config::OutputTypeExe => {
+            sess.fatal("cannot write linked output or crate metadata to stdout; \
+                        use `--emit` with `asm`, `llvm-ir`, `llvm-ir-unopt`, `llvm-bc`, \
+                        `obj`, `dep-info` or `exported-symbols`, or give `-o` a file name")
+        }
+        config::OutputTypeObject | config::OutputTypeBitcode if stdout_isatty() => {
+            sess.fatal("refusing to write binary output to a terminal; \
+                        redirect stdout or give `-o` a file name")
+        }
+        output_type => output_type,
+    }
+}
+
+#[cfg(unix)]
+fn stdout_isatty() -> bool {
+    unsafe { libc::isatty(libc::STDOUT_FILENO) != 0 }
+}
+#[cfg(windows)]
+fn stdout_isatty() -> bool {
+    const STD_OUTPUT_HANDLE: libc::DWORD = -11;
+    extern "system" {
+        fn GetStdHandle(which: libc::DWORD) -> libc::HANDLE;
+        fn GetConsoleMode(hConsoleHandle: libc::HANDLE,
+                          lpMode: libc::LPDWORD) -> libc::BOOL;
+    }
+    unsafe {
+        let handle = GetStdHandle(STD_OUTPUT_HANDLE);
+        let mut out = 0;
+        GetConsoleMode(handle, &mut out) != 0
+    }
+}
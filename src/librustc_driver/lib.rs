@@ -33,11 +33,12 @@
 #![feature(quote)]
 #![feature(rustc_diagnostic_macros)]
 #![feature(rustc_private)]
-#![feature(unsafe_destructor)]
 #![feature(staged_api)]
+#![feature(std_misc)]
 #![feature(exit_status)]
 #![feature(io)]
 #![feature(set_stdio)]
+#![feature(recover)]
 #![feature(unicode)]
 #![feature(convert)]
 
@@ -76,8 +77,10 @@ use rustc::util::common::time;
 
 use std::cmp::Ordering::Equal;
 use std::env;
-use std::io::{self, Read, Write};
+use std::fs::File;
+use std::io::{self, BufRead, Read, Write};
 use std::iter::repeat;
+use std::panic::{self, AssertRecoverSafe};
 use std::path::PathBuf;
 use std::str;
 use std::sync::{Arc, Mutex};
@@ -137,6 +140,16 @@ pub fn run_compiler<'a>(args: &[String],
         }
     };
 
+    // `--test` with an explicit `--crate-type` builds the test harness and the
+    // crate itself from one parse; the latter gets a session of its own.
+    let lib_sopts = if sopts.test && !sopts.crate_types.is_empty() {
+        let mut lib_sopts = sopts.clone();
+        lib_sopts.test = false;
+        Some((lib_sopts, input_file_path.clone(), descriptions.clone()))
+    } else {
+        None
+    };
+
     let mut sess = build_session(sopts, input_file_path, descriptions);
     rustc_lint::register_builtins(&mut sess.lint_store.borrow_mut(), Some(&sess));
     if sess.unstable_options() {
@@ -159,7 +172,17 @@ pub fn run_compiler<'a>(args: &[String],
 
     let plugins = sess.opts.debugging_opts.extra_plugins.clone();
     let control = callbacks.build_controller(&sess);
-    driver::compile_input(sess, cfg, &input, &odir, &ofile, Some(plugins), control);
+    match lib_sopts {
+        Some((lib_sopts, input_file_path, descriptions)) => {
+            let lib_sess = build_session(lib_sopts, input_file_path, descriptions);
+            rustc_lint::register_builtins(&mut lib_sess.lint_store.borrow_mut(),
+                                          Some(&lib_sess));
+            let lib_control = callbacks.build_controller(&lib_sess);
+            driver::compile_test_and_library(sess, lib_sess, cfg, &input, &odir, &ofile,
+                                             Some(plugins), control, lib_control);
+        }
+        None => driver::compile_input(sess, cfg, &input, &odir, &ofile, Some(plugins), control),
+    }
 }
 
 // Extract output directory and file from matches.
@@ -175,8 +198,10 @@ fn make_input(free_matches: &[String]) -> Option<(Input, Option<PathBuf>)> {
         let ifile = &free_matches[0][..];
         if ifile == "-" {
             let mut src = String::new();
-            io::stdin().read_to_string(&mut src).unwrap();
-            Some((Input::Str(src), None))
+            if io::stdin().read_to_string(&mut src).is_err() {
+                early_error("couldn't read from stdin, as it did not contain valid UTF-8");
+            }
+            Some((Input::Str { name: driver::stdin_src(), input: src }, None))
         } else {
             Some((Input::File(PathBuf::from(ifile)), Some(PathBuf::from(ifile))))
         }
@@ -310,6 +335,10 @@ impl<'a> CompilerCalls<'a> for RustcDefaultCalls {
                     describe_lints(&ls, false);
                     return None;
                 }
+                if sopts.debugging_opts.demangle {
+                    demangle_stdin();
+                    return None;
+                }
                 let sess = build_session(sopts.clone(), None, descriptions.clone());
                 rustc_lint::register_builtins(&mut sess.lint_store.borrow_mut(), Some(&sess));
                 let should_stop = RustcDefaultCalls::print_crate_info(&sess, None, odir, ofile);
@@ -412,7 +441,7 @@ impl RustcDefaultCalls {
                                                          &mut v).unwrap();
                     println!("{}", String::from_utf8(v).unwrap());
                 }
-                &Input::Str(_) => {
+                &Input::Str { .. } => {
                     early_error("cannot list metadata for stdin");
                 }
             }
@@ -436,6 +465,9 @@ impl RustcDefaultCalls {
         for req in &sess.opts.prints {
             match *req {
                 PrintRequest::Sysroot => println!("{}", sess.sysroot().display()),
+                PrintRequest::TargetSpecJson => {
+                    println!("{}", sess.target.target.to_json().pretty());
+                }
                 PrintRequest::FileNames |
                 PrintRequest::CrateName => {
                     let input = match input {
@@ -529,11 +561,35 @@ fn usage(verbose: bool, include_unstable_options: bool) {
 Additional help:
     -C help             Print codegen options
     -W help             Print 'lint' options and default settings
-    -Z help             Print internal options for debugging rustc{}\n",
+    -Z help             Print internal options for debugging rustc{}
+
+An argument of the form @path is replaced with the arguments listed in the
+file at path, one per line.\n",
               getopts::usage(&message, &groups),
               extra_help);
 }
 
+/// Demangles the symbol names read from stdin, one per line, writing each
+/// of them to stdout as soon as it is read. Lines that aren't Rust symbols
+/// are copied unchanged, so this works as a filter for tools like `perf`.
+fn demangle_stdin() {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    for line in stdin.lock().lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => early_error(&format!("failed to read from stdin: {}", e)),
+        };
+        let written = std::rt::backtrace::demangle(&mut out, line.trim_right_matches('\r'))
+            .and_then(|()| out.write_all(b"\n"))
+            .and_then(|()| out.flush());
+        if let Err(e) = written {
+            early_error(&format!("failed to write to stdout: {}", e));
+        }
+    }
+}
+
 fn describe_lints(lint_store: &lint::LintStore, loaded_plugins: bool) {
     println!("
 Available lint options:
@@ -674,12 +730,49 @@ fn describe_codegen_flags() {
     }
 }
 
+/// Replaces every argument of the form `@path` with the arguments read from
+/// the response file at `path`, so that command lines longer than the OS
+/// allows can still be passed to the compiler.
+///
+/// A response file holds one argument per line. Lines are taken verbatim, so
+/// an argument containing spaces needs no quoting; there is no escaping, and
+/// an argument can't contain a newline. A trailing carriage return is
+/// dropped and blank lines are ignored. Response files can't refer to other
+/// response files.
+pub fn expand_response_files(args: Vec<String>) -> Vec<String> {
+    let mut expanded = Vec::with_capacity(args.len());
+    for arg in args {
+        if !arg.starts_with("@") {
+            expanded.push(arg);
+            continue
+        }
+        let path = &arg[1..];
+        let mut contents = String::new();
+        if let Err(e) = File::open(path).and_then(|mut f| f.read_to_string(&mut contents)) {
+            early_error(&format!("failed to read response file `{}`: {}", path, e));
+        }
+        for line in contents.lines() {
+            let line = line.trim_right_matches('\r');
+            if line.is_empty() {
+                continue
+            }
+            if line.starts_with("@") {
+                early_error(&format!("response file `{}` refers to another response \
+                                      file `{}`, which is not allowed", path, &line[1..]));
+            }
+            expanded.push(line.to_string());
+        }
+    }
+    expanded
+}
+
 /// Process command line options. Emits messages as appropriate. If compilation
 /// should continue, returns a getopts::Matches object parsed from args, otherwise
 /// returns None.
 pub fn handle_options(mut args: Vec<String>) -> Option<getopts::Matches> {
     // Throw away the first argument, the name of the binary
     let _binary = args.remove(0);
+    let args = expand_response_files(args);
 
     if args.is_empty() {
         // user did not write `-v` nor `-Z unstable-options`, so do not
@@ -777,10 +870,10 @@ fn parse_crate_attrs(sess: &Session, input: &Input) ->
                                                Vec::new(),
                                                &sess.parse_sess)
         }
-        Input::Str(ref src) => {
+        Input::Str { ref name, ref input } => {
             parse::parse_crate_attrs_from_source_str(
-                driver::anon_src().to_string(),
-                src.to_string(),
+                name.clone(),
+                input.clone(),
                 Vec::new(),
                 &sess.parse_sess)
         }
@@ -816,9 +909,14 @@ pub fn monitor<F:FnOnce()+Send+'static>(f: F) {
         cfg = cfg.stack_size(STACK_SIZE);
     }
 
-    match cfg.spawn(move || { io::set_panic(box err); f() }).unwrap().join() {
-        Ok(()) => { /* fallthrough */ }
-        Err(value) => {
+    let result = cfg.spawn(move || {
+        io::set_panic(box err);
+        panic::recover(AssertRecoverSafe(f))
+    }).unwrap().join();
+
+    match result {
+        Ok(Ok(())) => { /* fallthrough */ }
+        Ok(Err(value)) | Err(value) => {
             // Thread panicked without emitting a fatal diagnostic
             if !value.is::<diagnostic::FatalError>() {
                 let mut emitter = diagnostic::EmitterWriter::stderr(diagnostic::Auto, None);
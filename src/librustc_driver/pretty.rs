@@ -30,6 +30,7 @@ use rustc_borrowck::graphviz as borrowck_dot;
 use rustc_resolve as resolve;
 
 use syntax::ast;
+use syntax::ast_util;
 use syntax::ast_map::{self, blocks, NodePrinter};
 use syntax::codemap;
 use syntax::fold::{self, Folder};
@@ -315,6 +316,23 @@ impl<'tcx> pprust::PpAnn for TypedAnnotation<'tcx> {
                               &ppaux::ty_to_string(
                                   tcx,
                                   ty::expr_ty(tcx, expr))));
+                if let Some(adjustment) = tcx.adjustments.borrow().get(&expr.id) {
+                    if !adjustment.is_identity() {
+                        try!(pp::space(&mut s.s));
+                        try!(pp::word(&mut s.s, "~~>"));
+                        try!(pp::space(&mut s.s));
+                        try!(pp::word(&mut s.s, &adjustment_to_string(adjustment)));
+                        try!(pp::space(&mut s.s));
+                        try!(pp::word(&mut s.s,
+                                      &ppaux::ty_to_string(
+                                          tcx,
+                                          ty::expr_ty_adjusted(tcx, expr))));
+                    }
+                }
+                if let ast::ExprClosure(..) = expr.node {
+                    try!(pp::space(&mut s.s));
+                    try!(s.synth_comment(closure_to_string(tcx, expr.id)));
+                }
                 s.pclose()
             }
             _ => Ok(())
@@ -322,6 +340,62 @@ impl<'tcx> pprust::PpAnn for TypedAnnotation<'tcx> {
     }
 }
 
+/// Renders an adjustment compactly, in the order the operations would be
+/// written in source: e.g. `&*` for a reborrow, `&<unsize>*` for an array
+/// coerced to a slice.
+fn adjustment_to_string(adjustment: &ty::AutoAdjustment) -> String {
+    match *adjustment {
+        ty::AdjustReifyFnPointer(..) | ty::AdjustClosureFnPointer(..) => "reify".to_string(),
+        ty::AdjustUnsafeFnPointer => "unsafe".to_string(),
+        ty::AdjustDerefRef(ref adj) => {
+            let mut s = adj.autoref.as_ref().map_or(String::new(), autoref_to_string);
+            for _ in 0..adj.autoderefs {
+                s.push('*');
+            }
+            s
+        }
+    }
+}
+
+fn autoref_to_string(autoref: &ty::AutoRef) -> String {
+    let (prefix, inner) = match *autoref {
+        ty::AutoPtr(_, ast::MutImmutable, ref inner) => ("&", inner),
+        ty::AutoPtr(_, ast::MutMutable, ref inner) => ("&mut", inner),
+        ty::AutoUnsafe(ast::MutImmutable, ref inner) => ("*const", inner),
+        ty::AutoUnsafe(ast::MutMutable, ref inner) => ("*mut", inner),
+        ty::AutoUnsize(_) | ty::AutoUnsizeUniq(_) => return "<unsize>".to_string(),
+    };
+    format!("{}{}", prefix, inner.as_ref().map_or(String::new(), |r| autoref_to_string(&**r)))
+}
+
+/// Describes the inferred kind of a closure and how each of its upvars is
+/// captured, e.g. `FnMut [&mut count, move name]`.
+fn closure_to_string(tcx: &ty::ctxt, closure_id: ast::NodeId) -> String {
+    let kind = match tcx.closure_kind(ast_util::local_def(closure_id)) {
+        ty::FnClosureKind => "Fn",
+        ty::FnMutClosureKind => "FnMut",
+        ty::FnOnceClosureKind => "FnOnce",
+    };
+    let captures = ty::with_freevars(tcx, closure_id, |freevars| {
+        freevars.iter().map(|freevar| {
+            let var_id = freevar.def.local_node_id();
+            let upvar_id = ty::UpvarId { var_id: var_id, closure_expr_id: closure_id };
+            let name = ty::local_var_name_str(tcx, var_id);
+            let mode = match tcx.upvar_capture_map.borrow().get(&upvar_id) {
+                Some(&ty::UpvarCapture::ByValue) => "move ",
+                Some(&ty::UpvarCapture::ByRef(ref borrow)) => match borrow.kind {
+                    ty::ImmBorrow => "&",
+                    ty::UniqueImmBorrow => "&uniq ",
+                    ty::MutBorrow => "&mut ",
+                },
+                None => "",
+            };
+            format!("{}{}", mode, name)
+        }).collect::<Vec<_>>()
+    });
+    format!("{} [{}]", kind, captures.connect(", "))
+}
+
 fn gather_flowgraph_variants(sess: &Session) -> Vec<borrowck_dot::Variant> {
     let print_loans = sess.opts.debugging_opts.flowgraph_print_loans;
     let print_moves = sess.opts.debugging_opts.flowgraph_print_moves;
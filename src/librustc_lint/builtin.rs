@@ -23,10 +23,12 @@
 //! `LintPass` (also, note that such lints will need to be defined in
 //! `rustc::lint::builtin`, not here).
 //!
-//! If you define a new `LintPass`, you will also need to add it to the
-//! `add_builtin!` or `add_builtin_with_new!` invocation in `lib.rs`.
-//! Use the former for unit-like structs and the latter for structs with
-//! a `pub fn new()`.
+//! If you define a new `LintPass`, you will also need to implement either
+//! `EarlyLintPass` (for checks that only need the AST) or `LateLintPass`
+//! (for checks that need type information) for it, and add it to the
+//! matching `add_early_builtin!`/`add_late_builtin!` or `_with_new!`
+//! invocation in `lib.rs`. Use the former for unit-like structs and the
+//! latter for structs with a `pub fn new()`.
 
 use metadata::{csearch, decoder};
 use middle::def::*;
@@ -35,11 +37,16 @@ use middle::ty::{self, Ty};
 use middle::{def, pat_util, stability};
 use middle::const_eval::{eval_const_expr_partial, const_int, const_uint};
 use middle::cfg;
+use middle::crate_stats;
+use middle::expr_use_visitor as euv;
+use middle::mem_categorization as mc;
+use middle::region;
 use util::ppaux::{ty_to_string};
-use util::nodemap::{FnvHashMap, NodeSet};
-use lint::{Level, Context, LintPass, LintArray, Lint};
+use util::nodemap::{FnvHashMap, NodeMap, NodeSet, DefIdSet};
+use lint::{Level, LateContext, EarlyContext, LintContext, LintArray, Lint};
+use lint::{LintPass, EarlyLintPass, LateLintPass};
 
-use std::collections::BitSet;
+use std::collections::{BitSet, HashSet};
 use std::collections::hash_map::Entry::{Occupied, Vacant};
 use std::num::SignedInt;
 use std::{cmp, slice};
@@ -51,6 +58,7 @@ use syntax::attr::{self, AttrMetaMethods};
 use syntax::codemap::{self, Span};
 use syntax::feature_gate::{KNOWN_ATTRIBUTES, AttributeType};
 use syntax::parse::token;
+use syntax::print::pprust;
 use syntax::ast::{TyIs, TyUs, TyI8, TyU8, TyI16, TyU16, TyI32, TyU32, TyI64, TyU64};
 use syntax::ptr::P;
 use syntax::visit::{self, Visitor};
@@ -71,8 +79,10 @@ impl LintPass for WhileTrue {
     fn get_lints(&self) -> LintArray {
         lint_array!(WHILE_TRUE)
     }
+}
 
-    fn check_expr(&mut self, cx: &Context, e: &ast::Expr) {
+impl LateLintPass for WhileTrue {
+    fn check_expr(&mut self, cx: &LateContext, e: &ast::Expr) {
         if let ast::ExprWhile(ref cond, _, _) = e.node {
             if let ast::ExprLit(ref lit) = cond.node {
                 if let ast::LitBool(true) = lit.node {
@@ -85,25 +95,142 @@ impl LintPass for WhileTrue {
 }
 
 declare_lint! {
-    UNUSED_TYPECASTS,
+    pub TRIVIAL_CASTS,
+    Allow,
+    "detects casts that could be removed, or replaced by an implicit coercion"
+}
+
+declare_lint! {
+    pub TRIVIAL_NUMERIC_CASTS,
+    Allow,
+    "detects numeric casts to the type the value already has"
+}
+
+#[derive(Copy)]
+pub struct TrivialCasts;
+
+impl TrivialCasts {
+    /// Whether a value of type `from` would be coerced to `to` implicitly:
+    /// `&T` to `*const T`, `*mut T` to `*const T`, and pointers to a type
+    /// to pointers to a trait object.
+    fn is_coercion<'tcx>(from: Ty<'tcx>, to: Ty<'tcx>) -> bool {
+        let ((from_ty, from_mutbl), (to_ty, to_mutbl)) = match (&from.sty, &to.sty) {
+            (&ty::ty_rptr(_, ref f), &ty::ty_rptr(_, ref t)) |
+            (&ty::ty_rptr(_, ref f), &ty::ty_ptr(ref t)) |
+            (&ty::ty_ptr(ref f), &ty::ty_ptr(ref t)) => ((f.ty, f.mutbl), (t.ty, t.mutbl)),
+            (&ty::ty_uniq(f), &ty::ty_uniq(t)) => ((f, ast::MutMutable), (t, ast::MutMutable)),
+            _ => return false
+        };
+        if from_mutbl == ast::MutImmutable && to_mutbl == ast::MutMutable {
+            return false;
+        }
+        let is_object = |ty: Ty| match ty.sty { ty::ty_trait(..) => true, _ => false };
+        from_ty == to_ty || (is_object(to_ty) && !is_object(from_ty))
+    }
+}
+
+impl LintPass for TrivialCasts {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(TRIVIAL_CASTS, TRIVIAL_NUMERIC_CASTS)
+    }
+}
+
+impl LateLintPass for TrivialCasts {
+    fn check_expr(&mut self, cx: &LateContext, e: &ast::Expr) {
+        let expr = match e.node {
+            ast::ExprCast(ref expr, _) => expr,
+            _ => return
+        };
+
+        // The operand got its type from the cast, so the cast is needed even
+        // if the types match now.
+        if cx.tcx.disambiguating_casts.borrow().contains(&e.id) {
+            return;
+        }
+
+        // Generic code is checked as written: a cast between types that
+        // only coincide for some instantiations is not trivial.
+        let t_e = ty::expr_ty(cx.tcx, &**expr);
+        let t_1 = ty::expr_ty(cx.tcx, e);
+        let (lint, msg) = if ty::type_is_numeric(t_e) && ty::type_is_numeric(t_1) {
+            if t_e != t_1 {
+                return;
+            }
+            (TRIVIAL_NUMERIC_CASTS, "trivial numeric cast")
+        } else if t_e == t_1 || TrivialCasts::is_coercion(t_e, t_1) {
+            (TRIVIAL_CASTS, "trivial cast")
+        } else {
+            return;
+        };
+
+        cx.span_lint(lint, e.span,
+                     &format!("{}: `{}` as `{}`", msg,
+                              ty_to_string(cx.tcx, t_e),
+                              ty_to_string(cx.tcx, t_1)));
+        if cx.current_level(lint) != Level::Allow {
+            let as_span = Span { lo: expr.span.hi, hi: e.span.hi, expn_id: e.span.expn_id };
+            cx.sess().span_help(as_span, "cast is unnecessary; remove it");
+        }
+    }
+}
+
+declare_lint! {
+    pub UNPREDICTABLE_POINTER_COMPARISONS,
     Allow,
-    "detects unnecessary type casts that can be removed"
+    "comparisons of function pointers or trait object pointers"
 }
 
 #[derive(Copy)]
-pub struct UnusedCasts;
+pub struct UnpredictablePointerComparisons;
 
-impl LintPass for UnusedCasts {
+impl LintPass for UnpredictablePointerComparisons {
     fn get_lints(&self) -> LintArray {
-        lint_array!(UNUSED_TYPECASTS)
+        lint_array!(UNPREDICTABLE_POINTER_COMPARISONS)
     }
+}
+
+impl LateLintPass for UnpredictablePointerComparisons {
+    fn check_expr(&mut self, cx: &LateContext, e: &ast::Expr) {
+        let (l, r) = match e.node {
+            ast::ExprBinary(binop, ref l, ref r)
+                if binop.node == ast::BiEq || binop.node == ast::BiNe => (l, r),
+            _ => return
+        };
+
+        // A pointer the user cast to explicitly is compared on purpose.
+        fn is_cast(e: &ast::Expr) -> bool {
+            match e.node {
+                ast::ExprCast(..) => true,
+                ast::ExprParen(ref e) => is_cast(&**e),
+                _ => false,
+            }
+        }
+        if is_cast(&**l) || is_cast(&**r) {
+            return;
+        }
 
-    fn check_expr(&mut self, cx: &Context, e: &ast::Expr) {
-        if let ast::ExprCast(ref expr, ref ty) = e.node {
-            let t_t = ty::expr_ty(cx.tcx, e);
-            if ty::expr_ty(cx.tcx, &**expr) == t_t {
-                cx.span_lint(UNUSED_TYPECASTS, ty.span, "unnecessary type cast");
+        let (msg, help) = match ty::expr_ty(cx.tcx, &**l).sty {
+            ty::ty_bare_fn(..) => {
+                ("function pointer comparisons do not produce meaningful results \
+                  when the function may be duplicated across crates",
+                 "compare something that identifies the function instead, such as \
+                  an enum naming it")
             }
+            ty::ty_rptr(_, ty::mt { ty, .. }) | ty::ty_ptr(ty::mt { ty, .. }) => match ty.sty {
+                ty::ty_trait(..) => {
+                    ("comparing trait object pointers compares the vtable and \
+                      data address",
+                     "compare the underlying data, or only the data pointers by \
+                      casting both sides to `*const u8`")
+                }
+                _ => return
+            },
+            _ => return
+        };
+
+        cx.span_lint(UNPREDICTABLE_POINTER_COMPARISONS, e.span, msg);
+        if cx.current_level(UNPREDICTABLE_POINTER_COMPARISONS) != Level::Allow {
+            cx.sess().span_help(e.span, help);
         }
     }
 }
@@ -151,8 +278,10 @@ impl LintPass for TypeLimits {
         lint_array!(UNSIGNED_NEGATION, UNUSED_COMPARISONS, OVERFLOWING_LITERALS,
                     EXCEEDING_BITSHIFTS)
     }
+}
 
-    fn check_expr(&mut self, cx: &Context, e: &ast::Expr) {
+impl LateLintPass for TypeLimits {
+    fn check_expr(&mut self, cx: &LateContext, e: &ast::Expr) {
         match e.node {
             ast::ExprUnary(ast::UnNeg, ref expr) => {
                 match expr.node  {
@@ -413,7 +542,7 @@ declare_lint! {
 }
 
 struct ImproperCTypesVisitor<'a, 'tcx: 'a> {
-    cx: &'a Context<'a, 'tcx>
+    cx: &'a LateContext<'a, 'tcx>
 }
 
 impl<'a, 'tcx> ImproperCTypesVisitor<'a, 'tcx> {
@@ -463,14 +592,16 @@ impl LintPass for ImproperCTypes {
     fn get_lints(&self) -> LintArray {
         lint_array!(IMPROPER_CTYPES)
     }
+}
 
-    fn check_item(&mut self, cx: &Context, it: &ast::Item) {
-        fn check_ty(cx: &Context, ty: &ast::Ty) {
+impl LateLintPass for ImproperCTypes {
+    fn check_item(&mut self, cx: &LateContext, it: &ast::Item) {
+        fn check_ty(cx: &LateContext, ty: &ast::Ty) {
             let mut vis = ImproperCTypesVisitor { cx: cx };
             vis.visit_ty(ty);
         }
 
-        fn check_foreign_fn(cx: &Context, decl: &ast::FnDecl) {
+        fn check_foreign_fn(cx: &LateContext, decl: &ast::FnDecl) {
             for input in &decl.inputs {
                 check_ty(cx, &*input.ty);
             }
@@ -503,7 +634,7 @@ declare_lint! {
 pub struct BoxPointers;
 
 impl BoxPointers {
-    fn check_heap_type<'a, 'tcx>(&self, cx: &Context<'a, 'tcx>,
+    fn check_heap_type<'a, 'tcx>(&self, cx: &LateContext<'a, 'tcx>,
                                  span: Span, ty: Ty<'tcx>) {
         let mut n_uniq: usize = 0;
         ty::fold_ty(cx.tcx, ty, |t| {
@@ -528,8 +659,10 @@ impl LintPass for BoxPointers {
     fn get_lints(&self) -> LintArray {
         lint_array!(BOX_POINTERS)
     }
+}
 
-    fn check_item(&mut self, cx: &Context, it: &ast::Item) {
+impl LateLintPass for BoxPointers {
+    fn check_item(&mut self, cx: &LateContext, it: &ast::Item) {
         match it.node {
             ast::ItemFn(..) |
             ast::ItemTy(..) |
@@ -552,7 +685,7 @@ impl LintPass for BoxPointers {
         }
     }
 
-    fn check_expr(&mut self, cx: &Context, e: &ast::Expr) {
+    fn check_expr(&mut self, cx: &LateContext, e: &ast::Expr) {
         let ty = ty::expr_ty(cx.tcx, e);
         self.check_heap_type(cx, e.span, ty);
     }
@@ -565,7 +698,7 @@ declare_lint! {
 }
 
 struct RawPtrDeriveVisitor<'a, 'tcx: 'a> {
-    cx: &'a Context<'a, 'tcx>
+    cx: &'a LateContext<'a, 'tcx>
 }
 
 impl<'a, 'tcx, 'v> Visitor<'v> for RawPtrDeriveVisitor<'a, 'tcx> {
@@ -597,8 +730,10 @@ impl LintPass for RawPointerDerive {
     fn get_lints(&self) -> LintArray {
         lint_array!(RAW_POINTER_DERIVE)
     }
+}
 
-    fn check_item(&mut self, cx: &Context, item: &ast::Item) {
+impl LateLintPass for RawPointerDerive {
+    fn check_item(&mut self, cx: &LateContext, item: &ast::Item) {
         if !attr::contains_name(&item.attrs, "automatically_derived") {
             return;
         }
@@ -653,8 +788,10 @@ impl LintPass for UnusedAttributes {
     fn get_lints(&self) -> LintArray {
         lint_array!(UNUSED_ATTRIBUTES)
     }
+}
 
-    fn check_attribute(&mut self, cx: &Context, attr: &ast::Attribute) {
+impl LateLintPass for UnusedAttributes {
+    fn check_attribute(&mut self, cx: &LateContext, attr: &ast::Attribute) {
         // Note that check_name() marks the attribute as used if it matches.
         for &(ref name, ty) in KNOWN_ATTRIBUTES {
             match ty {
@@ -666,6 +803,16 @@ impl LintPass for UnusedAttributes {
             }
         }
 
+        for &(ref name, ty) in cx.sess().plugin_attributes.borrow().iter() {
+            match ty {
+                AttributeType::Whitelisted
+                | AttributeType::CrateLevel if attr.check_name(name) => {
+                    break;
+                },
+                _ => ()
+            }
+        }
+
         if !attr::is_used(attr) {
             cx.span_lint(UNUSED_ATTRIBUTES, attr.span, "unused attribute");
             if KNOWN_ATTRIBUTES.contains(&(&attr.name(), AttributeType::CrateLevel)) {
@@ -694,8 +841,10 @@ impl LintPass for PathStatements {
     fn get_lints(&self) -> LintArray {
         lint_array!(PATH_STATEMENTS)
     }
+}
 
-    fn check_stmt(&mut self, cx: &Context, s: &ast::Stmt) {
+impl LateLintPass for PathStatements {
+    fn check_stmt(&mut self, cx: &LateContext, s: &ast::Stmt) {
         match s.node {
             ast::StmtSemi(ref expr, _) => {
                 match expr.node {
@@ -728,8 +877,10 @@ impl LintPass for UnusedResults {
     fn get_lints(&self) -> LintArray {
         lint_array!(UNUSED_MUST_USE, UNUSED_RESULTS)
     }
+}
 
-    fn check_stmt(&mut self, cx: &Context, s: &ast::Stmt) {
+impl LateLintPass for UnusedResults {
+    fn check_stmt(&mut self, cx: &LateContext, s: &ast::Stmt) {
         let expr = match s.node {
             ast::StmtSemi(ref expr, _) => &**expr,
             _ => return
@@ -762,7 +913,7 @@ impl LintPass for UnusedResults {
             cx.span_lint(UNUSED_RESULTS, s.span, "unused result");
         }
 
-        fn check_must_use(cx: &Context, attrs: &[ast::Attribute], sp: Span) -> bool {
+        fn check_must_use(cx: &LateContext, attrs: &[ast::Attribute], sp: Span) -> bool {
             for attr in attrs {
                 if attr.check_name("must_use") {
                     let mut msg = "unused result which must be used".to_string();
@@ -793,7 +944,7 @@ declare_lint! {
 pub struct NonCamelCaseTypes;
 
 impl NonCamelCaseTypes {
-    fn check_case(&self, cx: &Context, sort: &str, ident: ast::Ident, span: Span) {
+    fn check_case(&self, cx: &LateContext, sort: &str, ident: ast::Ident, span: Span) {
         fn is_camel_case(ident: ast::Ident) -> bool {
             let ident = token::get_ident(ident);
             if ident.is_empty() {
@@ -834,8 +985,10 @@ impl LintPass for NonCamelCaseTypes {
     fn get_lints(&self) -> LintArray {
         lint_array!(NON_CAMEL_CASE_TYPES)
     }
+}
 
-    fn check_item(&mut self, cx: &Context, it: &ast::Item) {
+impl LateLintPass for NonCamelCaseTypes {
+    fn check_item(&mut self, cx: &LateContext, it: &ast::Item) {
         let has_extern_repr = it.attrs.iter().any(|attr| {
             attr::find_repr_attrs(cx.tcx.sess.diagnostic(), attr).iter()
                 .any(|r| r == &attr::ReprExtern)
@@ -864,7 +1017,7 @@ impl LintPass for NonCamelCaseTypes {
         }
     }
 
-    fn check_generics(&mut self, cx: &Context, it: &ast::Generics) {
+    fn check_generics(&mut self, cx: &LateContext, it: &ast::Generics) {
         for gen in &*it.ty_params {
             self.check_case(cx, "type parameter", gen.ident, gen.span);
         }
@@ -878,7 +1031,7 @@ enum MethodContext {
     PlainImpl
 }
 
-fn method_context(cx: &Context, id: ast::NodeId, span: Span) -> MethodContext {
+fn method_context(cx: &LateContext, id: ast::NodeId, span: Span) -> MethodContext {
     match cx.tcx.impl_or_trait_items.borrow().get(&local_def(id)) {
         None => cx.sess().span_bug(span, "missing method descriptor?!"),
         Some(item) => match item.container() {
@@ -899,10 +1052,16 @@ declare_lint! {
     "methods, functions, lifetime parameters and modules should have snake case names"
 }
 
-#[derive(Copy)]
-pub struct NonSnakeCase;
+pub struct NonSnakeCase {
+    /// Methods of the inherent impls seen so far.
+    inherent_methods: NodeSet,
+}
 
 impl NonSnakeCase {
+    pub fn new() -> NonSnakeCase {
+        NonSnakeCase { inherent_methods: NodeSet() }
+    }
+
     fn to_snake_case(mut str: &str) -> String {
         let mut words = vec![];
         // Preserve leading underscores
@@ -935,7 +1094,7 @@ impl NonSnakeCase {
         words.connect("_")
     }
 
-    fn check_snake_case(&self, cx: &Context, sort: &str, ident: ast::Ident, span: Span) {
+    fn check_snake_case<C: LintContext>(cx: &C, sort: &str, ident: ast::Ident, span: Span) {
         fn is_snake_case(ident: ast::Ident) -> bool {
             let ident = token::get_ident(ident);
             if ident.is_empty() {
@@ -977,57 +1136,80 @@ impl LintPass for NonSnakeCase {
     fn get_lints(&self) -> LintArray {
         lint_array!(NON_SNAKE_CASE)
     }
+}
 
-    fn check_fn(&mut self, cx: &Context,
+impl EarlyLintPass for NonSnakeCase {
+    fn check_fn(&mut self, cx: &EarlyContext,
                 fk: visit::FnKind, _: &ast::FnDecl,
-                _: &ast::Block, span: Span, id: ast::NodeId) {
-        match fk {
-            visit::FkMethod(ident, _) => match method_context(cx, id, span) {
-                MethodContext::PlainImpl => {
-                    self.check_snake_case(cx, "method", ident, span)
-                },
-                MethodContext::TraitDefaultImpl => {
-                    self.check_snake_case(cx, "trait method", ident, span)
-                },
-                _ => (),
-            },
-            visit::FkItemFn(ident, _, _, _) => {
-                self.check_snake_case(cx, "function", ident, span)
-            },
-            _ => (),
+                _: &ast::Block, span: Span, _: ast::NodeId) {
+        if let visit::FkItemFn(ident, _, _, _) = fk {
+            NonSnakeCase::check_snake_case(cx, "function", ident, span)
         }
     }
 
-    fn check_item(&mut self, cx: &Context, it: &ast::Item) {
-        if let ast::ItemMod(_) = it.node {
-            self.check_snake_case(cx, "module", it.ident, it.span);
+    fn check_item(&mut self, cx: &EarlyContext, it: &ast::Item) {
+        match it.node {
+            ast::ItemMod(_) => {
+                NonSnakeCase::check_snake_case(cx, "module", it.ident, it.span);
+            }
+            // Methods of trait impls are named by the trait, so only
+            // inherent methods are checked.
+            ast::ItemImpl(_, _, _, None, _, ref impl_items) => {
+                for impl_item in impl_items {
+                    if let ast::MethodImplItem(..) = impl_item.node {
+                        self.inherent_methods.insert(impl_item.id);
+                    }
+                }
+            }
+            _ => {}
         }
     }
 
-    fn check_trait_item(&mut self, cx: &Context, trait_item: &ast::TraitItem) {
-        if let ast::MethodTraitItem(_, None) = trait_item.node {
-            self.check_snake_case(cx, "trait method", trait_item.ident, trait_item.span);
+    fn check_impl_item(&mut self, cx: &EarlyContext, impl_item: &ast::ImplItem) {
+        if self.inherent_methods.contains(&impl_item.id) {
+            NonSnakeCase::check_snake_case(cx, "method", impl_item.ident, impl_item.span);
         }
     }
 
-    fn check_lifetime_def(&mut self, cx: &Context, t: &ast::LifetimeDef) {
-        self.check_snake_case(cx, "lifetime", t.lifetime.name.ident(), t.lifetime.span);
+    fn check_trait_item(&mut self, cx: &EarlyContext, trait_item: &ast::TraitItem) {
+        if let ast::MethodTraitItem(..) = trait_item.node {
+            NonSnakeCase::check_snake_case(cx, "trait method",
+                                           trait_item.ident, trait_item.span);
+        }
     }
 
-    fn check_pat(&mut self, cx: &Context, p: &ast::Pat) {
-        if let &ast::PatIdent(_, ref path1, _) = &p.node {
-            let def = cx.tcx.def_map.borrow().get(&p.id).map(|d| d.full_def());
-            if let Some(def::DefLocal(_)) = def {
-                self.check_snake_case(cx, "variable", path1.node, p.span);
-            }
-        }
+    fn check_lifetime_def(&mut self, cx: &EarlyContext, t: &ast::LifetimeDef) {
+        NonSnakeCase::check_snake_case(cx, "lifetime", t.lifetime.name.ident(), t.lifetime.span);
     }
 
-    fn check_struct_def(&mut self, cx: &Context, s: &ast::StructDef,
+    fn check_struct_def(&mut self, cx: &EarlyContext, s: &ast::StructDef,
                         _: ast::Ident, _: &ast::Generics, _: ast::NodeId) {
         for sf in &s.fields {
             if let ast::StructField_ { kind: ast::NamedField(ident, _), .. } = sf.node {
-                self.check_snake_case(cx, "structure field", ident, sf.span);
+                NonSnakeCase::check_snake_case(cx, "structure field", ident, sf.span);
+            }
+        }
+    }
+}
+
+/// The part of `non_snake_case` that needs name resolution: telling variable
+/// bindings apart from paths to constants and unit structs in patterns.
+#[derive(Copy)]
+pub struct NonSnakeCaseBindings;
+
+impl LintPass for NonSnakeCaseBindings {
+    fn get_lints(&self) -> LintArray {
+        // NON_SNAKE_CASE is registered by `NonSnakeCase`.
+        lint_array!()
+    }
+}
+
+impl LateLintPass for NonSnakeCaseBindings {
+    fn check_pat(&mut self, cx: &LateContext, p: &ast::Pat) {
+        if let &ast::PatIdent(_, ref path1, _) = &p.node {
+            let def = cx.tcx.def_map.borrow().get(&p.id).map(|d| d.full_def());
+            if let Some(def::DefLocal(_)) = def {
+                NonSnakeCase::check_snake_case(cx, "variable", path1.node, p.span);
             }
         }
     }
@@ -1043,7 +1225,7 @@ declare_lint! {
 pub struct NonUpperCaseGlobals;
 
 impl NonUpperCaseGlobals {
-    fn check_upper_case(cx: &Context, sort: &str, ident: ast::Ident, span: Span) {
+    fn check_upper_case(cx: &LateContext, sort: &str, ident: ast::Ident, span: Span) {
         let s = token::get_ident(ident);
 
         if s.chars().any(|c| c.is_lowercase()) {
@@ -1065,8 +1247,10 @@ impl LintPass for NonUpperCaseGlobals {
     fn get_lints(&self) -> LintArray {
         lint_array!(NON_UPPER_CASE_GLOBALS)
     }
+}
 
-    fn check_item(&mut self, cx: &Context, it: &ast::Item) {
+impl LateLintPass for NonUpperCaseGlobals {
+    fn check_item(&mut self, cx: &LateContext, it: &ast::Item) {
         match it.node {
             // only check static constants
             ast::ItemStatic(_, ast::MutImmutable, _) => {
@@ -1079,7 +1263,7 @@ impl LintPass for NonUpperCaseGlobals {
         }
     }
 
-    fn check_pat(&mut self, cx: &Context, p: &ast::Pat) {
+    fn check_pat(&mut self, cx: &LateContext, p: &ast::Pat) {
         // Lint for constants that look like binding identifiers (#7526)
         match (&p.node, cx.tcx.def_map.borrow().get(&p.id).map(|d| d.full_def())) {
             (&ast::PatIdent(_, ref path1, _), Some(def::DefConst(..))) => {
@@ -1101,7 +1285,7 @@ declare_lint! {
 pub struct UnusedParens;
 
 impl UnusedParens {
-    fn check_unused_parens_core(&self, cx: &Context, value: &ast::Expr, msg: &str,
+    fn check_unused_parens_core(&self, cx: &LateContext, value: &ast::Expr, msg: &str,
                                 struct_lit_needs_parens: bool) {
         if let ast::ExprParen(ref inner) = value.node {
             let necessary = struct_lit_needs_parens && contains_exterior_struct_lit(&**inner);
@@ -1151,8 +1335,10 @@ impl LintPass for UnusedParens {
     fn get_lints(&self) -> LintArray {
         lint_array!(UNUSED_PARENS)
     }
+}
 
-    fn check_expr(&mut self, cx: &Context, e: &ast::Expr) {
+impl LateLintPass for UnusedParens {
+    fn check_expr(&mut self, cx: &LateContext, e: &ast::Expr) {
         let (value, msg, struct_lit_needs_parens) = match e.node {
             ast::ExprIf(ref cond, _, _) => (cond, "`if` condition", true),
             ast::ExprWhile(ref cond, _, _) => (cond, "`while` condition", true),
@@ -1170,7 +1356,7 @@ impl LintPass for UnusedParens {
         self.check_unused_parens_core(cx, &**value, msg, struct_lit_needs_parens);
     }
 
-    fn check_stmt(&mut self, cx: &Context, s: &ast::Stmt) {
+    fn check_stmt(&mut self, cx: &LateContext, s: &ast::Stmt) {
         let (value, msg) = match s.node {
             ast::StmtDecl(ref decl, _) => match decl.node {
                 ast::DeclLocal(ref local) => match local.init {
@@ -1198,8 +1384,10 @@ impl LintPass for UnusedImportBraces {
     fn get_lints(&self) -> LintArray {
         lint_array!(UNUSED_IMPORT_BRACES)
     }
+}
 
-    fn check_item(&mut self, cx: &Context, item: &ast::Item) {
+impl EarlyLintPass for UnusedImportBraces {
+    fn check_item(&mut self, cx: &EarlyContext, item: &ast::Item) {
         if let ast::ItemUse(ref view_path) = item.node {
             if let ast::ViewPathList(_, ref items) = view_path.node {
                 if items.len() == 1 {
@@ -1228,8 +1416,10 @@ impl LintPass for NonShorthandFieldPatterns {
     fn get_lints(&self) -> LintArray {
         lint_array!(NON_SHORTHAND_FIELD_PATTERNS)
     }
+}
 
-    fn check_pat(&mut self, cx: &Context, pat: &ast::Pat) {
+impl LateLintPass for NonShorthandFieldPatterns {
+    fn check_pat(&mut self, cx: &LateContext, pat: &ast::Pat) {
         let def_map = cx.tcx.def_map.borrow();
         if let ast::PatStruct(_, ref v, _) = pat.node {
             let field_pats = v.iter().filter(|fieldpat| {
@@ -1265,18 +1455,121 @@ impl LintPass for UnusedUnsafe {
     fn get_lints(&self) -> LintArray {
         lint_array!(UNUSED_UNSAFE)
     }
+}
 
-    fn check_expr(&mut self, cx: &Context, e: &ast::Expr) {
+impl LateLintPass for UnusedUnsafe {
+    fn check_expr(&mut self, cx: &LateContext, e: &ast::Expr) {
         if let ast::ExprBlock(ref blk) = e.node {
             // Don't warn about generated blocks, that'll just pollute the output.
             if blk.rules == ast::UnsafeBlock(ast::UserProvided) &&
                 !cx.tcx.used_unsafe.borrow().contains(&blk.id) {
+                    // Blocks inside an unsafe fn are needed when the
+                    // operations in them are required to be marked.
+                    if cx.tcx.used_unsafe_in_unsafe_fn.borrow().contains(&blk.id) &&
+                        cx.current_level(UNSAFE_OP_IN_UNSAFE_FN) != Level::Allow {
+                        return
+                    }
                     cx.span_lint(UNUSED_UNSAFE, blk.span, "unnecessary `unsafe` block");
             }
         }
     }
 }
 
+declare_lint! {
+    UNSAFE_OP_IN_UNSAFE_FN,
+    Allow,
+    "unsafe operations in an unsafe fn that are not inside an `unsafe` block"
+}
+
+#[derive(Copy)]
+pub struct UnsafeOpInUnsafeFn;
+
+impl LintPass for UnsafeOpInUnsafeFn {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(UNSAFE_OP_IN_UNSAFE_FN)
+    }
+}
+
+impl LateLintPass for UnsafeOpInUnsafeFn {
+    fn check_fn(&mut self, cx: &LateContext, _: visit::FnKind, _: &ast::FnDecl,
+                _: &ast::Block, _: Span, id: ast::NodeId) {
+        if let Some(ops) = cx.tcx.unsafe_ops_in_unsafe_fn.borrow().get(&id) {
+            for &(span, ref description) in ops {
+                cx.span_lint(UNSAFE_OP_IN_UNSAFE_FN, span,
+                             &format!("{} is unsafe and requires an `unsafe` block, \
+                                       even inside an unsafe fn", description));
+            }
+        }
+    }
+}
+
+declare_lint! {
+    pub UNUSED_UNSAFE_FN,
+    Allow,
+    "an `unsafe fn` whose body performs no unsafe operations"
+}
+
+#[derive(Copy)]
+pub struct UnusedUnsafeFn;
+
+impl LintPass for UnusedUnsafeFn {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(UNUSED_UNSAFE_FN)
+    }
+}
+
+impl LateLintPass for UnusedUnsafeFn {
+    fn check_fn(&mut self, cx: &LateContext, fk: visit::FnKind, _: &ast::FnDecl,
+                _: &ast::Block, span: Span, id: ast::NodeId) {
+        let abi = match fk {
+            visit::FkItemFn(_, _, ast::Unsafety::Unsafe, abi) => abi,
+            visit::FkMethod(_, sig) if sig.unsafety == ast::Unsafety::Unsafe => {
+                // The unsafety of trait methods, and of the impls of
+                // them, is part of the trait's contract.
+                match ty::impl_or_trait_item(cx.tcx, local_def(id)).container() {
+                    ty::TraitContainer(_) => return,
+                    ty::ImplContainer(impl_did) => {
+                        if ty::impl_trait_ref(cx.tcx, impl_did).is_some() {
+                            return;
+                        }
+                    }
+                }
+                sig.abi
+            }
+            _ => return,
+        };
+
+        // Functions callable from foreign code keep whatever contract
+        // they were declared with.
+        if abi != abi::Rust || attr::contains_name(cx.tcx.map.attrs(id), "no_mangle") {
+            return;
+        }
+
+        if !cx.tcx.used_unsafe.borrow().contains(&id) {
+            cx.span_lint(UNUSED_UNSAFE_FN, unsafe_keyword_span(cx, span).unwrap_or(span),
+                         "this function is declared unsafe but performs no unsafe \
+                          operations; consider documenting the invariant or removing \
+                          `unsafe`");
+        }
+    }
+}
+
+/// The span of the `unsafe` keyword in the header of the function at `span`.
+fn unsafe_keyword_span(cx: &LateContext, span: Span) -> Option<Span> {
+    let snippet = match cx.sess().codemap().span_to_snippet(span) {
+        Ok(snippet) => snippet,
+        Err(_) => return None,
+    };
+    let header = match snippet.find("fn") {
+        Some(end) => &snippet[..end],
+        None => return None,
+    };
+    header.find("unsafe").map(|start| {
+        let lo = span.lo + codemap::BytePos(start as u32);
+        Span { lo: lo, hi: lo + codemap::BytePos("unsafe".len() as u32), expn_id: span.expn_id }
+    })
+}
+
 declare_lint! {
     UNSAFE_CODE,
     Allow,
@@ -1290,8 +1583,10 @@ impl LintPass for UnsafeCode {
     fn get_lints(&self) -> LintArray {
         lint_array!(UNSAFE_CODE)
     }
+}
 
-    fn check_expr(&mut self, cx: &Context, e: &ast::Expr) {
+impl EarlyLintPass for UnsafeCode {
+    fn check_expr(&mut self, cx: &EarlyContext, e: &ast::Expr) {
         if let ast::ExprBlock(ref blk) = e.node {
             // Don't warn about generated blocks, that'll just pollute the output.
             if blk.rules == ast::UnsafeBlock(ast::UserProvided) {
@@ -1300,7 +1595,7 @@ impl LintPass for UnsafeCode {
         }
     }
 
-    fn check_item(&mut self, cx: &Context, it: &ast::Item) {
+    fn check_item(&mut self, cx: &EarlyContext, it: &ast::Item) {
         match it.node {
             ast::ItemTrait(ast::Unsafety::Unsafe, _, _, _) =>
                 cx.span_lint(UNSAFE_CODE, it.span, "declaration of an `unsafe` trait"),
@@ -1312,7 +1607,7 @@ impl LintPass for UnsafeCode {
         }
     }
 
-    fn check_fn(&mut self, cx: &Context, fk: visit::FnKind, _: &ast::FnDecl,
+    fn check_fn(&mut self, cx: &EarlyContext, fk: visit::FnKind, _: &ast::FnDecl,
                 _: &ast::Block, span: Span, _: ast::NodeId) {
         match fk {
             visit::FkItemFn(_, _, ast::Unsafety::Unsafe, _) =>
@@ -1328,7 +1623,7 @@ impl LintPass for UnsafeCode {
         }
     }
 
-    fn check_trait_item(&mut self, cx: &Context, trait_item: &ast::TraitItem) {
+    fn check_trait_item(&mut self, cx: &EarlyContext, trait_item: &ast::TraitItem) {
         if let ast::MethodTraitItem(ref sig, None) = trait_item.node {
             if sig.unsafety == ast::Unsafety::Unsafe {
                 cx.span_lint(UNSAFE_CODE, trait_item.span,
@@ -1338,26 +1633,67 @@ impl LintPass for UnsafeCode {
     }
 }
 
+declare_lint! {
+    pub LARGE_FN_BODIES,
+    Allow,
+    "function bodies with more expressions than `#![fn_expr_limit]` allows"
+}
+
+#[derive(Copy)]
+pub struct LargeFnBodies;
+
+impl LintPass for LargeFnBodies {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(LARGE_FN_BODIES)
+    }
+}
+
+impl EarlyLintPass for LargeFnBodies {
+    fn check_fn(&mut self, cx: &EarlyContext, fk: visit::FnKind, _: &ast::FnDecl,
+                body: &ast::Block, span: Span, _: ast::NodeId) {
+        if let visit::FkFnBlock = fk {
+            // Closures are measured as part of the function they appear in.
+            return;
+        }
+
+        let exprs = crate_stats::body_size(body).exprs;
+        if exprs > cx.sess().fn_expr_limit.get() {
+            // A function written by a macro is reported where the macro was
+            // invoked from hand-written code.
+            cx.span_lint(LARGE_FN_BODIES, crate_stats::outermost_call_site(cx.sess(), span),
+                         &format!("this function has {} expressions and will be slow \
+                                   to compile", exprs));
+        }
+    }
+}
+
 declare_lint! {
     pub UNUSED_MUT,
     Warn,
     "detect mut variables which don't need to be mutable"
 }
 
-#[derive(Copy)]
-pub struct UnusedMut;
+pub struct UnusedMut {
+    /// Locals whose mutability is used implicitly, see `ImplicitMutBorrows`.
+    implicitly_borrowed: NodeSet,
+}
 
 impl UnusedMut {
-    fn check_unused_mut_pat(&self, cx: &Context, pats: &[P<ast::Pat>]) {
+    pub fn new() -> UnusedMut {
+        UnusedMut { implicitly_borrowed: NodeSet() }
+    }
+
+    fn check_unused_mut_pat(&self, cx: &LateContext, pats: &[P<ast::Pat>]) {
         // collect all mutable pattern and group their NodeIDs by their Identifier to
         // avoid false warnings in match arms with multiple patterns
 
         let mut mutables = FnvHashMap();
         for p in pats {
-            pat_util::pat_bindings(&cx.tcx.def_map, &**p, |mode, id, _, path1| {
+            pat_util::pat_bindings(&cx.tcx.def_map, &**p, |mode, id, span, path1| {
                 let ident = path1.node;
                 if let ast::BindByValue(ast::MutMutable) = mode {
-                    if !token::get_ident(ident).starts_with("_") {
+                    if !token::get_ident(ident).starts_with("_") &&
+                       !in_external_macro(cx, span) {
                         match mutables.entry(ident.name.usize()) {
                             Vacant(entry) => { entry.insert(vec![id]); },
                             Occupied(mut entry) => { entry.get_mut().push(id); },
@@ -1369,20 +1705,145 @@ impl UnusedMut {
 
         let used_mutables = cx.tcx.used_mut_nodes.borrow();
         for (_, v) in &mutables {
-            if !v.iter().any(|e| used_mutables.contains(e)) {
-                cx.span_lint(UNUSED_MUT, cx.tcx.map.span(v[0]),
+            if !v.iter().any(|e| used_mutables.contains(e) ||
+                                 self.implicitly_borrowed.contains(e)) {
+                let span = cx.tcx.map.span(v[0]);
+                cx.span_lint(UNUSED_MUT, span,
                              "variable does not need to be mutable");
+                if cx.current_level(UNUSED_MUT) != Level::Allow {
+                    if let Some(mut_span) = mut_keyword_span(cx, span) {
+                        cx.sess().span_help(mut_span, "remove this `mut`");
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Whether `span` comes from the expansion of a macro defined in another crate
+/// (or inside the compiler), whose bindings the user has no control over.
+fn in_external_macro(cx: &LateContext, span: Span) -> bool {
+    let cm = cx.sess().codemap();
+    cm.with_expn_info(span.expn_id, |info| {
+        info.map_or(false, |info| {
+            // Macros imported from other crates are parsed from a filemap
+            // named `<crate macros>`, see `CrateReader::read_exported_macros`.
+            info.callee.span.map_or(true, |def| {
+                let name = &cm.lookup_byte_offset(def.lo).fm.name;
+                name.starts_with("<") && name.ends_with(" macros>")
+            })
+        })
+    })
+}
+
+/// The span of the `mut ` (including the whitespace up to the binding's name)
+/// at the start of the binding `span`.
+fn mut_keyword_span(cx: &LateContext, span: Span) -> Option<Span> {
+    let snippet = match cx.sess().codemap().span_to_snippet(span) {
+        Ok(snippet) => snippet,
+        Err(_) => return None,
+    };
+    if !snippet.starts_with("mut") {
+        return None;
+    }
+    let rest = &snippet["mut".len()..];
+    let ws = rest.len() - rest.trim_left().len();
+    if ws == 0 {
+        return None;
+    }
+    Some(Span {
+        lo: span.lo,
+        hi: span.lo + codemap::BytePos(("mut".len() + ws) as u32),
+        expn_id: span.expn_id,
+    })
+}
+
+/// Collects the locals whose mutability is used by an implicit `&mut` borrow:
+/// a method receiver that was autoref'd mutably, or the operand of an
+/// overloaded operator taking `&mut self` (`IndexMut`, `DerefMut`). These are
+/// recorded straight from the final adjustment and method tables, so that they
+/// are counted regardless of how the call was resolved or where it came from.
+struct ImplicitMutBorrows<'a, 'tcx: 'a> {
+    tcx: &'a ty::ctxt<'tcx>,
+    borrowed: NodeSet,
+}
+
+impl<'a, 'tcx> ImplicitMutBorrows<'a, 'tcx> {
+    fn mark_local(&mut self, mut expr: &ast::Expr) {
+        // Borrowing a field borrows the local it belongs to.
+        loop {
+            match expr.node {
+                ast::ExprField(ref base, _) |
+                ast::ExprTupField(ref base, _) |
+                ast::ExprParen(ref base) => expr = &**base,
+                ast::ExprPath(..) => {
+                    let def = self.tcx.def_map.borrow().get(&expr.id).map(|d| d.full_def());
+                    if let Some(def::DefLocal(id)) = def {
+                        self.borrowed.insert(id);
+                    }
+                    return;
+                }
+                _ => return,
+            }
+        }
+    }
+
+    fn takes_mut_self(&self, expr: &ast::Expr) -> bool {
+        let method_call = ty::MethodCall::expr(expr.id);
+        match self.tcx.method_map.borrow().get(&method_call) {
+            Some(method) => {
+                match ty::ty_fn_sig(method.ty).0.inputs.get(0).map(|ty| &ty.sty) {
+                    Some(&ty::ty_rptr(_, ty::mt { mutbl: ast::MutMutable, .. })) => true,
+                    _ => false,
+                }
             }
+            None => false,
         }
     }
 }
 
+impl<'a, 'tcx, 'v> Visitor<'v> for ImplicitMutBorrows<'a, 'tcx> {
+    fn visit_expr(&mut self, e: &ast::Expr) {
+        match e.node {
+            ast::ExprMethodCall(_, _, ref args) => {
+                let receiver = &*args[0];
+                let autoref_mut = match self.tcx.adjustments.borrow().get(&receiver.id) {
+                    Some(&ty::AdjustDerefRef(ty::AutoDerefRef {
+                        autoderefs: 0,
+                        autoref: Some(ty::AutoPtr(_, ast::MutMutable, _))
+                    })) => true,
+                    _ => false,
+                };
+                if autoref_mut {
+                    self.mark_local(receiver);
+                }
+            }
+            ast::ExprIndex(ref base, _) |
+            ast::ExprUnary(ast::UnDeref, ref base) => {
+                if self.takes_mut_self(e) {
+                    self.mark_local(&**base);
+                }
+            }
+            _ => {}
+        }
+        visit::walk_expr(self, e);
+    }
+}
+
 impl LintPass for UnusedMut {
     fn get_lints(&self) -> LintArray {
         lint_array!(UNUSED_MUT)
     }
+}
+
+impl LateLintPass for UnusedMut {
+    fn check_crate(&mut self, cx: &LateContext, krate: &ast::Crate) {
+        let mut visitor = ImplicitMutBorrows { tcx: cx.tcx, borrowed: NodeSet() };
+        visit::walk_crate(&mut visitor, krate);
+        self.implicitly_borrowed = visitor.borrowed;
+    }
 
-    fn check_expr(&mut self, cx: &Context, e: &ast::Expr) {
+    fn check_expr(&mut self, cx: &LateContext, e: &ast::Expr) {
         if let ast::ExprMatch(_, ref arms, _) = e.node {
             for a in arms {
                 self.check_unused_mut_pat(cx, &a.pats)
@@ -1390,7 +1851,7 @@ impl LintPass for UnusedMut {
         }
     }
 
-    fn check_stmt(&mut self, cx: &Context, s: &ast::Stmt) {
+    fn check_stmt(&mut self, cx: &LateContext, s: &ast::Stmt) {
         if let ast::StmtDecl(ref d, _) = s.node {
             if let ast::DeclLocal(ref l) = d.node {
                 self.check_unused_mut_pat(cx, slice::ref_slice(&l.pat));
@@ -1398,7 +1859,7 @@ impl LintPass for UnusedMut {
         }
     }
 
-    fn check_fn(&mut self, cx: &Context,
+    fn check_fn(&mut self, cx: &LateContext,
                 _: visit::FnKind, decl: &ast::FnDecl,
                 _: &ast::Block, _: Span, _: ast::NodeId) {
         for a in &decl.inputs {
@@ -1420,8 +1881,10 @@ impl LintPass for UnusedAllocation {
     fn get_lints(&self) -> LintArray {
         lint_array!(UNUSED_ALLOCATION)
     }
+}
 
-    fn check_expr(&mut self, cx: &Context, e: &ast::Expr) {
+impl LateLintPass for UnusedAllocation {
+    fn check_expr(&mut self, cx: &LateContext, e: &ast::Expr) {
         match e.node {
             ast::ExprUnary(ast::UnUniq, _) => (),
             _ => return
@@ -1477,7 +1940,7 @@ impl MissingDoc {
     }
 
     fn check_missing_docs_attrs(&self,
-                               cx: &Context,
+                               cx: &LateContext,
                                id: Option<ast::NodeId>,
                                attrs: &[ast::Attribute],
                                sp: Span,
@@ -1519,8 +1982,10 @@ impl LintPass for MissingDoc {
     fn get_lints(&self) -> LintArray {
         lint_array!(MISSING_DOCS)
     }
+}
 
-    fn enter_lint_attrs(&mut self, _: &Context, attrs: &[ast::Attribute]) {
+impl LateLintPass for MissingDoc {
+    fn enter_lint_attrs(&mut self, _: &LateContext, attrs: &[ast::Attribute]) {
         let doc_hidden = self.doc_hidden() || attrs.iter().any(|attr| {
             attr.check_name("doc") && match attr.meta_item_list() {
                 None => false,
@@ -1530,26 +1995,26 @@ impl LintPass for MissingDoc {
         self.doc_hidden_stack.push(doc_hidden);
     }
 
-    fn exit_lint_attrs(&mut self, _: &Context, _: &[ast::Attribute]) {
+    fn exit_lint_attrs(&mut self, _: &LateContext, _: &[ast::Attribute]) {
         self.doc_hidden_stack.pop().expect("empty doc_hidden_stack");
     }
 
-    fn check_struct_def(&mut self, _: &Context, _: &ast::StructDef,
+    fn check_struct_def(&mut self, _: &LateContext, _: &ast::StructDef,
                         _: ast::Ident, _: &ast::Generics, id: ast::NodeId) {
         self.struct_def_stack.push(id);
     }
 
-    fn check_struct_def_post(&mut self, _: &Context, _: &ast::StructDef,
+    fn check_struct_def_post(&mut self, _: &LateContext, _: &ast::StructDef,
                              _: ast::Ident, _: &ast::Generics, id: ast::NodeId) {
         let popped = self.struct_def_stack.pop().expect("empty struct_def_stack");
         assert!(popped == id);
     }
 
-    fn check_crate(&mut self, cx: &Context, krate: &ast::Crate) {
+    fn check_crate(&mut self, cx: &LateContext, krate: &ast::Crate) {
         self.check_missing_docs_attrs(cx, None, &krate.attrs, krate.span, "crate");
     }
 
-    fn check_item(&mut self, cx: &Context, it: &ast::Item) {
+    fn check_item(&mut self, cx: &LateContext, it: &ast::Item) {
         let desc = match it.node {
             ast::ItemFn(..) => "a function",
             ast::ItemMod(..) => "a module",
@@ -1562,7 +2027,7 @@ impl LintPass for MissingDoc {
         self.check_missing_docs_attrs(cx, Some(it.id), &it.attrs, it.span, desc);
     }
 
-    fn check_trait_item(&mut self, cx: &Context, trait_item: &ast::TraitItem) {
+    fn check_trait_item(&mut self, cx: &LateContext, trait_item: &ast::TraitItem) {
         let desc = match trait_item.node {
             ast::MethodTraitItem(..) => "a trait method",
             ast::TypeTraitItem(..) => "an associated type"
@@ -1572,7 +2037,7 @@ impl LintPass for MissingDoc {
                                       trait_item.span, desc);
     }
 
-    fn check_impl_item(&mut self, cx: &Context, impl_item: &ast::ImplItem) {
+    fn check_impl_item(&mut self, cx: &LateContext, impl_item: &ast::ImplItem) {
         // If the method is an impl for a trait, don't doc.
         if method_context(cx, impl_item.id, impl_item.span) == MethodContext::TraitImpl {
             return;
@@ -1588,7 +2053,7 @@ impl LintPass for MissingDoc {
                                       impl_item.span, desc);
     }
 
-    fn check_struct_field(&mut self, cx: &Context, sf: &ast::StructField) {
+    fn check_struct_field(&mut self, cx: &LateContext, sf: &ast::StructField) {
         if let ast::NamedField(_, vis) = sf.node.kind {
             if vis == ast::Public || self.in_variant {
                 let cur_struct_def = *self.struct_def_stack.last()
@@ -1600,13 +2065,13 @@ impl LintPass for MissingDoc {
         }
     }
 
-    fn check_variant(&mut self, cx: &Context, v: &ast::Variant, _: &ast::Generics) {
+    fn check_variant(&mut self, cx: &LateContext, v: &ast::Variant, _: &ast::Generics) {
         self.check_missing_docs_attrs(cx, Some(v.node.id), &v.node.attrs, v.span, "a variant");
         assert!(!self.in_variant);
         self.in_variant = true;
     }
 
-    fn check_variant_post(&mut self, _: &Context, _: &ast::Variant, _: &ast::Generics) {
+    fn check_variant_post(&mut self, _: &LateContext, _: &ast::Variant, _: &ast::Generics) {
         assert!(self.in_variant);
         self.in_variant = false;
     }
@@ -1625,8 +2090,10 @@ impl LintPass for MissingCopyImplementations {
     fn get_lints(&self) -> LintArray {
         lint_array!(MISSING_COPY_IMPLEMENTATIONS)
     }
+}
 
-    fn check_item(&mut self, cx: &Context, item: &ast::Item) {
+impl LateLintPass for MissingCopyImplementations {
+    fn check_item(&mut self, cx: &LateContext, item: &ast::Item) {
         if !cx.exported_items.contains(&item.id) {
             return;
         }
@@ -1663,6 +2130,92 @@ impl LintPass for MissingCopyImplementations {
     }
 }
 
+declare_lint! {
+    pub RAW_POINTER_ONLY_PARAMS,
+    Allow,
+    "detects type parameters of types with destructors that are only used behind raw pointers"
+}
+
+/// Types that own their contents through raw pointers (like `Vec<T>`)
+/// must say so with a `PhantomData<T>` field, otherwise the drop
+/// checker assumes that dropping the type never drops a `T`.
+#[derive(Copy)]
+pub struct RawPointerOnlyParams;
+
+impl LintPass for RawPointerOnlyParams {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(RAW_POINTER_ONLY_PARAMS)
+    }
+}
+
+impl LateLintPass for RawPointerOnlyParams {
+    fn check_item(&mut self, cx: &LateContext, item: &ast::Item) {
+        let ast_generics = match item.node {
+            ast::ItemStruct(_, ref ast_generics) |
+            ast::ItemEnum(_, ref ast_generics) => ast_generics,
+            _ => return,
+        };
+        if ast_generics.ty_params.is_empty() {
+            return;
+        }
+        // Without a destructor the drop checker has nothing to say
+        // about the parameters, raw pointers or not.
+        if !cx.tcx.destructor_for_type.borrow().contains_key(&local_def(item.id)) {
+            return;
+        }
+
+        let field_tys: Vec<Ty> = match ty::lookup_item_type(cx.tcx, local_def(item.id)).ty.sty {
+            ty::ty_struct(did, substs) => {
+                ty::struct_fields(cx.tcx, did, substs).iter().map(|f| f.mt.ty).collect()
+            }
+            ty::ty_enum(did, substs) => {
+                ty::substd_enum_variants(cx.tcx, did, substs)
+                    .iter()
+                    .flat_map(|variant| variant.args.iter())
+                    .cloned()
+                    .collect()
+            }
+            _ => return,
+        };
+
+        for ty_param in ast_generics.ty_params.iter() {
+            let is_param = |t: Ty| match t.sty {
+                ty::ty_param(ref p) => p.name == ty_param.ident.name,
+                _ => false,
+            };
+
+            let mut behind_raw_ptr = false;
+            let mut elsewhere = false;
+            for &field_ty in &field_tys {
+                let mut walker = field_ty.walk();
+                while let Some(t) = walker.next() {
+                    match t.sty {
+                        ty::ty_ptr(..) => {
+                            if t.walk().any(|t| is_param(t)) {
+                                behind_raw_ptr = true;
+                            }
+                            walker.skip_current_subtree();
+                        }
+                        _ if is_param(t) => elsewhere = true,
+                        _ => {}
+                    }
+                }
+            }
+
+            if behind_raw_ptr && !elsewhere {
+                let name = token::get_ident(ty_param.ident);
+                cx.span_lint(RAW_POINTER_ONLY_PARAMS,
+                             ty_param.span,
+                             &format!("type parameter `{}` is only used behind raw pointers \
+                                       in a type with a destructor; if this type owns values \
+                                       of type `{}`, add a `PhantomData<{}>` field so that \
+                                       drop-checking accounts for them",
+                                      name, name, name));
+            }
+        }
+    }
+}
+
 declare_lint! {
     MISSING_DEBUG_IMPLEMENTATIONS,
     Allow,
@@ -1685,8 +2238,10 @@ impl LintPass for MissingDebugImplementations {
     fn get_lints(&self) -> LintArray {
         lint_array!(MISSING_DEBUG_IMPLEMENTATIONS)
     }
+}
 
-    fn check_item(&mut self, cx: &Context, item: &ast::Item) {
+impl LateLintPass for MissingDebugImplementations {
+    fn check_item(&mut self, cx: &LateContext, item: &ast::Item) {
         if !cx.exported_items.contains(&item.id) {
             return;
         }
@@ -1726,6 +2281,213 @@ impl LintPass for MissingDebugImplementations {
     }
 }
 
+declare_lint! {
+    pub DEPENDENCY_TYPES_IN_PUBLIC,
+    Allow,
+    "types and traits from dependencies in exported signatures that this crate does not \
+     reexport"
+}
+
+/// Reports the types and traits of other crates that appear in this crate's
+/// public API without being reachable through it: downstream users then get
+/// errors mentioning a crate they have never heard of. Types of the standard
+/// library and the crates it is built from are exempt.
+pub struct DependencyTypesInPublic {
+    // The crates whose types may appear freely.
+    exempt_crates: HashSet<ast::CrateNum>,
+    // The items, and the modules, that this crate reexports with `pub use`.
+    reexported_items: DefIdSet,
+    reexported_modules: Vec<ast::DefId>,
+}
+
+impl DependencyTypesInPublic {
+    pub fn new() -> DependencyTypesInPublic {
+        DependencyTypesInPublic {
+            exempt_crates: HashSet::new(),
+            reexported_items: DefIdSet(),
+            reexported_modules: Vec::new(),
+        }
+    }
+
+    fn is_reexported(&self, cx: &LateContext, did: ast::DefId) -> bool {
+        if self.reexported_items.contains(&did) {
+            return true;
+        }
+        let path: Vec<_> = csearch::get_item_path(cx.tcx, did).iter().map(|e| e.name()).collect();
+        self.reexported_modules.iter().any(|module| {
+            module.krate == did.krate && (module.node == ast::CRATE_NODE_ID || {
+                let module_path = csearch::get_item_path(cx.tcx, *module);
+                module_path.len() < path.len() &&
+                    module_path.iter().zip(path.iter()).all(|(elem, &name)| elem.name() == name)
+            })
+        })
+    }
+
+    fn check_type<'tcx>(&self, cx: &LateContext, span: Span, ty: Ty<'tcx>) {
+        let mut reported = DefIdSet();
+        for t in ty.walk() {
+            let did = match t.sty {
+                ty::ty_struct(did, _) | ty::ty_enum(did, _) => did,
+                ty::ty_trait(ref data) => data.principal_def_id(),
+                _ => continue,
+            };
+            if did.krate == ast::LOCAL_CRATE || self.exempt_crates.contains(&did.krate) ||
+               !reported.insert(did) || self.is_reexported(cx, did) {
+                continue;
+            }
+
+            let path = ty::item_path_str(cx.tcx, did);
+            let krate = cx.sess().cstore.get_crate_data(did.krate).name.clone();
+            cx.span_lint(DEPENDENCY_TYPES_IN_PUBLIC, span,
+                         &format!("`{}` from the crate `{}` appears in a public signature, \
+                                   but this crate does not reexport it", path, krate));
+            if cx.current_level(DEPENDENCY_TYPES_IN_PUBLIC) != Level::Allow {
+                cx.sess().fileline_help(span, &format!("add `pub use {};`, or wrap it in a \
+                                                        type of this crate", path));
+            }
+        }
+    }
+
+    fn check_fn_sig<'tcx>(&self, cx: &LateContext, span: Span, sig: &ty::FnSig<'tcx>) {
+        for &input in &sig.inputs {
+            self.check_type(cx, span, input);
+        }
+        if let ty::FnConverging(output) = sig.output {
+            self.check_type(cx, span, output);
+        }
+    }
+
+    fn check_method(&self, cx: &LateContext, span: Span, id: ast::NodeId) {
+        if let ty::MethodTraitItem(method) = ty::impl_or_trait_item(cx.tcx, local_def(id)) {
+            // The methods of a trait impl are the trait's signatures, not
+            // this crate's.
+            if let ty::ImplContainer(impl_did) = method.container {
+                if ty::impl_trait_ref(cx.tcx, impl_did).is_some() {
+                    return;
+                }
+            }
+            self.check_fn_sig(cx, span, &method.fty.sig.0);
+        }
+    }
+}
+
+impl LintPass for DependencyTypesInPublic {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(DEPENDENCY_TYPES_IN_PUBLIC)
+    }
+}
+
+struct ReexportCollector<'a, 'tcx: 'a> {
+    tcx: &'a ty::ctxt<'tcx>,
+    items: DefIdSet,
+    modules: Vec<ast::DefId>,
+}
+
+impl<'a, 'tcx> ReexportCollector<'a, 'tcx> {
+    fn add(&mut self, id: ast::NodeId) {
+        let def = match self.tcx.def_map.borrow().get(&id) {
+            Some(resolution) => resolution.base_def,
+            None => return,
+        };
+        // Reexporting a module, or the variants of an enum, also reexports
+        // the items in it.
+        match def {
+            def::DefMod(did) | def::DefTy(did, true) => self.modules.push(did),
+            _ => {}
+        }
+        self.items.insert(def.def_id());
+    }
+}
+
+impl<'a, 'tcx, 'v> Visitor<'v> for ReexportCollector<'a, 'tcx> {
+    fn visit_item(&mut self, it: &ast::Item) {
+        if let ast::ItemUse(ref view_path) = it.node {
+            if it.vis == ast::Public {
+                match view_path.node {
+                    ast::ViewPathSimple(..) | ast::ViewPathGlob(..) => self.add(it.id),
+                    ast::ViewPathList(_, ref list) => {
+                        for path_item in list {
+                            self.add(path_item.node.id());
+                        }
+                    }
+                }
+            }
+        }
+        visit::walk_item(self, it);
+    }
+}
+
+impl LateLintPass for DependencyTypesInPublic {
+    fn check_crate(&mut self, cx: &LateContext, krate: &ast::Crate) {
+        // std, and every crate it is built from, directly or not.
+        let cstore = &cx.sess().cstore;
+        let mut roots = Vec::new();
+        cstore.iter_crate_data(|cnum, data| {
+            if data.name == "std" || data.name == "core" {
+                roots.push(cnum);
+            }
+        });
+        while let Some(cnum) = roots.pop() {
+            if self.exempt_crates.insert(cnum) {
+                roots.extend(cstore.get_crate_data(cnum).cnum_map.borrow().values().cloned());
+            }
+        }
+
+        let mut collector = ReexportCollector {
+            tcx: cx.tcx,
+            items: DefIdSet(),
+            modules: Vec::new(),
+        };
+        visit::walk_crate(&mut collector, krate);
+        self.reexported_items = collector.items;
+        self.reexported_modules = collector.modules;
+    }
+
+    fn check_item(&mut self, cx: &LateContext, it: &ast::Item) {
+        if !cx.exported_items.contains(&it.id) {
+            return;
+        }
+
+        match it.node {
+            ast::ItemFn(..) | ast::ItemStatic(..) | ast::ItemConst(..) | ast::ItemTy(..) => {
+                let ty = ty::lookup_item_type(cx.tcx, local_def(it.id)).ty;
+                self.check_type(cx, it.span, ty);
+            }
+            ast::ItemStruct(ref def, _) => {
+                for field in &def.fields {
+                    let public = match field.node.kind {
+                        ast::NamedField(_, vis) | ast::UnnamedField(vis) => vis == ast::Public,
+                    };
+                    if public {
+                        let ty = ty::node_id_to_type(cx.tcx, field.node.id);
+                        self.check_type(cx, field.span, ty);
+                    }
+                }
+            }
+            ast::ItemEnum(..) => {
+                for variant in &*ty::enum_variants(cx.tcx, local_def(it.id)) {
+                    for &arg in &variant.args {
+                        self.check_type(cx, it.span, arg);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn check_trait_item(&mut self, cx: &LateContext, trait_item: &ast::TraitItem) {
+        if cx.exported_items.contains(&trait_item.id) {
+            self.check_method(cx, trait_item.span, trait_item.id);
+        }
+    }
+
+    fn check_impl_item(&mut self, cx: &LateContext, impl_item: &ast::ImplItem) {
+        if cx.exported_items.contains(&impl_item.id) {
+            self.check_method(cx, impl_item.span, impl_item.id);
+        }
+    }
+}
+
 declare_lint! {
     DEPRECATED,
     Warn,
@@ -1737,17 +2499,32 @@ declare_lint! {
 pub struct Stability;
 
 impl Stability {
-    fn lint(&self, cx: &Context, _id: ast::DefId, span: Span, stability: &Option<attr::Stability>) {
+    fn lint(&self, cx: &LateContext, _id: ast::DefId, span: Span,
+            stability: &Option<attr::Stability>, deprecation: &Option<attr::Deprecation>) {
         // Deprecated attributes apply in-crate and cross-crate.
         let (lint, label) = match *stability {
             Some(attr::Stability { deprecated_since: Some(_), .. }) =>
                 (DEPRECATED, "deprecated"),
-            _ => return
+            _ => {
+                // Standalone `#[deprecated]` outside of the staged
+                // stability system.
+                if let Some(ref depr) = *deprecation {
+                    let mut msg = match depr.note {
+                        Some(ref note) => format!("use of deprecated item: {}", *note),
+                        None => "use of deprecated item".to_string(),
+                    };
+                    if let Some(ref since) = depr.since {
+                        msg.push_str(&format!(" (deprecated since {})", *since));
+                    }
+                    cx.span_lint(DEPRECATED, span, &msg[..]);
+                }
+                return
+            }
         };
 
         output(cx, span, stability, lint, label);
 
-        fn output(cx: &Context, span: Span, stability: &Option<attr::Stability>,
+        fn output(cx: &LateContext, span: Span, stability: &Option<attr::Stability>,
                   lint: &'static Lint, label: &'static str) {
             let msg = match *stability {
                 Some(attr::Stability { reason: Some(ref s), .. }) => {
@@ -1765,25 +2542,27 @@ impl LintPass for Stability {
     fn get_lints(&self) -> LintArray {
         lint_array!(DEPRECATED)
     }
+}
 
-    fn check_item(&mut self, cx: &Context, item: &ast::Item) {
+impl LateLintPass for Stability {
+    fn check_item(&mut self, cx: &LateContext, item: &ast::Item) {
         stability::check_item(cx.tcx, item, false,
-                              &mut |id, sp, stab| self.lint(cx, id, sp, stab));
+                              &mut |id, sp, stab, depr| self.lint(cx, id, sp, stab, depr));
     }
 
-    fn check_expr(&mut self, cx: &Context, e: &ast::Expr) {
+    fn check_expr(&mut self, cx: &LateContext, e: &ast::Expr) {
         stability::check_expr(cx.tcx, e,
-                              &mut |id, sp, stab| self.lint(cx, id, sp, stab));
+                              &mut |id, sp, stab, depr| self.lint(cx, id, sp, stab, depr));
     }
 
-    fn check_path(&mut self, cx: &Context, path: &ast::Path, id: ast::NodeId) {
+    fn check_path(&mut self, cx: &LateContext, path: &ast::Path, id: ast::NodeId) {
         stability::check_path(cx.tcx, path, id,
-                              &mut |id, sp, stab| self.lint(cx, id, sp, stab));
+                              &mut |id, sp, stab, depr| self.lint(cx, id, sp, stab, depr));
     }
 
-    fn check_pat(&mut self, cx: &Context, pat: &ast::Pat) {
+    fn check_pat(&mut self, cx: &LateContext, pat: &ast::Pat) {
         stability::check_pat(cx.tcx, pat,
-                             &mut |id, sp, stab| self.lint(cx, id, sp, stab))
+                             &mut |id, sp, stab, depr| self.lint(cx, id, sp, stab, depr))
     }
 }
 
@@ -1801,8 +2580,10 @@ impl LintPass for UnconditionalRecursion {
     fn get_lints(&self) -> LintArray {
         lint_array![UNCONDITIONAL_RECURSION]
     }
+}
 
-    fn check_fn(&mut self, cx: &Context, fn_kind: visit::FnKind, _: &ast::FnDecl,
+impl LateLintPass for UnconditionalRecursion {
+    fn check_fn(&mut self, cx: &LateContext, fn_kind: visit::FnKind, _: &ast::FnDecl,
                 blk: &ast::Block, sp: Span, id: ast::NodeId) {
         type F = for<'tcx> fn(&ty::ctxt<'tcx>,
                               ast::NodeId, ast::NodeId, ast::Ident, ast::NodeId) -> bool;
@@ -1988,8 +2769,10 @@ impl LintPass for PluginAsLibrary {
     fn get_lints(&self) -> LintArray {
         lint_array![PLUGIN_AS_LIBRARY]
     }
+}
 
-    fn check_item(&mut self, cx: &Context, it: &ast::Item) {
+impl LateLintPass for PluginAsLibrary {
+    fn check_item(&mut self, cx: &LateContext, it: &ast::Item) {
         if cx.sess().plugin_registrar_fn.get().is_some() {
             // We're compiling a plugin; it's fine to link other plugins.
             return;
@@ -2044,8 +2827,10 @@ impl LintPass for InvalidNoMangleItems {
                     PRIVATE_NO_MANGLE_STATICS,
                     NO_MANGLE_CONST_ITEMS)
     }
+}
 
-    fn check_item(&mut self, cx: &Context, it: &ast::Item) {
+impl LateLintPass for InvalidNoMangleItems {
+    fn check_item(&mut self, cx: &LateContext, it: &ast::Item) {
         match it.node {
             ast::ItemFn(..) => {
                 if attr::contains_name(&it.attrs, "no_mangle") &&
@@ -2077,6 +2862,253 @@ impl LintPass for InvalidNoMangleItems {
     }
 }
 
+// This is allowed by default: the inline hint still feeds into LLVM's
+// inlining heuristics for each instantiation, and the standard library
+// relies on that all over the place.
+declare_lint! {
+    REDUNDANT_INLINE,
+    Allow,
+    "`#[inline]` on exported generic functions, which are always available \
+     to other crates"
+}
+
+#[derive(Copy)]
+pub struct RedundantInline;
+
+impl LintPass for RedundantInline {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(REDUNDANT_INLINE)
+    }
+}
+
+impl LateLintPass for RedundantInline {
+    fn check_item(&mut self, cx: &LateContext, it: &ast::Item) {
+        let generics = match it.node {
+            ast::ItemFn(_, _, _, ref generics, _) => generics,
+            _ => return
+        };
+        if !generics.is_type_parameterized() || !cx.exported_items.contains(&it.id) {
+            return;
+        }
+        if attr::find_inline_attr(None, &it.attrs) == attr::InlineAttr::Hint {
+            let msg = format!("`#[inline]` is redundant on generic function `{}`, which is \
+                               always made available to other crates",
+                              it.ident);
+            cx.span_lint(REDUNDANT_INLINE, it.span, &msg);
+        }
+    }
+}
+
+/// Whether `e` is a method call `x.clone()` that resolves to `Clone::clone`.
+fn is_clone_method_call(cx: &LateContext, e: &ast::Expr) -> bool {
+    match e.node {
+        ast::ExprMethodCall(ref ident, _, ref args) if args.len() == 1 => {
+            if &token::get_ident(ident.node)[..] != "clone" {
+                return false;
+            }
+        }
+        _ => return false
+    }
+    let trait_did = match cx.tcx.method_map.borrow().get(&ty::MethodCall::expr(e.id)) {
+        None => return false,
+        Some(m) => match m.origin {
+            ty::MethodStatic(did) | ty::MethodStaticClosure(did) => {
+                ty::trait_of_item(cx.tcx, did)
+            }
+            ty::MethodTypeParam(ref param) => Some(param.trait_ref.def_id),
+            ty::MethodTraitObject(ref object) => Some(object.trait_ref.def_id),
+        }
+    };
+    trait_did.map_or(false, |did| ty::item_path_str(cx.tcx, did) == "core::clone::Clone")
+}
+
+declare_lint! {
+    pub CLONE_ON_COPY,
+    Warn,
+    "calling `clone` on a value whose type implements `Copy`"
+}
+
+#[derive(Copy)]
+pub struct CloneOnCopy;
+
+impl LintPass for CloneOnCopy {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(CLONE_ON_COPY)
+    }
+}
+
+impl LateLintPass for CloneOnCopy {
+    fn check_expr(&mut self, cx: &LateContext, e: &ast::Expr) {
+        if !is_clone_method_call(cx, e) {
+            return;
+        }
+        // Whether a type parameter is `Copy` depends on the bounds in scope;
+        // only concrete types are checked.
+        let ty = ty::expr_ty(cx.tcx, e);
+        if ty::type_has_params(ty) || ty::type_has_self(ty) {
+            return;
+        }
+        let param_env = ty::empty_parameter_environment(cx.tcx);
+        if ty::type_moves_by_default(&param_env, e.span, ty) {
+            return;
+        }
+        let msg = format!("using `clone` on `{}`, which implements `Copy`",
+                          ty_to_string(cx.tcx, ty));
+        cx.span_lint(CLONE_ON_COPY, e.span, &msg);
+        if cx.current_level(CLONE_ON_COPY) != Level::Allow {
+            let receiver = match e.node {
+                ast::ExprMethodCall(_, _, ref args) => &*args[0],
+                _ => return
+            };
+            if ty::expr_ty(cx.tcx, receiver) == ty {
+                cx.sess().span_help(e.span, "remove the call to `clone`; the value is \
+                                             copied anyway");
+            } else {
+                cx.sess().span_help(e.span, "dereference the receiver instead of \
+                                             calling `clone`");
+            }
+        }
+    }
+}
+
+declare_lint! {
+    pub REDUNDANT_CLONE,
+    Allow,
+    "calling `clone` on a local variable that is not used afterwards"
+}
+
+pub struct RedundantClone {
+    /// The borrows of each local variable found in the functions checked so
+    /// far: the node doing the borrowing and the region of the loan.
+    borrows: NodeMap<Vec<(ast::NodeId, ty::Region)>>,
+}
+
+impl RedundantClone {
+    pub fn new() -> RedundantClone {
+        RedundantClone { borrows: NodeMap() }
+    }
+}
+
+impl LintPass for RedundantClone {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(REDUNDANT_CLONE)
+    }
+}
+
+impl LateLintPass for RedundantClone {
+    fn check_fn(&mut self, cx: &LateContext,
+                _: visit::FnKind, decl: &ast::FnDecl,
+                body: &ast::Block, _: Span, id: ast::NodeId) {
+        if cx.current_level(REDUNDANT_CLONE) == Level::Allow {
+            return;
+        }
+        // Closures get a `check_fn` of their own: walking a function does not
+        // look inside the closures it contains.
+        let param_env = ty::ParameterEnvironment::for_item(cx.tcx, id);
+        let mut collector = LocalBorrows { borrows: &mut self.borrows };
+        let mut euv = euv::ExprUseVisitor::new(&mut collector, &param_env);
+        euv.walk_fn(decl, body);
+    }
+
+    fn check_expr(&mut self, cx: &LateContext, e: &ast::Expr) {
+        if !cx.tcx.dead_clone_receivers.borrow().contains(&e.id) ||
+           !is_clone_method_call(cx, e) {
+            return;
+        }
+        let receiver = match e.node {
+            ast::ExprMethodCall(_, _, ref args) => &*args[0],
+            _ => return
+        };
+        // A receiver behind a reference cannot be moved out of: the clone
+        // is the only way to get an owned value.
+        let ty = ty::expr_ty(cx.tcx, e);
+        if ty::expr_ty(cx.tcx, receiver) != ty {
+            return;
+        }
+        // `clone_on_copy` already covers these.
+        if !ty::type_has_params(ty) && !ty::type_has_self(ty) {
+            let param_env = ty::empty_parameter_environment(cx.tcx);
+            if !ty::type_moves_by_default(&param_env, e.span, ty) {
+                return;
+            }
+        }
+        let var = match cx.tcx.def_map.borrow().get(&receiver.id).map(|d| d.full_def()) {
+            Some(DefLocal(var)) => var,
+            _ => return
+        };
+
+        // Liveness only knows that nothing reads the variable again; a loan
+        // taken out earlier may still be alive here, in which case the
+        // variable cannot be moved. The clone's own autoref is not one.
+        let clone_extent = region::CodeExtent::from_node_id(e.id);
+        let borrowed = self.borrows.get(&var).map_or(false, |borrows| {
+            borrows.iter().any(|&(borrow_id, region)| {
+                borrow_id != receiver.id && match region {
+                    ty::ReScope(scope) => cx.tcx.region_maps.is_subscope_of(clone_extent,
+                                                                            scope),
+                    _ => true
+                }
+            })
+        });
+        if borrowed {
+            return;
+        }
+
+        let name = pprust::expr_to_string(receiver);
+        cx.span_lint(REDUNDANT_CLONE, e.span,
+                     &format!("redundant clone: `{}` is not used after it is cloned", name));
+        if cx.current_level(REDUNDANT_CLONE) != Level::Allow {
+            cx.sess().span_help(e.span,
+                                &format!("remove the call to `clone` and move `{}` instead",
+                                         name));
+        }
+    }
+}
+
+struct LocalBorrows<'a> {
+    borrows: &'a mut NodeMap<Vec<(ast::NodeId, ty::Region)>>,
+}
+
+impl<'a> LocalBorrows<'a> {
+    /// The local variable that borrowing `cmt` keeps borrowed, if any.
+    fn borrowed_local(cmt: &mc::cmt) -> Option<ast::NodeId> {
+        match cmt.cat {
+            mc::cat_local(id) => Some(id),
+            mc::cat_deref(ref base, _, _) |
+            mc::cat_interior(ref base, _) |
+            mc::cat_downcast(ref base, _) => LocalBorrows::borrowed_local(base),
+            mc::cat_rvalue(..) | mc::cat_static_item | mc::cat_upvar(..) => None,
+        }
+    }
+}
+
+impl<'a, 'tcx> euv::Delegate<'tcx> for LocalBorrows<'a> {
+    fn consume(&mut self, _: ast::NodeId, _: Span, _: mc::cmt, _: euv::ConsumeMode) {}
+
+    fn matched_pat(&mut self, _: &ast::Pat, _: mc::cmt, _: euv::MatchMode) {}
+
+    fn consume_pat(&mut self, _: &ast::Pat, _: mc::cmt, _: euv::ConsumeMode) {}
+
+    fn borrow(&mut self,
+              borrow_id: ast::NodeId,
+              _: Span,
+              cmt: mc::cmt,
+              loan_region: ty::Region,
+              _: ty::BorrowKind,
+              _: euv::LoanCause) {
+        if let Some(var) = LocalBorrows::borrowed_local(&cmt) {
+            match self.borrows.entry(var) {
+                Occupied(entry) => entry.into_mut().push((borrow_id, loan_region)),
+                Vacant(entry) => { entry.insert(vec![(borrow_id, loan_region)]); }
+            }
+        }
+    }
+
+    fn decl_without_init(&mut self, _: ast::NodeId, _: Span) {}
+
+    fn mutate(&mut self, _: ast::NodeId, _: Span, _: mc::cmt, _: euv::MutateMode) {}
+}
+
 /// Forbids using the `#[feature(...)]` attribute
 #[derive(Copy)]
 pub struct UnstableFeatures;
@@ -2091,7 +3123,10 @@ impl LintPass for UnstableFeatures {
     fn get_lints(&self) -> LintArray {
         lint_array!(UNSTABLE_FEATURES)
     }
-    fn check_attribute(&mut self, ctx: &Context, attr: &ast::Attribute) {
+}
+
+impl EarlyLintPass for UnstableFeatures {
+    fn check_attribute(&mut self, ctx: &EarlyContext, attr: &ast::Attribute) {
         if attr::contains_name(&[attr.node.value.clone()], "feature") {
             ctx.span_lint(UNSTABLE_FEATURES, attr.span, "unstable feature");
         }
@@ -30,7 +30,6 @@
       html_favicon_url = "http://www.rust-lang.org/favicon.ico",
       html_root_url = "http://doc.rust-lang.org/nightly/")]
 
-#![feature(box_patterns)]
 #![feature(box_syntax)]
 #![feature(collections)]
 #![feature(core)]
@@ -38,7 +37,6 @@
 #![feature(quote)]
 #![feature(rustc_diagnostic_macros)]
 #![feature(rustc_private)]
-#![feature(unsafe_destructor)]
 #![feature(staged_api)]
 #![feature(str_char)]
 #![cfg_attr(test, feature(test))]
@@ -56,7 +54,7 @@ pub use rustc::session as session;
 pub use rustc::util as util;
 
 use session::Session;
-use lint::{LintPassObject, LintId};
+use lint::{EarlyLintPassObject, LateLintPassObject, LintId};
 
 mod builtin;
 
@@ -64,18 +62,36 @@ mod builtin;
 /// defined in this crate and the ones defined in
 /// `rustc::lint::builtin`).
 pub fn register_builtins(store: &mut lint::LintStore, sess: Option<&Session>) {
-    macro_rules! add_builtin {
+    macro_rules! add_early_builtin {
         ($sess:ident, $($name:ident),*,) => (
             {$(
-                store.register_pass($sess, false, box builtin::$name as LintPassObject);
+                store.register_early_pass($sess, false, box builtin::$name as EarlyLintPassObject);
                 )*}
             )
     }
 
-    macro_rules! add_builtin_with_new {
+    macro_rules! add_early_builtin_with_new {
         ($sess:ident, $($name:ident),*,) => (
             {$(
-                store.register_pass($sess, false, box builtin::$name::new() as LintPassObject);
+                store.register_early_pass($sess, false,
+                                          box builtin::$name::new() as EarlyLintPassObject);
+                )*}
+            )
+    }
+
+    macro_rules! add_late_builtin {
+        ($sess:ident, $($name:ident),*,) => (
+            {$(
+                store.register_late_pass($sess, false, box builtin::$name as LateLintPassObject);
+                )*}
+            )
+    }
+
+    macro_rules! add_late_builtin_with_new {
+        ($sess:ident, $($name:ident),*,) => (
+            {$(
+                store.register_late_pass($sess, false,
+                                         box builtin::$name::new() as LateLintPassObject);
                 )*}
             )
     }
@@ -86,39 +102,55 @@ pub fn register_builtins(store: &mut lint::LintStore, sess: Option<&Session>) {
             )
     }
 
-    add_builtin!(sess,
-                 HardwiredLints,
-                 WhileTrue,
-                 UnusedCasts,
-                 ImproperCTypes,
-                 BoxPointers,
-                 UnusedAttributes,
-                 PathStatements,
-                 UnusedResults,
-                 NonCamelCaseTypes,
-                 NonSnakeCase,
-                 NonUpperCaseGlobals,
-                 UnusedParens,
-                 UnusedImportBraces,
-                 NonShorthandFieldPatterns,
-                 UnusedUnsafe,
-                 UnsafeCode,
-                 UnusedMut,
-                 UnusedAllocation,
-                 MissingCopyImplementations,
-                 UnstableFeatures,
-                 Stability,
-                 UnconditionalRecursion,
-                 InvalidNoMangleItems,
-                 PluginAsLibrary,
-                 );
-
-    add_builtin_with_new!(sess,
-                          TypeLimits,
-                          RawPointerDerive,
-                          MissingDoc,
-                          MissingDebugImplementations,
-                          );
+    add_early_builtin!(sess,
+                       UnusedImportBraces,
+                       UnsafeCode,
+                       UnstableFeatures,
+                       LargeFnBodies,
+                       );
+
+    add_early_builtin_with_new!(sess,
+                                NonSnakeCase,
+                                );
+
+    add_late_builtin!(sess,
+                      HardwiredLints,
+                      WhileTrue,
+                      TrivialCasts,
+                      UnpredictablePointerComparisons,
+                      ImproperCTypes,
+                      BoxPointers,
+                      UnusedAttributes,
+                      PathStatements,
+                      UnusedResults,
+                      NonCamelCaseTypes,
+                      NonSnakeCaseBindings,
+                      NonUpperCaseGlobals,
+                      UnusedParens,
+                      NonShorthandFieldPatterns,
+                      UnusedUnsafe,
+                      UnsafeOpInUnsafeFn,
+                      UnusedUnsafeFn,
+                      UnusedAllocation,
+                      MissingCopyImplementations,
+                      RawPointerOnlyParams,
+                      Stability,
+                      UnconditionalRecursion,
+                      InvalidNoMangleItems,
+                      RedundantInline,
+                      PluginAsLibrary,
+                      CloneOnCopy,
+                      );
+
+    add_late_builtin_with_new!(sess,
+                               TypeLimits,
+                               RawPointerDerive,
+                               MissingDoc,
+                               MissingDebugImplementations,
+                               DependencyTypesInPublic,
+                               UnusedMut,
+                               RedundantClone,
+                               );
 
     add_lint_group!(sess, "bad_style",
                     NON_CAMEL_CASE_TYPES, NON_SNAKE_CASE, NON_UPPER_CASE_GLOBALS);
@@ -128,11 +160,17 @@ pub fn register_builtins(store: &mut lint::LintStore, sess: Option<&Session>) {
                     UNUSED_MUT, UNREACHABLE_CODE, UNUSED_MUST_USE,
                     UNUSED_UNSAFE, PATH_STATEMENTS);
 
+    // The trivial cast lints replace `unused_typecasts`.
+    add_lint_group!(sess, "unused_typecasts",
+                    TRIVIAL_CASTS, TRIVIAL_NUMERIC_CASTS);
+
     // We have one lint pass defined specially
-    store.register_pass(sess, false, box lint::GatherNodeLevels as LintPassObject);
+    store.register_late_pass(sess, false, box lint::GatherNodeLevels as LateLintPassObject);
 
     // Insert temporary renamings for a one-time deprecation
     store.register_renamed("raw_pointer_deriving", "raw_pointer_derive");
 
     store.register_renamed("unknown_features", "unused_features");
+
+    store.register_renamed("visible_private_types", "private_in_public");
 }
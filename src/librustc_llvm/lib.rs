@@ -44,6 +44,7 @@ pub use self::TypeKind::*;
 pub use self::AtomicBinOp::*;
 pub use self::AtomicOrdering::*;
 pub use self::FileType::*;
+pub use self::VerifierFailureAction::*;
 pub use self::MetadataType::*;
 pub use self::AsmDialect::*;
 pub use self::CodeGenOptLevel::*;
@@ -369,6 +370,15 @@ pub enum FileType {
     ObjectFileType = 1
 }
 
+// Consts for the LLVMVerifierFailureAction type (in include/llvm-c/Analysis.h)
+#[repr(C)]
+#[derive(Copy)]
+pub enum VerifierFailureAction {
+    AbortProcessAction = 0,
+    PrintMessageAction = 1,
+    ReturnStatusAction = 2
+}
+
 #[derive(Copy)]
 pub enum MetadataType {
     MD_dbg = 0,
@@ -1538,6 +1548,15 @@ extern {
     /// Writes a module to the specified path. Returns 0 on success.
     pub fn LLVMWriteBitcodeToFile(M: ModuleRef, Path: *const c_char) -> c_int;
 
+    /// Checks a module for invalid IR. Returns true if the module is broken,
+    /// in which case `OutMessage` describes what is wrong with it and must
+    /// be freed with `LLVMDisposeMessage`.
+    pub fn LLVMVerifyModule(M: ModuleRef,
+                            Action: VerifierFailureAction,
+                            OutMessage: *mut *mut c_char)
+                            -> Bool;
+    pub fn LLVMDisposeMessage(Message: *mut c_char);
+
     /// Creates target data from a target layout string.
     pub fn LLVMCreateTargetData(StringRep: *const c_char) -> TargetDataRef;
     /// Adds the target data to the given pass manager. The pass manager
@@ -2104,6 +2123,12 @@ pub fn SetLinkage(global: ValueRef, link: Linkage) {
     }
 }
 
+pub fn SetVisibility(global: ValueRef, vis: Visibility) {
+    unsafe {
+        LLVMSetVisibility(global, vis as c_uint);
+    }
+}
+
 pub fn SetUnnamedAddr(global: ValueRef, unnamed: bool) {
     unsafe {
         LLVMSetUnnamedAddr(global, unnamed as Bool);
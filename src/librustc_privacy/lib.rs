@@ -34,12 +34,14 @@ use self::FieldName::*;
 
 use std::mem::replace;
 
+use rustc::lint;
 use rustc::metadata::csearch;
 use rustc::middle::def;
 use rustc::middle::privacy::ImportUse::*;
 use rustc::middle::privacy::LastPrivate::*;
 use rustc::middle::privacy::PrivateDep::*;
 use rustc::middle::privacy::{ExternalExports, ExportedItems, PublicItems};
+use rustc::middle::subst;
 use rustc::middle::ty::{MethodTypeParam, MethodStatic};
 use rustc::middle::ty::{MethodCall, MethodMap, MethodOrigin, MethodParam};
 use rustc::middle::ty::{MethodStaticClosure, MethodObject};
@@ -496,7 +498,7 @@ impl<'a, 'tcx> PrivacyVisitor<'a, 'tcx> {
                                           .get_parent_did(closest_private_id);
                             match ty::impl_trait_ref(self.tcx, imp) {
                                 Some(..) => return Allowable,
-                                _ if ii.vis == ast::Public => {
+                                _ if ii.vis != ast::Inherited => {
                                     return Allowable
                                 }
                                 _ => ii.vis
@@ -521,7 +523,8 @@ impl<'a, 'tcx> PrivacyVisitor<'a, 'tcx> {
                 }
                 _ => ast::Public,
             };
-            if vis != ast::Public { break }
+            // Crate-visible items are as good as public within this crate.
+            if vis == ast::Inherited { break }
             // if we've reached the root, then everything was allowable and this
             // access is public.
             if closest_private_id == ast::CRATE_NODE_ID { return Allowable }
@@ -594,6 +597,11 @@ impl<'a, 'tcx> PrivacyVisitor<'a, 'tcx> {
                      source_did: Option<ast::DefId>, msg: &str) -> CheckResult {
         let id = match self.def_privacy(to_check) {
             ExternallyDenied => {
+                if csearch::get_item_visibility(&self.tcx.sess.cstore, to_check) == ast::PubCrate {
+                    let note = "items declared `pub(crate)` can only be used within the crate \
+                                that defines them".to_string();
+                    return Some((span, format!("{} is crate-visible", msg), Some((span, note))))
+                }
                 return Some((span, format!("{} is private", msg), None))
             }
             Allowable => return None,
@@ -661,8 +669,7 @@ impl<'a, 'tcx> PrivacyVisitor<'a, 'tcx> {
             }
             UnnamedField(idx) => &fields[idx]
         };
-        if field.vis == ast::Public ||
-            (is_local(field.id) && self.private_accessible(field.id.node)) {
+        if self.field_accessible(field) {
             return
         }
 
@@ -683,6 +690,67 @@ impl<'a, 'tcx> PrivacyVisitor<'a, 'tcx> {
         self.tcx.sess.span_err(span, &msg[..]);
     }
 
+    fn field_accessible(&self, field: &ty::field_ty) -> bool {
+        field.vis == ast::Public ||
+            (is_local(field.id) && (field.vis == ast::PubCrate ||
+                                    self.private_accessible(field.id.node)))
+    }
+
+    // Checks that a struct expression only sets fields that are visible here,
+    // reporting all of the private ones together. With functional record
+    // update, private fields may still be taken from the base expression as
+    // long as doing so moves at least one of them out of it: the base then
+    // cannot be used as a whole afterwards, so its private state is not
+    // duplicated.
+    fn check_struct_expr_fields(&mut self,
+                                span: Span,
+                                struct_id: ast::DefId,
+                                substs: &subst::Substs<'tcx>,
+                                fields: &[ast::Field],
+                                has_base: bool) {
+        let private_fields: Vec<_> =
+            ty::lookup_struct_fields(self.tcx, struct_id).into_iter()
+                                                         .filter(|f| !self.field_accessible(f))
+                                                         .collect();
+        if private_fields.is_empty() {
+            return
+        }
+
+        let all_from_base = has_base && private_fields.iter().all(|f| {
+            !fields.iter().any(|field| field.ident.node.name == f.name)
+        });
+        if all_from_base {
+            let param_env = ty::empty_parameter_environment(self.tcx);
+            let moves_one = private_fields.iter().any(|f| {
+                let field_ty = ty::lookup_field_type(self.tcx, struct_id, f.id, substs);
+                // A type parameter may turn out to be `Copy`.
+                !ty::type_has_params(field_ty) &&
+                    ty::type_moves_by_default(&param_env, span, field_ty)
+            });
+            if moves_one {
+                return
+            }
+        }
+
+        let names: Vec<_> = private_fields.iter()
+                                          .map(|f| format!("`{}`", token::get_name(f.name)))
+                                          .collect();
+        let struct_desc = format!("struct `{}`", ty::item_path_str(self.tcx, struct_id));
+        let msg = if names.len() == 1 {
+            format!("field {} of {} is private", names[0], struct_desc)
+        } else {
+            format!("fields {} of {} are private", names.connect(", "), struct_desc)
+        };
+        self.tcx.sess.span_err(span, &msg[..]);
+        if all_from_base {
+            self.tcx.sess.span_note(span, "private fields can only be taken from the base \
+                                           expression if at least one of them is moved out \
+                                           of it, not just copied");
+        } else {
+            self.tcx.sess.span_note(span, "consider using a constructor function");
+        }
+    }
+
     // Given the ID of a method, checks to ensure it's in scope.
     fn check_static_method(&mut self,
                            span: Span,
@@ -868,17 +936,13 @@ impl<'a, 'tcx, 'v> Visitor<'v> for PrivacyVisitor<'a, 'tcx> {
                     }
                 }
             }
-            ast::ExprStruct(_, ref fields, _) => {
+            ast::ExprStruct(_, ref fields, ref base) => {
                 match ty::expr_ty(self.tcx, expr).sty {
-                    ty::ty_struct(ctor_id, _) => {
-                        // RFC 736: ensure all unmentioned fields are visible.
-                        // Rather than computing the set of unmentioned fields
-                        // (i.e. `all_fields - fields`), just check them all.
-                        let all_fields = ty::lookup_struct_fields(self.tcx, ctor_id);
-                        for field in all_fields {
-                            self.check_field(expr.span, ctor_id,
-                                             NamedField(field.name));
-                        }
+                    ty::ty_struct(ctor_id, substs) => {
+                        // RFC 736: ensure all unmentioned fields are visible,
+                        // unless they are moved out of the base expression.
+                        self.check_struct_expr_fields(expr.span, ctor_id, substs,
+                                                      &fields[..], base.is_some());
                     }
                     ty::ty_enum(_, _) => {
                         match self.tcx.def_map.borrow().get(&expr.id).unwrap().full_def() {
@@ -901,14 +965,12 @@ impl<'a, 'tcx, 'v> Visitor<'v> for PrivacyVisitor<'a, 'tcx> {
                 }
             }
             ast::ExprPath(..) => {
+                // A tuple struct constructor, whether called or used as a
+                // function value, is only accessible if all of the fields it
+                // initializes are.
                 let guard = |did: ast::DefId| {
                     let fields = ty::lookup_struct_fields(self.tcx, did);
-                    let any_priv = fields.iter().any(|f| {
-                        f.vis != ast::Public && (
-                            !is_local(f.id) ||
-                            !self.private_accessible(f.id.node))
-                    });
-                    if any_priv {
+                    if !fields.iter().all(|f| self.field_accessible(f)) {
                         self.tcx.sess.span_err(expr.span,
                             "cannot invoke tuple struct constructor \
                              with private fields");
@@ -1092,6 +1154,10 @@ impl<'a, 'tcx> SanePrivacyVisitor<'a, 'tcx> {
                                                            visibility");
                             }
                         }
+                        ast::PubCrate => {
+                            tcx.sess.span_err(v.span, "unnecessary `pub(crate)` \
+                                                       visibility");
+                        }
                         ast::Inherited => {}
                     }
                 }
@@ -1161,6 +1227,8 @@ struct VisiblePrivateTypesVisitor<'a, 'tcx: 'a> {
     exported_items: &'a ExportedItems,
     public_items: &'a PublicItems,
     in_variant: bool,
+    /// The exported item whose signature is currently being checked.
+    current_item: ast::NodeId,
 }
 
 struct CheckTypeForPrivatenessVisitor<'a, 'b: 'a, 'tcx: 'b> {
@@ -1175,26 +1243,75 @@ struct CheckTypeForPrivatenessVisitor<'a, 'b: 'a, 'tcx: 'b> {
 }
 
 impl<'a, 'tcx> VisiblePrivateTypesVisitor<'a, 'tcx> {
-    fn path_is_private_type(&self, path_id: ast::NodeId) -> bool {
+    /// Returns the definition of the private type or trait `path_id` refers
+    /// to, if it refers to one.
+    fn private_type_def(&self, path_id: ast::NodeId) -> Option<ast::DefId> {
         let did = match self.tcx.def_map.borrow().get(&path_id).map(|d| d.full_def()) {
             // `int` etc. (None doesn't seem to occur.)
-            None | Some(def::DefPrimTy(..)) => return false,
+            None | Some(def::DefPrimTy(..)) => return None,
             Some(def) => def.def_id()
         };
         // A path can only be private if:
         // it's in this crate...
         if !is_local(did) {
-            return false
+            return None
         }
 
         // .. and it corresponds to a private type in the AST (this returns
         // None for type parameters)
         match self.tcx.map.find(did.node) {
-            Some(ast_map::NodeItem(ref item)) => item.vis != ast::Public,
-            Some(_) | None => false,
+            Some(ast_map::NodeItem(ref item)) if item.vis != ast::Public => Some(did),
+            Some(_) | None => None,
+        }
+    }
+
+    fn path_is_private_type(&self, path_id: ast::NodeId) -> bool {
+        self.private_type_def(path_id).is_some()
+    }
+
+    /// Reports the private type or trait `did`, named at `span`, in the
+    /// signature of the current item. This goes through the
+    /// `private_in_public` lint unless `-Z private-in-public-errors` was
+    /// passed, in which case it is a hard error.
+    fn report_private_in_public(&self, span: Span, msg: &str, did: ast::DefId) {
+        if self.tcx.sess.features.borrow().visible_private_types {
+            return
+        }
+
+        let leaks_through = match self.tcx.map.find(self.current_item) {
+            Some(ast_map::NodeItem(&ast::Item { node: ast::ItemImpl(..), .. })) => {
+                "a public impl".to_string()
+            }
+            _ if self.exported_items.contains(&self.current_item) => {
+                format!("the exported item `{}`",
+                        self.tcx.map.path_to_string(self.current_item))
+            }
+            _ => format!("the public item `{}`",
+                         self.tcx.map.path_to_string(self.current_item)),
+        };
+        let note = format!("`{}` is declared here without `pub`, but is part of \
+                            the public API through {}",
+                           self.tcx.map.path_to_string(did.node), leaks_through);
+        let def_span = self.tcx.map.span(did.node);
+
+        if self.tcx.sess.opts.debugging_opts.private_in_public_errors {
+            self.tcx.sess.span_err(span, msg);
+            self.tcx.sess.span_note(def_span, &note[..]);
+        } else {
+            self.tcx.sess.add_lint_with_notes(lint::builtin::PRIVATE_IN_PUBLIC,
+                                              self.current_item,
+                                              span,
+                                              msg.to_string(),
+                                              vec![(def_span, note)]);
         }
     }
 
+    fn walk_public_impl_item(&mut self, impl_item: &ast::ImplItem) {
+        let old_item = replace(&mut self.current_item, impl_item.id);
+        visit::walk_impl_item(self, impl_item);
+        self.current_item = old_item;
+    }
+
     fn trait_is_public(&self, trait_id: ast::NodeId) -> bool {
         // FIXME: this would preferably be using `exported_items`, but all
         // traits are exported currently (see `EmbargoVisitor.exported_trait`)
@@ -1204,11 +1321,11 @@ impl<'a, 'tcx> VisiblePrivateTypesVisitor<'a, 'tcx> {
     fn check_ty_param_bound(&self,
                             ty_param_bound: &ast::TyParamBound) {
         if let ast::TraitTyParamBound(ref trait_ref, _) = *ty_param_bound {
-            if !self.tcx.sess.features.borrow().visible_private_types &&
-                self.path_is_private_type(trait_ref.trait_ref.ref_id) {
-                    let span = trait_ref.trait_ref.path.span;
-                    self.tcx.sess.span_err(span, "private trait in exported type \
-                                                  parameter bound");
+            if let Some(did) = self.private_type_def(trait_ref.trait_ref.ref_id) {
+                self.report_private_in_public(trait_ref.trait_ref.path.span,
+                                              "private trait in exported type \
+                                               parameter bound",
+                                              did);
             }
         }
     }
@@ -1238,8 +1355,8 @@ impl<'a, 'b, 'tcx, 'v> Visitor<'v> for CheckTypeForPrivatenessVisitor<'a, 'b, 't
     fn visit_expr(&mut self, _: &ast::Expr) {}
 }
 
-impl<'a, 'tcx, 'v> Visitor<'v> for VisiblePrivateTypesVisitor<'a, 'tcx> {
-    fn visit_item(&mut self, item: &ast::Item) {
+impl<'a, 'tcx> VisiblePrivateTypesVisitor<'a, 'tcx> {
+    fn check_item(&mut self, item: &ast::Item) {
         match item.node {
             // contents of a private mod can be reexported, so we need
             // to check internals.
@@ -1334,10 +1451,10 @@ impl<'a, 'tcx, 'v> Visitor<'v> for VisiblePrivateTypesVisitor<'a, 'tcx> {
                                     ast::MethodImplItem(..)
                                         if self.item_is_public(&impl_item.id, impl_item.vis) =>
                                     {
-                                        visit::walk_impl_item(self, impl_item)
+                                        self.walk_public_impl_item(impl_item)
                                     }
                                     ast::TypeImplItem(..) => {
-                                        visit::walk_impl_item(self, impl_item)
+                                        self.walk_public_impl_item(impl_item)
                                     }
                                     _ => {}
                                 }
@@ -1381,7 +1498,7 @@ impl<'a, 'tcx, 'v> Visitor<'v> for VisiblePrivateTypesVisitor<'a, 'tcx> {
                                 if sig.explicit_self.node == ast::SelfStatic &&
                                         self.item_is_public(&impl_item.id, impl_item.vis) {
                                     found_pub_static = true;
-                                    visit::walk_impl_item(self, impl_item);
+                                    self.walk_public_impl_item(impl_item);
                                 }
                             }
                             ast::TypeImplItem(_) |
@@ -1395,10 +1512,6 @@ impl<'a, 'tcx, 'v> Visitor<'v> for VisiblePrivateTypesVisitor<'a, 'tcx> {
                 return
             }
 
-            // `type ... = ...;` can contain private types, because
-            // we're introducing a new name.
-            ast::ItemTy(..) => return,
-
             // not at all public, so we don't care
             _ if !self.item_is_public(&item.id, item.vis) => {
                 return;
@@ -1414,6 +1527,14 @@ impl<'a, 'tcx, 'v> Visitor<'v> for VisiblePrivateTypesVisitor<'a, 'tcx> {
         debug!("VisiblePrivateTypesVisitor entering item {:?}", item);
         visit::walk_item(self, item);
     }
+}
+
+impl<'a, 'tcx, 'v> Visitor<'v> for VisiblePrivateTypesVisitor<'a, 'tcx> {
+    fn visit_item(&mut self, item: &ast::Item) {
+        let old_item = replace(&mut self.current_item, item.id);
+        self.check_item(item);
+        self.current_item = old_item;
+    }
 
     fn visit_generics(&mut self, generics: &ast::Generics) {
         for ty_param in &*generics.ty_params {
@@ -1438,16 +1559,19 @@ impl<'a, 'tcx, 'v> Visitor<'v> for VisiblePrivateTypesVisitor<'a, 'tcx> {
 
     fn visit_foreign_item(&mut self, item: &ast::ForeignItem) {
         if self.exported_items.contains(&item.id) {
-            visit::walk_foreign_item(self, item)
+            let old_item = replace(&mut self.current_item, item.id);
+            visit::walk_foreign_item(self, item);
+            self.current_item = old_item;
         }
     }
 
     fn visit_ty(&mut self, t: &ast::Ty) {
         debug!("VisiblePrivateTypesVisitor checking ty {:?}", t);
         if let ast::TyPath(_, ref p) = t.node {
-            if !self.tcx.sess.features.borrow().visible_private_types &&
-                self.path_is_private_type(t.id) {
-                self.tcx.sess.span_err(p.span, "private type in exported type signature");
+            if let Some(did) = self.private_type_def(t.id) {
+                self.report_private_in_public(p.span,
+                                              "private type in exported type signature",
+                                              did);
             }
         }
         visit::walk_ty(self, t)
@@ -1539,6 +1663,7 @@ pub fn check_crate(tcx: &ty::ctxt,
             exported_items: &exported_items,
             public_items: &public_items,
             in_variant: false,
+            current_item: ast::CRATE_NODE_ID,
         };
         visit::walk_crate(&mut visitor, krate);
     }
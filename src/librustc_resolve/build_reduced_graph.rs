@@ -261,7 +261,7 @@ impl<'a, 'b:'a, 'tcx:'b> GraphBuilder<'a, 'b, 'tcx> {
     fn build_reduced_graph_for_item(&mut self, item: &Item, parent: &Rc<Module>) -> Rc<Module> {
         let name = item.ident.name;
         let sp = item.span;
-        let is_public = item.vis == ast::Public;
+        let is_public = item.vis != ast::Inherited;
         let modifiers = if is_public { PUBLIC } else { DefModifiers::empty() } | IMPORTABLE;
 
         match item.node {
@@ -589,7 +589,7 @@ impl<'a, 'b:'a, 'tcx:'b> GraphBuilder<'a, 'b, 'tcx> {
                                             foreign_item: &ForeignItem,
                                             parent: &Rc<Module>) {
         let name = foreign_item.ident.name;
-        let is_public = foreign_item.vis == ast::Public;
+        let is_public = foreign_item.vis != ast::Inherited;
         let modifiers = if is_public { PUBLIC } else { DefModifiers::empty() } | IMPORTABLE;
         let name_bindings =
             self.add_child(name, parent, ForbidDuplicateValues,
@@ -776,7 +776,7 @@ impl<'a, 'b:'a, 'tcx:'b> GraphBuilder<'a, 'b, 'tcx> {
           }
           DefLocal(..) | DefPrimTy(..) | DefTyParam(..) |
           DefUse(..) | DefUpvar(..) | DefRegion(..) |
-          DefLabel(..) | DefSelfTy(..) => {
+          DefLabel(..) | DefSelfTy(..) | DefErr => {
             panic!("didn't expect `{:?}`", def);
           }
         }
@@ -827,6 +827,10 @@ pub struct Resolver<'a, 'tcx:'a> {
 
     used_imports: HashSet<(NodeId, Namespace)>,
     used_crates: HashSet<CrateNum>,
+
+    // The number of errors reported for unresolved imports and paths that
+    // later passes can get past, because the paths resolve to `DefErr`.
+    recovered_errors: uint,
 }
 
 #[derive(PartialEq)]
@@ -891,6 +895,8 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
             emit_errors: true,
             make_glob_map: make_glob_map == MakeGlobMap::Yes,
             glob_map: HashMap::new(),
+
+            recovered_errors: 0,
         }
     }
 
@@ -1547,6 +1553,7 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
         let imports = module_.imports.borrow();
         let import_count = imports.len();
         if index != import_count {
+            let errors = self.session.err_count();
             let sn = self.session
                          .codemap()
                          .span_to_snippet((*imports)[index].span)
@@ -1559,6 +1566,8 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                                   sn);
                 self.resolve_error((*imports)[index].span, &err[..]);
             }
+            // As in `resolve_imports_for_module`.
+            self.recovered_errors += self.session.err_count() - errors;
         }
 
         // Descend into children and anonymous children.
@@ -2316,6 +2325,8 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                         self.record_def(ty.id, def);
                     }
                     None => {
+                        let errors = self.session.err_count();
+
                         // Keep reporting some errors even if they're ignored above.
                         self.resolve_path(ty.id, path, 0, TypeNS, true);
 
@@ -2328,6 +2339,7 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                         let msg = format!("use of undeclared {} `{}`", kind,
                                           path_names_to_string(path, 0));
                         self.resolve_error(ty.span, &msg[..]);
+                        self.record_error_def(ty.id, errors);
                     }
                 }
             }
@@ -3137,6 +3149,7 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                 if let Some(path_res) = resolution {
                     // Check if struct variant
                     if let DefVariant(_, _, true) = path_res.base_def {
+                        let errors = self.session.err_count();
                         let path_name = path_names_to_string(path, 0);
                         self.resolve_error(expr.span,
                                 &format!("`{}` is a struct variant name, but \
@@ -3152,6 +3165,7 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                         } else {
                             self.session.span_help(expr.span, &msg);
                         }
+                        self.record_error_def(expr.id, errors);
                     } else {
                         // Write the result into the def map.
                         debug!("(resolving expr) resolved `{}`",
@@ -3168,6 +3182,8 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                         self.record_def(expr.id, path_res);
                     }
                 } else {
+                    let errors = self.session.err_count();
+
                     // Be helpful if the name refers to a struct
                     // (The pattern matching def_tys where the id is in self.structs
                     // matches on regular structs while excluding tuple- and enum-like
@@ -3245,6 +3261,7 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                             }
                         }
                     }
+                    self.record_error_def(expr.id, errors);
                 }
 
                 visit::walk_expr(self, expr);
@@ -3427,6 +3444,17 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
         }
     }
 
+    /// Resolve `node_id` to `DefErr` once the errors about it are reported,
+    /// counting them as errors later passes can get past.
+    fn record_error_def(&mut self, node_id: NodeId, errors_before: uint) {
+        self.recovered_errors += self.session.err_count() - errors_before;
+        self.record_def(node_id, PathResolution {
+            base_def: DefErr,
+            last_private: LastMod(AllPublic),
+            depth: 0,
+        });
+    }
+
     fn enforce_default_binding_mode(&mut self,
                                         pat: &Pat,
                                         pat_binding_mode: BindingMode,
@@ -3562,14 +3590,23 @@ pub fn resolve_crate<'a, 'tcx>(session: &'a Session,
     build_reduced_graph::build_reduced_graph(&mut resolver, krate);
     session.abort_if_errors();
 
+    // Under `-Z keep-going`, unresolved imports and paths don't stop
+    // compilation here if they are the only errors: the paths resolve to
+    // `DefErr`, which type checking knows to skip.
+    let abort_unless_recovered = |resolver: &Resolver| {
+        if !session.keep_going() || session.err_count() != resolver.recovered_errors {
+            session.abort_if_errors();
+        }
+    };
+
     resolve_imports::resolve_imports(&mut resolver);
-    session.abort_if_errors();
+    abort_unless_recovered(&resolver);
 
     record_exports::record(&mut resolver);
-    session.abort_if_errors();
+    abort_unless_recovered(&resolver);
 
     resolver.resolve_crate(krate);
-    session.abort_if_errors();
+    abort_unless_recovered(&resolver);
 
     check_unused::check_crate(&mut resolver, krate);
 
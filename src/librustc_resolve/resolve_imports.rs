@@ -276,7 +276,11 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
                                           &import_directive.module_path,
                                           import_directive.subclass),
                                       help);
+                    let errors = self.resolver.session.err_count();
                     self.resolver.resolve_error(span, &msg[..]);
+                    // Uses of the names the import would have brought in
+                    // are reported, and recovered from, on their own.
+                    self.resolver.recovered_errors += self.resolver.session.err_count() - errors;
                 }
                 ResolveResult::Indeterminate => break, // Bail out. We'll come around next time.
                 ResolveResult::Success(()) => () // Good. Continue.
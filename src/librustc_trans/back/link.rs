@@ -21,13 +21,16 @@ use session::Session;
 use metadata::common::LinkMeta;
 use metadata::{encoder, cstore, filesearch, csearch, creader};
 use metadata::filesearch::FileDoesntMatch;
-use trans::{CrateContext, CrateTranslation, gensym_name};
+use trans::{CrateContext, CrateTranslation, seq_name};
 use middle::ty::{self, Ty};
 use util::common::time;
 use util::ppaux;
 use util::sha2::{Digest, Sha256};
 use rustc_back::tempdir::TempDir;
 
+use std::collections::HashMap;
+use std::collections::hash_map::Entry::{Occupied, Vacant};
+use std::env;
 use std::ffi::OsString;
 use std::fs::{self, PathExt};
 use std::io::{self, Read, Write};
@@ -166,6 +169,39 @@ pub fn find_crate_name(sess: Option<&Session>,
     "rust-out".to_string()
 }
 
+/// The oldest OS version the output should run on, for targets that have
+/// one (OS X and iOS): from `-C macosx-version-min` or `-C ios-version-min`,
+/// else from the target's environment variable (e.g.
+/// `MACOSX_DEPLOYMENT_TARGET`). A version like `10.7` is padded to the
+/// `10.7.0` form used in LLVM triples.
+pub fn deployment_target(sess: &Session) -> Option<String> {
+    let opts = &sess.target.target.options;
+    if opts.deployment_target_env.is_empty() {
+        return None;
+    }
+    let flag = match &sess.target.target.target_os[..] {
+        "ios" => sess.opts.cg.ios_version_min.clone(),
+        _ => sess.opts.cg.macosx_version_min.clone(),
+    };
+    let version = match flag.or_else(|| env::var(&opts.deployment_target_env).ok()) {
+        Some(ref version) if !version.is_empty() => version.clone(),
+        _ => return None,
+    };
+
+    let mut parts: Vec<&str> = version.split('.').collect();
+    let valid = parts.len() <= 3 && parts.iter().all(|part| {
+        !part.is_empty() && part.chars().all(|c| c.is_digit(10))
+    });
+    if !valid {
+        sess.fatal(&format!("invalid deployment target `{}`: expected a version like `10.7`",
+                            version));
+    }
+    while parts.len() < 3 {
+        parts.push("0");
+    }
+    Some(parts.connect("."))
+}
+
 pub fn build_link_meta(sess: &Session, krate: &ast::Crate,
                        name: String) -> LinkMeta {
     let r = LinkMeta {
@@ -340,14 +376,18 @@ pub fn mangle_internal_name_by_type_and_seq<'a, 'tcx>(ccx: &CrateContext<'a, 'tc
                                                       t: Ty<'tcx>,
                                                       name: &str) -> String {
     let s = ppaux::ty_to_string(ccx.tcx(), t);
-    let path = [PathName(token::intern(&s[..])),
-                gensym_name(name)];
     let hash = get_symbol_hash(ccx, t);
+    let path = [PathName(token::intern(&s[..])),
+                seq_name(ccx, &format!("{}{}", s, hash), name)];
     mangle(path.iter().cloned(), Some(&hash[..]))
 }
 
-pub fn mangle_internal_name_by_path_and_seq(path: PathElems, flav: &str) -> String {
-    mangle(path.chain(Some(gensym_name(flav)).into_iter()), None)
+pub fn mangle_internal_name_by_path_and_seq(ccx: &CrateContext, path: PathElems,
+                                            flav: &str) -> String {
+    let path: Vec<_> = path.collect();
+    let prefix = mangle(path.iter().cloned(), None);
+    let seq = seq_name(ccx, &prefix, flav);
+    mangle(path.into_iter().chain(Some(seq).into_iter()), None)
 }
 
 pub fn get_cc_prog(sess: &Session) -> String {
@@ -380,6 +420,9 @@ pub fn link_binary(sess: &Session,
             sess.bug(&format!("invalid output type `{:?}` for target os `{}`",
                              crate_type, sess.opts.target_triple));
         }
+        if crate_type != config::CrateTypeRlib {
+            check_duplicate_symbols(sess, trans, crate_type);
+        }
         let out_file = link_binary_output(sess, trans, crate_type, outputs,
                                           crate_name);
         out_filenames.push(out_file);
@@ -397,6 +440,48 @@ pub fn link_binary(sess: &Session,
     out_filenames
 }
 
+/// Reports the `#[no_mangle]` and `#[export_name]` symbols that more than one
+/// of the crates going into an output of type `crate_type` define. Left to
+/// the linker, this is an error without any Rust context, or with dynamic
+/// libraries no error at all as one of the definitions silently wins.
+fn check_duplicate_symbols(sess: &Session,
+                           trans: &CrateTranslation,
+                           crate_type: config::CrateType) {
+    let data = match trans.crate_formats.get(&crate_type) {
+        Some(data) => data,
+        None => return
+    };
+
+    let local = &trans.link.crate_name;
+    let mut definitions = HashMap::new();
+    for &(ref name, span) in &trans.unmangled_symbols {
+        definitions.insert(name.clone(), (local.clone(), Some(span)));
+    }
+
+    for &(cnum, _) in &sess.cstore.get_used_crates(cstore::RequireDynamic) {
+        // Crates that only appear inside a dylib we link are checked when
+        // that dylib is built.
+        if data[cnum as uint - 1].is_none() {
+            continue
+        }
+        let krate = sess.cstore.get_crate_data(cnum).name.clone();
+        for name in csearch::get_unmangled_symbols(&sess.cstore, cnum) {
+            match definitions.entry(name) {
+                Vacant(entry) => { entry.insert((krate.clone(), None)); }
+                Occupied(entry) => {
+                    let (ref first, span) = *entry.get();
+                    let msg = format!("symbol `{}` is defined by both crate `{}` and crate `{}`",
+                                      entry.key(), first, krate);
+                    match span {
+                        Some(span) => sess.span_err(span, &msg),
+                        None => sess.err(&msg),
+                    }
+                }
+            }
+        }
+    }
+    sess.abort_if_errors();
+}
 
 /// Returns default crate type for target
 ///
@@ -466,8 +551,9 @@ fn link_binary_output(sess: &Session,
                       outputs: &OutputFilenames,
                       crate_name: &str) -> PathBuf {
     let obj_filename = outputs.temp_path(OutputTypeObject);
-    let out_filename = match outputs.single_output_file {
-        Some(ref file) => file.clone(),
+    let explicit = outputs.outputs.get(&OutputTypeExe).or(outputs.single_output_file.as_ref());
+    let out_filename = match explicit {
+        Some(file) => file.clone(),
         None => {
             let out_filename = outputs.path(OutputTypeExe);
             filename_for_input(sess, crate_type, crate_name, &out_filename)
@@ -783,6 +869,9 @@ fn link_natively(sess: &Session, trans: &CrateTranslation, dylib: bool,
     let mut cmd = Command::new(&pname[..]);
 
     cmd.args(&sess.target.target.options.pre_link_args);
+    if let Some(version) = deployment_target(sess) {
+        cmd.arg(&format!("{}={}", sess.target.target.options.version_min_link_arg, version));
+    }
     link_args(&mut cmd, sess, dylib, tmpdir.path(),
               trans, obj_filename, out_filename);
     cmd.args(&sess.target.target.options.post_link_args);
@@ -951,6 +1040,17 @@ fn link_args(cmd: &mut Command,
         cmd.arg("-nodefaultlibs");
     }
 
+    // Without an explicit subsystem the linker picks "console", which opens a
+    // console window for GUI applications. The startup objects of the GNU
+    // toolchain call `main` whichever subsystem is chosen (the WinMain entry
+    // point they select for "windows" ends up in the same place), so our own
+    // `main` shim needs no changes.
+    if !dylib {
+        if let Some(ref subsystem) = *sess.windows_subsystem.borrow() {
+            cmd.arg(&format!("-Wl,--subsystem,{}", subsystem));
+        }
+    }
+
     // Mark all dynamic libraries and executables as compatible with ASLR
     // FIXME #17098: ASLR breaks gdb
     if t.options.is_like_windows && sess.opts.debuginfo == NoDebugInfo {
@@ -9,7 +9,7 @@
 // except according to those terms.
 
 use back::lto;
-use back::link::{get_cc_prog, remove};
+use back::link::{get_cc_prog, remove, deployment_target};
 use session::config::{OutputFilenames, NoDebugInfo, Passes, SomePasses, AllPasses};
 use session::Session;
 use session::config;
@@ -25,15 +25,18 @@ use syntax::diagnostic::{Emitter, Handler, Level, mk_handler};
 
 use std::ffi::{CStr, CString};
 use std::fs;
+use std::io::{self, Write};
 use std::iter::Unfold;
 use std::mem;
 use std::path::Path;
 use std::process::{Command, Stdio};
 use std::ptr;
+use std::rt::backtrace;
 use std::str;
 use std::sync::{Arc, Mutex};
 use std::sync::mpsc::channel;
 use std::thread;
+use std::time::Duration;
 use libc::{self, c_uint, c_int, c_void};
 
 #[derive(Clone, Copy, PartialEq, PartialOrd, Ord, Eq)]
@@ -220,7 +223,20 @@ fn create_target_machine(sess: &Session) -> TargetMachineRef {
         }
     };
 
-    let triple = &sess.target.target.llvm_target;
+    // With a deployment target, the OS in the triple carries the version,
+    // e.g. `x86_64-apple-darwin` becomes `x86_64-apple-macosx10.7.0`.
+    let triple = match deployment_target(sess) {
+        Some(version) => {
+            let llvm_target = &sess.target.target.llvm_target;
+            let arch_vendor = match llvm_target.rfind('-') {
+                Some(i) => &llvm_target[..i],
+                None => &llvm_target[..],
+            };
+            format!("{}-{}{}", arch_vendor,
+                    sess.target.target.options.deployment_target_os, version)
+        }
+        None => sess.target.target.llvm_target.clone(),
+    };
 
     let tm = unsafe {
         let triple = CString::new(triple.as_bytes()).unwrap();
@@ -269,16 +285,20 @@ struct ModuleConfig {
     emit_no_opt_bc: bool,
     emit_bc: bool,
     emit_lto_bc: bool,
+    emit_no_opt_ir: bool,
     emit_ir: bool,
     emit_asm: bool,
     emit_obj: bool,
 
     // Miscellaneous flags.  These are mostly copied from command-line
     // options.
-    no_verify: bool,
+    verify: bool,
+    corrupt_ir: bool,
     no_prepopulate_passes: bool,
     no_builtins: bool,
     time_passes: bool,
+    print_llvm_timings: bool,
+    llvm_time_limit: Option<uint>,
 }
 
 unsafe impl Send for ModuleConfig { }
@@ -293,22 +313,29 @@ impl ModuleConfig {
             emit_no_opt_bc: false,
             emit_bc: false,
             emit_lto_bc: false,
+            emit_no_opt_ir: false,
             emit_ir: false,
             emit_asm: false,
             emit_obj: false,
 
-            no_verify: false,
+            verify: false,
+            corrupt_ir: false,
             no_prepopulate_passes: false,
             no_builtins: false,
             time_passes: false,
+            print_llvm_timings: false,
+            llvm_time_limit: None,
         }
     }
 
     fn set_flags(&mut self, sess: &Session, trans: &CrateTranslation) {
-        self.no_verify = sess.no_verify();
+        self.verify = sess.verify_llvm_ir();
+        self.corrupt_ir = sess.opts.debugging_opts.corrupt_llvm_ir;
         self.no_prepopulate_passes = sess.opts.cg.no_prepopulate_passes;
         self.no_builtins = trans.no_builtins;
         self.time_passes = sess.time_passes();
+        self.print_llvm_timings = sess.opts.debugging_opts.print_llvm_timings;
+        self.llvm_time_limit = sess.opts.debugging_opts.llvm_time_limit;
     }
 }
 
@@ -404,6 +431,103 @@ unsafe extern "C" fn diagnostic_handler(info: DiagnosticInfoRef, user: *mut c_vo
     }
 }
 
+/// Runs the LLVM verifier on `llmod`, reporting a fatal error with the
+/// verifier's message if the module is broken.
+unsafe fn verify_module(handler: &Handler, llmod: ModuleRef, name_extra: &str) {
+    let mut msg = ptr::null_mut();
+    if llvm::LLVMVerifyModule(llmod, llvm::ReturnStatusAction, &mut msg) == llvm::False {
+        return;
+    }
+    let err = if msg.is_null() {
+        String::new()
+    } else {
+        let err = String::from_utf8_lossy(CStr::from_ptr(msg).to_bytes()).to_string();
+        llvm::LLVMDisposeMessage(msg);
+        err
+    };
+    handler.fatal(&format!("LLVM module `{}` failed verification: {}",
+                           name_extra, err.trim_right()));
+}
+
+/// Adds a function whose only basic block has no terminator, which the
+/// verifier rejects. Used by `-Z corrupt-llvm-ir` to test `verify_module`.
+unsafe fn corrupt_module(llcx: ContextRef, llmod: ModuleRef) {
+    let void = llvm::LLVMVoidTypeInContext(llcx);
+    let fn_ty = llvm::LLVMFunctionType(void, ptr::null(), 0, llvm::False);
+    let name = CString::new("rustc_corrupt_llvm_ir").unwrap();
+    let llfn = llvm::LLVMAddFunction(llmod, name.as_ptr(), fn_ty);
+    let name = CString::new("start").unwrap();
+    llvm::LLVMAppendBasicBlockInContext(llcx, llfn, name.as_ptr());
+}
+
+/// Runs the passes in `fpm` over each function defined in `llmod`, like
+/// `LLVMRustRunFunctionPassManager` does, and returns how long each function
+/// took. Names are read before the passes run on a function, since they may
+/// change it.
+unsafe fn run_function_passes_timed(fpm: PassManagerRef,
+                                    llmod: ModuleRef) -> Vec<(String, Duration)> {
+    let mut timings = Vec::new();
+    llvm::LLVMInitializeFunctionPassManager(fpm);
+    let mut llfn = llvm::LLVMGetFirstFunction(llmod);
+    while !llfn.is_null() {
+        if llvm::LLVMIsDeclaration(llfn) == llvm::False {
+            let name = CStr::from_ptr(llvm::LLVMGetValueName(llfn)).to_bytes();
+            let name = String::from_utf8_lossy(name).into_owned();
+            let elapsed = Duration::span(|| {
+                llvm::LLVMRunFunctionPassManager(fpm, llfn);
+            });
+            timings.push((name, elapsed));
+        }
+        llfn = llvm::LLVMGetNextFunction(llfn);
+    }
+    llvm::LLVMFinalizeFunctionPassManager(fpm);
+    timings
+}
+
+fn demangled(symbol: &str) -> String {
+    let mut out = Vec::new();
+    match backtrace::demangle(&mut out, symbol) {
+        Ok(()) => String::from_utf8(out).unwrap_or_else(|_| symbol.to_string()),
+        Err(_) => symbol.to_string(),
+    }
+}
+
+fn format_duration(d: Duration) -> String {
+    let ms = d.num_milliseconds();
+    format!("{}.{:03}s", ms / 1000, ms % 1000)
+}
+
+/// Implements `-Z llvm-time-limit` and `-Z print-llvm-timings` for one
+/// codegen unit, given the times from `run_function_passes_timed`.
+fn report_llvm_timings(cgcx: &CodegenContext,
+                       config: &ModuleConfig,
+                       name_extra: &str,
+                       mut timings: Vec<(String, Duration)>,
+                       module_passes: Duration) {
+    if let Some(limit) = config.llvm_time_limit {
+        let limit_duration = Duration::seconds(limit as i64);
+        for &(ref name, elapsed) in &timings {
+            if elapsed > limit_duration {
+                cgcx.handler.warn(&format!("LLVM's function passes took {} on `{}`, \
+                                            more than the limit of {} seconds",
+                                           format_duration(elapsed), demangled(name), limit));
+            }
+        }
+    }
+
+    if config.print_llvm_timings {
+        timings.sort_by(|a, b| b.1.cmp(&a.1));
+        // Build the whole report first so that the reports of codegen units
+        // running on different threads aren't interleaved.
+        let mut report = format!("llvm timings for codegen unit {}:\n", name_extra);
+        report.push_str(&format!("  {:>9} (module passes)\n", format_duration(module_passes)));
+        for &(ref name, elapsed) in timings.iter().take(10) {
+            report.push_str(&format!("  {:>9} {}\n", format_duration(elapsed), demangled(name)));
+        }
+        print!("{}", report);
+    }
+}
+
 // Unsafe due to LLVM calls.
 unsafe fn optimize_and_codegen(cgcx: &CodegenContext,
                                mtrans: ModuleTranslation,
@@ -423,6 +547,10 @@ unsafe fn optimize_and_codegen(cgcx: &CodegenContext,
     llvm::LLVMSetInlineAsmDiagnosticHandler(llcx, inline_asm_handler, fv);
     llvm::LLVMContextSetDiagnosticHandler(llcx, diagnostic_handler, fv);
 
+    if config.corrupt_ir {
+        corrupt_module(llcx, llmod);
+    }
+
     if config.emit_no_opt_bc {
         let ext = format!("{}.no-opt.bc", name_extra);
         let out = output_names.with_extension(&ext);
@@ -430,6 +558,21 @@ unsafe fn optimize_and_codegen(cgcx: &CodegenContext,
         llvm::LLVMWriteBitcodeToFile(llmod, out.as_ptr());
     }
 
+    if config.emit_no_opt_ir {
+        let ext = format!("{}.unopt.ll", name_extra);
+        let out = output_names.with_extension(&ext);
+        let out = path2cstr(&out);
+        with_codegen(tm, llmod, config.no_builtins, |cpm| {
+            llvm::LLVMRustPrintModule(cpm, llmod, out.as_ptr());
+        })
+    }
+
+    // Verify the module before LLVM gets to see it, so that broken IR is
+    // reported as an error rather than crashing one of the passes below.
+    if config.verify {
+        verify_module(cgcx.handler, llmod, &name_extra);
+    }
+
     match config.opt_level {
         Some(opt_level) => {
             // Create the two optimizing pass managers. These mirror what clang
@@ -439,14 +582,6 @@ unsafe fn optimize_and_codegen(cgcx: &CodegenContext,
             let fpm = llvm::LLVMCreateFunctionPassManagerForModule(llmod);
             let mpm = llvm::LLVMCreatePassManager();
 
-            // If we're verifying or linting, add them to the function pass
-            // manager.
-            let addpass = |pass: &str| {
-                let pass = CString::new(pass).unwrap();
-                llvm::LLVMRustAddPass(fpm, pass.as_ptr())
-            };
-            if !config.no_verify { assert!(addpass("verify")); }
-
             if !config.no_prepopulate_passes {
                 llvm::LLVMRustAddAnalysisPasses(tm, fpm, llmod);
                 llvm::LLVMRustAddAnalysisPasses(tm, mpm, llmod);
@@ -462,10 +597,20 @@ unsafe fn optimize_and_codegen(cgcx: &CodegenContext,
             }
 
             // Finally, run the actual optimization passes
-            time(config.time_passes, "llvm function passes", (), |()|
-                 llvm::LLVMRustRunFunctionPassManager(fpm, llmod));
-            time(config.time_passes, "llvm module passes", (), |()|
-                 llvm::LLVMRunPassManager(mpm, llmod));
+            if config.print_llvm_timings || config.llvm_time_limit.is_some() {
+                let timings = time(config.time_passes, "llvm function passes", (), |()|
+                                   run_function_passes_timed(fpm, llmod));
+                let module_passes = Duration::span(|| {
+                    time(config.time_passes, "llvm module passes", (), |()|
+                         llvm::LLVMRunPassManager(mpm, llmod));
+                });
+                report_llvm_timings(cgcx, &config, &name_extra, timings, module_passes);
+            } else {
+                time(config.time_passes, "llvm function passes", (), |()|
+                     llvm::LLVMRustRunFunctionPassManager(fpm, llmod));
+                time(config.time_passes, "llvm module passes", (), |()|
+                     llvm::LLVMRunPassManager(mpm, llmod));
+            }
 
             // Deallocate managers that we're now done with
             llvm::LLVMDisposePassManager(fpm);
@@ -547,6 +692,21 @@ unsafe fn optimize_and_codegen(cgcx: &CodegenContext,
     llvm::LLVMRustDisposeTargetMachine(tm);
 }
 
+/// Write one line per exported symbol: its name, the path of the item it
+/// belongs to and its kind, separated by tabs.
+pub fn write_exported_symbols(sess: &Session, trans: &CrateTranslation, path: &Path) {
+    let result = fs::File::create(path).and_then(|mut file| {
+        for symbol in &trans.exported_symbols {
+            try!(writeln!(file, "{}\t{}\t{}", symbol.name, symbol.path, symbol.kind));
+        }
+        Ok(())
+    });
+    if let Err(e) = result {
+        sess.fatal(&format!("error writing exported symbols to `{}`: {}",
+                            path.display(), e));
+    }
+}
+
 pub fn run_passes(sess: &Session,
                   trans: &CrateTranslation,
                   output_types: &[config::OutputType],
@@ -602,6 +762,7 @@ pub fn run_passes(sess: &Session,
         match *output_type {
             config::OutputTypeBitcode => { modules_config.emit_bc = true; },
             config::OutputTypeLlvmAssembly => { modules_config.emit_ir = true; },
+            config::OutputTypeUnoptLlvmAssembly => { modules_config.emit_no_opt_ir = true; },
             config::OutputTypeAssembly => {
                 modules_config.emit_asm = true;
                 // If we're not using the LLVM assembler, this function
@@ -616,7 +777,7 @@ pub fn run_passes(sess: &Session,
                 modules_config.emit_obj = true;
                 metadata_config.emit_obj = true;
             },
-            config::OutputTypeDepInfo => {}
+            config::OutputTypeDepInfo | config::OutputTypeExportedSymbols => {}
         }
     }
 
@@ -774,6 +935,9 @@ pub fn run_passes(sess: &Session,
             config::OutputTypeLlvmAssembly => {
                 copy_if_one_unit("0.ll", config::OutputTypeLlvmAssembly, false);
             }
+            config::OutputTypeUnoptLlvmAssembly => {
+                copy_if_one_unit("0.unopt.ll", config::OutputTypeUnoptLlvmAssembly, false);
+            }
             config::OutputTypeAssembly => {
                 copy_if_one_unit("0.s", config::OutputTypeAssembly, false);
             }
@@ -789,7 +953,7 @@ pub fn run_passes(sess: &Session,
                     link_obj(&crate_output.temp_path(config::OutputTypeObject));
                 }
             }
-            config::OutputTypeDepInfo => {}
+            config::OutputTypeDepInfo | config::OutputTypeExportedSymbols => {}
         }
     }
     let user_wants_bitcode = user_wants_bitcode;
@@ -989,6 +1153,25 @@ pub fn run_assembler(sess: &Session, outputs: &OutputFilenames) {
     }
 }
 
+/// Streams an output that was requested with `-o -` to stdout once it has been
+/// written to its usual path, and removes the file. LLVM can only write
+/// artifacts to a file, so this is the last step of producing them.
+pub fn copy_output_to_stdout(sess: &Session,
+                             outputs: &OutputFilenames,
+                             output_type: config::OutputType) {
+    let path = outputs.path(output_type);
+    let stdout = io::stdout();
+    let result = fs::File::open(&path).and_then(|mut file| {
+        let mut stdout = stdout.lock();
+        try!(io::copy(&mut file, &mut stdout));
+        stdout.flush()
+    });
+    if let Err(e) = result {
+        sess.fatal(&format!("failed to write {} to stdout: {}", path.display(), e));
+    }
+    remove(sess, &path);
+}
+
 unsafe fn configure_llvm(sess: &Session) {
     use std::sync::{Once, ONCE_INIT};
     static INIT: Once = ONCE_INIT;
@@ -42,6 +42,7 @@
 #![feature(fs)]
 #![feature(convert)]
 #![feature(path_relative_from)]
+#![feature(std_misc)]
 
 extern crate arena;
 extern crate flate;
@@ -55,6 +55,7 @@ use util::ppaux;
 
 mod span_utils;
 mod recorder;
+pub mod types;
 
 // Helper function to escape quotes in a string
 fn escape(s: String) -> String {
@@ -254,7 +255,8 @@ impl <'l, 'tcx> DxrVisitor<'l, 'tcx> {
             def::DefTyParam(..) |
             def::DefUse(_) |
             def::DefMethod(..) |
-            def::DefPrimTy(_) => {
+            def::DefPrimTy(_) |
+            def::DefErr => {
                 self.sess.span_bug(span, &format!("lookup_def_kind for unexpected item: {:?}",
                                                  def));
             },
@@ -0,0 +1,112 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Dump the type of every expression and pattern binding in the crate, for
+//! `-Z save-types=FILE`. Each line of the output is a tab separated record:
+//!
+//! ```text
+//! file:lo-hi  expr     TYPE   [ADJUSTED]
+//! file:lo-hi  binding  NAME   TYPE
+//! ```
+//!
+//! where `lo` and `hi` are byte offsets into `file`. The adjusted type of an
+//! expression (after autoderef, autoref, unsizing etc.) is only given when it
+//! differs from the type of the expression itself. Records are sorted by span
+//! so that the output is deterministic.
+
+use middle::ty;
+use util::ppaux::ty_to_string;
+
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+use syntax::ast;
+use syntax::codemap::Span;
+use syntax::parse::token;
+use syntax::visit::{self, Visitor};
+
+struct TypeRecord {
+    file: String,
+    lo: usize,
+    hi: usize,
+    kind: &'static str,
+    fields: Vec<String>,
+}
+
+struct TypeDumper<'a, 'tcx: 'a> {
+    tcx: &'a ty::ctxt<'tcx>,
+    records: Vec<TypeRecord>,
+}
+
+impl<'a, 'tcx> TypeDumper<'a, 'tcx> {
+    fn record(&mut self, span: Span, kind: &'static str, fields: Vec<String>) {
+        let cm = self.tcx.sess.codemap();
+        let lo = cm.lookup_byte_offset(span.lo);
+        let hi = cm.lookup_byte_offset(span.hi);
+        self.records.push(TypeRecord {
+            file: lo.fm.name.clone(),
+            lo: lo.pos.0 as usize,
+            hi: hi.pos.0 as usize,
+            kind: kind,
+            fields: fields,
+        });
+    }
+}
+
+impl<'a, 'tcx, 'v> Visitor<'v> for TypeDumper<'a, 'tcx> {
+    fn visit_expr(&mut self, expr: &ast::Expr) {
+        // Expressions in types (e.g. array lengths) are not given a type.
+        if let Some(ty) = ty::expr_ty_opt(self.tcx, expr) {
+            let mut fields = vec![ty_to_string(self.tcx, ty)];
+            let adjusted = ty::expr_ty_adjusted(self.tcx, expr);
+            if adjusted != ty {
+                fields.push(ty_to_string(self.tcx, adjusted));
+            }
+            self.record(expr.span, "expr", fields);
+        }
+        visit::walk_expr(self, expr);
+    }
+
+    fn visit_pat(&mut self, pat: &ast::Pat) {
+        if let ast::PatIdent(_, ref path1, _) = pat.node {
+            if let Some(ty) = ty::node_id_to_type_opt(self.tcx, pat.id) {
+                let name = token::get_ident(path1.node).to_string();
+                let ty = ty_to_string(self.tcx, ty);
+                self.record(pat.span, "binding", vec![name, ty]);
+            }
+        }
+        visit::walk_pat(self, pat);
+    }
+}
+
+pub fn dump_types(tcx: &ty::ctxt, krate: &ast::Crate, path: &Path) {
+    let mut dumper = TypeDumper { tcx: tcx, records: Vec::new() };
+    visit::walk_crate(&mut dumper, krate);
+
+    let mut records = dumper.records;
+    records.sort_by(|a, b| {
+        (&a.file, a.lo, a.hi, a.kind).cmp(&(&b.file, b.lo, b.hi, b.kind))
+    });
+
+    let result = File::create(path).and_then(|mut out| {
+        for record in &records {
+            try!(write!(out, "{}:{}-{}\t{}", record.file, record.lo, record.hi, record.kind));
+            for field in &record.fields {
+                try!(write!(out, "\t{}", field));
+            }
+            try!(write!(out, "\n"));
+        }
+        Ok(())
+    });
+    if let Err(e) = result {
+        tcx.sess.fatal(&format!("could not write types to {}: {}", path.display(), e));
+    }
+}
@@ -128,10 +128,12 @@
 //!        +-------------------------------------------------+
 //!
 //! The handling for the cleanups is a bit... sensitive.  Basically, the body
-//! is the one that invokes `add_clean()` for each binding.  During the guard
-//! evaluation, we add temporary cleanups and revoke them after the guard is
-//! evaluated (it could fail, after all). Note that guards and moves are
-//! just plain incompatible.
+//! is the one that schedules the cleanups for each binding.  During the guard
+//! evaluation, no cleanups are scheduled at all, and by-move bindings simply
+//! point at the matched value in place: the move only really happens once
+//! the guard has succeeded and the body takes ownership. A failed guard thus
+//! leaves the matched value intact for the remaining arms; `check_match`
+//! makes sure the guard cannot move out of by-move bindings itself.
 //!
 //! Some relevant helper functions that manage bindings:
 //! - `create_bindings_map()`
@@ -45,10 +45,11 @@
 
 pub use self::Repr::*;
 
+use std::cmp::Ordering;
 use std::num::Int;
 use std::rc::Rc;
 
-use llvm::{ValueRef, True, IntEQ, IntNE};
+use llvm::{ValueRef, True, IntEQ, IntNE, TypeKind};
 use back::abi::FAT_PTR_ADDR;
 use middle::subst;
 use middle::ty::{self, Ty, ClosureTyper};
@@ -56,17 +57,20 @@ use middle::ty::Disr;
 use syntax::ast;
 use syntax::attr;
 use syntax::attr::IntType;
+use syntax::parse::token;
 use trans::_match;
 use trans::build::*;
 use trans::cleanup;
 use trans::cleanup::CleanupMethods;
 use trans::common::*;
+use trans::context::SharedCrateContext;
 use trans::datum;
 use trans::debuginfo::DebugLoc;
 use trans::machine;
 use trans::monomorphize;
 use trans::type_::Type;
 use trans::type_of;
+use util::nodemap::FnvHashSet;
 use util::ppaux::ty_to_string;
 
 type Hint = attr::ReprAttr;
@@ -90,28 +94,33 @@ pub enum Repr<'tcx> {
     /// indicates whether such a flag is present.
     General(IntType, Vec<Struct<'tcx>>, bool),
     /// Two cases distinguished by a nullable pointer: the case with discriminant
-    /// `nndiscr` must have single field which is known to be nonnull due to its type.
-    /// The other case is known to be zero sized. Hence we represent the enum
-    /// as simply a nullable pointer: if not null it indicates the `nndiscr` variant,
-    /// otherwise it indicates the other case.
+    /// `nndiscr` must have single field which is known never to hold the value
+    /// `niche` due to its type (zero for pointers, an invalid bit pattern for
+    /// `bool`, `char` and C-like enums). The other case is known to be zero
+    /// sized. Hence we represent the enum as simply that field: if it is not
+    /// `niche` it indicates the `nndiscr` variant, otherwise it indicates the
+    /// other case.
     RawNullablePointer {
         nndiscr: Disr,
         nnty: Ty<'tcx>,
+        niche: Disr,
         nullfields: Vec<Ty<'tcx>>
     },
     /// Two cases distinguished by a nullable pointer: the case with discriminant
     /// `nndiscr` is represented by the struct `nonnull`, where the `discrfield`th
-    /// field is known to be nonnull due to its type; if that field is null, then
-    /// it represents the other case, which is inhabited by at most one value
-    /// (and all other fields are undefined/unused).
+    /// field is known never to hold the value `niche` due to its type; if that
+    /// field is `niche`, then it represents the other case, which is inhabited
+    /// by at most one value (and all other fields are undefined/unused).
     ///
     /// For example, `std::option::Option` instantiated at a safe pointer type
     /// is represented such that `None` is a null pointer and `Some` is the
-    /// identity function.
+    /// identity function, and `Option<(u32, bool)>` stores `None` as a `2` in
+    /// the `bool`.
     StructWrappedNullablePointer {
         nonnull: Struct<'tcx>,
         nndiscr: Disr,
         discrfield: DiscrField,
+        niche: Disr,
         nullfields: Vec<Ty<'tcx>>,
     }
 }
@@ -125,7 +134,21 @@ pub struct Struct<'tcx> {
     pub align: u32,
     pub sized: bool,
     pub packed: bool,
-    pub fields: Vec<Ty<'tcx>>
+    pub fields: Vec<Ty<'tcx>>,
+    // The position of each field within the LLVM struct. Fields are indexed
+    // in declaration order everywhere else; see `reorder_struct_fields`.
+    pub memory_index: Vec<uint>,
+}
+
+impl<'tcx> Struct<'tcx> {
+    /// The indices of the fields, in the order they are laid out in memory.
+    pub fn memory_order(&self) -> Vec<uint> {
+        let mut order = vec![0; self.fields.len()];
+        for (i, &position) in self.memory_index.iter().enumerate() {
+            order[position] = i;
+        }
+        order
+    }
 }
 
 /// Convenience for `represent_type`.  There should probably be more or
@@ -164,10 +187,20 @@ fn represent_type_uncached<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
                 monomorphize::normalize_associated_type(cx.tcx(), &fty)
             }).collect::<Vec<_>>();
             let packed = ty::lookup_packed(cx.tcx(), def_id);
+            let fixed_layout = packed || ty::lookup_simd(cx.tcx(), def_id) ||
+                ty::lookup_repr_hints(cx.tcx(), def_id).contains(&attr::ReprExtern);
+            let mut memory_index = if fixed_layout {
+                (0..ftys.len()).collect()
+            } else {
+                reorder_struct_fields(cx, &ftys[..], !substs.types.is_empty())
+            };
             let dtor = ty::ty_dtor(cx.tcx(), def_id).has_drop_flag();
-            if dtor { ftys.push(cx.tcx().types.bool); }
+            if dtor {
+                ftys.push(cx.tcx().types.bool);
+                memory_index.push(ftys.len() - 1);
+            }
 
-            Univariant(mk_struct(cx, &ftys[..], packed, t), dtor)
+            Univariant(mk_struct_in_order(cx, &ftys[..], packed, memory_index, t), dtor)
         }
         ty::ty_closure(def_id, substs) => {
             let typer = NormalizingClosureTyper::new(cx.tcx());
@@ -224,27 +257,30 @@ fn represent_type_uncached<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
             }
 
             if !dtor && cases.len() == 2 && hint == attr::ReprAny {
-                // Nullable pointer optimization
+                // Nullable pointer optimization, also covering fields with
+                // other spare values such as `bool`
                 let mut discr = 0;
                 while discr < 2 {
                     if cases[1 - discr].is_zerolen(cx, t) {
                         let st = mk_struct(cx, &cases[discr].tys,
                                            false, t);
-                        match cases[discr].find_ptr(cx) {
-                            Some(ref df) if df.len() == 1 && st.fields.len() == 1 => {
+                        match cases[discr].find_discr_field(cx) {
+                            Some((ref df, niche)) if df.len() == 1 && st.fields.len() == 1 => {
                                 return RawNullablePointer {
                                     nndiscr: discr as Disr,
                                     nnty: st.fields[0],
+                                    niche: niche,
                                     nullfields: cases[1 - discr].tys.clone()
                                 };
                             }
-                            Some(mut discrfield) => {
+                            Some((mut discrfield, niche)) => {
                                 discrfield.push(0);
                                 discrfield.reverse();
                                 return StructWrappedNullablePointer {
                                     nndiscr: discr as Disr,
                                     nonnull: st,
                                     discrfield: discrfield,
+                                    niche: niche,
                                     nullfields: cases[1 - discr].tys.clone()
                                 };
                             }
@@ -341,21 +377,31 @@ struct Case<'tcx> {
 /// This represents the (GEP) indices to follow to get to the discriminant field
 pub type DiscrField = Vec<uint>;
 
-fn find_discr_field_candidate<'tcx>(tcx: &ty::ctxt<'tcx>,
-                                    ty: Ty<'tcx>,
-                                    mut path: DiscrField) -> Option<DiscrField> {
+/// Finds a field within `ty` that never holds some particular value, returning
+/// the (reversed) path to it along with that value. Pointers are never null;
+/// `bool`, `char` and C-like enums have other spare bit patterns.
+fn find_discr_field_candidate<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
+                                        ty: Ty<'tcx>,
+                                        mut path: DiscrField) -> Option<(DiscrField, Disr)> {
+    let tcx = cx.tcx();
     match ty.sty {
         // Fat &T/&mut T/Box<T> i.e. T is [T], str, or Trait
         ty::ty_rptr(_, ty::mt { ty, .. }) | ty::ty_uniq(ty) if !type_is_sized(tcx, ty) => {
             path.push(FAT_PTR_ADDR);
-            Some(path)
+            Some((path, 0))
         },
 
         // Regular thin pointer: &T/&mut T/Box<T>
-        ty::ty_rptr(..) | ty::ty_uniq(..) => Some(path),
+        ty::ty_rptr(..) | ty::ty_uniq(..) => Some((path, 0)),
 
         // Functions are just pointers
-        ty::ty_bare_fn(..) => Some(path),
+        ty::ty_bare_fn(..) => Some((path, 0)),
+
+        // A bool is only ever 0 or 1
+        ty::ty_bool => Some((path, 2)),
+
+        // A char is a Unicode codepoint, so never above 0x10FFFF
+        ty::ty_char => Some((path, 0x10FFFF + 1)),
 
         // Is this the NonZero lang item wrapping a pointer or integer type?
         ty::ty_struct(did, substs) if Some(did) == tcx.lang_items.non_zero() => {
@@ -365,7 +411,7 @@ fn find_discr_field_candidate<'tcx>(tcx: &ty::ctxt<'tcx>,
             match nonzero_field.sty {
                 ty::ty_ptr(..) | ty::ty_int(..) | ty::ty_uint(..) => {
                     path.push(0);
-                    Some(path)
+                    Some((path, 0))
                 },
                 _ => None
             }
@@ -374,12 +420,20 @@ fn find_discr_field_candidate<'tcx>(tcx: &ty::ctxt<'tcx>,
         // Perhaps one of the fields of this struct is non-zero
         // let's recurse and find out
         ty::ty_struct(def_id, substs) => {
+            let repr = represent_type(cx, ty);
             let fields = ty::lookup_struct_fields(tcx, def_id);
             for (j, field) in fields.iter().enumerate() {
                 let field_ty = ty::lookup_field_type(tcx, def_id, field.id, substs);
-                if let Some(mut fpath) = find_discr_field_candidate(tcx, field_ty, path.clone()) {
-                    fpath.push(j);
-                    return Some(fpath);
+                let field_ty = monomorphize::normalize_associated_type(tcx, &field_ty);
+                if let Some((mut fpath, niche)) = find_discr_field_candidate(cx, field_ty,
+                                                                             path.clone()) {
+                    // The path is made of GEP indices, so it follows the
+                    // struct's memory layout rather than declaration order.
+                    fpath.push(match *repr {
+                        Univariant(ref st, _) => st.memory_index[j],
+                        _ => j
+                    });
+                    return Some((fpath, niche));
                 }
             }
             None
@@ -388,9 +442,10 @@ fn find_discr_field_candidate<'tcx>(tcx: &ty::ctxt<'tcx>,
         // Can we use one of the fields in this tuple?
         ty::ty_tup(ref tys) => {
             for (j, &ty) in tys.iter().enumerate() {
-                if let Some(mut fpath) = find_discr_field_candidate(tcx, ty, path.clone()) {
+                if let Some((mut fpath, niche)) = find_discr_field_candidate(cx, ty,
+                                                                             path.clone()) {
                     fpath.push(j);
-                    return Some(fpath);
+                    return Some((fpath, niche));
                 }
             }
             None
@@ -399,15 +454,33 @@ fn find_discr_field_candidate<'tcx>(tcx: &ty::ctxt<'tcx>,
         // Is this a fixed-size array of something non-zero
         // with at least one element?
         ty::ty_vec(ety, Some(d)) if d > 0 => {
-            if let Some(mut vpath) = find_discr_field_candidate(tcx, ety, path) {
+            if let Some((mut vpath, niche)) = find_discr_field_candidate(cx, ety, path) {
                 vpath.push(0);
-                Some(vpath)
+                Some((vpath, niche))
             } else {
                 None
             }
         },
 
-        // Anything else is not a pointer
+        // A C-like enum never holds the value just past its largest
+        // discriminant, unless its discriminants cover the whole integer.
+        ty::ty_enum(..) => {
+            match *represent_type(cx, ty) {
+                CEnum(ity, min, max) => {
+                    let bits = machine::llbitsize_of_real(cx, ll_inttype(cx, ity));
+                    let mask = -1u64 >> (64 - bits as uint);
+                    let niche = max.wrapping_add(1) & mask;
+                    if niche == min & mask {
+                        None
+                    } else {
+                        Some((path, niche))
+                    }
+                }
+                _ => None
+            }
+        },
+
+        // Anything else has no spare values
         _ => None
     }
 }
@@ -417,11 +490,11 @@ impl<'tcx> Case<'tcx> {
         mk_struct(cx, &self.tys, false, scapegoat).size == 0
     }
 
-    fn find_ptr<'a>(&self, cx: &CrateContext<'a, 'tcx>) -> Option<DiscrField> {
+    fn find_discr_field<'a>(&self, cx: &CrateContext<'a, 'tcx>) -> Option<(DiscrField, Disr)> {
         for (i, &ty) in self.tys.iter().enumerate() {
-            if let Some(mut path) = find_discr_field_candidate(cx.tcx(), ty, vec![]) {
+            if let Some((mut path, niche)) = find_discr_field_candidate(cx, ty, vec![]) {
                 path.push(i);
-                return Some(path);
+                return Some((path, niche));
             }
         }
         None
@@ -444,12 +517,28 @@ fn mk_struct<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
                        tys: &[Ty<'tcx>], packed: bool,
                        scapegoat: Ty<'tcx>)
                        -> Struct<'tcx> {
+    mk_struct_in_order(cx, tys, packed, (0..tys.len()).collect(), scapegoat)
+}
+
+/// Like `mk_struct`, but the `i`th field is laid out at position
+/// `memory_index[i]` rather than in declaration order.
+fn mk_struct_in_order<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
+                                tys: &[Ty<'tcx>], packed: bool,
+                                memory_index: Vec<uint>,
+                                scapegoat: Ty<'tcx>)
+                                -> Struct<'tcx> {
+    assert_eq!(tys.len(), memory_index.len());
+    let mut ordered_tys = tys.to_vec();
+    for (i, &position) in memory_index.iter().enumerate() {
+        ordered_tys[position] = tys[i];
+    }
+
     let sized = tys.iter().all(|&ty| type_is_sized(cx.tcx(), ty));
     let lltys : Vec<Type> = if sized {
-        tys.iter()
+        ordered_tys.iter()
            .map(|&ty| type_of::sizing_type_of(cx, ty)).collect()
     } else {
-        tys.iter().filter(|&ty| type_is_sized(cx.tcx(), *ty))
+        ordered_tys.iter().filter(|&ty| type_is_sized(cx.tcx(), *ty))
            .map(|&ty| type_of::sizing_type_of(cx, ty)).collect()
     };
 
@@ -462,9 +551,40 @@ fn mk_struct<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
         sized: sized,
         packed: packed,
         fields: tys.to_vec(),
+        memory_index: memory_index,
     }
 }
 
+/// Choose where each field of a struct without a fixed layout (that is,
+/// not `#[repr(C)]`, `#[repr(packed)]` or `#[simd]`) goes in memory. Fields
+/// are sorted by decreasing alignment, which leaves no padding between
+/// fields whose sizes are multiples of their alignment; the sort is stable
+/// so fields of equal alignment keep their declaration order.
+///
+/// The last field of a generic struct stays last, since it may be unsized
+/// in another instantiation and unsizing coercions rely on both
+/// instantiations sharing the layout of the other fields.
+fn reorder_struct_fields<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
+                                   tys: &[Ty<'tcx>],
+                                   generic: bool) -> Vec<uint> {
+    let pinned = tys.len() > 0 && (generic || !type_is_sized(cx.tcx(), tys[tys.len() - 1]));
+    let sorted = if pinned { tys.len() - 1 } else { tys.len() };
+
+    let mut order = (0..sorted).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| {
+        type_of::align_of(cx, tys[b]).cmp(&type_of::align_of(cx, tys[a]))
+    });
+    if pinned {
+        order.push(sorted);
+    }
+
+    let mut memory_index = vec![0; tys.len()];
+    for (position, &i) in order.iter().enumerate() {
+        memory_index[i] = position;
+    }
+    memory_index
+}
+
 #[derive(Debug)]
 struct IntBounds {
     slo: i64,
@@ -695,11 +815,12 @@ fn generic_type_of<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
 
 fn struct_llfields<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>, st: &Struct<'tcx>,
                              sizing: bool, dst: bool) -> Vec<Type> {
+    let fields = st.memory_order().into_iter().map(|i| st.fields[i]);
     if sizing {
-        st.fields.iter().filter(|&ty| !dst || type_is_sized(cx.tcx(), *ty))
-            .map(|&ty| type_of::sizing_type_of(cx, ty)).collect()
+        fields.filter(|&ty| !dst || type_is_sized(cx.tcx(), ty))
+            .map(|ty| type_of::sizing_type_of(cx, ty)).collect()
     } else {
-        st.fields.iter().map(|&ty| type_of::in_memory_type_of(cx, ty)).collect()
+        fields.map(|ty| type_of::in_memory_type_of(cx, ty)).collect()
     }
 }
 
@@ -744,14 +865,15 @@ pub fn trans_get_discr<'blk, 'tcx>(bcx: Block<'blk, 'tcx>, r: &Repr<'tcx>,
             val = C_u8(bcx.ccx(), 0);
             signed = false;
         }
-        RawNullablePointer { nndiscr, nnty, .. } =>  {
+        RawNullablePointer { nndiscr, nnty, niche, .. } =>  {
             let cmp = if nndiscr == 0 { IntEQ } else { IntNE };
             let llptrty = type_of::sizing_type_of(bcx.ccx(), nnty);
-            val = ICmp(bcx, cmp, Load(bcx, scrutinee), C_null(llptrty), DebugLoc::None);
+            val = ICmp(bcx, cmp, Load(bcx, scrutinee), niche_value(llptrty, niche),
+                       DebugLoc::None);
             signed = false;
         }
-        StructWrappedNullablePointer { nndiscr, ref discrfield, .. } => {
-            val = struct_wrapped_nullable_bitdiscr(bcx, nndiscr, discrfield, scrutinee);
+        StructWrappedNullablePointer { nndiscr, ref discrfield, niche, .. } => {
+            val = struct_wrapped_nullable_bitdiscr(bcx, nndiscr, discrfield, niche, scrutinee);
             signed = false;
         }
     }
@@ -762,11 +884,50 @@ pub fn trans_get_discr<'blk, 'tcx>(bcx: Block<'blk, 'tcx>, r: &Repr<'tcx>,
 }
 
 fn struct_wrapped_nullable_bitdiscr(bcx: Block, nndiscr: Disr, discrfield: &DiscrField,
-                                    scrutinee: ValueRef) -> ValueRef {
+                                    niche: Disr, scrutinee: ValueRef) -> ValueRef {
     let llptrptr = GEPi(bcx, scrutinee, &discrfield[..]);
     let llptr = Load(bcx, llptrptr);
     let cmp = if nndiscr == 0 { IntEQ } else { IntNE };
-    ICmp(bcx, cmp, llptr, C_null(val_ty(llptr)), DebugLoc::None)
+    ICmp(bcx, cmp, llptr, niche_value(val_ty(llptr), niche), DebugLoc::None)
+}
+
+/// Translate the GEP indices of a `StructWrappedNullablePointer`'s
+/// discriminant field (after the leading 0) back into field indices in
+/// declaration order, which is how debuginfo describes the fields.
+pub fn discrfield_source_path<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
+                                        nonnull: &Struct<'tcx>,
+                                        discrfield: &DiscrField) -> Vec<uint> {
+    // The fields of an enum variant are never reordered.
+    let mut path = vec![discrfield[1]];
+    let mut ty = nonnull.fields[discrfield[1]];
+    for &ix in &discrfield[2..] {
+        let (source_ix, field_ty) = match ty.sty {
+            ty::ty_struct(..) => match *represent_type(cx, ty) {
+                Univariant(ref st, _) => {
+                    let source_ix = st.memory_order()[ix];
+                    (source_ix, st.fields[source_ix])
+                }
+                _ => cx.sess().bug("discrfield_source_path: struct is not univariant")
+            },
+            ty::ty_tup(ref tys) => (ix, tys[ix]),
+            ty::ty_vec(ety, _) => (ix, ety),
+            // The address of a fat pointer is always the last step.
+            _ => (ix, ty)
+        };
+        path.push(source_ix);
+        ty = field_ty;
+    }
+    path
+}
+
+/// The value of type `llty` that the discriminant field of a nullable
+/// pointer-like representation holds for the dataless case.
+fn niche_value(llty: Type, niche: Disr) -> ValueRef {
+    if niche == 0 {
+        C_null(llty)
+    } else {
+        C_integral(llty, niche, false)
+    }
 }
 
 /// Helper for cases where the discriminant is simply loaded.
@@ -842,20 +1003,20 @@ pub fn trans_set_discr<'blk, 'tcx>(bcx: Block<'blk, 'tcx>, r: &Repr<'tcx>,
             assert_eq!(discr, 0);
             if dtor {
                 Store(bcx, C_u8(bcx.ccx(), 1),
-                    GEPi(bcx, val, &[0, st.fields.len() - 1]));
+                    struct_field_ptr(bcx, st, val, st.fields.len() - 1, false));
             }
         }
-        RawNullablePointer { nndiscr, nnty, ..} => {
+        RawNullablePointer { nndiscr, nnty, niche, ..} => {
             if discr != nndiscr {
                 let llptrty = type_of::sizing_type_of(bcx.ccx(), nnty);
-                Store(bcx, C_null(llptrty), val)
+                Store(bcx, niche_value(llptrty, niche), val)
             }
         }
-        StructWrappedNullablePointer { nndiscr, ref discrfield, .. } => {
+        StructWrappedNullablePointer { nndiscr, ref discrfield, niche, .. } => {
             if discr != nndiscr {
                 let llptrptr = GEPi(bcx, val, &discrfield[..]);
                 let llptrty = val_ty(llptrptr).element_type();
-                Store(bcx, C_null(llptrty), llptrptr)
+                Store(bcx, niche_value(llptrty, niche), llptrptr)
             }
         }
     }
@@ -934,14 +1095,16 @@ pub fn struct_field_ptr<'blk, 'tcx>(bcx: Block<'blk, 'tcx>, st: &Struct<'tcx>, v
                                     ix: uint, needs_cast: bool) -> ValueRef {
     let val = if needs_cast {
         let ccx = bcx.ccx();
-        let fields = st.fields.iter().map(|&ty| type_of::type_of(ccx, ty)).collect::<Vec<_>>();
+        let fields = st.memory_order().into_iter().map(|i| {
+            type_of::type_of(ccx, st.fields[i])
+        }).collect::<Vec<_>>();
         let real_ty = Type::struct_(ccx, &fields[..], st.packed);
         PointerCast(bcx, val, real_ty.ptr_to())
     } else {
         val
     };
 
-    GEPi(bcx, val, &[0, ix])
+    GEPi(bcx, val, &[0, st.memory_index[ix]])
 }
 
 pub fn fold_variants<'blk, 'tcx, F>(bcx: Block<'blk, 'tcx>,
@@ -995,7 +1158,7 @@ pub fn trans_drop_flag_ptr<'blk, 'tcx>(mut bcx: Block<'blk, 'tcx>, r: &Repr<'tcx
     let ptr_ty = ty::mk_imm_ptr(bcx.tcx(), tcx.types.bool);
     match *r {
         Univariant(ref st, true) => {
-            let flag_ptr = GEPi(bcx, val, &[0, st.fields.len() - 1]);
+            let flag_ptr = struct_field_ptr(bcx, st, val, st.fields.len() - 1, false);
             datum::immediate_rvalue_bcx(bcx, flag_ptr, ptr_ty).to_expr_datumblock()
         }
         General(_, _, true) => {
@@ -1060,26 +1223,32 @@ pub fn trans_const<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>, r: &Repr<'tcx>, discr
             let contents = build_const_struct(ccx, st, vals);
             C_struct(ccx, &contents[..], st.packed)
         }
-        RawNullablePointer { nndiscr, nnty, .. } => {
+        RawNullablePointer { nndiscr, nnty, niche, .. } => {
             if discr == nndiscr {
                 assert_eq!(vals.len(), 1);
                 vals[0]
             } else {
-                C_null(type_of::sizing_type_of(ccx, nnty))
+                niche_value(type_of::sizing_type_of(ccx, nnty), niche)
             }
         }
-        StructWrappedNullablePointer { ref nonnull, nndiscr, .. } => {
+        StructWrappedNullablePointer { ref nonnull, nndiscr, ref discrfield, niche, .. } => {
             if discr == nndiscr {
                 C_struct(ccx, &build_const_struct(ccx,
                                                  nonnull,
                                                  vals),
                          false)
             } else {
-                let vals = nonnull.fields.iter().map(|&ty| {
+                let mut vals = nonnull.fields.iter().map(|&ty| {
                     // Always use null even if it's not the `discrfield`th
                     // field; see #8506.
                     C_null(type_of::sizing_type_of(ccx, ty))
                 }).collect::<Vec<ValueRef>>();
+                if niche != 0 {
+                    // The first index steps into the struct itself.
+                    let ix = discrfield[1];
+                    let llty = type_of::sizing_type_of(ccx, nonnull.fields[ix]);
+                    vals[ix] = const_with_niche(ccx, llty, &discrfield[2..], niche);
+                }
                 C_struct(ccx, &build_const_struct(ccx,
                                                  nonnull,
                                                  &vals[..]),
@@ -1089,22 +1258,67 @@ pub fn trans_const<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>, r: &Repr<'tcx>, discr
     }
 }
 
-/// Compute struct field offsets relative to struct begin.
-fn compute_struct_field_offsets<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
-                                          st: &Struct<'tcx>) -> Vec<u64> {
-    let mut offsets = vec!();
+/// Build a null constant of type `llty`, except that the element reached by
+/// following the GEP indices in `path` holds the value `niche`.
+fn const_with_niche(ccx: &CrateContext, llty: Type, path: &[uint], niche: Disr) -> ValueRef {
+    if path.len() == 0 {
+        return niche_value(llty, niche);
+    }
+    match llty.kind() {
+        TypeKind::Struct => {
+            let elts = llty.field_types().iter().enumerate().map(|(i, &ety)| {
+                if i == path[0] {
+                    const_with_niche(ccx, ety, &path[1..], niche)
+                } else {
+                    C_null(ety)
+                }
+            }).collect::<Vec<ValueRef>>();
+            C_struct(ccx, &elts[..], llty.is_packed())
+        }
+        TypeKind::Array => {
+            let ety = llty.element_type();
+            let elts = (0..llty.array_length()).map(|i| {
+                if i == path[0] {
+                    const_with_niche(ccx, ety, &path[1..], niche)
+                } else {
+                    C_null(ety)
+                }
+            }).collect::<Vec<ValueRef>>();
+            C_array(ety, &elts[..])
+        }
+        _ => ccx.sess().bug("const_with_niche: discriminant path through a non-aggregate")
+    }
+}
+
+/// Compute struct field offsets relative to struct begin, indexed in
+/// declaration order. Fields needn't be laid out in that order, so
+/// everything that needs to know where a field lives (constants, debuginfo,
+/// `-Z print-type-sizes`) must go through here.
+pub fn compute_struct_field_offsets<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
+                                              st: &Struct<'tcx>) -> Vec<u64> {
+    let mut offsets = vec![0; st.fields.len()];
 
     let mut offset = 0;
-    for &ty in &st.fields {
+    for i in st.memory_order() {
+        let ty = st.fields[i];
+        if !type_is_sized(ccx.tcx(), ty) {
+            // Only the last field can be unsized; it starts wherever its
+            // element or object type is aligned to.
+            let llty = type_of::in_memory_type_of(ccx, ty);
+            if !st.packed {
+                offset = roundup(offset, machine::llalign_of_min(ccx, llty));
+            }
+            offsets[i] = offset;
+            continue;
+        }
         let llty = type_of::sizing_type_of(ccx, ty);
         if !st.packed {
             let type_align = type_of::align_of(ccx, ty);
             offset = roundup(offset, type_align);
         }
-        offsets.push(offset);
+        offsets[i] = offset;
         offset += machine::llsize_of_alloc(ccx, llty);
     }
-    assert_eq!(st.fields.len(), offsets.len());
     offsets
 }
 
@@ -1126,7 +1340,8 @@ fn build_const_struct<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
     // offset of current value
     let mut offset = 0;
     let mut cfields = Vec::new();
-    for (&val, &target_offset) in vals.iter().zip(target_offsets.iter()) {
+    for i in st.memory_order() {
+        let (val, target_offset) = (vals[i], target_offsets[i]);
         if !st.packed {
             let val_align = machine::llalign_of_min(ccx, val_ty(val));
             offset = roundup(offset, val_align);
@@ -1187,7 +1402,7 @@ pub fn const_get_field(ccx: &CrateContext, r: &Repr, val: ValueRef,
                        _discr: Disr, ix: uint) -> ValueRef {
     match *r {
         CEnum(..) => ccx.sess().bug("element access in C-like enum const"),
-        Univariant(..) => const_struct_field(ccx, val, ix),
+        Univariant(ref st, _) => const_struct_field(ccx, val, st.memory_index[ix]),
         General(..) => const_struct_field(ccx, val, ix + 1),
         RawNullablePointer { .. } => {
             assert_eq!(ix, 0);
@@ -1218,3 +1433,192 @@ fn const_struct_field(ccx: &CrateContext, val: ValueRef, ix: uint) -> ValueRef {
         real_ix = real_ix + 1;
     }
 }
+
+/// Prints the layout of every struct and enum that was given a
+/// representation while translating the crate, for `-Z
+/// print-type-sizes`. Generic types are reported once per
+/// instantiation, largest first.
+pub fn print_type_sizes<'tcx>(shared_ccx: &SharedCrateContext<'tcx>) {
+    let mut seen = FnvHashSet();
+    let mut reports = Vec::new();
+    for ccx in shared_ccx.iter() {
+        // Computing the layouts may add more entries to the cache, so
+        // don't hold on to the borrow while doing so.
+        let reprs: Vec<(Ty<'tcx>, Rc<Repr<'tcx>>)> =
+            ccx.adt_reprs().borrow().iter().map(|(&t, r)| (t, r.clone())).collect();
+        for (t, repr) in reprs {
+            match t.sty {
+                ty::ty_struct(..) | ty::ty_enum(..) => {}
+                _ => continue
+            }
+            if !type_is_sized(ccx.tcx(), t) || !seen.insert(t) {
+                continue;
+            }
+            reports.push(type_size_report(&ccx, t, &*repr));
+        }
+    }
+
+    reports.sort_by(|a, b| {
+        match b.size.cmp(&a.size) {
+            Ordering::Equal => a.name.cmp(&b.name),
+            ordering => ordering,
+        }
+    });
+
+    for report in &reports {
+        println!("print-type-size type: `{}`: {} bytes, alignment: {} bytes",
+                 report.name, report.size, report.align);
+        for line in &report.lines {
+            println!("print-type-size     {}", line);
+        }
+    }
+}
+
+struct TypeSizeReport {
+    name: String,
+    size: u64,
+    align: u32,
+    lines: Vec<String>,
+}
+
+fn type_size_report<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
+                              t: Ty<'tcx>,
+                              r: &Repr<'tcx>)
+                              -> TypeSizeReport {
+    let llty = type_of::sizing_type_of(cx, t);
+    let mut lines = Vec::new();
+
+    match (r, &t.sty) {
+        (&CEnum(ity, _, _), _) => {
+            lines.push(format!("discriminant: {} bytes",
+                               machine::llsize_of_alloc(cx, ll_inttype(cx, ity))));
+        }
+        (&Univariant(ref st, dtor), &ty::ty_struct(def_id, substs)) => {
+            let fields = ty::struct_fields(cx.tcx(), def_id, substs);
+            let names = fields.iter().map(|f| f.name).collect();
+            report_struct_fields(cx, st, 0, names, dtor, "", &mut lines);
+        }
+        (&Univariant(ref st, dtor), &ty::ty_enum(def_id, substs)) => {
+            // A single-variant enum, laid out like a struct.
+            let variants = ty::substd_enum_variants(cx.tcx(), def_id, substs);
+            if let Some(variant) = variants.get(0) {
+                lines.push(format!("variant `{}`: {} bytes",
+                                   token::get_name(variant.name), st.size));
+                report_struct_fields(cx, st, 0, variant_field_names(&**variant),
+                                     dtor, "    ", &mut lines);
+            }
+        }
+        (&General(ity, ref sts, dtor), &ty::ty_enum(def_id, substs)) => {
+            lines.push(format!("discriminant: {} bytes",
+                               machine::llsize_of_alloc(cx, ll_inttype(cx, ity))));
+            let variants = ty::substd_enum_variants(cx.tcx(), def_id, substs);
+            for (st, variant) in sts.iter().zip(variants.iter()) {
+                lines.push(format!("variant `{}`: {} bytes",
+                                   token::get_name(variant.name), st.size));
+                // The first field of each case is the discriminant.
+                report_struct_fields(cx, st, 1, variant_field_names(&**variant),
+                                     dtor, "    ", &mut lines);
+            }
+        }
+        (&RawNullablePointer { nndiscr, nnty, niche, .. }, &ty::ty_enum(def_id, substs)) => {
+            let variants = ty::substd_enum_variants(cx.tcx(), def_id, substs);
+            for (discr, variant) in variants.iter().enumerate() {
+                if discr as Disr == nndiscr {
+                    let size = machine::llsize_of_alloc(cx, type_of::sizing_type_of(cx, nnty));
+                    lines.push(format!("variant `{}`: {} bytes",
+                                       token::get_name(variant.name), size));
+                    lines.push(format!("    field `.0`: {} bytes, offset: 0 bytes", size));
+                } else {
+                    lines.push(format!("variant `{}`: 0 bytes ({})",
+                                       token::get_name(variant.name), niche_description(niche)));
+                }
+            }
+        }
+        (&StructWrappedNullablePointer { ref nonnull, nndiscr, niche, .. },
+         &ty::ty_enum(def_id, substs)) => {
+            let variants = ty::substd_enum_variants(cx.tcx(), def_id, substs);
+            for (discr, variant) in variants.iter().enumerate() {
+                if discr as Disr == nndiscr {
+                    lines.push(format!("variant `{}`: {} bytes",
+                                       token::get_name(variant.name), nonnull.size));
+                    report_struct_fields(cx, nonnull, 0, variant_field_names(&**variant),
+                                         false, "    ", &mut lines);
+                } else {
+                    lines.push(format!("variant `{}`: 0 bytes ({})",
+                                       token::get_name(variant.name), niche_description(niche)));
+                }
+            }
+        }
+        _ => {
+            cx.sess().bug(&format!("type_size_report: unexpected representation for {}",
+                                   ty_to_string(cx.tcx(), t)));
+        }
+    }
+
+    TypeSizeReport {
+        name: ty_to_string(cx.tcx(), t),
+        size: machine::llsize_of_alloc(cx, llty),
+        align: machine::llalign_of_min(cx, llty),
+        lines: lines,
+    }
+}
+
+fn niche_description(niche: Disr) -> String {
+    if niche == 0 {
+        "encoded as a null pointer".to_string()
+    } else {
+        format!("encoded as the invalid value {} in a field", niche)
+    }
+}
+
+fn variant_field_names(variant: &ty::VariantInfo) -> Vec<ast::Name> {
+    match variant.arg_names {
+        Some(ref names) => names.iter().map(|ident| ident.name).collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Describes the fields of `st` starting at `first_field`, including
+/// any padding LLVM inserted between them. Fields without a name in
+/// `names` (tuple structs and variants) are numbered instead.
+fn report_struct_fields<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
+                                  st: &Struct<'tcx>,
+                                  first_field: usize,
+                                  names: Vec<ast::Name>,
+                                  has_drop_flag: bool,
+                                  indent: &str,
+                                  lines: &mut Vec<String>) {
+    let offsets = compute_struct_field_offsets(cx, st);
+    let num_fields = st.fields.len();
+    let mut end = if first_field > 0 {
+        let discr_ty = type_of::sizing_type_of(cx, st.fields[0]);
+        offsets[0] + machine::llsize_of_alloc(cx, discr_ty)
+    } else {
+        0
+    };
+
+    for i in st.memory_order().into_iter().filter(|&i| i >= first_field) {
+        let offset = offsets[i];
+        if offset > end {
+            lines.push(format!("{}padding: {} bytes", indent, offset - end));
+        }
+        let size = machine::llsize_of_alloc(cx, type_of::sizing_type_of(cx, st.fields[i]));
+        let index = i - first_field;
+        let name = if has_drop_flag && i == num_fields - 1 {
+            "drop flag".to_string()
+        } else {
+            match names.get(index) {
+                Some(&name) if name != token::special_names::unnamed_field => {
+                    format!("field `.{}`", token::get_name(name))
+                }
+                _ => format!("field `.{}`", index),
+            }
+        };
+        lines.push(format!("{}{}: {} bytes, offset: {} bytes", indent, name, size, offset));
+        end = offset + size;
+    }
+
+    if st.size > end {
+        lines.push(format!("{}end padding: {} bytes", indent, st.size - end));
+    }
+}
@@ -27,7 +27,7 @@
 
 pub use self::ValueOrigin::*;
 
-use super::CrateTranslation;
+use super::{CrateTranslation, ExportedSymbol};
 use super::ModuleTranslation;
 
 use back::link::{mangle_exported_name};
@@ -40,6 +40,7 @@ use middle::astencode;
 use middle::cfg;
 use middle::lang_items::{LangItem, ExchangeMallocFnLangItem, StartFnLangItem};
 use middle::weak_lang_items;
+use middle::reachable;
 use middle::subst::{Subst, Substs};
 use middle::ty::{self, Ty, ClosureTyper};
 use session::config::{self, NoDebugInfo};
@@ -52,7 +53,7 @@ use trans::callee;
 use trans::cleanup::CleanupMethods;
 use trans::cleanup;
 use trans::closure;
-use trans::common::{Block, C_bool, C_bytes_in_context, C_i32, C_integral};
+use trans::common::{Block, C_bool, C_bytes_in_context, C_cstr, C_i32, C_integral};
 use trans::common::{C_null, C_struct_in_context, C_u64, C_u8, C_undef};
 use trans::common::{CrateContext, ExternMap, FunctionContext};
 use trans::common::{Result, NodeIdAndSpan};
@@ -81,7 +82,7 @@ use trans::value::Value;
 use util::common::indenter;
 use util::ppaux::{Repr, ty_to_string};
 use util::sha2::Sha256;
-use util::nodemap::NodeMap;
+use util::nodemap::{FnvHashMap, NodeMap};
 
 use arena::TypedArena;
 use libc::{c_uint, uint64_t};
@@ -97,6 +98,7 @@ use syntax::attr::AttrMetaMethods;
 use syntax::attr;
 use syntax::codemap::Span;
 use syntax::parse::token::InternedString;
+use syntax::parse::token;
 use syntax::visit::Visitor;
 use syntax::visit;
 use syntax::{ast, ast_util, ast_map};
@@ -829,6 +831,9 @@ pub fn fail_if_zero_or_overflows<'blk, 'tcx>(
                                   ty_to_string(cx.tcx(), rhs_t)));
         }
     };
+    // The builder folds comparisons of constants, and `with_cond` emits
+    // nothing for a condition that is constant false, so neither check costs
+    // anything when the divisor is a suitable constant.
     let bcx = with_cond(cx, is_zero, |bcx| {
         controlflow::trans_fail(bcx, call_info, InternedString::new(zero_text))
     });
@@ -911,7 +916,18 @@ pub fn invoke<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
         return (C_null(Type::i8(bcx.ccx())), bcx);
     }
 
-    let attributes = get_fn_llvm_attributes(bcx.ccx(), fn_ty);
+    let mut attributes = get_fn_llvm_attributes(bcx.ccx(), fn_ty);
+
+    // A diverging call never returns normally, so mark the call site itself
+    // as such (the callee may be a fn pointer we know nothing about). Marking
+    // it cold as well keeps paths like failed bounds checks out of the way of
+    // the optimizer, e.g. when vectorizing the loop they sit in.
+    if let ty::ty_bare_fn(_, ref f) = fn_ty.sty {
+        if f.sig.0.output == ty::FnDiverging {
+            attributes.arg(llvm::FunctionIndex as uint, llvm::NoReturnAttribute)
+                      .arg(llvm::FunctionIndex as uint, llvm::ColdAttribute);
+        }
+    }
 
     match bcx.opt_node_id {
         None => {
@@ -1407,6 +1423,7 @@ pub fn new_fn_ctxt<'a, 'tcx>(ccx: &'a CrateContext<'a, 'tcx>,
           caller_expects_out_pointer: uses_outptr,
           lllocals: RefCell::new(NodeMap()),
           llupvars: RefCell::new(NodeMap()),
+          slice_lens: RefCell::new(NodeMap()),
           id: id,
           param_substs: param_substs,
           span: sp,
@@ -1679,6 +1696,50 @@ pub fn build_return_block<'blk, 'tcx>(fcx: &FunctionContext<'blk, 'tcx>,
 // trans_closure: Builds an LLVM function out of a source function.
 // If the function closes over its environment a closure will be
 // returned.
+/// Returns the `__rust_profile_enter` hook called by `-Z instrument-functions`.
+/// It is declared weak, so programs still link when nothing defines it.
+fn get_profile_enter_hook(ccx: &CrateContext) -> ValueRef {
+    let name = "__rust_profile_enter";
+    match ccx.externs().borrow().get(name) {
+        Some(n) => return *n,
+        None => ()
+    }
+
+    let ty = Type::func(&[Type::i8p(ccx)], &Type::void(ccx));
+    let f = decl_cdecl_fn(ccx, name, ty, ty::mk_nil(ccx.tcx()));
+    if unsafe { llvm::LLVMIsDeclaration(f) } != 0 {
+        llvm::SetLinkage(f, llvm::ExternalWeakLinkage);
+    }
+
+    ccx.externs().borrow_mut().insert(name.to_string(), f);
+    f
+}
+
+/// Calls the profiling hook, if one is linked in, with the symbol name of the
+/// function being entered. Functions that ask to be inlined, or that opt out
+/// with `#[no_instrument_function]`, are left alone.
+fn instrument_function_entry<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
+                                         fn_ast_id: ast::NodeId)
+                                         -> Block<'blk, 'tcx> {
+    let ccx = bcx.ccx();
+    let attrs = ccx.tcx().map.attrs(fn_ast_id);
+    if attr::requests_inline(attrs) || attr::contains_name(attrs, "no_instrument_function") {
+        return bcx;
+    }
+
+    let hook = get_profile_enter_hook(ccx);
+    let name = unsafe {
+        let name = CStr::from_ptr(llvm::LLVMGetValueName(bcx.fcx.llfn)).to_bytes();
+        token::intern_and_get_ident(str::from_utf8(name).unwrap())
+    };
+    let llname = PointerCast(bcx, C_cstr(ccx, name, true), Type::i8p(ccx));
+    let hooked = ICmp(bcx, llvm::IntNE, hook, C_null(val_ty(hook)), DebugLoc::None);
+    with_cond(bcx, hooked, |bcx| {
+        Call(bcx, hook, &[llname], None, DebugLoc::None);
+        bcx
+    })
+}
+
 pub fn trans_closure<'a, 'b, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
                                    decl: &ast::FnDecl,
                                    body: &ast::Block,
@@ -1714,6 +1775,10 @@ pub fn trans_closure<'a, 'b, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
                       &arena);
     let mut bcx = init_function(&fcx, false, output_type);
 
+    if ccx.sess().opts.debugging_opts.instrument_functions {
+        bcx = instrument_function_entry(bcx, fn_ast_id);
+    }
+
     // cleanup scope for the incoming arguments
     let fn_cleanup_debug_loc =
         debuginfo::get_cleanup_debug_loc_for_ast_node(ccx, fn_ast_id, body.span, true);
@@ -2778,7 +2843,8 @@ pub fn get_item_val(ccx: &CrateContext, id: ast::NodeId) -> ValueRef {
                 ast::ForeignItemFn(..) => {
                     let abi = ccx.tcx().map.get_foreign_abi(id);
                     let ty = ty::node_id_to_type(ccx.tcx(), ni.id);
-                    let name = foreign::link_name(&*ni);
+                    let name = foreign::decorated_link_name(ccx, abi, ty,
+                                                            &foreign::link_name(&*ni));
                     let llfn = foreign::register_foreign_item_fn(ccx, abi, ty, &name);
                     set_llvm_fn_attrs(ccx, &ni.attrs, llfn);
                     llfn
@@ -2933,20 +2999,6 @@ fn internalize_symbols(cx: &SharedCrateContext, reachable: &HashSet<String>) {
     unsafe {
         let mut declared = HashSet::new();
 
-        let iter_globals = |llmod| {
-            ValueIter {
-                cur: llvm::LLVMGetFirstGlobal(llmod),
-                step: llvm::LLVMGetNextGlobal,
-            }
-        };
-
-        let iter_functions = |llmod| {
-            ValueIter {
-                cur: llvm::LLVMGetFirstFunction(llmod),
-                step: llvm::LLVMGetNextFunction,
-            }
-        };
-
         // Collect all external declarations in all compilation units.
         for ccx in cx.iter() {
             for val in iter_globals(ccx.llmod()).chain(iter_functions(ccx.llmod())) {
@@ -2985,29 +3037,111 @@ fn internalize_symbols(cx: &SharedCrateContext, reachable: &HashSet<String>) {
             }
         }
     }
+}
 
+/// Give every external definition that isn't reachable from other crates
+/// hidden visibility, which keeps it out of the dynamic symbol table of the
+/// final product. The metadata symbol lives in a module of its own and keeps
+/// its default visibility, so downstream crates can still find it.
+fn hide_unexported_symbols(cx: &SharedCrateContext, reachable: &HashSet<String>) {
+    unsafe {
+        for ccx in cx.iter() {
+            for val in iter_globals(ccx.llmod()).chain(iter_functions(ccx.llmod())) {
+                if llvm::LLVMGetLinkage(val) != llvm::ExternalLinkage as c_uint ||
+                   llvm::LLVMIsDeclaration(val) != 0 {
+                    continue
+                }
 
-    struct ValueIter {
-        cur: ValueRef,
-        step: unsafe extern "C" fn(ValueRef) -> ValueRef,
+                let name = CStr::from_ptr(llvm::LLVMGetValueName(val)).to_bytes();
+                if !reachable.contains(str::from_utf8(name).unwrap()) {
+                    llvm::SetVisibility(val, llvm::HiddenVisibility);
+                }
+            }
+        }
     }
+}
 
-    impl Iterator for ValueIter {
-        type Item = ValueRef;
+/// The definitions the final product exports: those left with external
+/// linkage and default visibility once `internalize_symbols` and
+/// `hide_unexported_symbols` have run, plus the crate metadata.
+fn exported_symbols(cx: &SharedCrateContext) -> Vec<ExportedSymbol> {
+    let item_ids: FnvHashMap<String, ast::NodeId> =
+        cx.item_symbols().borrow().iter().map(|(&id, sym)| (sym.clone(), id)).collect();
+    let path_of = |name: &str| match item_ids.get(name) {
+        Some(&id) => {
+            format!("{}::{}", cx.link_meta().crate_name,
+                    ty::item_path_str(cx.tcx(), local_def(id)))
+        }
+        None => name.to_string(),
+    };
 
-        fn next(&mut self) -> Option<ValueRef> {
-            let old = self.cur;
-            if !old.is_null() {
-                self.cur = unsafe {
-                    let step: unsafe extern "C" fn(ValueRef) -> ValueRef =
-                        mem::transmute_copy(&self.step);
-                    step(old)
-                };
-                Some(old)
-            } else {
-                None
+    let mut symbols = Vec::new();
+    unsafe {
+        let is_exported = |val: ValueRef| {
+            llvm::LLVMGetLinkage(val) == llvm::ExternalLinkage as c_uint &&
+                llvm::LLVMIsDeclaration(val) == 0 &&
+                llvm::LLVMGetVisibility(val) == llvm::LLVMDefaultVisibility as c_uint
+        };
+        let name_of = |val: ValueRef| {
+            str::from_utf8(CStr::from_ptr(llvm::LLVMGetValueName(val)).to_bytes())
+                .unwrap().to_string()
+        };
+
+        for ccx in cx.iter() {
+            for val in iter_functions(ccx.llmod()).filter(|&val| is_exported(val)) {
+                let name = name_of(val);
+                symbols.push(ExportedSymbol { path: path_of(&name), name: name, kind: "fn" });
+            }
+            for val in iter_globals(ccx.llmod()).filter(|&val| is_exported(val)) {
+                let name = name_of(val);
+                let kind = if name.starts_with("vtable") { "vtable" } else { "static" };
+                symbols.push(ExportedSymbol { path: path_of(&name), name: name, kind: kind });
             }
         }
+        for val in iter_globals(cx.metadata_llmod()).filter(|&val| is_exported(val)) {
+            let name = name_of(val);
+            symbols.push(ExportedSymbol { path: name.clone(), name: name, kind: "metadata" });
+        }
+    }
+
+    symbols.sort_by(|a, b| a.name.cmp(&b.name));
+    symbols
+}
+
+struct ValueIter {
+    cur: ValueRef,
+    step: unsafe extern "C" fn(ValueRef) -> ValueRef,
+}
+
+impl Iterator for ValueIter {
+    type Item = ValueRef;
+
+    fn next(&mut self) -> Option<ValueRef> {
+        let old = self.cur;
+        if !old.is_null() {
+            self.cur = unsafe {
+                let step: unsafe extern "C" fn(ValueRef) -> ValueRef =
+                    mem::transmute_copy(&self.step);
+                step(old)
+            };
+            Some(old)
+        } else {
+            None
+        }
+    }
+}
+
+unsafe fn iter_globals(llmod: llvm::ModuleRef) -> ValueIter {
+    ValueIter {
+        cur: llvm::LLVMGetFirstGlobal(llmod),
+        step: llvm::LLVMGetNextGlobal,
+    }
+}
+
+unsafe fn iter_functions(llmod: llvm::ModuleRef) -> ValueIter {
+    ValueIter {
+        cur: llvm::LLVMGetFirstFunction(llmod),
+        step: llvm::LLVMGetNextFunction,
     }
 }
 
@@ -3097,6 +3231,9 @@ pub fn trans_crate<'tcx>(analysis: ty::CrateAnalysis<'tcx>)
             }
         }
     }
+    if shared_ccx.sess().print_type_sizes() {
+        adt::print_type_sizes(&shared_ccx);
+    }
     if shared_ccx.sess().count_llvm_insns() {
         for (k, v) in &*shared_ccx.stats().llvm_insns.borrow() {
             println!("{:7} {}", *v, *k);
@@ -3139,6 +3276,17 @@ pub fn trans_crate<'tcx>(analysis: ty::CrateAnalysis<'tcx>)
         internalize_symbols(&shared_ccx, &reachable.iter().cloned().collect());
     }
 
+    if shared_ccx.sess().opts.cg.default_hidden_visibility.unwrap_or(false) {
+        hide_unexported_symbols(&shared_ccx, &reachable.iter().cloned().collect());
+    }
+
+    let exported_symbols = exported_symbols(&shared_ccx);
+    let unmangled_symbols = reachable::unmangled_symbols(shared_ccx.tcx(),
+                                                         shared_ccx.reachable());
+    let unmangled_symbols = unmangled_symbols.into_iter().map(|(name, id)| {
+        (name, shared_ccx.tcx().map.span(id))
+    }).collect();
+
     let metadata_module = ModuleTranslation {
         llcx: shared_ccx.metadata_llcx(),
         llmod: shared_ccx.metadata_llmod(),
@@ -3152,6 +3300,8 @@ pub fn trans_crate<'tcx>(analysis: ty::CrateAnalysis<'tcx>)
         link: link_meta,
         metadata: metadata,
         reachable: reachable,
+        exported_symbols: exported_symbols,
+        unmangled_symbols: unmangled_symbols,
         crate_formats: formats,
         no_builtins: no_builtins,
     };
@@ -105,6 +105,22 @@ pub fn CondBr(cx: Block,
     B(cx).cond_br(if_, then, else_);
 }
 
+pub fn CondBrWeighted(cx: Block,
+                      if_: ValueRef,
+                      then: BasicBlockRef,
+                      else_: BasicBlockRef,
+                      weights: (u32, u32),
+                      debug_loc: DebugLoc) {
+    if cx.unreachable.get() {
+        return;
+    }
+    check_not_terminated(cx);
+    terminate(cx, "CondBr");
+    debug_loc.apply(cx.fcx);
+    let (then_weight, else_weight) = weights;
+    B(cx).cond_br_weighted(if_, then, else_, then_weight, else_weight);
+}
+
 pub fn Switch(cx: Block, v: ValueRef, else_: BasicBlockRef, num_cases: uint)
     -> ValueRef {
     if cx.unreachable.get() { return _Undef(v); }
@@ -600,6 +616,15 @@ pub fn VolatileLoad(cx: Block, pointer_val: ValueRef) -> ValueRef {
     }
 }
 
+pub fn UnalignedLoad(cx: Block, pointer_val: ValueRef) -> ValueRef {
+    unsafe {
+        if cx.unreachable.get() {
+            return llvm::LLVMGetUndef(Type::nil(cx.ccx()).to_ref());
+        }
+        B(cx).unaligned_load(pointer_val)
+    }
+}
+
 pub fn AtomicLoad(cx: Block, pointer_val: ValueRef, order: AtomicOrdering) -> ValueRef {
     unsafe {
         let ccx = cx.fcx.ccx;
@@ -656,6 +681,11 @@ pub fn VolatileStore(cx: Block, val: ValueRef, ptr: ValueRef) {
     B(cx).volatile_store(val, ptr)
 }
 
+pub fn UnalignedStore(cx: Block, val: ValueRef, ptr: ValueRef) {
+    if cx.unreachable.get() { return; }
+    B(cx).unaligned_store(val, ptr)
+}
+
 pub fn AtomicStore(cx: Block, val: ValueRef, ptr: ValueRef, order: AtomicOrdering) {
     if cx.unreachable.get() { return; }
     B(cx).atomic_store(val, ptr, order)
@@ -140,6 +140,26 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
         }
     }
 
+    /// Like `cond_br`, but tells LLVM how often each destination is expected
+    /// to be taken relative to the other.
+    pub fn cond_br_weighted(&self, cond: ValueRef, then_llbb: BasicBlockRef,
+                            else_llbb: BasicBlockRef, then_weight: u32, else_weight: u32) {
+        self.count_insn("condbr");
+        unsafe {
+            let br = llvm::LLVMBuildCondBr(self.llbuilder, cond, then_llbb, else_llbb);
+            let name = "branch_weights";
+            let md = [llvm::LLVMMDStringInContext(self.ccx.llcx(),
+                                                  name.as_ptr() as *const c_char,
+                                                  name.len() as c_uint),
+                      C_i32(self.ccx, then_weight as i32),
+                      C_i32(self.ccx, else_weight as i32)];
+            llvm::LLVMSetMetadata(br, llvm::MD_prof as c_uint,
+                                  llvm::LLVMMDNodeInContext(self.ccx.llcx(),
+                                                            md.as_ptr(),
+                                                            md.len() as c_uint));
+        }
+    }
+
     pub fn switch(&self, v: ValueRef, else_llbb: BasicBlockRef, num_cases: uint) -> ValueRef {
         unsafe {
             llvm::LLVMBuildSwitch(self.llbuilder, v, else_llbb, num_cases as c_uint)
@@ -468,6 +488,15 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
         }
     }
 
+    pub fn unaligned_load(&self, ptr: ValueRef) -> ValueRef {
+        self.count_insn("load.unaligned");
+        unsafe {
+            let insn = llvm::LLVMBuildLoad(self.llbuilder, ptr, noname());
+            llvm::LLVMSetAlignment(insn, 1);
+            insn
+        }
+    }
+
     pub fn atomic_load(&self, ptr: ValueRef, order: AtomicOrdering) -> ValueRef {
         self.count_insn("load.atomic");
         unsafe {
@@ -532,6 +561,18 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
         }
     }
 
+    pub fn unaligned_store(&self, val: ValueRef, ptr: ValueRef) {
+        debug!("Store {} -> {}",
+               self.ccx.tn().val_to_string(val),
+               self.ccx.tn().val_to_string(ptr));
+        assert!(!self.llbuilder.is_null());
+        self.count_insn("store.unaligned");
+        unsafe {
+            let insn = llvm::LLVMBuildStore(self.llbuilder, val, ptr);
+            llvm::LLVMSetAlignment(insn, 1);
+        }
+    }
+
     pub fn atomic_store(&self, val: ValueRef, ptr: ValueRef, order: AtomicOrdering) {
         debug!("Store {} -> {}",
                self.ccx.tn().val_to_string(val),
@@ -118,12 +118,14 @@ pub fn compute_abi_info(ccx: &CrateContext,
         },
         "aarch64" => cabi_aarch64::compute_abi_info(ccx, atys, rty, ret_def),
         "arm" => {
-            let flavor = if ccx.sess().target.target.target_os == "ios" {
+            let target = &ccx.sess().target.target;
+            let flavor = if target.target_os == "ios" {
                 cabi_arm::Flavor::Ios
             } else {
                 cabi_arm::Flavor::General
             };
-            cabi_arm::compute_abi_info(ccx, atys, rty, ret_def, flavor)
+            let hard_float = target.llvm_target.ends_with("hf");
+            cabi_arm::compute_abi_info(ccx, atys, rty, ret_def, flavor, hard_float)
         },
         "mips" => cabi_mips::compute_abi_info(ccx, atys, rty, ret_def),
         "powerpc" => cabi_powerpc::compute_abi_info(ccx, atys, rty, ret_def),
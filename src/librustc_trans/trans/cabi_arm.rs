@@ -130,11 +130,57 @@ fn ty_size(ty: Type, align_fn: TyAlignFn) -> uint {
     }
 }
 
-fn classify_ret_ty(ccx: &CrateContext, ty: Type, align_fn: TyAlignFn) -> ArgType {
+/// Returns the base type and member count of a homogeneous floating-point
+/// aggregate: a struct or array made up, at any depth, of one to four members
+/// of the same floating-point or vector type. Under the hard-float variant of
+/// the AAPCS these are passed and returned in VFP registers.
+fn homogeneous_aggregate(ty: Type) -> Option<(Type, uint)> {
+    fn visit(ty: Type, base: &mut Option<Type>, count: &mut uint) -> bool {
+        match ty.kind() {
+            Float | Double | Vector => {
+                match *base {
+                    Some(b) if b != ty => return false,
+                    _ => *base = Some(ty),
+                }
+                *count += 1;
+                *count <= 4
+            }
+            Struct => {
+                !ty.is_packed() &&
+                    ty.field_types().iter().all(|&t| visit(t, base, count))
+            }
+            Array => {
+                let elt = ty.element_type();
+                (0..ty.array_length()).all(|_| visit(elt, base, count))
+            }
+            _ => false
+        }
+    }
+
+    match ty.kind() {
+        Struct | Array => {}
+        _ => return None
+    }
+    let mut base = None;
+    let mut count = 0;
+    if visit(ty, &mut base, &mut count) && count > 0 {
+        base.map(|b| (b, count))
+    } else {
+        None
+    }
+}
+
+fn classify_ret_ty(ccx: &CrateContext,
+                   ty: Type,
+                   align_fn: TyAlignFn,
+                   hard_float: bool) -> ArgType {
     if is_reg_ty(ty) {
         let attr = if ty == Type::i1(ccx) { Some(ZExtAttribute) } else { None };
         return ArgType::direct(ty, None, None, attr);
     }
+    if hard_float && homogeneous_aggregate(ty).is_some() {
+        return ArgType::direct(ty, None, None, None);
+    }
     let size = ty_size(ty, align_fn);
     if size <= 4 {
         let llty = if size <= 1 {
@@ -149,11 +195,17 @@ fn classify_ret_ty(ccx: &CrateContext, ty: Type, align_fn: TyAlignFn) -> ArgType
     ArgType::indirect(ty, Some(StructRetAttribute))
 }
 
-fn classify_arg_ty(ccx: &CrateContext, ty: Type, align_fn: TyAlignFn) -> ArgType {
+fn classify_arg_ty(ccx: &CrateContext,
+                   ty: Type,
+                   align_fn: TyAlignFn,
+                   hard_float: bool) -> ArgType {
     if is_reg_ty(ty) {
         let attr = if ty == Type::i1(ccx) { Some(ZExtAttribute) } else { None };
         return ArgType::direct(ty, None, None, attr);
     }
+    if hard_float && homogeneous_aggregate(ty).is_some() {
+        return ArgType::direct(ty, None, None, None);
+    }
     let align = align_fn(ty);
     let size = ty_size(ty, align_fn);
     let llty = if align <= 4 {
@@ -179,7 +231,8 @@ pub fn compute_abi_info(ccx: &CrateContext,
                         atys: &[Type],
                         rty: Type,
                         ret_def: bool,
-                        flavor: Flavor) -> FnType {
+                        flavor: Flavor,
+                        hard_float: bool) -> FnType {
     let align_fn = match flavor {
         Flavor::General => general_ty_align as TyAlignFn,
         Flavor::Ios => ios_ty_align as TyAlignFn,
@@ -187,12 +240,12 @@ pub fn compute_abi_info(ccx: &CrateContext,
 
     let mut arg_tys = Vec::new();
     for &aty in atys {
-        let ty = classify_arg_ty(ccx, aty, align_fn);
+        let ty = classify_arg_ty(ccx, aty, align_fn, hard_float);
         arg_tys.push(ty);
     }
 
     let ret_ty = if ret_def {
-        classify_ret_ty(ccx, rty, align_fn)
+        classify_ret_ty(ccx, rty, align_fn, hard_float)
     } else {
         ArgType::direct(Type::void(ccx), None, None, None)
     };
@@ -184,6 +184,11 @@ fn classify_ty(ty: Type) -> Vec<RegClass> {
             (SSEDs,       SSEUp) |
             (SSEInt(_),   SSEUp) => return,
 
+            // Two floats sharing an eightbyte are passed as one <2 x float>,
+            // whichever of them was classified first.
+            (SSEFs,       SSEFv) |
+            (SSEFv,       SSEFs) => SSEFv,
+
             (_,           _) => newv
         };
         cls[i] = to_write;
@@ -412,18 +417,50 @@ pub fn compute_abi_info(ccx: &CrateContext,
         }
     }
 
-    let mut arg_tys = Vec::new();
-    for t in atys {
-        let ty = x86_64_ty(ccx, *t, |cls| cls.is_pass_byval(), ByValAttribute);
-        arg_tys.push(ty);
-    }
+    // The registers still available for passing arguments: an aggregate
+    // that does not fit in the remaining ones is passed in memory as a
+    // whole, never split between registers and the stack.
+    let mut int_regs = 6; // RDI, RSI, RDX, RCX, R8, R9
+    let mut sse_regs = 8; // XMM0-7
 
     let ret_ty = if ret_def {
-        x86_64_ty(ccx, rty, |cls| cls.is_ret_bysret(), StructRetAttribute)
+        x86_64_ty(ccx, rty, |cls| {
+            if cls.is_ret_bysret() {
+                // The hidden `sret` pointer takes up an integer register.
+                int_regs -= 1;
+                true
+            } else {
+                false
+            }
+        }, StructRetAttribute)
     } else {
         ArgType::direct(Type::void(ccx), None, None, None)
     };
 
+    let mut arg_tys = Vec::new();
+    for t in atys {
+        let ty = x86_64_ty(ccx, *t, |cls| {
+            let needed_int = cls.iter().filter(|&&c| c == Int).count() as int;
+            let needed_sse = cls.iter().filter(|c| c.is_sse()).count() as int;
+            let in_mem = cls.is_pass_byval() ||
+                         int_regs < needed_int ||
+                         sse_regs < needed_sse;
+            if !in_mem {
+                int_regs -= needed_int;
+                sse_regs -= needed_sse;
+            }
+            in_mem
+        }, ByValAttribute);
+
+        if t.is_reg_ty() {
+            match t.kind() {
+                Float | Double => sse_regs = cmp::max(sse_regs - 1, 0),
+                _ => int_regs = cmp::max(int_regs - 1, 0),
+            }
+        }
+        arg_tys.push(ty);
+    }
+
     return FnType {
         arg_tys: arg_tys,
         ret_ty: ret_ty,
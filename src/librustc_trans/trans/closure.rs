@@ -8,24 +8,28 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use back::link::mangle_internal_name_by_path_and_seq;
-use llvm::ValueRef;
+use arena::TypedArena;
+use back::link::{self, mangle_internal_name_by_path_and_seq};
+use llvm::{get_param, ValueRef};
 use middle::mem_categorization::Typer;
 use trans::adt;
 use trans::base::*;
 use trans::build::*;
+use trans::callee::{self, ArgVals, Callee};
 use trans::cleanup::{CleanupMethods, ScopeId};
 use trans::common::*;
 use trans::datum::{Datum, rvalue_scratch_datum};
 use trans::datum::{Rvalue, ByValue};
-use trans::debuginfo;
+use trans::debuginfo::{self, DebugLoc};
 use trans::expr;
 use trans::monomorphize::{self, MonoId};
 use trans::type_of::*;
-use middle::ty::{self, ClosureTyper};
+use middle::ty::{self, ClosureTyper, Ty};
 use middle::subst::{Substs};
 use session::config::FullDebugInfo;
+use util::ppaux::Repr;
 
+use syntax::abi;
 use syntax::ast;
 use syntax::ast_util;
 
@@ -155,7 +159,7 @@ pub fn get_or_create_declaration_if_closure<'a, 'tcx>(ccx: &CrateContext<'a, 'tc
     }
 
     let symbol = ccx.tcx().map.with_path(closure_id.node, |path| {
-        mangle_internal_name_by_path_and_seq(path, "closure")
+        mangle_internal_name_by_path_and_seq(ccx, path, "closure")
     });
 
     let llfn = decl_internal_rust_fn(ccx, function_type, &symbol[..]);
@@ -259,3 +263,81 @@ pub fn trans_closure_expr<'a, 'tcx>(dest: Dest<'a, 'tcx>,
 
     Some(bcx)
 }
+
+/// Returns a function of type `fn_ty` which calls the closure of type
+/// `closure_ty`, for when a closure that captures nothing is coerced to a fn
+/// pointer. Such a closure never looks at its environment, so it is just
+/// passed an undefined one.
+pub fn trans_closure_fn_pointer_shim<'a, 'tcx>(ccx: &'a CrateContext<'a, 'tcx>,
+                                               closure_ty: Ty<'tcx>,
+                                               fn_ty: Ty<'tcx>)
+                                               -> ValueRef {
+    let _icx = push_ctxt("closure::trans_closure_fn_pointer_shim");
+    let tcx = ccx.tcx();
+
+    debug!("trans_closure_fn_pointer_shim(closure_ty={}, fn_ty={})",
+           closure_ty.repr(tcx), fn_ty.repr(tcx));
+
+    let (closure_id, substs) = match closure_ty.sty {
+        ty::ty_closure(closure_id, substs) => (closure_id, substs),
+        _ => tcx.sess.bug(&format!("trans_closure_fn_pointer_shim invoked on non-closure: {}",
+                                   closure_ty.repr(tcx)))
+    };
+    let llclosure = get_or_create_declaration_if_closure(ccx, closure_id, substs).unwrap().val;
+
+    let fn_ty = erase_regions(tcx, &fn_ty);
+    let sig = match fn_ty.sty {
+        ty::ty_bare_fn(None, ref f) => ty::erase_late_bound_regions(tcx, &f.sig),
+        _ => tcx.sess.bug(&format!("trans_closure_fn_pointer_shim invoked on non-fn-pointer: {}",
+                                   fn_ty.repr(tcx)))
+    };
+
+    // The closure takes its environment first, and then its arguments as a
+    // tuple which the "rust-call" ABI spreads out.
+    let self_ty = self_type_for_closure(ccx, closure_id, closure_ty);
+    let closure_fn_ty = ty::mk_bare_fn(tcx, None, tcx.mk_bare_fn(ty::BareFnTy {
+        unsafety: ast::Unsafety::Normal,
+        abi: abi::RustCall,
+        sig: ty::Binder(ty::FnSig {
+            inputs: vec![self_ty, ty::mk_tup(tcx, sig.inputs.clone())],
+            output: sig.output,
+            variadic: false
+        })
+    }));
+
+    let function_name = link::mangle_internal_name_by_type_and_seq(ccx, fn_ty,
+                                                                   "closure_fn_pointer_shim");
+    let llfn = decl_internal_rust_fn(ccx, fn_ty, &function_name[..]);
+
+    let empty_substs = tcx.mk_substs(Substs::trans_empty());
+    let (block_arena, fcx): (TypedArena<_>, FunctionContext);
+    block_arena = TypedArena::new();
+    fcx = new_fn_ctxt(ccx,
+                      llfn,
+                      ast::DUMMY_NODE_ID,
+                      false,
+                      sig.output,
+                      empty_substs,
+                      None,
+                      &block_arena);
+    let mut bcx = init_function(&fcx, false, sig.output);
+
+    let mut llargs = vec![C_undef(type_of_explicit_arg(ccx, self_ty))];
+    llargs.extend((0..sig.inputs.len()).map(|i| get_param(fcx.llfn, fcx.arg_pos(i) as u32)));
+    assert!(!fcx.needs_ret_allocas);
+
+    let dest = fcx.llretslotptr.get().map(|_|
+        expr::SaveIn(fcx.get_ret_slot(bcx, sig.output, "ret_slot"))
+    );
+
+    bcx = callee::trans_call_inner(bcx,
+                                   DebugLoc::None,
+                                   closure_fn_ty,
+                                   |bcx, _| Callee { bcx: bcx, data: callee::Fn(llclosure) },
+                                   ArgVals(&llargs[..]),
+                                   dest).bcx;
+
+    finish_fn(&fcx, bcx, sig.output, DebugLoc::None);
+
+    llfn
+}
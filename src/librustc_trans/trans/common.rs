@@ -308,13 +308,14 @@ pub fn return_type_is_void(ccx: &CrateContext, ty: Ty) -> bool {
     ty::type_is_nil(ty) || ty::type_is_empty(ccx.tcx(), ty)
 }
 
-/// Generates a unique symbol based off the name given. This is used to create
-/// unique symbols for things like closures.
-pub fn gensym_name(name: &str) -> PathElem {
-    let num = token::gensym(name).usize();
+/// Generates a path element based off the name given that is unique among the
+/// symbols starting with `prefix`. This is used to create unique symbols for
+/// things like closures.
+pub fn seq_name(ccx: &CrateContext, prefix: &str, name: &str) -> PathElem {
+    let num = ccx.next_symbol_seq(&format!("{}{}", prefix, name));
     // use one colon which will get translated to a period by the mangler, and
-    // we're guaranteed that `num` is globally unique for this crate.
-    PathName(token::gensym(&format!("{}:{}", name, num)))
+    // `num` is unique for this prefix and name.
+    PathName(token::intern(&format!("{}:{}", name, num)))
 }
 
 /*
@@ -431,6 +432,11 @@ pub struct FunctionContext<'a, 'tcx: 'a> {
     // Same as above, but for closure upvars
     pub llupvars: RefCell<NodeMap<ValueRef>>,
 
+    // Lengths of slices held in immutable locals, as last loaded by
+    // `trans_index`, along with the basic block that load is known to
+    // dominate.
+    pub slice_lens: RefCell<NodeMap<(BasicBlockRef, ValueRef)>>,
+
     // The NodeId of the function, or -1 if it doesn't correspond to
     // a user-defined function.
     pub id: ast::NodeId,
@@ -871,8 +877,7 @@ pub fn C_cstr(cx: &CrateContext, s: InternedString, null_terminated: bool) -> Va
                                                 s.len() as c_uint,
                                                 !null_terminated as Bool);
 
-        let gsym = token::gensym("str");
-        let buf = CString::new(format!("str{}", gsym.usize()));
+        let buf = CString::new(format!("str{}", cx.next_symbol_seq("str")));
         let buf = buf.unwrap();
         let g = llvm::LLVMAddGlobal(cx.llmod(), val_ty(sc).to_ref(), buf.as_ptr());
         llvm::LLVMSetInitializer(g, sc);
@@ -249,6 +249,9 @@ pub fn const_expr<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
         Some(ty::AdjustUnsafeFnPointer) => {
             // purely a type-level thing
         }
+        Some(ty::AdjustClosureFnPointer(_)) => {
+            llconst = closure::trans_closure_fn_pointer_shim(cx, ety, ety_adjusted);
+        }
         Some(ty::AdjustDerefRef(adj)) => {
             let mut ty = ety;
             // Save the last autoderef in case we can avoid it.
@@ -72,6 +72,9 @@ pub struct SharedCrateContext<'tcx> {
 
     available_monomorphizations: RefCell<FnvHashSet<String>>,
     available_drop_glues: RefCell<FnvHashMap<Ty<'tcx>, String>>,
+    /// The next sequence number for internal symbols named after each
+    /// prefix, see `CrateContext::next_symbol_seq`.
+    symbol_seqs: RefCell<FnvHashMap<String, uint>>,
 }
 
 /// The local portion of a `CrateContext`.  There is one `LocalCrateContext`
@@ -273,6 +276,7 @@ impl<'tcx> SharedCrateContext<'tcx> {
             check_overflow: check_overflow,
             available_monomorphizations: RefCell::new(FnvHashSet()),
             available_drop_glues: RefCell::new(FnvHashMap()),
+            symbol_seqs: RefCell::new(FnvHashMap()),
         };
 
         for i in 0..local_count {
@@ -724,6 +728,17 @@ impl<'b, 'tcx> CrateContext<'b, 'tcx> {
                     obj.repr(self.tcx())))
     }
 
+    /// Returns the next sequence number for an internal symbol named after
+    /// `prefix`. Numbering each prefix separately, rather than using one
+    /// counter for the whole crate, keeps those names the same from build to
+    /// build as long as the code they come from is.
+    pub fn next_symbol_seq(&self, prefix: &str) -> uint {
+        let mut seqs = self.shared.symbol_seqs.borrow_mut();
+        let seq = seqs.get(prefix).cloned().unwrap_or(0);
+        seqs.insert(prefix.to_string(), seq + 1);
+        seq
+    }
+
     pub fn check_overflow(&self) -> bool {
         self.shared.check_overflow
     }
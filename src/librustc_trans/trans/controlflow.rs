@@ -8,8 +8,9 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use llvm::ValueRef;
+use llvm::{BasicBlockRef, ValueRef};
 use middle::def;
+use middle::intrinsicck;
 use middle::lang_items::{PanicFnLangItem, PanicBoundsCheckFnLangItem};
 use trans::base::*;
 use trans::basic_block::BasicBlock;
@@ -191,13 +192,15 @@ pub fn trans_if<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
             let else_bcx_out = expr::trans_into(else_bcx_in, &*elexpr, dest);
             next_bcx = bcx.fcx.join_blocks(if_id,
                                            &[then_bcx_out, else_bcx_out]);
-            CondBr(bcx, cond_val, then_bcx_in.llbb, else_bcx_in.llbb, cond_source_loc);
+            cond_br_with_hint(bcx, cond_val, then_bcx_in.llbb, else_bcx_in.llbb,
+                              branch_hint(bcx.tcx(), cond, thn, els), cond_source_loc);
         }
 
         None => {
             next_bcx = bcx.fcx.new_id_block("next-block", if_id);
             Br(then_bcx_out, next_bcx.llbb, DebugLoc::None);
-            CondBr(bcx, cond_val, then_bcx_in.llbb, next_bcx.llbb, cond_source_loc);
+            cond_br_with_hint(bcx, cond_val, then_bcx_in.llbb, next_bcx.llbb,
+                              branch_hint(bcx.tcx(), cond, thn, els), cond_source_loc);
         }
     }
 
@@ -208,6 +211,109 @@ pub fn trans_if<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
     next_bcx
 }
 
+/// The relative weights given to the expected and unexpected arms of an `if`.
+const LIKELY_BRANCH_WEIGHT: u32 = 2000;
+const UNLIKELY_BRANCH_WEIGHT: u32 = 1;
+
+/// Emits the conditional branch of an `if`, attaching branch weights if one
+/// arm is expected to be taken (`Some(true)` for the `then` arm).
+fn cond_br_with_hint(bcx: Block,
+                     cond_val: ValueRef,
+                     then_llbb: BasicBlockRef,
+                     else_llbb: BasicBlockRef,
+                     hint: Option<bool>,
+                     debug_loc: DebugLoc) {
+    match hint {
+        Some(then_likely) => {
+            let weights = if then_likely {
+                (LIKELY_BRANCH_WEIGHT, UNLIKELY_BRANCH_WEIGHT)
+            } else {
+                (UNLIKELY_BRANCH_WEIGHT, LIKELY_BRANCH_WEIGHT)
+            };
+            CondBrWeighted(bcx, cond_val, then_llbb, else_llbb, weights, debug_loc)
+        }
+        None => CondBr(bcx, cond_val, then_llbb, else_llbb, debug_loc)
+    }
+}
+
+/// Determines which arm of an `if` is expected to be taken: the one the
+/// condition says is likely if it is wrapped in `likely` or `unlikely`, and
+/// otherwise the one that does not end by diverging or calling a `#[cold]`
+/// function.
+fn branch_hint(tcx: &ty::ctxt,
+               cond: &ast::Expr,
+               thn: &ast::Block,
+               els: Option<&ast::Expr>) -> Option<bool> {
+    // Look through the `unsafe` block the intrinsics have to be called in.
+    let mut cond = cond;
+    loop {
+        cond = match cond.node {
+            ast::ExprParen(ref e) => &**e,
+            ast::ExprBlock(ref b) if b.stmts.is_empty() && b.expr.is_some() => {
+                &**b.expr.as_ref().unwrap()
+            }
+            _ => break
+        };
+    }
+    if let ast::ExprCall(ref callee, _) = cond.node {
+        if let Some(did) = callee_fn_def_id(tcx, &**callee) {
+            match intrinsicck::intrinsic_name(tcx, did) {
+                Some(ref name) if &name[..] == "likely" => return Some(true),
+                Some(ref name) if &name[..] == "unlikely" => return Some(false),
+                _ => {}
+            }
+        }
+    }
+
+    let then_cold = block_is_cold(tcx, thn);
+    let else_cold = els.map_or(false, |e| expr_is_cold(tcx, e));
+    match (then_cold, else_cold) {
+        (true, false) => Some(false),
+        (false, true) => Some(true),
+        _ => None
+    }
+}
+
+fn callee_fn_def_id(tcx: &ty::ctxt, callee: &ast::Expr) -> Option<ast::DefId> {
+    match tcx.def_map.borrow().get(&callee.id).map(|d| d.full_def()) {
+        Some(def::DefFn(did, _)) => Some(did),
+        _ => None
+    }
+}
+
+fn block_is_cold(tcx: &ty::ctxt, block: &ast::Block) -> bool {
+    match block.expr {
+        Some(ref e) => expr_is_cold(tcx, &**e),
+        None => {
+            match block.stmts.last().map(|s| &s.node) {
+                Some(&ast::StmtExpr(ref e, _)) | Some(&ast::StmtSemi(ref e, _)) => {
+                    expr_is_cold(tcx, &**e)
+                }
+                _ => false
+            }
+        }
+    }
+}
+
+/// Whether evaluating `expr` ends in a call to a diverging or `#[cold]`
+/// function, e.g. `panic!()`.
+fn expr_is_cold(tcx: &ty::ctxt, expr: &ast::Expr) -> bool {
+    match expr.node {
+        ast::ExprCall(ref callee, _) => {
+            let diverges = match ty::expr_ty(tcx, &**callee).sty {
+                ty::ty_bare_fn(_, ref f) => f.sig.0.output == ty::FnDiverging,
+                _ => false
+            };
+            diverges || callee_fn_def_id(tcx, &**callee).map_or(false, |did| {
+                ty::has_attr(tcx, did, "cold")
+            })
+        }
+        ast::ExprBlock(ref b) => block_is_cold(tcx, &**b),
+        ast::ExprParen(ref e) => expr_is_cold(tcx, &**e),
+        _ => false
+    }
+}
+
 pub fn trans_while<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
                                loop_expr: &ast::Expr,
                                cond: &ast::Expr,
@@ -374,7 +480,7 @@ pub fn trans_fail<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
 
     let v_str = C_str_slice(ccx, fail_str);
     let loc = bcx.sess().codemap().lookup_char_pos(call_info.span.lo);
-    let filename = token::intern_and_get_ident(&loc.file.name);
+    let filename = token::intern_and_get_ident(&bcx.sess().remap_path(&loc.file.name));
     let filename = C_str_slice(ccx, filename);
     let line = C_u32(ccx, loc.line as u32);
     let expr_file_line_const = C_struct(ccx, &[v_str, filename, line], false);
@@ -401,7 +507,7 @@ pub fn trans_fail_bounds_check<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
 
     // Extract the file/line from the span
     let loc = bcx.sess().codemap().lookup_char_pos(call_info.span.lo);
-    let filename = token::intern_and_get_ident(&loc.file.name);
+    let filename = token::intern_and_get_ident(&bcx.sess().remap_path(&loc.file.name));
 
     // Invoke the lang item
     let filename = C_str_slice(ccx,  filename);
@@ -1328,7 +1328,7 @@ pub fn create_function_debug_context<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
         ast_map::NodeExpr(ref expr) => {
             match expr.node {
                 ast::ExprClosure(_, ref fn_decl, ref top_level_block) => {
-                    let name = format!("fn{}", token::gensym("fn"));
+                    let name = format!("fn{}", cx.next_symbol_seq("fn"));
                     let name = token::str_to_ident(&name[..]);
                     (name, fn_decl,
                         // This is not quite right. It should actually inherit
@@ -1650,7 +1650,9 @@ fn compile_unit_metadata(cx: &CrateContext) -> DIDescriptor {
                            (option_env!("CFG_VERSION")).expect("CFG_VERSION"));
 
     let compile_unit_name = compile_unit_name.as_ptr();
-    let work_dir = path2cstr(&work_dir);
+    // FIXME (#9639): This needs to handle non-utf8 paths
+    let work_dir = cx.sess().remap_path(work_dir.to_str().unwrap());
+    let work_dir = CString::new(work_dir).unwrap();
     let producer = CString::new(producer).unwrap();
     let flags = "\0";
     let split_name = "\0";
@@ -1755,13 +1757,13 @@ fn file_metadata(cx: &CrateContext, full_path: &str) -> DIFile {
     let work_dir = cx.sess().working_dir.to_str().unwrap();
     let file_name =
         if full_path.starts_with(work_dir) {
-            &full_path[work_dir.len() + 1..full_path.len()]
+            full_path[work_dir.len() + 1..full_path.len()].to_string()
         } else {
-            full_path
+            cx.sess().remap_path(full_path)
         };
 
     let file_name = CString::new(file_name).unwrap();
-    let work_dir = CString::new(work_dir).unwrap();
+    let work_dir = CString::new(cx.sess().remap_path(work_dir)).unwrap();
     let file_metadata = unsafe {
         llvm::LLVMDIBuilderCreateFile(DIB(cx), file_name.as_ptr(),
                                       work_dir.as_ptr())
@@ -2009,6 +2011,7 @@ impl<'tcx> RecursiveTypeDescription<'tcx> {
 
 // Creates MemberDescriptions for the fields of a struct
 struct StructMemberDescriptionFactory<'tcx> {
+    struct_type: Ty<'tcx>,
     fields: Vec<ty::field<'tcx>>,
     is_simd: bool,
     span: Span,
@@ -2027,6 +2030,17 @@ impl<'tcx> StructMemberDescriptionFactory<'tcx> {
             0xdeadbeef
         };
 
+        // Fields aren't necessarily laid out in declaration order, so the
+        // offsets can't be read off the LLVM type by field index.
+        let field_offsets = if self.is_simd {
+            Vec::new()
+        } else {
+            match *adt::represent_type(cx, self.struct_type) {
+                adt::Univariant(ref st, _) => adt::compute_struct_field_offsets(cx, st),
+                _ => cx.sess().bug("debuginfo: struct without a univariant representation")
+            }
+        };
+
         self.fields.iter().enumerate().map(|(i, field)| {
             let name = if field.name == special_idents::unnamed_field.name {
                 "".to_string()
@@ -2038,7 +2052,7 @@ impl<'tcx> StructMemberDescriptionFactory<'tcx> {
                 assert!(field_size != 0xdeadbeef);
                 FixedMemberOffset { bytes: i * field_size }
             } else {
-                ComputedMemberOffset
+                FixedMemberOffset { bytes: field_offsets[i] as uint }
             };
 
             MemberDescription {
@@ -2086,6 +2100,7 @@ fn prepare_struct_metadata<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
         struct_metadata_stub,
         struct_llvm_type,
         StructMDF(StructMemberDescriptionFactory {
+            struct_type: struct_type,
             fields: fields,
             is_simd: ty::type_is_simd(cx.tcx(), struct_type),
             span: span,
@@ -2239,7 +2254,7 @@ impl<'tcx> EnumMemberDescriptionFactory<'tcx> {
                     ]
                 }
             }
-            adt::RawNullablePointer { nndiscr: non_null_variant_index, nnty, .. } => {
+            adt::RawNullablePointer { nndiscr: non_null_variant_index, nnty, niche, .. } => {
                 // As far as debuginfo is concerned, the pointer this enum
                 // represents is still wrapped in a struct. This is to make the
                 // DWARF representation of enums uniform.
@@ -2292,9 +2307,9 @@ impl<'tcx> EnumMemberDescriptionFactory<'tcx> {
                 // member's name.
                 let null_variant_index = (1 - non_null_variant_index) as uint;
                 let null_variant_name = token::get_name((*self.variants)[null_variant_index].name);
-                let union_member_name = format!("RUST$ENCODED$ENUM${}${}",
-                                                0,
-                                                null_variant_name);
+                let union_member_name = encoded_enum_member_name("0",
+                                                                 niche,
+                                                                 &null_variant_name);
 
                 // Finally create the (singleton) list of descriptions of union
                 // members.
@@ -2310,7 +2325,8 @@ impl<'tcx> EnumMemberDescriptionFactory<'tcx> {
             },
             adt::StructWrappedNullablePointer { nonnull: ref struct_def,
                                                 nndiscr,
-                                                ref discrfield, ..} => {
+                                                ref discrfield,
+                                                niche, ..} => {
                 // Create a description of the non-null variant
                 let (variant_type_metadata, variant_llvm_type, member_description_factory) =
                     describe_enum_variant(cx,
@@ -2333,13 +2349,13 @@ impl<'tcx> EnumMemberDescriptionFactory<'tcx> {
                 // member's name.
                 let null_variant_index = (1 - nndiscr) as uint;
                 let null_variant_name = token::get_name((*self.variants)[null_variant_index].name);
-                let discrfield = discrfield.iter()
-                                           .skip(1)
+                let discrfield = adt::discrfield_source_path(cx, struct_def, discrfield)
+                                           .iter()
                                            .map(|x| x.to_string())
                                            .collect::<Vec<_>>().connect("$");
-                let union_member_name = format!("RUST$ENCODED$ENUM${}${}",
-                                                discrfield,
-                                                null_variant_name);
+                let union_member_name = encoded_enum_member_name(&discrfield,
+                                                                 niche,
+                                                                 &null_variant_name);
 
                 // Create the (singleton) list of descriptions of union members.
                 vec![
@@ -2357,6 +2373,19 @@ impl<'tcx> EnumMemberDescriptionFactory<'tcx> {
     }
 }
 
+// Names the sole union member of a nullable-pointer-like enum so that the
+// debugger scripts in src/etc can tell the dataless case apart: the path to the
+// discriminant field and the variant name, preceded by the value marking that
+// variant when it isn't zero.
+fn encoded_enum_member_name(discrfield: &str, niche: ty::Disr, null_variant_name: &str)
+                            -> String {
+    if niche == 0 {
+        format!("RUST$ENCODED$ENUM${}${}", discrfield, null_variant_name)
+    } else {
+        format!("RUST$ENCODED$NICHE${}${}${}", niche, discrfield, null_variant_name)
+    }
+}
+
 // Creates MemberDescriptions for the fields of a single enum variant.
 struct VariantMemberDescriptionFactory<'tcx> {
     args: Vec<(String, Ty<'tcx>)>,
@@ -3554,7 +3583,7 @@ fn create_scope_map(cx: &CrateContext,
                 walk_expr(cx, &**rhs, scope_stack, scope_map);
             }
 
-            ast::ExprRange(ref start, ref end) => {
+            ast::ExprRange(ref start, ref end, _) => {
                 start.as_ref().map(|e| walk_expr(cx, &**e, scope_stack, scope_map));
                 end.as_ref().map(|e| walk_expr(cx, &**e, scope_stack, scope_map));
             }
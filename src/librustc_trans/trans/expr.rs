@@ -73,6 +73,7 @@ use trans::tvec;
 use trans::type_of;
 use middle::ty::{struct_fields, tup_fields};
 use middle::ty::{AdjustDerefRef, AdjustReifyFnPointer, AdjustUnsafeFnPointer, AutoUnsafe};
+use middle::ty::AdjustClosureFnPointer;
 use middle::ty::{AutoPtr};
 use middle::ty::{self, Ty};
 use middle::ty::MethodCall;
@@ -81,7 +82,7 @@ use util::ppaux::Repr;
 use trans::machine::{llsize_of, llsize_of_alloc};
 use trans::type_::Type;
 
-use syntax::{ast, ast_util, codemap};
+use syntax::{ast, ast_map, ast_util, codemap};
 use syntax::parse::token::InternedString;
 use syntax::ptr::P;
 use syntax::parse::token;
@@ -347,7 +348,8 @@ pub fn unsized_info<'ccx, 'tcx, MK_UPCAST_VTABLE>(
             let trait_ref = monomorphize::apply_param_substs(ccx.tcx(),
                                                              param_substs,
                                                              &trait_ref);
-            consts::ptrcast(meth::get_vtable(ccx, trait_ref, param_substs),
+            let span = ccx.tcx().map.opt_span(id).unwrap_or(codemap::DUMMY_SP);
+            consts::ptrcast(meth::get_vtable(ccx, span, trait_ref, param_substs),
                             Type::vtable_ptr(ccx))
         }
         &ty::UnsizeUpcast(_) => {
@@ -386,6 +388,13 @@ fn apply_adjustments<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
         AdjustUnsafeFnPointer => {
             // purely a type-level thing
         }
+        AdjustClosureFnPointer(fn_ty) => {
+            // The closure captures nothing, so its value can be dropped on
+            // the floor in favour of a function which calls it.
+            let fn_ty = monomorphize_type(bcx, fn_ty);
+            let llfn = closure::trans_closure_fn_pointer_shim(bcx.ccx(), datum.ty, fn_ty);
+            datum = immediate_rvalue(llfn, fn_ty).to_expr_datum();
+        }
         AdjustDerefRef(ref adj) => {
             let (autoderefs, use_autoref) = match adj.autoref {
                 // Extracting a value from a box counts as a deref, but if we are
@@ -852,6 +861,7 @@ fn trans_index<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
             }
         }
         None => {
+            let slice_local = immutable_slice_local(bcx, base);
             let base_datum = unpack_datum!(bcx, trans_to_lvalue(bcx,
                                                                 base,
                                                                 "index"));
@@ -878,7 +888,18 @@ fn trans_index<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
 
             let unit_ty = ty::sequence_element_type(bcx.tcx(), base_datum.ty);
 
-            let (base, len) = base_datum.get_vec_base_and_len(bcx);
+            // Reuse the length loaded by an earlier index into the same
+            // slice, provided that load dominates this block.
+            let cached_len = slice_local.and_then(|id| {
+                match bcx.fcx.slice_lens.borrow().get(&id) {
+                    Some(&(llbb, len)) if llbb == bcx.llbb => Some(len),
+                    _ => None
+                }
+            });
+            let (base, len) = match cached_len {
+                Some(len) => (Load(bcx, get_dataptr(bcx, base_datum.val)), len),
+                None => base_datum.get_vec_base_and_len(bcx),
+            };
 
             debug!("trans_index: base {}", bcx.val_to_string(base));
             debug!("trans_index: len {}", bcx.val_to_string(len));
@@ -900,6 +921,11 @@ fn trans_index<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
                                                      ix_val,
                                                      len)
             });
+            // The block we continue in is only reachable through the one
+            // the length was loaded in.
+            if let Some(id) = slice_local {
+                bcx.fcx.slice_lens.borrow_mut().insert(id, (bcx.llbb, len));
+            }
             let elt = InBoundsGEP(bcx, base, &[ix_val]);
             let elt = PointerCast(bcx, elt, type_of::type_of(ccx, unit_ty).ptr_to());
             Datum::new(elt, unit_ty, LvalueExpr)
@@ -909,6 +935,35 @@ fn trans_index<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
     DatumBlock::new(bcx, elt_datum)
 }
 
+/// If `base` names an immutable local binding of a slice reference, returns
+/// the id of that binding. The length of such a slice cannot change while the
+/// binding is in scope, so `trans_index` need not reload it for every index.
+fn immutable_slice_local(bcx: Block, base: &ast::Expr) -> Option<ast::NodeId> {
+    let nid = match base.node {
+        ast::ExprPath(..) => match bcx.def(base.id) {
+            def::DefLocal(nid) => nid,
+            _ => return None
+        },
+        _ => return None
+    };
+    match bcx.tcx().map.find(nid) {
+        Some(ast_map::NodeLocal(pat)) | Some(ast_map::NodeArg(pat)) => {
+            match pat.node {
+                ast::PatIdent(ast::BindByValue(ast::MutImmutable), _, _) => {}
+                _ => return None
+            }
+        }
+        _ => return None
+    }
+    match expr_ty(bcx, base).sty {
+        ty::ty_rptr(_, ty::mt { ty, .. }) => match ty.sty {
+            ty::ty_vec(_, None) => Some(nid),
+            _ => None
+        },
+        _ => None
+    }
+}
+
 fn trans_def<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
                          ref_expr: &ast::Expr,
                          def: def::Def)
@@ -1096,7 +1151,7 @@ fn trans_rvalue_dps_unadjusted<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
                          node_id_type(bcx, expr.id),
                          dest)
         }
-        ast::ExprRange(ref start, ref end) => {
+        ast::ExprRange(ref start, ref end, limits) => {
             // FIXME it is just not right that we are synthesising ast nodes in
             // trans. Shudder.
             fn make_field(field_name: &str, expr: P<ast::Expr>) -> ast::Field {
@@ -1113,10 +1168,15 @@ fn trans_rvalue_dps_unadjusted<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
             // in trans.
             let (did, fields, ty_params) = match (start, end) {
                 (&Some(ref start), &Some(ref end)) => {
-                    // Desugar to Range
+                    // Desugar to Range or RangeInclusive
                     let fields = vec![make_field("start", start.clone()),
                                       make_field("end", end.clone())];
-                    (tcx.lang_items.range_struct(), fields, vec![node_id_type(bcx, start.id)])
+                    let did = if limits == ast::RangeLimits::Closed {
+                        tcx.lang_items.range_inclusive_struct()
+                    } else {
+                        tcx.lang_items.range_struct()
+                    };
+                    (did, fields, vec![node_id_type(bcx, start.id)])
                 }
                 (&Some(ref start), &None) => {
                     // Desugar to RangeFrom
@@ -2543,16 +2603,17 @@ impl OverflowOpViaInputCheck {
         let outer_bits = And(bcx, rhs, invert_mask, binop_debug_loc);
         let cond = ICmp(bcx, llvm::IntNE, outer_bits,
                         C_integral(rhs_llty, 0, false), binop_debug_loc);
-        let result = match *self {
+        let (result, msg) = match *self {
             OverflowOpViaInputCheck::Shl =>
-                build_unchecked_lshift(bcx, lhs, rhs, binop_debug_loc),
+                (build_unchecked_lshift(bcx, lhs, rhs, binop_debug_loc),
+                 "attempted to shift left with overflow"),
             OverflowOpViaInputCheck::Shr =>
-                build_unchecked_rshift(bcx, lhs_t, lhs, rhs, binop_debug_loc),
+                (build_unchecked_rshift(bcx, lhs_t, lhs, rhs, binop_debug_loc),
+                 "attempted to shift right with overflow"),
         };
         let bcx =
             base::with_cond(bcx, cond, |bcx|
-                controlflow::trans_fail(bcx, info,
-                    InternedString::new("shift operation overflowed")));
+                controlflow::trans_fail(bcx, info, InternedString::new(msg)));
 
         (bcx, result)
     }
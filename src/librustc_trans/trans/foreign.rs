@@ -10,13 +10,16 @@
 
 
 use back::{link};
-use llvm::{ValueRef, CallConv, get_param};
+use llvm::{AttrBuilder, ValueRef, CallConv, get_param};
 use llvm;
 use middle::weak_lang_items;
 use trans::base::{llvm_linkage_by_name, push_ctxt};
 use trans::base;
 use trans::build::*;
+use trans::builder::Builder;
 use trans::cabi;
+use trans::callee;
+use trans::cleanup;
 use trans::common::*;
 use trans::debuginfo::DebugLoc;
 use trans::machine;
@@ -94,6 +97,7 @@ pub fn llvm_calling_convention(ccx: &CrateContext,
         // It's the ABI's job to select this, not us.
         System => ccx.sess().bug("system abi should be selected elsewhere"),
 
+        // `adjust_abi` has already turned these into "C" on x86_64.
         Stdcall => llvm::X86StdcallCallConv,
         Fastcall => llvm::X86FastcallCallConv,
         C => llvm::CCallConv,
@@ -105,6 +109,40 @@ pub fn llvm_calling_convention(ccx: &CrateContext,
     }
 }
 
+/// Returns the symbol under which the foreign function `name` is imported.
+///
+/// The Win32 import libraries name `stdcall` and `fastcall` functions on
+/// 32-bit Windows `_name@N` and `@name@N` respectively, where `N` is the
+/// number of bytes of arguments the callee pops off the stack. The leading
+/// `\x01` stops LLVM from prefixing the symbol with another underscore.
+/// Names that already start with `\x01` are used verbatim.
+pub fn decorated_link_name<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
+                                     abi: Abi,
+                                     fty: Ty<'tcx>,
+                                     name: &str) -> String {
+    let target = &ccx.sess().target.target;
+    if !target.options.is_like_windows || target.arch != "x86" || name.starts_with("\x01") {
+        return name.to_string();
+    }
+    let prefix = match target.adjust_abi(abi) {
+        Stdcall => "_",
+        Fastcall => "@",
+        _ => return name.to_string()
+    };
+
+    let tys = foreign_types_for_fn_ty(ccx, fty);
+    let word = machine::llsize_of_alloc(ccx, ccx.int_type());
+    let mut bytes = if tys.fn_ty.ret_ty.is_indirect() { word } else { 0 };
+    for arg in tys.fn_ty.arg_tys.iter().filter(|arg| !arg.is_ignore()) {
+        let size = match arg.kind {
+            cabi::Indirect if arg.attr != Some(llvm::ByValAttribute) => word,
+            _ => machine::llsize_of_alloc(ccx, arg.cast.unwrap_or(arg.ty)),
+        };
+        bytes += (size + word - 1) / word * word;
+    }
+    format!("\x01{}{}@{}", prefix, name, bytes)
+}
+
 pub fn register_static(ccx: &CrateContext,
                        foreign_item: &ast::ForeignItem) -> ValueRef {
     let ty = ty::node_id_to_type(ccx.tcx(), foreign_item.id);
@@ -457,7 +495,7 @@ fn gate_simd_ffi(tcx: &ty::ctxt, decl: &ast::FnDecl, ty: &ty::BareFnTy) {
 pub fn trans_foreign_mod(ccx: &CrateContext, foreign_mod: &ast::ForeignMod) {
     let _icx = push_ctxt("foreign::trans_foreign_mod");
     for foreign_item in &foreign_mod.items {
-        let lname = link_name(&**foreign_item);
+        let mut lname = link_name(&**foreign_item).to_string();
 
         if let ast::ForeignItemFn(ref decl, _) = foreign_item.node {
             match foreign_mod.abi {
@@ -470,6 +508,7 @@ pub fn trans_foreign_mod(ccx: &CrateContext, foreign_mod: &ast::ForeignMod) {
                                                      "foreign fn's sty isn't a bare_fn_ty?")
                     }
 
+                    lname = decorated_link_name(ccx, abi, ty, &lname);
                     let llfn = register_foreign_item_fn(ccx, abi, ty, &lname);
                     base::set_llvm_fn_attrs(ccx, &foreign_item.attrs, llfn);
                     // Unlike for other items, we shouldn't call
@@ -480,8 +519,7 @@ pub fn trans_foreign_mod(ccx: &CrateContext, foreign_mod: &ast::ForeignMod) {
             }
         }
 
-        ccx.item_symbols().borrow_mut().insert(foreign_item.id,
-                                             lname.to_string());
+        ccx.item_symbols().borrow_mut().insert(foreign_item.id, lname);
     }
 }
 
@@ -801,7 +839,12 @@ pub fn trans_rust_fn_with_foreign_abi<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
         debug!("calling llrustfn = {}, t = {}",
                ccx.tn().val_to_string(llrustfn), t.repr(ccx.tcx()));
         let attributes = base::get_fn_llvm_attributes(ccx, t);
-        let llrust_ret_val = builder.call(llrustfn, &llrust_args, Some(attributes));
+        let llrust_ret_val = if ccx.sess().no_landing_pads() {
+            builder.call(llrustfn, &llrust_args, Some(attributes))
+        } else {
+            invoke_aborting_on_unwind(ccx, &builder, llwrapfn, llrustfn,
+                                      &llrust_args, attributes)
+        };
 
         // Get the return value where the foreign fn expects it.
         let llforeign_ret_ty = match tys.fn_ty.ret_ty.cast {
@@ -861,6 +904,45 @@ pub fn trans_rust_fn_with_foreign_abi<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
     }
 }
 
+/// Calls `llrustfn` from the foreign wrapper `llwrapfn` through an `invoke`
+/// whose landing pad calls the `foreign_unwind` lang item, so that a panic
+/// escaping the Rust function aborts the process rather than unwinding into
+/// foreign frames. Leaves `builder` positioned in the normal return block.
+unsafe fn invoke_aborting_on_unwind(ccx: &CrateContext,
+                                    builder: &Builder,
+                                    llwrapfn: ValueRef,
+                                    llrustfn: ValueRef,
+                                    llargs: &[ValueRef],
+                                    attributes: AttrBuilder)
+                                    -> ValueRef {
+    let normal = llvm::LLVMAppendBasicBlockInContext(ccx.llcx(), llwrapfn,
+                                                     "normal return\0".as_ptr() as *const _);
+    let unwind = llvm::LLVMAppendBasicBlockInContext(ccx.llcx(), llwrapfn,
+                                                     "unwind\0".as_ptr() as *const _);
+    let llret = builder.invoke(llrustfn, llargs, normal, unwind, Some(attributes));
+
+    builder.position_at_end(unwind);
+    let llretty = Type::struct_(ccx, &[Type::i8p(ccx), Type::i32(ccx)], false);
+    let llpad = builder.landing_pad(llretty, cleanup::eh_personality(ccx), 1);
+    builder.set_cleanup(llpad);
+    match ccx.tcx().lang_items.foreign_unwind_fn() {
+        Some(def_id) => {
+            let empty_substs = ccx.tcx().mk_substs(Substs::trans_empty());
+            let llfn = callee::trans_fn_ref(ccx, def_id, ExprId(0), empty_substs).val;
+            builder.call(llfn, &[], None);
+        }
+        // Without the runtime there is nobody to report the panic, so just
+        // trap.
+        None => {
+            builder.call(ccx.get_intrinsic(&("llvm.trap")), &[], None);
+        }
+    }
+    builder.unreachable();
+
+    builder.position_at_end(normal);
+    llret
+}
+
 ///////////////////////////////////////////////////////////////////////////
 // General ABI Support
 //
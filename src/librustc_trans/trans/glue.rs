@@ -350,7 +350,7 @@ fn size_and_align_of_dst<'blk, 'tcx>(bcx: Block<'blk, 'tcx>, t: Ty<'tcx>, info:
             let sized_align = C_uint(ccx, llalign_of_min(ccx, sizing_type));
 
             // Recurse to get the size of the dynamically sized field (must be
-            // the last field).
+            // the last field, which `adt` never reorders).
             let fields = ty::struct_fields(bcx.tcx(), id, substs);
             let last_field = fields[fields.len()-1];
             let field_ty = last_field.mt.ty;
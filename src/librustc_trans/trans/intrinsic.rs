@@ -12,6 +12,7 @@
 
 use llvm;
 use llvm::{SequentiallyConsistent, Acquire, Release, AtomicXchg, ValueRef, TypeKind};
+use middle::intrinsicck;
 use middle::subst;
 use middle::subst::FnSpace;
 use trans::base::*;
@@ -457,6 +458,18 @@ pub fn trans_intrinsic_call<'a, 'blk, 'tcx>(mut bcx: Block<'blk, 'tcx>,
             VolatileStore(bcx, val, ptr);
             C_nil(ccx)
         },
+        (_, "unaligned_load") => {
+            let tp_ty = *substs.types.get(FnSpace, 0);
+            let ptr = to_arg_ty_ptr(bcx, llargs[0], tp_ty);
+            from_arg_ty(bcx, UnalignedLoad(bcx, ptr), tp_ty)
+        },
+        (_, "unaligned_store") => {
+            let tp_ty = *substs.types.get(FnSpace, 0);
+            let ptr = to_arg_ty_ptr(bcx, llargs[0], tp_ty);
+            let val = to_arg_ty(bcx, llargs[1], tp_ty);
+            UnalignedStore(bcx, val, ptr);
+            C_nil(ccx)
+        },
 
         (_, "ctlz8") => count_zeros_intrinsic(bcx,
                                               "llvm.ctlz.i8",
@@ -665,6 +678,12 @@ pub fn trans_intrinsic_call<'a, 'blk, 'tcx>(mut bcx: Block<'blk, 'tcx>,
         (_, "overflowing_sub") => Sub(bcx, llargs[0], llargs[1], call_debug_location),
         (_, "overflowing_mul") => Mul(bcx, llargs[0], llargs[1], call_debug_location),
 
+        (_, "likely") | (_, "unlikely") => {
+            let expect = ccx.get_intrinsic(&("llvm.expect.i1"));
+            let expected = C_bool(ccx, &name[..] == "likely");
+            Call(bcx, expect, &[llargs[0], expected], None, call_debug_location)
+        }
+
         (_, "return_address") => {
             if !fcx.caller_expects_out_pointer {
                 tcx.sess.span_err(call_info.span,
@@ -682,6 +701,17 @@ pub fn trans_intrinsic_call<'a, 'blk, 'tcx>(mut bcx: Block<'blk, 'tcx>,
             let split: Vec<&str> = name.split('_').collect();
             assert!(split.len() >= 2, "Atomic intrinsic not correct format");
 
+            // intrinsicck has only been able to check operands that did not
+            // involve type parameters.
+            if split[1] != "fence" {
+                let tp_ty = *substs.types.get(FnSpace, 0);
+                if !intrinsicck::is_atomic_operand_type(tp_ty) {
+                    ccx.sess().span_fatal(call_info.span,
+                        &format!("`{}` can only operate on integer and raw pointer types, \
+                                  found `{}`", name, ty_to_string(tcx, tp_ty)));
+                }
+            }
+
             let order = if split.len() == 2 {
                 llvm::SequentiallyConsistent
             } else {
@@ -35,13 +35,13 @@ use trans::type_::Type;
 use trans::type_of::*;
 use middle::ty::{self, Ty};
 use middle::ty::MethodCall;
-use util::ppaux::Repr;
+use util::ppaux::{Repr, UserString};
 
 use std::rc::Rc;
 use syntax::abi::{Rust, RustCall};
 use syntax::parse::token;
 use syntax::{ast, ast_map, attr, visit};
-use syntax::codemap::DUMMY_SP;
+use syntax::codemap::{DUMMY_SP, Span};
 use syntax::ptr::P;
 
 // drop_glue pointer, size, align.
@@ -233,9 +233,8 @@ pub fn trans_static_method_callee<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
     debug!("trait_substs={}", trait_substs.repr(tcx));
     let trait_ref = ty::Binder(Rc::new(ty::TraitRef { def_id: trait_id,
                                                       substs: trait_substs }));
-    let vtbl = fulfill_obligation(ccx,
-                                  DUMMY_SP,
-                                  trait_ref);
+    let span = tcx.map.opt_span(expr_id).unwrap_or(DUMMY_SP);
+    let vtbl = fulfill_obligation(ccx, span, trait_ref);
 
     // Now that we know which impl is being used, we can dispatch to
     // the actual function:
@@ -682,6 +681,7 @@ pub fn trans_object_shim<'a, 'tcx>(
 /// making an object `Foo<Trait>` from a value of type `Foo<T>`, then
 /// `trait_ref` would map `T:Trait`.
 pub fn get_vtable<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
+                            span: Span,
                             trait_ref: ty::PolyTraitRef<'tcx>,
                             param_substs: &'tcx subst::Substs<'tcx>)
                             -> ValueRef
@@ -699,7 +699,7 @@ pub fn get_vtable<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
 
     // Not in the cache. Build it.
     let methods = traits::supertraits(tcx, trait_ref.clone()).flat_map(|trait_ref| {
-        let vtable = fulfill_obligation(ccx, DUMMY_SP, trait_ref.clone());
+        let vtable = fulfill_obligation(ccx, span, trait_ref.clone());
         match vtable {
             // Should default trait error here?
             traits::VtableDefaultImpl(_) |
@@ -711,7 +711,7 @@ pub fn get_vtable<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
                     impl_def_id: id,
                     substs,
                     nested: _ }) => {
-                emit_vtable_methods(ccx, id, substs, param_substs).into_iter()
+                emit_vtable_methods(ccx, &trait_ref, id, substs, param_substs).into_iter()
             }
             traits::VtableClosure(closure_def_id, substs) => {
                 let llfn = trans_fn_ref_with_substs(
@@ -762,6 +762,7 @@ pub fn get_vtable<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
 }
 
 fn emit_vtable_methods<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
+                                 trait_ref: &ty::PolyTraitRef<'tcx>,
                                  impl_id: ast::DefId,
                                  substs: subst::Substs<'tcx>,
                                  param_substs: &'tcx subst::Substs<'tcx>)
@@ -782,6 +783,15 @@ fn emit_vtable_methods<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
 
     ty::populate_implementations_for_trait_if_necessary(tcx, trt_id);
 
+    // `-Z print-vtables`: say where each slot of the vtable comes from.
+    let print_slot = |slot: uint, name: ast::Name, source: &str| {
+        if ccx.sess().opts.debugging_opts.print_vtables {
+            println!("vtable for `{} as {}` slot {} `{}`: {}",
+                     trait_ref.self_ty().user_string(tcx), trait_ref.user_string(tcx),
+                     slot, token::get_name(name), source);
+        }
+    };
+
     let trait_item_def_ids = ty::trait_item_def_ids(tcx, trt_id);
     trait_item_def_ids
         .iter()
@@ -797,7 +807,8 @@ fn emit_vtable_methods<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
         // Now produce pointers for each remaining method. If the
         // method could never be called from this object, just supply
         // null.
-        .map(|trait_method_def_id| {
+        .enumerate()
+        .map(|(slot, trait_method_def_id)| {
             debug!("emit_vtable_methods: trait_method_def_id={}",
                    trait_method_def_id.repr(tcx));
 
@@ -811,7 +822,10 @@ fn emit_vtable_methods<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
                    trait_method_type.repr(tcx));
 
             // The substitutions we have are on the impl, so we grab
-            // the method type from the impl to substitute into.
+            // the method type from the impl to substitute into. This is
+            // the impl's own method if it has one, else the copy of the
+            // trait's default that coherence made for this impl (which
+            // is what carries the impl's substs, also cross-crate).
             let impl_method_def_id = method_with_name(ccx, impl_id, name);
             let impl_method_type = match ty::impl_or_trait_item(tcx, impl_method_def_id) {
                 ty::MethodTraitItem(m) => m,
@@ -825,6 +839,7 @@ fn emit_vtable_methods<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
 
             if impl_method_type.generics.has_type_params(subst::FnSpace) {
                 debug!("emit_vtable_methods: generic");
+                print_slot(slot, name, "null (generic method)");
                 return nullptr;
             }
 
@@ -833,6 +848,7 @@ fn emit_vtable_methods<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
             if ty::type_has_self(bare_fn_ty) {
                 debug!("emit_vtable_methods: type_has_self {}",
                        bare_fn_ty.repr(tcx));
+                print_slot(slot, name, "null (mentions `Self`)");
                 return nullptr;
             }
 
@@ -841,17 +857,27 @@ fn emit_vtable_methods<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
             // particular set of type parameters. Note that this
             // method could then never be called, so we do not want to
             // try and trans it, in that case. Issue #23435.
-            if ty::provided_source(tcx, impl_method_def_id).is_some() {
+            let provided_source = ty::provided_source(tcx, impl_method_def_id);
+            if provided_source.is_some() {
                 let predicates =
                     monomorphize::apply_param_substs(tcx,
                                                      &substs,
                                                      &impl_method_type.predicates.predicates);
                 if !predicates_hold(ccx, predicates.into_vec()) {
                     debug!("emit_vtable_methods: predicates do not hold");
+                    print_slot(slot, name, "null (where clauses do not hold)");
                     return nullptr;
                 }
             }
 
+            match provided_source {
+                Some(source) => {
+                    print_slot(slot, name, &format!("default from `{}`",
+                                                    ty::item_path_str(tcx, source)))
+                }
+                None => print_slot(slot, name, "the impl's own method"),
+            }
+
             trans_fn_ref_with_substs(ccx,
                                      impl_method_def_id,
                                      ExprId(0),
@@ -891,7 +917,8 @@ pub fn trans_trait_cast<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
     bcx = datum.store_to(bcx, llboxdest);
 
     // Store the vtable into the second half of pair.
-    let vtable = get_vtable(bcx.ccx(), trait_ref, bcx.fcx.param_substs);
+    let span = bcx.tcx().map.opt_span(id).unwrap_or(DUMMY_SP);
+    let vtable = get_vtable(bcx.ccx(), span, trait_ref, bcx.fcx.param_substs);
     let llvtabledest = GEPi(bcx, lldest, &[0, abi::FAT_PTR_EXTRA]);
     let llvtabledest = PointerCast(bcx, llvtabledest, val_ty(vtable).ptr_to());
     Store(bcx, vtable, llvtabledest);
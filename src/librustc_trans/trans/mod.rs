@@ -11,10 +11,11 @@
 use llvm::{ContextRef, ModuleRef};
 use metadata::common::LinkMeta;
 use middle::dependency_format;
+use syntax::codemap::Span;
 
 pub use self::base::trans_crate;
 pub use self::context::CrateContext;
-pub use self::common::gensym_name;
+pub use self::common::seq_name;
 
 #[macro_use]
 mod macros;
@@ -66,12 +67,28 @@ pub struct ModuleTranslation {
 unsafe impl Send for ModuleTranslation { }
 unsafe impl Sync for ModuleTranslation { }
 
+/// A symbol the translated crate exports, for `--emit exported-symbols`.
+pub struct ExportedSymbol {
+    /// The symbol name as the linker sees it.
+    pub name: String,
+    /// The Rust path of the item behind the symbol, or the symbol name again
+    /// for symbols that don't come from an item.
+    pub path: String,
+    /// One of "fn", "static", "vtable" or "metadata".
+    pub kind: &'static str,
+}
+
 pub struct CrateTranslation {
     pub modules: Vec<ModuleTranslation>,
     pub metadata_module: ModuleTranslation,
     pub link: LinkMeta,
     pub metadata: Vec<u8>,
     pub reachable: Vec<String>,
+    /// Sorted by symbol name.
+    pub exported_symbols: Vec<ExportedSymbol>,
+    /// The `#[no_mangle]` and `#[export_name]` symbols this crate defines,
+    /// with the span of the item behind each.
+    pub unmangled_symbols: Vec<(String, Span)>,
     pub crate_formats: dependency_format::Dependencies,
     pub no_builtins: bool,
 }
@@ -110,6 +110,12 @@ pub trait AstConv<'tcx> {
     /// What type should we use when a type is omitted?
     fn ty_infer(&self, span: Span) -> Ty<'tcx>;
 
+    /// Records that `ty`, a type returned by `ty_infer` for an omitted
+    /// type parameter, should fall back to `default` if nothing else
+    /// constrains it.
+    fn ty_infer_default(&self, _ty: Ty<'tcx>, _default: Ty<'tcx>) {
+    }
+
     /// Projecting an associated type from a (potentially)
     /// higher-ranked trait reference is more complicated, because of
     /// the possibility of late-bound regions appearing in the
@@ -390,8 +396,8 @@ fn create_substs_for_ast_path<'tcx>(
 
     // Fill with `ty_infer` if no params were specified, as long as
     // they were optional (e.g. paths inside expressions).
-    let mut type_substs = if param_mode == PathParamMode::Optional &&
-                             types_provided.is_empty() {
+    let inferred = param_mode == PathParamMode::Optional && types_provided.is_empty();
+    let mut type_substs = if inferred {
         (0..formal_ty_param_count).map(|_| this.ty_infer(span)).collect()
     } else {
         types_provided
@@ -428,6 +434,18 @@ fn create_substs_for_ast_path<'tcx>(
         }
     }
 
+    // Inferred parameters only fall back to their defaults once
+    // inference is done with them.
+    if inferred {
+        for (param, &ty) in ty_param_defs.iter().zip(substs.types.get_slice(TypeSpace).iter()) {
+            if let Some(default) = param.default {
+                if self_ty.is_some() || !ty::type_has_self(default) {
+                    this.ty_infer_default(ty, default.subst_spanned(tcx, &substs, Some(span)));
+                }
+            }
+        }
+    }
+
     let actual_supplied_ty_param_count = substs.types.len(TypeSpace);
     for param in &ty_param_defs[actual_supplied_ty_param_count..] {
         if let Some(default) = param.default {
@@ -991,6 +1009,7 @@ fn ast_ty_to_trait_ref<'tcx>(this: &AstConv<'tcx>,
 fn trait_ref_to_object_type<'tcx>(this: &AstConv<'tcx>,
                                   rscope: &RegionScope,
                                   span: Span,
+                                  principal_span: Span,
                                   trait_ref: ty::PolyTraitRef<'tcx>,
                                   projection_bounds: Vec<ty::PolyProjectionPredicate<'tcx>>,
                                   bounds: &[ast::TyParamBound])
@@ -999,6 +1018,7 @@ fn trait_ref_to_object_type<'tcx>(this: &AstConv<'tcx>,
     let existential_bounds = conv_existential_bounds(this,
                                                      rscope,
                                                      span,
+                                                     principal_span,
                                                      trait_ref.clone(),
                                                      projection_bounds,
                                                      bounds);
@@ -1207,7 +1227,7 @@ pub fn finish_resolving_def_to_ty<'tcx>(this: &AstConv<'tcx>,
                                                           &mut projection_bounds);
 
             check_path_args(tcx, segments.init(), NO_TPS | NO_REGIONS);
-            trait_ref_to_object_type(this, rscope, span, trait_ref,
+            trait_ref_to_object_type(this, rscope, span, span, trait_ref,
                                      projection_bounds, &[])
         }
         def::DefTy(did, _) | def::DefStruct(did) => {
@@ -1250,6 +1270,10 @@ pub fn finish_resolving_def_to_ty<'tcx>(this: &AstConv<'tcx>,
         def::DefPrimTy(prim_ty) => {
             prim_ty_to_ty(tcx, segments, prim_ty)
         }
+        def::DefErr => {
+            // Resolution has already reported this path.
+            return this.tcx().types.err;
+        }
         _ => {
             span_err!(tcx.sess, span, E0248,
                       "found value name used as a type: {:?}", *def);
@@ -1298,6 +1322,7 @@ pub fn ast_ty_to_ty<'tcx>(this: &AstConv<'tcx>,
                     trait_ref_to_object_type(this,
                                              rscope,
                                              ast_ty.span,
+                                             ty.span,
                                              trait_ref,
                                              projection_bounds,
                                              bounds)
@@ -1381,12 +1406,13 @@ pub fn ast_ty_to_ty<'tcx>(this: &AstConv<'tcx>,
             match const_eval::eval_const_expr_partial(tcx, &**e, Some(tcx.types.uint)) {
                 Ok(r) => {
                     match r {
-                        const_eval::const_int(i) =>
-                            ty::mk_vec(tcx, ast_ty_to_ty(this, rscope, &**ty),
-                                        Some(i as uint)),
-                        const_eval::const_uint(i) =>
-                            ty::mk_vec(tcx, ast_ty_to_ty(this, rscope, &**ty),
-                                        Some(i as uint)),
+                        const_eval::const_int(_) | const_eval::const_uint(_) => {
+                            match ty::array_length_from_const(tcx, &**e, &r, "array length") {
+                                Some(len) => ty::mk_vec(tcx, ast_ty_to_ty(this, rscope, &**ty),
+                                                        Some(len)),
+                                None => this.tcx().types.err
+                            }
+                        }
                         _ => {
                             span_err!(tcx.sess, ast_ty.span, E0249,
                                       "expected constant expr for array length");
@@ -1719,6 +1745,7 @@ fn conv_existential_bounds<'tcx>(
     this: &AstConv<'tcx>,
     rscope: &RegionScope,
     span: Span,
+    principal_span: Span,
     principal_trait_ref: ty::PolyTraitRef<'tcx>,
     projection_bounds: Vec<ty::PolyProjectionPredicate<'tcx>>,
     ast_bounds: &[ast::TyParamBound])
@@ -1728,7 +1755,8 @@ fn conv_existential_bounds<'tcx>(
         partition_bounds(this.tcx(), span, ast_bounds);
 
     conv_existential_bounds_from_partitioned_bounds(
-        this, rscope, span, principal_trait_ref, projection_bounds, partitioned_bounds)
+        this, rscope, span, principal_span, principal_trait_ref,
+        projection_bounds, partitioned_bounds)
 }
 
 fn conv_ty_poly_trait_ref<'tcx>(
@@ -1741,13 +1769,14 @@ fn conv_ty_poly_trait_ref<'tcx>(
     let mut partitioned_bounds = partition_bounds(this.tcx(), span, &ast_bounds[..]);
 
     let mut projection_bounds = Vec::new();
-    let main_trait_bound = if !partitioned_bounds.trait_bounds.is_empty() {
+    let (main_trait_bound, principal_span) = if !partitioned_bounds.trait_bounds.is_empty() {
         let trait_bound = partitioned_bounds.trait_bounds.remove(0);
-        instantiate_poly_trait_ref(this,
-                                   rscope,
-                                   trait_bound,
-                                   None,
-                                   &mut projection_bounds)
+        (instantiate_poly_trait_ref(this,
+                                    rscope,
+                                    trait_bound,
+                                    None,
+                                    &mut projection_bounds),
+         trait_bound.trait_ref.path.span)
     } else {
         span_err!(this.tcx().sess, span, E0224,
                   "at least one non-builtin trait is required for an object type");
@@ -1758,6 +1787,7 @@ fn conv_ty_poly_trait_ref<'tcx>(
         conv_existential_bounds_from_partitioned_bounds(this,
                                                         rscope,
                                                         span,
+                                                        principal_span,
                                                         main_trait_bound.clone(),
                                                         projection_bounds,
                                                         partitioned_bounds);
@@ -1769,6 +1799,7 @@ pub fn conv_existential_bounds_from_partitioned_bounds<'tcx>(
     this: &AstConv<'tcx>,
     rscope: &RegionScope,
     span: Span,
+    principal_span: Span,
     principal_trait_ref: ty::PolyTraitRef<'tcx>,
     mut projection_bounds: Vec<ty::PolyProjectionPredicate<'tcx>>, // Empty for boxed closures
     partitioned_bounds: PartitionedBounds)
@@ -1779,10 +1810,17 @@ pub fn conv_existential_bounds_from_partitioned_bounds<'tcx>(
                             region_bounds } =
         partitioned_bounds;
 
-    if !trait_bounds.is_empty() {
-        let b = &trait_bounds[0];
+    // An object type has exactly one principal trait; every other trait
+    // named in its bounds must be one of the builtin traits (`Send`,
+    // `Sync`, ...), which are recorded in `builtin_bounds` instead.
+    for b in &trait_bounds {
         span_err!(this.tcx().sess, b.trait_ref.path.span, E0225,
-                  "only the builtin traits can be used as closure or object bounds");
+                  "only one non-builtin trait can be used in an object type; \
+                   `{}` is not a builtin trait",
+                  pprust::path_to_string(&b.trait_ref.path));
+        span_note!(this.tcx().sess, principal_span,
+                   "the principal trait of this object type is `{}`",
+                   principal_trait_ref.user_string(this.tcx()));
     }
 
     let region_bound = compute_object_lifetime_bound(this,
@@ -1846,7 +1884,13 @@ fn compute_object_lifetime_bound<'tcx>(
     // can find no region bound.
     if derived_region_bounds.len() == 0 {
         match rscope.object_lifetime_default(span) {
-            Some(r) => { return r; }
+            Some(r) => {
+                if r == ty::ReStatic {
+                    tcx.static_object_lifetime_defaults.borrow_mut()
+                                                       .insert(principal_trait_ref.def_id());
+                }
+                return r;
+            }
             None => {
                 span_err!(this.tcx().sess, span, E0228,
                           "the lifetime bound for this object type cannot be deduced \
@@ -400,6 +400,7 @@ pub fn check_match<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
     // of execution reach it, we will panic, so bottom is an appropriate
     // type in that case)
     let expected = expected.adjust_for_branches(fcx);
+    let mut all_arms_diverge = true;
     let result_ty = arms.iter().fold(fcx.infcx().next_diverging_ty_var(), |result_ty, arm| {
         let bty = match expected {
             // We don't coerce to `()` so that if the match expression is a
@@ -420,6 +421,7 @@ pub fn check_match<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
         if let Some(ref e) = arm.guard {
             check_expr_has_type(fcx, &**e, tcx.types.bool);
         }
+        all_arms_diverge &= fcx.infcx().type_var_diverges(fcx.node_ty(arm.body.id));
 
         if ty::type_is_error(result_ty) || ty::type_is_error(bty) {
             tcx.types.err
@@ -449,7 +451,13 @@ pub fn check_match<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
         }
     });
 
-    fcx.write_ty(expr.id, result_ty);
+    // A match whose arms all diverge diverges too, whatever type the arms
+    // were checked against.
+    if all_arms_diverge && !ty::type_is_error(result_ty) {
+        fcx.write_ty(expr.id, fcx.infcx().next_diverging_ty_var());
+    } else {
+        fcx.write_ty(expr.id, result_ty);
+    }
 }
 
 pub struct pat_ctxt<'a, 'tcx: 'a> {
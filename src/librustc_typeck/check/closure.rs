@@ -122,6 +122,12 @@ fn deduce_expectations_from_expected_type<'a,'tcx>(
         ty::ty_infer(ty::TyVar(vid)) => {
             deduce_expectations_from_obligations(fcx, vid)
         }
+        ty::ty_bare_fn(None, ref fn_ty) => {
+            // The closure may be coerced to this fn pointer if it captures
+            // nothing. Its kind is left to upvar inference, so that one
+            // which does capture something is reported as a type mismatch.
+            (Some(fn_ty.sig.0.clone()), None)
+        }
         _ => {
             (None, None)
         }
@@ -72,6 +72,7 @@ use util::common::indent;
 use util::ppaux;
 use util::ppaux::Repr;
 
+use syntax::abi;
 use syntax::ast;
 
 struct Coerce<'a, 'tcx: 'a> {
@@ -154,6 +155,11 @@ impl<'f, 'tcx> Coerce<'f, 'tcx> {
                     // unsafe qualifier.
                     self.coerce_from_fn_pointer(a, a_f, b)
                 }
+                ty::ty_closure(closure_def_id, _) => {
+                    // Closures which capture nothing are coercible to fn
+                    // pointers.
+                    self.coerce_closure_to_fn_pointer(a, closure_def_id, b)
+                }
                 _ => {
                     // Otherwise, just use subtyping rules.
                     self.subtype(a, b)
@@ -504,6 +510,47 @@ impl<'f, 'tcx> Coerce<'f, 'tcx> {
         })
     }
 
+    fn coerce_closure_to_fn_pointer(&self,
+                                    a: Ty<'tcx>,
+                                    closure_def_id: ast::DefId,
+                                    b: Ty<'tcx>)
+                                    -> CoerceResult<'tcx> {
+        self.unpack_actual_value(b, |b| {
+            debug!("coerce_closure_to_fn_pointer(a={}, b={})",
+                   a.repr(self.tcx()), b.repr(self.tcx()));
+
+            let fn_ty_b = match b.sty {
+                ty::ty_bare_fn(None, fn_ty_b) => fn_ty_b,
+                _ => return self.subtype(a, b)
+            };
+            let captures = ty::with_freevars(self.tcx(), closure_def_id.node,
+                                             |fv| !fv.is_empty());
+            let closure_ty = match self.fcx.inh.closure_tys.borrow().get(&closure_def_id) {
+                Some(closure_ty) if !captures => closure_ty.clone(),
+                _ => return self.subtype(a, b)
+            };
+
+            // The closure's arguments are stored tupled up, as the
+            // "rust-call" ABI expects them.
+            let inputs = match closure_ty.sig.0.inputs[0].sty {
+                ty::ty_tup(ref inputs) => inputs.clone(),
+                _ => self.tcx().sess.bug("closure with untupled arguments")
+            };
+            let a_fn_pointer = ty::mk_bare_fn(self.tcx(), None, self.tcx().mk_bare_fn(
+                ty::BareFnTy {
+                    unsafety: fn_ty_b.unsafety,
+                    abi: abi::Rust,
+                    sig: ty::Binder(ty::FnSig {
+                        inputs: inputs,
+                        output: closure_ty.sig.0.output,
+                        variadic: false
+                    })
+                }));
+            try!(self.subtype(a_fn_pointer, b));
+            Ok(Some(ty::AdjustClosureFnPointer(a_fn_pointer)))
+        })
+    }
+
     fn coerce_unsafe_ptr(&self,
                          a: Ty<'tcx>,
                          b: Ty<'tcx>,
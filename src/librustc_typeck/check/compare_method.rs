@@ -15,11 +15,24 @@ use middle::subst::{self, Subst, Substs, VecPerParamSpace};
 use util::ppaux::{self, Repr};
 
 use syntax::ast;
+use syntax::ast_map;
 use syntax::codemap::{Span};
 use syntax::parse::token;
 
 use super::assoc;
 
+/// The first part of an impl method's signature found to differ from the
+/// trait's declaration of the method, used to give a more precise error than
+/// the mismatch of the whole function types.
+enum SigDifference {
+    Unsafety,
+    Abi,
+    SelfMutability,
+    /// The index of the input (counting `self`) and its impl and trait types.
+    Input(usize, String, String),
+    Output(String, String),
+}
+
 /// Checks that a method from an impl conforms to the signature of
 /// the same method as declared in the trait.
 ///
@@ -92,6 +105,7 @@ pub fn compare_impl_method<'tcx>(tcx: &ty::ctxt<'tcx>,
             if num_impl_m_type_params == 1 {""} else {"s"},
             num_trait_m_type_params,
             if num_trait_m_type_params == 1 {""} else {"s"});
+        note_trait_declaration(tcx, trait_m, None);
         return;
     }
 
@@ -104,6 +118,7 @@ pub fn compare_impl_method<'tcx>(tcx: &ty::ctxt<'tcx>,
             if impl_m.fty.sig.0.inputs.len() == 1 {""} else {"s"},
             ty::item_path_str(tcx, trait_m.def_id),
             trait_m.fty.sig.0.inputs.len());
+        note_trait_declaration(tcx, trait_m, None);
         return;
     }
 
@@ -196,6 +211,7 @@ pub fn compare_impl_method<'tcx>(tcx: &ty::ctxt<'tcx>,
                                            &impl_m.generics,
                                            &trait_to_skol_substs,
                                            impl_to_skol_substs) {
+        note_trait_declaration(tcx, trait_m, None);
         return;
     }
 
@@ -248,14 +264,32 @@ pub fn compare_impl_method<'tcx>(tcx: &ty::ctxt<'tcx>,
 
     let mut selcx = traits::SelectionContext::new(&infcx, &trait_param_env);
 
+    let impl_m_generics = method_sig(tcx, impl_m.def_id).map(|sig| &sig.generics);
     for predicate in impl_pred.fns {
         let traits::Normalized { value: predicate, .. } =
             traits::normalize(&mut selcx, normalize_cause.clone(), &predicate);
 
+        // Point at the bound that introduced the predicate where we can.
+        let span = impl_m_generics.and_then(|generics| predicate_span(tcx, generics, &predicate))
+                                  .unwrap_or(impl_m_span);
+
+        // The fulfillment context only records region obligations for
+        // regionck, which never runs on this comparison, so check region
+        // predicates against the trait method's bounds directly.
+        match predicate {
+            ty::Predicate::RegionOutlives(..) | ty::Predicate::TypeOutlives(..) => {
+                if !region_predicate_entailed(&trait_param_env.caller_bounds, &predicate) {
+                    traits::report_extra_requirement(tcx, span, &predicate, trait_m.def_id);
+                }
+                continue;
+            }
+            _ => {}
+        }
+
         let cause = traits::ObligationCause {
-            span: impl_m_span,
+            span: span,
             body_id: impl_m_body_id,
-            code: traits::ObligationCauseCode::CompareImplMethodObligation
+            code: traits::ObligationCauseCode::CompareImplMethodObligation(trait_m.def_id)
         };
 
         fulfillment_cx.register_predicate_obligation(
@@ -303,7 +337,7 @@ pub fn compare_impl_method<'tcx>(tcx: &ty::ctxt<'tcx>,
                            None,
                            tcx.mk_bare_fn(ty::BareFnTy { unsafety: impl_m.fty.unsafety,
                                                          abi: impl_m.fty.abi,
-                                                         sig: ty::Binder(impl_sig) }));
+                                                         sig: ty::Binder(impl_sig.clone()) }));
         debug!("compare_impl_method: impl_fty={}",
                impl_fty.repr(tcx));
 
@@ -323,26 +357,27 @@ pub fn compare_impl_method<'tcx>(tcx: &ty::ctxt<'tcx>,
                            None,
                            tcx.mk_bare_fn(ty::BareFnTy { unsafety: trait_m.fty.unsafety,
                                                          abi: trait_m.fty.abi,
-                                                         sig: ty::Binder(trait_sig) }));
+                                                         sig: ty::Binder(trait_sig.clone()) }));
 
         debug!("compare_impl_method: trait_fty={}",
                trait_fty.repr(tcx));
 
-        try!(infer::mk_subty(&infcx, false, origin, impl_fty, trait_fty));
+        if let Err(terr) = infer::mk_subty(&infcx, false, origin, impl_fty, trait_fty) {
+            let difference = first_difference(&infcx, impl_m_span, &terr, impl_m, trait_m,
+                                              &impl_sig, &trait_sig);
+            return Err((terr, difference));
+        }
 
-        infcx.leak_check(&skol_map, snapshot)
+        infcx.leak_check(&skol_map, snapshot).map_err(|terr| (terr, None))
     });
 
     match err {
         Ok(()) => { }
-        Err(terr) => {
+        Err((terr, difference)) => {
             debug!("checking trait method for compatibility: impl ty {}, trait ty {}",
                    impl_fty.repr(tcx),
                    trait_fty.repr(tcx));
-            span_err!(tcx.sess, impl_m_span, E0053,
-                      "method `{}` has an incompatible type for trait: {}",
-                      token::get_name(trait_m.name),
-                      ty::type_err_to_str(tcx, &terr));
+            report_incompatible_method(tcx, impl_m, impl_m_span, trait_m, &terr, difference);
             return;
         }
     }
@@ -401,3 +436,307 @@ pub fn compare_impl_method<'tcx>(tcx: &ty::ctxt<'tcx>,
         return true;
     }
 }
+
+/// Finds the first part of the (skolemized and normalized) signatures of
+/// `impl_m` and `trait_m` that is not compatible, checking in the order the
+/// parts are written: qualifiers, `self`, the parameters and the return type.
+fn first_difference<'a, 'tcx>(infcx: &infer::InferCtxt<'a, 'tcx>,
+                              span: Span,
+                              terr: &ty::type_err<'tcx>,
+                              impl_m: &ty::Method<'tcx>,
+                              trait_m: &ty::Method<'tcx>,
+                              impl_sig: &ty::FnSig<'tcx>,
+                              trait_sig: &ty::FnSig<'tcx>)
+                              -> Option<SigDifference> {
+    match *terr {
+        ty::terr_unsafety_mismatch(_) => return Some(SigDifference::Unsafety),
+        ty::terr_abi_mismatch(_) => return Some(SigDifference::Abi),
+        _ => {}
+    }
+    match (&impl_m.explicit_self, &trait_m.explicit_self) {
+        (&ty::ByReferenceExplicitSelfCategory(_, impl_mutbl),
+         &ty::ByReferenceExplicitSelfCategory(_, trait_mutbl)) if impl_mutbl != trait_mutbl => {
+            return Some(SigDifference::SelfMutability);
+        }
+        _ => {}
+    }
+
+    // Parameters are contravariant, so each trait parameter type must be a
+    // subtype of the corresponding impl parameter type.
+    let origin = infer::MethodCompatCheck(span);
+    for (i, (&impl_arg, &trait_arg)) in impl_sig.inputs.iter()
+                                                .zip(trait_sig.inputs.iter())
+                                                .enumerate() {
+        let compatible = infcx.probe(|_| {
+            infer::mk_subty(infcx, false, origin, trait_arg, impl_arg).is_ok()
+        });
+        if !compatible {
+            return Some(SigDifference::Input(i,
+                                             infcx.ty_to_string(impl_arg),
+                                             infcx.ty_to_string(trait_arg)));
+        }
+    }
+
+    match (impl_sig.output, trait_sig.output) {
+        (ty::FnConverging(impl_out), ty::FnConverging(trait_out)) => {
+            let compatible = infcx.probe(|_| {
+                infer::mk_subty(infcx, false, origin, impl_out, trait_out).is_ok()
+            });
+            if !compatible {
+                return Some(SigDifference::Output(infcx.ty_to_string(impl_out),
+                                                  infcx.ty_to_string(trait_out)));
+            }
+            None
+        }
+        (ty::FnDiverging, ty::FnDiverging) => None,
+        (impl_out, trait_out) => {
+            let output_to_string = |output: ty::FnOutput<'tcx>| match output {
+                ty::FnConverging(ty) => infcx.ty_to_string(ty),
+                ty::FnDiverging => "!".to_string(),
+            };
+            Some(SigDifference::Output(output_to_string(impl_out),
+                                       output_to_string(trait_out)))
+        }
+    }
+}
+
+fn report_incompatible_method<'tcx>(tcx: &ty::ctxt<'tcx>,
+                                    impl_m: &ty::Method<'tcx>,
+                                    impl_m_span: Span,
+                                    trait_m: &ty::Method<'tcx>,
+                                    terr: &ty::type_err<'tcx>,
+                                    difference: Option<SigDifference>) {
+    let terr = ty::type_err_to_str(tcx, terr);
+    let span = method_sig(tcx, impl_m.def_id)
+        .and_then(|sig| difference.as_ref().and_then(|d| difference_span(sig, d)))
+        .unwrap_or(impl_m_span);
+    let detail = match difference {
+        None | Some(SigDifference::Unsafety) | Some(SigDifference::Abi) => terr,
+        Some(SigDifference::SelfMutability) => {
+            format!("it takes `{}` but the trait declares `{}`",
+                    ppaux::explicit_self_category_to_str(&impl_m.explicit_self),
+                    ppaux::explicit_self_category_to_str(&trait_m.explicit_self))
+        }
+        Some(SigDifference::Input(i, ref impl_ty, ref trait_ty)) => {
+            let has_self = match trait_m.explicit_self {
+                ty::StaticExplicitSelfCategory => false,
+                _ => true,
+            };
+            let param = if has_self && i == 0 {
+                "`self`".to_string()
+            } else if has_self {
+                format!("parameter {}", i)
+            } else {
+                format!("parameter {}", i + 1)
+            };
+            format!("{} has type `{}` but the trait expects `{}`: {}",
+                    param, impl_ty, trait_ty, terr)
+        }
+        Some(SigDifference::Output(ref impl_ty, ref trait_ty)) => {
+            format!("the return type is `{}` but the trait expects `{}`: {}",
+                    impl_ty, trait_ty, terr)
+        }
+    };
+    span_err!(tcx.sess, span, E0053,
+              "method `{}` has an incompatible type for trait: {}",
+              token::get_name(trait_m.name), detail);
+    note_trait_declaration(tcx, trait_m, difference.as_ref());
+}
+
+/// Whether the region predicate `predicate` required by an impl method
+/// follows from `bounds`, the bounds in scope for the trait method.
+fn region_predicate_entailed<'tcx>(bounds: &[ty::Predicate<'tcx>],
+                                   predicate: &ty::Predicate<'tcx>)
+                                   -> bool {
+    match *predicate {
+        ty::Predicate::RegionOutlives(ref data) => {
+            let ty::OutlivesPredicate(a, b) = data.0;
+            region_outlives(bounds, a, b)
+        }
+        ty::Predicate::TypeOutlives(ref data) => {
+            let ty::OutlivesPredicate(ty, r) = data.0;
+            match ty.sty {
+                ty::ty_param(..) | ty::ty_projection(..) => {
+                    bounds.iter().any(|bound| match *bound {
+                        ty::Predicate::TypeOutlives(ref bound) => {
+                            let ty::OutlivesPredicate(bound_ty, bound_r) = bound.0;
+                            bound_ty == ty && region_outlives(bounds, bound_r, r)
+                        }
+                        _ => false,
+                    })
+                }
+                // Any other type only outlives `r` if the regions and
+                // parameters within it do, which the well-formedness of the
+                // signature already requires.
+                _ => true,
+            }
+        }
+        ty::Predicate::Trait(..) |
+        ty::Predicate::Equate(..) |
+        ty::Predicate::Projection(..) => true,
+    }
+}
+
+/// Whether `a: b` follows from the region predicates in `bounds`.
+fn region_outlives<'tcx>(bounds: &[ty::Predicate<'tcx>], a: ty::Region, b: ty::Region) -> bool {
+    let mut reached = vec![a];
+    let mut i = 0;
+    while i < reached.len() {
+        let r = reached[i];
+        if r == b || r == ty::ReStatic {
+            return true;
+        }
+        for bound in bounds {
+            if let ty::Predicate::RegionOutlives(ref bound) = *bound {
+                let ty::OutlivesPredicate(longer, shorter) = bound.0;
+                if longer == r && !reached.contains(&shorter) {
+                    reached.push(shorter);
+                }
+            }
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Finds the bound in `generics` that `predicate` was derived from.
+fn predicate_span<'tcx>(tcx: &ty::ctxt<'tcx>,
+                        generics: &ast::Generics,
+                        predicate: &ty::Predicate<'tcx>)
+                        -> Option<Span> {
+    let (bounded, bound) = match *predicate {
+        ty::Predicate::Trait(ref data) => {
+            (data.0.self_ty(), BoundKind::Trait(data.0.def_id()))
+        }
+        ty::Predicate::Projection(ref data) => {
+            let trait_ref = &data.0.projection_ty.trait_ref;
+            (trait_ref.self_ty(), BoundKind::Trait(trait_ref.def_id))
+        }
+        ty::Predicate::TypeOutlives(ref data) => {
+            let ty::OutlivesPredicate(ty, r) = data.0;
+            match region_name(r) {
+                Some(name) => (ty, BoundKind::Region(name)),
+                None => return None,
+            }
+        }
+        ty::Predicate::RegionOutlives(ref data) => {
+            let ty::OutlivesPredicate(a, b) = data.0;
+            let (a, b) = match (region_name(a), region_name(b)) {
+                (Some(a), Some(b)) => (a, b),
+                _ => return None,
+            };
+            let find = |bounds: &[ast::Lifetime]| {
+                bounds.iter().find(|lt| lt.name == b).map(|lt| lt.span)
+            };
+            return generics.lifetimes.iter()
+                .filter(|def| def.lifetime.name == a)
+                .filter_map(|def| find(&def.bounds[..]))
+                .chain(generics.where_clause.predicates.iter().filter_map(|pred| {
+                    match *pred {
+                        ast::WherePredicate::RegionPredicate(ref pred)
+                            if pred.lifetime.name == a => find(&pred.bounds[..]),
+                        _ => None,
+                    }
+                }))
+                .next();
+        }
+        ty::Predicate::Equate(..) => return None,
+    };
+
+    let param_name = match bounded.sty {
+        ty::ty_param(ref p) => p.name,
+        _ => return None,
+    };
+    let find = |bounds: &[ast::TyParamBound]| {
+        bounds.iter().filter_map(|b| {
+            match (b, &bound) {
+                (&ast::TraitTyParamBound(ref ptr, _), &BoundKind::Trait(def_id))
+                    if ty::trait_ref_to_def_id(tcx, &ptr.trait_ref) == def_id => {
+                    Some(ptr.span)
+                }
+                (&ast::RegionTyParamBound(ref lt), &BoundKind::Region(name))
+                    if lt.name == name => {
+                    Some(lt.span)
+                }
+                _ => None,
+            }
+        }).next()
+    };
+    generics.ty_params.iter()
+        .filter(|tp| tp.ident.name == param_name)
+        .filter_map(|tp| find(&tp.bounds[..]))
+        .chain(generics.where_clause.predicates.iter().filter_map(|pred| {
+            match *pred {
+                ast::WherePredicate::BoundPredicate(ref pred)
+                    if is_param_path(&pred.bounded_ty, param_name) => find(&pred.bounds[..]),
+                _ => None,
+            }
+        }))
+        .next()
+}
+
+enum BoundKind {
+    Trait(ast::DefId),
+    Region(ast::Name),
+}
+
+fn region_name(r: ty::Region) -> Option<ast::Name> {
+    match r {
+        ty::ReEarlyBound(_, _, _, name) => Some(name),
+        ty::ReFree(ty::FreeRegion { bound_region: ty::BrNamed(_, name), .. }) => Some(name),
+        ty::ReStatic => Some(token::special_idents::static_lifetime.name),
+        _ => None,
+    }
+}
+
+fn is_param_path(ty: &ast::Ty, name: ast::Name) -> bool {
+    match ty.node {
+        ast::TyPath(None, ref path) => {
+            path.segments.len() == 1 && path.segments[0].identifier.name == name
+        }
+        _ => false,
+    }
+}
+
+/// Points at the trait's declaration of `trait_m`, or at the part of it named
+/// by `difference`, if the trait is defined in the local crate.
+fn note_trait_declaration<'tcx>(tcx: &ty::ctxt<'tcx>,
+                                trait_m: &ty::Method<'tcx>,
+                                difference: Option<&SigDifference>) {
+    if trait_m.def_id.krate != ast::LOCAL_CRATE {
+        return;
+    }
+    let span = method_sig(tcx, trait_m.def_id)
+        .and_then(|sig| difference.and_then(|d| difference_span(sig, d)))
+        .or_else(|| tcx.map.opt_span(trait_m.def_id.node));
+    if let Some(span) = span {
+        span_note!(tcx.sess, span, "the trait declaration is here");
+    }
+}
+
+fn method_sig<'a, 'tcx>(tcx: &'a ty::ctxt<'tcx>, def_id: ast::DefId)
+                        -> Option<&'a ast::MethodSig> {
+    if def_id.krate != ast::LOCAL_CRATE {
+        return None;
+    }
+    match tcx.map.find(def_id.node) {
+        Some(ast_map::NodeTraitItem(item)) => match item.node {
+            ast::MethodTraitItem(ref sig, _) => Some(sig),
+            _ => None,
+        },
+        Some(ast_map::NodeImplItem(item)) => match item.node {
+            ast::MethodImplItem(ref sig, _) => Some(sig),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn difference_span(sig: &ast::MethodSig, difference: &SigDifference) -> Option<Span> {
+    match *difference {
+        SigDifference::Unsafety | SigDifference::Abi => None,
+        SigDifference::SelfMutability => Some(sig.explicit_self.span),
+        SigDifference::Input(i, _, _) => sig.decl.inputs.get(i).map(|arg| arg.ty.span),
+        SigDifference::Output(..) => Some(sig.decl.output.span()),
+    }
+}
@@ -14,6 +14,7 @@ use middle::ty::{self, Ty};
 use middle::infer;
 
 use std::result::Result::{Err, Ok};
+use syntax::abi;
 use syntax::ast;
 use syntax::codemap::Span;
 use util::ppaux::Repr;
@@ -66,6 +67,26 @@ pub fn coerce<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>, sp: Span,
       Ok(()) => { /* ok */ }
       Err(ref err) => {
         fcx.report_mismatched_types(sp, expected, expr_ty, err);
+        note_closure_to_fn_pointer(fcx, sp, expected, expr_ty);
       }
     }
 }
+
+// Explains why a closure didn't coerce to the fn pointer that was expected.
+fn note_closure_to_fn_pointer<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>, sp: Span,
+                                        expected: Ty<'tcx>, expr_ty: Ty<'tcx>) {
+    let (closure_def_id, fn_ty) = match (&expr_ty.sty, &expected.sty) {
+        (&ty::ty_closure(closure_def_id, _), &ty::ty_bare_fn(None, fn_ty)) => {
+            (closure_def_id, fn_ty)
+        }
+        _ => return
+    };
+    if ty::with_freevars(fcx.tcx(), closure_def_id.node, |fv| !fv.is_empty()) {
+        fcx.tcx().sess.span_note(sp, "only closures which capture nothing from their \
+                                      environment can be coerced to `fn` pointers");
+    } else if fn_ty.abi != abi::Rust {
+        fcx.tcx().sess.span_note(sp, &format!("closures can only be coerced to `fn` pointers \
+                                               of the Rust ABI, not `extern {}`",
+                                              fn_ty.abi));
+    }
+}
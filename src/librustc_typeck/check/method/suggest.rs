@@ -20,8 +20,8 @@ use middle::def;
 use metadata::{csearch, cstore, decoder};
 use util::ppaux::UserString;
 
-use syntax::{ast, ast_util};
-use syntax::codemap::Span;
+use syntax::{ast, ast_map, ast_util};
+use syntax::codemap::{NO_EXPANSION, Span};
 use syntax::print::pprust;
 
 use std::cell;
@@ -172,12 +172,14 @@ fn suggest_traits_to_import<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
 
         fcx.sess().fileline_help(span, &msg[..]);
 
+        let insertion_point = use_insertion_point(fcx);
         for (i, trait_did) in candidates.iter().enumerate() {
-            fcx.sess().fileline_help(span,
-                                     &*format!("candidate #{}: use `{}`",
-                                               i + 1,
-                                               ty::item_path_str(fcx.tcx(), *trait_did)))
-
+            let path = ty::item_path_str(fcx.tcx(), *trait_did);
+            let msg = format!("candidate #{}: use `{}`", i + 1, path);
+            match insertion_point {
+                Some(sp) => fcx.sess().span_suggestion(sp, &msg, format!("use {};", path)),
+                None => fcx.sess().fileline_help(span, &msg),
+            }
         }
         return
     }
@@ -227,6 +229,33 @@ fn suggest_traits_to_import<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
     }
 }
 
+/// Where a `use` of a trait can be inserted to bring it into scope for the
+/// function being checked: just before the item the function belongs to, or
+/// before the impl or trait if it is a method.
+fn use_insertion_point(fcx: &FnCtxt) -> Option<Span> {
+    let map = &fcx.tcx().map;
+    let mut id = map.get_parent(fcx.body_id);
+    loop {
+        match map.find(id) {
+            Some(ast_map::NodeItem(item)) if item.span.expn_id == NO_EXPANSION => {
+                // Outer attributes and doc comments come before the item.
+                let lo = item.attrs.iter().fold(item.span.lo, |lo, attr| {
+                    if attr.span.lo < lo { attr.span.lo } else { lo }
+                });
+                return Some(Span { lo: lo, hi: lo, expn_id: NO_EXPANSION });
+            }
+            Some(ast_map::NodeImplItem(_)) | Some(ast_map::NodeTraitItem(_)) => {
+                let parent = map.get_parent(id);
+                if parent == id {
+                    return None;
+                }
+                id = parent;
+            }
+            _ => return None,
+        }
+    }
+}
+
 /// Checks whether there is a local type somewhere in the chain of
 /// autoderefs of `rcvr_ty`.
 fn type_derefs_to_local<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
@@ -111,6 +111,7 @@ use util::ppaux::{self, Repr};
 use util::nodemap::{DefIdMap, FnvHashMap, NodeMap};
 use util::lev_distance::lev_distance;
 
+use std::cmp;
 use std::cell::{Cell, Ref, RefCell};
 use std::collections::hash_map::Entry::{Occupied, Vacant};
 use std::mem::replace;
@@ -184,6 +185,12 @@ pub struct Inherited<'a, 'tcx: 'a> {
     // def-id of the closure, so that once we decide, we can easily go
     // back and process them.
     deferred_call_resolutions: RefCell<DefIdMap<Vec<DeferredCallResolutionHandler<'tcx>>>>,
+
+    // Inference variables created for omitted type parameters that
+    // have defaults, paired with the default (already substituted).
+    // A variable still unconstrained by the end of inference falls
+    // back to its default, see `default_type_parameters`.
+    type_var_defaults: RefCell<Vec<(Ty<'tcx>, Ty<'tcx>)>>,
 }
 
 trait DeferredCallResolution<'tcx> {
@@ -399,6 +406,7 @@ impl<'a, 'tcx> Inherited<'a, 'tcx> {
             fn_sig_map: RefCell::new(NodeMap()),
             fulfillment_cx: RefCell::new(traits::FulfillmentContext::new()),
             deferred_call_resolutions: RefCell::new(DefIdMap()),
+            type_var_defaults: RefCell::new(Vec::new()),
         }
     }
 
@@ -465,14 +473,14 @@ impl<'a, 'tcx> Visitor<'tcx> for CheckItemTypesVisitor<'a, 'tcx> {
     }
 }
 
-pub fn check_item_types(ccx: &CrateCtxt) {
+pub fn check_item_types(ccx: &CrateCtxt, errors_before: uint) {
     let krate = ccx.tcx.map.krate();
     let mut visit = wf::CheckTypeWellFormedVisitor::new(ccx);
     visit::walk_crate(&mut visit, krate);
 
     // If types are not well-formed, it leads to all manner of errors
     // downstream, so stop reporting errors at this point.
-    ccx.tcx.sess.abort_if_errors();
+    ccx.tcx.sess.abort_if_new_errors(errors_before);
 
     let mut visit = CheckItemTypesVisitor { ccx: ccx };
     visit::walk_crate(&mut visit, krate);
@@ -704,6 +712,11 @@ pub fn check_struct(ccx: &CrateCtxt, id: ast::NodeId, span: Span) {
 }
 
 pub fn check_item<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>, it: &'tcx ast::Item) {
+    // Types in the errors for an item are printed as seen from its module.
+    ty::with_path_printing_scope(ccx.tcx, it.id, || check_item_in_scope(ccx, it))
+}
+
+fn check_item_in_scope<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>, it: &'tcx ast::Item) {
     debug!("check_item(it.id={}, it.ident={})",
            it.id,
            ty::item_path_str(ccx.tcx, local_def(it.id)));
@@ -718,7 +731,8 @@ pub fn check_item<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>, it: &'tcx ast::Item) {
                             &enum_definition.variants,
                             it.id);
       }
-      ast::ItemFn(ref decl, _, _, _, ref body) => {
+      ast::ItemFn(ref decl, _, abi, _, ref body) => {
+        check_abi_for_target(ccx.tcx, it.span, abi);
         let fn_pty = ty::lookup_item_type(ccx.tcx, ast_util::local_def(it.id));
         let param_env = ParameterEnvironment::for_item(ccx.tcx, it.id);
         check_bare_fn(ccx, &**decl, &**body, it.id, it.span, fn_pty.ty, param_env);
@@ -779,6 +793,7 @@ pub fn check_item<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>, it: &'tcx ast::Item) {
         check_bounds_are_used(ccx, t.span, &generics.ty_params, pty_ty);
       }
       ast::ItemForeignMod(ref m) => {
+        check_abi_for_target(ccx.tcx, it.span, m.abi);
         if m.abi == abi::RustIntrinsic {
             for item in &m.items {
                 check_intrinsic_type(ccx, &**item);
@@ -804,6 +819,27 @@ pub fn check_item<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>, it: &'tcx ast::Item) {
     }
 }
 
+/// Reports an error if `abi` is tied to architectures other than the one
+/// being compiled for, e.g. `extern "stdcall"` on ARM.
+fn check_abi_for_target(tcx: &ty::ctxt, span: Span, abi: abi::Abi) {
+    let target = &tcx.sess.target.target;
+    let arch = match target.abi_arch() {
+        Some(arch) => arch,
+        None => return
+    };
+    if abi.for_arch(arch).is_none() {
+        span_err!(tcx.sess, span, E0329,
+                  "the {} ABI is not supported on `{}` targets", abi, arch);
+        let archs = abi.archs().iter()
+                              .map(|a| format!("`{}`", a))
+                              .collect::<Vec<_>>()
+                              .connect(", ");
+        tcx.sess.span_note(span,
+            &format!("the {} ABI only applies to {}; use \"system\" or \"C\" \
+                      for code that must build on other targets", abi, archs));
+    }
+}
+
 fn check_trait_on_unimplemented<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
                                generics: &ast::Generics,
                                item: &ast::Item) {
@@ -1201,10 +1237,36 @@ fn check_cast<'a,'tcx>(fcx: &FnCtxt<'a,'tcx>,
     let t_1 = structurally_resolved_type(fcx, span, t_1);
     let t_e = structurally_resolved_type(fcx, span, t_e);
 
+    // If the operand's type is still an integer or float variable, or if it
+    // is a literal which took its type from the cast, the cast is what
+    // determines the operand's type. Remember that for the trivial cast lints.
+    let operand_is_var = match t_e.sty {
+        ty::ty_infer(ty::IntVar(_)) | ty::ty_infer(ty::FloatVar(_)) => true,
+        _ => false
+    };
+    if operand_is_var || is_unsuffixed_numeric_lit(e) {
+        fcx.tcx().disambiguating_casts.borrow_mut().insert(id);
+    }
+
     check_cast_inner(fcx, span, t_1, t_e, e);
     fcx.write_ty(id, t_1);
 }
 
+/// Whether `e` is a numeric literal without a suffix, possibly negated or in
+/// parentheses.
+fn is_unsuffixed_numeric_lit(e: &ast::Expr) -> bool {
+    match e.node {
+        ast::ExprLit(ref lit) => match lit.node {
+            ast::LitInt(_, ast::UnsuffixedIntLit(_)) | ast::LitFloatUnsuffixed(_) => true,
+            _ => false
+        },
+        ast::ExprParen(ref e) | ast::ExprUnary(ast::UnNeg, ref e) => {
+            is_unsuffixed_numeric_lit(&**e)
+        }
+        _ => false
+    }
+}
+
 impl<'a, 'tcx> AstConv<'tcx> for FnCtxt<'a, 'tcx> {
     fn tcx(&self) -> &ty::ctxt<'tcx> { self.ccx.tcx }
 
@@ -1268,6 +1330,10 @@ impl<'a, 'tcx> AstConv<'tcx> for FnCtxt<'a, 'tcx> {
         self.infcx().next_ty_var()
     }
 
+    fn ty_infer_default(&self, ty: Ty<'tcx>, default: Ty<'tcx>) {
+        self.inh.type_var_defaults.borrow_mut().push((ty, default));
+    }
+
     fn projected_ty_from_poly_trait_ref(&self,
                                         span: Span,
                                         poly_trait_ref: ty::PolyTraitRef<'tcx>,
@@ -1389,6 +1455,8 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
 
     /// Apply "fallbacks" to some types
     /// ! gets replaced with (), unconstrained ints with i32, and unconstrained floats with f64.
+    /// Finally, omitted type parameters that are still unconstrained get their declared
+    /// defaults.
     pub fn default_type_parameters(&self) {
         use middle::ty::UnconstrainedNumeric::{UnconstrainedInt, UnconstrainedFloat, Neither};
         for (_, &mut ref ty) in &mut *self.inh.node_types.borrow_mut() {
@@ -1407,6 +1475,15 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                 }
             }
         }
+
+        for &(ty, default) in &*self.inh.type_var_defaults.borrow() {
+            let resolved = self.infcx().resolve_type_vars_if_possible(&ty);
+            if let ty::ty_infer(ty::TyVar(_)) = resolved.sty {
+                if !self.infcx().type_var_diverges(resolved) {
+                    demand::eqtype(self, codemap::DUMMY_SP, ty, default);
+                }
+            }
+        }
     }
 
     #[inline]
@@ -1534,6 +1611,7 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         match *adj {
             ty::AdjustReifyFnPointer(..) => { }
             ty::AdjustUnsafeFnPointer => { }
+            ty::AdjustClosureFnPointer(..) => { }
             ty::AdjustDerefRef(ref d_r) => {
                 match d_r.autoref {
                     Some(ref a_r) => {
@@ -2662,7 +2740,7 @@ fn expected_types_for_fn_args<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
                 // No argument expectations are produced if unification fails.
                 let origin = infer::Misc(call_span);
                 let ures = fcx.infcx().sub_types(false, origin, formal_ret_ty, ret_ty);
-                // FIXME(#15760) can't use try! here, FromError doesn't default
+                // FIXME(#15760) can't use try! here, From doesn't default
                 // to identity so the resulting type is not constrained.
                 if let Err(e) = ures {
                     return Err(e);
@@ -3659,7 +3737,10 @@ fn check_expr_with_unifier<'a, 'tcx, F>(fcx: &FnCtxt<'a, 'tcx>,
           };
 
           let mut def = path_res.base_def;
-          if path_res.depth == 0 {
+          if def == def::DefErr {
+              // Resolution has already reported this path.
+              fcx.write_error(id);
+          } else if path_res.depth == 0 {
               let (scheme, predicates) =
                   type_scheme_and_predicates_for_def(fcx, expr.span, def);
               instantiate_path(fcx, &path.segments,
@@ -4061,7 +4142,7 @@ fn check_expr_with_unifier<'a, 'tcx, F>(fcx: &FnCtxt<'a, 'tcx>,
               }
           }
        }
-       ast::ExprRange(ref start, ref end) => {
+       ast::ExprRange(ref start, ref end, limits) => {
           let t_start = start.as_ref().map(|e| {
             check_expr(fcx, &**e);
             fcx.expr_ty(&**e)
@@ -4100,6 +4181,9 @@ fn check_expr_with_unifier<'a, 'tcx, F>(fcx: &FnCtxt<'a, 'tcx>,
             Some(idx_type) => {
                 // Find the did from the appropriate lang item.
                 let did = match (start, end) {
+                    (&Some(_), &Some(_)) if limits == ast::RangeLimits::Closed => {
+                        tcx.lang_items.range_inclusive_struct()
+                    }
                     (&Some(_), &Some(_)) => tcx.lang_items.range_struct(),
                     (&Some(_), &None) => tcx.lang_items.range_from_struct(),
                     (&None, &Some(_)) => tcx.lang_items.range_to_struct(),
@@ -4356,34 +4440,37 @@ fn check_block_with_expected<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
     };
 
     let mut warned = false;
-    let mut any_diverges = false;
+    // The span of the first statement that diverges, if any.
+    let mut diverges_at = None;
     let mut any_err = false;
     for s in &blk.stmts {
         check_stmt(fcx, &**s);
         let s_id = ast_util::stmt_id(&**s);
         let s_ty = fcx.node_ty(s_id);
-        if any_diverges && !warned && match s.node {
-            ast::StmtDecl(ref decl, _) => {
-                match decl.node {
-                    ast::DeclLocal(_) => true,
-                    _ => false,
+        if let (Some(div_span), false) = (diverges_at, warned) {
+            if match s.node {
+                ast::StmtDecl(ref decl, _) => {
+                    match decl.node {
+                        ast::DeclLocal(_) => true,
+                        _ => false,
+                    }
                 }
+                ast::StmtExpr(_, _) | ast::StmtSemi(_, _) => true,
+                _ => false
+            } {
+                warned = warn_if_unreachable(fcx, blk, div_span, s_id, s.span,
+                                             "unreachable statement");
             }
-            ast::StmtExpr(_, _) | ast::StmtSemi(_, _) => true,
-            _ => false
-        } {
-            fcx.ccx
-                .tcx
-                .sess
-                .add_lint(lint::builtin::UNREACHABLE_CODE,
-                          s_id,
-                          s.span,
-                          "unreachable statement".to_string());
-            warned = true;
-        }
-        any_diverges = any_diverges || fcx.infcx().type_var_diverges(s_ty);
+        }
+        if diverges_at.is_none() && fcx.infcx().type_var_diverges(s_ty) {
+            diverges_at = Some(match s.node {
+                ast::StmtExpr(ref e, _) | ast::StmtSemi(ref e, _) => e.span,
+                _ => s.span
+            });
+        }
         any_err = any_err || ty::type_is_error(s_ty);
     }
+    let any_diverges = diverges_at.is_some();
     match blk.expr {
         None => if any_err {
             fcx.write_error(blk.id);
@@ -4393,14 +4480,9 @@ fn check_block_with_expected<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
             fcx.write_nil(blk.id);
         },
         Some(ref e) => {
-            if any_diverges && !warned {
-                fcx.ccx
-                    .tcx
-                    .sess
-                    .add_lint(lint::builtin::UNREACHABLE_CODE,
-                              e.id,
-                              e.span,
-                              "unreachable expression".to_string());
+            if let (Some(div_span), false) = (diverges_at, warned) {
+                warn_if_unreachable(fcx, blk, div_span, e.id, e.span,
+                                    "unreachable expression");
             }
             let ety = match expected {
                 ExpectHasType(ety) => {
@@ -4426,6 +4508,34 @@ fn check_block_with_expected<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
     *fcx.ps.borrow_mut() = prev;
 }
 
+/// Adds an `unreachable_code` lint for the statement or expression `id`,
+/// which follows the diverging expression at `div_span` in `blk`. Both spans
+/// are first traced back to the macro invocations written in `blk`, if any:
+/// when they come from the same invocation, the unreachable code is an
+/// artifact of the macro and nothing is reported. Returns whether the lint
+/// was added.
+fn warn_if_unreachable(fcx: &FnCtxt,
+                       blk: &ast::Block,
+                       div_span: Span,
+                       id: ast::NodeId,
+                       span: Span,
+                       msg: &str) -> bool {
+    let codemap = fcx.tcx().sess.codemap();
+    let div_span = codemap::original_sp(codemap, div_span, blk.span);
+    let span = codemap::original_sp(codemap, span, blk.span);
+    if span == div_span ||
+       (span.expn_id != codemap::NO_EXPANSION && span.expn_id == div_span.expn_id) {
+        return false;
+    }
+    fcx.tcx().sess.add_lint_with_notes(
+        lint::builtin::UNREACHABLE_CODE,
+        id,
+        span,
+        msg.to_string(),
+        vec![(div_span, "any code following this expression is unreachable".to_string())]);
+    true
+}
+
 /// Checks a constant appearing in a type. At the moment this is just the
 /// length expression in a fixed-length vector, but someday it might be
 /// extended to type-level numeric literals.
@@ -4669,7 +4779,7 @@ pub fn check_enum_variants<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>,
                     }
                 }
                 attr::ReprPacked => {
-                    ccx.tcx.sess.bug("range_to_inttype: found ReprPacked on an enum");
+                    // Already reported by `collect::check_repr_attrs`.
                 }
             }
             disr_vals.push(current_disr_val);
@@ -4737,7 +4847,8 @@ fn type_scheme_and_predicates_for_def<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
         def::DefUse(..) |
         def::DefRegion(..) |
         def::DefLabel(..) |
-        def::DefSelfTy(..) => {
+        def::DefSelfTy(..) |
+        def::DefErr => {
             fcx.ccx.tcx.sess.span_bug(sp, &format!("expected value, found {:?}", defn));
         }
     }
@@ -4876,7 +4987,8 @@ pub fn instantiate_path<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
         def::DefUse(..) |
         def::DefRegion(..) |
         def::DefLabel(..) |
-        def::DefUpvar(..) => {
+        def::DefUpvar(..) |
+        def::DefErr => {
             segment_spaces = repeat(None).take(segments.len()).collect();
         }
     }
@@ -5139,6 +5251,18 @@ pub fn instantiate_path<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
         // everything.
         if provided_len == 0 && !(require_type_space && space == subst::TypeSpace) {
             substs.types.replace(space, fcx.infcx().next_ty_vars(desired.len()));
+
+            // Remember the defaults of these parameters in case inference
+            // leaves them unconstrained. Defaults mentioning `Self` can
+            // only be substituted once the self type is known.
+            for (def, &ty) in desired.iter().zip(substs.types.get_slice(space).iter()) {
+                if let Some(default) = def.default {
+                    if substs.types.len(subst::SelfSpace) != 0 || !ty::type_has_self(default) {
+                        let default = default.subst_spanned(fcx.tcx(), substs, Some(span));
+                        fcx.ty_infer_default(ty, default);
+                    }
+                }
+            }
             return;
         }
 
@@ -5301,6 +5425,56 @@ pub fn check_bounds_are_used<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
     }
 }
 
+/// The names of the non-atomic intrinsics `check_intrinsic_type` knows about,
+/// used to suggest a replacement for a misspelt one.
+const INTRINSIC_NAMES: &'static [&'static str] = &[
+    "abort", "unreachable", "breakpoint", "size_of", "pref_align_of", "min_align_of", "init",
+    "uninit", "forget", "transmute", "move_val_init", "needs_drop", "owns_managed", "type_name",
+    "type_id", "offset", "copy", "copy_nonoverlapping", "volatile_copy_memory",
+    "volatile_copy_nonoverlapping_memory", "write_bytes", "volatile_set_memory", "sqrtf32",
+    "sqrtf64", "powif32", "powif64", "sinf32", "sinf64", "cosf32", "cosf64", "powf32", "powf64",
+    "expf32", "expf64", "exp2f32", "exp2f64", "logf32", "logf64", "log10f32", "log10f64",
+    "log2f32", "log2f64", "fmaf32", "fmaf64", "fabsf32", "fabsf64", "copysignf32", "copysignf64",
+    "floorf32", "floorf64", "ceilf32", "ceilf64", "truncf32", "truncf64", "rintf32", "rintf64",
+    "nearbyintf32", "nearbyintf64", "roundf32", "roundf64", "ctpop8", "ctpop16", "ctpop32",
+    "ctpop64", "ctlz8", "ctlz16", "ctlz32", "ctlz64", "cttz8", "cttz16", "cttz32", "cttz64",
+    "bswap16", "bswap32", "bswap64", "volatile_load", "volatile_store", "unaligned_load",
+    "unaligned_store", "i8_add_with_overflow", "i8_sub_with_overflow", "i8_mul_with_overflow",
+    "i16_add_with_overflow", "i16_sub_with_overflow", "i16_mul_with_overflow",
+    "i32_add_with_overflow", "i32_sub_with_overflow", "i32_mul_with_overflow",
+    "i64_add_with_overflow", "i64_sub_with_overflow", "i64_mul_with_overflow",
+    "u8_add_with_overflow", "u8_sub_with_overflow", "u8_mul_with_overflow",
+    "u16_add_with_overflow", "u16_sub_with_overflow", "u16_mul_with_overflow",
+    "u32_add_with_overflow", "u32_sub_with_overflow", "u32_mul_with_overflow",
+    "u64_add_with_overflow", "u64_sub_with_overflow", "u64_mul_with_overflow", "overflowing_add",
+    "overflowing_sub", "overflowing_mul", "return_address", "assume", "likely", "unlikely",
+];
+
+/// The operations that may follow `atomic_` in an atomic intrinsic's name.
+const ATOMIC_OPERATIONS: &'static [&'static str] = &[
+    "cxchg", "load", "store", "xchg", "xadd", "xsub", "and", "nand", "or", "xor", "max", "min",
+    "umax", "umin", "fence",
+];
+
+/// Intrinsics whose type parameter may be unsized; all others require every
+/// type parameter to be `Sized`.
+const UNSIZED_PARAM_INTRINSICS: &'static [&'static str] = &["type_name", "type_id"];
+
+fn suggest_intrinsic_name(tcx: &ty::ctxt, span: Span, name: &str, known: &[&str]) {
+    let mut best_dist = name.len();
+    let mut best = None;
+    for &candidate in known {
+        let dist = lev_distance(candidate, name);
+        if dist < best_dist && dist <= cmp::max(name.len(), 3) / 3 {
+            best = Some(candidate);
+            best_dist = dist;
+        }
+    }
+    if let Some(candidate) = best {
+        tcx.sess.span_help(span, &format!("did you mean `{}`?", candidate));
+    }
+}
+
 pub fn check_intrinsic_type(ccx: &CrateCtxt, it: &ast::ForeignItem) {
     fn param<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>, n: u32) -> Ty<'tcx> {
         let name = token::intern(&format!("P{}", n));
@@ -5335,6 +5509,7 @@ pub fn check_intrinsic_type(ccx: &CrateCtxt, it: &ast::ForeignItem) {
             op => {
                 span_err!(tcx.sess, it.span, E0092,
                     "unrecognized atomic operation function: `{}`", op);
+                suggest_intrinsic_name(tcx, it.span, op, ATOMIC_OPERATIONS);
                 return;
             }
         };
@@ -5491,6 +5666,10 @@ pub fn check_intrinsic_type(ccx: &CrateCtxt, it: &ast::ForeignItem) {
                 (1, vec!( ty::mk_imm_ptr(tcx, param(ccx, 0)) ), param(ccx, 0)),
             "volatile_store" =>
                 (1, vec!( ty::mk_mut_ptr(tcx, param(ccx, 0)), param(ccx, 0) ), ty::mk_nil(tcx)),
+            "unaligned_load" =>
+                (1, vec!( ty::mk_imm_ptr(tcx, param(ccx, 0)) ), param(ccx, 0)),
+            "unaligned_store" =>
+                (1, vec!( ty::mk_mut_ptr(tcx, param(ccx, 0)), param(ccx, 0) ), ty::mk_nil(tcx)),
 
             "i8_add_with_overflow" | "i8_sub_with_overflow" | "i8_mul_with_overflow" =>
                 (0, vec!(tcx.types.i8, tcx.types.i8),
@@ -5531,9 +5710,12 @@ pub fn check_intrinsic_type(ccx: &CrateCtxt, it: &ast::ForeignItem) {
 
             "assume" => (0, vec![tcx.types.bool], ty::mk_nil(tcx)),
 
+            "likely" | "unlikely" => (0, vec![tcx.types.bool], tcx.types.bool),
+
             ref other => {
                 span_err!(tcx.sess, it.span, E0093,
                     "unrecognized intrinsic function: `{}`", *other);
+                suggest_intrinsic_name(tcx, it.span, other, INTRINSIC_NAMES);
                 return;
             }
         };
@@ -5556,6 +5738,16 @@ pub fn check_intrinsic_type(ccx: &CrateCtxt, it: &ast::ForeignItem) {
              parameters: found {}, expected {}",
              i_n_tps, n_tps);
     } else {
+        if n_tps > 0 && !UNSIZED_PARAM_INTRINSICS.contains(&&name[..]) {
+            let param_env = ParameterEnvironment::for_item(tcx, it.id);
+            for &param_ty in param_env.free_substs.types.get_slice(subst::FnSpace) {
+                if !ty::type_is_sized(&param_env, it.span, param_ty) {
+                    span_err!(tcx.sess, it.span, E0330,
+                              "type parameter `{}` of intrinsic `{}` must be `Sized`",
+                              ppaux::ty_to_string(tcx, param_ty), name);
+                }
+            }
+        }
         require_same_types(tcx,
                            None,
                            false,
@@ -12,6 +12,7 @@ use astconv::AstConv;
 use check::{FnCtxt, Inherited, blank_fn_ctxt, vtable, regionck};
 use constrained_type_params::identify_constrained_type_params;
 use CrateCtxt;
+use lint;
 use middle::region;
 use middle::subst::{self, TypeSpace, FnSpace, ParamSpace, SelfSpace};
 use middle::traits;
@@ -22,6 +23,7 @@ use util::ppaux::{Repr, UserString};
 
 use std::collections::HashSet;
 use syntax::ast;
+use syntax::ast_map;
 use syntax::ast_util::{local_def};
 use syntax::attr;
 use syntax::codemap::Span;
@@ -81,18 +83,17 @@ impl<'ccx, 'tcx> CheckTypeWellFormedVisitor<'ccx, 'tcx> {
             ast::ItemImpl(_, ast::ImplPolarity::Positive, _, _, _, _) => {
                 self.check_impl(item);
             }
-            ast::ItemImpl(_, ast::ImplPolarity::Negative, _, Some(_), _, _) => {
+            ast::ItemImpl(_, ast::ImplPolarity::Negative, _, Some(ref ast_trait_ref), _, _) => {
+                // `Send` and `Sync` are themselves declared with a default
+                // impl in `core::marker`, so they need no special casing
+                // here: a negative impl is only meaningful for a trait that
+                // would otherwise be implemented automatically.
                 let trait_ref = ty::impl_id_to_trait_ref(ccx.tcx, item.id);
                 ty::populate_implementations_for_trait_if_necessary(ccx.tcx, trait_ref.def_id);
-                match ccx.tcx.lang_items.to_builtin_kind(trait_ref.def_id) {
-                    Some(ty::BoundSend) | Some(ty::BoundSync) => {}
-                    Some(_) | None => {
-                        if !ty::trait_has_default_impl(ccx.tcx, trait_ref.def_id) {
-                            span_err!(ccx.tcx.sess, item.span, E0192,
-                                      "negative impls are only allowed for traits with \
-                                       default impls (e.g., `Send` and `Sync`)")
-                        }
-                    }
+                if !ty::trait_has_default_impl(ccx.tcx, trait_ref.def_id) {
+                    span_err!(ccx.tcx.sess, ast_trait_ref.path.span, E0192,
+                              "negative impls are only allowed for traits with \
+                               default impls (e.g., `Send` and `Sync`)")
                 }
             }
             ast::ItemFn(..) => {
@@ -304,6 +305,36 @@ impl<'ccx, 'tcx> CheckTypeWellFormedVisitor<'ccx, 'tcx> {
         });
     }
 
+    /// Checks that the types in the signature of the method `id` meet their
+    /// own bounds, e.g. that `Foo<Bar>` only appears where `Bar` satisfies
+    /// `Foo`'s bounds. Unlike items, methods were not checked this way in
+    /// the past, so for now unmet bounds are only reported through the
+    /// `ill_formed_signatures` lint unless `-Z future-wf-errors` is given.
+    ///
+    /// As with `check_item_type`, region obligations are ignored: a
+    /// signature is allowed to rely on the bounds its types imply.
+    fn check_method_sig(&mut self, span: Span, id: ast::NodeId) {
+        let ccx = self.ccx;
+        let method = match ty::impl_or_trait_item(ccx.tcx, local_def(id)) {
+            ty::MethodTraitItem(method) => method,
+            ty::TypeTraitItem(_) => return,
+        };
+        let param_env = ty::construct_parameter_environment(ccx.tcx,
+                                                            span,
+                                                            &method.generics,
+                                                            &method.predicates,
+                                                            id);
+        let inh = Inherited::new(ccx.tcx, param_env);
+        let fcx = blank_fn_ctxt(ccx, &inh, ty::FnConverging(ty::mk_nil(ccx.tcx)), id);
+        {
+            let mut bounds_checker = BoundsChecker::new(&fcx, span, id, None);
+            let fty = ty::mk_bare_fn(ccx.tcx, None, ccx.tcx.mk_bare_fn(method.fty.clone()));
+            let fty = fcx.instantiate_type_scheme(span, &fcx.inh.param_env.free_substs, &fty);
+            bounds_checker.check_traits_in_ty(fty);
+        }
+        report_signature_errors(&fcx, id);
+    }
+
     fn check_variances_for_type_defn(&self,
                                      item: &ast::Item,
                                      ast_generics: &ast::Generics)
@@ -402,23 +433,18 @@ impl<'ccx, 'tcx> CheckTypeWellFormedVisitor<'ccx, 'tcx> {
                          param_name: ast::Name,
                          suggested_marker_id: Option<ast::DefId>)
     {
-        self.tcx().sess.span_err(
-            span,
-            &format!("parameter `{}` is never used",
-                     param_name.user_string(self.tcx())));
-
-        match suggested_marker_id {
+        let suggestion = match suggested_marker_id {
             Some(def_id) => {
-                self.tcx().sess.fileline_help(
-                    span,
-                    &format!("consider removing `{}` or using a marker such as `{}`",
-                             param_name.user_string(self.tcx()),
-                             ty::item_path_str(self.tcx(), def_id)));
+                format!("; consider removing it or using a marker such as `{}`",
+                        ty::item_path_str(self.tcx(), def_id))
             }
-            None => {
-                // no lang items, no help!
-            }
-        }
+            // no lang items, no suggestion!
+            None => String::new(),
+        };
+        span_err!(self.tcx().sess, span, E0324,
+                  "parameter `{}` is never used{}",
+                  param_name.user_string(self.tcx()),
+                  suggestion);
     }
 }
 
@@ -460,17 +486,32 @@ fn reject_non_type_param_bounds<'tcx>(tcx: &ty::ctxt<'tcx>,
     }
 }
 
-fn reject_shadowing_type_parameters<'tcx>(tcx: &ty::ctxt<'tcx>,
-                                          span: Span,
-                                          generics: &ty::Generics<'tcx>) {
-    let impl_params = generics.types.get_slice(subst::TypeSpace).iter()
-        .map(|tp| tp.name).collect::<HashSet<_>>();
-
-    for method_param in generics.types.get_slice(subst::FnSpace) {
-        if impl_params.contains(&method_param.name) {
-            span_err!(tcx.sess, span, E0194,
+/// Reports type parameters of the method `method_id` that reuse the name of a
+/// type parameter of the enclosing impl or trait.
+fn reject_shadowing_type_parameters(tcx: &ty::ctxt,
+                                    method_id: ast::NodeId,
+                                    method_generics: &ast::Generics) {
+    let outer_generics = match tcx.map.find(tcx.map.get_parent(method_id)) {
+        Some(ast_map::NodeItem(item)) => {
+            match item.node {
+                ast::ItemImpl(_, _, ref generics, _, _, _) |
+                ast::ItemTrait(_, ref generics, _, _) => generics,
+                _ => return,
+            }
+        }
+        _ => return,
+    };
+
+    for method_param in method_generics.ty_params.iter() {
+        let shadowed = outer_generics.ty_params.iter()
+                                     .find(|p| p.ident.name == method_param.ident.name);
+        if let Some(outer_param) = shadowed {
+            span_err!(tcx.sess, method_param.span, E0194,
                 "type parameter `{}` shadows another type parameter of the same name",
-                          token::get_name(method_param.name));
+                          token::get_ident(method_param.ident));
+            span_note!(tcx.sess, outer_param.span,
+                       "the shadowed type parameter `{}` is declared here",
+                       token::get_ident(outer_param.ident));
         }
     }
 }
@@ -486,33 +527,27 @@ impl<'ccx, 'tcx, 'v> Visitor<'v> for CheckTypeWellFormedVisitor<'ccx, 'tcx> {
                 b: &'v ast::Block, span: Span, id: ast::NodeId) {
         match fk {
             visit::FkFnBlock | visit::FkItemFn(..) => {}
-            visit::FkMethod(..) => {
-                match ty::impl_or_trait_item(self.tcx(), local_def(id)) {
-                    ty::ImplOrTraitItem::MethodTraitItem(ty_method) => {
-                        reject_shadowing_type_parameters(self.tcx(), span, &ty_method.generics)
-                    }
-                    _ => {}
-                }
+            visit::FkMethod(_, sig) => {
+                reject_shadowing_type_parameters(self.tcx(), id, &sig.generics);
+                self.check_method_sig(span, id);
             }
         }
         visit::walk_fn(self, fk, fd, b, span)
     }
 
     fn visit_trait_item(&mut self, trait_item: &'v ast::TraitItem) {
-        if let ast::MethodTraitItem(_, None) = trait_item.node {
+        if let ast::MethodTraitItem(ref sig, None) = trait_item.node {
             match ty::impl_or_trait_item(self.tcx(), local_def(trait_item.id)) {
                 ty::ImplOrTraitItem::MethodTraitItem(ty_method) => {
                     reject_non_type_param_bounds(
                         self.tcx(),
                         trait_item.span,
                         &ty_method.predicates);
-                    reject_shadowing_type_parameters(
-                        self.tcx(),
-                        trait_item.span,
-                        &ty_method.generics);
                 }
                 _ => {}
             }
+            reject_shadowing_type_parameters(self.tcx(), trait_item.id, &sig.generics);
+            self.check_method_sig(trait_item.span, trait_item.id);
         }
 
         visit::walk_trait_item(self, trait_item)
@@ -673,6 +708,33 @@ impl<'cx,'tcx> TypeFolder<'tcx> for BoundsChecker<'cx,'tcx> {
     }
 }
 
+/// Reports the obligations registered by `check_method_sig` that do not
+/// hold, as errors under `-Z future-wf-errors` and as lints otherwise.
+fn report_signature_errors<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>, id: ast::NodeId) {
+    vtable::select_all_fcx_obligations_and_apply_defaults(fcx);
+    let result = fcx.inh.fulfillment_cx.borrow_mut().select_all_or_error(fcx.infcx(), fcx);
+    let errors = match result {
+        Ok(()) => return,
+        Err(errors) => errors,
+    };
+
+    let tcx = fcx.tcx();
+    if tcx.sess.opts.debugging_opts.future_wf_errors {
+        traits::report_fulfillment_errors(fcx.infcx(), &errors);
+        return;
+    }
+    for error in &errors {
+        let predicate = fcx.infcx().resolve_type_vars_if_possible(&error.obligation.predicate);
+        tcx.sess.add_lint(lint::builtin::ILL_FORMED_SIGNATURES,
+                          id,
+                          error.obligation.cause.span,
+                          format!("the requirement `{}` is not satisfied by the types in \
+                                   this signature; this will become an error in the future \
+                                   (see `-Z future-wf-errors`)",
+                                  predicate.user_string(tcx)));
+    }
+}
+
 ///////////////////////////////////////////////////////////////////////////
 // ADT
 
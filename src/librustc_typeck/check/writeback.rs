@@ -269,6 +269,10 @@ impl<'cx, 'tcx> WritebackCx<'cx, 'tcx> {
                         ty::AdjustUnsafeFnPointer
                     }
 
+                    ty::AdjustClosureFnPointer(fn_ty) => {
+                        ty::AdjustClosureFnPointer(self.resolve(&fn_ty, reason))
+                    }
+
                     ty::AdjustDerefRef(adj) => {
                         for autoderef in 0..adj.autoderefs {
                             let method_call = MethodCall::autoderef(id, autoderef);
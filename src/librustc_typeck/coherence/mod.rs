@@ -200,8 +200,16 @@ impl<'a, 'tcx> CoherenceChecker<'a, 'tcx> {
 
         let impl_type_scheme = ty::lookup_item_type(tcx, impl_id);
 
+        // Only the defaults the impl doesn't override get a copy: a second
+        // item with the same name would let a lookup by name (for vtables,
+        // or from downstream crates through metadata) find the default
+        // instead of the impl's own method.
+        let overridden: HashSet<ast::Name> = all_impl_items.iter().map(|item| {
+            ty::impl_or_trait_item(tcx, item.def_id()).name()
+        }).collect();
+
         let prov = ty::provided_trait_methods(tcx, trait_ref.def_id);
-        for trait_method in &prov {
+        for trait_method in prov.iter().filter(|m| !overridden.contains(&m.name)) {
             // Synthesize an ID.
             let new_id = tcx.sess.next_node_id();
             let new_did = local_def(new_id);
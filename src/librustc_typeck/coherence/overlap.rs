@@ -9,23 +9,27 @@
 // except according to those terms.
 
 //! Overlap: No two impls for the same trait are implemented for the
-//! same type.
+//! same type, and no two inherent impls that can apply to the same type
+//! define items with the same name.
 
 use middle::traits;
 use middle::ty;
 use middle::infer::{self, new_infer_ctxt};
+use std::rc::Rc;
 use syntax::ast::{DefId};
 use syntax::ast::{LOCAL_CRATE};
 use syntax::ast;
 use syntax::ast_util;
 use syntax::visit;
 use syntax::codemap::Span;
+use syntax::parse::token;
 use util::nodemap::DefIdMap;
-use util::ppaux::Repr;
+use util::ppaux::{Repr, UserString};
 
 pub fn check(tcx: &ty::ctxt) {
     let mut overlap = OverlapChecker { tcx: tcx, default_impls: DefIdMap() };
     overlap.check_for_overlapping_impls();
+    overlap.check_for_duplicate_inherent_items();
 
     // this secondary walk specifically checks for impls of defaulted
     // traits, for which additional overlap rules exist
@@ -61,6 +65,75 @@ impl<'cx, 'tcx> OverlapChecker<'cx, 'tcx> {
         }
     }
 
+    fn check_for_duplicate_inherent_items(&self) {
+        debug!("check_for_duplicate_inherent_items");
+
+        // Every inherent impl of a local type is itself local, so the map
+        // built up while collecting this crate's impls is all we need.
+        let inherent_impls: Vec<Rc<Vec<ast::DefId>>> =
+            self.tcx.inherent_impls.borrow().values().cloned().collect();
+
+        for impls in &inherent_impls {
+            for (i, &impl1_def_id) in impls.iter().enumerate() {
+                if impl1_def_id.krate != ast::LOCAL_CRATE {
+                    continue;
+                }
+
+                for &impl2_def_id in &impls[(i+1)..] {
+                    if impl2_def_id.krate != ast::LOCAL_CRATE {
+                        continue;
+                    }
+                    self.check_if_inherent_impls_share_items(impl1_def_id, impl2_def_id);
+                }
+            }
+        }
+    }
+
+    /// Reports each item of `impl2` whose name is already used by an item
+    /// of `impl1`, if some type could be covered by both impls. Generic
+    /// impls like `impl Foo<i32>` and `impl Foo<u32>` never overlap.
+    fn check_if_inherent_impls_share_items(&self,
+                                           impl1_def_id: ast::DefId,
+                                           impl2_def_id: ast::DefId)
+    {
+        debug!("check_if_inherent_impls_share_items({}, {})",
+               impl1_def_id.repr(self.tcx),
+               impl2_def_id.repr(self.tcx));
+
+        let impl_items = self.tcx.impl_items.borrow();
+        let item_names = |impl_def_id: ast::DefId| -> Vec<(ast::Name, ast::DefId)> {
+            impl_items[&impl_def_id].iter().map(|item| {
+                let def_id = item.def_id();
+                (ty::impl_or_trait_item(self.tcx, def_id).name(), def_id)
+            }).collect()
+        };
+        let items1 = item_names(impl1_def_id);
+        let items2 = item_names(impl2_def_id);
+
+        let duplicates: Vec<_> = items2.iter().filter_map(|&(name, def_id2)| {
+            items1.iter()
+                  .find(|&&(n, _)| n == name)
+                  .map(|&(_, def_id1)| (name, def_id1, def_id2))
+        }).collect();
+        if duplicates.is_empty() {
+            return;
+        }
+
+        let infcx = infer::new_infer_ctxt(self.tcx);
+        if !traits::overlapping_inherent_impls(&infcx, impl1_def_id, impl2_def_id) {
+            return;
+        }
+
+        for &(name, first, second) in &duplicates {
+            span_err!(self.tcx.sess, self.tcx.map.span(second.node), E0335,
+                      "duplicate definitions with name `{}`",
+                      token::get_name(name));
+            span_note!(self.tcx.sess, self.tcx.map.span(first.node),
+                       "other definition for `{}` here",
+                       token::get_name(name));
+        }
+    }
+
     fn check_for_overlapping_impls_of_trait(&self,
                                             trait_def_id: ast::DefId,
                                             trait_impls: &Vec<ast::DefId>)
@@ -100,7 +173,45 @@ impl<'cx, 'tcx> OverlapChecker<'cx, 'tcx> {
             return;
         }
 
-        self.report_overlap_error(trait_def_id, impl1_def_id, impl2_def_id);
+        match (ty::trait_impl_polarity(self.tcx, impl1_def_id),
+               ty::trait_impl_polarity(self.tcx, impl2_def_id)) {
+            (Some(p1), Some(p2)) if p1 != p2 => {
+                self.report_polarity_conflict(trait_def_id, impl1_def_id, impl2_def_id, p1);
+            }
+            _ => {
+                self.report_overlap_error(trait_def_id, impl1_def_id, impl2_def_id);
+            }
+        }
+    }
+
+    /// Reports a type that has both a positive and a negative impl of
+    /// the same (defaulted) trait, pointing at each of them.
+    fn report_polarity_conflict(&self, trait_def_id: ast::DefId,
+                                impl1: ast::DefId, impl2: ast::DefId,
+                                impl1_polarity: ast::ImplPolarity) {
+        let self_ty = ty::impl_trait_ref(self.tcx, impl1).unwrap().self_ty();
+        span_err!(self.tcx.sess, self.span_of_impl(impl1), E0323,
+                  "found both positive and negative implementation of trait `{}` \
+                   for type `{}`",
+                  ty::item_path_str(self.tcx, trait_def_id),
+                  self_ty.user_string(self.tcx));
+
+        let (this_kind, other_kind) = match impl1_polarity {
+            ast::ImplPolarity::Positive => ("positive", "negative"),
+            ast::ImplPolarity::Negative => ("negative", "positive"),
+        };
+        span_note!(self.tcx.sess, self.span_of_impl(impl1),
+                   "{} implementation here", this_kind);
+        if impl2.krate == ast::LOCAL_CRATE {
+            span_note!(self.tcx.sess, self.span_of_impl(impl2),
+                       "{} implementation here", other_kind);
+        } else {
+            let crate_store = &self.tcx.sess.cstore;
+            let cdata = crate_store.get_crate_data(impl2.krate);
+            span_note!(self.tcx.sess, self.span_of_impl(impl1),
+                       "{} implementation in crate `{}`",
+                       other_kind, cdata.name);
+        }
     }
 
     fn report_overlap_error(&self, trait_def_id: ast::DefId,
@@ -90,6 +90,7 @@ use syntax::abi;
 use syntax::ast;
 use syntax::ast_map;
 use syntax::ast_util::local_def;
+use syntax::attr;
 use syntax::codemap::Span;
 use syntax::parse::token::{special_idents};
 use syntax::parse::token;
@@ -767,9 +768,86 @@ fn ensure_no_ty_param_bounds(ccx: &CrateCtxt,
     }
 }
 
+/// Checks that the `#[repr]` hints on `it` make sense for the kind of
+/// item they are attached to and do not contradict one another.
+fn check_repr_attrs(ccx: &CrateCtxt, it: &ast::Item) {
+    let tcx = ccx.tcx;
+    let mut hints = Vec::new();
+    for attr in &it.attrs {
+        hints.extend(attr::find_repr_attrs_with_spans(attr).into_iter());
+    }
+    if hints.is_empty() {
+        return;
+    }
+
+    // Hints that are accepted on this kind of item but do nothing.
+    let mut no_effect = Vec::new();
+    match it.node {
+        ast::ItemStruct(..) => {
+            for &(sp, hint) in &hints {
+                if let attr::ReprInt(..) = hint {
+                    no_effect.push((sp, "integer representation hints have no effect \
+                                         on a struct".to_string()));
+                }
+            }
+        }
+        ast::ItemEnum(ref enum_definition, _) => {
+            let is_c_like = enum_definition.variants.iter().all(|v| {
+                match v.node.kind {
+                    ast::TupleVariantKind(ref args) => args.is_empty(),
+                    ast::StructVariantKind(_) => false,
+                }
+            });
+            for &(sp, hint) in &hints {
+                match hint {
+                    attr::ReprPacked => {
+                        span_err!(tcx.sess, sp, E0326,
+                                  "the `packed` representation is only supported for structs");
+                    }
+                    attr::ReprInt(..) if !is_c_like => {
+                        span_err!(tcx.sess, sp, E0327,
+                                  "integer representation hints are not supported \
+                                   for enums with fields");
+                    }
+                    _ => {}
+                }
+            }
+
+            // An enum can only have one discriminant representation.
+            let discr_hints: Vec<_> = hints.iter().filter(|&&(_, hint)| {
+                match hint {
+                    attr::ReprInt(..) | attr::ReprExtern => true,
+                    attr::ReprPacked | attr::ReprAny => false,
+                }
+            }).collect();
+            for &&(sp, hint) in discr_hints.iter().skip(1) {
+                let (first_sp, first_hint) = *discr_hints[0];
+                let same = match (hint, first_hint) {
+                    (attr::ReprInt(_, ity), attr::ReprInt(_, first_ity)) => ity == first_ity,
+                    _ => hint == first_hint,
+                };
+                if !same {
+                    span_err!(tcx.sess, sp, E0328,
+                              "conflicting representation hints");
+                    span_note!(tcx.sess, first_sp,
+                               "conflicts with the representation hint here");
+                }
+            }
+        }
+        _ => {
+            no_effect.push((hints[0].0, format!("`#[repr]` has no effect on a {}",
+                                                it.node.descriptive_variant())));
+        }
+    }
+    for (sp, msg) in no_effect {
+        span_warn!(tcx.sess, sp, E0325, "{}", msg);
+    }
+}
+
 fn convert_item(ccx: &CrateCtxt, it: &ast::Item) {
     let tcx = ccx.tcx;
     debug!("convert: item {} with id {}", token::get_ident(it.ident), it.id);
+    check_repr_attrs(ccx, it);
     match it.node {
         // These don't define types.
         ast::ItemExternCrate(_) | ast::ItemUse(_) |
@@ -1805,20 +1883,26 @@ fn get_or_create_type_parameter_def<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>,
         None => None,
         Some(ref path) => {
             let ty = ast_ty_to_ty(&ccx.icx(&()), &ExplicitRscope, &**path);
-            let cur_idx = index;
 
+            // A default can only be substituted once the parameters
+            // before it are known, so it must not mention this parameter
+            // or any later one.
+            let mut forward = false;
             ty::walk_ty(ty, |t| {
                 match t.sty {
-                    ty::ty_param(p) => if p.idx > cur_idx {
-                        span_err!(tcx.sess, path.span, E0128,
-                                  "type parameters with a default cannot use \
-                                  forward declared identifiers");
-                        },
-                        _ => {}
-                    }
+                    ty::ty_param(p) if p.space == space && p.idx >= index => forward = true,
+                    _ => {}
+                }
             });
 
-            Some(ty)
+            if forward {
+                span_err!(tcx.sess, path.span, E0128,
+                          "type parameters with a default cannot use \
+                           forward declared identifiers");
+                Some(tcx.types.err)
+            } else {
+                Some(ty)
+            }
         }
     };
 
@@ -147,7 +147,7 @@ register_diagnostics! {
     E0222, // variadic function must have C calling convention
     E0223, // ambiguous associated type
     E0224, // at least one non-builtin train is required for an object type
-    E0225, // only the builtin traits can be used as closure or object bounds
+    E0225, // only one non-builtin trait can be used in an object type
     E0226, // only a single explicit lifetime bound is permitted
     E0227, // ambiguous lifetime bound, explicit lifetime bound required
     E0228, // explicit lifetime bound required
@@ -177,7 +177,16 @@ register_diagnostics! {
     E0319, // trait impls for defaulted traits allowed just for structs/enums
     E0320, // recursive overflow during dropck
     E0321, // extended coherence rules for defaulted traits violated
-    E0322  // cannot implement Sized explicitly
+    E0322, // cannot implement Sized explicitly
+    E0323, // both a positive and a negative impl of a defaulted trait
+    E0324, // type or lifetime parameter is never used
+    E0325, // representation hint has no effect on this kind of item
+    E0326, // `packed` representation on an enum
+    E0327, // integer representation on an enum with fields
+    E0328, // conflicting representation hints
+    E0329, // ABI not supported on the target architecture
+    E0330, // intrinsic type parameter is not `Sized`
+    E0335  // duplicate item name across inherent impls of a type
 }
 
 __build_diagnostic_array! { DIAGNOSTICS }
@@ -320,6 +320,9 @@ fn check_for_entry_fn(ccx: &CrateCtxt) {
 
 pub fn check_crate(tcx: &ty::ctxt, trait_map: ty::TraitMap) {
     let time_passes = tcx.sess.time_passes();
+    // Under `-Z keep-going`, resolution may have reported errors it
+    // recovered from; only errors from type checking itself stop it early.
+    let errors = tcx.sess.err_count();
     let ccx = CrateCtxt {
         trait_map: trait_map,
         all_traits: RefCell::new(None),
@@ -331,7 +334,7 @@ pub fn check_crate(tcx: &ty::ctxt, trait_map: ty::TraitMap) {
 
     // this ensures that later parts of type checking can assume that items
     // have valid types and not error
-    tcx.sess.abort_if_errors();
+    tcx.sess.abort_if_new_errors(errors);
 
     time(time_passes, "variance inference", (), |_|
          variance::infer_variance(tcx));
@@ -340,7 +343,7 @@ pub fn check_crate(tcx: &ty::ctxt, trait_map: ty::TraitMap) {
         coherence::check_coherence(&ccx));
 
     time(time_passes, "type checking", (), |_|
-        check::check_item_types(&ccx));
+        check::check_item_types(&ccx, errors));
 
     check_for_entry_fn(&ccx);
     tcx.sess.abort_if_errors();
@@ -188,7 +188,7 @@ impl<'a, 'tcx> Clean<Crate> for visit_ast::RustdocVisitor<'a, 'tcx> {
 
         let src = match cx.input {
             Input::File(ref path) => path.clone(),
-            Input::Str(_) => PathBuf::new() // FIXME: this is wrong
+            Input::Str { .. } => PathBuf::new() // FIXME: this is wrong
         };
 
         Crate {
@@ -10,15 +10,13 @@
 pub use self::MaybeTyped::*;
 
 use rustc_lint;
-use rustc_driver::driver;
+use rustc_driver::{driver, Compilation};
 use rustc::session::{self, config};
 use rustc::session::config::UnstableFeatures;
 use rustc::middle::{privacy, ty};
 use rustc::lint;
-use rustc_trans::back::link;
-use rustc_resolve as resolve;
 
-use syntax::{ast, ast_map, codemap, diagnostic};
+use syntax::{ast, codemap, diagnostic};
 
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
@@ -31,17 +29,17 @@ pub use rustc::session::config::Input;
 pub use rustc::session::search_paths::SearchPaths;
 
 /// Are we generating documentation (`Typed`) or tests (`NotTyped`)?
-pub enum MaybeTyped<'tcx> {
-    Typed(ty::ctxt<'tcx>),
+pub enum MaybeTyped<'a, 'tcx: 'a> {
+    Typed(&'a ty::ctxt<'tcx>),
     NotTyped(session::Session)
 }
 
 pub type ExternalPaths = RefCell<Option<HashMap<ast::DefId,
                                                 (Vec<String>, clean::TypeKind)>>>;
 
-pub struct DocContext<'tcx> {
-    pub krate: &'tcx ast::Crate,
-    pub maybe_typed: MaybeTyped<'tcx>,
+pub struct DocContext<'a, 'tcx: 'a> {
+    pub krate: &'a ast::Crate,
+    pub maybe_typed: MaybeTyped<'a, 'tcx>,
     pub input: Input,
     pub external_paths: ExternalPaths,
     pub external_traits: RefCell<Option<HashMap<ast::DefId, clean::Trait>>>,
@@ -50,22 +48,22 @@ pub struct DocContext<'tcx> {
     pub populated_crate_impls: RefCell<HashSet<ast::CrateNum>>,
 }
 
-impl<'tcx> DocContext<'tcx> {
-    pub fn sess<'a>(&'a self) -> &'a session::Session {
+impl<'a, 'tcx> DocContext<'a, 'tcx> {
+    pub fn sess<'b>(&'b self) -> &'b session::Session {
         match self.maybe_typed {
-            Typed(ref tcx) => &tcx.sess,
+            Typed(tcx) => &tcx.sess,
             NotTyped(ref sess) => sess
         }
     }
 
-    pub fn tcx_opt<'a>(&'a self) -> Option<&'a ty::ctxt<'tcx>> {
+    pub fn tcx_opt<'b>(&'b self) -> Option<&'b ty::ctxt<'tcx>> {
         match self.maybe_typed {
-            Typed(ref tcx) => Some(tcx),
+            Typed(tcx) => Some(tcx),
             NotTyped(_) => None
         }
     }
 
-    pub fn tcx<'a>(&'a self) -> &'a ty::ctxt<'tcx> {
+    pub fn tcx<'b>(&'b self) -> &'b ty::ctxt<'tcx> {
         let tcx_opt = self.tcx_opt();
         tcx_opt.expect("tcx not present")
     }
@@ -119,29 +117,36 @@ pub fn run_core(search_paths: SearchPaths, cfgs: Vec<String>, externs: Externs,
 
     let cfg = config::build_configuration(&sess);
 
-    let krate = driver::phase_1_parse_input(&sess, cfg, &input);
-
-    let name = link::find_crate_name(Some(&sess), &krate.attrs,
-                                     &input);
-
-    let krate = driver::phase_2_configure_and_expand(&sess, krate, &name, None)
-                    .expect("phase_2_configure_and_expand aborted in rustdoc!");
-
-    let mut forest = ast_map::Forest::new(krate);
-    let arenas = ty::CtxtArenas::new();
-    let ast_map = driver::assign_node_ids_and_map(&sess, &mut forest);
+    // Run the regular compiler pipeline up to the end of analysis and clean
+    // the crate from the callback, while the type context is still alive.
+    let result = RefCell::new(None);
+    {
+        let mut control = driver::CompileController::basic();
+        control.after_analysis.callback = box |state| {
+            let tcx = state.tcx.unwrap();
+            let analysis = state.analysis.unwrap();
+            *result.borrow_mut() = Some(clean_crate(state.input, tcx, analysis));
+            state.stop = Compilation::Stop;
+        };
+        driver::compile_input(sess, cfg, &input, &None, &None, None, control);
+    }
+    result.into_inner().expect("rustdoc: compilation aborted before analysis")
+}
 
-    let ty::CrateAnalysis {
-        exported_items, public_items, ty_cx, ..
-    } = driver::phase_3_run_analysis_passes(sess,
-                                            ast_map,
-                                            &arenas,
-                                            name,
-                                            resolve::MakeGlobMap::No);
+fn clean_crate<'tcx>(input: &Input,
+                     tcx: &ty::ctxt<'tcx>,
+                     ty_analysis: &ty::CrateAnalysis<'tcx>)
+                     -> (clean::Crate, CrateAnalysis) {
+    let input = match *input {
+        Input::File(ref p) => Input::File(p.clone()),
+        Input::Str { ref name, ref input } => {
+            Input::Str { name: name.clone(), input: input.clone() }
+        }
+    };
 
     let ctxt = DocContext {
-        krate: ty_cx.map.krate(),
-        maybe_typed: Typed(ty_cx),
+        krate: tcx.map.krate(),
+        maybe_typed: Typed(tcx),
         input: input,
         external_traits: RefCell::new(Some(HashMap::new())),
         external_typarams: RefCell::new(Some(HashMap::new())),
@@ -152,8 +157,8 @@ pub fn run_core(search_paths: SearchPaths, cfgs: Vec<String>, externs: Externs,
     debug!("crate: {:?}", ctxt.krate);
 
     let analysis = CrateAnalysis {
-        exported_items: exported_items,
-        public_items: public_items,
+        exported_items: ty_analysis.exported_items.clone(),
+        public_items: ty_analysis.public_items.clone(),
         external_paths: RefCell::new(None),
         external_traits: RefCell::new(None),
         external_typarams: RefCell::new(None),
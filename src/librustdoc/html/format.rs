@@ -599,6 +599,7 @@ impl fmt::Display for VisSpace {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self.get() {
             Some(ast::Public) => write!(f, "pub "),
+            Some(ast::PubCrate) => write!(f, "pub(crate) "),
             Some(ast::Inherited) | None => Ok(())
         }
     }
@@ -46,6 +46,20 @@ impl<T: Send + Sync + 'static> Lazy<T> {
         }
     }
 
+    /// Returns the value if it has already been initialized and not yet torn
+    /// down, without initializing it.
+    pub fn peek(&'static self) -> Option<Arc<T>> {
+        let _g = self.lock.lock();
+        unsafe {
+            let ptr = *self.ptr.get();
+            if ptr.is_null() || ptr as usize == 1 {
+                None
+            } else {
+                Some((*ptr).clone())
+            }
+        }
+    }
+
     fn init(&'static self) -> Box<Arc<T>> {
         rt::at_exit(move || unsafe {
             let g = self.lock.lock();
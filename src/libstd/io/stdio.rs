@@ -231,13 +231,28 @@ pub struct StdoutLock<'a> {
 /// The returned handle implements the `Write` trait.
 #[stable(feature = "rust1", since = "1.0.0")]
 pub fn stdout() -> Stdout {
-    static INSTANCE: Lazy<Mutex<LineWriter<StdoutRaw>>> = lazy_init!(stdout_init);
-    return Stdout {
-        inner: INSTANCE.get().expect("cannot access stdout during shutdown"),
-    };
+    Stdout {
+        inner: STDOUT.get().expect("cannot access stdout during shutdown"),
+    }
+}
+
+static STDOUT: Lazy<Mutex<LineWriter<StdoutRaw>>> = lazy_init!(stdout_init);
 
-    fn stdout_init() -> Arc<Mutex<LineWriter<StdoutRaw>>> {
-        Arc::new(Mutex::new(LineWriter::new(stdout_raw())))
+fn stdout_init() -> Arc<Mutex<LineWriter<StdoutRaw>>> {
+    Arc::new(Mutex::new(LineWriter::new(stdout_raw())))
+}
+
+/// Flushes the buffered standard output, if it was ever used.
+///
+/// Called by the runtime before the process exits, either when `main` returns
+/// or from `process::exit`. Standard error isn't buffered, so there is
+/// nothing to flush there. Errors are ignored as there is no one left to
+/// report them to.
+#[doc(hidden)]
+pub fn flush_stdio() {
+    if let Some(out) = STDOUT.peek() {
+        let mut out = out.lock().unwrap_or_else(|e| e.into_inner());
+        let _ = out.flush();
     }
 }
 
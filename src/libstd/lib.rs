@@ -266,6 +266,7 @@ pub mod net;
 pub mod old_io;
 pub mod old_path;
 pub mod os;
+pub mod panic;
 pub mod path;
 pub mod process;
 pub mod rand;
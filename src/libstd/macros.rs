@@ -90,14 +90,15 @@ macro_rules! println {
 }
 
 /// Helper macro for unwrapping `Result` values while returning early with an
-/// error if the value of the expression is `Err`.
+/// error if the value of the expression is `Err`. The error is converted to
+/// the error type of the enclosing function with `From::from`.
 #[macro_export]
 #[stable(feature = "rust1", since = "1.0.0")]
 macro_rules! try {
     ($expr:expr) => (match $expr {
         $crate::result::Result::Ok(val) => val,
         $crate::result::Result::Err(err) => {
-            return $crate::result::Result::Err($crate::error::FromError::from_error(err))
+            return $crate::result::Result::Err($crate::convert::From::from(err))
         }
     })
 }
@@ -231,6 +232,29 @@ pub mod builtin {
     #[macro_export]
     macro_rules! option_env { ($name:expr) => ({ /* compiler built-in */ }) }
 
+    /// Fail compilation with the given error message.
+    ///
+    /// The message must be a string literal, or a macro such as `concat!`
+    /// expanding to one. When `compile_error!` is invoked by another macro,
+    /// the error points at the outermost invocation, in the code that used
+    /// that macro. An invocation inside an item that `#[cfg]` removes is
+    /// never expanded and so reports nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// macro_rules! pointer_width {
+    ///     (32) => (4);
+    ///     (64) => (8);
+    ///     ($other:tt) => (compile_error!(concat!("unsupported pointer width: ",
+    ///                                            stringify!($other))));
+    /// }
+    ///
+    /// let bytes = pointer_width!(16);
+    /// ```
+    #[macro_export]
+    macro_rules! compile_error { ($msg:expr) => ({ /* compiler built-in */ }) }
+
     /// Concatenate identifiers into one identifier.
     ///
     /// This macro takes any number of comma-separated identifiers, and
@@ -156,6 +156,7 @@ impl BytesContainer for EnvKey {
 }
 
 /// A HashMap representation of environment variables.
+#[cfg_attr(not(stage0), allow(private_in_public))]
 pub type EnvMap = HashMap<EnvKey, CString>;
 
 /// The `Command` type acts as a process builder, providing fine-grained control
@@ -0,0 +1,137 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Panic support in the standard library
+//!
+//! A panic unwinds the stack of the thread it happens on until it reaches the
+//! start of the thread. Unwinding out of a Rust function called through an
+//! `extern "C"` function pointer is undefined behavior, however, so code
+//! which is called back from C (and other code which must keep running when
+//! some piece of work panics, like a test harness) needs a way to stop the
+//! unwinding at a boundary. `recover` is that boundary.
+//!
+//! Code which observes the state a panic left behind must be prepared for
+//! broken invariants, which is what the `RecoverSafe` bound on `recover`
+//! guards against: closures which capture mutable references (or shared
+//! references to types with interior mutability) can't be passed to it unless
+//! they are wrapped in `AssertRecoverSafe`, asserting that this is fine.
+
+#![unstable(feature = "recover",
+            reason = "awaiting feedback on the marker traits and naming")]
+
+use prelude::v1::*;
+
+use cell::UnsafeCell;
+use ops::{Deref, DerefMut};
+use rt::unwind;
+use sync::{Mutex, RwLock};
+use thread::Result;
+
+/// A marker trait for types which can be used from within `recover` and
+/// still be observed once the panic was caught.
+///
+/// All types are `RecoverSafe` except for mutable references, whose referent
+/// may be left in a broken state by the panic, and shared references to types
+/// which aren't `RefRecoverSafe`. `AssertRecoverSafe` opts a value in anyway.
+pub trait RecoverSafe {}
+
+/// A marker trait for types whose shared references are `RecoverSafe`.
+///
+/// Types with interior mutability (i.e. containing an `UnsafeCell`) are not
+/// `RefRecoverSafe`, except for `Mutex` and `RwLock`, which are poisoned when
+/// a panic happens while they are held.
+pub trait RefRecoverSafe {}
+
+/// A wrapper asserting that the value it contains is `RecoverSafe`.
+///
+/// The wrapper dereferences to the value, and can be called if the value is
+/// a closure.
+///
+/// # Examples
+///
+/// ```
+/// # #![feature(recover)]
+/// use std::panic::{self, AssertRecoverSafe};
+///
+/// let mut count = 0;
+/// {
+///     let mut count = AssertRecoverSafe(&mut count);
+///     let result = panic::recover(move || {
+///         **count += 1;
+///         panic!("oh no");
+///     });
+///     assert!(result.is_err());
+/// }
+/// assert_eq!(count, 1);
+/// ```
+pub struct AssertRecoverSafe<T>(pub T);
+
+impl RecoverSafe for .. {}
+impl<'a, T: ?Sized> !RecoverSafe for &'a mut T {}
+impl<'a, T: RefRecoverSafe + ?Sized> RecoverSafe for &'a T {}
+impl<T> RecoverSafe for AssertRecoverSafe<T> {}
+
+impl RefRecoverSafe for .. {}
+impl<T: ?Sized> !RefRecoverSafe for UnsafeCell<T> {}
+impl<T> RefRecoverSafe for Mutex<T> {}
+impl<T> RefRecoverSafe for RwLock<T> {}
+impl<T> RefRecoverSafe for AssertRecoverSafe<T> {}
+
+impl<T> Deref for AssertRecoverSafe<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for AssertRecoverSafe<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<R, F: FnOnce() -> R> FnOnce<()> for AssertRecoverSafe<F> {
+    type Output = R;
+
+    extern "rust-call" fn call_once(self, _args: ()) -> R {
+        (self.0)()
+    }
+}
+
+/// Invokes a closure, capturing the cause of a panic if one occurs.
+///
+/// Returns `Ok` with the closure's result if it returns normally, and `Err`
+/// with the value the closure panicked with otherwise. The panic message has
+/// already been printed by then.
+///
+/// Panics which abort the process (such as one happening while the thread is
+/// already unwinding) are not caught.
+///
+/// # Examples
+///
+/// ```
+/// # #![feature(recover)]
+/// use std::panic;
+///
+/// let result = panic::recover(|| 1 + 1);
+/// assert_eq!(result.ok(), Some(2));
+///
+/// let result = panic::recover(|| panic!("oh no"));
+/// assert!(result.is_err());
+/// ```
+pub fn recover<F, R>(f: F) -> Result<R> where F: FnOnce() -> R + RecoverSafe {
+    let mut result = None;
+    unsafe {
+        let result = &mut result;
+        try!(unwind::try(move || *result = Some(f())))
+    }
+    Ok(result.unwrap())
+}
@@ -23,6 +23,7 @@ use libc;
 use path;
 use sync::mpsc::{channel, Receiver};
 use sys::pipe2::{self, AnonPipe};
+use sys;
 use sys::process2::Process as ProcessImp;
 use sys::process2::Command as CommandImp;
 use sys::process2::ExitStatus as ExitStatusImp;
@@ -527,6 +528,33 @@ impl Child {
     }
 }
 
+/// Terminates the current process with the specified exit code.
+///
+/// This function will never return and will immediately terminate the current
+/// process. The exit code is passed through to the underlying OS and will be
+/// available for consumption by another process.
+///
+/// Buffered standard output is flushed first, but no destructors on the
+/// current stack or any other thread's stack will be run, and no `at_exit`
+/// handlers are called. If a clean shutdown is needed it is recommended to
+/// only call this function at a known point where there are no more
+/// destructors left to run.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(exit_status)]
+/// use std::process;
+///
+/// println!("giving up");
+/// process::exit(3);
+/// ```
+#[unstable(feature = "exit_status", reason = "recently added")]
+pub fn exit(code: i32) -> ! {
+    io::flush_stdio();
+    sys::os::exit(code)
+}
+
 #[cfg(test)]
 mod tests {
     use io::ErrorKind;
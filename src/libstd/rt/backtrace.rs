@@ -18,6 +18,7 @@ use env;
 use sync::atomic::{self, Ordering};
 
 pub use sys::backtrace::write;
+pub use sys_common::backtrace::demangle;
 
 // For now logging is turned off by default, and this function checks to see
 // whether the magical environment variable is present to see if it's turned on.
@@ -75,4 +76,64 @@ mod test {
         t!("ZN13test$u20$test4foobE", "test test::foob");
         t!("ZN12test$RF$test4foobE", "test&test::foob");
     }
+
+    #[test]
+    fn demangle_osx() {
+        t!("__ZN4test1a2bcE", "test::a::bc");
+        t!("__ZN3std2rt10lang_start20h4e4b1bd469a3828eJvwE",
+           "std::rt::lang_start::h4e4b1bd469a3828eJvw");
+    }
+
+    #[test]
+    fn demangle_real_symbols() {
+        t!("_ZN4main20h2bd1ea1dbb0c3bc3eaaE", "main::h2bd1ea1dbb0c3bc3eaa");
+        t!("_ZN3vec17Vec$LT$T$GT$.Drop4drop20h8536317832163889rWgE",
+           "vec::Vec<T>.Drop::drop::h8536317832163889rWg");
+        t!("_ZN39_$LT$collections..vec..Vec$LT$T$GT$$GT$4push20h3a3b9c5aed6e071dryaE",
+           "<collections::vec::Vec<T>>::push::h3a3b9c5aed6e071drya");
+        t!("_ZN51_$LT$$RF$$u27$a$u20$str$u20$as$u20$fmt..Display$GT$3fmt\
+            20hdd9d7e11cc9a6e5eZXaE",
+           "<&'a str as fmt::Display>::fmt::hdd9d7e11cc9a6e5eZXa");
+    }
+
+    #[test]
+    fn demangle_nested_generics() {
+        t!("_ZN50HashMap$LT$K$C$$u20$Vec$LT$Option$LT$V$GT$$GT$$GT$3getE",
+           "HashMap<K, Vec<Option<V>>>::get");
+        t!("_ZN27_$LP$$BP$const$u20$T$C$$RP$E", "(*const T,)");
+        t!("_ZN20_$u5b$$RF$$SP$T$u5d$E", "[&@T]");
+    }
+
+    #[test]
+    fn demangle_unicode_escapes() {
+        t!("_ZN8caf$ue9$E", "caf\u{e9}");
+        t!("_ZN13_$u1f600$face4ruleE", "\u{1f600}face::rule");
+        t!("_ZN14_$u7e$box$u7e$E", "~box~");
+        t!("_ZN10caf$u{e9}$E", "caf$u{e9}$");
+    }
+
+    #[test]
+    fn demangle_llvm_suffixes() {
+        t!("_ZN3foo3barE.constprop.0", "foo::bar.constprop.0");
+        t!("_ZN3foo3barE.isra.1.llvm.123", "foo::bar.isra.1.llvm.123");
+        t!("_ZN3foo3barEx", "_ZN3foo3barEx");
+    }
+
+    #[test]
+    fn demangle_pathological() {
+        // Escapes that don't decode are left as they are...
+        t!("_ZN8$LT$$XX$E", "<$XX$");
+        t!("_ZN3$u$E", "$u$");
+        t!("_ZN9$u110000$E", "$u110000$");
+        t!("_ZN7$ud800$E", "$ud800$");
+        t!("_ZN11$u12345678$E", "$u12345678$");
+        t!("_ZN3$LTE", "$LT");
+        // ... and so are symbols whose lengths don't add up.
+        t!("_ZN99fooE", "_ZN99fooE");
+        t!("_ZN0E", "_ZN0E");
+        t!("_ZNE", "_ZNE");
+        t!("_ZN3fooE3barE", "_ZN3fooE3barE");
+        t!("_ZN184467440737095516163fooE", "_ZN184467440737095516163fooE");
+        t!("_ZN2\u{e9}E", "_ZN2\u{e9}E");
+    }
 }
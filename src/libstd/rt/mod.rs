@@ -67,6 +67,7 @@ fn lang_start(main: *const u8, argc: int, argv: *const *const u8) -> int {
 
     use mem;
     use env;
+    use io;
     use rt;
     use sys_common::thread_info::{self, NewThread};
     use sys_common;
@@ -87,11 +88,16 @@ fn lang_start(main: *const u8, argc: int, argv: *const *const u8) -> int {
     } else {
         my_stack_top + TWENTY_KB
     };
+    // The OS decides how big the main thread's stack is, but if the user
+    // told us about a bigger stack through RUST_MIN_STACK, believe them.
+    let stack_estimate = rt::util::min_stack_from_env()
+                             .unwrap_or(OS_DEFAULT_STACK_ESTIMATE);
+
     // saturating-sub to sidestep underflow
-    let my_stack_bottom = if top_plus_spill < OS_DEFAULT_STACK_ESTIMATE {
+    let my_stack_bottom = if top_plus_spill < stack_estimate {
         0
     } else {
-        top_plus_spill - OS_DEFAULT_STACK_ESTIMATE
+        top_plus_spill - stack_estimate
     };
 
     let failed = unsafe {
@@ -108,7 +114,7 @@ fn lang_start(main: *const u8, argc: int, argv: *const *const u8) -> int {
         // but we just do this to name the main thread and to give it correct
         // info about the stack bounds.
         let thread: Thread = NewThread::new(Some("<main>".to_string()));
-        thread_info::set(sys::thread::guard::main(), thread);
+        thread_info::set(sys::thread::guard::main(), stack_estimate, thread);
 
         // By default, some platforms will send a *signal* when a EPIPE error
         // would otherwise be delivered. This runtime doesn't install a SIGPIPE
@@ -135,6 +141,9 @@ fn lang_start(main: *const u8, argc: int, argv: *const *const u8) -> int {
             let main: fn() = mem::transmute(main);
             main();
         });
+        // Flush before the `at_exit` handlers tear stdio down, as a handle
+        // leaked by user code would otherwise keep the buffer alive.
+        io::flush_stdio();
         cleanup();
         res.is_err()
     };
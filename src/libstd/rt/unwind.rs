@@ -70,9 +70,13 @@ use libc::c_void;
 use mem;
 use sync::atomic::{self, Ordering};
 use sys_common::mutex::{Mutex, MUTEX_INIT};
+use sys_common::thread_info;
 
 use rt::libunwind as uw;
 
+// The unwinder hands us back a pointer to `uwe`, which must therefore stay
+// at the start of the struct.
+#[repr(C)]
 struct Exception {
     uwe: uw::_Unwind_Exception,
     cause: Option<Box<Any + Send + 'static>>,
@@ -120,6 +124,10 @@ thread_local! { static PANICKING: Cell<bool> = Cell::new(false) }
 pub unsafe fn try<F: FnOnce()>(f: F) -> Result<(), Box<Any + Send>> {
     let mut f = Some(f);
 
+    // This may be the first time a thread created outside of Rust calls into
+    // it, in which case it needs its stack overflow handler set up.
+    thread_info::ensure_registered();
+
     let prev = PANICKING.with(|s| s.get());
     PANICKING.with(|s| s.set(false));
     let ep = rust_try(try_fn::<F>, &mut f as *mut _ as *mut c_void);
@@ -162,6 +170,7 @@ pub fn panicking() -> bool {
 #[inline(never)]
 #[no_mangle]
 #[allow(private_no_mangle_fns)]
+#[cfg_attr(not(stage0), no_instrument_function)]
 fn rust_panic(cause: Box<Any + Send + 'static>) -> ! {
     rtdebug!("begin_unwind()");
 
@@ -239,6 +248,7 @@ pub mod eabi {
     }
 
     #[lang="eh_personality"]
+    #[cfg_attr(not(stage0), no_instrument_function)]
     #[no_mangle] // referenced from rust_try.ll
     #[allow(private_no_mangle_fns)]
     extern fn rust_eh_personality(
@@ -293,6 +303,7 @@ pub mod eabi {
     }
 
     #[lang="eh_personality"]
+    #[cfg_attr(not(stage0), no_instrument_function)]
     #[no_mangle] // referenced from rust_try.ll
     pub extern "C" fn rust_eh_personality(
         version: c_int,
@@ -346,6 +357,7 @@ pub mod eabi {
     }
 
     #[lang="eh_personality"]
+    #[cfg_attr(not(stage0), no_instrument_function)]
     #[no_mangle] // referenced from rust_try.ll
     #[allow(private_no_mangle_fns)]
     extern "C" fn rust_eh_personality(
@@ -433,6 +445,7 @@ pub mod eabi {
     }
 
     #[lang="eh_personality"]
+    #[cfg_attr(not(stage0), no_instrument_function)]
     #[no_mangle] // referenced from rust_try.ll
     #[allow(private_no_mangle_fns)]
     extern "C" fn rust_eh_personality(
@@ -483,11 +496,25 @@ pub mod eabi {
 #[cfg(not(test))]
 /// Entry point of panic from the libcore crate.
 #[lang = "panic_fmt"]
+#[cfg_attr(not(stage0), no_instrument_function)]
 pub extern fn rust_begin_unwind(msg: fmt::Arguments,
                                 file: &'static str, line: uint) -> ! {
     begin_unwind_fmt(msg, &(file, line))
 }
 
+#[cfg(not(test))]
+/// Entry point for a panic which reached a function with a foreign ABI.
+///
+/// The compiler calls this from the landing pad it wraps around every Rust
+/// function callable from foreign code, as unwinding into foreign frames is
+/// undefined behavior.
+#[lang = "foreign_unwind"]
+#[inline(never)] #[cold]
+pub fn foreign_unwind() -> ! {
+    rterrln!("Rust cannot unwind across an `extern \"C\"` boundary; aborting");
+    unsafe { intrinsics::abort() }
+}
+
 /// The entry point for unwinding with a formatted message.
 ///
 /// This is designed to reduce the amount of code required at the call
@@ -496,6 +523,7 @@ pub extern fn rust_begin_unwind(msg: fmt::Arguments,
 /// the actual formatting into this shared place.
 #[inline(never)] #[cold]
 #[stable(since = "1.0.0", feature = "rust1")]
+#[cfg_attr(not(stage0), no_instrument_function)]
 pub fn begin_unwind_fmt(msg: fmt::Arguments, file_line: &(&'static str, uint)) -> ! {
     use fmt::Write;
 
@@ -512,6 +540,7 @@ pub fn begin_unwind_fmt(msg: fmt::Arguments, file_line: &(&'static str, uint)) -
 /// This is the entry point of unwinding for panic!() and assert!().
 #[inline(never)] #[cold] // avoid code bloat at the call sites as much as possible
 #[stable(since = "1.0.0", feature = "rust1")]
+#[cfg_attr(not(stage0), no_instrument_function)]
 pub fn begin_unwind<M: Any + Send>(msg: M, file_line: &(&'static str, uint)) -> ! {
     // Note that this should be the only allocation performed in this code path.
     // Currently this means that panic!() on OOM will invoke this code path,
@@ -534,6 +563,7 @@ pub fn begin_unwind<M: Any + Send>(msg: M, file_line: &(&'static str, uint)) ->
 /// Doing this split took the LLVM IR line counts of `fn main() { panic!()
 /// }` from ~1900/3700 (-O/no opts) to 180/590.
 #[inline(never)] #[cold] // this is the slow path, please never inline this
+#[cfg_attr(not(stage0), no_instrument_function)]
 fn begin_unwind_inner(msg: Box<Any + Send>,
                       file_line: &(&'static str, uint)) -> ! {
     // Make sure the default failure handler is registered before we look at the
@@ -43,14 +43,18 @@ pub fn limit_thread_creation_due_to_osx_and_valgrind() -> bool {
     (cfg!(target_os="macos")) && running_on_valgrind()
 }
 
+/// The stack size requested through the `RUST_MIN_STACK` environment variable.
+pub fn min_stack_from_env() -> Option<uint> {
+    env::var("RUST_MIN_STACK").ok().and_then(|s| s.parse().ok())
+}
+
 pub fn min_stack() -> uint {
     static MIN: atomic::AtomicUsize = atomic::ATOMIC_USIZE_INIT;
     match MIN.load(Ordering::SeqCst) {
         0 => {}
         n => return n - 1,
     }
-    let amt = env::var("RUST_MIN_STACK").ok().and_then(|s| s.parse().ok());
-    let amt = amt.unwrap_or(2 * 1024 * 1024);
+    let amt = min_stack_from_env().unwrap_or(2 * 1024 * 1024);
     // 0 is our sentinel value, so ensure that we'll never see 0 after
     // initialization has run
     MIN.store(amt + 1, Ordering::SeqCst);
@@ -73,8 +77,24 @@ pub fn abort(args: fmt::Arguments) -> ! {
     unsafe { intrinsics::abort(); }
 }
 
-pub unsafe fn report_overflow() {
+/// Reports that the current thread overflowed its stack. `fault` is the
+/// address whose access hit the guard page, if the platform tells us.
+pub unsafe fn report_overflow(fault: Option<uint>) {
+    use sys_common::thread_info;
     use thread;
     rterrln!("\nthread '{}' has overflowed its stack",
              thread::current().name().unwrap_or("<unknown>"));
+
+    let guard = thread_info::stack_guard();
+    let size = thread_info::stack_size();
+    match fault {
+        Some(addr) if guard != 0 && addr < guard => {
+            rterrln!("accessed {:#x}, {} bytes below the end of the stack at {:#x}",
+                     addr, guard - addr, guard);
+        }
+        _ => {}
+    }
+    if size != 0 {
+        rterrln!("the thread's stack size is {} bytes", size);
+    }
 }
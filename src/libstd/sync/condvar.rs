@@ -18,6 +18,20 @@ use sys_common::mutex as sys_mutex;
 use time::Duration;
 use sync::{mutex, MutexGuard, PoisonError};
 
+/// A type indicating whether a timed wait on a condition variable returned
+/// due to a time out or not.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[unstable(feature = "std_misc")]
+pub struct WaitTimeoutResult(bool);
+
+impl WaitTimeoutResult {
+    /// Returns whether the wait was known to have timed out.
+    #[unstable(feature = "std_misc")]
+    pub fn timed_out(&self) -> bool {
+        self.0
+    }
+}
+
 /// A Condition Variable
 ///
 /// Condition variables represent the ability to block a thread such that it
@@ -94,12 +108,15 @@ impl Condvar {
     /// notified.
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn new() -> Condvar {
-        Condvar {
+        let mut c = Condvar {
             inner: box StaticCondvar {
                 inner: unsafe { sys::Condvar::new() },
                 mutex: AtomicUsize::new(0),
             }
-        }
+        };
+        // The condvar is boxed, so it is already at its final address.
+        unsafe { c.inner.inner.init(); }
+        c
     }
 
     /// Block the current thread until this condition variable receives a
@@ -146,14 +163,16 @@ impl Condvar {
     /// preemption or platform differences that may not cause the maximum amount
     /// of time waited to be precisely `dur`.
     ///
-    /// If the wait timed out, then `false` will be returned. Otherwise if a
-    /// notification was received then `true` will be returned.
+    /// The returned `WaitTimeoutResult` value indicates if the timeout is
+    /// known to have elapsed. Where the platform allows it the timeout is
+    /// measured with a monotonic clock, so it is not affected by changes to
+    /// the system time.
     ///
     /// Like `wait`, the lock specified will be re-acquired when this function
     /// returns, regardless of whether the timeout elapsed or not.
     #[unstable(feature = "std_misc")]
     pub fn wait_timeout<'a, T>(&self, guard: MutexGuard<'a, T>, dur: Duration)
-                           -> LockResult<(MutexGuard<'a, T>, bool)> {
+                               -> LockResult<(MutexGuard<'a, T>, WaitTimeoutResult)> {
         unsafe {
             let me: &'static Condvar = &*(self as *const _);
             me.inner.wait_timeout(guard, dur)
@@ -179,6 +198,50 @@ impl Condvar {
         }
     }
 
+    /// Wait on this condition variable for a notification, timing out after a
+    /// specified duration, for as long as `condition` returns `true`.
+    ///
+    /// `condition` is checked before blocking and again after every wakeup,
+    /// so spurious wakeups are handled here; each new wait is only given the
+    /// part of `dur` that is left. The returned `WaitTimeoutResult` indicates
+    /// whether the time ran out while `condition` still held.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![feature(std_misc)]
+    /// use std::sync::{Arc, Mutex, Condvar};
+    /// use std::thread;
+    /// use std::time::Duration;
+    ///
+    /// let pair = Arc::new((Mutex::new(false), Condvar::new()));
+    /// let pair2 = pair.clone();
+    ///
+    /// thread::spawn(move|| {
+    ///     let &(ref lock, ref cvar) = &*pair2;
+    ///     *lock.lock().unwrap() = true;
+    ///     cvar.notify_one();
+    /// });
+    ///
+    /// let &(ref lock, ref cvar) = &*pair;
+    /// let guard = lock.lock().unwrap();
+    /// let (started, result) = cvar.wait_timeout_while(guard, Duration::seconds(60),
+    ///                                                 |started| !*started).unwrap();
+    /// assert!(*started || result.timed_out());
+    /// ```
+    #[unstable(feature = "std_misc")]
+    pub fn wait_timeout_while<'a, T, F>(&self,
+                                        guard: MutexGuard<'a, T>,
+                                        dur: Duration,
+                                        condition: F)
+                                        -> LockResult<(MutexGuard<'a, T>, WaitTimeoutResult)>
+            where F: FnMut(&mut T) -> bool {
+        unsafe {
+            let me: &'static Condvar = &*(self as *const _);
+            me.inner.wait_timeout_while(guard, dur, condition)
+        }
+    }
+
     /// Wake up one blocked thread on this condvar.
     ///
     /// If there is a blocked thread on this condition variable, then it will
@@ -236,17 +299,17 @@ impl StaticCondvar {
     #[unstable(feature = "std_misc",
                reason = "may be merged with Condvar in the future")]
     pub fn wait_timeout<'a, T>(&'static self, guard: MutexGuard<'a, T>, dur: Duration)
-                               -> LockResult<(MutexGuard<'a, T>, bool)> {
-        let (poisoned, success) = unsafe {
+                               -> LockResult<(MutexGuard<'a, T>, WaitTimeoutResult)> {
+        let (poisoned, result) = unsafe {
             let lock = mutex::guard_lock(&guard);
             self.verify(lock);
             let success = self.inner.wait_timeout(lock, dur);
-            (mutex::guard_poison(&guard).get(), success)
+            (mutex::guard_poison(&guard).get(), WaitTimeoutResult(!success))
         };
         if poisoned {
-            Err(PoisonError::new((guard, success)))
+            Err(PoisonError::new((guard, result)))
         } else {
-            Ok((guard, success))
+            Ok((guard, result))
         }
     }
 
@@ -275,15 +338,15 @@ impl StaticCondvar {
             let now = SteadyTime::now();
             let consumed = &now - &start;
             let guard = guard_result.unwrap_or_else(|e| e.into_inner());
-            let (new_guard_result, no_timeout) = match self.wait_timeout(guard, dur - consumed) {
-                Ok((new_guard, no_timeout)) => (Ok(new_guard), no_timeout),
+            let (new_guard_result, result) = match self.wait_timeout(guard, dur - consumed) {
+                Ok((new_guard, result)) => (Ok(new_guard), result),
                 Err(err) => {
-                    let (new_guard, no_timeout) = err.into_inner();
-                    (Err(PoisonError::new(new_guard)), no_timeout)
+                    let (new_guard, result) = err.into_inner();
+                    (Err(PoisonError::new(new_guard)), result)
                 }
             };
             guard_result = new_guard_result;
-            if !no_timeout {
+            if result.timed_out() {
                 let result = f(guard_result
                                     .as_mut()
                                     .map(|g| &mut **g)
@@ -295,6 +358,38 @@ impl StaticCondvar {
         poison::map_result(guard_result, |g| (g, true))
     }
 
+    /// Wait on this condition variable for a notification, timing out after a
+    /// specified duration, for as long as `condition` returns `true`.
+    ///
+    /// See `Condvar::wait_timeout_while`.
+    #[unstable(feature = "std_misc",
+               reason = "may be merged with Condvar in the future")]
+    pub fn wait_timeout_while<'a, T, F>(&'static self,
+                                        guard: MutexGuard<'a, T>,
+                                        dur: Duration,
+                                        mut condition: F)
+                                        -> LockResult<(MutexGuard<'a, T>, WaitTimeoutResult)>
+            where F: FnMut(&mut T) -> bool {
+        let start = SteadyTime::now();
+        let mut guard_result: LockResult<MutexGuard<'a, T>> = Ok(guard);
+        loop {
+            let keep_waiting = match guard_result {
+                Ok(ref mut g) => condition(&mut **g),
+                Err(ref mut e) => condition(&mut **e.get_mut()),
+            };
+            if !keep_waiting {
+                return poison::map_result(guard_result, |g| (g, WaitTimeoutResult(false)));
+            }
+            let consumed = &SteadyTime::now() - &start;
+            if consumed >= dur {
+                return poison::map_result(guard_result, |g| (g, WaitTimeoutResult(true)));
+            }
+            let guard = guard_result.unwrap_or_else(|e| e.into_inner());
+            guard_result = poison::map_result(self.wait_timeout(guard, dur - consumed),
+                                              |(g, _)| g);
+        }
+    }
+
     /// Wake up one blocked thread on this condvar.
     ///
     /// See `Condvar::notify_one`.
@@ -348,6 +443,7 @@ mod tests {
     use sync::mpsc::channel;
     use sync::{StaticMutex, MUTEX_INIT, Condvar, Mutex, Arc};
     use sync::atomic::{AtomicUsize, ATOMIC_USIZE_INIT, Ordering};
+    use sys::time::SteadyTime;
     use thread;
     use time::Duration;
 
@@ -423,19 +519,59 @@ mod tests {
         static M: StaticMutex = MUTEX_INIT;
 
         let g = M.lock().unwrap();
-        let (g, _no_timeout) = C.wait_timeout(g, Duration::nanoseconds(1000)).unwrap();
+        let (g, _res) = C.wait_timeout(g, Duration::nanoseconds(1000)).unwrap();
         // spurious wakeups mean this isn't necessarily true
-        // assert!(!no_timeout);
+        // assert!(res.timed_out());
         let _t = thread::spawn(move || {
             let _g = M.lock().unwrap();
             C.notify_one();
         });
-        let (g, no_timeout) = C.wait_timeout(g, Duration::days(1)).unwrap();
-        assert!(no_timeout);
+        let (g, res) = C.wait_timeout(g, Duration::days(1)).unwrap();
+        assert!(!res.timed_out());
         drop(g);
         unsafe { C.destroy(); M.destroy(); }
     }
 
+    #[test]
+    fn wait_timeout_while_never_notified() {
+        let m = Mutex::new(());
+        let c = Condvar::new();
+        let dur = Duration::milliseconds(20);
+
+        let start = SteadyTime::now();
+        let (_g, res) = c.wait_timeout_while(m.lock().unwrap(), dur, |_| true).unwrap();
+        // Spurious wakeups are waited through, so the full timeout must pass.
+        assert!(res.timed_out());
+        assert!(&SteadyTime::now() - &start >= dur);
+    }
+
+    #[test]
+    fn wait_timeout_while_notified_early() {
+        let pair = Arc::new((Mutex::new(false), Condvar::new()));
+        let pair2 = pair.clone();
+
+        let &(ref m, ref c) = &*pair;
+        let g = m.lock().unwrap();
+        let _t = thread::spawn(move|| {
+            let &(ref m, ref c) = &*pair2;
+            *m.lock().unwrap() = true;
+            c.notify_one();
+        });
+        let (g, res) = c.wait_timeout_while(g, Duration::days(1), |done| !*done).unwrap();
+        assert!(!res.timed_out());
+        assert!(*g);
+    }
+
+    #[test]
+    fn wait_timeout_while_already_false() {
+        let m = Mutex::new(0);
+        let c = Condvar::new();
+        let (g, res) = c.wait_timeout_while(m.lock().unwrap(), Duration::zero(),
+                                            |n| { *n += 1; false }).unwrap();
+        assert!(!res.timed_out());
+        assert_eq!(*g, 1);
+    }
+
     #[test]
     fn wait_timeout_with() {
         static C: StaticCondvar = CONDVAR_INIT;
@@ -11,6 +11,7 @@
 use prelude::v1::*;
 use io::prelude::*;
 
+use char;
 use io;
 
 #[cfg(target_pointer_width = "64")]
@@ -29,112 +30,155 @@ pub const HEX_WIDTH: uint = 10;
 //
 // For example, "_ZN4testE" => "test" and "_ZN3foo3bar" => "foo::bar".
 //
+// Characters that aren't allowed in symbols are escaped within each element
+// (see `sanitize` in src/librustc_trans/back/link.rs): the most common ones
+// get names like "$LT$" for '<', any other code point becomes "$u{hex}$",
+// and ':' turns into '.'. Elements that don't start like an identifier get
+// an extra leading underscore. Symbols for items usually end with a hash
+// element: 'h', 16 hex digits and up to 3 characters telling apart items
+// with the same path. It is printed like any other element, as it is what
+// tells apart instances of generic functions.
+//
 // We're the ones printing our backtraces, so we can't rely on anything else to
 // demangle our symbols. It's *much* nicer to look at demangled symbols, so
-// this function is implemented to give us nice pretty output.
+// this function is implemented to give us nice pretty output. It is also
+// available as `std::rt::backtrace::demangle`, and through `rustc -Z demangle`,
+// for tools that need to do the same.
 //
-// Note that this demangler isn't quite as fancy as it could be. We have lots
-// of other information in our symbols like hashes, version, type information,
-// etc. Additionally, this doesn't handle glue symbols at all.
+// Anything that doesn't look like a Rust symbol is written out unchanged.
+// Suffixes LLVM adds to symbols when it clones functions, like ".constprop.0",
+// are kept.
 pub fn demangle(writer: &mut Write, s: &str) -> io::Result<()> {
     // First validate the symbol. If it doesn't look like anything we're
     // expecting, we just print it literally. Note that we must handle non-rust
     // symbols because we could have any function in the backtrace.
-    let mut valid = true;
-    let mut inner = s;
-    if s.len() > 4 && s.starts_with("_ZN") && s.ends_with("E") {
-        inner = &s[3 .. s.len() - 1];
-    // On Windows, dbghelp strips leading underscores, so we accept "ZN...E" form too.
-    } else if s.len() > 3 && s.starts_with("ZN") && s.ends_with("E") {
-        inner = &s[2 .. s.len() - 1];
-    } else {
-        valid = false;
-    }
+    let (elements, suffix) = match parse_symbol(s) {
+        Some(parsed) => parsed,
+        None => return writer.write_all(s.as_bytes()),
+    };
 
-    if valid {
-        let mut chars = inner.chars();
-        while valid {
-            let mut i = 0;
-            for c in chars.by_ref() {
-                if c.is_numeric() {
-                    i = i * 10 + c as uint - '0' as uint;
-                } else {
-                    break
-                }
-            }
-            if i == 0 {
-                valid = chars.next().is_none();
-                break
-            } else if chars.by_ref().take(i - 1).count() != i - 1 {
-                valid = false;
-            }
+    for (i, element) in elements.iter().enumerate() {
+        if i != 0 {
+            try!(writer.write_all(b"::"));
         }
+        try!(demangle_element(writer, element));
     }
+    writer.write_all(suffix.as_bytes())
+}
 
-    // Alright, let's do this.
-    if !valid {
-        try!(writer.write_all(s.as_bytes()));
+/// Splits a mangled symbol into its path elements and whatever LLVM
+/// appended after the closing "E".
+fn parse_symbol(s: &str) -> Option<(Vec<&str>, &str)> {
+    // On Windows, dbghelp strips leading underscores, so we accept "ZN...E"
+    // form too. OS X adds one more underscore instead.
+    let inner = if s.starts_with("__ZN") {
+        &s[4..]
+    } else if s.starts_with("_ZN") {
+        &s[3..]
+    } else if s.starts_with("ZN") {
+        &s[2..]
     } else {
-        let mut first = true;
-        while inner.len() > 0 {
-            if !first {
-                try!(writer.write_all(b"::"));
-            } else {
-                first = false;
-            }
-            let mut rest = inner;
-            while rest.char_at(0).is_numeric() {
-                rest = &rest[1..];
-            }
-            let i: uint = inner[.. (inner.len() - rest.len())].parse().unwrap();
-            inner = &rest[i..];
-            rest = &rest[..i];
-            while rest.len() > 0 {
-                if rest.starts_with("$") {
-                    macro_rules! demangle {
-                        ($($pat:expr, => $demangled:expr),*) => ({
-                            $(if rest.starts_with($pat) {
-                                try!(writer.write_all($demangled));
-                                rest = &rest[$pat.len()..];
-                              } else)*
-                            {
-                                try!(writer.write_all(rest.as_bytes()));
-                                break;
-                            }
+        return None;
+    };
+    // Byte offsets are only char boundaries if the symbol is ASCII, which
+    // mangled symbols always are.
+    if !inner.bytes().all(|b| b < 0x80) {
+        return None;
+    }
 
-                        })
-                    }
+    let mut elements = Vec::new();
+    let mut rest = inner;
+    loop {
+        let digits = rest.bytes().take_while(|&b| b >= b'0' && b <= b'9').count();
+        if digits == 0 {
+            break;
+        }
+        let len: uint = match rest[..digits].parse() {
+            Ok(len) if len > 0 => len,
+            _ => return None,
+        };
+        rest = &rest[digits..];
+        if len > rest.len() {
+            return None;
+        }
+        elements.push(&rest[..len]);
+        rest = &rest[len..];
+    }
 
-                    // see src/librustc/back/link.rs for these mappings
-                    demangle! (
-                        "$SP$", => b"@",
-                        "$BP$", => b"*",
-                        "$RF$", => b"&",
-                        "$LT$", => b"<",
-                        "$GT$", => b">",
-                        "$LP$", => b"(",
-                        "$RP$", => b")",
-                        "$C$", => b",",
+    if elements.is_empty() || !rest.starts_with("E") {
+        return None;
+    }
+    let suffix = &rest[1..];
+    if !suffix.is_empty() && !suffix.starts_with(".") {
+        return None;
+    }
+    Some((elements, suffix))
+}
 
-                        // in theory we can demangle any Unicode code point, but
-                        // for simplicity we just catch the common ones.
-                        "$u7e$", => b"~",
-                        "$u20$", => b" ",
-                        "$u27$", => b"'",
-                        "$u5b$", => b"[",
-                        "$u5d$", => b"]"
-                    )
-                } else {
-                    let idx = match rest.find('$') {
-                        None => rest.len(),
-                        Some(i) => i,
-                    };
-                    try!(writer.write_all(rest[..idx].as_bytes()));
-                    rest = &rest[idx..];
+fn demangle_element(writer: &mut Write, element: &str) -> io::Result<()> {
+    let mut rest = element;
+    if rest.starts_with("_$") {
+        rest = &rest[1..];
+    }
+    while !rest.is_empty() {
+        if rest.starts_with("$") {
+            match unescape(&rest[1..]) {
+                Some((c, len)) => {
+                    try!(write!(writer, "{}", c));
+                    rest = &rest[1 + len..];
+                }
+                None => {
+                    // Not an escape we know about; print it as it is.
+                    try!(writer.write_all(rest.as_bytes()));
+                    break;
                 }
             }
+        } else if rest.starts_with("..") {
+            try!(writer.write_all(b"::"));
+            rest = &rest[2..];
+        } else {
+            let idx = rest.char_indices().skip(1)
+                          .find(|&(_, c)| c == '$' || c == '.')
+                          .map(|(i, _)| i)
+                          .unwrap_or(rest.len());
+            try!(writer.write_all(rest[..idx].as_bytes()));
+            rest = &rest[idx..];
         }
     }
-
     Ok(())
 }
+
+/// Decodes the escape at the start of `s`, which follows its opening '$'.
+/// Returns the escaped character and the length of the escape, including
+/// its closing '$'.
+fn unescape(s: &str) -> Option<(char, uint)> {
+    let end = match s.find('$') {
+        Some(end) => end,
+        None => return None,
+    };
+    let c = match &s[..end] {
+        "SP" => '@',
+        "BP" => '*',
+        "RF" => '&',
+        "LT" => '<',
+        "GT" => '>',
+        "LP" => '(',
+        "RP" => ')',
+        "C" => ',',
+        code if code.starts_with("u") && code.len() > 1 && code.len() <= 7 => {
+            let mut value = 0;
+            for c in code[1..].chars() {
+                match c.to_digit(16) {
+                    Some(digit) => value = value * 16 + digit,
+                    None => return None,
+                }
+            }
+            match char::from_u32(value) {
+                Some(c) => c,
+                None => return None,
+            }
+        }
+        _ => return None,
+    };
+    Some((c, end + 1))
+}
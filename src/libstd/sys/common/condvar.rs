@@ -31,6 +31,15 @@ impl Condvar {
     #[inline]
     pub unsafe fn new() -> Condvar { Condvar(imp::Condvar::new()) }
 
+    /// Prepares a condition variable created by `new` for timed waits that
+    /// are not affected by changes to the system clock, where the platform
+    /// supports that.
+    ///
+    /// Behavior is undefined if this is called after the condition variable
+    /// is first used, or if the condition variable is moved afterwards.
+    #[inline]
+    pub unsafe fn init(&mut self) { self.0.init() }
+
     /// Signal one waiter on this condition variable to wake up.
     #[inline]
     pub unsafe fn notify_one(&self) { self.0.notify_one() }
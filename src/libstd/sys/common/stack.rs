@@ -100,7 +100,7 @@ extern fn stack_exhausted() {
         //  #9854 - unwinding on windows through __morestack has never worked
         //  #2361 - possible implementation of not using landing pads
 
-        ::rt::util::report_overflow();
+        ::rt::util::report_overflow(None);
 
         intrinsics::abort();
     }
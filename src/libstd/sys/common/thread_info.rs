@@ -14,12 +14,18 @@ use core::prelude::*;
 
 use cell::RefCell;
 use string::String;
+use sys::stack_overflow;
+use sys::thread::guard;
 use thread::Thread;
 use thread::LocalKeyState;
 
 struct ThreadInfo {
     stack_guard: uint,
+    stack_size: uint,
     thread: Thread,
+    // Threads not spawned by Rust get their stack overflow handler installed
+    // when they are first seen, and keep it until the thread exits.
+    _handler: Option<stack_overflow::Handler>,
 }
 
 thread_local! { static THREAD_INFO: RefCell<Option<ThreadInfo>> = RefCell::new(None) }
@@ -34,8 +40,10 @@ impl ThreadInfo {
         THREAD_INFO.with(move |c| {
             if c.borrow().is_none() {
                 *c.borrow_mut() = Some(ThreadInfo {
-                    stack_guard: 0,
+                    stack_guard: unsafe { guard::current() },
+                    stack_size: 0,
                     thread: NewThread::new(None),
+                    _handler: Some(unsafe { stack_overflow::Handler::new() }),
                 })
             }
             f(c.borrow_mut().as_mut().unwrap())
@@ -51,11 +59,26 @@ pub fn stack_guard() -> uint {
     ThreadInfo::with(|info| info.stack_guard)
 }
 
-pub fn set(stack_guard: uint, thread: Thread) {
+/// The size of the current thread's stack, or 0 if it isn't known.
+pub fn stack_size() -> uint {
+    ThreadInfo::with(|info| info.stack_size)
+}
+
+/// Registers the current thread with the runtime if it hasn't been already,
+/// which is the case for threads created outside of Rust calling into it.
+pub fn ensure_registered() {
+    if THREAD_INFO.state() != LocalKeyState::Destroyed {
+        ThreadInfo::with(|_| ());
+    }
+}
+
+pub fn set(stack_guard: uint, stack_size: uint, thread: Thread) {
     THREAD_INFO.with(|c| assert!(c.borrow().is_none()));
     THREAD_INFO.with(move |c| *c.borrow_mut() = Some(ThreadInfo{
         stack_guard: stack_guard,
+        stack_size: stack_size,
         thread: thread,
+        _handler: None,
     }));
 }
 
@@ -12,6 +12,7 @@ use prelude::v1::*;
 
 use cell::UnsafeCell;
 use libc;
+use mem;
 use ptr;
 use sys::mutex::{self, Mutex};
 use sys::time;
@@ -19,13 +20,19 @@ use sys::sync as ffi;
 use time::Duration;
 use num::{Int, NumCast};
 
-pub struct Condvar { inner: UnsafeCell<ffi::pthread_cond_t> }
+pub struct Condvar {
+    inner: UnsafeCell<ffi::pthread_cond_t>,
+    // Whether `init` switched the timed waits over to CLOCK_MONOTONIC. A
+    // condvar made from PTHREAD_COND_INITIALIZER always uses the system clock.
+    monotonic: bool,
+}
 
 unsafe impl Send for Condvar {}
 unsafe impl Sync for Condvar {}
 
 pub const CONDVAR_INIT: Condvar = Condvar {
     inner: UnsafeCell { value: ffi::PTHREAD_COND_INITIALIZER },
+    monotonic: false,
 };
 
 impl Condvar {
@@ -33,9 +40,29 @@ impl Condvar {
     pub unsafe fn new() -> Condvar {
         // Might be moved and address is changing it is better to avoid
         // initialization of potentially opaque OS data before it landed
-        Condvar { inner: UnsafeCell::new(ffi::PTHREAD_COND_INITIALIZER) }
+        Condvar {
+            inner: UnsafeCell::new(ffi::PTHREAD_COND_INITIALIZER),
+            monotonic: false,
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    pub unsafe fn init(&mut self) {
+        let mut attr: ffi::pthread_condattr_t = mem::zeroed();
+        let r = ffi::pthread_condattr_init(&mut attr);
+        assert_eq!(r, 0);
+        let r = ffi::pthread_condattr_setclock(&mut attr, libc::CLOCK_MONOTONIC);
+        assert_eq!(r, 0);
+        let r = ffi::pthread_cond_init(self.inner.get(), &attr);
+        assert_eq!(r, 0);
+        let r = ffi::pthread_condattr_destroy(&mut attr);
+        assert_eq!(r, 0);
+        self.monotonic = true;
     }
 
+    #[cfg(not(target_os = "linux"))]
+    pub unsafe fn init(&mut self) {}
+
     #[inline]
     pub unsafe fn notify_one(&self) {
         let r = ffi::pthread_cond_signal(self.inner.get());
@@ -62,23 +89,23 @@ impl Condvar {
             return false;
         }
 
-        // First, figure out what time it currently is, in both system and stable time.
-        // pthread_cond_timedwait uses system time, but we want to report timeout based on stable
-        // time.
-        let mut sys_now = libc::timeval { tv_sec: 0, tv_usec: 0 };
+        // First, figure out what time it currently is, both on the clock
+        // pthread_cond_timedwait measures its deadline with and in stable
+        // time. We want to report timeout based on stable time.
         let stable_now = time::SteadyTime::now();
-        let r = ffi::gettimeofday(&mut sys_now, ptr::null_mut());
-        debug_assert_eq!(r, 0);
+        let now = self.clock_now();
 
         let seconds = NumCast::from(dur.num_seconds());
-        let timeout = match seconds.and_then(|s| sys_now.tv_sec.checked_add(s)) {
-            Some(sec) => {
-                libc::timespec {
-                    tv_sec: sec,
-                    tv_nsec: (dur - Duration::seconds(dur.num_seconds()))
-                        .num_nanoseconds().unwrap() as libc::c_long,
-                }
-            }
+        let nanos = now.tv_nsec +
+            (dur - Duration::seconds(dur.num_seconds()))
+                .num_nanoseconds().unwrap() as libc::c_long;
+        let (seconds, nanos) = if nanos >= 1_000_000_000 {
+            (seconds.and_then(|s: libc::time_t| s.checked_add(1)), nanos - 1_000_000_000)
+        } else {
+            (seconds, nanos)
+        };
+        let timeout = match seconds.and_then(|s| now.tv_sec.checked_add(s)) {
+            Some(sec) => libc::timespec { tv_sec: sec, tv_nsec: nanos },
             None => {
                 libc::timespec {
                     tv_sec: Int::max_value(),
@@ -96,6 +123,20 @@ impl Condvar {
         &time::SteadyTime::now() - &stable_now < dur
     }
 
+    // The current time on the clock this condvar's timed waits use.
+    unsafe fn clock_now(&self) -> libc::timespec {
+        if self.monotonic {
+            return monotonic_now();
+        }
+        let mut sys_now = libc::timeval { tv_sec: 0, tv_usec: 0 };
+        let r = ffi::gettimeofday(&mut sys_now, ptr::null_mut());
+        debug_assert_eq!(r, 0);
+        libc::timespec {
+            tv_sec: sys_now.tv_sec,
+            tv_nsec: sys_now.tv_usec as libc::c_long * 1000,
+        }
+    }
+
     #[inline]
     #[cfg(not(target_os = "dragonfly"))]
     pub unsafe fn destroy(&self) {
@@ -114,3 +155,16 @@ impl Condvar {
         debug_assert!(r == 0 || r == libc::EINVAL);
     }
 }
+
+#[cfg(target_os = "linux")]
+unsafe fn monotonic_now() -> libc::timespec {
+    let mut now = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+    let r = ffi::clock_gettime(libc::CLOCK_MONOTONIC, &mut now);
+    debug_assert_eq!(r, 0);
+    now
+}
+
+#[cfg(not(target_os = "linux"))]
+unsafe fn monotonic_now() -> libc::timespec {
+    unreachable!()
+}
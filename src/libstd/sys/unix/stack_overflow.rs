@@ -46,7 +46,7 @@ mod imp {
     use intrinsics;
     use self::signal::{siginfo, sigaction, SIGBUS, SIG_DFL,
                        SA_SIGINFO, SA_ONSTACK, sigaltstack,
-                       SIGSTKSZ};
+                       SIGSTKSZ, SS_DISABLE};
     use libc;
     use libc::funcs::posix88::mman::{mmap, munmap};
     use libc::consts::os::posix88::{SIGSEGV,
@@ -88,7 +88,7 @@ mod imp {
             term(signum);
         }
 
-        report_overflow();
+        report_overflow(Some(addr));
 
         intrinsics::abort()
     }
@@ -119,6 +119,14 @@ mod imp {
     }
 
     pub unsafe fn make_handler() -> Handler {
+        // Leave the alternate signal stack of threads which already have one
+        // (e.g. set up by the C code which created them) alone.
+        let mut current: sigaltstack = mem::zeroed();
+        sigaltstack(ptr::null(), &mut current);
+        if current.ss_flags & SS_DISABLE == 0 {
+            return Handler { _data: ptr::null_mut() };
+        }
+
         let alt_stack = mmap(ptr::null_mut(),
                              signal::SIGSTKSZ,
                              PROT_READ | PROT_WRITE,
@@ -141,7 +149,9 @@ mod imp {
     }
 
     pub unsafe fn drop_handler(handler: &mut Handler) {
-        munmap(handler._data, SIGSTKSZ);
+        if !handler._data.is_null() {
+            munmap(handler._data, SIGSTKSZ);
+        }
     }
 
     pub type sighandler_t = *mut libc::c_void;
@@ -163,6 +173,7 @@ mod imp {
         pub static SIGBUS: libc::c_int = 7;
 
         pub static SIGSTKSZ: libc::size_t = 8192;
+        pub static SS_DISABLE: libc::c_int = 2;
 
         pub const SIG_DFL: sighandler_t = 0 as sighandler_t;
 
@@ -221,6 +232,8 @@ mod imp {
         #[cfg(any(target_os = "bitrig", target_os = "openbsd"))]
         pub const SIGSTKSZ: libc::size_t = 40960;
 
+        pub const SS_DISABLE: libc::c_int = 4;
+
         pub const SIG_DFL: sighandler_t = 0 as sighandler_t;
 
         pub type sigset_t = u32;
@@ -15,6 +15,8 @@ use libc;
 pub use self::os::{PTHREAD_MUTEX_INITIALIZER, pthread_mutex_t};
 pub use self::os::{PTHREAD_COND_INITIALIZER, pthread_cond_t};
 pub use self::os::{PTHREAD_RWLOCK_INITIALIZER, pthread_rwlock_t};
+#[cfg(target_os = "linux")]
+pub use self::os::pthread_condattr_t;
 
 extern {
     // mutexes
@@ -44,6 +46,18 @@ extern {
     pub fn pthread_rwlock_unlock(lock: *mut pthread_rwlock_t) -> libc::c_int;
 }
 
+#[cfg(target_os = "linux")]
+extern {
+    // cvars timed with a clock other than the system clock
+    pub fn pthread_cond_init(cond: *mut pthread_cond_t,
+                             attr: *const pthread_condattr_t) -> libc::c_int;
+    pub fn pthread_condattr_init(attr: *mut pthread_condattr_t) -> libc::c_int;
+    pub fn pthread_condattr_setclock(attr: *mut pthread_condattr_t,
+                                     clock_id: libc::c_int) -> libc::c_int;
+    pub fn pthread_condattr_destroy(attr: *mut pthread_condattr_t) -> libc::c_int;
+    pub fn clock_gettime(clk_id: libc::c_int, tp: *mut libc::timespec) -> libc::c_int;
+}
+
 #[cfg(any(target_os = "freebsd",
           target_os = "dragonfly",
           target_os = "bitrig",
@@ -170,6 +184,10 @@ mod os {
         __align: libc::c_longlong,
         size: [u8; __SIZEOF_PTHREAD_RWLOCK_T],
     }
+    #[repr(C)]
+    pub struct pthread_condattr_t {
+        __align: libc::c_int,
+    }
 
     pub const PTHREAD_MUTEX_INITIALIZER: pthread_mutex_t = pthread_mutex_t {
         __align: 0,
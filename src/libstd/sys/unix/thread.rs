@@ -161,7 +161,9 @@ pub mod guard {
         let mut guardsize = 0;
         assert_eq!(pthread_attr_getguardsize(&attr, &mut guardsize), 0);
         if guardsize == 0 {
-            panic!("there is no guard page");
+            // Threads created outside of Rust may not have a guard page.
+            assert_eq!(pthread_attr_destroy(&mut attr), 0);
+            return 0;
         }
         let mut stackaddr = ptr::null_mut();
         let mut size = 0;
@@ -11,6 +11,7 @@
 use prelude::v1::*;
 use libc::c_int;
 
+#[cfg_attr(not(stage0), allow(private_in_public))]
 pub type Key = pthread_key_t;
 
 #[inline]
@@ -30,6 +30,9 @@ impl Condvar {
     #[inline]
     pub unsafe fn new() -> Condvar { CONDVAR_INIT }
 
+    #[inline]
+    pub unsafe fn init(&mut self) {}
+
     #[inline]
     pub unsafe fn wait(&self, mutex: &Mutex) {
         let r = ffi::SleepConditionVariableSRW(self.inner.get(),
@@ -336,7 +336,7 @@ impl Builder {
                 }
                 stack::record_os_managed_stack_bounds(my_stack_bottom,
                                                       my_stack_top);
-                thread_info::set(imp::guard::current(), their_thread);
+                thread_info::set(imp::guard::current(), stack_size, their_thread);
             }
 
             let mut output = None;
@@ -49,21 +49,32 @@ pub enum Abi {
 }
 
 #[allow(non_camel_case_types)]
-#[derive(Copy, PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum Architecture {
     X86,
     X86_64,
     Arm,
     Mips,
-    Mipsel
+    Mipsel,
+    AArch64,
+    PowerPC,
 }
 
+#[allow(non_upper_case_globals)]
+const IntelBits: u32 = (1 << (X86 as usize)) | (1 << (X86_64 as usize));
+
+#[allow(non_upper_case_globals)]
+const ArmBits: u32 = 1 << (Arm as usize);
+
 #[derive(Copy)]
 pub struct AbiData {
     abi: Abi,
 
     // Name of this ABI as we like it called.
     name: &'static str,
+
+    // The architectures on which this ABI means something.
+    abi_arch: AbiArchitecture,
 }
 
 #[derive(Copy)]
@@ -79,21 +90,21 @@ pub enum AbiArchitecture {
 #[allow(non_upper_case_globals)]
 const AbiDatas: &'static [AbiData] = &[
     // Platform-specific ABIs
-    AbiData {abi: Cdecl, name: "cdecl" },
-    AbiData {abi: Stdcall, name: "stdcall" },
-    AbiData {abi: Fastcall, name: "fastcall" },
-    AbiData {abi: Aapcs, name: "aapcs" },
-    AbiData {abi: Win64, name: "win64" },
+    AbiData {abi: Cdecl, name: "cdecl", abi_arch: Archs(IntelBits) },
+    AbiData {abi: Stdcall, name: "stdcall", abi_arch: Archs(IntelBits) },
+    AbiData {abi: Fastcall, name: "fastcall", abi_arch: Archs(IntelBits) },
+    AbiData {abi: Aapcs, name: "aapcs", abi_arch: Archs(ArmBits) },
+    AbiData {abi: Win64, name: "win64", abi_arch: Archs(1 << (X86_64 as usize)) },
 
     // Cross-platform ABIs
     //
     // NB: Do not adjust this ordering without
     // adjusting the indices below.
-    AbiData {abi: Rust, name: "Rust" },
-    AbiData {abi: C, name: "C" },
-    AbiData {abi: System, name: "system" },
-    AbiData {abi: RustIntrinsic, name: "rust-intrinsic" },
-    AbiData {abi: RustCall, name: "rust-call" },
+    AbiData {abi: Rust, name: "Rust", abi_arch: RustArch },
+    AbiData {abi: C, name: "C", abi_arch: AllArch },
+    AbiData {abi: System, name: "system", abi_arch: AllArch },
+    AbiData {abi: RustIntrinsic, name: "rust-intrinsic", abi_arch: RustArch },
+    AbiData {abi: RustCall, name: "rust-call", abi_arch: RustArch },
 ];
 
 /// Returns the ABI with the given name (if any).
@@ -119,6 +130,57 @@ impl Abi {
     pub fn name(&self) -> &'static str {
         self.data().name
     }
+
+    /// Returns the ABI to use on `arch`, or `None` if this ABI is
+    /// meaningless there (e.g. `stdcall` on ARM).
+    pub fn for_arch(&self, arch: Architecture) -> Option<Abi> {
+        match self.data().abi_arch {
+            RustArch | AllArch => Some(*self),
+            Archs(bits) if bits & arch.bit() != 0 => Some(*self),
+            Archs(_) => None,
+        }
+    }
+
+    /// Returns the architectures this ABI is restricted to, if any.
+    pub fn archs(&self) -> Vec<Architecture> {
+        match self.data().abi_arch {
+            RustArch | AllArch => vec![],
+            Archs(bits) => {
+                ALL_ARCHS.iter().cloned().filter(|a| bits & a.bit() != 0).collect()
+            }
+        }
+    }
+}
+
+const ALL_ARCHS: &'static [Architecture] = &[X86, X86_64, Arm, Mips, Mipsel, AArch64, PowerPC];
+
+impl Architecture {
+    fn bit(&self) -> u32 {
+        1 << (*self as usize)
+    }
+
+    /// Returns the architecture with the given target `arch` name (if any).
+    pub fn lookup(name: &str) -> Option<Architecture> {
+        ALL_ARCHS.iter().cloned().find(|a| a.name() == name)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match *self {
+            X86 => "x86",
+            X86_64 => "x86_64",
+            Arm => "arm",
+            Mips => "mips",
+            Mipsel => "mipsel",
+            AArch64 => "aarch64",
+            PowerPC => "powerpc",
+        }
+    }
+}
+
+impl fmt::Display for Architecture {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.name().fmt(f)
+    }
 }
 
 impl fmt::Display for Abi {
@@ -162,6 +224,15 @@ fn lookup_baz() {
     assert!(abi.is_none());
 }
 
+#[test]
+fn stdcall_is_intel_only() {
+    assert_eq!(Stdcall.for_arch(X86), Some(Stdcall));
+    assert_eq!(Stdcall.for_arch(X86_64), Some(Stdcall));
+    assert_eq!(Stdcall.for_arch(Arm), None);
+    assert_eq!(System.for_arch(Arm), Some(System));
+    assert_eq!(Architecture::lookup("aarch64"), Some(AArch64));
+}
+
 #[test]
 fn indices_are_correct() {
     for (i, abi_data) in AbiDatas.iter().enumerate() {
@@ -668,6 +668,15 @@ pub enum BinOp_ {
 
 pub type BinOp = Spanned<BinOp_>;
 
+/// Whether a range includes its upper bound.
+#[derive(Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug, Copy)]
+pub enum RangeLimits {
+    /// `start..end`, excluding `end`
+    HalfOpen,
+    /// `start...end`, including `end`
+    Closed,
+}
+
 #[derive(Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug, Copy)]
 pub enum UnOp {
     /// The `box` operator
@@ -870,8 +879,8 @@ pub enum Expr_ {
     ExprTupField(P<Expr>, Spanned<usize>),
     /// An indexing operation (`foo[2]`)
     ExprIndex(P<Expr>, P<Expr>),
-    /// A range (`1..2`, `1..`, or `..2`)
-    ExprRange(Option<P<Expr>>, Option<P<Expr>>),
+    /// A range (`1..2`, `1..`, `..2`, or `1...2`)
+    ExprRange(Option<P<Expr>>, Option<P<Expr>>, RangeLimits),
 
     /// Variable reference, possibly containing `::` and/or type
     /// parameters, e.g. foo::bar::<baz>.
@@ -1103,7 +1112,8 @@ impl TokenTree {
                                                          None,
                                                          tts.iter().cloned().collect(),
                                                          true);
-        macro_parser::parse(cx.parse_sess(), cx.cfg(), arg_rdr, mtch)
+        macro_parser::parse(cx.parse_sess(), cx.cfg(), arg_rdr, mtch,
+                            cx.ecfg.macro_work_limit)
     }
 }
 
@@ -1695,13 +1705,15 @@ pub struct PolyTraitRef {
 pub enum Visibility {
     Public,
     Inherited,
+    /// `pub(crate)`: public within the defining crate, private outside of it.
+    PubCrate,
 }
 
 impl Visibility {
     pub fn inherit_from(&self, parent_visibility: Visibility) -> Visibility {
         match self {
             &Inherited => parent_visibility,
-            &Public => *self
+            &Public | &PubCrate => *self
         }
     }
 }
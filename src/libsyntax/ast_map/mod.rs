@@ -75,6 +75,7 @@ impl<'a> Iterator for LinkedPath<'a> {
 }
 
 /// The type of the iterator used by with_path.
+#[cfg_attr(not(stage0), allow(private_in_public))]
 pub type PathElems<'a, 'b> = iter::Chain<iter::Cloned<slice::Iter<'a, PathElem>>, LinkedPath<'b>>;
 
 pub fn path_to_string<PI: Iterator<Item=PathElem>>(path: PI) -> String {
@@ -327,6 +327,11 @@ pub fn requests_inline(attrs: &[Attribute]) -> bool {
 }
 
 /// Tests if a cfg-pattern matches the cfg set
+/// Evaluate a cfg pattern against the crate configuration.
+///
+/// The configuration is a multimap: `--cfg feature="a" --cfg feature="b"`
+/// gives the key `feature` two values, so `feature = "b"` holds when any
+/// entry matches it and `not(feature = "b")` only when none does.
 pub fn cfg_matches(diagnostic: &SpanHandler, cfgs: &[P<MetaItem>], cfg: &ast::MetaItem) -> bool {
     match cfg.node {
         ast::MetaList(ref pred, ref mis) if &pred[..] == "any" =>
@@ -497,6 +502,65 @@ pub fn find_stability(diagnostic: &SpanHandler, attrs: &[Attribute],
     return s;
 }
 
+/// Represents a `#[deprecated]` attribute outside of the staged stability
+/// system, as usable by any crate.
+#[derive(RustcEncodable,RustcDecodable,Clone,Debug)]
+pub struct Deprecation {
+    pub since: Option<InternedString>,
+    pub note: Option<InternedString>,
+}
+
+/// Find the `#[deprecated(since = "...", note = "...")]` attribute, if any.
+/// Crates using `#![staged_api]` should use `find_stability` instead, where
+/// `#[deprecated]` is paired with a stability level.
+pub fn find_deprecation(diagnostic: &SpanHandler, attrs: &[Attribute],
+                        item_sp: Span) -> Option<Deprecation> {
+    let mut depr: Option<Deprecation> = None;
+
+    'outer: for attr in attrs {
+        if &attr.name()[..] != "deprecated" {
+            continue
+        }
+
+        mark_used(attr);
+
+        if depr.is_some() {
+            diagnostic.span_err(item_sp, "multiple deprecated attributes");
+            break
+        }
+
+        let mut since = None;
+        let mut note = None;
+        if let Some(metas) = attr.meta_item_list() {
+            for meta in metas {
+                let slot = match &meta.name()[..] {
+                    "since" => &mut since,
+                    "note" => &mut note,
+                    other => {
+                        diagnostic.span_err(meta.span,
+                                            &format!("unknown meta item '{}'", other));
+                        continue 'outer;
+                    }
+                };
+                match meta.value_str() {
+                    Some(v) => *slot = Some(v),
+                    None => {
+                        diagnostic.span_err(meta.span, "incorrect meta item");
+                        continue 'outer;
+                    }
+                }
+            }
+        } else if let MetaNameValue(..) = attr.node.value.node {
+            diagnostic.span_err(attr.span(), "incorrect deprecated attribute type");
+            continue
+        }
+
+        depr = Some(Deprecation { since: since, note: note });
+    }
+
+    depr
+}
+
 pub fn require_unique_names(diagnostic: &SpanHandler, metas: &[P<MetaItem>]) {
     let mut set = HashSet::new();
     for meta in metas {
@@ -524,24 +588,13 @@ pub fn find_repr_attrs(diagnostic: &SpanHandler, attr: &Attribute) -> Vec<ReprAt
             for item in items {
                 match item.node {
                     ast::MetaWord(ref word) => {
-                        let hint = match &word[..] {
-                            // Can't use "extern" because it's not a lexical identifier.
-                            "C" => Some(ReprExtern),
-                            "packed" => Some(ReprPacked),
-                            _ => match int_type_of_word(&word) {
-                                Some(ity) => Some(ReprInt(item.span, ity)),
-                                None => {
-                                    // Not a word we recognize
-                                    diagnostic.span_err(item.span,
-                                                        "unrecognized representation hint");
-                                    None
-                                }
-                            }
-                        };
-
-                        match hint {
+                        match repr_hint_of_word(&word, item.span) {
                             Some(h) => acc.push(h),
-                            None => { }
+                            None => {
+                                // Not a word we recognize
+                                diagnostic.span_err(item.span,
+                                                    "unrecognized representation hint");
+                            }
                         }
                     }
                     // Not a word:
@@ -555,6 +608,35 @@ pub fn find_repr_attrs(diagnostic: &SpanHandler, attr: &Attribute) -> Vec<ReprAt
     acc
 }
 
+/// Like `find_repr_attrs`, but pairs each hint with the span it was
+/// written at. Unrecognized hints are skipped without an error, since
+/// `find_repr_attrs` already reports them.
+pub fn find_repr_attrs_with_spans(attr: &Attribute) -> Vec<(Span, ReprAttr)> {
+    match attr.node.value.node {
+        ast::MetaList(ref s, ref items) if *s == "repr" => {
+            mark_used(attr);
+            items.iter().filter_map(|item| {
+                match item.node {
+                    ast::MetaWord(ref word) => {
+                        repr_hint_of_word(&word, item.span).map(|h| (item.span, h))
+                    }
+                    _ => None
+                }
+            }).collect()
+        }
+        _ => Vec::new()
+    }
+}
+
+fn repr_hint_of_word(word: &str, span: Span) -> Option<ReprAttr> {
+    match word {
+        // Can't use "extern" because it's not a lexical identifier.
+        "C" => Some(ReprExtern),
+        "packed" => Some(ReprPacked),
+        _ => int_type_of_word(word).map(|ity| ReprInt(span, ity))
+    }
+}
+
 fn int_type_of_word(s: &str) -> Option<IntType> {
     match s {
         "i8" => Some(SignedInt(ast::TyI8)),
@@ -616,3 +698,47 @@ impl IntType {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::cfg_matches;
+    use ast::MetaItem;
+    use parse;
+    use ptr::P;
+
+    fn meta(sess: &parse::ParseSess, src: &str) -> P<MetaItem> {
+        parse::parse_meta_from_source_str("cfgspec".to_string(), src.to_string(),
+                                          Vec::new(), sess)
+    }
+
+    fn matches(config: &[&str], pattern: &str) -> bool {
+        let sess = parse::new_parse_sess();
+        let config: Vec<_> = config.iter().map(|src| meta(&sess, src)).collect();
+        cfg_matches(&sess.span_diagnostic, &config, &meta(&sess, pattern))
+    }
+
+    #[test]
+    fn any_value_of_a_repeated_key_matches() {
+        let config = ["feature = \"a\"", "feature = \"b\""];
+        assert!(matches(&config, "feature = \"a\""));
+        assert!(matches(&config, "feature = \"b\""));
+        assert!(!matches(&config, "feature = \"c\""));
+        assert!(matches(&config, "all(feature = \"a\", feature = \"b\")"));
+    }
+
+    #[test]
+    fn not_matches_only_when_no_value_does() {
+        let config = ["feature = \"a\"", "feature = \"b\""];
+        assert!(!matches(&config, "not(feature = \"b\")"));
+        assert!(matches(&config, "not(feature = \"c\")"));
+        assert!(!matches(&config, "not(any(feature = \"c\", feature = \"a\"))"));
+    }
+
+    #[test]
+    fn words_and_values_are_distinct() {
+        let config = ["feature = \"a\"", "test"];
+        assert!(!matches(&config, "feature"));
+        assert!(matches(&config, "test"));
+        assert!(!matches(&config, "test = \"a\""));
+    }
+}
@@ -194,6 +194,20 @@ pub fn original_sp(cm: &CodeMap, sp: Span, enclosing_sp: Span) -> Span {
     }
 }
 
+/// Applies a `FROM=TO` path prefix mapping to `path`: if `path` starts with
+/// `FROM`, that prefix is replaced with `TO`. Used to keep build directories
+/// out of the paths embedded in compiler output.
+pub fn remap_path_prefix(path: &str, mapping: &str) -> String {
+    let mut parts = mapping.splitn(1, '=');
+    let from = parts.next().unwrap();
+    match parts.next() {
+        Some(to) if !from.is_empty() && path.starts_with(from) => {
+            format!("{}{}", to, &path[from.len()..])
+        }
+        _ => path.to_string(),
+    }
+}
+
 // _____________________________________________________________________________
 // Loc, LocWithOpt, FileMapAndLine, FileMapAndBytePos
 //
@@ -477,9 +491,15 @@ impl FileMap {
                     let begin: BytePos = line - self.start_pos;
                     let begin = begin.to_usize();
                     let slice = &src[begin..];
-                    match slice.find('\n') {
+                    let line = match slice.find('\n') {
                         Some(e) => &slice[..e],
                         None => slice
+                    };
+                    // Leave the `\r` of a `\r\n` line ending out of snippets.
+                    if line.ends_with("\r") {
+                        &line[..line.len() - 1]
+                    } else {
+                        line
                     }.to_string()
                 })
             }
@@ -1067,4 +1087,38 @@ mod test {
 
         assert_eq!(sstr, "blork.rs:2:1: 2:12");
     }
+
+    #[test]
+    fn t10() {
+        // Test lines and columns in a file with CRLF line endings and
+        // multi-byte chars
+        let cm = CodeMap::new();
+        let fm = cm.new_filemap("crlf.rs".to_string(),
+                                "€ a\r\n€€ b\r\nc".to_string());
+        fm.next_line(BytePos(0));
+        fm.next_line(BytePos(7));
+        fm.next_line(BytePos(17));
+        fm.record_multibyte_char(BytePos(0), 3);
+        fm.record_multibyte_char(BytePos(7), 3);
+        fm.record_multibyte_char(BytePos(10), 3);
+
+        assert_eq!(fm.get_line(0), Some("€ a".to_string()));
+        assert_eq!(fm.get_line(1), Some("€€ b".to_string()));
+        assert_eq!(fm.get_line(2), Some("c".to_string()));
+
+        let b = cm.lookup_char_pos(BytePos(14));
+        assert_eq!(b.line, 2);
+        assert_eq!(b.col, CharPos(3));
+
+        let span = Span {lo: BytePos(4), hi: BytePos(5), expn_id: NO_EXPANSION};
+        assert_eq!(cm.span_to_string(span), "crlf.rs:1:3: 1:4");
+    }
+
+    #[test]
+    fn t_remap_path_prefix() {
+        assert_eq!(remap_path_prefix("/tmp/a/foo.rs", "/tmp/a=/src"), "/src/foo.rs");
+        assert_eq!(remap_path_prefix("/tmp/b/foo.rs", "/tmp/a=/src"), "/tmp/b/foo.rs");
+        assert_eq!(remap_path_prefix("/tmp/a/foo.rs", "/tmp/a="), "/foo.rs");
+        assert_eq!(remap_path_prefix("foo.rs", "=/src/"), "foo.rs");
+    }
 }
@@ -31,6 +31,23 @@ pub fn strip_unconfigured_items(diagnostic: &SpanHandler, krate: ast::Crate) ->
     strip_items(krate, |attrs| in_cfg(diagnostic, &config, attrs))
 }
 
+/// Like `strip_unconfigured_items`, but for a single item produced during
+/// expansion, so that decorators such as `#[derive]` never see the parts of it
+/// that are configured out. Returns `None` if the item itself is configured
+/// out.
+pub fn strip_unconfigured_item(diagnostic: &SpanHandler,
+                               config: &ast::CrateConfig,
+                               item: P<ast::Item>)
+                               -> Option<P<ast::Item>> {
+    let mut cfg_attr_folder = CfgAttrFolder {
+        diag: diagnostic,
+        config: config.clone(),
+    };
+    let item = cfg_attr_folder.fold_item(item)
+                              .expect_one("expected fold to produce exactly one item");
+    strip_item(item, |attrs| in_cfg(diagnostic, config, attrs))
+}
+
 impl<F> fold::Folder for Context<F> where F: FnMut(&[ast::Attribute]) -> bool {
     fn fold_mod(&mut self, module: ast::Mod) -> ast::Mod {
         fold_mod(self, module)
@@ -64,6 +81,15 @@ pub fn strip_items<F>(krate: ast::Crate, in_cfg: F) -> ast::Crate where
     ctxt.fold_crate(krate)
 }
 
+fn strip_item<F>(item: P<ast::Item>, in_cfg: F) -> Option<P<ast::Item>> where
+    F: FnMut(&[ast::Attribute]) -> bool,
+{
+    let mut ctxt = Context {
+        in_cfg: in_cfg,
+    };
+    ctxt.fold_item(item).into_iter().next()
+}
+
 fn fold_mod<F>(cx: &mut Context<F>,
                ast::Mod {inner, items}: ast::Mod)
                -> ast::Mod where
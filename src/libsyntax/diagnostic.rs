@@ -13,7 +13,7 @@ pub use self::RenderSpan::*;
 pub use self::ColorConfig::*;
 use self::Destination::*;
 
-use codemap::{COMMAND_LINE_SP, COMMAND_LINE_EXPN, Pos, Span};
+use codemap::{COMMAND_LINE_SP, COMMAND_LINE_EXPN, DUMMY_SP, NO_EXPANSION, Pos, Span};
 use codemap;
 use diagnostics;
 
@@ -21,6 +21,7 @@ use std::cell::{RefCell, Cell};
 use std::fmt;
 use std::io::prelude::*;
 use std::io;
+use std::rt;
 use term::WriterWrapper;
 use term;
 use libc;
@@ -28,7 +29,7 @@ use libc;
 /// maximum number of lines we will print for each error; arbitrary.
 const MAX_LINES: usize = 6;
 
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub enum RenderSpan {
     /// A FullSpan renders with both with an initial line for the
     /// message, prefixed by file:linenum, followed by a summary of
@@ -38,17 +39,22 @@ pub enum RenderSpan {
     /// A FileLine renders with just a line for the message prefixed
     /// by file:linenum.
     FileLine(Span),
+
+    /// A Suggestion renders like a FullSpan, followed by the code suggested
+    /// to replace the span with, lined up beneath the source it replaces.
+    /// An empty span suggests inserting the code there.
+    Suggestion(Span, String),
 }
 
 impl RenderSpan {
-    fn span(self) -> Span {
-        match self {
-            FullSpan(s) | FileLine(s) => s
+    fn span(&self) -> Span {
+        match *self {
+            FullSpan(s) | FileLine(s) | Suggestion(s, _) => s
         }
     }
     fn is_full_span(&self) -> bool {
         match self {
-            &FullSpan(..) => true,
+            &FullSpan(..) | &Suggestion(..) => true,
             &FileLine(..) => false,
         }
     }
@@ -90,18 +96,22 @@ pub struct SpanHandler {
 impl SpanHandler {
     pub fn span_fatal(&self, sp: Span, msg: &str) -> ! {
         self.handler.emit(Some((&self.cm, sp)), msg, Fatal);
+        self.check_error_span(sp);
         panic!(FatalError);
     }
     pub fn span_fatal_with_code(&self, sp: Span, msg: &str, code: &str) -> ! {
         self.handler.emit_with_code(Some((&self.cm, sp)), msg, code, Fatal);
+        self.check_error_span(sp);
         panic!(FatalError);
     }
     pub fn span_err(&self, sp: Span, msg: &str) {
         self.handler.emit(Some((&self.cm, sp)), msg, Error);
+        self.check_error_span(sp);
         self.handler.bump_err_count();
     }
     pub fn span_err_with_code(&self, sp: Span, msg: &str, code: &str) {
         self.handler.emit_with_code(Some((&self.cm, sp)), msg, code, Error);
+        self.check_error_span(sp);
         self.handler.bump_err_count();
     }
     pub fn span_warn(&self, sp: Span, msg: &str) {
@@ -125,6 +135,11 @@ impl SpanHandler {
     pub fn fileline_help(&self, sp: Span, msg: &str) {
         self.handler.custom_emit(&self.cm, FileLine(sp), msg, Help);
     }
+    /// Suggest replacing the code at `sp` with `suggestion`, or inserting
+    /// it there if `sp` is empty.
+    pub fn span_suggestion(&self, sp: Span, msg: &str, suggestion: String) {
+        self.handler.custom_emit(&self.cm, Suggestion(sp, suggestion), msg, Help);
+    }
     pub fn span_bug(&self, sp: Span, msg: &str) -> ! {
         self.handler.emit(Some((&self.cm, sp)), msg, Bug);
         panic!(ExplicitBug);
@@ -135,6 +150,31 @@ impl SpanHandler {
     pub fn handler<'a>(&'a self) -> &'a Handler {
         &self.handler
     }
+
+    /// Whether `sp` points at something the user can find: not the dummy
+    /// span, and not inside a macro expansion whose call site is unknown.
+    pub fn span_is_usable(&self, sp: Span) -> bool {
+        if sp == DUMMY_SP {
+            return false;
+        }
+        if sp.expn_id == NO_EXPANSION || sp.expn_id == COMMAND_LINE_EXPN {
+            return true;
+        }
+        self.cm.with_expn_info(sp.expn_id, |info| info.is_some())
+    }
+
+    /// Under `-Z report-dummy-spans`, follow an error reported without a
+    /// usable span with a backtrace of the code that reported it.
+    fn check_error_span(&self, sp: Span) {
+        if !self.handler.report_dummy_spans.get() || self.span_is_usable(sp) {
+            return;
+        }
+        self.handler.note("the error above was reported without a usable span, from:");
+        let mut stderr = io::stderr();
+        if let Err(e) = rt::backtrace::write(&mut stderr) {
+            panic!("failed to print backtrace: {:?}", e);
+        }
+    }
 }
 
 /// A handler deals with errors; certain errors
@@ -143,10 +183,16 @@ impl SpanHandler {
 pub struct Handler {
     err_count: Cell<usize>,
     emit: RefCell<Box<Emitter + Send>>,
-    pub can_emit_warnings: bool
+    pub can_emit_warnings: bool,
+    report_dummy_spans: Cell<bool>,
 }
 
 impl Handler {
+    /// Print a backtrace for each error reported at a span that isn't
+    /// usable; see `SpanHandler::span_is_usable`.
+    pub fn set_report_dummy_spans(&self, report: bool) {
+        self.report_dummy_spans.set(report);
+    }
     pub fn fatal(&self, msg: &str) -> ! {
         self.emit.borrow_mut().emit(None, msg, None, Fatal);
         panic!(FatalError);
@@ -231,7 +277,8 @@ pub fn mk_handler(can_emit_warnings: bool, e: Box<Emitter + Send>) -> Handler {
     Handler {
         err_count: Cell::new(0),
         emit: RefCell::new(e),
-        can_emit_warnings: can_emit_warnings
+        can_emit_warnings: can_emit_warnings,
+        report_dummy_spans: Cell::new(false),
     }
 }
 
@@ -444,8 +491,15 @@ fn emit(dst: &mut EmitterWriter, cm: &codemap::CodeMap, rsp: RenderSpan,
         let span_end = Span { lo: sp.hi, hi: sp.hi, expn_id: sp.expn_id};
         let ses = cm.span_to_string(span_end);
         try!(print_diagnostic(dst, &ses[..], lvl, msg, code));
-        if rsp.is_full_span() {
-            try!(custom_highlight_lines(dst, cm, sp, lvl, cm.span_to_lines(sp)));
+        match rsp {
+            FullSpan(_) => {
+                try!(custom_highlight_lines(dst, cm, sp, lvl, cm.span_to_lines(sp)));
+            }
+            Suggestion(_, ref suggestion) => {
+                try!(highlight_lines(dst, cm, sp, lvl, cm.span_to_lines(sp)));
+                try!(print_suggestion(dst, cm, sp, suggestion));
+            }
+            FileLine(_) => {}
         }
     } else {
         try!(print_diagnostic(dst, &ss[..], lvl, msg, code));
@@ -573,6 +627,25 @@ fn highlight_lines(err: &mut EmitterWriter,
     Ok(())
 }
 
+/// Print the code suggested for `sp` beneath the snippet `highlight_lines`
+/// printed for it: the first line starts at the column the span does, and
+/// later lines keep their own indentation.
+fn print_suggestion(w: &mut EmitterWriter,
+                    cm: &codemap::CodeMap,
+                    sp: Span,
+                    suggestion: &str)
+                    -> io::Result<()> {
+    let lines = cm.span_to_lines(sp);
+    let last_line = lines.lines.last().map_or(0, |&line| line);
+    let skip = format!("{}:{} ", lines.file.name, last_line + 1).width(false);
+    let col = cm.lookup_char_pos(sp.lo).col.to_usize();
+    for (i, line) in suggestion.lines().enumerate() {
+        let indent = if i == 0 { skip + col } else { skip };
+        try!(write!(&mut w.dst, "{0:1$}{2}\n", "", indent, line));
+    }
+    Ok(())
+}
+
 /// Here are the differences between this and the normal `highlight_lines`:
 /// `custom_highlight_lines` will always put arrow on the last byte of the
 /// span (instead of the first byte). Also, when the span is too long (more
@@ -465,6 +465,9 @@ fn initial_syntax_expander_table<'feat>(ecfg: &expand::ExpansionConfig<'feat>)
     syntax_expanders.insert(intern("concat_idents"),
                             builtin_normal_expander(
                                     ext::concat_idents::expand_syntax_ext));
+    syntax_expanders.insert(intern("compile_error"),
+                            builtin_normal_expander(
+                                    ext::compile_error::expand_compile_error));
     syntax_expanders.insert(intern("concat"),
                             builtin_normal_expander(
                                     ext::concat::expand_syntax_ext));
@@ -503,6 +506,18 @@ fn initial_syntax_expander_table<'feat>(ecfg: &expand::ExpansionConfig<'feat>)
         syntax_expanders.insert(intern("quote_attr"),
                            builtin_normal_expander(
                                 ext::quote::expand_quote_attr));
+        syntax_expanders.insert(intern("quote_tokens_spanned"),
+                           builtin_normal_expander(
+                                ext::quote::expand_quote_tokens_spanned));
+        syntax_expanders.insert(intern("quote_expr_spanned"),
+                           builtin_normal_expander(
+                                ext::quote::expand_quote_expr_spanned));
+        syntax_expanders.insert(intern("quote_item_spanned"),
+                           builtin_normal_expander(
+                                ext::quote::expand_quote_item_spanned));
+        syntax_expanders.insert(intern("quote_stmt_spanned"),
+                           builtin_normal_expander(
+                                ext::quote::expand_quote_stmt_spanned));
     }
 
     syntax_expanders.insert(intern("line"),
@@ -550,6 +565,9 @@ pub struct ExtCtxt<'a> {
     pub backtrace: ExpnId,
     pub ecfg: expand::ExpansionConfig<'a>,
     pub use_std: bool,
+    /// The name the standard library is available under at the crate root,
+    /// if it is linked at all; see `std_inject::std_crate_name`.
+    pub std_crate_name: Option<ast::Ident>,
 
     pub mod_path: Vec<ast::Ident> ,
     pub trace_mac: bool,
@@ -570,6 +588,7 @@ impl<'a> ExtCtxt<'a> {
             mod_path: Vec::new(),
             ecfg: ecfg,
             use_std: true,
+            std_crate_name: Some(str_to_ident("std")),
             trace_mac: false,
             exported_macros: Vec::new(),
             syntax_env: env,
@@ -749,7 +768,7 @@ impl<'a> ExtCtxt<'a> {
         str_to_ident(st)
     }
     pub fn ident_of_std(&self, st: &str) -> ast::Ident {
-        self.ident_of(if self.use_std { "std" } else { st })
+        self.std_crate_name.unwrap_or_else(|| self.ident_of(st))
     }
     pub fn name_of(&self, st: &str) -> ast::Name {
         token::intern(st)
@@ -0,0 +1,37 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/*
+ * The compiler code necessary to support the compile_error! extension, which
+ * lets a library fail compilation with a message of its own, for instance
+ * when a macro is given input it does not support.
+ */
+
+use ast;
+use codemap::Span;
+use ext::base::*;
+use ext::base;
+
+pub fn expand_compile_error<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
+                                 -> Box<base::MacResult+'cx> {
+    // The argument is expanded first, so the message can be built with
+    // `concat!` and `stringify!`.
+    let msg = match get_single_str_from_tts(cx, sp, tts, "compile_error!") {
+        None => return DummyResult::any(sp),
+        Some(msg) => msg
+    };
+
+    // Point at the user's own code rather than at the inside of whatever
+    // macro_rules definition expanded to this invocation.
+    let span = cx.original_span();
+    cx.span_err(span, &msg);
+
+    DummyResult::any(sp)
+}
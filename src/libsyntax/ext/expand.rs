@@ -20,6 +20,7 @@ use ext::build::AstBuilder;
 use attr;
 use attr::AttrMetaMethods;
 use codemap;
+use config;
 use codemap::{Span, Spanned, ExpnInfo, NameAndSpan, MacroBang, MacroAttribute};
 use ext::base::*;
 use feature_gate::{self, Features};
@@ -1098,6 +1099,23 @@ impl<'a> Folder for PatIdentRenamer<'a> {
 fn expand_annotatable(a: Annotatable,
                       fld: &mut MacroExpander)
                       -> SmallVector<Annotatable> {
+    // Items produced by macros have not been through `#[cfg]` stripping yet,
+    // and decorators must not see the parts of them that are configured out.
+    let a = match a {
+        Annotatable::Item(it) => {
+            if fld.cx.backtrace() == codemap::NO_EXPANSION {
+                Annotatable::Item(it)
+            } else {
+                match config::strip_unconfigured_item(&fld.cx.parse_sess.span_diagnostic,
+                                                      &fld.cx.cfg,
+                                                      it) {
+                    Some(it) => Annotatable::Item(it),
+                    None => return SmallVector::zero(),
+                }
+            }
+        }
+        a => a,
+    };
     let a = expand_item_multi_modifier(a, fld);
 
     let mut decorator_items = SmallVector::zero();
@@ -1433,6 +1451,11 @@ pub struct ExpansionConfig<'feat> {
     pub crate_name: String,
     pub features: Option<&'feat Features>,
     pub recursion_limit: usize,
+    /// How many matcher positions a single `macro_rules!` invocation may
+    /// explore before matching is abandoned.
+    pub macro_work_limit: usize,
+    /// A `FROM=TO` mapping applied to the paths `file!()` expands to.
+    pub remap_path_prefix: Option<String>,
 }
 
 macro_rules! feature_tests {
@@ -1454,6 +1477,8 @@ impl<'feat> ExpansionConfig<'feat> {
             crate_name: crate_name,
             features: None,
             recursion_limit: 64,
+            macro_work_limit: 10_000_000,
+            remap_path_prefix: None,
         }
     }
 
@@ -1476,6 +1501,7 @@ pub fn expand_crate<'feat>(parse_sess: &parse::ParseSess,
                            c: Crate) -> Crate {
     let mut cx = ExtCtxt::new(parse_sess, c.config.clone(), cfg);
     cx.use_std = std_inject::use_std(&c);
+    cx.std_crate_name = std_inject::std_crate_name(&c);
 
     let mut expander = MacroExpander::new(&mut cx);
 
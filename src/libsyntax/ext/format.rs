@@ -708,9 +708,9 @@ pub fn expand_preparsed_format_args(ecx: &mut ExtCtxt, sp: Span,
             cx.ecx.span_err(cx.args[i].span, "argument never used");
         }
     }
-    for (name, e) in &cx.names {
+    for name in &cx.name_ordering {
         if !cx.name_types.contains_key(name) {
-            cx.ecx.span_err(e.span, "named argument never used");
+            cx.ecx.span_err(cx.names.get(name).unwrap().span, "named argument never used");
         }
     }
 
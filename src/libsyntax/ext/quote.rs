@@ -26,7 +26,6 @@ use ptr::P;
 
 pub mod rt {
     use ast;
-    use codemap::Spanned;
     use ext::base::ExtCtxt;
     use parse::token;
     use parse;
@@ -36,7 +35,7 @@ pub mod rt {
     use ast::{TokenTree, Generics, Expr};
 
     pub use parse::new_parser_from_tts;
-    pub use codemap::{BytePos, Span, dummy_spanned};
+    pub use codemap::{BytePos, Span, dummy_spanned, DUMMY_SP};
 
     pub trait ToTokens {
         fn to_tokens(&self, _cx: &ExtCtxt) -> Vec<TokenTree> ;
@@ -54,7 +53,13 @@ pub mod rt {
         }
     }
 
-    impl<T: ToTokens> ToTokens for Spanned<T> {
+    impl ToTokens for ast::SpannedIdent {
+        fn to_tokens(&self, _cx: &ExtCtxt) -> Vec<TokenTree> {
+            vec![ast::TtToken(self.span, token::Ident(self.node, token::Plain))]
+        }
+    }
+
+    impl ToTokens for ast::Attribute {
         fn to_tokens(&self, cx: &ExtCtxt) -> Vec<TokenTree> {
             // FIXME: use the span?
             self.node.to_tokens(cx)
@@ -308,19 +313,40 @@ pub mod rt {
         )
     }
 
-    impl_to_tokens! { ast::Ident }
-    impl_to_tokens! { P<ast::Item> }
+    // Spliced identifiers and nodes are handed to the parser as they are
+    // rather than printed and re-parsed, so they keep their own spans and
+    // syntax contexts.
+    impl ToTokens for ast::Ident {
+        fn to_tokens(&self, _cx: &ExtCtxt) -> Vec<TokenTree> {
+            vec![ast::TtToken(DUMMY_SP, token::Ident(*self, token::Plain))]
+        }
+    }
+
+    macro_rules! impl_to_tokens_interpolated {
+        ($t:ty, $nt:ident, |$x:ident| $node:expr) => (
+            impl ToTokens for $t {
+                fn to_tokens(&self, _cx: &ExtCtxt) -> Vec<TokenTree> {
+                    let $x = self;
+                    vec![ast::TtToken(self.span, token::Interpolated(token::$nt($node)))]
+                }
+            }
+        )
+    }
+
+    impl_to_tokens_interpolated! { P<ast::Item>, NtItem, |x| x.clone() }
+    impl_to_tokens_interpolated! { P<ast::Pat>, NtPat, |x| x.clone() }
+    impl_to_tokens_interpolated! { ast::Ty, NtTy, |x| P(x.clone()) }
+    impl_to_tokens_interpolated! { P<ast::Stmt>, NtStmt, |x| x.clone() }
+    impl_to_tokens_interpolated! { ast::Stmt, NtStmt, |x| P(x.clone()) }
+    impl_to_tokens_interpolated! { P<ast::Expr>, NtExpr, |x| x.clone() }
+    impl_to_tokens_interpolated! { ast::Block, NtBlock, |x| P(x.clone()) }
+
     impl_to_tokens! { P<ast::ImplItem> }
     impl_to_tokens! { P<ast::TraitItem> }
-    impl_to_tokens! { P<ast::Pat> }
     impl_to_tokens! { ast::Arm }
     impl_to_tokens_lifetime! { &'a [P<ast::Item>] }
-    impl_to_tokens! { ast::Ty }
     impl_to_tokens_lifetime! { &'a [ast::Ty] }
     impl_to_tokens! { Generics }
-    impl_to_tokens! { P<ast::Stmt> }
-    impl_to_tokens! { P<ast::Expr> }
-    impl_to_tokens! { ast::Block }
     impl_to_tokens! { ast::Arg }
     impl_to_tokens! { ast::Attribute_ }
     impl_to_tokens_lifetime! { &'a str }
@@ -401,7 +427,7 @@ pub fn expand_quote_tokens<'cx>(cx: &'cx mut ExtCtxt,
                                 sp: Span,
                                 tts: &[ast::TokenTree])
                                 -> Box<base::MacResult+'cx> {
-    let (cx_expr, expr) = expand_tts(cx, sp, tts);
+    let (cx_expr, expr) = expand_tts(cx, sp, tts, false);
     let expanded = expand_wrapper(cx, sp, cx_expr, expr, &[&["syntax", "ext", "quote", "rt"]]);
     base::MacEager::expr(expanded)
 }
@@ -410,7 +436,7 @@ pub fn expand_quote_expr<'cx>(cx: &'cx mut ExtCtxt,
                               sp: Span,
                               tts: &[ast::TokenTree])
                               -> Box<base::MacResult+'cx> {
-    let expanded = expand_parse_call(cx, sp, "parse_expr", vec!(), tts);
+    let expanded = expand_parse_call(cx, sp, "parse_expr", vec!(), tts, false);
     base::MacEager::expr(expanded)
 }
 
@@ -418,7 +444,7 @@ pub fn expand_quote_item<'cx>(cx: &mut ExtCtxt,
                               sp: Span,
                               tts: &[ast::TokenTree])
                               -> Box<base::MacResult+'cx> {
-    let expanded = expand_parse_call(cx, sp, "parse_item", vec!(), tts);
+    let expanded = expand_parse_call(cx, sp, "parse_item", vec!(), tts, false);
     base::MacEager::expr(expanded)
 }
 
@@ -426,7 +452,44 @@ pub fn expand_quote_pat<'cx>(cx: &'cx mut ExtCtxt,
                              sp: Span,
                              tts: &[ast::TokenTree])
                              -> Box<base::MacResult+'cx> {
-    let expanded = expand_parse_call(cx, sp, "parse_pat", vec!(), tts);
+    let expanded = expand_parse_call(cx, sp, "parse_pat", vec!(), tts, false);
+    base::MacEager::expr(expanded)
+}
+
+// The `_spanned` variants take the span to give the quoted tokens as their
+// second argument, e.g. `quote_expr_spanned!(cx, sp, 1 + 2)`, instead of
+// attributing everything to the call site of the extension.
+
+pub fn expand_quote_tokens_spanned<'cx>(cx: &'cx mut ExtCtxt,
+                                        sp: Span,
+                                        tts: &[ast::TokenTree])
+                                        -> Box<base::MacResult+'cx> {
+    let (cx_expr, expr) = expand_tts(cx, sp, tts, true);
+    let expanded = expand_wrapper(cx, sp, cx_expr, expr, &[&["syntax", "ext", "quote", "rt"]]);
+    base::MacEager::expr(expanded)
+}
+
+pub fn expand_quote_expr_spanned<'cx>(cx: &'cx mut ExtCtxt,
+                                      sp: Span,
+                                      tts: &[ast::TokenTree])
+                                      -> Box<base::MacResult+'cx> {
+    let expanded = expand_parse_call(cx, sp, "parse_expr", vec!(), tts, true);
+    base::MacEager::expr(expanded)
+}
+
+pub fn expand_quote_item_spanned<'cx>(cx: &'cx mut ExtCtxt,
+                                      sp: Span,
+                                      tts: &[ast::TokenTree])
+                                      -> Box<base::MacResult+'cx> {
+    let expanded = expand_parse_call(cx, sp, "parse_item", vec!(), tts, true);
+    base::MacEager::expr(expanded)
+}
+
+pub fn expand_quote_stmt_spanned<'cx>(cx: &'cx mut ExtCtxt,
+                                      sp: Span,
+                                      tts: &[ast::TokenTree])
+                                      -> Box<base::MacResult+'cx> {
+    let expanded = expand_parse_call(cx, sp, "parse_stmt", vec!(), tts, true);
     base::MacEager::expr(expanded)
 }
 
@@ -434,7 +497,7 @@ pub fn expand_quote_arm(cx: &mut ExtCtxt,
                         sp: Span,
                         tts: &[ast::TokenTree])
                         -> Box<base::MacResult+'static> {
-    let expanded = expand_parse_call(cx, sp, "parse_arm", vec!(), tts);
+    let expanded = expand_parse_call(cx, sp, "parse_arm", vec!(), tts, false);
     base::MacEager::expr(expanded)
 }
 
@@ -442,7 +505,7 @@ pub fn expand_quote_ty(cx: &mut ExtCtxt,
                        sp: Span,
                        tts: &[ast::TokenTree])
                        -> Box<base::MacResult+'static> {
-    let expanded = expand_parse_call(cx, sp, "parse_ty", vec!(), tts);
+    let expanded = expand_parse_call(cx, sp, "parse_ty", vec!(), tts, false);
     base::MacEager::expr(expanded)
 }
 
@@ -450,7 +513,7 @@ pub fn expand_quote_stmt(cx: &mut ExtCtxt,
                          sp: Span,
                          tts: &[ast::TokenTree])
                          -> Box<base::MacResult+'static> {
-    let expanded = expand_parse_call(cx, sp, "parse_stmt", vec!(), tts);
+    let expanded = expand_parse_call(cx, sp, "parse_stmt", vec!(), tts, false);
     base::MacEager::expr(expanded)
 }
 
@@ -459,7 +522,7 @@ pub fn expand_quote_attr(cx: &mut ExtCtxt,
                          tts: &[ast::TokenTree])
                          -> Box<base::MacResult+'static> {
     let expanded = expand_parse_call(cx, sp, "parse_attribute",
-                                    vec!(cx.expr_bool(sp, true)), tts);
+                                    vec!(cx.expr_bool(sp, true)), tts, false);
 
     base::MacEager::expr(expanded)
 }
@@ -468,8 +531,8 @@ pub fn expand_quote_matcher(cx: &mut ExtCtxt,
                             sp: Span,
                             tts: &[ast::TokenTree])
                             -> Box<base::MacResult+'static> {
-    let (cx_expr, tts) = parse_arguments_to_quote(cx, tts);
-    let mut vector = mk_stmts_let(cx, sp);
+    let (cx_expr, sp_expr, tts) = parse_arguments_to_quote(cx, tts, false);
+    let mut vector = mk_stmts_let(cx, sp, sp_expr);
     vector.extend(statements_mk_tts(cx, &tts[..], true).into_iter());
     let block = cx.expr_block(
         cx.block_all(sp,
@@ -765,8 +828,8 @@ fn statements_mk_tt(cx: &ExtCtxt, tt: &ast::TokenTree, matcher: bool) -> Vec<P<a
     }
 }
 
-fn parse_arguments_to_quote(cx: &ExtCtxt, tts: &[ast::TokenTree])
-                            -> (P<ast::Expr>, Vec<ast::TokenTree>) {
+fn parse_arguments_to_quote(cx: &ExtCtxt, tts: &[ast::TokenTree], spanned: bool)
+                            -> (P<ast::Expr>, Option<P<ast::Expr>>, Vec<ast::TokenTree>) {
     // NB: It appears that the main parser loses its mind if we consider
     // $foo as a SubstNt during the main parse, so we have to re-parse
     // under quote_depth > 0. This is silly and should go away; the _guess_ is
@@ -781,14 +844,25 @@ fn parse_arguments_to_quote(cx: &ExtCtxt, tts: &[ast::TokenTree])
         p.fatal("expected token `,`");
     }
 
+    let sp_expr = if spanned {
+        let sp_expr = p.parse_expr();
+        if !p.eat(&token::Comma) {
+            p.fatal("expected token `,`");
+        }
+        Some(sp_expr)
+    } else {
+        None
+    };
+
     let tts = p.parse_all_token_trees();
     p.abort_if_errors();
 
-    (cx_expr, tts)
+    (cx_expr, sp_expr, tts)
 }
 
-fn mk_stmts_let(cx: &ExtCtxt, sp: Span) -> Vec<P<ast::Stmt>> {
-    // We also bind a single value, sp, to ext_cx.call_site()
+fn mk_stmts_let(cx: &ExtCtxt, sp: Span, sp_expr: Option<P<ast::Expr>>) -> Vec<P<ast::Stmt>> {
+    // We also bind a single value, sp, to ext_cx.call_site(), unless the
+    // caller supplied a span of its own to one of the `_spanned` variants.
     //
     // This causes every span in a token-tree quote to be attributed to the
     // call site of the extension using the quote. We can't really do much
@@ -814,10 +888,13 @@ fn mk_stmts_let(cx: &ExtCtxt, sp: Span) -> Vec<P<ast::Stmt>> {
     // of quotes, for example) but at this point it seems not likely to be
     // worth the hassle.
 
-    let e_sp = cx.expr_method_call(sp,
-                                   cx.expr_ident(sp, id_ext("ext_cx")),
-                                   id_ext("call_site"),
-                                   Vec::new());
+    let e_sp = match sp_expr {
+        Some(e) => e,
+        None => cx.expr_method_call(sp,
+                                    cx.expr_ident(sp, id_ext("ext_cx")),
+                                    id_ext("call_site"),
+                                    Vec::new()),
+    };
 
     let stmt_let_sp = cx.stmt_let(sp, false,
                                   id_ext("_sp"),
@@ -836,11 +913,11 @@ fn statements_mk_tts(cx: &ExtCtxt, tts: &[ast::TokenTree], matcher: bool) -> Vec
     ss
 }
 
-fn expand_tts(cx: &ExtCtxt, sp: Span, tts: &[ast::TokenTree])
+fn expand_tts(cx: &ExtCtxt, sp: Span, tts: &[ast::TokenTree], spanned: bool)
               -> (P<ast::Expr>, P<ast::Expr>) {
-    let (cx_expr, tts) = parse_arguments_to_quote(cx, tts);
+    let (cx_expr, sp_expr, tts) = parse_arguments_to_quote(cx, tts, spanned);
 
-    let mut vector = mk_stmts_let(cx, sp);
+    let mut vector = mk_stmts_let(cx, sp, sp_expr);
     vector.extend(statements_mk_tts(cx, &tts[..], false).into_iter());
     let block = cx.expr_block(
         cx.block_all(sp,
@@ -872,8 +949,9 @@ fn expand_parse_call(cx: &ExtCtxt,
                      sp: Span,
                      parse_method: &str,
                      arg_exprs: Vec<P<ast::Expr>> ,
-                     tts: &[ast::TokenTree]) -> P<ast::Expr> {
-    let (cx_expr, tts_expr) = expand_tts(cx, sp, tts);
+                     tts: &[ast::TokenTree],
+                     spanned: bool) -> P<ast::Expr> {
+    let (cx_expr, tts_expr) = expand_tts(cx, sp, tts, spanned);
 
     let cfg_call = || cx.expr_method_call(
         sp, cx.expr_ident(sp, id_ext("ext_cx")),
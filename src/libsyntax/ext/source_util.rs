@@ -60,7 +60,11 @@ pub fn expand_file(cx: &mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
 
     let topmost = cx.original_span_in_file();
     let loc = cx.codemap().lookup_char_pos(topmost.lo);
-    let filename = token::intern_and_get_ident(&loc.file.name);
+    let filename = match cx.ecfg.remap_path_prefix {
+        Some(ref mapping) => codemap::remap_path_prefix(&loc.file.name, mapping),
+        None => loc.file.name.clone(),
+    };
+    let filename = token::intern_and_get_ident(&filename);
     base::MacEager::expr(cx.expr_str(topmost, filename))
 }
 
@@ -192,9 +196,11 @@ pub fn expand_include_bytes(cx: &mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
 // resolve a file-system path to an absolute file-system path (if it
 // isn't already)
 fn res_rel_file(cx: &mut ExtCtxt, sp: codemap::Span, arg: &Path) -> PathBuf {
-    // NB: relative paths are resolved relative to the compilation unit
+    // NB: relative paths are resolved relative to the file the invocation
+    // was written in, which for a nested `include!` is the included file
     if !arg.is_absolute() {
-        let mut cu = PathBuf::from(&cx.codemap().span_to_filename(sp));
+        let call_site = call_site_in_file(cx, sp);
+        let mut cu = PathBuf::from(&cx.codemap().span_to_filename(call_site));
         cu.pop();
         cu.push(arg);
         cu
@@ -202,3 +208,22 @@ fn res_rel_file(cx: &mut ExtCtxt, sp: codemap::Span, arg: &Path) -> PathBuf {
         arg.to_path_buf()
     }
 }
+
+/// Follows the invocation at `sp` back through the macros whose expansion
+/// produced it, to where it appears in a source file. Expansions of
+/// `include!` are not followed: their output was written in the included
+/// file.
+fn call_site_in_file(cx: &ExtCtxt, sp: codemap::Span) -> codemap::Span {
+    let mut sp = sp;
+    loop {
+        let call_site = cx.codemap().with_expn_info(sp.expn_id, |ei| {
+            ei.and_then(|ei| {
+                if ei.callee.name == "include" { None } else { Some(ei.call_site) }
+            })
+        });
+        match call_site {
+            Some(call_site) => sp = call_site,
+            None => return sp,
+        }
+    }
+}
@@ -75,6 +75,10 @@
 //!
 //! Remaining input: ``
 //! eof: [a $( a )* a b ·]
+//!
+//! Nested repetitions with ambiguous separators can make the number of live
+//! items grow exponentially with the input, so `parse` gives up with an
+//! `Error` once it has processed more than `work_limit` items.
 
 pub use self::NamedMatch::*;
 pub use self::ParseResult::*;
@@ -97,6 +101,7 @@ use ptr::P;
 
 use std::mem;
 use std::rc::Rc;
+use std::usize;
 use std::collections::HashMap;
 use std::collections::hash_map::Entry::{Vacant, Occupied};
 
@@ -257,7 +262,7 @@ pub fn parse_or_else(sess: &ParseSess,
                      rdr: TtReader,
                      ms: Vec<TokenTree> )
                      -> HashMap<Ident, Rc<NamedMatch>> {
-    match parse(sess, cfg, rdr, &ms[..]) {
+    match parse(sess, cfg, rdr, &ms[..], usize::MAX) {
         Success(m) => m,
         Failure(sp, str) => {
             sess.span_diagnostic.span_fatal(sp, &str[..])
@@ -282,8 +287,10 @@ pub fn token_name_eq(t1 : &Token, t2 : &Token) -> bool {
 pub fn parse(sess: &ParseSess,
              cfg: ast::CrateConfig,
              mut rdr: TtReader,
-             ms: &[TokenTree])
+             ms: &[TokenTree],
+             work_limit: usize)
              -> NamedParseResult {
+    let mut work = 0;
     let mut cur_eis = Vec::new();
     cur_eis.push(initial_matcher_pos(Rc::new(ms.iter()
                                                 .cloned()
@@ -305,6 +312,13 @@ pub fn parse(sess: &ParseSess,
                 Some(ei) => ei,
             };
 
+            work += 1;
+            if work > work_limit {
+                return Error(sp, format!("macro expansion exceeds work limit while \
+                                          matching this rule (limit is {} steps)",
+                                         work_limit));
+            }
+
             // When unzipped trees end, remove them
             while ei.idx >= ei.top_elts.len() {
                 match ei.stack.pop() {
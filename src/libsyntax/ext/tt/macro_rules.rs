@@ -10,6 +10,7 @@
 
 use ast::{self, TokenTree, TtDelimited, TtSequence, TtToken};
 use codemap::{Span, DUMMY_SP};
+use diagnostic::FatalError;
 use ext::base::{ExtCtxt, MacResult, SyntaxExtension};
 use ext::base::{NormalTT, TTMacroExpander};
 use ext::tt::macro_parser::{Success, Error, Failure};
@@ -140,6 +141,7 @@ fn generic_extension<'cx>(cx: &'cx ExtCtxt,
     for (i, lhs) in lhses.iter().enumerate() { // try each arm's matchers
         match **lhs {
           MatchedNonterminal(NtTT(ref lhs_tt)) => {
+            let lhs_sp = lhs_tt.get_span();
             let lhs_tt = match **lhs_tt {
                 TtDelimited(_, ref delim) => &delim.tts[..],
                 _ => cx.span_fatal(sp, "malformed macro lhs")
@@ -175,7 +177,11 @@ fn generic_extension<'cx>(cx: &'cx ExtCtxt,
                 best_fail_spot = sp;
                 best_fail_msg = (*msg).clone();
               },
-              Error(sp, ref msg) => cx.span_fatal(sp, &msg[..])
+              Error(sp, ref msg) => {
+                cx.span_err(sp, &msg[..]);
+                cx.span_note(lhs_sp, "while matching this rule");
+                panic!(FatalError);
+              }
             }
           }
           _ => cx.bug("non-matcher found in parsed lhses")
@@ -309,7 +315,7 @@ fn check_matcher<'a, I>(cx: &mut ExtCtxt, matcher: I, follow: &Token)
                         Eof
                     },
                     // die next iteration
-                    Some(&&TtDelimited(_, ref delim)) => delim.close_token(),
+                    Some(&&TtDelimited(_, ref delim)) => delim.open_token(),
                     // else, we're at the end of the macro or sequence
                     None => follow.clone()
                 };
@@ -350,7 +356,7 @@ fn check_matcher<'a, I>(cx: &mut ExtCtxt, matcher: I, follow: &Token)
                             Some((span, tok)) => {
                                 let fol = match tokens.peek() {
                                     Some(&&TtToken(_, ref tok)) => tok.clone(),
-                                    Some(&&TtDelimited(_, ref delim)) => delim.close_token(),
+                                    Some(&&TtDelimited(_, ref delim)) => delim.open_token(),
                                     Some(_) => {
                                         cx.span_err(sp, "sequence repetition followed by \
                                                 another sequence repetition, which is not allowed");
@@ -370,7 +376,7 @@ fn check_matcher<'a, I>(cx: &mut ExtCtxt, matcher: I, follow: &Token)
                     None => {
                         let fol = match tokens.peek() {
                             Some(&&TtToken(_, ref tok)) => tok.clone(),
-                            Some(&&TtDelimited(_, ref delim)) => delim.close_token(),
+                            Some(&&TtDelimited(_, ref delim)) => delim.open_token(),
                             Some(_) => {
                                 cx.span_err(sp, "sequence repetition followed by another \
                                              sequence repetition, which is not allowed");
@@ -378,6 +384,19 @@ fn check_matcher<'a, I>(cx: &mut ExtCtxt, matcher: I, follow: &Token)
                             },
                             None => Eof
                         };
+                        // Without a separator the contents can also be
+                        // followed by their own first token, so a fragment
+                        // ending the sequence has to accept that too.
+                        if let (Some(&TtToken(_, ref first)), Some(last)) =
+                                (seq.tts.first(), seq.tts.last()) {
+                            match (first, last) {
+                                (&MatchNt(..), _) | (&SubstNt(..), _) => {}
+                                (_, &TtToken(_, MatchNt(..))) if *first != fol => {
+                                    check_matcher(cx, Some(last).into_iter(), first);
+                                }
+                                _ => {}
+                            }
+                        }
                         check_matcher(cx, seq.tts.iter(), &fol)
                     }
                 }
@@ -425,6 +444,7 @@ fn is_in_follow(_: &ExtCtxt, tok: &Token, frag: &str) -> Result<bool, String> {
             },
             "path" | "ty" => {
                 match *tok {
+                    OpenDelim(token::Brace) => Ok(true),
                     Comma | FatArrow | Colon | Eq | Gt => Ok(true),
                     Ident(i, _) if i.as_str() == "as" => Ok(true),
                     _ => Ok(false)
@@ -37,6 +37,8 @@ use visit::Visitor;
 use parse::token::{self, InternedString};
 
 use std::ascii::AsciiExt;
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
 
 // If you change this list without updating src/doc/reference.md, @cmr will be sad
 // Don't ever remove anything from this list; set them to 'Removed'.
@@ -84,6 +86,7 @@ const KNOWN_FEATURES: &'static [(&'static str, &'static str, Status)] = &[
     ("on_unimplemented", "1.0.0", Active),
     ("simd_ffi", "1.0.0", Active),
     ("allocator", "1.0.0", Active),
+    ("needs_allocator", "1.0.0", Active),
 
     ("if_let", "1.0.0", Accepted),
     ("while_let", "1.0.0", Accepted),
@@ -153,6 +156,13 @@ const KNOWN_FEATURES: &'static [(&'static str, &'static str, Status)] = &[
     // below (it has to be checked before expansion possibly makes
     // macros disappear).
     ("allow_internal_unstable", "1.0.0", Active),
+
+    // Allows `a...b` ranges, which include their upper bound.
+    ("inclusive_range_syntax", "1.0.0", Active),
+
+    // Allows `pub(crate)` items, visible throughout their crate but not
+    // exported from it.
+    ("pub_crate", "1.0.0", Active),
 ];
 // (changing above list without updating src/doc/reference.md makes @cmr sad)
 
@@ -232,6 +242,9 @@ pub const KNOWN_ATTRIBUTES: &'static [(&'static str, AttributeType)] = &[
                                       is an experimental feature")),
     ("allocator", Gated("allocator",
                         "the `#[allocator]` attribute is an experimental feature")),
+    ("needs_allocator", Gated("needs_allocator",
+                              "the `#[needs_allocator]` attribute is \
+                               an experimental feature")),
     ("rustc_variance", Gated("rustc_attrs",
                              "the `#[rustc_variance]` attribute \
                               is an experimental feature")),
@@ -259,6 +272,7 @@ pub const KNOWN_ATTRIBUTES: &'static [(&'static str, AttributeType)] = &[
     ("no_builtins", Whitelisted),
     ("no_mangle", Whitelisted),
     ("no_stack_check", Whitelisted),
+    ("no_instrument_function", Whitelisted),
     ("packed", Whitelisted),
     ("static_assert", Gated("static_assert",
                             "`#[static_assert]` is an experimental feature, and has a poor API")),
@@ -292,6 +306,9 @@ pub const KNOWN_ATTRIBUTES: &'static [(&'static str, AttributeType)] = &[
     ("no_main", CrateLevel),
     ("no_builtins", CrateLevel),
     ("recursion_limit", CrateLevel),
+    ("macro_work_limit", CrateLevel),
+    ("fn_expr_limit", CrateLevel),
+    ("windows_subsystem", CrateLevel),
 ];
 
 #[derive(PartialEq, Copy, Debug)]
@@ -331,9 +348,25 @@ pub struct Features {
     /// spans of #![feature] attrs for stable language features. for error reporting
     pub declared_stable_lang_features: Vec<Span>,
     /// #![feature] attrs for non-language (library) features
-    pub declared_lib_features: Vec<(InternedString, Span)>
+    pub declared_lib_features: Vec<(InternedString, Span)>,
+    /// #![feature] attrs for active language features, with their spans
+    pub declared_lang_features: Vec<(&'static str, Span)>,
+    /// the declared language features that some gated construct needed
+    pub used_lang_features: HashSet<&'static str>,
 }
 
+/// Language features that are only checked by later passes, through the flags
+/// in `Features`. Their uses can't be seen here, so they're never reported
+/// as unused.
+const LATE_CHECKED_FEATURES: &'static [&'static str] = &[
+    "quote",
+    "rustc_diagnostic_macros",
+    "simd_ffi",
+    "unboxed_closures",
+    "unmarked_api",
+    "visible_private_types",
+];
+
 impl Features {
     pub fn new() -> Features {
         Features {
@@ -351,9 +384,25 @@ impl Features {
             simd_ffi: false,
             unmarked_api: false,
             declared_stable_lang_features: Vec::new(),
-            declared_lib_features: Vec::new()
+            declared_lib_features: Vec::new(),
+            declared_lang_features: Vec::new(),
+            used_lang_features: HashSet::new(),
         }
     }
+
+    /// Carries over the feature uses found by an earlier gating pass, as
+    /// the code they were found in may be gone by now.
+    pub fn note_uses_from(&mut self, earlier: &Features) {
+        self.used_lang_features.extend(earlier.used_lang_features.iter().cloned());
+    }
+
+    /// The declared language features that no gated construct needed, with
+    /// the spans of their entries in `#![feature]`.
+    pub fn unused_lang_features(&self) -> Vec<(&'static str, Span)> {
+        self.declared_lang_features.iter().cloned().filter(|&(name, _)| {
+            !self.used_lang_features.contains(name) && !LATE_CHECKED_FEATURES.contains(&name)
+        }).collect()
+    }
 }
 
 struct Context<'a> {
@@ -361,19 +410,30 @@ struct Context<'a> {
     span_handler: &'a SpanHandler,
     cm: &'a CodeMap,
     do_warnings: bool,
+    /// The declared features that a gate was checked for.
+    used_features: RefCell<HashSet<&'static str>>,
+    /// Whether gates only record the features they're checked for, without
+    /// reporting anything.
+    record_only: Cell<bool>,
+    /// Attributes registered by plugins.
+    plugin_attributes: &'a [(String, AttributeType)],
+    /// The attributes of the crate root, where crate-level attributes go.
+    crate_attrs: &'a [ast::Attribute],
 }
 
 impl<'a> Context<'a> {
     fn gate_feature(&self, feature: &str, span: Span, explain: &str) {
         let has_feature = self.has_feature(feature);
         debug!("gate_feature(feature = {:?}, span = {:?}); has? {}", feature, span, has_feature);
-        if !has_feature {
+        self.note_use(feature);
+        if !has_feature && !self.record_only.get() {
             emit_feature_err(self.span_handler, feature, span, explain);
         }
     }
 
     fn warn_feature(&self, feature: &str, span: Span, explain: &str) {
-        if !self.has_feature(feature) && self.do_warnings {
+        self.note_use(feature);
+        if !self.has_feature(feature) && self.do_warnings && !self.record_only.get() {
             emit_feature_warn(self.span_handler, feature, span, explain);
         }
     }
@@ -381,6 +441,20 @@ impl<'a> Context<'a> {
         self.features.iter().any(|&n| n == feature)
     }
 
+    fn note_use(&self, feature: &str) {
+        if let Some(&name) = self.features.iter().find(|&&n| n == feature) {
+            self.used_features.borrow_mut().insert(name);
+        }
+    }
+
+    /// Runs `f` with gates only recording the features they need.
+    fn recording_only<F: FnOnce()>(&self, f: F) {
+        let old = self.record_only.get();
+        self.record_only.set(true);
+        f();
+        self.record_only.set(old);
+    }
+
     fn check_attribute(&self, attr: &ast::Attribute) {
         debug!("check_attribute(attr = {:?})", attr);
         let name = &*attr.name();
@@ -393,6 +467,18 @@ impl<'a> Context<'a> {
                 return;
             }
         }
+        for &(ref n, ty) in self.plugin_attributes {
+            if *n == name {
+                let at_crate_root = self.crate_attrs.iter().any(|a| a.node.id == attr.node.id);
+                if ty == CrateLevel && !at_crate_root && !self.record_only.get() {
+                    self.span_handler.span_err(attr.span,
+                        &format!("`{0}` is a crate-level attribute, and can only be used \
+                                  as `#![{0}]` at the crate root", name));
+                }
+                debug!("check_attribute: {:?} is registered by a plugin, {:?}", name, ty);
+                return;
+            }
+        }
         if name.starts_with("rustc_") {
             self.gate_feature("rustc_attrs", attr.span,
                               "unless otherwise specified, attributes \
@@ -493,14 +579,30 @@ impl<'a> PostExpansionVisitor<'a> {
     fn gate_feature(&self, feature: &str, span: Span, explain: &str) {
         if !self.context.cm.span_allows_unstable(span) {
             self.context.gate_feature(feature, span, explain)
+        } else {
+            // Still a use of the feature, by the crate the macro is from.
+            self.context.recording_only(|| self.context.gate_feature(feature, span, explain))
         }
     }
+
+    fn gate_pub_crate(&self, span: Span) {
+        self.gate_feature("pub_crate", span,
+                          "`pub(crate)` visibility is experimental");
+    }
 }
 
 impl<'a, 'v> Visitor<'v> for PostExpansionVisitor<'a> {
+    fn visit_mac(&mut self, mac: &ast::Mac) {
+        // Only reached when noting the uses in code that hasn't been expanded
+        // yet, see `check_crate_macros`.
+        visit::walk_mac(self, mac)
+    }
+
     fn visit_attribute(&mut self, attr: &ast::Attribute) {
         if !self.context.cm.span_allows_unstable(attr.span) {
             self.context.check_attribute(attr);
+        } else {
+            self.context.recording_only(|| self.context.check_attribute(attr))
         }
     }
 
@@ -512,6 +614,9 @@ impl<'a, 'v> Visitor<'v> for PostExpansionVisitor<'a> {
     }
 
     fn visit_item(&mut self, i: &ast::Item) {
+        if i.vis == ast::PubCrate {
+            self.gate_pub_crate(i.span);
+        }
         match i.node {
             ast::ItemExternCrate(_) => {
                 if attr::contains_name(&i.attrs[..], "macro_reexport") {
@@ -620,10 +725,27 @@ impl<'a, 'v> Visitor<'v> for PostExpansionVisitor<'a> {
             self.gate_feature("link_llvm_intrinsics", i.span,
                               "linking to LLVM intrinsics is experimental");
         }
+        if i.vis == ast::PubCrate {
+            self.gate_pub_crate(i.span);
+        }
 
         visit::walk_foreign_item(self, i)
     }
 
+    fn visit_impl_item(&mut self, ii: &ast::ImplItem) {
+        if ii.vis == ast::PubCrate {
+            self.gate_pub_crate(ii.span);
+        }
+        visit::walk_impl_item(self, ii)
+    }
+
+    fn visit_struct_field(&mut self, s: &ast::StructField) {
+        if let ast::UnnamedField(ast::PubCrate) = s.node.kind {
+            self.gate_pub_crate(s.span);
+        }
+        visit::walk_struct_field(self, s)
+    }
+
     fn visit_ty(&mut self, t: &ast::Ty) {
         match t.node {
             ast::TyPath(None, ref p) => {
@@ -661,6 +783,11 @@ impl<'a, 'v> Visitor<'v> for PostExpansionVisitor<'a> {
                                   "box expression syntax is experimental; \
                                    you can call `Box::new` instead.");
             }
+            ast::ExprRange(_, _, ast::RangeLimits::Closed) => {
+                self.gate_feature("inclusive_range_syntax",
+                                  e.span,
+                                  "inclusive range syntax is experimental");
+            }
             ast::ExprLit(ref lit) => {
                 match lit.node {
                     ast::LitInt(_, ty) => {
@@ -723,6 +850,7 @@ impl<'a, 'v> Visitor<'v> for PostExpansionVisitor<'a> {
 }
 
 fn check_crate_inner<F>(cm: &CodeMap, span_handler: &SpanHandler, krate: &ast::Crate,
+                        plugin_attributes: &[(String, AttributeType)],
                         do_warnings: bool,
                         check: F)
                        -> Features
@@ -733,10 +861,15 @@ fn check_crate_inner<F>(cm: &CodeMap, span_handler: &SpanHandler, krate: &ast::C
         span_handler: span_handler,
         do_warnings: do_warnings,
         cm: cm,
+        used_features: RefCell::new(HashSet::new()),
+        record_only: Cell::new(false),
+        plugin_attributes: plugin_attributes,
+        crate_attrs: &krate.attrs,
     };
 
     let mut accepted_features = Vec::new();
     let mut unknown_features = Vec::new();
+    let mut active_features = Vec::new();
 
     for attr in &krate.attrs {
         if !attr.check_name("feature") {
@@ -763,9 +896,11 @@ fn check_crate_inner<F>(cm: &CodeMap, span_handler: &SpanHandler, krate: &ast::C
                                         .find(|& &(n, _, _)| name == n) {
                         Some(&(name, _, Active)) => {
                             cx.features.push(name);
+                            active_features.push((name, mi.span));
                         }
                         Some(&(name, _, Deprecated)) => {
                             cx.features.push(name);
+                            active_features.push((name, mi.span));
                             span_handler.span_warn(
                                 mi.span,
                                 "feature is deprecated and will only be available \
@@ -806,20 +941,31 @@ fn check_crate_inner<F>(cm: &CodeMap, span_handler: &SpanHandler, krate: &ast::C
         simd_ffi: cx.has_feature("simd_ffi"),
         unmarked_api: cx.has_feature("unmarked_api"),
         declared_stable_lang_features: accepted_features,
-        declared_lib_features: unknown_features
+        declared_lib_features: unknown_features,
+        declared_lang_features: active_features,
+        used_lang_features: cx.used_features.into_inner(),
     }
 }
 
 pub fn check_crate_macros(cm: &CodeMap, span_handler: &SpanHandler, krate: &ast::Crate)
 -> Features {
-    check_crate_inner(cm, span_handler, krate, true,
-                      |ctx, krate| visit::walk_crate(&mut MacroVisitor { context: ctx }, krate))
+    check_crate_inner(cm, span_handler, krate, &[], true, |ctx, krate| {
+        let ctx = &*ctx;
+        visit::walk_crate(&mut MacroVisitor { context: ctx }, krate);
+        // This runs before `#[cfg]` stripping, so note the features used by
+        // code that's configured away too: it still needs them under other
+        // configurations, and shouldn't make them look unused.
+        ctx.recording_only(|| {
+            visit::walk_crate(&mut PostExpansionVisitor { context: ctx }, krate)
+        });
+    })
 }
 
 pub fn check_crate(cm: &CodeMap, span_handler: &SpanHandler, krate: &ast::Crate,
+                   plugin_attributes: &[(String, AttributeType)],
                    do_warnings: bool) -> Features
 {
-    check_crate_inner(cm, span_handler, krate, do_warnings,
+    check_crate_inner(cm, span_handler, krate, plugin_attributes, do_warnings,
                       |ctx, krate| visit::walk_crate(&mut PostExpansionVisitor { context: ctx },
                                                      krate))
 }
@@ -1255,9 +1255,10 @@ pub fn noop_fold_expr<T: Folder>(Expr {id, node, span}: Expr, folder: &mut T) ->
             ExprIndex(el, er) => {
                 ExprIndex(folder.fold_expr(el), folder.fold_expr(er))
             }
-            ExprRange(e1, e2) => {
+            ExprRange(e1, e2, limits) => {
                 ExprRange(e1.map(|x| folder.fold_expr(x)),
-                          e2.map(|x| folder.fold_expr(x)))
+                          e2.map(|x| folder.fold_expr(x)),
+                          limits)
             }
             ExprPath(qself, path) => {
                 let qself = qself.map(|QSelf { ty, position }| {
@@ -99,6 +99,7 @@ pub mod ext {
     pub mod base;
     pub mod build;
     pub mod cfg;
+    pub mod compile_error;
     pub mod concat;
     pub mod concat_idents;
     pub mod deriving;
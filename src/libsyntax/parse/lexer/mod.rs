@@ -511,7 +511,15 @@ impl<'a> StringReader<'a> {
             },
             c if is_whitespace(Some(c)) => {
                 let start_bpos = self.last_pos;
-                while is_whitespace(self.curr) { self.bump(); }
+                while is_whitespace(self.curr) {
+                    // `\r` only ever starts a `\r\n` line ending
+                    if self.curr_is('\r') && !self.nextch_is('\n') {
+                        let last_pos = self.last_pos;
+                        self.err_span_(last_pos, self.pos,
+                                       "bare CR not allowed outside of a line ending");
+                    }
+                    self.bump();
+                }
                 let c = Some(TokenAndSpan {
                     tok: token::Whitespace,
                     sp: codemap::mk_sp(start_bpos, self.last_pos)
@@ -1527,6 +1535,29 @@ mod test {
         token::Ident(str_to_ident(id), style)
     }
 
+    #[test] fn crlf_line_starts () {
+        let span_handler = mk_sh();
+        let mut string_reader = setup(&span_handler, "a \u{20ac}\r\nb".to_string());
+        let mut tok = string_reader.next_token();
+        while tok.tok != token::Ident(str_to_ident("b"), token::Plain) {
+            tok = string_reader.next_token();
+        }
+        let loc = span_handler.cm.lookup_char_pos(tok.sp.lo);
+        assert_eq!(loc.line, 2);
+        assert_eq!(loc.col.to_usize(), 0);
+        assert_eq!(loc.file.get_line(0), Some("a \u{20ac}".to_string()));
+        assert_eq!(span_handler.handler.err_count(), 0);
+    }
+
+    #[test] fn bare_cr_is_an_error () {
+        let span_handler = mk_sh();
+        check_tokenization(setup(&span_handler, "a\rb".to_string()),
+                           vec![mk_ident("a", token::Plain),
+                                token::Whitespace,
+                                mk_ident("b", token::Plain)]);
+        assert_eq!(span_handler.handler.err_count(), 1);
+    }
+
     #[test] fn doublecolonparsing () {
         check_tokenization(setup(&mk_sh(), "a b".to_string()),
                            vec![mk_ident("a", token::Plain),
@@ -13,7 +13,7 @@ pub use self::PathParsingMode::*;
 use abi;
 use ast::{BareFnTy};
 use ast::{RegionTyParamBound, TraitTyParamBound, TraitBoundModifier};
-use ast::{Public, Unsafety};
+use ast::{Public, PubCrate, Unsafety};
 use ast::{Mod, BiAdd, Arg, Arm, Attribute, BindByRef, BindByValue};
 use ast::{BiBitAnd, BiBitOr, BiBitXor, BiRem, BiLt, BiGt, Block};
 use ast::{BlockCheckMode, CaptureByRef, CaptureByValue, CaptureClause};
@@ -24,7 +24,7 @@ use ast::{Expr, Expr_, ExprAddrOf, ExprMatch, ExprAgain};
 use ast::{ExprAssign, ExprAssignOp, ExprBinary, ExprBlock, ExprBox};
 use ast::{ExprBreak, ExprCall, ExprCast};
 use ast::{ExprField, ExprTupField, ExprClosure, ExprIf, ExprIfLet, ExprIndex};
-use ast::{ExprLit, ExprLoop, ExprMac, ExprRange};
+use ast::{ExprLit, ExprLoop, ExprMac, ExprRange, RangeLimits};
 use ast::{ExprMethodCall, ExprParen, ExprPath};
 use ast::{ExprRepeat, ExprRet, ExprStruct, ExprTup, ExprUnary};
 use ast::{ExprVec, ExprWhile, ExprWhileLet, ExprForLoop, Field, FnDecl};
@@ -266,8 +266,9 @@ pub struct Parser<'a> {
     pub mod_path_stack: Vec<InternedString>,
     /// Stack of spans of open delimiters. Used for error message.
     pub open_braces: Vec<Span>,
-    /// Flag if this parser "owns" the directory that it is currently parsing
-    /// in. This will affect how nested files are looked up.
+    /// Flag if the file this parser is reading "owns" the directory it lives
+    /// in, i.e. it is a crate root or a `mod.rs`. This will affect how nested
+    /// files are looked up.
     pub owns_directory: bool,
     /// Name of the root module this parser originated from. If `None`, then the
     /// name is not known. This does not change while the parser is descending
@@ -589,12 +590,17 @@ impl<'a> Parser<'a> {
         }
     }
 
-    /// Signal an error if the current token is a reserved keyword
+    /// Signal an error if the current token is a reserved keyword. The
+    /// token is left in place, so parsing can carry on treating it as an
+    /// identifier.
     pub fn check_reserved_keywords(&mut self) {
         if self.token.is_reserved_keyword() {
+            let span = self.span;
             let token_str = self.this_token_to_string();
-            self.fatal(&format!("`{}` is a reserved keyword",
-                               token_str))
+            self.span_err(span, &format!("`{}` is a reserved keyword", token_str));
+            self.fileline_help(span, &format!("reserved keywords cannot be used as names; \
+                                               rename this to something like `{}_`",
+                                              token_str));
         }
     }
 
@@ -925,6 +931,14 @@ impl<'a> Parser<'a> {
         self.fileline_help(sp, help);
         panic!(diagnostic::FatalError);
     }
+    /// Signal a fatal error for `syntax` that is recognized, and reserved for
+    /// `feature`, but not implemented yet.
+    pub fn span_fatal_unimplemented(&self, sp: Span, syntax: &str, feature: &str) -> ! {
+        self.span_fatal_help(sp,
+                             &format!("{} is reserved syntax that is not implemented yet",
+                                      syntax),
+                             &format!("this syntax is reserved for the `{}` feature", feature))
+    }
     pub fn span_note(&self, sp: Span, m: &str) {
         self.sess.span_diagnostic.span_note(sp, m)
     }
@@ -1274,11 +1288,8 @@ impl<'a> Parser<'a> {
             self.parse_ty_bare_fn(Vec::new())
         } else if self.eat_keyword_noexpect(keywords::Typeof) {
             // TYPEOF
-            // In order to not be ambiguous, the type must be surrounded by parens.
-            self.expect(&token::OpenDelim(token::Paren));
-            let e = self.parse_expr();
-            self.expect(&token::CloseDelim(token::Paren));
-            TyTypeof(e)
+            let last_span = self.last_span;
+            self.span_fatal_unimplemented(last_span, "`typeof`", "typeof")
         } else if self.eat_lt() {
             // QUALIFIED PATH `<TYPE as TRAIT_REF>::item`
             let self_type = self.parse_ty_sum();
@@ -1844,9 +1855,10 @@ impl<'a> Parser<'a> {
 
     pub fn mk_range(&mut self,
                     start: Option<P<Expr>>,
-                    end: Option<P<Expr>>)
+                    end: Option<P<Expr>>,
+                    limits: RangeLimits)
                     -> ast::Expr_ {
-        ExprRange(start, end)
+        ExprRange(start, end, limits)
     }
 
     pub fn mk_field(&mut self, expr: P<Expr>, ident: ast::SpannedIdent) -> ast::Expr_ {
@@ -2719,7 +2731,7 @@ impl<'a> Parser<'a> {
                 None
             };
             let hi = self.span.hi;
-            let ex = self.mk_range(None, opt_end);
+            let ex = self.mk_range(None, opt_end, RangeLimits::HalfOpen);
             self.mk_expr(lo, hi, ex)
           }
           _ => {
@@ -2771,7 +2783,24 @@ impl<'a> Parser<'a> {
 
             let lo = lhs.span.lo;
             let hi = self.span.hi;
-            let range = self.mk_range(Some(lhs), opt_end);
+            let range = self.mk_range(Some(lhs), opt_end, RangeLimits::HalfOpen);
+            return self.mk_expr(lo, hi, range);
+          }
+          // An inclusive range expression, `expr...expr`.
+          token::DotDotDot => {
+            self.bump();
+
+            if !self.is_at_start_of_range_notation_rhs() {
+                let span = self.span;
+                self.span_err(span, "inclusive range with no end");
+                let range = self.mk_range(Some(lhs), None, RangeLimits::HalfOpen);
+                return self.mk_expr(op_span.lo, span.hi, range);
+            }
+            let end = self.parse_binops();
+
+            let lo = lhs.span.lo;
+            let hi = self.span.hi;
+            let range = self.mk_range(Some(lhs), Some(end), RangeLimits::Closed);
             return self.mk_expr(lo, hi, range);
           }
 
@@ -3819,6 +3848,7 @@ impl<'a> Parser<'a> {
         let ident = self.parse_ident();
 
         let bounds = self.parse_colon_then_ty_param_bounds(BoundParsingMode::Modified);
+        self.check_plus_after_fn_bound_output(ident, &bounds);
 
         let default = if self.check(&token::Eq) {
             self.bump();
@@ -3836,6 +3866,49 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// In `T: Fn() -> R + Send`, the `+ Send` could as well belong to the
+    /// return type (a trait object `R + Send`) as be a second bound on `T`.
+    /// `where` clauses resolve this in favour of the bound, but in a type
+    /// parameter list the reading is too easily mistaken, so reject it and
+    /// suggest both spellings.
+    fn check_plus_after_fn_bound_output(&self, ident: Ident, bounds: &[TyParamBound]) {
+        for (i, bound) in bounds.iter().enumerate() {
+            let rest = &bounds[i + 1..];
+            let output = match *bound {
+                TraitTyParamBound(ref poly_trait_ref, _) if !rest.is_empty() => {
+                    match poly_trait_ref.trait_ref.path.segments.last() {
+                        Some(&ast::PathSegment {
+                            parameters: ast::ParenthesizedParameters(ref data), ..
+                        }) => data.output.as_ref(),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+            let output = match output {
+                Some(output) => output,
+                None => continue,
+            };
+
+            let hi = match rest[rest.len() - 1] {
+                TraitTyParamBound(ref poly_trait_ref, _) => poly_trait_ref.span.hi,
+                RegionTyParamBound(ref lifetime) => lifetime.span.hi,
+            };
+            let sp = mk_sp(output.span.lo, hi);
+            self.span_err(sp, "ambiguous `+` after the return type of a parenthesized bound");
+            self.fileline_help(sp, &format!("to return a trait object, parenthesize the \
+                                             return type: `-> ({} +{})`",
+                                            pprust::ty_to_string(output),
+                                            pprust::bounds_to_string(rest)));
+            self.fileline_help(sp, &format!("to bound `{}` by each of these, move them to \
+                                             a `where` clause: `where {}:{}`",
+                                            token::get_ident(ident),
+                                            token::get_ident(ident),
+                                            pprust::bounds_to_string(bounds)));
+            return;
+        }
+    }
+
     /// Parse a set of optional generic type parameter declarations. Where
     /// clauses are not parsed here, and must be added later via
     /// `parse_where_clause()`.
@@ -4388,7 +4461,7 @@ impl<'a> Parser<'a> {
 
     fn complain_if_pub_macro(&mut self, visa: Visibility, span: Span) {
         match visa {
-            Public => {
+            Public | PubCrate => {
                 self.span_err(span, "can't qualify macro invocation with `pub`");
                 self.fileline_help(span, "try adjusting the macro to put `pub` inside \
                                       the invocation");
@@ -4741,15 +4814,36 @@ impl<'a> Parser<'a> {
 
     /// Parse visibility: PUB, PRIV, or nothing
     fn parse_visibility(&mut self) -> Visibility {
-        if self.eat_keyword(keywords::Pub) { Public }
-        else { Inherited }
+        if !self.eat_keyword(keywords::Pub) {
+            return Inherited
+        }
+        if self.check(&token::OpenDelim(token::Paren)) &&
+           self.look_ahead(1, |t| t.is_keyword(keywords::Crate)) &&
+           self.look_ahead(2, |t| *t == token::CloseDelim(token::Paren)) {
+            // `pub(crate)`
+            self.bump();
+            self.bump();
+            self.bump();
+            PubCrate
+        } else {
+            Public
+        }
     }
 
     /// Given a termination token, parse all of the items in a module
     fn parse_mod_items(&mut self, term: &token::Token, inner_lo: BytePos) -> Mod {
         let mut items = vec![];
-        while let Some(item) = self.parse_item() {
-            items.push(item);
+        loop {
+            if let Some(item) = self.parse_item() {
+                items.push(item);
+            } else if self.token.is_reserved_keyword() {
+                // Report a reserved keyword where an item is expected, and
+                // carry on with the rest of the item.
+                self.check_reserved_keywords();
+                self.bump();
+            } else {
+                break;
+            }
         }
 
         if !self.eat(term) {
@@ -4790,11 +4884,8 @@ impl<'a> Parser<'a> {
             self.push_mod_path(id, outer_attrs);
             self.expect(&token::OpenDelim(token::Brace));
             let mod_inner_lo = self.span.lo;
-            let old_owns_directory = self.owns_directory;
-            self.owns_directory = true;
             let attrs = self.parse_inner_attributes();
             let m = self.parse_mod_items(&token::CloseDelim(token::Brace), mod_inner_lo);
-            self.owns_directory = old_owns_directory;
             self.pop_mod_path();
             (id, ItemMod(m), Some(attrs))
         }
@@ -4820,33 +4911,43 @@ impl<'a> Parser<'a> {
                     outer_attrs: &[ast::Attribute],
                     id_sp: Span)
                     -> (ast::Item_, Vec<ast::Attribute> ) {
-        let mut prefix = PathBuf::from(&self.sess.span_diagnostic.cm
-                                            .span_to_filename(self.span));
-        prefix.pop();
-        let mut dir_path = prefix;
+        let mut dir_path = PathBuf::from(&self.sess.span_diagnostic.cm
+                                              .span_to_filename(self.span));
+        dir_path.pop();
+        // A file that doesn't own its directory keeps the children of its
+        // inline modules in a directory named after itself, so that
+        // `mod bar { mod foo; }` in `a.rs` loads `a/bar/foo.rs`.
+        if !self.owns_directory && !self.mod_path_stack.is_empty() {
+            if let Some(ref name) = self.root_module_name {
+                dir_path.push(name);
+            }
+        }
         for part in &self.mod_path_stack {
             dir_path.push(&**part);
         }
         let mod_string = token::get_ident(id);
         let (file_path, owns_directory) = match ::attr::first_attr_value_str_by_name(
                 outer_attrs, "path") {
-            Some(d) => (dir_path.join(&*d), true),
+            Some(d) => {
+                let path = dir_path.join(&*d);
+                if path.is_dir() {
+                    // A directory stands for the `mod.rs` inside it.
+                    (path.join("mod.rs"), true)
+                } else {
+                    (path, true)
+                }
+            }
             None => {
                 let mod_name = mod_string.to_string();
-                let default_path_str = format!("{}.rs", mod_name);
-                let secondary_path_str = format!("{}/mod.rs", mod_name);
-                let default_path = dir_path.join(&default_path_str[..]);
-                let secondary_path = dir_path.join(&secondary_path_str[..]);
+                let default_path = dir_path.join(&format!("{}.rs", mod_name));
+                let secondary_path = dir_path.join(&mod_name).join("mod.rs");
                 let default_exists = default_path.exists();
                 let secondary_exists = secondary_path.exists();
 
-                if !self.owns_directory {
+                if !self.owns_directory && self.mod_path_stack.is_empty() {
                     self.span_err(id_sp,
                                   "cannot declare a new module at this location");
-                    let this_module = match self.mod_path_stack.last() {
-                        Some(name) => name.to_string(),
-                        None => self.root_module_name.as_ref().unwrap().clone(),
-                    };
+                    let this_module = self.root_module_name.as_ref().unwrap().clone();
                     self.span_note(id_sp,
                                    &format!("maybe move this module `{0}` \
                                             to its own directory via \
@@ -4866,14 +4967,17 @@ impl<'a> Parser<'a> {
                     (true, false) => (default_path, false),
                     (false, true) => (secondary_path, true),
                     (false, false) => {
-                        self.span_fatal_help(id_sp,
-                                             &format!("file not found for module `{}`",
-                                                     mod_name),
-                                             &format!("name the file either {} or {} inside \
-                                                     the directory {:?}",
-                                                     default_path_str,
-                                                     secondary_path_str,
-                                                     dir_path.display()));
+                        self.span_err(id_sp,
+                                      &format!("file not found for module `{}`", mod_name));
+                        self.fileline_help(id_sp,
+                                           &format!("tried {} and {}",
+                                                    default_path.display(),
+                                                    secondary_path.display()));
+                        self.fileline_help(id_sp,
+                                           &format!("add `#[path = \"...\"]` to load `{}` \
+                                                     from a different file or directory",
+                                                    mod_name));
+                        panic!(diagnostic::FatalError);
                     }
                     (true, true) => {
                         self.span_fatal_help(
@@ -4881,8 +4985,8 @@ impl<'a> Parser<'a> {
                             &format!("file for module `{}` found at both {} \
                                      and {}",
                                     mod_name,
-                                    default_path_str,
-                                    secondary_path_str),
+                                    default_path.display(),
+                                    secondary_path.display()),
                             "delete or rename one of them to remove the ambiguity");
                     }
                 }
@@ -5281,7 +5385,9 @@ impl<'a> Parser<'a> {
                                     token_str));
         }
 
-        if self.eat_keyword_noexpect(keywords::Virtual) {
+        if self.token.is_keyword(keywords::Virtual) &&
+            self.look_ahead(1, |t| t.is_keyword(keywords::Struct)) {
+            self.bump();
             let span = self.span;
             self.span_err(span, "`virtual` structs have been removed from the language");
         }
@@ -5301,6 +5407,10 @@ impl<'a> Parser<'a> {
         }
         if self.eat_keyword(keywords::Const) {
             // CONST ITEM
+            if self.token.is_keyword(keywords::Fn) {
+                let span = mk_sp(self.last_span.lo, self.span.hi);
+                self.span_fatal_unimplemented(span, "`const fn`", "const_fn");
+            }
             if self.eat_keyword(keywords::Mut) {
                 let last_span = self.last_span;
                 self.span_err(last_span, "const globals cannot be mutable");
@@ -5551,7 +5661,7 @@ impl<'a> Parser<'a> {
         // FAILURE TO PARSE ITEM
         match visibility {
             Inherited => {}
-            Public => {
+            Public | PubCrate => {
                 let last_span = self.last_span;
                 self.span_fatal(last_span, "unmatched visibility `pub`");
             }
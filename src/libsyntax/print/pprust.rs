@@ -460,6 +460,7 @@ pub mod with_hygiene {
 pub fn visibility_qualified(vis: ast::Visibility, s: &str) -> String {
     match vis {
         ast::Public => format!("pub {}", s),
+        ast::PubCrate => format!("pub(crate) {}", s),
         ast::Inherited => s.to_string()
     }
 }
@@ -1091,6 +1092,7 @@ impl<'a> State<'a> {
     pub fn print_visibility(&mut self, vis: ast::Visibility) -> io::Result<()> {
         match vis {
             ast::Public => self.word_nbsp("pub"),
+            ast::PubCrate => self.word_nbsp("pub(crate)"),
             ast::Inherited => Ok(())
         }
     }
@@ -1851,11 +1853,15 @@ impl<'a> State<'a> {
                 try!(self.print_expr(&**index));
                 try!(word(&mut self.s, "]"));
             }
-            ast::ExprRange(ref start, ref end) => {
+            ast::ExprRange(ref start, ref end, limits) => {
                 if let &Some(ref e) = start {
                     try!(self.print_expr(&**e));
                 }
-                try!(word(&mut self.s, ".."));
+                if limits == ast::RangeLimits::HalfOpen {
+                    try!(word(&mut self.s, ".."));
+                } else {
+                    try!(word(&mut self.s, "..."));
+                }
                 if let &Some(ref e) = end {
                     try!(self.print_expr(&**e));
                 }
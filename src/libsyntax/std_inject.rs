@@ -41,6 +41,23 @@ pub fn use_std(krate: &ast::Crate) -> bool {
     !attr::contains_name(&krate.attrs, "no_std")
 }
 
+/// The name the standard library can be referred to by from the crate root:
+/// `std` if it is injected, otherwise the name of an explicit (and possibly
+/// renamed) `extern crate std`, if there is one.
+pub fn std_crate_name(krate: &ast::Crate) -> Option<ast::Ident> {
+    if use_std(krate) {
+        return Some(token::str_to_ident("std"));
+    }
+    let std = token::intern("std");
+    krate.module.items.iter().filter_map(|item| {
+        match item.node {
+            ast::ItemExternCrate(Some(name)) if name == std => Some(item.ident),
+            ast::ItemExternCrate(None) if item.ident.name == std => Some(item.ident),
+            _ => None
+        }
+    }).next()
+}
+
 fn no_prelude(attrs: &[ast::Attribute]) -> bool {
     attr::contains_name(attrs, "no_implicit_prelude")
 }
@@ -173,8 +173,8 @@ impl<'a> fold::Folder for TestHarnessGenerator<'a> {
         let tests = mem::replace(&mut self.tests, tests);
         let tested_submods = mem::replace(&mut self.tested_submods, tested_submods);
 
-        // Remove any #[main] from the AST so it doesn't clash with
-        // the one we're going to add. Only if compiling an executable.
+        // Remove any #[main] or #[start] from the AST so it doesn't clash
+        // with (or take precedence over) the entry point we're going to add.
 
         mod_folded.items = mem::replace(&mut mod_folded.items, vec![]).move_map(|item| {
             item.map(|ast::Item {id, ident, attrs, node, vis, span}| {
@@ -182,7 +182,7 @@ impl<'a> fold::Folder for TestHarnessGenerator<'a> {
                     id: id,
                     ident: ident,
                     attrs: attrs.into_iter().filter_map(|attr| {
-                        if !attr.check_name("main") {
+                        if !attr.check_name("main") && !attr.check_name("start") {
                             Some(attr)
                         } else {
                             None
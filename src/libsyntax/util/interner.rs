@@ -139,6 +139,13 @@ impl Deref for RcStr {
     fn deref(&self) -> &str { &self.string[..] }
 }
 
+/// The state of a `StrInterner` at some point, which it can later be
+/// rolled back to.
+#[derive(Copy, Clone, Debug)]
+pub struct InternerSnapshot {
+    len: usize,
+}
+
 /// A StrInterner differs from Interner<String> in that it accepts
 /// &str rather than RcStr, resulting in less allocation.
 pub struct StrInterner {
@@ -226,6 +233,28 @@ impl StrInterner {
         *self.map.borrow_mut() = other.map.into_inner();
         *self.vect.borrow_mut() = other.vect.into_inner();
     }
+
+    /// Records the current state of the interner, for use with `rollback`.
+    pub fn snapshot(&self) -> InternerSnapshot {
+        InternerSnapshot { len: self.len() }
+    }
+
+    /// Forgets every name interned or gensym'd since `snapshot` was taken,
+    /// e.g. after speculative parsing. Names handed out in the meantime
+    /// must not be used afterwards.
+    pub fn rollback(&self, snapshot: InternerSnapshot) {
+        let mut vect = self.vect.borrow_mut();
+        assert!(snapshot.len <= vect.len(),
+                "interner rolled back to a snapshot taken before a reset");
+        let mut map = self.map.borrow_mut();
+        for (i, val) in vect[snapshot.len..].iter().enumerate() {
+            let idx = Name((snapshot.len + i) as u32);
+            if map.get(val) == Some(&idx) {
+                map.remove(val);
+            }
+        }
+        vect.truncate(snapshot.len);
+    }
 }
 
 #[cfg(test)]
@@ -307,4 +336,21 @@ mod tests {
         assert_eq!(i.get(Name(3)), RcStr::new("zebra"));
         assert_eq!(i.get(Name(4)), RcStr::new("dog"));
     }
+
+    #[test]
+    fn string_interner_rollback() {
+        let i : StrInterner = StrInterner::new();
+        assert_eq!(i.intern("dog"), Name(0));
+        let snapshot = i.snapshot();
+        assert_eq!(i.intern("cat"), Name(1));
+        assert_eq!(i.gensym("dog"), Name(2));
+        assert_eq!(i.gensym_copy(Name(1)), Name(3));
+        i.rollback(snapshot);
+        assert_eq!(i.len(), 1);
+        assert_eq!(i.find("cat"), None);
+        assert_eq!(i.intern("dog"), Name(0));
+        // names are handed out again from where the snapshot was taken
+        assert_eq!(i.intern("zebra"), Name(1));
+        assert_eq!(i.gensym("cat"), Name(2));
+    }
 }
@@ -11,14 +11,20 @@
 use self::SmallVectorRepr::*;
 use self::IntoIterRepr::*;
 
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::iter::{IntoIterator, FromIterator};
 use std::mem;
+use std::ptr;
 use std::slice;
 use std::vec;
 
 use fold::MoveMap;
+use serialize::{Encodable, Decodable, Encoder, Decoder};
 
-/// A vector type optimized for cases where the size is almost always 0 or 1
+/// A vector type optimized for cases where the size is almost always 0, 1
+/// or 2. Up to two elements are stored inline; the vector only allocates
+/// (spills) once a third element is added.
 pub struct SmallVector<T> {
     repr: SmallVectorRepr<T>,
 }
@@ -26,9 +32,19 @@ pub struct SmallVector<T> {
 enum SmallVectorRepr<T> {
     Zero,
     One(T),
+    Two([T; 2]),
     Many(Vec<T>),
 }
 
+/// Moves both elements out of an inline pair.
+fn split_pair<T>(pair: [T; 2]) -> (T, T) {
+    unsafe {
+        let parts = (ptr::read(&pair[0]), ptr::read(&pair[1]));
+        mem::forget(pair);
+        parts
+    }
+}
+
 impl<T> FromIterator<T> for SmallVector<T> {
     fn from_iter<I: IntoIterator<Item=T>>(iter: I) -> SmallVector<T> {
         let mut v = SmallVector::zero();
@@ -58,6 +74,21 @@ impl<T> SmallVector<T> {
         SmallVector { repr: Many(vs) }
     }
 
+    /// Like `many`, but keeps vectors of up to two elements inline.
+    pub fn from_vec(mut vs: Vec<T>) -> SmallVector<T> {
+        let repr = match vs.len() {
+            0 => Zero,
+            1 => One(vs.pop().unwrap()),
+            2 => {
+                let b = vs.pop().unwrap();
+                let a = vs.pop().unwrap();
+                Two([a, b])
+            }
+            _ => Many(vs)
+        };
+        SmallVector { repr: repr }
+    }
+
     pub fn as_slice<'a>(&'a self) -> &'a [T] {
         match self.repr {
             Zero => {
@@ -65,24 +96,87 @@ impl<T> SmallVector<T> {
                 result
             }
             One(ref v) => slice::ref_slice(v),
+            Two(ref pair) => pair,
             Many(ref vs) => vs
         }
     }
 
-    pub fn push(&mut self, v: T) {
+    pub fn as_mut_slice<'a>(&'a mut self) -> &'a mut [T] {
         match self.repr {
-            Zero => self.repr = One(v),
-            One(..) => {
-                let one = mem::replace(&mut self.repr, Zero);
-                match one {
-                    One(v1) => mem::replace(&mut self.repr, Many(vec!(v1, v))),
-                    _ => unreachable!()
-                };
+            Zero => {
+                let result: &mut [T] = &mut [];
+                result
             }
-            Many(ref mut vs) => vs.push(v)
+            One(ref mut v) => slice::mut_ref_slice(v),
+            Two(ref mut pair) => pair,
+            Many(ref mut vs) => vs
         }
     }
 
+    pub fn iter<'a>(&'a self) -> slice::Iter<'a, T> {
+        self.as_slice().iter()
+    }
+
+    pub fn push(&mut self, v: T) {
+        if let Many(ref mut vs) = self.repr {
+            vs.push(v);
+            return;
+        }
+        self.repr = match mem::replace(&mut self.repr, Zero) {
+            Zero => One(v),
+            One(v1) => Two([v1, v]),
+            Two(pair) => {
+                let (v1, v2) = split_pair(pair);
+                Many(vec!(v1, v2, v))
+            }
+            Many(..) => unreachable!()
+        };
+    }
+
+    /// Inserts `v` at position `idx`, shifting later elements along.
+    pub fn insert(&mut self, idx: usize, v: T) {
+        let len = self.len();
+        assert!(idx <= len, "insertion index {} out of bounds (len {})", idx, len);
+        if idx == len {
+            return self.push(v);
+        }
+        self.repr = match mem::replace(&mut self.repr, Zero) {
+            One(v1) => Two([v, v1]),
+            Two(pair) => {
+                let (v1, v2) = split_pair(pair);
+                let mut vs = vec!(v1, v2);
+                vs.insert(idx, v);
+                Many(vs)
+            }
+            Many(mut vs) => {
+                vs.insert(idx, v);
+                Many(vs)
+            }
+            Zero => unreachable!()
+        };
+    }
+
+    /// Removes and returns the element at position `idx`. A vector that has
+    /// already spilled stays on the heap.
+    pub fn remove(&mut self, idx: usize) -> T {
+        let len = self.len();
+        assert!(idx < len, "removal index {} out of bounds (len {})", idx, len);
+        let (repr, v) = match mem::replace(&mut self.repr, Zero) {
+            One(v) => (Zero, v),
+            Two(pair) => {
+                let (v1, v2) = split_pair(pair);
+                if idx == 0 { (One(v2), v1) } else { (One(v1), v2) }
+            }
+            Many(mut vs) => {
+                let v = vs.remove(idx);
+                (Many(vs), v)
+            }
+            Zero => unreachable!()
+        };
+        self.repr = repr;
+        v
+    }
+
     pub fn push_all(&mut self, other: SmallVector<T>) {
         for v in other.into_iter() {
             self.push(v);
@@ -90,10 +184,9 @@ impl<T> SmallVector<T> {
     }
 
     pub fn get<'a>(&'a self, idx: usize) -> &'a T {
-        match self.repr {
-            One(ref v) if idx == 0 => v,
-            Many(ref vs) => &vs[idx],
-            _ => panic!("out of bounds access")
+        match self.as_slice().get(idx) {
+            Some(v) => v,
+            None => panic!("out of bounds access")
         }
     }
 
@@ -122,15 +215,27 @@ impl<T> SmallVector<T> {
         let repr = match self.repr {
             Zero => ZeroIterator,
             One(v) => OneIterator(v),
+            Two(pair) => {
+                let (v1, v2) = split_pair(pair);
+                TwoIterator(v1, v2)
+            }
             Many(vs) => ManyIterator(vs.into_iter())
         };
         IntoIter { repr: repr }
     }
 
+    pub fn into_vec(self) -> Vec<T> {
+        match self.repr {
+            Many(vs) => vs,
+            repr => SmallVector { repr: repr }.into_iter().collect()
+        }
+    }
+
     pub fn len(&self) -> usize {
         match self.repr {
             Zero => 0,
             One(..) => 1,
+            Two(..) => 2,
             Many(ref vals) => vals.len()
         }
     }
@@ -145,6 +250,7 @@ pub struct IntoIter<T> {
 enum IntoIterRepr<T> {
     ZeroIterator,
     OneIterator(T),
+    TwoIterator(T, T),
     ManyIterator(vec::IntoIter<T>),
 }
 
@@ -162,6 +268,15 @@ impl<T> Iterator for IntoIter<T> {
                     _ => unreachable!()
                 }
             }
+            TwoIterator(..) => {
+                match mem::replace(&mut self.repr, ZeroIterator) {
+                    TwoIterator(v1, v2) => {
+                        self.repr = OneIterator(v2);
+                        Some(v1)
+                    }
+                    _ => unreachable!()
+                }
+            }
             ManyIterator(ref mut inner) => inner.next()
         }
     }
@@ -170,6 +285,7 @@ impl<T> Iterator for IntoIter<T> {
         match self.repr {
             ZeroIterator => (0, Some(0)),
             OneIterator(..) => (1, Some(1)),
+            TwoIterator(..) => (2, Some(2)),
             ManyIterator(ref inner) => inner.size_hint()
         }
     }
@@ -180,12 +296,65 @@ impl<T> MoveMap<T> for SmallVector<T> {
         let repr = match self.repr {
             Zero => Zero,
             One(v) => One(f(v)),
+            Two(pair) => {
+                let (v1, v2) = split_pair(pair);
+                let v1 = f(v1);
+                Two([v1, f(v2)])
+            }
             Many(vs) => Many(vs.move_map(f))
         };
         SmallVector { repr: repr }
     }
 }
 
+impl<T: Clone> Clone for SmallVector<T> {
+    fn clone(&self) -> SmallVector<T> {
+        let repr = match self.repr {
+            Zero => Zero,
+            One(ref v) => One(v.clone()),
+            Two(ref pair) => Two([pair[0].clone(), pair[1].clone()]),
+            Many(ref vs) => Many(vs.clone())
+        };
+        SmallVector { repr: repr }
+    }
+}
+
+// A vector compares, hashes and serializes as its elements, whichever
+// representation it happens to be in.
+
+impl<T: PartialEq> PartialEq for SmallVector<T> {
+    fn eq(&self, other: &SmallVector<T>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: Eq> Eq for SmallVector<T> {}
+
+impl<T: Hash> Hash for SmallVector<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SmallVector<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_slice(), f)
+    }
+}
+
+impl<T: Encodable> Encodable for SmallVector<T> {
+    fn encode<S: Encoder>(&self, s: &mut S) -> Result<(), S::Error> {
+        self.as_slice().encode(s)
+    }
+}
+
+impl<T: Decodable> Decodable for SmallVector<T> {
+    fn decode<D: Decoder>(d: &mut D) -> Result<SmallVector<T>, D::Error> {
+        let vs: Vec<T> = try!(Decodable::decode(d));
+        Ok(SmallVector::from_vec(vs))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -213,6 +382,59 @@ mod test {
         assert_eq!(&3, v.get(2));
     }
 
+    fn is_spilled<T>(v: &SmallVector<T>) -> bool {
+        match v.repr {
+            super::SmallVectorRepr::Many(..) => true,
+            _ => false
+        }
+    }
+
+    #[test]
+    fn test_spill() {
+        let mut v = SmallVector::zero();
+        v.push(1);
+        v.push(2);
+        assert!(!is_spilled(&v));
+        assert_eq!([1, 2], v.as_slice());
+        v.push(3);
+        assert!(is_spilled(&v));
+        assert_eq!([1, 2, 3], v.as_slice());
+
+        assert!(!is_spilled(&SmallVector::from_vec(vec![1, 2])));
+        assert!(is_spilled(&SmallVector::from_vec(vec![1, 2, 3])));
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut v = SmallVector::zero();
+        v.insert(0, 2);
+        v.insert(0, 1);
+        assert!(!is_spilled(&v));
+        assert_eq!([1, 2], v.as_slice());
+        v.insert(1, 3);
+        assert!(is_spilled(&v));
+        assert_eq!([1, 3, 2], v.as_slice());
+
+        let mut v = SmallVector::from_vec(vec![1, 2]);
+        assert_eq!(1, v.remove(0));
+        assert_eq!([2], v.as_slice());
+        assert_eq!(2, v.remove(0));
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_insert_out_of_bounds() {
+        SmallVector::one(1).insert(2, 2);
+    }
+
+    #[test]
+    fn test_eq_across_reprs() {
+        assert_eq!(SmallVector::one(1), SmallVector::many(vec![1]));
+        assert_eq!(SmallVector::from_vec(vec![1, 2]), SmallVector::many(vec![1, 2]));
+        assert!(SmallVector::one(1) != SmallVector::from_vec(vec![1, 2]));
+    }
+
     #[test]
     fn test_from_iter() {
         let v: SmallVector<isize> = (vec![1, 2, 3]).into_iter().collect();
@@ -231,6 +453,9 @@ mod test {
         let v = SmallVector::one(1);
         assert_eq!([1], v.into_iter().collect::<Vec<_>>());
 
+        let v = SmallVector::from_vec(vec![1, 2]);
+        assert_eq!([1, 2], v.into_iter().collect::<Vec<_>>());
+
         let v = SmallVector::many(vec![1, 2, 3]);
         assert_eq!([1, 2, 3], v.into_iter().collect::<Vec<_>>());
     }
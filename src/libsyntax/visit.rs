@@ -823,7 +823,7 @@ pub fn walk_expr<'v, V: Visitor<'v>>(visitor: &mut V, expression: &'v Expr) {
             visitor.visit_expr(&**main_expression);
             visitor.visit_expr(&**index_expression)
         }
-        ExprRange(ref start, ref end) => {
+        ExprRange(ref start, ref end, _) => {
             walk_expr_opt(visitor, start);
             walk_expr_opt(visitor, end)
         }
@@ -46,6 +46,7 @@
 #![feature(set_stdio)]
 #![feature(os)]
 #![feature(convert)]
+#![feature(recover)]
 #![cfg_attr(test, feature(old_io))]
 
 extern crate getopts;
@@ -78,6 +79,7 @@ use std::io::prelude::*;
 use std::io;
 use std::iter::repeat;
 use std::num::{Float, Int};
+use std::panic::{self, AssertRecoverSafe};
 use std::path::{PathBuf};
 use std::sync::mpsc::{channel, Sender};
 use std::sync::{Arc, Mutex};
@@ -257,6 +259,12 @@ pub fn test_main(args: &[String], tests: Vec<TestDescAndFn> ) {
             Some(Err(msg)) => panic!("{:?}", msg),
             None => return
         };
+    if opts.list {
+        if let Err(e) = list_tests_console(&opts, tests) {
+            panic!("io error when listing tests: {:?}", e);
+        }
+        return;
+    }
     match run_tests_console(&opts, tests) {
         Ok(true) => {}
         Ok(false) => panic!("Some tests failed"),
@@ -298,6 +306,8 @@ pub struct TestOpts {
     pub logfile: Option<PathBuf>,
     pub nocapture: bool,
     pub color: ColorConfig,
+    pub quiet: bool,
+    pub list: bool,
 }
 
 impl TestOpts {
@@ -311,6 +321,8 @@ impl TestOpts {
             logfile: None,
             nocapture: false,
             color: AutoColor,
+            quiet: false,
+            list: false,
         }
     }
 }
@@ -327,6 +339,9 @@ fn optgroups() -> Vec<getopts::OptGroup> {
                           of stdout", "PATH"),
       getopts::optflag("", "nocapture", "don't capture stdout/stderr of each \
                                          task, allow printing directly"),
+      getopts::optflag("q", "quiet", "Display one character per test instead of one line"),
+      getopts::optflag("", "list", "List all tests and benchmarks that would be run \
+                                    and exit"),
       getopts::optopt("", "color", "Configure coloring of output:
             auto   = colorize if stdout is a tty and tests are run on serially (default);
             always = always colorize output;
@@ -395,6 +410,9 @@ pub fn parse_opts(args: &[String]) -> Option<OptRes> {
         nocapture = env::var("RUST_TEST_NOCAPTURE").is_ok();
     }
 
+    let quiet = matches.opt_present("quiet");
+    let list = matches.opt_present("list");
+
     let color = match matches.opt_str("color").as_ref().map(|s| &**s) {
         Some("auto") | None => AutoColor,
         Some("always") => AlwaysColor,
@@ -413,6 +431,8 @@ pub fn parse_opts(args: &[String]) -> Option<OptRes> {
         logfile: logfile,
         nocapture: nocapture,
         color: color,
+        quiet: quiet,
+        list: list,
     };
 
     Some(Ok(test_opts))
@@ -444,6 +464,7 @@ struct ConsoleTestState<T> {
     log_out: Option<File>,
     out: OutputLocation<T>,
     use_color: bool,
+    quiet: bool,
     total: uint,
     passed: uint,
     failed: uint,
@@ -470,6 +491,7 @@ impl<T: Write> ConsoleTestState<T> {
             out: out,
             log_out: log_out,
             use_color: use_color(opts),
+            quiet: opts.quiet,
             total: 0,
             passed: 0,
             failed: 0,
@@ -543,11 +565,35 @@ impl<T: Write> ConsoleTestState<T> {
 
     pub fn write_test_start(&mut self, test: &TestDesc,
                             align: NamePadding) -> io::Result<()> {
+        if self.quiet {
+            return Ok(());
+        }
         let name = test.padded_name(self.max_name_len, align);
         self.write_plain(&format!("test {} ... ", name))
     }
 
-    pub fn write_result(&mut self, result: &TestResult) -> io::Result<()> {
+    pub fn write_short_result(&mut self, test: &TestDesc,
+                              result: &TestResult) -> io::Result<()> {
+        match *result {
+            TrOk => self.write_pretty(".", term::color::GREEN),
+            TrFailed => self.write_pretty("F", term::color::RED),
+            TrIgnored => self.write_pretty("i", term::color::YELLOW),
+            // Measurements are only useful with the name they belong to.
+            TrMetrics(..) | TrBench(..) => {
+                try!(self.write_plain(&format!("\ntest {} ... ", test.name)));
+                self.write_result(test, result)
+            }
+        }
+    }
+
+    pub fn write_result(&mut self, test: &TestDesc,
+                        result: &TestResult) -> io::Result<()> {
+        match *result {
+            TrOk | TrFailed | TrIgnored if self.quiet => {
+                return self.write_short_result(test, result);
+            }
+            _ => {}
+        }
         try!(match *result {
             TrOk => self.write_ok(),
             TrFailed => self.write_failed(),
@@ -645,6 +691,37 @@ pub fn fmt_bench_samples(bs: &BenchSamples) -> String {
     }
 }
 
+// Lists the tests that would be run, one per line, followed by their number.
+pub fn list_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Result<()> {
+    let mut st = try!(ConsoleTestState::new(opts, None::<io::Stdout>));
+
+    let mut ntest = 0;
+    let mut nbench = 0;
+    for test in filter_tests(opts, tests) {
+        let kind = match test.testfn {
+            StaticBenchFn(..) | DynBenchFn(..) => { nbench += 1; "bench" },
+            _ => { ntest += 1; "test" },
+        };
+        try!(st.write_plain(&format!("{}: {}\n", test.desc.name, kind)));
+    }
+
+    fn plural(count: uint, s: &str) -> String {
+        match count {
+            1 => format!("{} {}", 1, s),
+            n => format!("{} {}s", n, s),
+        }
+    }
+    if !opts.quiet {
+        if ntest != 0 || nbench != 0 {
+            try!(st.write_plain("\n"));
+        }
+        try!(st.write_plain(&format!("{}, {}\n",
+                                     plural(ntest, "test"),
+                                     plural(nbench, "benchmark"))));
+    }
+    Ok(())
+}
+
 // A simple console test runner
 pub fn run_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn> ) -> io::Result<bool> {
 
@@ -655,7 +732,7 @@ pub fn run_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn> ) -> io::Res
             TeWait(ref test, padding) => st.write_test_start(test, padding),
             TeResult(test, result, stdout) => {
                 try!(st.write_log(&test, &result));
-                try!(st.write_result(&result));
+                try!(st.write_result(&test, &result));
                 match result {
                     TrOk => st.passed += 1,
                     TrIgnored => st.ignored += 1,
@@ -724,6 +801,7 @@ fn should_sort_failures_before_printing_them() {
         log_out: None,
         out: Raw(Vec::new()),
         use_color: false,
+        quiet: false,
         total: 0,
         passed: 0,
         failed: 0,
@@ -938,9 +1016,10 @@ pub fn run_test(opts: &TestOpts,
                     io::set_print(box Sink(data2.clone()));
                     io::set_panic(box Sink(data2));
                 }
-                testfn.invoke(())
+                panic::recover(AssertRecoverSafe(move || testfn.invoke(())))
             }).unwrap();
-            let test_result = calc_result(&desc, result_guard.join());
+            let result = result_guard.join().and_then(|result| result);
+            let test_result = calc_result(&desc, result);
             let stdout = data.lock().unwrap().to_vec();
             monitor_ch.send((desc.clone(), test_result, stdout)).unwrap();
         });
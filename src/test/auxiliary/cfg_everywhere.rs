@@ -0,0 +1,101 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Shared by cfg-everywhere-foo.rs and cfg-everywhere-notfoo.rs, which compile
+// it with and without `--cfg foo`.
+
+#[cfg(foo)]
+use std::ffi::CString;
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Shape {
+    Circle,
+    #[cfg(foo)]
+    Square(u32),
+}
+
+pub struct Counts {
+    pub circles: u32,
+    #[cfg(foo)]
+    pub squares: u32,
+}
+
+pub struct Wrapper(#[cfg(foo)] pub u32, pub u32);
+
+pub trait Describe {
+    fn describe(&self) -> &'static str;
+
+    #[cfg(foo)]
+    fn sides(&self) -> u32;
+}
+
+impl Describe for Shape {
+    #[cfg(foo)]
+    fn describe(&self) -> &'static str {
+        match *self {
+            Shape::Circle => "circle",
+            #[cfg(foo)]
+            Shape::Square(_) => "square",
+        }
+    }
+
+    #[cfg(not(foo))]
+    fn describe(&self) -> &'static str {
+        match *self {
+            Shape::Circle => "only circles",
+            #[cfg(foo)]
+            Shape::Square(_) => unreachable!(),
+        }
+    }
+
+    #[cfg(foo)]
+    fn sides(&self) -> u32 {
+        match *self {
+            Shape::Circle => 0,
+            Shape::Square(_) => 4,
+        }
+    }
+}
+
+extern {
+    #[cfg(foo)]
+    fn atoi(s: *const u8) -> i32;
+    #[cfg(not(foo))]
+    fn abs(x: i32) -> i32;
+}
+
+#[cfg(foo)]
+pub fn parse(s: &str) -> i32 {
+    let s = CString::new(s).unwrap();
+    unsafe { atoi(s.as_ptr() as *const u8) }
+}
+
+#[cfg(not(foo))]
+pub fn parse(s: &str) -> i32 {
+    unsafe { abs(s.parse().unwrap()) }
+}
+
+pub fn count(shapes: &[Shape]) -> Counts {
+    let mut counts = new_counts();
+    for shape in shapes.iter().cloned() {
+        match shape {
+            Shape::Circle => counts.circles += 1,
+            #[cfg(foo)]
+            Shape::Square(_) => counts.squares += 1,
+        }
+    }
+    counts
+}
+
+#[cfg(foo)]
+fn new_counts() -> Counts { Counts { circles: 0, squares: 0 } }
+
+#[cfg(not(foo))]
+fn new_counts() -> Counts { Counts { circles: 0 } }
@@ -0,0 +1,38 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type = "lib"]
+
+#[deprecated(since = "0.2.0", note = "use bar instead")]
+pub fn foo() {}
+
+pub fn bar() {}
+
+#[deprecated]
+pub struct Old;
+
+#[deprecated(note = "no longer needed")]
+pub trait OldTrait {
+    fn method(&self) {}
+}
+
+pub struct Thing;
+
+impl Thing {
+    #[deprecated(since = "0.3.0")]
+    pub fn old_method(&self) {}
+
+    pub fn new_method(&self) {}
+}
+
+#[deprecated(since = "0.1.0", note = "the whole module is going away")]
+pub mod legacy {
+    pub fn helper() {}
+}
@@ -17,7 +17,8 @@ extern crate syntax;
 #[macro_use] extern crate rustc;
 
 use syntax::{ast, attr};
-use rustc::lint::{Context, LintPass, LintPassObject, LintArray};
+use rustc::lint::{LateContext, LintContext, LintPass, LateLintPass};
+use rustc::lint::{LintPassObject, LateLintPassObject, LintArray};
 use rustc::plugin::Registry;
 
 declare_lint!(CRATE_NOT_OKAY, Warn, "crate not marked with #![crate_okay]");
@@ -28,8 +29,10 @@ impl LintPass for Pass {
     fn get_lints(&self) -> LintArray {
         lint_array!(CRATE_NOT_OKAY)
     }
+}
 
-    fn check_crate(&mut self, cx: &Context, krate: &ast::Crate) {
+impl LateLintPass for Pass {
+    fn check_crate(&mut self, cx: &LateContext, krate: &ast::Crate) {
         if !attr::contains_name(&krate.attrs, "crate_okay") {
             cx.span_lint(CRATE_NOT_OKAY, krate.span,
                          "crate is not marked with #![crate_okay]");
@@ -39,5 +42,5 @@ impl LintPass for Pass {
 
 #[plugin_registrar]
 pub fn plugin_registrar(reg: &mut Registry) {
-    reg.register_lint_pass(box Pass as LintPassObject);
+    reg.register_lint_pass(LintPassObject::Late(box Pass as LateLintPassObject));
 }
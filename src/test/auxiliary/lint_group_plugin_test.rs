@@ -21,7 +21,8 @@ extern crate rustc;
 
 use syntax::ast;
 use syntax::parse::token;
-use rustc::lint::{Context, LintPass, LintPassObject, LintArray};
+use rustc::lint::{LateContext, LintContext, LintPass, LateLintPass};
+use rustc::lint::{LintPassObject, LateLintPassObject, LintArray};
 use rustc::plugin::Registry;
 
 declare_lint!(TEST_LINT, Warn, "Warn about items named 'lintme'");
@@ -34,8 +35,10 @@ impl LintPass for Pass {
     fn get_lints(&self) -> LintArray {
         lint_array!(TEST_LINT, PLEASE_LINT)
     }
+}
 
-    fn check_item(&mut self, cx: &Context, it: &ast::Item) {
+impl LateLintPass for Pass {
+    fn check_item(&mut self, cx: &LateContext, it: &ast::Item) {
         let name = token::get_ident(it.ident);
         if &name[..] == "lintme" {
             cx.span_lint(TEST_LINT, it.span, "item is named 'lintme'");
@@ -47,6 +50,6 @@ impl LintPass for Pass {
 
 #[plugin_registrar]
 pub fn plugin_registrar(reg: &mut Registry) {
-    reg.register_lint_pass(box Pass as LintPassObject);
+    reg.register_lint_pass(LintPassObject::Late(box Pass as LateLintPassObject));
     reg.register_lint_group("lint_me", vec![TEST_LINT, PLEASE_LINT]);
 }
@@ -0,0 +1,81 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-stage1
+// force-host
+
+#![feature(plugin_registrar, quote, rustc_private)]
+#![crate_type = "dylib"]
+
+extern crate syntax;
+extern crate rustc;
+
+use syntax::ast::{self, TokenTree};
+use syntax::codemap::Span;
+use syntax::ext::base::{ExtCtxt, MacResult, MacEager};
+use syntax::parse::token;
+use syntax::util::small_vector::SmallVector;
+use rustc::plugin::Registry;
+
+#[plugin_registrar]
+pub fn plugin_registrar(reg: &mut Registry) {
+    reg.register_macro("quoted_module", expand)
+}
+
+// `quoted_module!(name, items...)` wraps the items in `pub mod name`, along
+// with a `count` function returning how many items there were.
+fn expand(cx: &mut ExtCtxt, sp: Span, tts: &[TokenTree]) -> Box<MacResult+'static> {
+    let mut parser = cx.new_parser_from_tts(tts);
+    let name_sp = parser.span;
+    let name = parser.parse_ident();
+    parser.expect(&token::Comma);
+    let mut items = Vec::new();
+    while let Some(item) = parser.parse_item() {
+        items.push(item);
+    }
+    let item_spans: Vec<Span> = items.iter().map(|item| item.span).collect();
+
+    let stmts: Vec<ast::Stmt> = items.iter().map(|_| {
+        (*quote_stmt_spanned!(cx, name_sp, let n = n + 1;).unwrap()).clone()
+    }).collect();
+
+    let module = quote_item_spanned!(cx, name_sp,
+        pub mod $name {
+            $items
+
+            pub fn count() -> usize {
+                let n = 0;
+                $stmts
+                n
+            }
+        }
+    ).unwrap();
+
+    // The quoted tokens are attributed to the caller's name for the module,
+    // while the spliced name and items come through untouched.
+    if module.span.lo != name_sp.lo || module.span.hi != name_sp.hi {
+        cx.span_err(module.span, "quoted module did not get the requested span");
+    }
+    if module.ident.name != name.name || module.ident.ctxt != name.ctxt {
+        cx.span_err(name_sp, "quoted module lost the syntax context of its name");
+    }
+    match module.node {
+        ast::ItemMod(ref m) => {
+            for (item, item_sp) in m.items.iter().zip(item_spans.iter()) {
+                if item.span != *item_sp {
+                    cx.span_err(item.span, "spliced item lost its span");
+                }
+            }
+        }
+        _ => cx.span_bug(sp, "quoted module is not a module"),
+    }
+
+    MacEager::items(SmallVector::one(module))
+}
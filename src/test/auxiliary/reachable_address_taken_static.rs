@@ -0,0 +1,31 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Generic functions that take the address of statics private to this
+// crate, directly and through a static initializer.
+
+static COUNT: u32 = 42;
+
+static NAMES: [&'static str; 2] = ["first", "second"];
+
+static DEFAULT: &'static u32 = &COUNT;
+
+pub fn count_ref<T>() -> &'static u32 {
+    &COUNT
+}
+
+pub fn name<T>(i: usize) -> &'static str {
+    let names = &NAMES;
+    names[i]
+}
+
+pub fn default<T>() -> u32 {
+    *DEFAULT
+}
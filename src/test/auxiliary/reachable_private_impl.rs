@@ -0,0 +1,60 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Generic functions whose bodies call methods of impls that are not
+// otherwise exported. The impls below are private, but once the callers
+// are monomorphized in another crate the methods must still be linkable.
+
+use std::ops::Add;
+
+trait Describe {
+    fn describe(&self) -> u32;
+
+    fn describe_twice(&self) -> u32 {
+        self.describe() * 2
+    }
+}
+
+impl Describe for u32 {
+    fn describe(&self) -> u32 {
+        *self + 1
+    }
+}
+
+struct Meters(u32);
+
+impl Add for Meters {
+    type Output = Meters;
+
+    fn add(self, other: Meters) -> Meters {
+        Meters(self.0 + other.0)
+    }
+}
+
+pub fn concrete<T>(x: u32) -> u32 {
+    x.describe()
+}
+
+pub fn default_method<T>(x: u32) -> u32 {
+    x.describe_twice()
+}
+
+pub fn through_path<T>(x: u32) -> u32 {
+    Describe::describe(&x)
+}
+
+pub fn through_object<T>(x: u32) -> u32 {
+    let object: &Describe = &x;
+    object.describe()
+}
+
+pub fn overloaded_operator<T>(a: u32, b: u32) -> u32 {
+    (Meters(a) + Meters(b)).0
+}
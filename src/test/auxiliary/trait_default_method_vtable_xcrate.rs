@@ -0,0 +1,27 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+pub trait Steps {
+    fn first(&self) -> String { format!("first({})", self.second()) }
+    fn second(&self) -> String { format!("second({})", self.third()) }
+    fn third(&self) -> String { "third".to_string() }
+}
+
+pub struct Upstream;
+
+// Overrides only the middle method; the defaults around it must still
+// dispatch to this impl's `second`.
+impl Steps for Upstream {
+    fn second(&self) -> String { format!("upstream({})", self.third()) }
+}
+
+pub fn upstream_boxed() -> Box<Steps + 'static> {
+    Box::new(Upstream)
+}
@@ -0,0 +1,27 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type="lib"]
+
+pub struct Meters(pub uint);
+pub struct Pair<T>(pub T, pub T);
+pub struct Sealed(uint);
+struct Hidden(pub uint);
+
+pub mod units {
+    pub struct Feet(pub uint);
+}
+
+pub use units::Feet;
+
+pub fn hidden(x: uint) -> uint {
+    let Hidden(x) = Hidden(x);
+    x
+}
@@ -47,4 +47,8 @@ pub fn main() {
     let x = quote_stmt!(ecx, 3);     //~ ERROR macro undefined: 'quote_stmt!'
     let x = quote_matcher!(ecx, 3);  //~ ERROR macro undefined: 'quote_matcher!'
     let x = quote_attr!(ecx, 3);     //~ ERROR macro undefined: 'quote_attr!'
+    let x = quote_tokens_spanned!(ecx, sp, 3); //~ ERROR macro undefined: 'quote_tokens_spanned!'
+    let x = quote_expr_spanned!(ecx, sp, 3);   //~ ERROR macro undefined: 'quote_expr_spanned!'
+    let x = quote_item_spanned!(ecx, sp, 3);   //~ ERROR macro undefined: 'quote_item_spanned!'
+    let x = quote_stmt_spanned!(ecx, sp, 3);   //~ ERROR macro undefined: 'quote_stmt_spanned!'
 }
@@ -0,0 +1,19 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Negative array lengths and repeat counts are rejected at their span, including
+// unsuffixed literals that would otherwise wrap around as `usize`.
+
+fn main() {
+    let _: [u8; -1]; //~ ERROR expected usize, found negative value
+    let _: [u8; (-1)]; //~ ERROR expected usize, found negative value
+    let _: [u8; -1_isize]; //~ ERROR expected usize, found negative value
+    let _ = [0u8; -1]; //~ ERROR expected usize, found negative value
+}
@@ -0,0 +1,29 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Pattern guards may not move out of an arm's by-move bindings, nor mutate
+// them.
+
+fn consume(_: String) -> bool { true }
+
+fn main() {
+    let x = Some("a".to_string());
+    match x {
+        Some(s) if consume(s) => {}
+        //~^ ERROR cannot move out of `s` in a pattern guard
+        Some(mut s) if { s.push('b'); false } => {}
+        //~^ ERROR cannot mutably borrow in a pattern guard
+        Some(s) if { let t = s; t.is_empty() } => {}
+        //~^ ERROR cannot move out of `s` in a pattern guard
+        Some(s) if (move || s.is_empty())() => {}
+        //~^ ERROR cannot move out of `s` in a pattern guard
+        _ => {}
+    }
+}
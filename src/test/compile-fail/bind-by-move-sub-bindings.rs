@@ -0,0 +1,50 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![allow(unused_variables)]
+
+struct Foo(String);
+
+fn main() {
+    match Some(Foo(String::new())) {
+        x @ Some(Foo(ref s)) => {}
+        //~^ ERROR cannot bind by-move with sub-bindings
+        //~| NOTE sub-binding occurs here
+        //~| HELP bind `x` by reference instead: `ref x @ ...`
+        None => {}
+    }
+
+    match Some(Foo(String::new())) {
+        x @ Some(Foo(ref mut s)) => {}
+        //~^ ERROR cannot bind by-move with sub-bindings
+        //~| NOTE sub-binding occurs here
+        None => {}
+    }
+
+    match Some(Foo(String::new())) {
+        ref mut x @ Some(Foo(ref s)) => {}
+        //~^ ERROR pattern bindings are not allowed after an `@`
+        None => {}
+    }
+
+    match Some(Foo(String::new())) {
+        ref x @ Some(Foo(ref mut s)) => {}
+        //~^ ERROR pattern bindings are not allowed after an `@`
+        None => {}
+    }
+
+    match Some(Foo(String::new())) {
+        ref x @ Some(Foo(s)) => {}
+        //~^ ERROR pattern bindings are not allowed after an `@`
+        //~| ERROR cannot bind by-move and by-ref in the same pattern
+        //~| NOTE by-ref binding occurs here
+        None => {}
+    }
+}
@@ -0,0 +1,23 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Removing the only arm of a match leaves it empty, which is an ordinary
+// exhaustiveness error.
+
+enum Foo {
+    Bar,
+}
+
+fn main() {
+    match Foo::Bar { //~ ERROR non-exhaustive patterns: type Foo is non-empty
+        #[cfg(nonexistent)]
+        Foo::Bar => {}
+    }
+}
@@ -8,6 +8,8 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+#![allow(non_snake_case)]
+
 // Test that the borrow checker prevents pointers to temporaries
 // with statement lifetimes from escaping.
 
@@ -0,0 +1,34 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Only closures which capture nothing can be coerced to fn pointers, and
+// only to ones of the Rust ABI.
+
+struct Unit;
+
+fn apply(f: fn(isize) -> isize, x: isize) -> isize {
+    f(x)
+}
+
+fn main() {
+    let y = 1;
+    apply(|x| x + y, 1);
+    //~^ ERROR mismatched types
+    //~| NOTE only closures which capture nothing from their environment
+
+    let u = Unit;
+    let _: fn() = move || { let _u = u; };
+    //~^ ERROR mismatched types
+    //~| NOTE only closures which capture nothing from their environment
+
+    let _: extern "C" fn() = || {};
+    //~^ ERROR mismatched types
+    //~| NOTE closures can only be coerced to `fn` pointers of the Rust ABI, not `extern "C"`
+}
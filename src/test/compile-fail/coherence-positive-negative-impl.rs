@@ -0,0 +1,38 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(optin_builtin_traits)]
+
+// Test that a type cannot have both a positive and a negative impl of
+// the same defaulted trait.
+
+use std::marker::MarkerTrait;
+
+unsafe trait MyDefault : MarkerTrait {}
+
+unsafe impl MyDefault for .. {}
+
+struct Foo;
+
+unsafe impl MyDefault for Foo {}
+//~^ ERROR found both positive and negative implementation of trait `MyDefault` for type `Foo`
+//~^^ NOTE positive implementation here
+
+impl !MyDefault for Foo {}
+//~^ NOTE negative implementation here
+
+struct Bar;
+
+unsafe impl Send for Bar {}
+//~^ ERROR found both positive and negative implementation of trait `core::marker::Send`
+
+impl !Send for Bar {}
+
+fn main() {}
@@ -8,6 +8,8 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+#![allow(non_snake_case)]
+
 // Tests that methods that implement a trait cannot be invoked
 // unless the trait is imported.
 
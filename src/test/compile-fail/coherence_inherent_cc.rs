@@ -10,6 +10,8 @@
 
 // aux-build:coherence_inherent_cc_lib.rs
 
+#![allow(non_snake_case)]
+
 // Tests that methods that implement a trait cannot be invoked
 // unless the trait is imported.
 
@@ -0,0 +1,59 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-tidy-linelength
+
+// Check that a mismatch between an impl method and its trait declaration
+// is reported at the first part of the signature that differs.
+
+trait Foo {
+    fn by_ref(&self);
+    fn one_param(&self, x: u8);
+    fn params(&self, x: u8, y: &u16);
+    fn static_params(x: u8, y: u16);
+    fn ret(&self) -> u32;
+    unsafe fn unsafety(&self);
+    extern "C" fn abi(&self);
+    fn ty_params<T>(&self, t: T);
+    fn lifetimes<'a, 'b: 'a>(&self, x: &'a u8, y: &'b u8);
+}
+
+struct Bar;
+
+impl Foo for Bar {
+    fn by_ref(&mut self) {}
+    //~^ ERROR method `by_ref` has an incompatible type for trait: it takes `&mut self` but the trait declares `&self`
+
+    fn one_param(&self, x: u8, y: u8) {}
+    //~^ ERROR method `one_param` has 3 parameters but the declaration in trait `Foo::one_param` has 2
+
+    fn params(&self, x: u8, y: &mut u16) {}
+    //~^ ERROR method `params` has an incompatible type for trait: parameter 2 has type `&mut u16` but the trait expects `&u16`: values differ in mutability
+
+    fn static_params(x: i8, y: u16) {}
+    //~^ ERROR method `static_params` has an incompatible type for trait: parameter 1 has type `i8` but the trait expects `u8`
+
+    fn ret(&self) -> u64 { 0 }
+    //~^ ERROR method `ret` has an incompatible type for trait: the return type is `u64` but the trait expects `u32`
+
+    fn unsafety(&self) {}
+    //~^ ERROR method `unsafety` has an incompatible type for trait: expected unsafe fn, found normal fn
+
+    fn abi(&self) {}
+    //~^ ERROR method `abi` has an incompatible type for trait: expected "C" fn, found "Rust" fn
+
+    fn ty_params(&self, t: u8) {}
+    //~^ ERROR method `ty_params` has 0 type parameters but its trait declaration has 1 type parameter
+
+    fn lifetimes<'a, 'b>(&self, x: &'a u8, y: &'b u8) {}
+    //~^ ERROR lifetime parameters or bounds on method `lifetimes` do not match the trait declaration
+}
+
+fn main() {}
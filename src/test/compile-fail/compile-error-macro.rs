@@ -0,0 +1,31 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// `compile_error!` reports its message where the user's code invoked it,
+// whether directly or through another macro.
+
+compile_error!("top-level error"); //~ ERROR top-level error
+
+macro_rules! width {
+    (16) => (2);
+    (32) => (4);
+    ($other:tt) => (compile_error!(concat!("unsupported width: ", stringify!($other))));
+}
+
+macro_rules! bytes_for {
+    ($w:tt) => (width!($w));
+}
+
+fn main() {
+    let _ = width!(32);
+    let _ = width!(12); //~ ERROR unsupported width: 12
+    let _ = bytes_for!(7); //~ ERROR unsupported width: 7
+    compile_error!("statement error"); //~ ERROR statement error
+}
@@ -0,0 +1,19 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+const SIZE: usize = [0u8; SIZE].len();
+//~^ ERROR cycle detected when computing value of `SIZE`
+
+static BUF: [u8; BUF_LEN] = [0; BUF_LEN];
+//~^ ERROR cycle detected when computing value of `BUF`
+const BUF_LEN: usize = BUF.len();
+//~^ NOTE ...which then requires computing value of `BUF_LEN`...
+
+fn main() {}
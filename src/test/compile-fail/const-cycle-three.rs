@@ -0,0 +1,24 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// A cycle between three items is reported once, listing each of them.
+
+const A: u32 = B + 1;
+//~^ ERROR cycle detected when computing value of `A`
+//~| NOTE ...which then again requires computing value of `A`, completing the cycle
+const B: u32 = C * 2;
+//~^ NOTE ...which then requires computing value of `B`...
+const C: u32 = A;
+//~^ NOTE ...which then requires computing value of `C`...
+
+// Not part of the cycle, so it doesn't cause another error.
+const D: u32 = A;
+
+fn main() {}
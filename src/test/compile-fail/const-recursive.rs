@@ -8,7 +8,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-// error-pattern: recursive constant
+// error-pattern: cycle detected when computing value of `a`
 static a: isize = b;
 static b: isize = a;
 
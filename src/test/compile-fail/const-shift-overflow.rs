@@ -0,0 +1,29 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that constant evaluation rejects shifts by at least the bit width of
+// the shifted type, rather than silently producing a value.
+
+#![allow(exceeding_bitshifts)]
+
+enum E {
+    A = 1 << 64, //~ ERROR constant evaluation error: attempted to shift left with overflow
+    B = 1 >> 64, //~ ERROR constant evaluation error: attempted to shift right with overflow
+}
+
+fn main() {
+    let _: [u8; 1 << 64];
+    //~^ ERROR array length constant evaluation error: attempted to shift left with overflow
+    let _: [u8; 1u8 << 8];
+    //~^ ERROR array length constant evaluation error: attempted to shift left with overflow
+    let _: [u8; 1 >> 64];
+    //~^ ERROR array length constant evaluation error: attempted to shift right with overflow
+    let _: [u8; (1u64 << 40) as usize >> 40];
+}
@@ -0,0 +1,23 @@
+// ignore-tidy-cr ignore-license
+// ignore-tidy-cr (repeated again because of tidy bug)
+// license is ignored because tidy can't handle the CRLF here properly.
+
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// This file has CRLF line endings (use xxd to see them). Errors must still
+// be reported on the right line, after multi-byte characters, and with the
+// CR left out of the snippet.
+
+fn main() {
+    let s = "naïve €€€"; let x: u32 = s; //~ ERROR mismatched types
+    let y: bool = 1;
+    //~^ ERROR mismatched types
+}
@@ -0,0 +1,24 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Malformed standalone `#[deprecated]` attributes.
+
+#![allow(dead_code)]
+
+#[deprecated = "use b"] //~ ERROR incorrect deprecated attribute type
+fn a() {}
+
+#[deprecated(since = "1.0", reason = "x")] //~ ERROR unknown meta item 'reason'
+fn b() {}
+
+#[deprecated(note)] //~ ERROR incorrect meta item
+fn c() {}
+
+fn main() {}
@@ -0,0 +1,44 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:deprecated_attr_xc.rs
+
+// `#[deprecated]` can be used without the staged stability system, both
+// across crates and within a crate.
+
+#![deny(deprecated)]
+#![allow(dead_code)]
+
+extern crate deprecated_attr_xc;
+
+#[deprecated(note = "local, too")]
+fn local_old() {}
+
+struct Local;
+
+impl deprecated_attr_xc::OldTrait for Local {} //~ ERROR use of deprecated item: no longer needed
+
+fn main() {
+    deprecated_attr_xc::foo();
+    //~^ ERROR use of deprecated item: use bar instead (deprecated since 0.2.0)
+    deprecated_attr_xc::bar();
+    let _ = deprecated_attr_xc::Old; //~ ERROR use of deprecated item
+    deprecated_attr_xc::Thing.old_method(); //~ ERROR deprecated item (deprecated since 0.3.0)
+    deprecated_attr_xc::Thing.new_method();
+    deprecated_attr_xc::legacy::helper(); //~ ERROR the whole module is going away
+    local_old(); //~ ERROR use of deprecated item: local, too
+}
+
+#[allow(deprecated)]
+fn allowed() {
+    deprecated_attr_xc::foo();
+    deprecated_attr_xc::legacy::helper();
+    local_old();
+}
@@ -0,0 +1,23 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:duplicate_lang_item.rs
+
+// A lang item that an upstream crate defines as well is reported at the
+// `extern crate` that brings the other definition in.
+
+#![feature(lang_items)]
+
+extern crate duplicate_lang_item; //~ ERROR duplicate entry for `strdup_uniq`
+
+#[lang = "strdup_uniq"]
+fn strdup_uniq() {}
+
+fn main() {}
@@ -0,0 +1,25 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+enum E {
+    A = E::B as isize,
+    //~^ ERROR cycle detected when computing value of `E::A`
+    B = E::A as isize,
+    //~^ NOTE ...which then requires computing value of `E::B`...
+}
+
+const X: isize = F::V as isize;
+//~^ ERROR cycle detected when computing value of `X`
+enum F {
+    V = X,
+    //~^ NOTE ...which then requires computing value of `F::V`...
+}
+
+fn main() {}
@@ -9,6 +9,7 @@
 // except according to those terms.
 
 #![forbid(unstable_features)]
+#![allow(unused_features)]
 #![feature(intrinsics)] //~ ERROR unstable feature
 
 fn main() { }
@@ -0,0 +1,48 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that `+` following the return type of a parenthesized bound is
+// rejected inside a type parameter list, where it could either extend
+// the return type to a trait object or add another bound to the type
+// parameter.
+
+#![feature(unboxed_closures)]
+
+use std::fmt::Debug;
+
+trait Shape {}
+
+fn by_value<F: FnMut() -> Shape + Debug>(f: F) {}
+//~^ ERROR ambiguous `+` after the return type of a parenthesized bound
+//~| HELP parenthesize the return type: `-> (Shape + Debug)`
+//~| HELP move them to a `where` clause: `where F: FnMut() -> Shape + Debug`
+
+struct Callback<F: Fn(u8) -> u8 + 'static> {
+//~^ ERROR ambiguous `+` after the return type of a parenthesized bound
+//~| HELP parenthesize the return type: `-> (u8 + 'static)`
+//~| HELP move them to a `where` clause: `where F: Fn(u8) -> u8 + 'static`
+    f: F,
+}
+
+trait Task {}
+
+impl<F: Clone + FnOnce(u32) -> u32 + Send + Sync> Task for F {}
+//~^ ERROR ambiguous `+` after the return type of a parenthesized bound
+//~| HELP parenthesize the return type: `-> (u32 + Send + Sync)`
+//~| HELP `where F: Clone + FnOnce(u32) -> u32 + Send + Sync`
+
+// Neither spelling suggested above is ambiguous, nor is a parenthesized
+// bound that is last in its list.
+fn bound<F>(f: F) where F: FnMut() -> u32 + Debug {}
+fn object<F: FnMut() -> Box<Shape + Debug>>(f: F) {}
+fn paren<F: FnMut() -> (Shape + Debug)>(f: F) {}
+fn last<F: Debug + FnMut() -> u32>(f: F) {}
+
+fn main() {}
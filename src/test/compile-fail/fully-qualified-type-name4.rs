@@ -8,16 +8,16 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-// Test that we use fully-qualified type names in error messages.
+// Test that a type imported by name is printed by that name in error messages.
 
 use std::option::Option;
 
 fn bar(x: usize) -> Option<usize> {
     return x;
     //~^ ERROR mismatched types
-    //~| expected `core::option::Option<usize>`
+    //~| expected `Option<usize>`
     //~| found `usize`
-    //~| expected enum `core::option::Option`
+    //~| expected enum `Option`
     //~| found usize
 }
 
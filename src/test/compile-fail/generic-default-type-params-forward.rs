@@ -0,0 +1,25 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that a type parameter default cannot refer to the parameter itself
+// or to a parameter declared after it.
+
+struct Forward<T = U, U = ()>(T, U);
+//~^ ERROR type parameters with a default cannot use forward declared identifiers
+
+struct Itself<T = Vec<T>>(T);
+//~^ ERROR type parameters with a default cannot use forward declared identifiers
+
+// Referring to an earlier parameter is fine.
+struct Backward<T, U = T>(T, U);
+
+fn main() {
+    let _: Backward<u8> = Backward(1, 2u8);
+}
@@ -31,7 +31,8 @@ fn give_any() {
 struct StaticInt;
 impl Foo<&'static isize> for StaticInt { }
 fn give_static() {
-    want_hrtb::<StaticInt>() //~ ERROR `for<'a> Foo<&'a isize>` is not implemented
+    want_hrtb::<StaticInt>()
+    //~^ ERROR the implementation only applies to the specific lifetime `'static`
 }
 
 fn main() { }
@@ -0,0 +1,36 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-tidy-linelength
+
+// Check the error for a higher-ranked requirement that fails because the
+// impl or closure that would satisfy it only does so for one lifetime.
+
+trait Parse<'a> {
+    fn parse(&self, s: &'a str) -> &'a str;
+}
+
+struct Static;
+
+impl Parse<'static> for Static { //~ NOTE the implementation is here
+    fn parse(&self, s: &'static str) -> &'static str { s }
+}
+
+fn want_parse<P>(_: P) where P: for<'a> Parse<'a> {}
+
+fn want_fn<F>(_: F) where F: for<'a> Fn(&'a str) -> &'a str {}
+
+fn main() {
+    want_parse(Static);
+    //~^ ERROR the requirement `for<'a> Static : Parse<'a>` is not satisfied because the implementation only applies to the specific lifetime `'static`
+
+    want_fn(|s: &'static str| s); //~ NOTE the closure is here
+    //~^ ERROR is not satisfied because the implementation only applies to the specific lifetime `'static`
+}
@@ -0,0 +1,25 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that an associated type binding on an impl method's bound that the
+// trait method does not have is reported as an extra requirement.
+
+trait Foo {
+    fn foo<I: Iterator>(&self, iter: I);
+}
+
+struct Bar;
+
+impl Foo for Bar {
+    fn foo<I: Iterator<Item=u8>>(&self, iter: I) {}
+    //~^ ERROR the requirement `<I as core::iter::Iterator>::Item == u8` appears on the impl method
+}
+
+fn main() {}
@@ -0,0 +1,33 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that region bounds on an impl method are checked against the
+// bounds of the trait method it implements.
+
+trait Foo<'a> {
+    fn type_bound<'b: 'a, T: 'b>(&self, x: &'b T);
+    fn region_bound<'b: 'a, 'c: 'a>(&self, x: &'b u8, y: &'c u8);
+    fn implied<'b: 'a, 'c: 'b>(&self, x: &'b u8, y: &'c u8);
+}
+
+struct Bar;
+
+impl<'a> Foo<'a> for Bar {
+    fn type_bound<'b: 'a, T: 'b + 'static>(&self, x: &'b T) {}
+    //~^ ERROR the requirement `T : 'static` appears on the impl method
+
+    fn region_bound<'b: 'a + 'c, 'c: 'a>(&self, x: &'b u8, y: &'c u8) {}
+    //~^ ERROR the requirement `'b : 'c` appears on the impl method
+
+    // `'c: 'a` follows from `'c: 'b` and `'b: 'a`.
+    fn implied<'b: 'a, 'c: 'b + 'a>(&self, x: &'b u8, y: &'c u8) {}
+}
+
+fn main() {}
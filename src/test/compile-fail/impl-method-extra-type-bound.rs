@@ -0,0 +1,29 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that a trait bound on an impl method that the trait method lacks
+// is reported, pointing at the bound in the impl's where clause.
+
+trait Foo {
+    fn foo<T>(&self, t: T);
+    fn bar<T: Copy>(&self, t: T);
+}
+
+struct Bar;
+
+impl Foo for Bar {
+    fn foo<T>(&self, t: T) where T: Clone {}
+    //~^ ERROR the requirement `T : core::clone::Clone` appears on the impl method
+
+    fn bar<T: Copy>(&self, t: T) where T: Copy + Clone {}
+    //~^ ERROR the requirement `T : core::clone::Clone` appears on the impl method
+}
+
+fn main() {}
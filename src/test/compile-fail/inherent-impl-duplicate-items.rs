@@ -0,0 +1,48 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Items with the same name in two inherent impls of the same type are
+// rejected, wherever the impls live.
+
+struct Foo;
+
+impl Foo {
+    fn bar(&self) {} //~ NOTE other definition for `bar` here
+    fn baz() {} //~ NOTE other definition for `baz` here
+}
+
+impl Foo {
+    fn bar(&self) {} //~ ERROR duplicate definitions with name `bar`
+}
+
+mod other {
+    impl super::Foo {
+        fn baz() {} //~ ERROR duplicate definitions with name `baz`
+    }
+}
+
+struct Bar<T>(T);
+
+impl Bar<i32> {
+    fn get(&self) -> i32 { self.0 }
+    fn new() -> Bar<i32> { Bar(0) } //~ NOTE other definition for `new` here
+}
+
+// Doesn't overlap with `Bar<i32>`, so reusing `get` is fine.
+impl Bar<u32> {
+    fn get(&self) -> u32 { self.0 }
+}
+
+impl<T: Default> Bar<T> {
+    fn new() -> Bar<T> { Bar(Default::default()) }
+    //~^ ERROR duplicate definitions with name `new`
+}
+
+fn main() {}
@@ -0,0 +1,51 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[inline(always)]
+fn countdown(n: u32) -> u32 { //~ ERROR `#[inline(always)]` function is recursive: `countdown` -> `countdown`
+    if n == 0 { 0 } else { countdown(n - 1) }
+}
+
+#[inline(always)]
+fn even(n: u32) -> bool { //~ ERROR `#[inline(always)]` function is recursive: `even` -> `odd` -> `even`
+    n == 0 || odd(n - 1)
+}
+
+#[inline(always)]
+fn odd(n: u32) -> bool {
+    n != 0 && even(n - 1)
+}
+
+// Only the functions that must be inlined matter: recursion through a
+// function that can be left out of line is fine.
+#[inline(always)]
+fn ping(n: u32) -> u32 {
+    if n == 0 { 0 } else { pong(n - 1) }
+}
+
+fn pong(n: u32) -> u32 {
+    ping(n)
+}
+
+struct Counter;
+
+impl Counter {
+    #[inline(always)]
+    fn step(n: u32) -> u32 { //~ ERROR `#[inline(always)]` function is recursive
+        if n == 0 { 0 } else { Counter::step(n - 1) }
+    }
+}
+
+fn main() {
+    countdown(3);
+    even(3);
+    ping(3);
+    Counter::step(3);
+}
@@ -0,0 +1,31 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Tests that atomic intrinsics called through a generic wrapper are checked
+// once the wrapper is instantiated.
+
+#![feature(core)]
+
+extern crate core;
+
+use core::intrinsics::atomic_load;
+
+unsafe fn load<T>(p: *const T) -> T {
+    atomic_load(p)
+    //~^ ERROR `atomic_load` can only operate on integer and raw pointer types, found `f32`
+}
+
+fn main() {
+    let x = 1.0f32;
+    unsafe {
+        load(&1usize);
+        load(&x);
+    }
+}
@@ -0,0 +1,33 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Tests that atomic intrinsics reject operands other than integers and raw
+// pointers at the call site.
+
+#![feature(core)]
+
+extern crate core;
+
+use core::intrinsics::{atomic_load, atomic_store, atomic_xadd};
+
+struct Pair(u32, u32);
+
+unsafe fn f(p: *mut f64, q: *mut Pair, r: *mut *const u8, s: *mut usize) {
+    atomic_load(p);
+    //~^ ERROR `atomic_load` can only operate on integer and raw pointer types, found `f64`
+    atomic_store(q, Pair(1, 2));
+    //~^ ERROR `atomic_store` can only operate on integer and raw pointer types, found `Pair`
+
+    // These are fine.
+    atomic_load(r);
+    atomic_xadd(s, 1);
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Tests that misspelt intrinsics are reported with the closest known name.
+
+#![feature(intrinsics)]
+
+extern "rust-intrinsic" {
+    fn size_off<T>() -> usize;
+    //~^ ERROR unrecognized intrinsic function: `size_off`
+    //~| HELP did you mean `size_of`?
+    fn atomic_lod<T>(src: *const T) -> T;
+    //~^ ERROR unrecognized atomic operation function: `lod`
+    //~| HELP did you mean `load`?
+}
+
+fn main() {}
@@ -0,0 +1,27 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Tests that intrinsics which need to know the size of their type parameter
+// cannot be declared with a `?Sized` one.
+
+#![feature(intrinsics)]
+
+extern "rust-intrinsic" {
+    fn offset<T: ?Sized>(dst: *const T, offset: isize) -> *const T;
+    //~^ ERROR type parameter `T` of intrinsic `offset` must be `Sized`
+    fn size_of<T: ?Sized>() -> usize;
+    //~^ ERROR type parameter `T` of intrinsic `size_of` must be `Sized`
+
+    // These are fine.
+    fn type_name<T: ?Sized>() -> &'static str;
+    fn min_align_of<T>() -> usize;
+}
+
+fn main() {}
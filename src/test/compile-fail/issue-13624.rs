@@ -17,9 +17,9 @@ mod a {
     Enum::EnumStructVariant { x: 1, y: 2, z: 3 }
     //~^ ERROR mismatched types
     //~| expected `()`
-    //~| found `a::Enum`
+    //~| found `Enum`
     //~| expected ()
-    //~| found enum `a::Enum`
+    //~| found enum `Enum`
   }
 }
 
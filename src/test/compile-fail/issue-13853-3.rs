@@ -9,6 +9,7 @@
 // except according to those terms.
 
 #![crate_type = "lib"]
+#![allow(non_snake_case)]
 
 use std::marker::PhantomData;
 
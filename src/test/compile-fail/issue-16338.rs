@@ -14,7 +14,7 @@ fn main() {
     let Slice { data: data, len: len } = "foo";
     //~^ ERROR mismatched types
     //~| expected `&str`
-    //~| found `core::raw::Slice<_>`
+    //~| found `Slice<_>`
     //~| expected &-ptr
-    //~| found struct `core::raw::Slice`
+    //~| found struct `Slice`
 }
@@ -8,12 +8,12 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-static FOO: usize = FOO; //~ ERROR recursive constant
+static FOO: usize = FOO; //~ ERROR cycle detected when computing value of `FOO`
 
 fn main() {
     let _x: [u8; FOO]; // caused stack overflow prior to fix
     let _y: usize = 1 + {
-        static BAR: usize = BAR; //~ ERROR recursive constant
+        static BAR: usize = BAR; //~ ERROR cycle detected when computing value of `BAR`
         let _z: [u8; BAR]; // caused stack overflow prior to fix
         1
     };
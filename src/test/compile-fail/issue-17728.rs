@@ -8,6 +8,8 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+#![allow(non_snake_case)]
+
 use std::fmt::{Debug, Formatter, Error};
 use std::collections::HashMap;
 
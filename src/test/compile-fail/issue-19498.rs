@@ -8,6 +8,8 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+#![allow(non_snake_case)]
+
 use self::A; //~ ERROR import `A` conflicts with existing submodule
 use self::B; //~ ERROR import `B` conflicts with existing submodule
 mod A {}
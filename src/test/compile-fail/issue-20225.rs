@@ -16,7 +16,7 @@ impl<'a, T> Fn<(&'a T,)> for Foo {
   type Output = ();
 
   extern "rust-call" fn call(&self, (_,): (T,)) {}
-  //~^ ERROR: has an incompatible type for trait: expected &-ptr
+  //~^ ERROR: has an incompatible type for trait: parameter 1 has type `(T,)`
 }
 
 fn main() {}
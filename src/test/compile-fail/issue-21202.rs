@@ -10,6 +10,8 @@
 
 // aux-build:issue_21202.rs
 
+#![allow(non_snake_case)]
+
 extern crate issue_21202 as crate1;
 
 use crate1::A;
@@ -8,6 +8,8 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+// compile-flags: -Z private-in-public-errors
+
 pub struct PublicType;
 struct PrivateType;
 
@@ -8,6 +8,8 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+#![allow(non_snake_case)]
+
 struct P { child: Option<Box<P>> }
 trait PTrait {
    fn getChildOption(&self) -> Option<Box<P>>;
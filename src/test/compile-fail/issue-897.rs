@@ -12,8 +12,7 @@
 
 fn f() -> ! {
     return panic!(); //~ ERROR `return` in a function declared as diverging
-    panic!(); // the unreachable statement error is in <std macro>, at this line, there
-             // only is a note
+    panic!(); //~ ERROR unreachable statement
 }
 
 fn main() { f() }
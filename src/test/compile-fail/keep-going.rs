@@ -0,0 +1,22 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z keep-going
+
+// Resolution errors that type checking can get past don't stop it from
+// reporting type errors as well.
+
+use std::collections::Missing; //~ ERROR unresolved import
+
+fn main() {
+    let x: usize = undefined_name; //~ ERROR unresolved name `undefined_name`
+    let y: usize = "not a number"; //~ ERROR mismatched types
+    let _ = x + y;
+}
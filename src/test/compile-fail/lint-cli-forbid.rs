@@ -0,0 +1,19 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -F unused-variables
+
+// `-F` on the command line cannot be lowered by attributes in the source.
+
+#[allow(unused_variables)]
+//~^ ERROR allow(unused_variables) overruled by outer forbid(unused_variables)
+fn main() {
+    let x = 1; //~ ERROR unused variable
+}
@@ -0,0 +1,42 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![deny(clone_on_copy)]
+
+#[derive(Copy, Clone)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+fn generic<T: Copy + Clone>(t: T) -> T {
+    // Whether `T` is `Copy` depends on the bounds in scope.
+    t.clone()
+}
+
+fn main() {
+    let a = 1i32;
+    let _ = a.clone(); //~ ERROR using `clone` on `i32`, which implements `Copy`
+    //~^ HELP remove the call to `clone`
+
+    let p = Point { x: 1, y: 2 };
+    let _ = p.clone(); //~ ERROR using `clone` on `Point`, which implements `Copy`
+
+    let r = &a;
+    let _ = r.clone(); //~ ERROR using `clone` on `i32`, which implements `Copy`
+    //~^ HELP dereference the receiver instead of calling `clone`
+
+    let s = String::new();
+    let _ = s.clone();
+    let v = vec![p];
+    let _ = v.clone();
+    let _ = generic(a);
+    let _ = p.x + p.y;
+}
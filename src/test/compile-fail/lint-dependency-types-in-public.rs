@@ -0,0 +1,53 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:dependency_types_inner.rs
+// aux-build:dependency_types_dep.rs
+
+// A library whose public API mentions types from its dependencies that it
+// does not reexport itself.
+
+#![crate_type = "lib"]
+#![deny(dependency_types_in_public)]
+#![allow(dead_code)]
+
+extern crate dependency_types_dep as dep;
+
+pub use dep::shapes::*;
+pub use dep::Reexported;
+
+pub fn leaks(_: dep::Widget) {}
+//~^ ERROR `dependency_types_dep::Widget` from the crate `dependency_types_dep` appears
+//~| HELP add `pub use dependency_types_dep::Widget;`, or wrap it in a type of this crate
+
+// Reexported by `dep`, but defined in a crate users may never have heard of.
+pub fn leaks_inner() -> dep::Gadget { loop {} }
+//~^ ERROR `dependency_types_inner::Gadget` from the crate `dependency_types_inner` appears
+
+pub fn trait_object(_: &dep::Render) {}
+//~^ ERROR `dependency_types_dep::Render` from the crate `dependency_types_dep` appears
+
+pub struct Holder {
+    pub widget: dep::Widget,
+    //~^ ERROR `dependency_types_dep::Widget` from the crate `dependency_types_dep` appears
+    hidden: dep::Widget,
+}
+
+pub fn reexported(_: dep::shapes::Circle, _: dep::Reexported) {}
+
+pub fn std_types(_: Vec<String>, _: Option<Box<u8>>) {}
+
+pub fn generic<T>(x: T) -> T { x }
+
+fn private(_: dep::Widget) {}
+
+mod hidden {
+    pub fn unreachable(_: ::dep::Widget) {}
+}
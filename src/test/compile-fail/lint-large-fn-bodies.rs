@@ -0,0 +1,63 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Function bodies with more expressions than `#![fn_expr_limit]` allows
+// are reported, at the outermost macro invocation if a macro wrote them.
+
+#![fn_expr_limit = "50"]
+#![deny(large_fn_bodies)]
+#![allow(dead_code)]
+
+macro_rules! sum {
+    ($($e:expr),*) => (0 $(+ $e)*)
+}
+
+macro_rules! twenty {
+    () => (sum!(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20))
+}
+
+macro_rules! make_fn {
+    ($name:ident) => (
+        fn $name() -> u32 {
+            twenty!() + twenty!()
+        }
+    )
+}
+
+macro_rules! make_fns {
+    ($($name:ident),*) => ($(make_fn!($name);)*)
+}
+
+fn small() -> u32 {
+    twenty!()
+}
+
+fn large() -> u32 { //~ ERROR expressions and will be slow to compile
+    twenty!() + twenty!()
+}
+
+#[allow(large_fn_bodies)]
+fn allowed() -> u32 {
+    twenty!() + twenty!()
+}
+
+// The closure's expressions count towards `with_closure`.
+fn with_closure() -> u32 { //~ ERROR expressions and will be slow to compile
+    let f = || twenty!();
+    f() + twenty!()
+}
+
+make_fn!(generated); //~ ERROR expressions and will be slow to compile
+
+make_fns!(first, second);
+//~^ ERROR expressions and will be slow to compile
+//~| ERROR expressions and will be slow to compile
+
+fn main() {}
@@ -0,0 +1,24 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Naming lints only need the AST, so they are still reported when the crate
+// fails to type check.
+
+#![allow(dead_code)]
+
+fn BadName() {}
+//~^ WARNING function `BadName` should have a snake case name such as `bad_name`
+
+mod SomeModule {}
+//~^ WARNING module `SomeModule` should have a snake case name such as `some_module`
+
+fn main() {
+    let x: i32 = "not a number"; //~ ERROR mismatched types
+}
@@ -1,4 +1,4 @@
-// Copyright 2012 The Rust Project Developers. See the COPYRIGHT
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
 // file at the top-level directory of this distribution and at
 // http://rust-lang.org/COPYRIGHT.
 //
@@ -8,16 +8,17 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use std::sync::mpsc::channel;
+#![deny(overlapping_patterns)]
 
 fn main() {
-    let (tx, rx) = channel();
-    let x = Some(rx);
-    tx.send(false);
+    let x: u8 = 0;
     match x {
-        Some(z) if z.recv().unwrap() => { panic!() },
-            //~^ ERROR cannot bind by-move into a pattern guard
-        Some(z) => { assert!(!z.recv().unwrap()); },
-        None => panic!()
+        0...10 => {}
+        10...20 => {} //~ ERROR this pattern overlaps with the pattern at
+        30 => {}
+        25...35 => {} //~ ERROR this pattern overlaps with the pattern at
+        40...50 if x > 1 => {}
+        45...55 => {}
+        _ => {}
     }
 }
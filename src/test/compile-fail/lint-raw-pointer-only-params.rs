@@ -0,0 +1,51 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![deny(raw_pointer_only_params)]
+#![allow(dead_code)]
+
+// Test that a type with a destructor which only mentions a type
+// parameter behind raw pointers is linted, since the drop checker
+// cannot know that it owns values of that type.
+
+use std::marker::PhantomData;
+
+struct OnlyRaw<T> { //~ ERROR type parameter `T` is only used behind raw pointers
+    ptr: *mut T,
+}
+
+impl<T> Drop for OnlyRaw<T> {
+    fn drop(&mut self) {}
+}
+
+struct WithMarker<T> {
+    ptr: *mut T,
+    marker: PhantomData<T>,
+}
+
+impl<T> Drop for WithMarker<T> {
+    fn drop(&mut self) {}
+}
+
+// No destructor, so nothing for the drop checker to get wrong.
+struct NoDrop<T> {
+    ptr: *const T,
+}
+
+enum RawEnum<T> { //~ ERROR type parameter `T` is only used behind raw pointers
+    Ptr(*const T),
+    Empty,
+}
+
+impl<T> Drop for RawEnum<T> {
+    fn drop(&mut self) {}
+}
+
+fn main() {}
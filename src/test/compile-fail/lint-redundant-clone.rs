@@ -0,0 +1,61 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![deny(redundant_clone)]
+#![allow(clone_on_copy, dead_code, unused_variables)]
+
+fn consume(_: String) {}
+
+fn last_use() {
+    let s = String::new();
+    consume(s.clone()); //~ ERROR redundant clone: `s` is not used after it is cloned
+    //~^ HELP remove the call to `clone` and move `s` instead
+}
+
+fn used_afterwards() {
+    let s = String::new();
+    consume(s.clone());
+    consume(s);
+}
+
+fn borrowed_receiver() {
+    let s = String::new();
+    let r = &s;
+    // `s` is dead after the clone, but `r` still borrows it.
+    consume(s.clone());
+    println!("{}", r);
+}
+
+fn borrowed_field() {
+    let v = (String::new(), 1);
+    let r = &v.0;
+    let _ = v.clone();
+    println!("{}", r);
+}
+
+fn behind_reference(s: &String) {
+    // `*s` cannot be moved out of.
+    consume(s.clone());
+}
+
+fn in_loop() {
+    let s = String::new();
+    loop {
+        consume(s.clone());
+    }
+}
+
+fn copy_receiver() {
+    let a = 1i32;
+    // Left to `clone_on_copy`.
+    let _ = a.clone();
+}
+
+fn main() {}
@@ -0,0 +1,44 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![deny(redundant_inline)]
+#![crate_type = "lib"]
+
+#[inline] //~ ERROR `#[inline]` is redundant on generic function `first`
+pub fn first<T: Copy>(v: &[T]) -> T {
+    v[0]
+}
+
+// `always` and `never` still change what happens to each instantiation.
+#[inline(always)]
+pub fn second<T: Copy>(v: &[T]) -> T {
+    v[1]
+}
+
+#[inline(never)]
+pub fn third<T: Copy>(v: &[T]) -> T {
+    v[2]
+}
+
+// Non-generic functions need the attribute to be inlined across crates.
+#[inline]
+pub fn fourth(v: &[u8]) -> u8 {
+    v[3]
+}
+
+// Private functions are never seen by other crates in the first place.
+#[inline]
+fn fifth<T: Copy>(v: &[T]) -> T {
+    v[4]
+}
+
+pub fn use_fifth(v: &[u8]) -> u8 {
+    fifth(v)
+}
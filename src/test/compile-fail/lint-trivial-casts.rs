@@ -0,0 +1,58 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![deny(trivial_casts, trivial_numeric_casts)]
+#![allow(dead_code)]
+
+trait Bar {}
+
+struct Foo;
+
+impl Bar for Foo {}
+
+// A cast in generic code is judged as written, not per instantiation.
+fn same<T>(p: *const T) -> *const T {
+    p as *const T //~ ERROR trivial cast: `*const T` as `*const T`
+}
+
+fn to_bytes<T>(p: *const T) -> *const u8 {
+    p as *const u8
+}
+
+#[allow(unused_typecasts)]
+fn old_name(x: i32) -> i32 {
+    x as i32
+}
+
+fn main() {
+    let x: i32 = 5;
+    let _ = x as i32; //~ ERROR trivial numeric cast: `i32` as `i32`
+    let _ = x as i64;
+
+    // The cast determines the type of the operand.
+    let _ = 5 as u8;
+    let _ = -1 as i64;
+    let _ = 1.5 as f32;
+    let y = 1;
+    let _ = y as i32;
+
+    let _ = &x as *const i32; //~ ERROR trivial cast: `&i32` as `*const i32`
+    let mut z = 0;
+    let p: *mut i32 = &mut z;
+    let _ = p as *const i32; //~ ERROR trivial cast: `*mut i32` as `*const i32`
+    let _ = p as *const u8;
+
+    let _ = &Foo as &Bar; //~ ERROR trivial cast: `&Foo` as `&Bar
+    let _ = Box::new(Foo) as Box<Bar>; //~ ERROR trivial cast: `Box<Foo>` as `Box<Bar
+
+    let b = 0u8;
+    let _ = to_bytes::<u8>(&b);
+    let _ = same(&b);
+}
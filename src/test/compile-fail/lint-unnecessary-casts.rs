@@ -8,17 +8,17 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-#![forbid(unused_typecasts)]
+#![forbid(trivial_numeric_casts)]
 
 fn foo_i32(_: i32) {}
 
 fn foo_u64(a: u64) {
     let b: i32 = a as i32;
-    foo_i32(b as i32); //~ ERROR: unnecessary type cast
+    foo_i32(b as i32); //~ ERROR: trivial numeric cast: `i32` as `i32`
 }
 
 fn main() {
     let x: u64 = 1;
-    let y: u64 = x as u64; //~ ERROR: unnecessary type cast
-    foo_u64(y as u64); //~ ERROR: unnecessary type cast
+    let y: u64 = x as u64; //~ ERROR: trivial numeric cast: `u64` as `u64`
+    foo_u64(y as u64); //~ ERROR: trivial numeric cast: `u64` as `u64`
 }
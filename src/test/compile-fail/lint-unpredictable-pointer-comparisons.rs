@@ -0,0 +1,48 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![deny(unpredictable_pointer_comparisons)]
+#![allow(dead_code)]
+
+trait Foo { fn foo(&self) {} }
+impl Foo for u8 {}
+
+fn a() {}
+fn b() {}
+
+fn fn_items() -> bool {
+    let f: fn() = a;
+    let g: fn() = b;
+    f == g //~ ERROR function pointer comparisons do not produce meaningful results
+}
+
+fn fn_pointers(f: fn(), g: fn()) -> bool {
+    f != g //~ ERROR function pointer comparisons do not produce meaningful results
+}
+
+fn object_pointers(x: &Foo, y: &Foo) -> bool {
+    let x: *const Foo = x;
+    let y: *const Foo = y;
+    x == y //~ ERROR comparing trait object pointers compares the vtable and data address
+}
+
+fn deliberate_casts(x: &Foo, y: &Foo, f: fn(), g: fn()) -> bool {
+    (x as *const Foo as *const u8) == (y as *const Foo as *const u8) &&
+    (x as *const Foo) == (y as *const Foo) &&
+    (f as usize) == (g as usize)
+}
+
+fn thin_pointers(x: &u8, y: &u8) -> bool {
+    let x: *const u8 = x;
+    let y: *const u8 = y;
+    x == y
+}
+
+fn main() {}
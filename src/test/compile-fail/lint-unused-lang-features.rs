@@ -0,0 +1,35 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![deny(unused_features)]
+#![allow(dead_code)]
+
+#![feature(intrinsics)] //~ ERROR unused feature `intrinsics`
+#![feature(box_patterns, start)]
+//~^ ERROR unused feature `start`
+
+// Only used by code that's configured away, which still counts.
+#![feature(box_syntax)]
+
+// Only checked by later passes, so never reported.
+#![feature(unboxed_closures)]
+
+fn unbox(b: Box<i32>) -> i32 {
+    match b {
+        box x => x,
+    }
+}
+
+#[cfg(not_enabled)]
+fn boxed() -> Box<i32> {
+    box 1
+}
+
+fn main() {}
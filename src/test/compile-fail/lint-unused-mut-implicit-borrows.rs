@@ -0,0 +1,56 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:unused_mut_macro.rs
+
+// Mutability used only through implicit `&mut` borrows counts as a use, and
+// bindings made by macros from other crates aren't linted.
+
+#![deny(unused_mut)]
+#![allow(unused_variables)]
+
+#[macro_use]
+extern crate unused_mut_macro;
+
+use std::collections::HashMap;
+
+struct Wrapper {
+    inner: Vec<u8>,
+}
+
+macro_rules! push_one {
+    ($v:expr) => ($v.push(1))
+}
+
+fn main() {
+    // Only mutated through a `&mut self` method.
+    let mut v = Vec::new();
+    v.push(1u8);
+
+    // Only mutated through a field's `&mut self` method.
+    let mut w = Wrapper { inner: Vec::new() };
+    w.inner.push(1);
+
+    // Only mutated through `IndexMut`.
+    let mut m = vec![0u8];
+    m[0] = 1;
+    let mut h = HashMap::new();
+    h.insert(1, 2);
+
+    // Only mutated inside a local macro expansion.
+    let mut p = Vec::new();
+    push_one!(p);
+
+    // The binding made inside the external macro is left alone.
+    let c = copied!(3);
+
+    let mut unused = 3; //~ ERROR variable does not need to be mutable
+    //~| HELP remove this `mut`
+}
@@ -0,0 +1,55 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![deny(unused_unsafe_fn)]
+#![allow(dead_code)]
+
+unsafe fn nothing_unsafe() -> u32 { 1 } //~ ERROR declared unsafe but performs no unsafe operations
+
+pub unsafe fn calls_safe() -> u32 { //~ ERROR declared unsafe but performs no unsafe operations
+    nothing_unsafe_safe()
+}
+
+fn nothing_unsafe_safe() -> u32 { 2 }
+
+unsafe fn derefs(p: *const u32) -> u32 { *p }
+
+unsafe fn calls_unsafe() -> u32 { nothing_unsafe() }
+
+unsafe fn in_block(p: *const u32) -> u32 { unsafe { *p } }
+
+unsafe fn in_closure(p: *const u32) -> u32 { (|| *p)() }
+
+struct Foo;
+
+impl Foo {
+    unsafe fn method(&self) {} //~ ERROR declared unsafe but performs no unsafe operations
+}
+
+// The unsafety of these is declared by the trait.
+trait Bar {
+    unsafe fn required(&self);
+    unsafe fn provided(&self) {}
+}
+
+impl Bar for Foo {
+    unsafe fn required(&self) {}
+}
+
+// Callable from foreign code.
+#[no_mangle]
+pub unsafe extern fn exported() {}
+
+pub unsafe extern "C" fn foreign_abi() {}
+
+#[no_mangle]
+pub unsafe fn no_mangle() {}
+
+fn main() {}
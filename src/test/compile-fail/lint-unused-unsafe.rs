@@ -60,6 +60,19 @@ fn good2() {
 
 unsafe fn good3() { foo::bar() }
 fn good4() { unsafe { foo::bar() } }
+fn good5(p: *const u8) -> u8 { unsafe { *p } }
+fn good6(p: *const u8) {
+    // compiler-generated `unsafe` blocks inside `println!` don't hide this use
+    unsafe { println!("{}", *p) }
+}
+
+fn bad8(p: *const u8) -> u8 {
+    unsafe {
+        let x = *p;
+        unsafe { x + *p }                //~ ERROR: unnecessary `unsafe` block
+    }
+}
+fn bad9() { unsafe { println!("") } }   //~ ERROR: unnecessary `unsafe` block
 
 #[allow(unused_unsafe)] fn allowed() { unsafe {} }
 
@@ -8,7 +8,9 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-#![deny(visible_private_types)]
+// compile-flags: -Z private-in-public-errors
+
+#![deny(private_in_public)]
 #![allow(dead_code)]
 #![crate_type="lib"]
 
@@ -0,0 +1,76 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Writing to part of a local does not count as reading it, so writes that
+// are never read back are reported.
+
+#![deny(unused_assignments)]
+#![allow(unused_variables, dead_code)]
+
+struct Config { x: i32, y: i32 }
+
+struct Logged { x: i32 }
+
+impl Drop for Logged {
+    fn drop(&mut self) {
+        println!("{}", self.x);
+    }
+}
+
+fn fields() {
+    let mut cfg = Config { x: 0, y: 0 };
+    cfg.x = 1; //~ ERROR value assigned to `cfg` is never read
+    cfg.y = 2; //~ ERROR value assigned to `cfg` is never read
+}
+
+fn fields_read() -> i32 {
+    let mut cfg = Config { x: 0, y: 0 };
+    cfg.x = 1;
+    cfg.y = 2;
+    cfg.x + cfg.y
+}
+
+fn nested() {
+    let mut t = (Config { x: 0, y: 0 }, 0);
+    t.1 = 1; //~ ERROR value assigned to `t` is never read
+    t.0.x = 2; //~ ERROR value assigned to `t` is never read
+}
+
+fn array_index(i: usize) {
+    let mut a = [0; 4];
+    a[i] = 1; //~ ERROR value assigned to `a` is never read
+}
+
+fn array_index_read(i: usize) -> i32 {
+    let mut a = [0; 4];
+    a[i] = 1;
+    a[0]
+}
+
+fn read_in_next_iteration() -> i32 {
+    let mut cfg = Config { x: 0, y: 0 };
+    let mut sum = 0;
+    for i in 0..3 {
+        sum += cfg.x;
+        cfg.x = i;
+    }
+    sum
+}
+
+fn drop_is_a_use() {
+    let mut l = Logged { x: 0 };
+    l.x = 1;
+}
+
+fn through_a_reference(cfg: &mut Config) {
+    cfg.x = 1;
+}
+
+fn main() {}
@@ -0,0 +1,30 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// The token opening a delimited group is what follows a fragment, and a
+// sequence without a separator can be followed by its own first token.
+
+macro_rules! delimited {
+    ($e:expr ( )) => (); //~ ERROR `$e:expr` is followed by `(`, which is not allowed for `expr`
+    ($e:expr [ ]) => (); //~ ERROR `$e:expr` is followed by `[`, which is not allowed for `expr`
+    ($s:stmt { }) => (); //~ ERROR `$s:stmt` is followed by `{`, which is not allowed for `stmt`
+    ($p:pat ( )) => (); //~ ERROR `$p:pat` is followed by `(`, which is not allowed for `pat`
+    ($t:ty [ ]) => (); //~ ERROR `$t:ty` is followed by `[`, which is not allowed for `ty`
+    ($t:ty { }) => ();
+    ($i:ident ( )) => ();
+}
+
+macro_rules! repeated {
+    ($(- $e:expr)*) => (); //~ ERROR `$e:expr` is followed by `-`, which is not allowed for `expr`
+    ($(, $e:expr)*) => ();
+    ($(- $t:ty)* ,) => (); //~ ERROR `$t:ty` is followed by `-`, which is not allowed for `ty`
+}
+
+fn main() { }
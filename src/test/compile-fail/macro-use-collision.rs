@@ -0,0 +1,24 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:two_macros.rs
+// aux-build:other_two_macros.rs
+// ignore-stage1
+
+#[macro_use]
+extern crate two_macros; //~ NOTE `macro_two` is first imported from `two_macros` here
+
+#[macro_use]
+extern crate other_two_macros;
+//~^ ERROR macro `macro_two` is imported from both `two_macros` and `other_two_macros`
+
+pub fn main() {
+    macro_three!();
+}
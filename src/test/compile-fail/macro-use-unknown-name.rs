@@ -0,0 +1,28 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:two_macros.rs
+// ignore-stage1
+
+#![feature(macro_reexport)]
+
+#[macro_use(macro_one, macro_tow)]
+//~^ ERROR imported macro not found
+//~| HELP did you mean `macro_two`?
+//~| NOTE `two_macros` exports `macro_one`, `macro_two`
+#[macro_reexport(macro_oen)]
+//~^ ERROR reexported macro not found
+//~| HELP did you mean `macro_one`?
+//~| NOTE `two_macros` exports `macro_one`, `macro_two`
+extern crate two_macros;
+
+pub fn main() {
+    macro_one!();
+}
@@ -0,0 +1,23 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Matching gives up once it has explored more positions than
+// `#![macro_work_limit]` allows, instead of running on indefinitely.
+
+#![macro_work_limit = "20"]
+
+macro_rules! idents {
+    ($($i:ident)*) => ()
+}
+
+idents! { a b c d e f g h i j k l m n o p q r s t u v w x y }
+//~^ ERROR macro expansion exceeds work limit while matching this rule
+
+fn main() {}
@@ -0,0 +1,43 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+fn main() {
+    let x: u8 = 0;
+    match x { //~ ERROR non-exhaustive patterns: `_` not covered
+        0...127 => {}
+        129...255 => {}
+    }
+
+    // The upper end of `i16` is missing.
+    let x: i16 = 0;
+    match x { //~ ERROR non-exhaustive patterns: `_` not covered
+        -32768...-1 => {}
+        0...32766 => {}
+    }
+
+    // `\u{E000}` is a valid `char`.
+    let c = 'a';
+    match c { //~ ERROR non-exhaustive patterns: `_` not covered
+        '\u{0}'...'\u{D7FF}' => {}
+        '\u{E001}'...'\u{10FFFF}' => {}
+    }
+
+    // The range of pointer-sized integers depends on the target.
+    let x: usize = 0;
+    match x { //~ ERROR non-exhaustive patterns: `_` not covered
+        0...18446744073709551615 => {}
+    }
+
+    let x = (false, 0u8);
+    match x { //~ ERROR non-exhaustive patterns: `(false, _)` not covered
+        (true, 0...99) => {}
+        (_, 100...255) => {}
+    }
+}
@@ -0,0 +1,27 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+fn main() {
+    let x: u8 = 0;
+    match x {
+        0...100 => {}
+        50...150 => {}
+        20...120 => {} //~ ERROR unreachable pattern
+        151...255 => {}
+        _ => {} //~ ERROR unreachable pattern
+    }
+
+    let c = 'a';
+    match c {
+        '\u{0}'...'\u{D7FF}' => {}
+        '\u{E000}'...'\u{10FFFF}' => {}
+        _ => {} //~ ERROR unreachable pattern
+    }
+}
@@ -0,0 +1,25 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// A directory given to `#[path]` loads the `mod.rs` inside it.
+#[path = "mod_dir_nested_inline_aux"]
+mod nested;
+
+mod outer {
+    mod inner {
+        mod missing; //~ ERROR file not found for module `missing`
+        //~^ HELP outer/inner/missing.rs and
+        //~^^ HELP add `#[path = "..."]` to load `missing` from a different file or directory
+    }
+}
+
+fn main() {
+    nested::present::present();
+}
@@ -0,0 +1,32 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(optin_builtin_traits)]
+
+// Negative impls are only meaningful for traits with a default impl;
+// for any other trait they are rejected rather than silently ignored.
+
+use std::marker::MarkerTrait;
+
+trait Plain : MarkerTrait {}
+
+struct Foo;
+
+impl !Plain for Foo {}
+//~^ ERROR negative impls are only allowed for traits with default impls
+
+impl !Clone for Foo {}
+//~^ ERROR negative impls are only allowed for traits with default impls
+
+impl !Send for Foo {}
+
+impl !Sync for Foo {}
+
+fn main() {}
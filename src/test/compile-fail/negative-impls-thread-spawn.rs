@@ -0,0 +1,28 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(optin_builtin_traits)]
+
+// Test that a type with a negative `Send` impl cannot be moved into a
+// spawned thread.
+
+use std::thread;
+
+struct NotSend;
+
+impl !Send for NotSend {}
+
+fn main() {
+    let x = NotSend;
+    thread::spawn(move || {
+    //~^ ERROR the trait `core::marker::Send` is not implemented for the type `NotSend`
+        let _x = x;
+    });
+}
@@ -0,0 +1,32 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(optin_builtin_traits)]
+
+// Test that a negative impl with a where clause applies to the types
+// that satisfy the where clause.
+
+use std::marker::{MarkerTrait, PhantomData};
+
+trait Local : MarkerTrait {}
+
+impl Local for u8 {}
+
+struct Wrapper<T>(PhantomData<T>);
+
+impl<T: Local> !Send for Wrapper<T> {}
+
+fn is_send<T: Send>() {}
+
+fn main() {
+    is_send::<Wrapper<u16>>();
+    is_send::<Wrapper<u8>>();
+    //~^ ERROR the trait `core::marker::Send` is not implemented for the type `Wrapper<u8>`
+}
@@ -28,37 +28,40 @@ mod foo {
 }
 
 fn main() {
+//~^ HELP candidate #1: use `foo::Bar`
+//~| HELP candidate #2: use `no_method_suggested_traits::foo::PubPub`
+//~| HELP candidate #1: use `foo::Bar`
+//~| HELP candidate #2: use `no_method_suggested_traits::foo::PubPub`
+//~| HELP candidate #1: use `foo::Bar`
+//~| HELP candidate #1: use `foo::Bar`
+//~| HELP candidate #1: use `no_method_suggested_traits::foo::PubPub`
+//~| HELP candidate #1: use `no_method_suggested_traits::foo::PubPub`
+    // The `use` suggestions for traits that are implemented but not in
+    // scope point at the start of `main`, where the `use` could go.
+
     // test the values themselves, and autoderef.
 
 
     1u32.method();
     //~^ HELP following traits are implemented but not in scope, perhaps add a `use` for one of them
     //~^^ ERROR does not implement
-    //~^^^ HELP `foo::Bar`
-    //~^^^^ HELP `no_method_suggested_traits::foo::PubPub`
     std::rc::Rc::new(&mut Box::new(&1u32)).method();
     //~^ HELP following traits are implemented but not in scope, perhaps add a `use` for one of them
     //~^^ ERROR does not implement
-    //~^^^ HELP `foo::Bar`
-    //~^^^^ HELP `no_method_suggested_traits::foo::PubPub`
 
     'a'.method();
     //~^ ERROR does not implement
     //~^^ HELP the following trait is implemented but not in scope, perhaps add a `use` for it:
-    //~^^^ HELP `foo::Bar`
     std::rc::Rc::new(&mut Box::new(&'a')).method();
     //~^ ERROR does not implement
     //~^^ HELP the following trait is implemented but not in scope, perhaps add a `use` for it:
-    //~^^^ HELP `foo::Bar`
 
     1i32.method();
     //~^ ERROR does not implement
     //~^^ HELP the following trait is implemented but not in scope, perhaps add a `use` for it:
-    //~^^^ HELP `no_method_suggested_traits::foo::PubPub`
     std::rc::Rc::new(&mut Box::new(&1i32)).method();
     //~^ ERROR does not implement
     //~^^ HELP the following trait is implemented but not in scope, perhaps add a `use` for it:
-    //~^^^ HELP `no_method_suggested_traits::foo::PubPub`
 
     Foo.method();
     //~^ ERROR does not implement
@@ -0,0 +1,47 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that a region error caused by an object lifetime bound that
+// defaulted to `'static` explains where the `'static` came from, and that
+// the defaults which do not involve `'static` are not mentioned.
+
+#![allow(dead_code)]
+
+trait Shape {
+    fn area(&self) -> usize;
+}
+
+// No context: `Box<Shape>` means `Box<Shape + 'static>`.
+struct Owned {
+    shape: Box<Shape>,
+}
+
+// A lifetime bound on the type parameter supplies the default.
+struct Holder<'a, T: ?Sized + 'a> {
+    value: &'a T,
+}
+
+fn owned<'a>(shape: Box<Shape + 'a>) -> Owned {
+    Owned { shape: shape }
+    //~^ ERROR mismatched types
+    //~| NOTE `Shape` written without a lifetime bound defaults to `Shape + 'static`
+}
+
+// `&'a Shape` means `&'a (Shape + 'a)`, so this is fine.
+fn reference<'a>(shape: &'a (Shape + 'a)) -> &'a Shape {
+    shape
+}
+
+// `Holder<'a, Shape>` means `Holder<'a, Shape + 'a>`, so this is fine too.
+fn held<'a>(value: &'a (Shape + 'a)) -> Holder<'a, Shape> {
+    Holder { value: value }
+}
+
+fn main() {}
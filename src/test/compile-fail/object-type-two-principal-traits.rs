@@ -0,0 +1,30 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that an object type names exactly one non-builtin trait, whether
+// the extra trait is written in a `+` sum or in a bounds list.
+
+trait Foo {}
+trait Bar {}
+
+fn sum(_: Box<Foo + Bar>) {}
+//~^ ERROR only one non-builtin trait can be used in an object type; `Bar` is not a builtin trait
+
+fn sum_with_builtin(_: Box<Foo + Send + Bar + 'static>) {}
+//~^ ERROR `Bar` is not a builtin trait
+
+fn reference<'a>(_: &'a (Foo + Sync + Bar + 'a)) {}
+//~^ ERROR `Bar` is not a builtin trait
+
+fn three(_: Box<Foo + Bar + Clone>) {}
+//~^ ERROR `Bar` is not a builtin trait
+//~| ERROR `Clone` is not a builtin trait
+
+fn main() {}
@@ -0,0 +1,68 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Borrowing a field of a packed struct may create a misaligned reference,
+// so it requires an unsafe block, however the borrow is written.
+
+#[repr(packed)]
+struct Packed {
+    a: u8,
+    b: u32,
+    inner: Inner,
+}
+
+#[repr(packed)]
+struct PackedTuple(u8, u32);
+
+#[derive(Copy)]
+struct Inner {
+    x: u32,
+}
+
+struct Outer {
+    tag: u8,
+    packed: Packed,
+}
+
+fn main() {
+    let mut p = Packed { a: 1, b: 2, inner: Inner { x: 3 } };
+
+    let _ = &p.b; //~ ERROR borrow of packed field requires unsafe function or block
+    let _ = &mut p.b; //~ ERROR borrow of packed field requires unsafe function or block
+    let _ = &(p.b); //~ ERROR borrow of packed field requires unsafe function or block
+
+    // The whole of the inline `Inner` is as misaligned as `p` leaves it.
+    let _ = &p.inner; //~ ERROR borrow of packed field requires unsafe function or block
+    let _ = &p.inner.x; //~ ERROR borrow of packed field requires unsafe function or block
+
+    let o = Outer { tag: 0, packed: Packed { a: 1, b: 2, inner: Inner { x: 3 } } };
+    let _ = &o.packed.b; //~ ERROR borrow of packed field requires unsafe function or block
+
+    let boxed = Box::new(Packed { a: 1, b: 2, inner: Inner { x: 3 } });
+    let _ = &boxed.b; //~ ERROR borrow of packed field requires unsafe function or block
+    let r = &p;
+    let _ = &r.b; //~ ERROR borrow of packed field requires unsafe function or block
+
+    // Method calls borrow their receiver implicitly.
+    let _ = p.b.clone(); //~ ERROR borrow of packed field requires unsafe function or block
+
+    match p.b { //~ ERROR borrow of packed field requires unsafe function or block
+        ref _b => {}
+    }
+    let ref _b = p.b; //~ ERROR borrow of packed field requires unsafe function or block
+    let ref mut _b = p.b; //~ ERROR borrow of packed field requires unsafe function or block
+
+    match p {
+        Packed { ref b, .. } => { let _ = b; }
+        //~^ ERROR borrow of packed field requires unsafe function or block
+    }
+    let PackedTuple(_, ref mut _b) = PackedTuple(1, 2);
+    //~^ ERROR borrow of packed field requires unsafe function or block
+}
@@ -8,6 +8,8 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+// compile-flags: -Z private-in-public-errors
+
 // Test that we properly check for private types in public signatures, even
 // inside a private module (#22261).
 
@@ -13,6 +13,7 @@
 
 #![allow(dead_code)]
 #![allow(unused_imports)]
+#![allow(non_snake_case)]
 
 
 // public type, private value
@@ -0,0 +1,54 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Private types and traits in the signatures of exported items are reported
+// through the `private_in_public` lint, with a note at the private definition.
+
+#![deny(private_in_public)]
+#![allow(dead_code)]
+#![crate_type = "lib"]
+
+struct Secret; //~ NOTE `Secret` is declared here without `pub`
+
+trait Hidden { //~ NOTE through the exported item `bounded`
+    fn dummy(&self) { }
+}
+
+pub fn make() -> Secret { Secret } //~ ERROR private type in exported type signature
+
+pub struct Holder {
+    pub secret: Secret, //~ ERROR private type in exported type signature
+    kept: Secret,
+}
+
+pub enum Either {
+    Left(Secret), //~ ERROR private type in exported type signature
+    Right,
+}
+
+pub fn bounded<T>(_: T) where T: Hidden {}
+//~^ ERROR private trait in exported type parameter bound
+
+pub type Alias = Option<Secret>; //~ ERROR private type in exported type signature
+
+type PrivateAlias = Option<Secret>;
+
+fn private_fn() -> Secret { Secret }
+
+mod inner {
+    struct Inner;
+
+    // Not reachable from outside the crate, but still public, so its
+    // signature is checked too.
+    pub fn leak() -> Inner { Inner } //~ ERROR private type in exported type signature
+}
+
+#[allow(private_in_public)]
+pub fn allowed() -> Secret { Secret }
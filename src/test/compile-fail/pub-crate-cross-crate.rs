@@ -0,0 +1,21 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:pub_crate_items.rs
+
+extern crate pub_crate_items;
+
+fn main() {
+    pub_crate_items::internal();
+    //~^ ERROR function `internal` is crate-visible
+    //~| NOTE items declared `pub(crate)` can only be used within the crate that defines them
+    let e = pub_crate_items::make();
+    let _ = e.field; //~ ERROR field `field` of struct `pub_crate_items::Exported` is private
+}
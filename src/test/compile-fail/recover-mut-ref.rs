@@ -0,0 +1,22 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(recover)]
+
+use std::panic;
+
+fn main() {
+    let mut count = 0;
+    let count = &mut count;
+    let _ = panic::recover(move || {
+        //~^ ERROR the trait `std::panic::RecoverSafe` is not implemented
+        *count += 1;
+    });
+}
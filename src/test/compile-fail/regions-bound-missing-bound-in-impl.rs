@@ -51,6 +51,7 @@ impl<'a, 't> Foo<'a, 't> for &'a isize {
     }
 
     fn another_bound<'x: 't>(self, x: Inv<'x>, y: Inv<'t>) {}
+    //~^ ERROR the requirement `'x : 't` appears on the impl method
 }
 
 fn main() { }
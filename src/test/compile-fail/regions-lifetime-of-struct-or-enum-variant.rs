@@ -8,6 +8,8 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+#![allow(non_snake_case)]
+
 // This tests verifies that unary structs and enum variants
 // are treated as rvalues and their lifetime is not bounded to
 // the static scope.
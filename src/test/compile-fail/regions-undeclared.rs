@@ -8,6 +8,8 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+#![allow(non_snake_case)]
+
 static c_x: &'blk isize = &22; //~ ERROR use of undeclared lifetime name `'blk`
 
 enum EnumDecl {
@@ -47,12 +47,12 @@ fn main() {
     //~| found `isize`
     //~| expected usize
     //~| found isize
-    //~| ERROR expected positive integer for repeat count, found negative integer
+    //~| ERROR expected usize, found negative value
     let f = [0_usize; -1_isize];
     //~^ ERROR mismatched types
     //~| expected `usize`
     //~| found `isize`
     //~| expected usize
     //~| found isize
-    //~| ERROR expected positive integer for repeat count, found negative integer
+    //~| ERROR expected usize, found negative value
 }
@@ -0,0 +1,32 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that an enum may only be given one discriminant representation.
+
+#[repr(u8, u16)] //~ ERROR conflicting representation hints
+enum Ints { //~^ NOTE conflicts with the representation hint here
+    A,
+    B,
+}
+
+#[repr(C)] //~ NOTE conflicts with the representation hint here
+#[repr(i32)] //~ ERROR conflicting representation hints
+enum CAndInt {
+    A,
+    B,
+}
+
+#[repr(u8, u8)]
+enum Repeated {
+    A,
+    B,
+}
+
+fn main() {}
@@ -0,0 +1,32 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that integer representation hints are rejected on enums that
+// are not C-like.
+
+#[repr(u8)] //~ ERROR integer representation hints are not supported for enums with fields
+enum Tuple {
+    A(u32),
+    B,
+}
+
+#[repr(i32)] //~ ERROR integer representation hints are not supported for enums with fields
+enum Struct {
+    A { x: u32 },
+    B,
+}
+
+#[repr(u8)]
+enum CLike {
+    A,
+    B,
+}
+
+fn main() {}
@@ -0,0 +1,34 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(rustc_attrs)]
+#![allow(dead_code)]
+
+// Test that representation hints which do not affect the item they
+// are attached to are warned about.
+
+#[repr(C)] //~ WARNING `#[repr]` has no effect on a function
+fn foo() {}
+
+#[repr(u8)] //~ WARNING `#[repr]` has no effect on a static item
+static BAR: u8 = 0;
+
+#[repr(u32)] //~ WARNING integer representation hints have no effect on a struct
+struct Baz {
+    x: u32,
+}
+
+#[repr(C)]
+struct Fine {
+    x: u32,
+}
+
+#[rustc_error]
+fn main() { } //~ ERROR compilation successful
@@ -0,0 +1,29 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that the error for a closure that is not `Send` names the
+// captured variable responsible.
+
+use std::rc::Rc;
+use std::thread;
+
+struct State {
+    counter: Rc<u32>,
+}
+
+fn main() {
+    let state = State { counter: Rc::new(0) };
+    thread::spawn(move || {
+    //~^ ERROR the trait `core::marker::Send` is not implemented for the type `Rc<u32>`
+    //~| NOTE required because it appears within the field `counter` of `State`
+    //~| NOTE required because it appears within the closure's captured variable `state`
+        let _ = state.counter;
+    });
+}
@@ -0,0 +1,36 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that the error for a type that is not `Send` explains where in
+// the type the offending component lives.
+
+use std::rc::Rc;
+
+struct Cache {
+    entries: Rc<u32>,
+}
+
+struct Inner {
+    cache: Cache,
+}
+
+struct Outer {
+    inner: Inner,
+}
+
+fn is_send<T: Send>() {}
+
+fn main() {
+    is_send::<Outer>();
+    //~^ ERROR within `Outer`, the trait `core::marker::Send` is not implemented for the type `Rc<u32>`
+    //~| NOTE required because it appears within the field `entries` of `Cache`
+    //~| NOTE required because it appears within the field `cache` of `Inner`
+    //~| NOTE required because it appears within the field `inner` of `Outer`
+}
@@ -8,23 +8,24 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-// Test that shadowed lifetimes generate an error.
+// Test that shadowed lifetimes are linted, pointing at both declarations.
+
+#![deny(shadowed_lifetimes)]
+#![allow(dead_code, unused_variables, unreachable_code)]
 
 struct Foo<'a>(&'a isize);
 
 impl<'a> Foo<'a> {
     //~^ NOTE shadowed lifetime `'a` declared here
     fn shadow_in_method<'a>(&'a self) -> &'a isize {
-        //~^ WARNING lifetime name `'a` shadows another lifetime name that is already in scope
-        //~| NOTE deprecated
+        //~^ ERROR lifetime name `'a` shadows another lifetime name that is already in scope
         self.0
     }
 
     fn shadow_in_type<'b>(&'b self) -> &'b isize {
         //~^ NOTE shadowed lifetime `'b` declared here
         let x: for<'b> fn(&'b isize) = panic!();
-        //~^ WARNING lifetime name `'b` shadows another lifetime name that is already in scope
-        //~| NOTE deprecated
+        //~^ ERROR lifetime name `'b` shadows another lifetime name that is already in scope
         self.0
     }
 
@@ -34,10 +35,11 @@ impl<'a> Foo<'a> {
     }
 }
 
+#[allow(shadowed_lifetimes)]
+fn allowed<'a>(x: &'a isize) -> for<'a> fn(&'a isize) {
+    // No lint here, and no notes either.
+    panic!()
+}
+
 fn main() {
-    // intentional error that occurs after `resolve_lifetime` runs,
-    // just to ensure that this test fails to compile; when shadowed
-    // lifetimes become either an error or a proper lint, this will
-    // not be needed.
-    let x: isize = 3_usize; //~ ERROR mismatched types
 }
@@ -8,13 +8,15 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-// Test that shadowed lifetimes generate an error.
+// Test that shadowed type parameters generate an error pointing at both
+// declarations.
 
 #![feature(box_syntax)]
 
 struct Foo<T>(T);
 
 impl<T> Foo<T> {
+//~^ NOTE the shadowed type parameter `T` is declared here
     fn shadow_in_method<T>(&self) {}
     //~^ ERROR type parameter `T` shadows another type parameter
 
@@ -25,6 +27,8 @@ impl<T> Foo<T> {
 }
 
 trait Bar<T> {
+//~^ NOTE the shadowed type parameter `T` is declared here
+//~| NOTE the shadowed type parameter `T` is declared here
     fn dummy(&self) -> T;
 
     fn shadow_in_required<T>(&self);
@@ -37,4 +41,19 @@ trait Bar<T> {
     fn not_shadow_in_provided<U>(&self) {}
 }
 
+impl<U> Foo<U> {
+    fn only_second_shadows<V, U>(&self) {}
+    //~^ ERROR type parameter `U` shadows another type parameter
+}
+
+trait Qux<A> {
+    fn method<B>(&self);
+}
+
+impl<B> Qux<B> for Foo<B> {
+//~^ NOTE the shadowed type parameter `B` is declared here
+    fn method<B>(&self) {}
+    //~^ ERROR type parameter `B` shadows another type parameter
+}
+
 fn main() {}
@@ -0,0 +1,28 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Indexing into a string never yields a mutable place, whichever way the
+// mutation is attempted.
+
+fn via_deref(s: &mut str) {
+    let _t = &mut (*s)[..]; //~ ERROR cannot borrow immutable indexed content as mutable
+}
+
+fn via_string(s: &mut String) {
+    let _t = &mut s[1..]; //~ ERROR cannot borrow immutable indexed content as mutable
+}
+
+fn via_binding(s: &mut str) {
+    match (*s)[..] {
+        ref mut _t => {} //~ ERROR cannot borrow immutable indexed content as mutable
+    }
+}
+
+fn main() {}
@@ -0,0 +1,40 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that a struct literal naming or leaving out private fields reports
+// them in a single error.
+
+mod geometry {
+    pub struct Rect {
+        pub name: String,
+        width: u32,
+        height: u32,
+        label: String,
+    }
+
+    pub fn unit(name: String) -> Rect {
+        Rect { name: name, width: 1, height: 1, label: String::new() }
+    }
+}
+
+fn main() {
+    let _ = geometry::Rect { name: String::new(), width: 1, height: 2, label: String::new() };
+    //~^ ERROR fields `width`, `height`, `label` of struct `geometry::Rect` are private
+    //~| NOTE consider using a constructor function
+
+    // `label` is moved out of the base, so this one is fine...
+    let base = geometry::unit(String::new());
+    let _ = geometry::Rect { name: String::new(), ..base };
+
+    // ...but not when the private fields are set explicitly.
+    let base = geometry::unit(String::new());
+    let _ = geometry::Rect { name: String::new(), width: 2, ..base };
+    //~^ ERROR fields `width`, `height`, `label` of struct `geometry::Rect` are private
+}
@@ -0,0 +1,28 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:tuple_struct_ctor_xc.rs
+
+// A tuple struct constructor from another crate requires both the struct
+// and all of its fields to be visible, whether it is called or used as a
+// function value.
+
+extern crate tuple_struct_ctor_xc;
+
+use tuple_struct_ctor_xc::Sealed;
+
+fn main() {
+    let _ = Sealed(1); //~ ERROR cannot invoke tuple struct constructor with private fields
+    let _: Vec<Sealed> = (1..3).map(Sealed).collect();
+    //~^ ERROR cannot invoke tuple struct constructor with private fields
+
+    let _ = tuple_struct_ctor_xc::Hidden(1); //~ ERROR is private
+    let _ = tuple_struct_ctor_xc::Hidden; //~ ERROR is private
+}
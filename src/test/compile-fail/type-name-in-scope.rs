@@ -0,0 +1,44 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Types that are in scope by name where an error is reported are printed
+// by that name; others keep their full path.
+
+use std::vec::Vec;
+use std::string::String;
+
+mod inner {
+    pub struct Local;
+
+    fn f(x: Local) -> () {
+        x
+        //~^ ERROR mismatched types
+        //~| expected `()`
+        //~| found `Local`
+    }
+}
+
+fn g(x: Vec<String>) -> u32 {
+    x
+    //~^ ERROR mismatched types
+    //~| expected `u32`
+    //~| found `Vec<String>`
+    //~| expected u32
+    //~| found struct `Vec`
+}
+
+fn h(x: inner::Local) -> u32 {
+    x
+    //~^ ERROR mismatched types
+    //~| expected `u32`
+    //~| found `inner::Local`
+}
+
+fn main() {}
@@ -43,8 +43,8 @@ fn main() {
     //~^ ERROR the trait `core::marker::Sync` is not implemented for the type `MyNotSync`
 
     is_sync::<MyTypeWUnsafe>();
-    //~^ ERROR the trait `core::marker::Sync` is not implemented for the type `core::cell::UnsafeCell<u8>`
+    //~^ ERROR the trait `core::marker::Sync` is not implemented for the type `UnsafeCell<u8>`
 
     is_sync::<MyTypeManaged>();
-    //~^ ERROR the trait `core::marker::Sync` is not implemented for the type `core::marker::Managed`
+    //~^ ERROR the trait `core::marker::Sync` is not implemented for the type `Managed`
 }
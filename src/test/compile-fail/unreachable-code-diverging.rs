@@ -0,0 +1,69 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![deny(unreachable_code)]
+#![allow(unused_variables)]
+
+fn diverge() -> ! {
+    panic!()
+}
+
+fn after_return() {
+    return; //~ NOTE any code following this expression is unreachable
+    let a = 1; //~ ERROR unreachable statement
+    let b = 2;
+}
+
+fn after_panic() {
+    panic!(); //~ NOTE any code following this expression is unreachable
+    println!("unreachable"); //~ ERROR unreachable statement
+}
+
+fn after_loop() -> u32 {
+    loop {} //~ NOTE any code following this expression is unreachable
+    1 //~ ERROR unreachable expression
+}
+
+fn after_diverging_call() {
+    diverge(); //~ NOTE any code following this expression is unreachable
+    let a = 1; //~ ERROR unreachable statement
+}
+
+fn after_diverging_match(x: Option<u32>) {
+    match x { //~ NOTE any code following this expression is unreachable
+        Some(_) => return,
+        None => panic!(),
+    }
+    let a = 1; //~ ERROR unreachable statement
+}
+
+fn after_converging_match(x: Option<u32>) {
+    match x {
+        Some(_) => return,
+        None => {}
+    }
+    let a = 1;
+}
+
+macro_rules! return_early {
+    () => {{ return; let _unused = 1; }}
+}
+
+fn inside_macro() {
+    // The unreachable statement is generated by the macro itself.
+    return_early!();
+}
+
+fn after_macro() {
+    return_early!(); //~ NOTE any code following this expression is unreachable
+    let a = 1; //~ ERROR unreachable statement
+}
+
+fn main() {}
@@ -0,0 +1,42 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Calls through a vector table of unsafe fns stored in a `static mut` need
+// `unsafe` for the call itself, however the callee was loaded.
+
+unsafe extern "C" fn reset() {}
+unsafe extern "C" fn nmi() {}
+
+static mut HANDLERS: [unsafe extern "C" fn(); 2] = [reset, nmi];
+
+fn one_statement(i: usize) {
+    unsafe { HANDLERS[i]() }
+}
+
+fn copied(i: usize) {
+    let handler = unsafe { HANDLERS[i] };
+    handler(); //~ ERROR call to unsafe function requires unsafe function or block
+}
+
+fn by_reference(i: usize) {
+    let handler = unsafe { &HANDLERS[i] };
+    handler(); //~ ERROR call to unsafe function requires unsafe function or block
+}
+
+fn boxed(i: usize) {
+    let handler = Box::new(unsafe { HANDLERS[i] });
+    handler(); //~ ERROR call to unsafe function requires unsafe function or block
+}
+
+fn static_read(i: usize) {
+    let _handler = HANDLERS[i]; //~ ERROR use of mutable static requires unsafe function or block
+}
+
+fn main() {}
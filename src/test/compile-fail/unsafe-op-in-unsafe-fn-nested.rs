@@ -0,0 +1,58 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// `unsafe_op_in_unsafe_fn` in closures and nested fns, its interaction with
+// `unused_unsafe`, and its level being controlled per item.
+
+#![allow(dead_code)]
+#![deny(unused_unsafe)]
+
+unsafe fn f() {}
+
+// The lint is off by default: no blocks are needed, so one that is written
+// is unnecessary, exactly as before.
+unsafe fn off_by_default() {
+    f();
+    unsafe { f() } //~ ERROR unnecessary `unsafe` block
+}
+
+#[deny(unsafe_op_in_unsafe_fn)]
+mod denied {
+    use super::f;
+
+    unsafe fn closures() {
+        let c = || f(); //~ ERROR call to unsafe function is unsafe and requires an `unsafe` block
+        c();
+        let d = || unsafe { f() };
+        d();
+    }
+
+    // Compiler-generated unsafe blocks are unaffected.
+    unsafe fn macros() {
+        println!("{}", 1);
+    }
+
+    #[allow(unsafe_op_in_unsafe_fn)]
+    unsafe fn allowed() {
+        f();
+        unsafe { f() } //~ ERROR unnecessary `unsafe` block
+
+        #[deny(unsafe_op_in_unsafe_fn)]
+        unsafe fn nested_denied() {
+            f(); //~ ERROR call to unsafe function is unsafe and requires an `unsafe` block
+        }
+
+        fn nested_safe() {
+            unsafe { f() }
+        }
+    }
+}
+
+fn main() {}
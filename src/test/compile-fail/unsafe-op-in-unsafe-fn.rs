@@ -0,0 +1,79 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// With `unsafe_op_in_unsafe_fn` enabled, each unsafe operation in the body of
+// an unsafe fn needs its own `unsafe` block.
+
+#![feature(asm)]
+#![deny(unsafe_op_in_unsafe_fn)]
+#![deny(unused_unsafe)]
+#![allow(dead_code)]
+
+extern {
+    fn ext();
+}
+
+static mut COUNTER: int = 0;
+
+unsafe fn raw_deref(p: *const int) -> int {
+    *p //~ ERROR dereference of unsafe pointer is unsafe and requires an `unsafe` block
+}
+
+unsafe fn mut_static() {
+    COUNTER += 1; //~ ERROR use of mutable static is unsafe and requires an `unsafe` block
+}
+
+unsafe fn inline_asm() {
+    asm!(""); //~ ERROR use of inline assembly is unsafe and requires an `unsafe` block
+}
+
+unsafe fn ffi() {
+    ext(); //~ ERROR call to unsafe function is unsafe and requires an `unsafe` block
+}
+
+// Explicit blocks are accepted, and are not unnecessary.
+unsafe fn explicit(p: *const int) -> int {
+    unsafe { ext(); }
+    unsafe {
+        unsafe { COUNTER += 1; } //~ ERROR unnecessary `unsafe` block
+    }
+    unsafe { *p }
+}
+
+unsafe fn outer() {
+    unsafe fn inner(p: *const int) -> int {
+        *p //~ ERROR dereference of unsafe pointer is unsafe and requires an `unsafe` block
+    }
+    inner(&0); //~ ERROR call to unsafe function is unsafe and requires an `unsafe` block
+}
+
+trait Poke {
+    unsafe fn poke(&self, p: *mut int);
+}
+
+struct S;
+
+impl Poke for S {
+    unsafe fn poke(&self, p: *mut int) {
+        *p = 1; //~ ERROR dereference of unsafe pointer is unsafe and requires an `unsafe` block
+    }
+}
+
+impl S {
+    #[allow(unsafe_op_in_unsafe_fn)]
+    unsafe fn allowed_method(&self, p: *const int) -> int { *p }
+}
+
+#[allow(unsafe_op_in_unsafe_fn)]
+unsafe fn allowed(p: *const int) -> int {
+    *p
+}
+
+fn main() {}
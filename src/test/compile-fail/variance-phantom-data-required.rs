@@ -0,0 +1,30 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![allow(dead_code)]
+
+// Test that every type parameter must be used by a field, by a raw
+// pointer, or by a `PhantomData` marker.
+
+use std::marker::PhantomData;
+
+struct Unused<T> { x: u32 }
+//~^ ERROR parameter `T` is never used; consider removing it or using a marker such as `core::marker::PhantomData`
+
+struct Marked<T> { x: u32, marker: PhantomData<T> }
+
+struct RawPointer<T> { ptr: *const T }
+
+enum UnusedEnum<T, U> { A(U), B }
+//~^ ERROR parameter `T` is never used; consider removing it or using a marker such as `core::marker::PhantomData`
+
+enum MarkedEnum<T, U> { A(U), B(PhantomData<T>) }
+
+fn main() {}
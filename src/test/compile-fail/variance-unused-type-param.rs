@@ -14,21 +14,17 @@
 // and that we offer a helpful suggestion.
 
 struct SomeStruct<A> { x: u32 }
-//~^ ERROR parameter `A` is never used
-//~| HELP PhantomData
+//~^ ERROR parameter `A` is never used; consider removing it or using a marker such as `core::marker::PhantomData`
 
 enum SomeEnum<A> { Nothing }
-//~^ ERROR parameter `A` is never used
-//~| HELP PhantomData
+//~^ ERROR parameter `A` is never used; consider removing it or using a marker such as `core::marker::PhantomData`
 
 trait SomeTrait<A> { fn foo(&self); }
-//~^ ERROR parameter `A` is never used
-//~| HELP PhantomFn
+//~^ ERROR parameter `A` is never used; consider removing it or using a marker such as `core::marker::PhantomFn`
 
 // Here T might *appear* used, but in fact it isn't.
 enum ListCell<T> {
-//~^ ERROR parameter `T` is never used
-//~| HELP PhantomData
+//~^ ERROR parameter `T` is never used; consider removing it or using a marker such as `core::marker::PhantomData`
     Cons(Box<ListCell<T>>),
     Nil
 }
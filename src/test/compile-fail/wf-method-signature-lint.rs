@@ -0,0 +1,28 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that without `-Z future-wf-errors`, ill-formed method signatures
+// are reported through the `ill_formed_signatures` lint.
+
+#![deny(ill_formed_signatures)]
+#![allow(dead_code, unused_variables)]
+
+trait Bound {}
+
+struct NeedsBound<T: Bound>(T);
+
+struct Foo;
+
+impl Foo {
+    fn inherent(&self, x: NeedsBound<u8>) {}
+    //~^ ERROR the requirement `u8 : Bound` is not satisfied by the types in this signature
+}
+
+fn main() {}
@@ -0,0 +1,43 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z future-wf-errors
+
+// Check that the types in fn and method signatures must meet their own
+// bounds, and that this is reported at the definition.
+
+trait Bound {}
+
+struct NeedsBound<T: Bound>(T);
+
+fn free(x: NeedsBound<u8>) {}
+//~^ ERROR the trait `Bound` is not implemented for the type `u8`
+
+trait Trait {
+    fn required(x: NeedsBound<u16>);
+    //~^ ERROR the trait `Bound` is not implemented for the type `u16`
+
+    fn provided(&self) -> Option<NeedsBound<u32>> { None }
+    //~^ ERROR the trait `Bound` is not implemented for the type `u32`
+}
+
+struct Foo;
+
+impl Foo {
+    fn inherent(&self, x: Vec<NeedsBound<i8>>) {}
+    //~^ ERROR the trait `Bound` is not implemented for the type `i8`
+}
+
+// Bounds declared on the method or its impl are taken into account.
+impl Foo {
+    fn bounded<U: Bound>(&self, x: NeedsBound<U>) {}
+}
+
+fn main() {}
@@ -0,0 +1,37 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that only the last field of a struct or enum variant may be
+// unsized, and that this is reported at the field.
+
+struct Foo {
+    a: str,
+    //~^ ERROR the trait `core::marker::Sized` is not implemented for the type `str`
+    b: u8,
+}
+
+struct Bar<T: ?Sized> {
+    a: T,
+    //~^ ERROR the trait `core::marker::Sized` is not implemented for the type `T`
+    b: u8,
+}
+
+enum Baz {
+    A([u8], u8),
+    //~^ ERROR the trait `core::marker::Sized` is not implemented for the type `[u8]`
+}
+
+// The last field can be unsized.
+struct Last<T: ?Sized> {
+    a: u8,
+    b: T,
+}
+
+fn main() {}
@@ -29,5 +29,5 @@ fn foo<T>(x: &T)
 
 fn main() {
     foo(&X);
-    //~^ error: `for<'a> Bar` is not implemented
+    //~^ error: the implementation only applies to the specific lifetime `'static`
 }
@@ -0,0 +1,102 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-tidy-linelength
+// min-lldb-version: 310
+
+// compile-flags:-g
+
+// Fields of structs without a fixed layout are placed in memory by
+// decreasing alignment; the debugger must still find each of them and
+// show them in declaration order.
+
+// === GDB TESTS ===================================================================================
+
+// gdb-command:run
+
+// gdb-command:print reordered
+// gdb-check:$1 = {a = 1, b = 2, c = 3}
+
+// gdb-command:print reordered.b
+// gdb-check:$2 = 2
+
+// gdb-command:print nested
+// gdb-check:$3 = {x = 4, inner = {a = 5, b = 6, c = 7}, y = 8}
+
+// gdb-command:print fixed
+// gdb-check:$4 = {a = 9, b = 10, c = 11}
+
+// gdb-command:print some_flagged
+// gdb-check:$5 = {RUST$ENCODED$NICHE$2$0$0$None = {{flag = true, x = 12}}}
+
+// gdb-command:print none_flagged
+// gdb-check:$6 = {RUST$ENCODED$NICHE$2$0$0$None = {{flag = [...], x = [...]}}}
+
+
+// === LLDB TESTS ==================================================================================
+
+// lldb-command:run
+
+// lldb-command:print reordered
+// lldb-check:[...]$0 = Reordered { a: 1, b: 2, c: 3 }
+
+// lldb-command:print reordered.b
+// lldb-check:[...]$1 = 2
+
+// lldb-command:print nested
+// lldb-check:[...]$2 = Nested { x: 4, inner: Reordered { a: 5, b: 6, c: 7 }, y: 8 }
+
+// lldb-command:print fixed
+// lldb-check:[...]$3 = Fixed { a: 9, b: 10, c: 11 }
+
+// lldb-command:print some_flagged
+// lldb-check:[...]$4 = Some(Flagged { flag: true, x: 12 })
+
+// lldb-command:print none_flagged
+// lldb-check:[...]$5 = None
+
+#![allow(unused_variables)]
+#![omit_gdb_pretty_printer_section]
+
+struct Reordered {
+    a: u8,
+    b: u64,
+    c: u16,
+}
+
+struct Nested {
+    x: u8,
+    inner: Reordered,
+    y: u32,
+}
+
+#[repr(C)]
+struct Fixed {
+    a: u8,
+    b: u64,
+    c: u16,
+}
+
+struct Flagged {
+    flag: bool,
+    x: u32,
+}
+
+fn main() {
+    let reordered = Reordered { a: 1, b: 2, c: 3 };
+    let nested = Nested { x: 4, inner: Reordered { a: 5, b: 6, c: 7 }, y: 8 };
+    let fixed = Fixed { a: 9, b: 10, c: 11 };
+    let some_flagged = Some(Flagged { flag: true, x: 12 });
+    let none_flagged: Option<Flagged> = None;
+
+    zzz(); // #break
+}
+
+fn zzz() {()}
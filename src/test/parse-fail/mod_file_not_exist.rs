@@ -9,7 +9,8 @@
 // except according to those terms.
 
 mod not_a_real_file; //~ ERROR file not found for module `not_a_real_file`
-//~^ HELP name the file either not_a_real_file.rs or not_a_real_file/mod.rs inside the directory
+//~^ HELP not_a_real_file.rs and
+//~^^ HELP add `#[path = "..."]` to load `not_a_real_file` from a different file or directory
 
 fn main() {
     assert_eq!(mod_file_aux::bar(), 10);
@@ -12,6 +12,7 @@
 
 fn foo(p: proc()) { } //~ ERROR `proc` is a reserved keyword
 
-fn bar() { proc() 1; }
+fn bar() { proc() 1; } //~ ERROR `proc` is a reserved keyword
+//~^ ERROR expected one of
 
 fn main() { }
@@ -0,0 +1,30 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Every reserved keyword is reported when used in expression position.
+
+fn main() {
+    abstract; //~ ERROR `abstract` is a reserved keyword
+    alignof; //~ ERROR `alignof` is a reserved keyword
+    become; //~ ERROR `become` is a reserved keyword
+    do; //~ ERROR `do` is a reserved keyword
+    final; //~ ERROR `final` is a reserved keyword
+    macro; //~ ERROR `macro` is a reserved keyword
+    offsetof; //~ ERROR `offsetof` is a reserved keyword
+    override; //~ ERROR `override` is a reserved keyword
+    priv; //~ ERROR `priv` is a reserved keyword
+    proc; //~ ERROR `proc` is a reserved keyword
+    pure; //~ ERROR `pure` is a reserved keyword
+    sizeof; //~ ERROR `sizeof` is a reserved keyword
+    typeof; //~ ERROR `typeof` is a reserved keyword
+    unsized; //~ ERROR `unsized` is a reserved keyword
+    virtual; //~ ERROR `virtual` is a reserved keyword
+    yield; //~ ERROR `yield` is a reserved keyword
+}
@@ -0,0 +1,31 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Every reserved keyword is reported, with a hint to rename it, when used as
+// an identifier. Parsing carries on after each one.
+
+fn main() {
+    let abstract = (); //~ ERROR `abstract` is a reserved keyword
+    let alignof = (); //~ ERROR `alignof` is a reserved keyword
+    let become = (); //~ ERROR `become` is a reserved keyword
+    let do = (); //~ ERROR `do` is a reserved keyword
+    let final = (); //~ ERROR `final` is a reserved keyword
+    let macro = (); //~ ERROR `macro` is a reserved keyword
+    let offsetof = (); //~ ERROR `offsetof` is a reserved keyword
+    let override = (); //~ ERROR `override` is a reserved keyword
+    let priv = (); //~ ERROR `priv` is a reserved keyword
+    let proc = (); //~ ERROR `proc` is a reserved keyword
+    let pure = (); //~ ERROR `pure` is a reserved keyword
+    let sizeof = (); //~ ERROR `sizeof` is a reserved keyword
+    let typeof = (); //~ ERROR `typeof` is a reserved keyword
+    let unsized = (); //~ ERROR `unsized` is a reserved keyword
+    let virtual = (); //~ ERROR `virtual` is a reserved keyword
+    let yield = (); //~ ERROR `yield` is a reserved keyword
+}
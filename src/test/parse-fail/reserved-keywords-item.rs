@@ -0,0 +1,31 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Every reserved keyword is reported where an item is expected, and the rest
+// of the item is still parsed.
+
+abstract fn abstract_() {} //~ ERROR `abstract` is a reserved keyword
+alignof fn alignof_() {} //~ ERROR `alignof` is a reserved keyword
+become fn become_() {} //~ ERROR `become` is a reserved keyword
+do fn do_() {} //~ ERROR `do` is a reserved keyword
+final fn final_() {} //~ ERROR `final` is a reserved keyword
+macro fn macro_() {} //~ ERROR `macro` is a reserved keyword
+offsetof fn offsetof_() {} //~ ERROR `offsetof` is a reserved keyword
+override fn override_() {} //~ ERROR `override` is a reserved keyword
+priv fn priv_() {} //~ ERROR `priv` is a reserved keyword
+proc fn proc_() {} //~ ERROR `proc` is a reserved keyword
+pure fn pure_() {} //~ ERROR `pure` is a reserved keyword
+sizeof fn sizeof_() {} //~ ERROR `sizeof` is a reserved keyword
+typeof fn typeof_() {} //~ ERROR `typeof` is a reserved keyword
+unsized fn unsized_() {} //~ ERROR `unsized` is a reserved keyword
+virtual fn virtual_() {} //~ ERROR `virtual` is a reserved keyword
+yield fn yield_() {} //~ ERROR `yield` is a reserved keyword
+
+fn main() {}
@@ -30,8 +30,9 @@ pub fn bar() {
 
     let _ =
         (((&((([(1 as i32), (2 as i32), (3 as i32)] as [i32; 3])) as [i32; 3])
-              as &[i32; 3]) as *const _ as *const [i32; 3]) as
-            *const [i32; (3 as usize)] as *const [i32; 3]);
+              as &[i32; 3] ~~> *const* *const [i32; 3]) as *const _ as
+             *const [i32; 3]) as *const [i32; (3 as usize)] as
+            *const [i32; 3]);
 
 
 
@@ -50,17 +51,26 @@ pub fn bar() {
                                                                                                                                                                                                                             =
                                                                                                                                                                                                                          (&([("test"
                                                                                                                                                                                                                                  as
+                                                                                                                                                                                                                                 &'static str
+                                                                                                                                                                                                                                 ~~>
+                                                                                                                                                                                                                                 &*
                                                                                                                                                                                                                                  &'static str)]
                                                                                                                                                                                                                                as
                                                                                                                                                                                                                                [&'static str; 1])
                                                                                                                                                                                                                              as
-                                                                                                                                                                                                                             &'static [&'static str; 1]);
+                                                                                                                                                                                                                             &'static [&'static str; 1]
+                                                                                                                                                                                                                             ~~>
+                                                                                                                                                                                                                             &<unsize>*
+                                                                                                                                                                                                                             &'static [&'static str]);
                                                                                                                                                                                                                      (__STATIC_FMTSTR
                                                                                                                                                                                                                          as
                                                                                                                                                                                                                          &'static [&'static str])
                                                                                                                                                                                                                  }
                                                                                                                                                                                                                     as
-                                                                                                                                                                                                                    &[&str]),
+                                                                                                                                                                                                                    &[&str]
+                                                                                                                                                                                                                    ~~>
+                                                                                                                                                                                                                    &*
+                                                                                                                                                                                                                    &[&'static str]),
                                                                                                                                                                                                                 (&(match (()
                                                                                                                                                                                                                              as
                                                                                                                                                                                                                              ())
@@ -74,7 +84,10 @@ pub fn bar() {
                                                                                                                                                                                                                       as
                                                                                                                                                                                                                       [core::fmt::ArgumentV1<'_>; 0])
                                                                                                                                                                                                                     as
-                                                                                                                                                                                                                    &[core::fmt::ArgumentV1<'_>; 0]))
+                                                                                                                                                                                                                    &[core::fmt::ArgumentV1<'_>; 0]
+                                                                                                                                                                                                                    ~~>
+                                                                                                                                                                                                                    &<unsize>*
+                                                                                                                                                                                                                    &[core::fmt::ArgumentV1<'_>]))
                                                                                                      as
                                                                                                      core::fmt::Arguments<'_>))
         as collections::string::String);
@@ -0,0 +1,26 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// pretty-compare-only
+// pretty-mode:typed
+// pp-exact:typed-adjustments.pp
+
+// Adjustments and closure captures are shown by typed pretty-printing.
+
+fn g(_: &i32) { }
+
+fn main() {
+    let x = 1i32;
+    let r = &&x;
+    g(r);
+    let _ = x.clone();
+    let mut n = 0u8;
+    || n += 1;
+}
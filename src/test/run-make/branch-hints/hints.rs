@@ -0,0 +1,60 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(core)]
+#![crate_type = "lib"]
+
+use std::intrinsics::{likely, unlikely};
+
+#[cold]
+#[inline(never)]
+fn slow_path(x: u32) -> u32 {
+    x.count_ones()
+}
+
+#[no_mangle]
+pub fn explicit_unlikely(x: u32) -> u32 {
+    if unsafe { unlikely(x == 0) } {
+        slow_path(x)
+    } else {
+        x * 2
+    }
+}
+
+#[no_mangle]
+pub fn explicit_likely(x: u32) -> u32 {
+    if unsafe { likely(x != 0) } {
+        x * 2
+    } else {
+        slow_path(x)
+    }
+}
+
+#[no_mangle]
+pub fn cold_arm(x: u32) -> u32 {
+    if x > 10 {
+        x + 1
+    } else {
+        slow_path(x)
+    }
+}
+
+#[no_mangle]
+pub fn panicking_arm(x: u32) -> u32 {
+    if x == 7 {
+        panic!("seven");
+    }
+    x + 3
+}
+
+#[no_mangle]
+pub fn bounds_check(v: &[u32], i: usize) -> u32 {
+    v[i]
+}
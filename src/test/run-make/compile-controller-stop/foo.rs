@@ -0,0 +1,80 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(rustc_private, path, convert, box_syntax)]
+
+extern crate rustc;
+extern crate rustc_driver;
+extern crate rustc_lint;
+extern crate syntax;
+
+use rustc::session::{build_session, Session};
+use rustc::session::config::{basic_options, build_configuration, Input, OutputTypeExe};
+use rustc_driver::Compilation;
+use rustc_driver::driver::{anon_src, compile_input, CompileController};
+use syntax::codemap::DUMMY_SP;
+use syntax::diagnostics::registry::Registry;
+
+use std::path::PathBuf;
+
+fn main() {
+    let src = r#"
+    struct Foo;
+    fn bar() {}
+    mod baz {}
+    fn main() {}
+    "#;
+
+    let args: Vec<String> = std::env::args().collect();
+
+    if args.len() < 4 {
+        panic!("expected rustc path");
+    }
+
+    let tmpdir = PathBuf::from(&args[1]);
+
+    let mut sysroot = PathBuf::from(&args[3]);
+    sysroot.pop();
+    sysroot.pop();
+
+    let sess = basic_sess(sysroot);
+    let cfg = build_configuration(&sess);
+
+    let mut control = CompileController::basic();
+    control.after_analysis.callback = box |state| {
+        let tcx = state.tcx.unwrap();
+        // The injected `std` and prelude imports have dummy spans; only
+        // count the items written in the source above.
+        let items = tcx.map.krate().module.items.iter()
+                       .filter(|item| item.span != DUMMY_SP)
+                       .count();
+        println!("items: {}", items);
+        state.stop = Compilation::Stop;
+    };
+
+    compile_input(sess,
+                  cfg,
+                  &Input::Str { name: anon_src(), input: src.to_string() },
+                  &None,
+                  &Some(tmpdir.join("out")),
+                  None,
+                  control);
+}
+
+fn basic_sess(sysroot: PathBuf) -> Session {
+    let mut opts = basic_options();
+    opts.output_types = vec![OutputTypeExe];
+    opts.maybe_sysroot = Some(sysroot);
+
+    let descriptions = Registry::new(&rustc::diagnostics::DIAGNOSTICS);
+    let sess = build_session(opts, None, descriptions);
+    rustc_lint::register_builtins(&mut sess.lint_store.borrow_mut(), Some(&sess));
+    sess
+}
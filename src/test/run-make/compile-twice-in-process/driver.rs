@@ -0,0 +1,65 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(rustc_private, path, convert)]
+
+extern crate rustc;
+extern crate rustc_driver;
+extern crate rustc_lint;
+extern crate syntax;
+
+use rustc::session::{build_session, Session};
+use rustc::session::config::{basic_options, build_configuration, Input,
+                             OutputTypeLlvmAssembly};
+use rustc_driver::driver::{compile_input, CompileController};
+use syntax::diagnostics::registry::Registry;
+
+use std::path::PathBuf;
+
+// Usage: driver SYSROOT INPUT OUTPUT...
+// Compiles INPUT once for each OUTPUT, all in this process.
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+
+    if args.len() < 4 {
+        panic!("expected sysroot, input and outputs");
+    }
+
+    let sysroot = PathBuf::from(&args[1]);
+    let input = PathBuf::from(&args[2]);
+    for output in &args[3..] {
+        compile(input.clone(), PathBuf::from(output), sysroot.clone());
+    }
+}
+
+fn basic_sess(sysroot: PathBuf) -> Session {
+    let mut opts = basic_options();
+    opts.output_types = vec![OutputTypeLlvmAssembly];
+    opts.maybe_sysroot = Some(sysroot);
+
+    let descriptions = Registry::new(&rustc::diagnostics::DIAGNOSTICS);
+    let sess = build_session(opts, None, descriptions);
+    rustc_lint::register_builtins(&mut sess.lint_store.borrow_mut(), Some(&sess));
+    sess
+}
+
+fn compile(input: PathBuf, output: PathBuf, sysroot: PathBuf) {
+    let sess = basic_sess(sysroot);
+    let cfg = build_configuration(&sess);
+    let control = CompileController::basic();
+
+    compile_input(sess,
+                  cfg,
+                  &Input::File(input),
+                  &None,
+                  &Some(output),
+                  None,
+                  control);
+}
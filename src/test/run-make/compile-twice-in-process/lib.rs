@@ -0,0 +1,31 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type = "lib"]
+
+// Exercises gensyms and hygiene, whose names end up in symbol hashes.
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+macro_rules! twice {
+    ($e:expr) => ({ let tmp = $e; tmp + tmp })
+}
+
+pub fn describe(p: &Point) -> String {
+    format!("({}, {}) {:?}", twice!(p.x), twice!(p.y), p)
+}
+
+pub fn adder(n: i32) -> Box<Fn(i32) -> i32> {
+    Box::new(move |m| n + m)
+}
@@ -0,0 +1,43 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+static LIMIT: u32 = 10;
+
+struct Small;
+
+struct Wrapper<T>(T);
+
+trait Describe {
+    fn describe(&self) -> u32 { 0 }
+}
+
+impl Small {
+    fn new() -> Small { Small }
+}
+
+impl<T> Wrapper<T> {
+    fn unwrap(self) -> T { self.0 }
+}
+
+fn identity<T>(x: T) -> T { x }
+
+fn big(x: u32) -> u32 {
+    let mut total = 0;
+    for i in 0..x {
+        total += if i % 2 == 0 { i * 3 + 1 } else { i / 2 };
+    }
+    total + LIMIT
+}
+
+fn main() {
+    let _ = Small::new();
+    let _ = identity(Wrapper(1u8)).unwrap();
+    let _ = big(4);
+}
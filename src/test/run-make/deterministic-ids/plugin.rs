@@ -0,0 +1,41 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type = "dylib"]
+#![feature(plugin_registrar, quote, rustc_private)]
+
+extern crate syntax;
+extern crate rustc;
+
+use syntax::ast::TokenTree;
+use syntax::codemap::Span;
+use syntax::ext::base::{ExtCtxt, MacEager, MacResult};
+use syntax::parse::token;
+use syntax::util::small_vector::SmallVector;
+use rustc::plugin::Registry;
+
+#[plugin_registrar]
+pub fn plugin_registrar(reg: &mut Registry) {
+    reg.register_macro("fresh_local", expand_fresh_local);
+    reg.register_macro("fresh_fn", expand_fresh_fn);
+}
+
+// Both macros introduce gensym'd names, whose numbering depends on the order
+// macros are expanded in.
+fn expand_fresh_local(cx: &mut ExtCtxt, _: Span, _: &[TokenTree]) -> Box<MacResult+'static> {
+    let local = token::gensym_ident("local");
+    MacEager::expr(quote_expr!(cx, { let $local = 1; $local + 1 }))
+}
+
+fn expand_fresh_fn(cx: &mut ExtCtxt, _: Span, _: &[TokenTree]) -> Box<MacResult+'static> {
+    let name = token::gensym_ident("fresh");
+    MacEager::items(SmallVector::one(quote_item!(cx, fn $name() -> u32 { fresh_local!() })
+                                         .unwrap()))
+}
@@ -0,0 +1,22 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type = "dylib"]
+
+pub static EXPORTED_STATIC: u32 = 3;
+
+pub fn exported_fn(x: u32) -> u32 {
+    x + EXPORTED_STATIC
+}
+
+#[no_mangle]
+pub extern fn exported_to_c(x: u32) -> u32 {
+    exported_fn(x)
+}
@@ -0,0 +1,32 @@
+// ignore-license
+#include <stdint.h>
+
+struct Pair {
+    int32_t a;
+    int32_t b;
+};
+
+int32_t __stdcall stdcall_add(int32_t a, int32_t b) {
+    return a + b;
+}
+
+int32_t __stdcall stdcall_pair(struct Pair p, int32_t c) {
+    return p.a * p.b + c;
+}
+
+int32_t __fastcall fastcall_digits(int32_t a, int32_t b, int32_t c) {
+    return a * 100 + b * 10 + c;
+}
+
+// Declared `extern "system"` on the Rust side.
+int32_t __stdcall system_sub(int32_t a, int32_t b) {
+    return a - b;
+}
+
+int32_t call_stdcall(int32_t (__stdcall *f)(int32_t, int32_t)) {
+    return f(6, 7);
+}
+
+int32_t call_fastcall(int32_t (__fastcall *f)(int32_t, int32_t, int32_t)) {
+    return f(1, 2, 3);
+}
@@ -0,0 +1,58 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[repr(C)]
+#[derive(Copy)]
+struct Pair {
+    a: i32,
+    b: i32,
+}
+
+#[link(name = "test", kind = "static")]
+extern "stdcall" {
+    fn stdcall_add(a: i32, b: i32) -> i32;
+    fn stdcall_pair(p: Pair, c: i32) -> i32;
+}
+
+extern "fastcall" {
+    fn fastcall_digits(a: i32, b: i32, c: i32) -> i32;
+}
+
+extern "system" {
+    fn system_sub(a: i32, b: i32) -> i32;
+}
+
+extern {
+    fn call_stdcall(f: extern "stdcall" fn(i32, i32) -> i32) -> i32;
+    fn call_fastcall(f: extern "fastcall" fn(i32, i32, i32) -> i32) -> i32;
+}
+
+extern "stdcall" fn rust_stdcall_mul(a: i32, b: i32) -> i32 {
+    a * b
+}
+
+extern "fastcall" fn rust_fastcall_digits(a: i32, b: i32, c: i32) -> i32 {
+    a * 100 + b * 10 + c
+}
+
+fn main() {
+    // A callee that pops the wrong number of bytes would quickly corrupt
+    // the stack, so make each call many times.
+    for _ in 0..1000 {
+        unsafe {
+            assert_eq!(stdcall_add(2, 3), 5);
+            assert_eq!(stdcall_pair(Pair { a: 4, b: 5 }, 6), 26);
+            assert_eq!(fastcall_digits(1, 2, 3), 123);
+            assert_eq!(system_sub(10, 4), 6);
+            assert_eq!(call_stdcall(rust_stdcall_mul), 42);
+            assert_eq!(call_fastcall(rust_fastcall_digits), 123);
+        }
+    }
+}
@@ -0,0 +1,202 @@
+// ignore-license
+#include <stdint.h>
+
+// Every shape below is passed and returned by value in both directions:
+// `transform_*` is called from Rust, and `call_*` calls back into Rust,
+// checking the result on the C side.
+
+struct OneF {
+    float a;
+};
+
+struct TwoF {
+    float a;
+    float b;
+};
+
+struct ThreeF {
+    float a;
+    float b;
+    float c;
+};
+
+struct FourF {
+    float a;
+    float b;
+    float c;
+    float d;
+};
+
+struct TwoD {
+    double a;
+    double b;
+};
+
+struct IntFloat {
+    int32_t a;
+    float b;
+};
+
+struct FloatInt {
+    float a;
+    int32_t b;
+};
+
+struct LongDouble {
+    int64_t a;
+    double b;
+};
+
+struct OverSize {
+    double a;
+    double b;
+    double c;
+    double d;
+    double e;
+};
+
+struct OneF transform_one_f(struct OneF v) {
+    struct OneF r = { v.a * 2 + 1 };
+    return r;
+}
+
+int call_one_f(struct OneF (*f)(struct OneF)) {
+    struct OneF in = { 1.5f };
+    struct OneF out = f(in);
+    return out.a == 4.0f;
+}
+
+struct TwoF transform_two_f(struct TwoF v) {
+    struct TwoF r = { v.a * 2 + 1, v.b * 2 + 1 };
+    return r;
+}
+
+int call_two_f(struct TwoF (*f)(struct TwoF)) {
+    struct TwoF in = { 1.5f, 2.5f };
+    struct TwoF out = f(in);
+    return out.a == 4.0f &&
+           out.b == 6.0f;
+}
+
+struct ThreeF transform_three_f(struct ThreeF v) {
+    struct ThreeF r = { v.a * 2 + 1, v.b * 2 + 1, v.c * 2 + 1 };
+    return r;
+}
+
+int call_three_f(struct ThreeF (*f)(struct ThreeF)) {
+    struct ThreeF in = { 1.5f, 2.5f, 3.5f };
+    struct ThreeF out = f(in);
+    return out.a == 4.0f &&
+           out.b == 6.0f &&
+           out.c == 8.0f;
+}
+
+struct FourF transform_four_f(struct FourF v) {
+    struct FourF r = { v.a * 2 + 1, v.b * 2 + 1, v.c * 2 + 1, v.d * 2 + 1 };
+    return r;
+}
+
+int call_four_f(struct FourF (*f)(struct FourF)) {
+    struct FourF in = { 1.5f, 2.5f, 3.5f, 4.5f };
+    struct FourF out = f(in);
+    return out.a == 4.0f &&
+           out.b == 6.0f &&
+           out.c == 8.0f &&
+           out.d == 10.0f;
+}
+
+struct TwoD transform_two_d(struct TwoD v) {
+    struct TwoD r = { v.a * 2 + 1, v.b * 2 + 1 };
+    return r;
+}
+
+int call_two_d(struct TwoD (*f)(struct TwoD)) {
+    struct TwoD in = { 1.5, 2.5 };
+    struct TwoD out = f(in);
+    return out.a == 4.0 &&
+           out.b == 6.0;
+}
+
+struct IntFloat transform_int_float(struct IntFloat v) {
+    struct IntFloat r = { v.a * 2 + 1, v.b * 2 + 1 };
+    return r;
+}
+
+int call_int_float(struct IntFloat (*f)(struct IntFloat)) {
+    struct IntFloat in = { 3, 2.5f };
+    struct IntFloat out = f(in);
+    return out.a == 7 &&
+           out.b == 6.0f;
+}
+
+struct FloatInt transform_float_int(struct FloatInt v) {
+    struct FloatInt r = { v.a * 2 + 1, v.b * 2 + 1 };
+    return r;
+}
+
+int call_float_int(struct FloatInt (*f)(struct FloatInt)) {
+    struct FloatInt in = { 1.5f, 5 };
+    struct FloatInt out = f(in);
+    return out.a == 4.0f &&
+           out.b == 11;
+}
+
+struct LongDouble transform_long_double(struct LongDouble v) {
+    struct LongDouble r = { v.a * 2 + 1, v.b * 2 + 1 };
+    return r;
+}
+
+int call_long_double(struct LongDouble (*f)(struct LongDouble)) {
+    struct LongDouble in = { 3, 2.5 };
+    struct LongDouble out = f(in);
+    return out.a == 7 &&
+           out.b == 6.0;
+}
+
+struct OverSize transform_over_size(struct OverSize v) {
+    struct OverSize r = { v.a * 2 + 1, v.b * 2 + 1, v.c * 2 + 1, v.d * 2 + 1, v.e * 2 + 1 };
+    return r;
+}
+
+int call_over_size(struct OverSize (*f)(struct OverSize)) {
+    struct OverSize in = { 1.5, 2.5, 3.5, 4.5, 5.5 };
+    struct OverSize out = f(in);
+    return out.a == 4.0 &&
+           out.b == 6.0 &&
+           out.c == 8.0 &&
+           out.d == 10.0 &&
+           out.e == 12.0;
+}
+
+struct LongPair {
+    int64_t a;
+    int64_t b;
+};
+
+// Five pairs of doubles need ten SSE registers and five pairs of longs need
+// ten integer registers, so on x86_64 the trailing arguments spill onto the
+// stack.
+double sum_two_d(struct TwoD v, struct TwoD w, struct TwoD x,
+                 struct TwoD y, struct TwoD z) {
+    return v.a + v.b + w.a + w.b + x.a + x.b + y.a + y.b + z.a + z.b;
+}
+
+int64_t sum_long_pairs(struct LongPair v, struct LongPair w, struct LongPair x,
+                       struct LongPair y, struct LongPair z) {
+    return v.a + v.b + w.a + w.b + x.a + x.b + y.a + y.b + z.a + z.b;
+}
+
+int call_sum_two_d(double (*f)(struct TwoD, struct TwoD, struct TwoD,
+                               struct TwoD, struct TwoD)) {
+    struct TwoD v = { 1.0, 2.0 }, w = { 3.0, 4.0 }, x = { 5.0, 6.0 },
+                y = { 7.0, 8.0 }, z = { 9.0, 10.0 };
+    return f(v, w, x, y, z) == 55.0;
+}
+
+int call_sum_long_pairs(int64_t (*f)(struct LongPair, struct LongPair,
+                                     struct LongPair, struct LongPair,
+                                     struct LongPair)) {
+    struct LongPair v = { 1, 2 }, w = { 3, 4 }, x = { 5, 6 },
+                    y = { 7, 8 }, z = { 9, 10 };
+    return f(v, w, x, y, z) == 55;
+}
@@ -0,0 +1,181 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Checks that structs of up to four floats, doubles and mixed integer/float
+// fields, and structs too large to be passed in registers, cross the C ABI
+// intact in both directions on every target.
+
+extern crate libc;
+
+use libc::c_int;
+
+#[repr(C)]
+#[derive(Copy, PartialEq, Debug)]
+struct OneF { a: f32 }
+
+#[repr(C)]
+#[derive(Copy, PartialEq, Debug)]
+struct TwoF { a: f32, b: f32 }
+
+#[repr(C)]
+#[derive(Copy, PartialEq, Debug)]
+struct ThreeF { a: f32, b: f32, c: f32 }
+
+#[repr(C)]
+#[derive(Copy, PartialEq, Debug)]
+struct FourF { a: f32, b: f32, c: f32, d: f32 }
+
+#[repr(C)]
+#[derive(Copy, PartialEq, Debug)]
+struct TwoD { a: f64, b: f64 }
+
+#[repr(C)]
+#[derive(Copy, PartialEq, Debug)]
+struct IntFloat { a: i32, b: f32 }
+
+#[repr(C)]
+#[derive(Copy, PartialEq, Debug)]
+struct FloatInt { a: f32, b: i32 }
+
+#[repr(C)]
+#[derive(Copy, PartialEq, Debug)]
+struct LongDouble { a: i64, b: f64 }
+
+#[repr(C)]
+#[derive(Copy, PartialEq, Debug)]
+struct OverSize { a: f64, b: f64, c: f64, d: f64, e: f64 }
+
+#[repr(C)]
+#[derive(Copy, PartialEq, Debug)]
+struct LongPair { a: i64, b: i64 }
+
+#[link(name = "test", kind = "static")]
+extern {
+    fn transform_one_f(v: OneF) -> OneF;
+    fn call_one_f(f: extern "C" fn(OneF) -> OneF) -> c_int;
+    fn transform_two_f(v: TwoF) -> TwoF;
+    fn call_two_f(f: extern "C" fn(TwoF) -> TwoF) -> c_int;
+    fn transform_three_f(v: ThreeF) -> ThreeF;
+    fn call_three_f(f: extern "C" fn(ThreeF) -> ThreeF) -> c_int;
+    fn transform_four_f(v: FourF) -> FourF;
+    fn call_four_f(f: extern "C" fn(FourF) -> FourF) -> c_int;
+    fn transform_two_d(v: TwoD) -> TwoD;
+    fn call_two_d(f: extern "C" fn(TwoD) -> TwoD) -> c_int;
+    fn transform_int_float(v: IntFloat) -> IntFloat;
+    fn call_int_float(f: extern "C" fn(IntFloat) -> IntFloat) -> c_int;
+    fn transform_float_int(v: FloatInt) -> FloatInt;
+    fn call_float_int(f: extern "C" fn(FloatInt) -> FloatInt) -> c_int;
+    fn transform_long_double(v: LongDouble) -> LongDouble;
+    fn call_long_double(f: extern "C" fn(LongDouble) -> LongDouble) -> c_int;
+    fn transform_over_size(v: OverSize) -> OverSize;
+    fn call_over_size(f: extern "C" fn(OverSize) -> OverSize) -> c_int;
+
+    fn sum_two_d(v: TwoD, w: TwoD, x: TwoD, y: TwoD, z: TwoD) -> f64;
+    fn sum_long_pairs(v: LongPair, w: LongPair, x: LongPair,
+                      y: LongPair, z: LongPair) -> i64;
+    fn call_sum_two_d(f: extern "C" fn(TwoD, TwoD, TwoD, TwoD, TwoD) -> f64) -> c_int;
+    fn call_sum_long_pairs(f: extern "C" fn(LongPair, LongPair, LongPair,
+                                            LongPair, LongPair) -> i64) -> c_int;
+}
+
+extern "C" fn rust_transform_one_f(v: OneF) -> OneF {
+    OneF { a: v.a * 2.0 + 1.0 }
+}
+
+extern "C" fn rust_transform_two_f(v: TwoF) -> TwoF {
+    TwoF { a: v.a * 2.0 + 1.0, b: v.b * 2.0 + 1.0 }
+}
+
+extern "C" fn rust_transform_three_f(v: ThreeF) -> ThreeF {
+    ThreeF { a: v.a * 2.0 + 1.0, b: v.b * 2.0 + 1.0, c: v.c * 2.0 + 1.0 }
+}
+
+extern "C" fn rust_transform_four_f(v: FourF) -> FourF {
+    FourF { a: v.a * 2.0 + 1.0, b: v.b * 2.0 + 1.0, c: v.c * 2.0 + 1.0, d: v.d * 2.0 + 1.0 }
+}
+
+extern "C" fn rust_transform_two_d(v: TwoD) -> TwoD {
+    TwoD { a: v.a * 2.0 + 1.0, b: v.b * 2.0 + 1.0 }
+}
+
+extern "C" fn rust_transform_int_float(v: IntFloat) -> IntFloat {
+    IntFloat { a: v.a * 2 + 1, b: v.b * 2.0 + 1.0 }
+}
+
+extern "C" fn rust_transform_float_int(v: FloatInt) -> FloatInt {
+    FloatInt { a: v.a * 2.0 + 1.0, b: v.b * 2 + 1 }
+}
+
+extern "C" fn rust_transform_long_double(v: LongDouble) -> LongDouble {
+    LongDouble { a: v.a * 2 + 1, b: v.b * 2.0 + 1.0 }
+}
+
+extern "C" fn rust_transform_over_size(v: OverSize) -> OverSize {
+    OverSize { a: v.a * 2.0 + 1.0, b: v.b * 2.0 + 1.0, c: v.c * 2.0 + 1.0,
+               d: v.d * 2.0 + 1.0, e: v.e * 2.0 + 1.0 }
+}
+
+extern "C" fn rust_sum_two_d(v: TwoD, w: TwoD, x: TwoD, y: TwoD, z: TwoD) -> f64 {
+    v.a + v.b + w.a + w.b + x.a + x.b + y.a + y.b + z.a + z.b
+}
+
+extern "C" fn rust_sum_long_pairs(v: LongPair, w: LongPair, x: LongPair,
+                                  y: LongPair, z: LongPair) -> i64 {
+    v.a + v.b + w.a + w.b + x.a + x.b + y.a + y.b + z.a + z.b
+}
+
+fn main() {
+    unsafe {
+        assert_eq!(transform_one_f(OneF { a: 1.5 }),
+                   OneF { a: 4.0 });
+        assert_eq!(call_one_f(rust_transform_one_f), 1);
+
+        assert_eq!(transform_two_f(TwoF { a: 1.5, b: 2.5 }),
+                   TwoF { a: 4.0, b: 6.0 });
+        assert_eq!(call_two_f(rust_transform_two_f), 1);
+
+        assert_eq!(transform_three_f(ThreeF { a: 1.5, b: 2.5, c: 3.5 }),
+                   ThreeF { a: 4.0, b: 6.0, c: 8.0 });
+        assert_eq!(call_three_f(rust_transform_three_f), 1);
+
+        assert_eq!(transform_four_f(FourF { a: 1.5, b: 2.5, c: 3.5, d: 4.5 }),
+                   FourF { a: 4.0, b: 6.0, c: 8.0, d: 10.0 });
+        assert_eq!(call_four_f(rust_transform_four_f), 1);
+
+        assert_eq!(transform_two_d(TwoD { a: 1.5, b: 2.5 }),
+                   TwoD { a: 4.0, b: 6.0 });
+        assert_eq!(call_two_d(rust_transform_two_d), 1);
+
+        assert_eq!(transform_int_float(IntFloat { a: 3, b: 2.5 }),
+                   IntFloat { a: 7, b: 6.0 });
+        assert_eq!(call_int_float(rust_transform_int_float), 1);
+
+        assert_eq!(transform_float_int(FloatInt { a: 1.5, b: 5 }),
+                   FloatInt { a: 4.0, b: 11 });
+        assert_eq!(call_float_int(rust_transform_float_int), 1);
+
+        assert_eq!(transform_long_double(LongDouble { a: 3, b: 2.5 }),
+                   LongDouble { a: 7, b: 6.0 });
+        assert_eq!(call_long_double(rust_transform_long_double), 1);
+
+        assert_eq!(transform_over_size(OverSize { a: 1.5, b: 2.5, c: 3.5, d: 4.5, e: 5.5 }),
+                   OverSize { a: 4.0, b: 6.0, c: 8.0, d: 10.0, e: 12.0 });
+        assert_eq!(call_over_size(rust_transform_over_size), 1);
+
+        assert_eq!(sum_two_d(TwoD { a: 1.0, b: 2.0 }, TwoD { a: 3.0, b: 4.0 },
+                             TwoD { a: 5.0, b: 6.0 }, TwoD { a: 7.0, b: 8.0 },
+                             TwoD { a: 9.0, b: 10.0 }), 55.0);
+        assert_eq!(sum_long_pairs(LongPair { a: 1, b: 2 }, LongPair { a: 3, b: 4 },
+                                  LongPair { a: 5, b: 6 }, LongPair { a: 7, b: 8 },
+                                  LongPair { a: 9, b: 10 }), 55);
+        assert_eq!(call_sum_two_d(rust_sum_two_d), 1);
+        assert_eq!(call_sum_long_pairs(rust_sum_long_pairs), 1);
+    }
+}
@@ -0,0 +1,8 @@
+// ignore-license
+#include <stdio.h>
+
+void call_back(void (*cb)(void)) {
+    cb();
+    printf("callback returned\n");
+    fflush(stdout);
+}
@@ -0,0 +1,38 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type = "dylib"]
+
+mod a {
+    #[inline(never)]
+    pub fn private_helper_a(x: u32) -> u32 {
+        ::b::private_helper_b(x) + 1
+    }
+}
+
+mod b {
+    #[inline(never)]
+    pub fn private_helper_b(x: u32) -> u32 {
+        x * 2
+    }
+}
+
+mod c {
+    pub fn exported_to_rust(x: u32) -> u32 {
+        ::a::private_helper_a(x)
+    }
+}
+
+pub use c::exported_to_rust;
+
+#[no_mangle]
+pub extern fn exported_to_c(x: u32) -> u32 {
+    ::a::private_helper_a(x) + ::b::private_helper_b(x)
+}
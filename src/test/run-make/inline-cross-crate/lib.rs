@@ -0,0 +1,30 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type = "rlib"]
+
+#[inline]
+pub fn add_one(x: u32) -> u32 {
+    x + 1
+}
+
+pub struct Doubler;
+
+impl Doubler {
+    #[inline]
+    pub fn twice(&self, x: u32) -> u32 {
+        x * 2
+    }
+}
+
+#[inline(never)]
+pub fn identity<T>(x: T) -> T {
+    x
+}
@@ -0,0 +1,21 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+extern crate lib;
+
+use lib::Doubler;
+
+fn main() {
+    let x = lib::add_one(std::env::args().count() as u32);
+    let y = Doubler.twice(x);
+    let a: u32 = lib::identity(y);
+    let b: i32 = lib::identity(y as i32);
+    assert_eq!(a as i32, b);
+}
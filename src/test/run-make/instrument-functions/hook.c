@@ -0,0 +1,20 @@
+// ignore-license
+#include <string.h>
+
+static unsigned known_calls = 0;
+static unsigned excluded_calls = 0;
+
+void __rust_profile_enter(const char *name) {
+    if (strstr(name, "known_function") != NULL)
+        known_calls++;
+    if (strstr(name, "excluded_function") != NULL)
+        excluded_calls++;
+}
+
+unsigned known_function_calls(void) {
+    return known_calls;
+}
+
+unsigned excluded_function_calls(void) {
+    return excluded_calls;
+}
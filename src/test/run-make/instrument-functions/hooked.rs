@@ -0,0 +1,39 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[link(name = "hook", kind = "static")]
+extern {
+    fn known_function_calls() -> u32;
+    fn excluded_function_calls() -> u32;
+}
+
+#[inline(never)]
+fn known_function(x: u32) -> u32 {
+    x + 1
+}
+
+#[inline(never)]
+#[no_instrument_function]
+fn excluded_function(x: u32) -> u32 {
+    x * 2
+}
+
+fn main() {
+    let mut x = 0;
+    for _ in 0..3 {
+        x = known_function(x);
+        x = excluded_function(x);
+    }
+    assert_eq!(x, 14);
+    unsafe {
+        assert_eq!(known_function_calls(), 3);
+        assert_eq!(excluded_function_calls(), 0);
+    }
+}
@@ -17,7 +17,7 @@ extern crate syntax;
 
 use rustc::session::{build_session, Session};
 use rustc::session::config::{basic_options, build_configuration, Input, OutputTypeExe};
-use rustc_driver::driver::{compile_input, CompileController};
+use rustc_driver::driver::{anon_src, compile_input, CompileController};
 use syntax::diagnostics::registry::Registry;
 
 use std::path::PathBuf;
@@ -62,7 +62,7 @@ fn compile(code: String, output: PathBuf, sysroot: PathBuf) {
 
     compile_input(sess,
             cfg,
-            &Input::Str(code),
+            &Input::Str { name: anon_src(), input: code },
             &None,
             &Some(output),
             None,
@@ -0,0 +1,32 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(test)]
+
+extern crate test;
+
+#[test]
+fn fast_one() {}
+
+#[test]
+fn fast_two() {}
+
+#[test]
+#[ignore]
+fn slow() {}
+
+#[test]
+fn never_run() {
+    // Listing must not run any test.
+    panic!("run while listing");
+}
+
+#[bench]
+fn bench_fast(_: &mut test::Bencher) {}
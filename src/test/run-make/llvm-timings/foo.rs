@@ -0,0 +1,29 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type = "rlib"]
+
+// A crate with only a couple of functions, so that `slow_to_optimize` is
+// always among the ten slowest.
+
+#[inline(never)]
+pub fn slow_to_optimize(mut x: u64) -> u64 {
+    for i in 0..100 {
+        x = (x << 5) ^ (x >> 3) ^ i;
+        if x % 7 == 3 {
+            x ^= x >> 5;
+        }
+    }
+    x
+}
+
+pub fn call() -> u64 {
+    slow_to_optimize(1)
+}
@@ -0,0 +1,38 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[repr(C)]
+pub struct Layout {
+    pub a: u8,
+    pub b: u32,
+    pub c: u16,
+}
+
+pub struct Wrapper<T> {
+    pub value: T,
+}
+
+pub enum Choice {
+    One(u8),
+    Two(u32, u16),
+}
+
+fn main() {
+    let layout = Layout { a: 1, b: 2, c: 3 };
+    let big = Wrapper { value: 1u64 };
+    let small = Wrapper { value: 1u8 };
+    let choice = Choice::Two(1, 2);
+    let choice = match choice {
+        Choice::One(x) => x as u64,
+        Choice::Two(x, y) => x as u64 + y as u64,
+    };
+    println!("{}", layout.a as u64 + layout.b as u64 + layout.c as u64 +
+                   big.value + small.value as u64 + choice);
+}
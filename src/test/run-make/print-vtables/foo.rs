@@ -0,0 +1,26 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+trait Steps {
+    fn first(&self) -> u32 { self.second() + 1 }
+    fn second(&self) -> u32 { self.third() + 1 }
+    fn third(&self) -> u32 { 0 }
+}
+
+struct Foo;
+
+impl Steps for Foo {
+    fn second(&self) -> u32 { self.third() + 10 }
+}
+
+fn main() {
+    let b: Box<Steps> = Box::new(Foo);
+    assert_eq!(b.first(), 11);
+}
@@ -0,0 +1,28 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(exit_status)]
+
+use std::process;
+
+struct Loud;
+
+impl Drop for Loud {
+    fn drop(&mut self) {
+        println!("destructor ran");
+    }
+}
+
+fn main() {
+    let _loud = Loud;
+    println!("first line");
+    print!("no newline");
+    process::exit(3);
+}
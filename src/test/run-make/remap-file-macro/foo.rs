@@ -0,0 +1,21 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+mod inner {
+    pub fn location() -> (&'static str, &'static str) {
+        (file!(), module_path!())
+    }
+}
+
+fn main() {
+    assert_eq!(file!(), "/remapped/foo.rs");
+    assert_eq!(module_path!(), "foo");
+    assert_eq!(inner::location(), ("/remapped/foo.rs", "foo::inner"));
+}
@@ -0,0 +1,35 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// A bit of everything that gets an internal symbol: closures, drop glue,
+// string constants and panic locations.
+
+struct Noisy(String);
+
+impl Drop for Noisy {
+    fn drop(&mut self) {
+        println!("dropping {}", self.0);
+    }
+}
+
+fn apply<F: Fn(i32) -> i32>(f: F, x: i32) -> i32 {
+    f(x)
+}
+
+fn main() {
+    let offset = 3;
+    let n = apply(|x| x + offset, 4);
+    let m = apply(|x| x * offset, n);
+    let v = vec![Noisy("a".to_string()), Noisy(format!("{}", m))];
+    if v.len() > 5 {
+        panic!("too many: {}", v.len());
+    }
+    let _ = v[m as usize % 2].0.len();
+}
@@ -0,0 +1,29 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(pub_crate)]
+
+// @has foo/fn.exported.html
+pub fn exported() {}
+
+// @!has foo/fn.internal.html
+// @!has foo/index.html '//a' 'internal'
+pub(crate) fn internal() {}
+
+// @!has foo/struct.Internal.html
+pub(crate) struct Internal;
+
+// @has foo/struct.Exported.html
+// @!has - '//code' 'helper'
+pub struct Exported;
+
+impl Exported {
+    pub(crate) fn helper(&self) {}
+}
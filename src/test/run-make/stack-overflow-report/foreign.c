@@ -0,0 +1,18 @@
+// ignore-license
+#include <pthread.h>
+
+typedef void (*callback)(void);
+
+static void *run(void *arg) {
+    ((callback) arg)();
+    return NULL;
+}
+
+void run_in_c_thread(callback cb) {
+    pthread_t thread;
+    pthread_attr_t attr;
+    pthread_attr_init(&attr);
+    pthread_attr_setstacksize(&attr, 256 * 1024);
+    pthread_create(&thread, &attr, run, (void *) cb);
+    pthread_join(thread, NULL);
+}
@@ -0,0 +1,32 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(std_misc)]
+
+use std::rt::unwind;
+
+#[link(name = "foreign", kind = "static")]
+extern {
+    fn run_in_c_thread(cb: extern fn());
+}
+
+fn recurse(n: u64) -> u64 {
+    let buf = [n; 64];
+    if buf[0] == std::u64::MAX { 0 } else { recurse(n + 1) + buf[63] }
+}
+
+extern fn callback() {
+    // Entering Rust through the unwinding boundary registers the thread.
+    let _ = unsafe { unwind::try(|| { recurse(0); }) };
+}
+
+fn main() {
+    unsafe { run_in_c_thread(callback) }
+}
@@ -0,0 +1,22 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::thread::Builder;
+
+fn recurse(n: u64) -> u64 {
+    let buf = [n; 64];
+    if buf[0] == std::u64::MAX { 0 } else { recurse(n + 1) + buf[63] }
+}
+
+fn main() {
+    let _ = Builder::new().name("deep".to_string()).stack_size(64 * 1024).spawn(|| {
+        recurse(0);
+    }).unwrap().join();
+}
@@ -0,0 +1,25 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(no_std)]
+#![no_std]
+#![crate_type = "lib"]
+
+#[cfg(target_vendor = "acme")]
+pub fn vendor_is_acme() {}
+
+#[cfg(target_vendor = "unknown")]
+pub fn vendor_is_unknown() {}
+
+#[cfg(target_env = "newlib")]
+pub fn env_is_newlib() {}
+
+#[cfg(target_env = "gnu")]
+pub fn env_is_gnu() {}
@@ -0,0 +1,30 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+macro_rules! make_answer {
+    ($name:ident, $value:expr) => (pub fn $name() -> u32 { $value })
+}
+
+make_answer!(answer, 42);
+
+#[cfg(test)]
+fn helper() -> u32 { answer() / 2 }
+
+#[cfg(test)]
+pub fn only_in_tests() {}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn uses_helper() {
+        assert_eq!(super::helper(), 21);
+        super::only_in_tests();
+    }
+}
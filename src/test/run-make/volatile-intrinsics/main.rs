@@ -11,11 +11,16 @@
 #![feature(core)]
 
 use std::intrinsics::{volatile_load, volatile_store};
+use std::ptr;
 
 pub fn main() {
     unsafe {
         let mut i : int = 1;
         volatile_store(&mut i, 2);
         assert_eq!(volatile_load(&i), 2);
+
+        let mut j = 3u8;
+        ptr::write_volatile(&mut j, 4);
+        assert_eq!(ptr::read_volatile(&j), 4);
     }
 }
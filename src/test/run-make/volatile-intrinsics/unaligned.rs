@@ -0,0 +1,20 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(core)]
+#![crate_type = "lib"]
+
+use std::intrinsics::{unaligned_load, unaligned_store};
+
+pub fn copy_unaligned(src: *const u32, dst: *mut u32) {
+    unsafe {
+        unaligned_store(dst, unaligned_load(src));
+    }
+}
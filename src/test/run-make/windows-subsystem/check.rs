@@ -0,0 +1,37 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::env;
+use std::fs::File;
+use std::io::prelude::*;
+
+fn u16_at(bytes: &[u8], offset: usize) -> u16 {
+    bytes[offset] as u16 | (bytes[offset + 1] as u16) << 8
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+    u16_at(bytes, offset) as u32 | (u16_at(bytes, offset + 2) as u32) << 16
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let expected: u16 = args[2].parse().unwrap();
+
+    let mut bytes = Vec::new();
+    File::open(&args[1]).unwrap().read_to_end(&mut bytes).unwrap();
+
+    // The DOS header points at the "PE\0\0" signature. It is followed by the
+    // 20 byte COFF header and the optional header, whose subsystem field is
+    // at the same offset for PE32 and PE32+ images.
+    let pe = u32_at(&bytes, 0x3c) as usize;
+    assert_eq!(&bytes[pe..pe + 4], &b"PE\0\0"[..]);
+    let subsystem = u16_at(&bytes, pe + 4 + 20 + 68);
+    assert_eq!(subsystem, expected);
+}
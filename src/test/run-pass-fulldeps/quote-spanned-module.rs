@@ -0,0 +1,26 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:quote_spanned_module.rs
+// ignore-stage1
+
+#![feature(plugin)]
+#![plugin(quote_spanned_module)]
+
+quoted_module!(shapes,
+    pub fn sides() -> u32 { 4 }
+    pub struct Square;
+);
+
+fn main() {
+    assert_eq!(shapes::sides(), 4);
+    let _square = shapes::Square;
+    assert_eq!(shapes::count(), 2);
+}
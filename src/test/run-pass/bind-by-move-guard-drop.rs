@@ -0,0 +1,59 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// A by-move binding whose guard fails must not be dropped; the value is
+// dropped exactly once, by whichever arm takes ownership of it.
+
+use std::cell::Cell;
+
+struct Noisy<'a> {
+    id: u32,
+    drops: &'a Cell<u32>,
+}
+
+impl<'a> Noisy<'a> {
+    fn id(&self) -> u32 { self.id }
+}
+
+impl<'a> Drop for Noisy<'a> {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+    }
+}
+
+fn main() {
+    let drops = Cell::new(0);
+    let value = Some(Noisy { id: 7, drops: &drops });
+    let taken = match value {
+        Some(n) if n.id() == 1 => { assert_eq!(drops.get(), 0); n.id() + 100 }
+        Some(n) if { assert_eq!(drops.get(), 0); n.id() == 2 } => n.id() + 200,
+        Some(n) => {
+            assert_eq!(drops.get(), 0);
+            assert_eq!(n.id(), 7);
+            n.id()
+        }
+        None => 0,
+    };
+    assert_eq!(taken, 7);
+    assert_eq!(drops.get(), 1);
+
+    // When every guard fails, the value is left for the fall-back arm, which
+    // doesn't bind it and so leaves it to be dropped with `other`.
+    let drops = Cell::new(0);
+    {
+        let other = Some(Noisy { id: 3, drops: &drops });
+        match other {
+            Some(n) if n.id() > 10 => drop(n),
+            _ => assert_eq!(drops.get(), 0),
+        }
+        assert_eq!(drops.get(), 0);
+    }
+    assert_eq!(drops.get(), 1);
+}
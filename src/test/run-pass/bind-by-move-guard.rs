@@ -0,0 +1,48 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// By-move bindings may be used by reference in pattern guards: the value is
+// only moved into the arm once its guard has succeeded.
+
+use std::sync::mpsc::channel;
+
+fn classify(opt: Option<String>) -> String {
+    match opt {
+        Some(s) if s.len() > 2 && s.starts_with("a") => s + " (long a)",
+        Some(s) if s.len() > 2 => s + " (long)",
+        Some(s) => s,
+        None => String::new(),
+    }
+}
+
+fn main() {
+    assert_eq!(classify(Some("abc".to_string())), "abc (long a)");
+    assert_eq!(classify(Some("bcd".to_string())), "bcd (long)");
+    assert_eq!(classify(Some("ab".to_string())), "ab");
+    assert_eq!(classify(None), "");
+
+    let (tx, rx) = channel();
+    let x = Some(rx);
+    tx.send(false).unwrap();
+    match x {
+        Some(z) if z.recv().unwrap() => { panic!() },
+        Some(z) => { assert!(z.try_recv().is_err()); },
+        None => panic!()
+    }
+
+    // Several by-move bindings, inspected through a closure.
+    let pair = (Box::new(vec![1, 2, 3]), "x".to_string());
+    let total = match pair {
+        (v, s) if s.is_empty() => v.len(),
+        (v, s) if v.iter().any(|&e| e == 2) => v.len() + s.len(),
+        _ => 0,
+    };
+    assert_eq!(total, 4);
+}
@@ -0,0 +1,44 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// A panic while translating the contents of `box EXPR` in place drops the
+// fields already written exactly once and frees the allocation.
+
+#![feature(box_syntax)]
+
+use std::sync::atomic::{AtomicUsize, ATOMIC_USIZE_INIT, Ordering};
+use std::thread;
+
+static DROPS: AtomicUsize = ATOMIC_USIZE_INIT;
+
+struct Counted;
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        DROPS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+struct Pair {
+    _first: Counted,
+    _second: [u8; 1024],
+}
+
+fn explode() -> [u8; 1024] {
+    panic!("explode")
+}
+
+pub fn main() {
+    let result = thread::spawn(move|| {
+        let _pair = box Pair { _first: Counted, _second: explode() };
+    }).join();
+    assert!(result.is_err());
+    assert_eq!(DROPS.load(Ordering::SeqCst), 1);
+}
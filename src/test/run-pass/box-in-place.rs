@@ -0,0 +1,53 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// `box EXPR` translates EXPR directly into the heap allocation. None of the
+// values below fit on the stack of the thread they are built on, so any
+// intermediate copy would overflow it.
+
+#![feature(box_syntax)]
+
+use std::thread::Builder;
+
+const N: usize = 1 << 20;
+
+struct Big {
+    data: [u8; N],
+    tag: u8,
+}
+
+enum Shape {
+    Blob([u8; N]),
+    Empty,
+}
+
+fn sum(data: &[u8]) -> usize {
+    data.iter().fold(0, |a, &b| a + b as usize)
+}
+
+pub fn main() {
+    Builder::new().stack_size(64 * 1024).scoped(move|| {
+        let array = box [1u8; N];
+        assert_eq!(sum(&*array), N);
+
+        let nested = box [[2u8; 1024]; 1024];
+        assert_eq!(nested.iter().fold(0, |a, row| a + sum(row)), 2 * N);
+
+        let big = box Big { data: [3; N], tag: 4 };
+        assert_eq!(sum(&big.data), 3 * N);
+        assert_eq!(big.tag, 4);
+
+        let shape = box Shape::Blob([5; N]);
+        match *shape {
+            Shape::Blob(ref data) => assert_eq!(sum(data), 5 * N),
+            Shape::Empty => panic!(),
+        }
+    }).unwrap().join();
+}
@@ -0,0 +1,137 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that move and ref bindings in box patterns, at one and two levels of
+// nesting, drop each value exactly once.
+
+#![feature(box_patterns, box_syntax)]
+
+use std::cell::Cell;
+
+struct Counted<'a> {
+    drops: &'a Cell<u32>,
+}
+
+impl<'a> Drop for Counted<'a> {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+    }
+}
+
+struct Pair<'a> {
+    a: Counted<'a>,
+    b: Counted<'a>,
+}
+
+fn counted(drops: &Cell<u32>) -> Counted {
+    Counted { drops: drops }
+}
+
+fn pair(drops: &Cell<u32>) -> Pair {
+    Pair { a: counted(drops), b: counted(drops) }
+}
+
+fn main() {
+    let drops = &Cell::new(0);
+
+    // Move the whole contents out of the box.
+    {
+        let b = box counted(drops);
+        match b {
+            box x => assert_eq!(x.drops.get(), 0),
+        }
+        assert_eq!(drops.get(), 1);
+    }
+    assert_eq!(drops.get(), 1);
+
+    // Borrow the contents in place.
+    drops.set(0);
+    {
+        let b = box counted(drops);
+        match b {
+            box ref x => assert_eq!(x.drops.get(), 0),
+        }
+        assert_eq!(drops.get(), 0);
+    }
+    assert_eq!(drops.get(), 1);
+
+    // Move one field out of a boxed struct, leaving the other in the box.
+    drops.set(0);
+    {
+        let b = box pair(drops);
+        match b {
+            box Pair { a, .. } => {
+                drop(a);
+                assert_eq!(drops.get(), 1);
+            }
+        }
+    }
+    assert_eq!(drops.get(), 2);
+
+    // Borrow both fields.
+    drops.set(0);
+    {
+        let b = box pair(drops);
+        match b {
+            box Pair { ref a, ref b } => {
+                assert_eq!(a.drops.get(), 0);
+                assert_eq!(b.drops.get(), 0);
+            }
+        }
+        assert_eq!(drops.get(), 0);
+    }
+    assert_eq!(drops.get(), 2);
+
+    // Two levels: a box inside an enum inside a box.
+    drops.set(0);
+    {
+        let b = box Some(box pair(drops));
+        match b {
+            box Some(box Pair { a, .. }) => drop(a),
+            box None => panic!(),
+        }
+        assert_eq!(drops.get(), 1);
+    }
+    assert_eq!(drops.get(), 2);
+
+    drops.set(0);
+    {
+        let b = box Some(box pair(drops));
+        match b {
+            box Some(box ref p) => assert_eq!(p.a.drops.get(), 0),
+            box None => panic!(),
+        }
+        assert_eq!(drops.get(), 0);
+    }
+    assert_eq!(drops.get(), 2);
+
+    drops.set(0);
+    {
+        let b = Some(box pair(drops));
+        match b {
+            Some(box Pair { a, b }) => {
+                drop(b);
+                assert_eq!(drops.get(), 1);
+                drop(a);
+                assert_eq!(drops.get(), 2);
+            }
+            None => panic!(),
+        }
+    }
+    assert_eq!(drops.get(), 2);
+
+    // The same through irrefutable `let` patterns.
+    drops.set(0);
+    {
+        let box Pair { a, b: _ } = box pair(drops);
+        drop(a);
+    }
+    assert_eq!(drops.get(), 2);
+}
@@ -0,0 +1,35 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: --cfg foo
+
+// Check `#[cfg]` on match arms, variants, fields, trait, impl and foreign
+// items (see also cfg-everywhere-notfoo.rs).
+
+#[path = "../auxiliary/cfg_everywhere.rs"]
+mod cfg_everywhere;
+
+use cfg_everywhere::{Describe, Shape, Wrapper};
+
+pub fn main() {
+    let shapes = [Shape::Circle, Shape::Square(2), Shape::Square(3)];
+    assert_eq!(shapes[0].describe(), "circle");
+    assert_eq!(shapes[1].describe(), "square");
+    assert_eq!(shapes[1].sides(), 4);
+
+    let counts = cfg_everywhere::count(&shapes);
+    assert_eq!(counts.circles, 1);
+    assert_eq!(counts.squares, 2);
+
+    let Wrapper(a, b) = Wrapper(1, 2);
+    assert_eq!((a, b), (1, 2));
+
+    assert_eq!(cfg_everywhere::parse("42"), 42);
+}
@@ -0,0 +1,30 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check `#[cfg]` on match arms, variants, fields, trait, impl and foreign
+// items (see also cfg-everywhere-foo.rs).
+
+#[path = "../auxiliary/cfg_everywhere.rs"]
+mod cfg_everywhere;
+
+use cfg_everywhere::{Describe, Shape, Wrapper};
+
+pub fn main() {
+    let shapes = [Shape::Circle, Shape::Circle];
+    assert_eq!(shapes[0].describe(), "only circles");
+
+    let counts = cfg_everywhere::count(&shapes);
+    assert_eq!(counts.circles, 2);
+
+    let Wrapper(b) = Wrapper(2);
+    assert_eq!(b, 2);
+
+    assert_eq!(cfg_everywhere::parse("-42"), 42);
+}
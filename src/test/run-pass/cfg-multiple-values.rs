@@ -0,0 +1,46 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: --cfg feature="a" --cfg feature="b" --cfg feature="a"
+
+// Every value given to a repeated `--cfg` key is visible to `#[cfg]`,
+// `#[cfg_attr]` and `cfg!`, which all agree with each other.
+
+#[cfg(feature = "a")]
+fn a() -> bool { true }
+
+#[cfg(feature = "b")]
+fn b() -> bool { true }
+
+#[cfg(feature = "c")]
+fn c() -> bool { panic!() }
+#[cfg(not(feature = "c"))]
+fn c() -> bool { false }
+
+#[cfg(not(feature = "b"))]
+fn not_b() -> bool { panic!() }
+#[cfg(feature = "b")]
+fn not_b() -> bool { false }
+
+#[cfg_attr(feature = "b", derive(PartialEq))]
+struct Derived;
+
+pub fn main() {
+    assert!(a() && b() && !c() && !not_b());
+    assert!(Derived == Derived);
+
+    assert!(cfg!(feature = "a"));
+    assert!(cfg!(feature = "b"));
+    assert!(!cfg!(feature = "c"));
+    assert!(!cfg!(not(feature = "b")));
+    assert!(cfg!(all(feature = "a", feature = "b")));
+    assert!(cfg!(any(feature = "c", feature = "b")));
+    assert!(!cfg!(feature));
+}
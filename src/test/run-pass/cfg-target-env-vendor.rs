@@ -0,0 +1,36 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that `target_env` and `target_vendor` are set for the builtin
+// targets.
+
+#[cfg(target_os = "linux")]
+fn main() {
+    assert!(cfg!(target_env = "gnu"));
+    assert!(cfg!(target_vendor = "unknown"));
+}
+
+#[cfg(windows)]
+fn main() {
+    assert!(cfg!(target_env = "gnu"));
+    assert!(cfg!(target_vendor = "pc"));
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+fn main() {
+    assert!(cfg!(target_env = ""));
+    assert!(cfg!(target_vendor = "apple"));
+}
+
+#[cfg(not(any(target_os = "linux", windows, target_os = "macos", target_os = "ios")))]
+fn main() {
+    assert!(cfg!(target_env = ""));
+    assert!(cfg!(target_vendor = "unknown"));
+}
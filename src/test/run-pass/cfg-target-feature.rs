@@ -0,0 +1,36 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-arm
+// ignore-aarch64
+// ignore-mips
+// ignore-mipsel
+// ignore-powerpc
+// compile-flags: -C target-feature=+sse2,+avx,-avx
+
+// Test that features enabled with `-C target-feature` are visible to
+// `cfg(target_feature)`, with the `+` stripped, and that disabling a
+// feature again removes it.
+
+#[cfg(target_feature = "sse2")]
+fn sse2() -> bool { true }
+#[cfg(not(target_feature = "sse2"))]
+fn sse2() -> bool { false }
+
+#[cfg(target_feature = "avx")]
+fn avx() -> bool { true }
+#[cfg(not(target_feature = "avx"))]
+fn avx() -> bool { false }
+
+fn main() {
+    assert!(sse2());
+    assert!(!avx());
+    assert!(cfg!(target_feature = "sse2"));
+}
@@ -0,0 +1,71 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Closures which capture nothing coerce to fn pointers wherever one is
+// expected.
+
+fn apply(f: fn(isize) -> isize, x: isize) -> isize {
+    f(x)
+}
+
+unsafe fn apply_unsafe(f: unsafe fn(isize) -> isize, x: isize) -> isize {
+    f(x)
+}
+
+fn pick(double: bool) -> fn(isize) -> isize {
+    if double {
+        return |x| x * 2;
+    }
+    |x| x + 1
+}
+
+struct Callbacks {
+    on_event: fn(&mut bool),
+}
+
+static TABLE: [fn(isize) -> isize; 2] = [|x| x - 1, |x| -x];
+
+fn main() {
+    assert_eq!(apply(|x| x + 1, 1), 2);
+    assert_eq!(unsafe { apply_unsafe(|x| x * 3, 2) }, 6);
+
+    let f: fn(isize) -> isize = |x| x * x;
+    assert_eq!(f(4), 16);
+    assert_eq!(apply(f, 5), 25);
+
+    assert_eq!(pick(true)(3), 6);
+    assert_eq!(pick(false)(3), 4);
+
+    let callbacks = Callbacks { on_event: |fired| *fired = true };
+    let mut fired = false;
+    (callbacks.on_event)(&mut fired);
+    assert!(fired);
+
+    let fs: [fn(isize) -> isize; 3] = [|x| x, |x| x + 10, |x| x + 20];
+    let sum = fs.iter().fold(0, |acc, f| acc + (*f)(1));
+    assert_eq!(sum, 33);
+
+    assert_eq!(TABLE[0](1), 0);
+    assert_eq!(TABLE[1](1), -1);
+
+    for i in 0..3 {
+        let g: fn(isize) -> isize = match i {
+            0 => |x| x,
+            1 => |x| x * 10,
+            _ => |x| x * 100,
+        };
+        assert_eq!(g(2), [2, 20, 200][i as usize]);
+    }
+
+    // The resulting pointers are ordinary fn pointers.
+    let h = f;
+    assert_eq!(h as usize, f as usize);
+    assert!(h(3) == 9);
+}
@@ -0,0 +1,38 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// `compile_error!` inside code that `#[cfg]` removes reports nothing, even
+// when the configured-out item comes from a macro.
+
+#[cfg(not_enabled)]
+compile_error!("configured out");
+
+#[cfg(not_enabled)]
+fn configured_out() {
+    compile_error!("configured out");
+}
+
+#[cfg_attr(not_enabled, cfg(not_enabled))]
+fn kept() -> u32 { 1 }
+
+macro_rules! unsupported_platform {
+    () => (
+        #[cfg(not_enabled)]
+        fn unsupported() {
+            compile_error!("configured out of a macro expansion");
+        }
+    )
+}
+
+unsupported_platform!();
+
+pub fn main() {
+    assert_eq!(kept(), 1);
+}
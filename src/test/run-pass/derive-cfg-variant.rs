@@ -0,0 +1,62 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// `#[derive]` must not see variants and fields that are configured out, even
+// on items produced by macros.
+
+struct NotClone;
+
+#[derive(Clone, PartialEq, Debug)]
+enum Direct {
+    A(u32),
+    #[cfg(nonexistent)]
+    B(NotClone),
+}
+
+macro_rules! make_enum {
+    () => {
+        #[derive(Clone, PartialEq, Debug)]
+        enum FromMacro {
+            A,
+            #[cfg(nonexistent)]
+            B(NotClone),
+            C {
+                x: u32,
+                #[cfg(nonexistent)]
+                y: NotClone,
+            },
+        }
+    }
+}
+
+make_enum!();
+
+macro_rules! make_gone {
+    () => {
+        // Configured out, together with the impl `#[derive]` would generate.
+        #[cfg(nonexistent)]
+        #[derive(Clone)]
+        struct Gone(NotClone);
+    }
+}
+
+make_gone!();
+
+pub fn main() {
+    let d = Direct::A(1);
+    assert_eq!(d.clone(), Direct::A(1));
+
+    let m = FromMacro::C { x: 2 };
+    assert_eq!(m.clone(), FromMacro::C { x: 2 });
+    match FromMacro::A.clone() {
+        FromMacro::A => {}
+        FromMacro::C { .. } => panic!(),
+    }
+}
@@ -0,0 +1,101 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Construction and matching of enums whose discriminant lives in the
+// spare values of a `bool`, `char` or C-like enum field.
+
+use std::cmp::Ordering;
+use std::cmp::Ordering::{Less, Equal, Greater};
+
+#[derive(Copy, PartialEq, Debug)]
+enum Colour { Red, Green, Blue }
+
+#[derive(Copy, PartialEq, Debug)]
+struct Glyph { width: u32, c: char }
+
+static NO_PAIR: Option<(u32, bool)> = None;
+static SOME_PAIR: Option<(u32, bool)> = Some((7, false));
+static NO_GLYPH: Option<Glyph> = None;
+static NO_FLAGS: Option<[bool; 2]> = None;
+static NO_CHAR: Option<char> = None;
+
+fn describe_bool(x: Option<bool>) -> &'static str {
+    match x {
+        Some(true) => "true",
+        Some(false) => "false",
+        None => "none",
+    }
+}
+
+fn describe_ordering(x: Option<Ordering>) -> int {
+    match x {
+        Some(Less) => -1,
+        Some(Equal) => 0,
+        Some(Greater) => 1,
+        None => 2,
+    }
+}
+
+fn describe_pair(x: Option<(u32, bool)>) -> u32 {
+    match x {
+        Some((n, true)) => n,
+        Some((n, false)) => n + 100,
+        None => 1000,
+    }
+}
+
+fn identity<T>(x: T) -> T { x }
+
+fn main() {
+    assert_eq!(describe_bool(Some(true)), "true");
+    assert_eq!(describe_bool(Some(false)), "false");
+    assert_eq!(describe_bool(None), "none");
+    assert_eq!(describe_bool(identity(None)), "none");
+
+    for &(o, expected) in [(Some(Less), -1), (Some(Equal), 0),
+                           (Some(Greater), 1), (None, 2)].iter() {
+        assert_eq!(describe_ordering(o), expected);
+    }
+
+    let colours = [Some(Colour::Red), Some(Colour::Green), Some(Colour::Blue), None];
+    assert_eq!(colours.iter().filter(|c| c.is_some()).count(), 3);
+    assert_eq!(colours[2], Some(Colour::Blue));
+    assert_eq!(colours[3], None);
+
+    let chars = ['a', '\u{10FFFF}', '\0'];
+    for &c in chars.iter() {
+        assert_eq!(Some(c).unwrap(), c);
+    }
+    assert_eq!(NO_CHAR, None);
+    assert!("\u{10FFFF}".chars().next().is_some());
+
+    assert_eq!(describe_pair(Some((3, true))), 3);
+    assert_eq!(describe_pair(Some((3, false))), 103);
+    assert_eq!(describe_pair(None), 1000);
+    assert_eq!(describe_pair(NO_PAIR), 1000);
+    assert_eq!(describe_pair(SOME_PAIR), 107);
+
+    assert_eq!(NO_GLYPH, None);
+    let mut glyph = Some(Glyph { width: 2, c: 'x' });
+    assert_eq!(glyph.map(|g| g.width), Some(2));
+    glyph = None;
+    assert!(glyph.is_none());
+    glyph = Some(Glyph { width: 0, c: '\0' });
+    assert_eq!(glyph.unwrap().c, '\0');
+
+    assert_eq!(NO_FLAGS, None);
+    let flags: Option<[bool; 2]> = Some([false, true]);
+    assert_eq!(flags.unwrap()[1], true);
+
+    let result: Result<bool, ()> = Err(());
+    assert!(result.is_err());
+    let result: Result<bool, ()> = Ok(false);
+    assert_eq!(result, Ok(false));
+}
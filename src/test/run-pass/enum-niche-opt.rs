@@ -0,0 +1,48 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Two-variant enums where one variant is dataless reuse the spare values of
+// a `bool`, `char` or C-like enum field as the discriminant.
+
+use std::cmp::Ordering;
+use std::mem::size_of;
+
+#[allow(dead_code)]
+enum Colour { Red, Green, Blue }
+
+#[allow(dead_code)]
+enum Full { A = 0, B = 255 }
+
+#[allow(dead_code)]
+struct Glyph { width: u32, c: char }
+
+#[allow(dead_code)]
+enum Padded { Data(u8, bool, u16), Nothing }
+
+fn main() {
+    // Raw fields
+    assert_eq!(size_of::<bool>(), size_of::<Option<bool>>());
+    assert_eq!(size_of::<char>(), size_of::<Option<char>>());
+    assert_eq!(size_of::<Ordering>(), size_of::<Option<Ordering>>());
+    assert_eq!(size_of::<Colour>(), size_of::<Option<Colour>>());
+    assert_eq!(size_of::<bool>(), size_of::<Result<bool, ()>>());
+
+    // Fields within tuples, structs and arrays
+    assert_eq!(size_of::<(u32, bool)>(), size_of::<Option<(u32, bool)>>());
+    assert_eq!(size_of::<Glyph>(), size_of::<Option<Glyph>>());
+    assert_eq!(size_of::<[bool; 3]>(), size_of::<Option<[bool; 3]>>());
+    assert_eq!(size_of::<(u8, bool, u16)>(), size_of::<Padded>());
+
+    // A C-like enum whose discriminants fill its integer has no spare value
+    assert!(size_of::<Full>() < size_of::<Option<Full>>());
+
+    // Only one level of niche is used for now
+    assert!(size_of::<Option<bool>>() < size_of::<Option<Option<bool>>>());
+}
@@ -0,0 +1,101 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// `Box<Error + Send>` works as a universal error type: errors are printed
+// through the trait object, and their causes can be walked.
+
+use std::error::Error;
+use std::fmt;
+use std::num::ParseIntError;
+
+#[derive(Debug)]
+struct ConfigError {
+    line: usize,
+    cause: LineError,
+}
+
+#[derive(Debug)]
+struct LineError {
+    cause: ParseIntError,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "bad config on line {}", self.line)
+    }
+}
+
+impl Error for ConfigError {
+    fn description(&self) -> &str { "bad config" }
+    fn cause(&self) -> Option<&Error> { Some(&self.cause) }
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "bad line")
+    }
+}
+
+impl Error for LineError {
+    fn description(&self) -> &str { "bad line" }
+    fn cause(&self) -> Option<&Error> { Some(&self.cause) }
+}
+
+fn parse(s: &str) -> Result<u32, Box<Error + Send>> {
+    if s.is_empty() {
+        return Err(From::from("empty input"));
+    }
+    match s.parse::<u32>() {
+        Ok(n) => Ok(n),
+        Err(e) => Err(From::from(ConfigError { line: 3, cause: LineError { cause: e } })),
+    }
+}
+
+fn try_parse(s: &str) -> Result<u32, Box<Error + Send>> {
+    let n = try!(s.parse::<u32>());
+    if n == 0 {
+        try!(Err(format!("zero is not allowed")));
+    }
+    Ok(n)
+}
+
+fn chain(err: &Error) -> Vec<String> {
+    let mut descriptions = vec![err.description().to_string()];
+    let mut cur = err.cause();
+    while let Some(e) = cur {
+        descriptions.push(e.description().to_string());
+        cur = e.cause();
+    }
+    descriptions
+}
+
+fn main() {
+    assert_eq!(parse("7").unwrap(), 7);
+
+    let err = parse("x").unwrap_err();
+    assert_eq!(err.to_string(), "bad config on line 3");
+    let descriptions = chain(&*err);
+    assert_eq!(descriptions.len(), 3);
+    assert_eq!(descriptions[0], "bad config");
+    assert_eq!(descriptions[1], "bad line");
+
+    let err = parse("").unwrap_err();
+    assert_eq!(err.to_string(), "empty input");
+    assert_eq!(err.description(), "empty input");
+    assert!(err.cause().is_none());
+
+    assert!(try_parse("x").is_err());
+    assert_eq!(try_parse("0").unwrap_err().to_string(), "zero is not allowed");
+
+    // Boxed errors can be sent to other threads.
+    let handle = std::thread::spawn(move || parse("y"));
+    let err = handle.join().unwrap().unwrap_err();
+    assert_eq!(format!("{}", err), "bad config on line 3");
+}
@@ -0,0 +1,32 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that the standard macros keep working when the standard library is
+// only available under another name.
+
+#![feature(no_std)]
+#![no_std]
+
+#[macro_use]
+extern crate std as core_plus;
+
+use core_plus::prelude::v1::*;
+
+fn main() {
+    let v = vec![1, 2, 3];
+    let s = format!("{:?} has {} elements", v, v.len());
+    assert_eq!(s, "[1, 2, 3] has 3 elements");
+    println!("{}", s);
+
+    let r = core_plus::thread::spawn(move || {
+        panic!("this is {}", "expected");
+    }).join();
+    assert!(r.is_err());
+}
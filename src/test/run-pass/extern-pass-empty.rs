@@ -12,11 +12,13 @@
 
 // pretty-expanded FIXME #23616
 
+#[repr(C)]
 struct TwoU8s {
     one: u8,
     two: u8,
 }
 
+#[repr(C)]
 struct ManyInts {
     arg1: i8,
     arg2: i16,
@@ -26,6 +28,7 @@ struct ManyInts {
     arg6: TwoU8s,
 }
 
+#[repr(C)]
 struct Empty;
 
 #[link(name = "rust_test_helpers")]
@@ -0,0 +1,64 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that fn pointers can be compared, hashed and formatted.
+
+use std::collections::HashMap;
+
+fn double(x: isize) -> isize { x * 2 }
+fn square(x: isize) -> isize { x * x }
+fn negate(x: isize) -> isize { -x }
+
+unsafe fn unsafe_double(x: isize) -> isize { x * 2 }
+extern "C" fn c_double(x: isize) -> isize { x * 2 }
+
+fn nullary() {}
+fn many(_: u8, _: u16, _: u32, _: u64, _: i8, _: i16,
+        _: i32, _: i64, _: usize, _: isize, _: char, _: bool) {}
+
+fn is_hex_address(s: &str) -> bool {
+    s.starts_with("0x") && s.len() > 2 && s[2..].chars().all(|c| c.is_digit(16))
+}
+
+fn main() {
+    let fns: [fn(isize) -> isize; 3] = [double, square, negate];
+
+    let mut names: HashMap<fn(isize) -> isize, &str> = HashMap::new();
+    names.insert(fns[0], "double");
+    names.insert(fns[1], "square");
+    names.insert(fns[2], "negate");
+    assert_eq!(names.len(), 3);
+    assert_eq!(names.get(&fns[1]), Some(&"square"));
+    assert_eq!(names.get(&(negate as fn(isize) -> isize)), Some(&"negate"));
+
+    let f = fns[0];
+    let h = f;
+    assert!(h == f);
+    assert!(h != fns[1]);
+    assert_eq!(h.cmp(&f), std::cmp::Ordering::Equal);
+    assert!(fns[0] < fns[1] || fns[1] < fns[0]);
+
+    let u: unsafe fn(isize) -> isize = unsafe_double;
+    let c: extern "C" fn(isize) -> isize = c_double;
+    let n: fn() = nullary;
+    let m: fn(u8, u16, u32, u64, i8, i16, i32, i64, usize, isize, char, bool) = many;
+    assert!(u == u);
+    assert!(c == c);
+    assert!(n == n);
+    assert!(m == m);
+
+    for s in &[format!("{:p}", f), format!("{:?}", f),
+               format!("{:p}", u), format!("{:?}", u),
+               format!("{:p}", c), format!("{:?}", c)] {
+        assert!(is_hex_address(s), "`{}` is not an address", s);
+    }
+    assert_eq!(format!("{:p}", f), format!("{:?}", h));
+    assert_eq!(format!("{:?}", f), format!("{:p}", f as *const ()));
+}
@@ -0,0 +1,50 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that references to arrays and collections can be iterated over
+// directly, through `IntoIterator`.
+
+use std::collections::HashMap;
+
+fn main() {
+    let mut array = [1, 2, 3];
+
+    let mut sum = 0;
+    for x in &array {
+        sum += *x;
+    }
+    assert_eq!(sum, 6);
+
+    for x in &mut array {
+        *x *= 2;
+    }
+    assert_eq!(array, [2, 4, 6]);
+
+    let slice: &[i32] = &array;
+    let mut sum = 0;
+    for x in slice {
+        sum += *x;
+    }
+    assert_eq!(sum, 12);
+
+    let mut map = HashMap::new();
+    map.insert("a", 1);
+    map.insert("b", 2);
+
+    for (_, v) in &mut map {
+        *v += 10;
+    }
+
+    let mut sum = 0;
+    for (_, v) in &map {
+        sum += *v;
+    }
+    assert_eq!(sum, 23);
+}
@@ -0,0 +1,44 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that functional record update can fill private fields of a struct
+// from another module out of the base expression, as long as one of them is
+// moved out of it.
+
+mod config {
+    pub struct Config {
+        pub verbose: bool,
+        pub level: u8,
+        path: String,
+        retries: u32,
+    }
+
+    pub fn new(path: &str) -> Config {
+        Config { verbose: false, level: 0, path: path.to_string(), retries: 3 }
+    }
+
+    impl Config {
+        pub fn path(&self) -> &str { &self.path }
+        pub fn retries(&self) -> u32 { self.retries }
+    }
+}
+
+pub fn main() {
+    let base = config::new("/tmp/x");
+    let loud = config::Config { verbose: true, ..base };
+    assert!(loud.verbose);
+    assert_eq!(loud.level, 0);
+    assert_eq!(loud.path(), "/tmp/x");
+    assert_eq!(loud.retries(), 3);
+
+    let quiet = config::Config { verbose: false, level: 2, ..config::new("/etc") };
+    assert_eq!(quiet.level, 2);
+    assert_eq!(quiet.path(), "/etc");
+}
@@ -0,0 +1,79 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that omitted type parameters with defaults are filled in from
+// earlier parameters in type position, and fall back to their defaults
+// in expressions once inference leaves them unconstrained.
+
+use std::marker::PhantomData;
+
+trait Named {
+    fn name() -> &'static str;
+}
+
+#[derive(Debug, PartialEq)]
+struct Plain;
+#[derive(Debug, PartialEq)]
+struct Fancy;
+
+impl Named for Plain {
+    fn name() -> &'static str { "plain" }
+}
+
+impl Named for Fancy {
+    fn name() -> &'static str { "fancy" }
+}
+
+// Shaped like `HashMap<K, V, S = RandomState>`, with `V` defaulting to `K`.
+#[derive(Debug, PartialEq)]
+struct Map<K, V = K, S = Plain> {
+    entries: Vec<(K, V)>,
+    state: PhantomData<S>,
+}
+
+impl<K, V, S: Named> Map<K, V, S> {
+    fn new() -> Map<K, V, S> {
+        Map { entries: Vec::new(), state: PhantomData }
+    }
+
+    fn insert(&mut self, k: K, v: V) {
+        self.entries.push((k, v));
+    }
+
+    fn state_name(&self) -> &'static str {
+        <S as Named>::name()
+    }
+}
+
+struct Tagged<T, Tag = Plain>(T, PhantomData<Tag>);
+
+fn tag_of<T, Tag: Named>(_: &Tagged<T, Tag>) -> &'static str {
+    <Tag as Named>::name()
+}
+
+fn main() {
+    let mut m: Map<u8> = Map::new();
+    m.insert(1, 2);
+    assert_eq!(m.entries, vec![(1u8, 2u8)]);
+    assert_eq!(m.state_name(), "plain");
+
+    let mut m: Map<u8, &str, Fancy> = Map::new();
+    m.insert(1, "one");
+    assert_eq!(m.state_name(), "fancy");
+
+    // Nothing but the default constrains `Tag` here.
+    let t = Tagged(5u8, PhantomData);
+    assert_eq!(tag_of(&t), "plain");
+
+    // Inference takes precedence over the default.
+    let f = Tagged(5u8, PhantomData);
+    let _: &Tagged<u8, Fancy> = &f;
+    assert_eq!(tag_of(&f), "fancy");
+}
@@ -0,0 +1,69 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// A HashMap with a user-defined hasher, keyed by a type with a derived Hash
+// impl that is generic over its contents.
+
+#![feature(std_misc)]
+
+use std::collections::HashMap;
+use std::collections::hash_state::HashState;
+use std::hash::{Hash, Hasher};
+
+struct FnvHasher(u64);
+
+impl Hasher for FnvHasher {
+    fn finish(&self) -> u64 { self.0 }
+
+    fn write(&mut self, bytes: &[u8]) {
+        let FnvHasher(mut hash) = *self;
+        for byte in bytes {
+            hash = hash ^ (*byte as u64);
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+        *self = FnvHasher(hash);
+    }
+}
+
+#[derive(Clone, Copy)]
+struct FnvState;
+
+impl HashState for FnvState {
+    type Hasher = FnvHasher;
+
+    fn hasher(&self) -> FnvHasher { FnvHasher(0xcbf29ce484222325) }
+}
+
+#[derive(Hash, PartialEq, Eq, Debug)]
+struct Pair<A, B> {
+    first: A,
+    second: B,
+}
+
+fn fnv<T: Hash>(t: &T) -> u64 {
+    let mut hasher = FnvState.hasher();
+    t.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn main() {
+    let mut map = HashMap::with_hash_state(FnvState);
+    map.insert(Pair { first: 1u8, second: "one" }, 1);
+    map.insert(Pair { first: 2u8, second: "two" }, 2);
+    assert_eq!(map.get(&Pair { first: 1u8, second: "one" }), Some(&1));
+    assert_eq!(map.get(&Pair { first: 2u8, second: "two" }), Some(&2));
+    assert_eq!(map.get(&Pair { first: 1u8, second: "two" }), None);
+
+    // The derived impl hashes the fields in order.
+    let mut hasher = FnvState.hasher();
+    1u8.hash(&mut hasher);
+    "one".hash(&mut hasher);
+    assert_eq!(fnv(&Pair { first: 1u8, second: "one" }), hasher.finish());
+}
@@ -0,0 +1,57 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that a closure bounded by `for<'a> Fn(&'a str) -> &'a str` can be
+// passed down through generic functions that repeat the bound, and that its
+// output type can be named through the `FnOnce::Output` projection, even
+// under a binder.
+
+#![feature(unboxed_closures)]
+
+fn call<F>(f: &F, s: &str) -> usize
+    where F: for<'a> Fn(&'a str) -> &'a str
+{
+    f(s).len()
+}
+
+fn map_words<F>(f: F, words: &[&str]) -> Vec<usize>
+    where F: for<'a> Fn(&'a str) -> &'a str
+{
+    words.iter().map(|w| call(&f, w)).collect()
+}
+
+fn project<'b, F>(f: &F, s: &'b str) -> <F as FnOnce<(&'b str,)>>::Output
+    where F: for<'a> Fn(&'a str) -> &'a str
+{
+    f(s)
+}
+
+fn through_fn_pointer<F>(f: F, s: &str) -> String
+    where F: for<'a> Fn(&'a str) -> &'a str
+{
+    // The projection in `project`'s signature appears under the binder of
+    // the fn pointer type, and must still normalize to `&'a str`.
+    let g: for<'a> fn(&F, &'a str) -> &'a str = project;
+    let h: for<'a> fn(&F, &'a str) -> <F as FnOnce<(&'a str,)>>::Output = project;
+    format!("{}/{}", g(&f, s), h(&f, s))
+}
+
+fn first_word(s: &str) -> &str {
+    s.split(' ').next().unwrap()
+}
+
+pub fn main() {
+    assert_eq!(map_words(|s| s.trim(), &["  a ", "bc", " def"]), vec![1, 2, 3]);
+    assert_eq!(map_words(first_word, &["one two", "three"]), vec![3, 5]);
+
+    let owned = "hello world".to_string();
+    assert_eq!(project(&first_word, &owned), "hello");
+    assert_eq!(through_fn_pointer(first_word, &owned), "hello/hello");
+}
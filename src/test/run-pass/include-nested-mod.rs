@@ -0,0 +1,24 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-pretty
+
+// The included file declares an out-of-line module, found next to the
+// included file rather than next to this one, and includes a file of its
+// own relative to itself.
+
+include!("include-nested-mod-aux/generated.rs");
+
+fn main() {
+    assert_eq!(child::child(), 1);
+    assert_eq!(nested(), 2);
+    assert!(generated_file().ends_with("generated.rs"));
+    assert!(file!().ends_with("include-nested-mod.rs"));
+}
@@ -19,6 +19,7 @@
 extern crate libc;
 use libc::{c_uint, uint32_t, c_void};
 
+#[repr(C)]
 pub struct KEYGEN {
     hash_algorithm: [c_uint; 2],
     count: uint32_t,
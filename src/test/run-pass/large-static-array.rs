@@ -0,0 +1,22 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// A large array that fits in the address space is accepted.
+
+use std::mem;
+
+static BIG: [u8; 1 << 24] = [0; 1 << 24];
+
+pub fn main() {
+    assert_eq!(BIG.len(), 1 << 24);
+    assert_eq!(BIG[(1 << 24) - 1], 0);
+    assert_eq!(mem::size_of::<[u8; 1 << 24]>(), 1 << 24);
+    assert_eq!(mem::size_of::<[(); 1 << 30]>(), 0);
+}
@@ -0,0 +1,30 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:two_macros.rs
+// aux-build:other_two_macros.rs
+// ignore-stage1
+
+// Both crates export `macro_two`, but importing only the other macros by
+// name keeps them from colliding.
+
+#[macro_use(macro_one)]
+extern crate two_macros;
+
+#[macro_use(macro_three)]
+extern crate other_two_macros;
+
+macro_rules! macro_two { () => ("local two") }
+
+pub fn main() {
+    assert_eq!(macro_one!(), "one");
+    assert_eq!(macro_two!(), "local two");
+    assert_eq!(macro_three!(), "three");
+}
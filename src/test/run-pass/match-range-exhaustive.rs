@@ -0,0 +1,67 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Literal and range patterns which together cover every value of an integer
+// or `char` type make a match exhaustive.
+
+fn unsigned(x: u8) -> u32 {
+    match x {
+        0...127 => 0,
+        128...254 => 1,
+        255 => 2,
+    }
+}
+
+fn signed(x: i8) -> u32 {
+    match x {
+        -128...-1 => 0,
+        0 => 1,
+        1...127 => 2,
+    }
+}
+
+fn wide(x: u64) -> bool {
+    match x {
+        0...9 => true,
+        10...18446744073709551615 => false,
+    }
+}
+
+fn chars(c: char) -> u32 {
+    // The surrogates `\u{D800}` to `\u{DFFF}` aren't `char`s.
+    match c {
+        '\u{0}'...'\u{D7FF}' => 0,
+        '\u{E000}'...'\u{10FFFF}' => 1,
+    }
+}
+
+fn nested(x: (bool, u8)) -> u32 {
+    match x {
+        (true, 0...99) => 0,
+        (false, _) => 1,
+        (_, 100...255) => 2,
+    }
+}
+
+fn main() {
+    assert_eq!(unsigned(0), 0);
+    assert_eq!(unsigned(200), 1);
+    assert_eq!(unsigned(255), 2);
+    assert_eq!(signed(-128), 0);
+    assert_eq!(signed(0), 1);
+    assert_eq!(signed(127), 2);
+    assert!(wide(3));
+    assert!(!wide(!0));
+    assert_eq!(chars('a'), 0);
+    assert_eq!(chars('\u{10FFFF}'), 1);
+    assert_eq!(nested((true, 5)), 0);
+    assert_eq!(nested((false, 5)), 1);
+    assert_eq!(nested((true, 100)), 2);
+}
@@ -0,0 +1,35 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-pretty
+
+// Out-of-line modules declared inside inline modules are looked up in a
+// directory made of the names of the enclosing inline modules. In a file
+// that doesn't own its directory (`file.rs` here), that directory is
+// named after the file's own module.
+
+mod mod_dir_nested_inline_aux {
+    pub mod bar {
+        pub mod foo;
+        pub mod baz;
+    }
+
+    pub mod file;
+
+    #[path = "dir"]
+    pub mod dir;
+}
+
+pub fn main() {
+    assert_eq!(mod_dir_nested_inline_aux::bar::foo::foo(), 1);
+    assert_eq!(mod_dir_nested_inline_aux::bar::baz::baz(), 2);
+    assert_eq!(mod_dir_nested_inline_aux::file::inner::leaf::leaf(), 3);
+    assert_eq!(mod_dir_nested_inline_aux::dir::dir(), 4);
+}
@@ -0,0 +1,37 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(optin_builtin_traits)]
+
+// Test that a negative impl whose where clause does not hold does not
+// prevent the default impl from applying.
+
+use std::marker::{MarkerTrait, PhantomData};
+use std::thread;
+
+trait Local : MarkerTrait {}
+
+impl Local for u8 {}
+
+struct Wrapper<T>(PhantomData<T>);
+
+impl<T: Local> !Send for Wrapper<T> {}
+
+fn is_send<T: Send>() {}
+
+fn main() {
+    is_send::<Wrapper<u16>>();
+    is_send::<Wrapper<String>>();
+
+    let w: Wrapper<u16> = Wrapper(PhantomData);
+    thread::spawn(move || {
+        let _w = w;
+    }).join().ok().unwrap();
+}
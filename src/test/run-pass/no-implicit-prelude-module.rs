@@ -0,0 +1,42 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// A module can opt out of the prelude with an inner attribute and then import
+// just the parts of it that it needs, without affecting the rest of the crate.
+
+mod narrow {
+    #![no_implicit_prelude]
+
+    use std::option::Option::{self, Some, None};
+    use std::vec::Vec;
+
+    pub fn first_even(v: &Vec<i32>) -> Option<i32> {
+        for &x in v {
+            if x % 2 == 0 {
+                return Some(x);
+            }
+        }
+        None
+    }
+
+    pub mod nested {
+        // The opt-out covers the whole subtree.
+        pub fn make() -> ::std::vec::Vec<i32> {
+            vec![1, 3, 4, 6]
+        }
+    }
+}
+
+fn main() {
+    let v = narrow::nested::make();
+    assert_eq!(narrow::first_even(&v), Some(4));
+    let empty: Vec<i32> = Vec::new();
+    assert_eq!(narrow::first_even(&empty), None);
+}
@@ -0,0 +1,61 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that the formatting and assertion macros from libcore work in a
+// `#![no_std]` crate, here writing into a fixed-size buffer.
+
+#![feature(lang_items, start, no_std, core)]
+#![no_std]
+
+extern crate "std" as other;
+
+#[macro_use]
+extern crate core;
+
+use core::prelude::*;
+use core::fmt::{self, Write};
+
+struct Buffer {
+    bytes: [u8; 16],
+    len: usize,
+}
+
+impl Write for Buffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let s = s.as_bytes();
+        if self.len + s.len() > self.bytes.len() {
+            return Err(fmt::Error);
+        }
+        for (i, &b) in s.iter().enumerate() {
+            self.bytes[self.len + i] = b;
+        }
+        self.len += s.len();
+        Ok(())
+    }
+}
+
+fn fill(buf: &mut Buffer) -> fmt::Result {
+    try!(write!(buf, "{} + {}", 1, 2));
+    try!(writeln!(buf, " = {}", 1 + 2));
+    Ok(())
+}
+
+#[start]
+fn start(_argc: isize, _argv: *const *const u8) -> isize {
+    let mut buf = Buffer { bytes: [0; 16], len: 0 };
+    assert!(fill(&mut buf).is_ok());
+    assert_eq!(buf.len, 10);
+    assert!(&buf.bytes[..buf.len] == &b"1 + 2 = 3\n"[..]);
+    debug_assert!(buf.bytes[buf.len] == 0);
+
+    // Running out of room is reported as an error rather than a panic.
+    assert!(write!(&mut buf, "{}", "does not fit").is_err());
+    0
+}
@@ -0,0 +1,59 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that an object type carrying builtin bounds alongside its principal
+// trait can be sent to another thread, and that the bounds survive the trip.
+
+#![feature(box_syntax)]
+
+use std::sync::mpsc::channel;
+use std::thread;
+
+trait Shape {
+    fn area(&self) -> usize;
+}
+
+struct Square(usize);
+struct Rect(usize, usize);
+
+impl Shape for Square {
+    fn area(&self) -> usize { self.0 * self.0 }
+}
+
+impl Shape for Rect {
+    fn area(&self) -> usize { self.0 * self.1 }
+}
+
+fn assert_send<T: Send>(_: &T) {}
+fn assert_sync<T: Sync>(_: &T) {}
+
+fn total(shapes: Vec<Box<Shape + Send + Sync + 'static>>) -> usize {
+    shapes.iter().map(|s| s.area()).fold(0, |a, b| a + b)
+}
+
+pub fn main() {
+    let (tx, rx) = channel::<Box<Shape + Send + 'static>>();
+
+    let guard = thread::scoped(move|| {
+        tx.send(box Square(3) as Box<Shape + Send>).unwrap();
+        tx.send(box Rect(2, 5)).unwrap();
+    });
+
+    let shapes: Vec<Box<Shape + Send>> = rx.iter().collect();
+    guard.join();
+    assert_eq!(shapes.len(), 2);
+    assert_eq!(shapes[0].area(), 9);
+    assert_eq!(shapes[1].area(), 10);
+
+    let synced: Box<Shape + Send + Sync> = box Square(4);
+    assert_send(&synced);
+    assert_sync(&synced);
+    assert_eq!(total(vec![synced, box Rect(1, 1) as Box<Shape + Send + Sync>]), 17);
+}
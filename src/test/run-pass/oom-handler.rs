@@ -0,0 +1,53 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-aarch64
+#![feature(alloc, libc)]
+
+extern crate alloc;
+extern crate libc;
+
+use std::env;
+use std::process::Command;
+use std::str;
+use std::usize;
+
+const MESSAGE: &'static str = "oom handler called";
+
+// Handlers must not allocate, so write straight to stderr.
+fn handler() {
+    unsafe {
+        libc::write(libc::STDERR_FILENO,
+                    MESSAGE.as_ptr() as *const libc::c_void,
+                    MESSAGE.len() as libc::size_t);
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() > 1 && args[1] == "child" {
+        alloc::oom::set_oom_handler(handler);
+        // Way more than any allocator will hand out, but without
+        // overflowing the size computation.
+        let mut v: Vec<u8> = Vec::new();
+        v.reserve_exact(usize::MAX / 2);
+        return;
+    }
+
+    // Fallible reservations report the failure instead.
+    let mut v: Vec<u8> = Vec::new();
+    assert!(v.try_reserve_exact(usize::MAX / 2).is_err());
+    assert!(v.try_reserve(16).is_ok());
+    assert!(v.capacity() >= 16);
+
+    let output = Command::new(&args[0]).arg("child").output().unwrap();
+    assert!(!output.status.success());
+    assert!(str::from_utf8(&output.stderr).unwrap().contains(MESSAGE));
+}
@@ -0,0 +1,69 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Reading and writing the fields of a packed struct by value is safe; only
+// borrowing them needs `unsafe`, and doing so uses the block.
+
+#![deny(unused_unsafe)]
+
+#[repr(packed)]
+struct Packed {
+    a: u8,
+    b: u32,
+    inner: Inner,
+}
+
+#[repr(packed)]
+struct PackedTuple(u8, u32);
+
+#[derive(Copy)]
+struct Inner {
+    x: u32,
+}
+
+fn main() {
+    let mut p = Packed { a: 1, b: 2, inner: Inner { x: 3 } };
+
+    let b = p.b;
+    assert_eq!(b, 2);
+    p.b = 3;
+    p.b += 1;
+    assert!(p.b == 4);
+    let x = p.inner.x;
+    assert_eq!(x, 3);
+    p.inner.x = 5;
+    let inner = p.inner;
+    assert_eq!(inner.x, 5);
+
+    match p.b {
+        4 => {}
+        _ => panic!(),
+    }
+    match p {
+        Packed { a, b, .. } => {
+            assert_eq!(a, 1);
+            assert_eq!(b, 4);
+        }
+    }
+    let PackedTuple(_, b) = PackedTuple(1, 2);
+    assert_eq!(b, 2);
+
+    let boxed = Box::new(Packed { a: 1, b: 2, inner: Inner { x: 3 } });
+    let b = boxed.b;
+    assert_eq!(b, 2);
+
+    let r = unsafe { &p.b };
+    assert_eq!(*r, 4);
+    let ref_b = unsafe {
+        let ref b = p.b;
+        b
+    };
+    assert_eq!(*ref_b, 4);
+}
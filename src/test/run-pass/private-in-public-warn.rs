@@ -0,0 +1,28 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Private types in exported signatures are only a warning by default.
+
+#![allow(dead_code)]
+
+struct Secret(int);
+
+pub fn make() -> Secret { Secret(3) }
+
+pub struct Holder {
+    pub secret: Secret,
+}
+
+pub fn main() {
+    let Secret(x) = make();
+    assert_eq!(x, 3);
+    let h = Holder { secret: Secret(4) };
+    assert_eq!(h.secret.0, 4);
+}
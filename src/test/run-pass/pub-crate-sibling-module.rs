@@ -0,0 +1,38 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Items declared `pub(crate)` may be used from anywhere in the crate.
+
+#![feature(pub_crate)]
+
+mod a {
+    pub(crate) fn f() -> u32 { 1 }
+
+    pub(crate) struct S {
+        pub(crate) x: u32,
+    }
+
+    pub(crate) mod inner {
+        pub(crate) fn g() -> u32 { 2 }
+    }
+}
+
+mod b {
+    use a::S;
+
+    pub fn sum() -> u32 {
+        let s = S { x: 3 };
+        ::a::f() + ::a::inner::g() + s.x
+    }
+}
+
+pub fn main() {
+    assert_eq!(b::sum(), 6);
+}
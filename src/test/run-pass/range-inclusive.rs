@@ -0,0 +1,51 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test inclusive range syntax and iteration up to the maximum value of a
+// type.
+
+#![feature(inclusive_range_syntax, inclusive_range)]
+
+use std::ops::RangeInclusive;
+use std::u8;
+
+fn main() {
+    let mut count = 0;
+    for i in 0u8...255 {
+        count += 1;
+        assert!(i <= u8::MAX);
+    }
+    assert_eq!(count, 256);
+
+    let r: RangeInclusive<i32> = 1...3;
+    assert_eq!(r.start, 1);
+    assert_eq!(r.end, 3);
+    assert_eq!(r.size_hint(), (3, Some(3)));
+    assert_eq!(r.collect::<Vec<_>>(), [1, 2, 3]);
+
+    assert_eq!((1...3).rev().collect::<Vec<_>>(), [3, 2, 1]);
+    assert_eq!((-128i8...-127).rev().collect::<Vec<_>>(), [-127, -128]);
+
+    // A single element is yielded exactly once, from either end.
+    let mut r = 255u8...255;
+    assert_eq!(r.next(), Some(255));
+    assert_eq!(r.next(), None);
+    assert_eq!(r.next_back(), None);
+
+    // Ranges whose start is past their end are empty.
+    assert_eq!((5...3).next(), None::<i32>);
+    assert_eq!((5...3).size_hint(), (0, Some(0)));
+
+    // The bounds may be arbitrary expressions.
+    let v = vec![1, 2, 3];
+    assert_eq!((v[0]...v.len() as i32 + 1).fold(0, |a, b| a + b), 10);
+
+    println!("{:?}", 1...10);
+}
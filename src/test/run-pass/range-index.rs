@@ -0,0 +1,48 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test indexing strings, slices and vectors by each form of range.
+
+fn main() {
+    let s = "Löwe 老虎";
+    assert_eq!(&s[1..6], "öwe ");
+    assert_eq!(&s[..3], "Lö");
+    assert_eq!(&s[6..], "老虎");
+    assert_eq!(&s[..], s);
+    assert_eq!(&s[3..3], "");
+
+    let string = s.to_string();
+    assert_eq!(&string[1..6], "öwe ");
+    assert_eq!(&string[..3], "Lö");
+    assert_eq!(&string[6..], "老虎");
+    assert_eq!(&string[..], s);
+
+    let mut v = vec![1, 2, 3, 4, 5];
+    assert_eq!(&v[1..3], [2, 3]);
+    assert_eq!(&v[..2], [1, 2]);
+    assert_eq!(&v[3..], [4, 5]);
+    assert_eq!(&v[..], [1, 2, 3, 4, 5]);
+
+    for x in &mut v[1..3] {
+        *x *= 10;
+    }
+    (&mut v[..1])[0] = 0;
+    (&mut v[4..])[0] = 50;
+    assert_eq!(&v[..], [0, 20, 30, 4, 50]);
+
+    let slice: &mut [i32] = &mut v;
+    assert_eq!(&slice[1..3], [20, 30]);
+    assert_eq!(&slice[..2], [0, 20]);
+    assert_eq!(&slice[3..], [4, 50]);
+
+    // Indexing through a reference auto-derefs.
+    let r = &&string;
+    assert_eq!(&r[..1], "L");
+}
@@ -0,0 +1,39 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-windows
+// ignore-android
+// ignore-freebsd
+// ignore-dragonfly
+// ignore-bitrig
+// ignore-openbsd
+// The drops are only kept from nesting where `#[thread_local]` is available.
+
+// Dropping a long `Rc`-linked list must not need stack space proportional to
+// its length, even on a thread with a small stack.
+
+use std::rc::Rc;
+use std::thread;
+
+struct Node {
+    _value: u32,
+    next: Option<Rc<Node>>,
+}
+
+fn main() {
+    thread::Builder::new().stack_size(64 * 1024).spawn(|| {
+        let mut head = None;
+        for i in 0..1_000_000 {
+            head = Some(Rc::new(Node { _value: i, next: head }));
+        }
+        assert!(head.as_ref().unwrap().next.is_some());
+        drop(head);
+    }).unwrap().join().unwrap();
+}
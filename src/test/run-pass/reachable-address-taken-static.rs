@@ -0,0 +1,22 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:reachable_address_taken_static.rs
+
+// Test that private statics whose address is taken in exported generic
+// functions are available to the crates instantiating those functions.
+
+extern crate reachable_address_taken_static as other;
+
+fn main() {
+    assert_eq!(*other::count_ref::<()>(), 42);
+    assert_eq!(other::name::<()>(1), "second");
+    assert_eq!(other::default::<()>(), 42);
+}
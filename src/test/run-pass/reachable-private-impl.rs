@@ -0,0 +1,24 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:reachable_private_impl.rs
+
+// Test that methods of private impls called from exported generic
+// functions are available to the crates instantiating those functions.
+
+extern crate reachable_private_impl as other;
+
+fn main() {
+    assert_eq!(other::concrete::<()>(1), 2);
+    assert_eq!(other::default_method::<()>(1), 4);
+    assert_eq!(other::through_path::<()>(2), 3);
+    assert_eq!(other::through_object::<()>(3), 4);
+    assert_eq!(other::overloaded_operator::<()>(3, 4), 7);
+}
@@ -0,0 +1,45 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(recover)]
+
+use std::panic::{self, AssertRecoverSafe};
+use std::sync::Mutex;
+
+fn main() {
+    // Returning normally yields the closure's value.
+    assert_eq!(panic::recover(|| 1 + 2).ok(), Some(3));
+
+    // A panic is caught, and the thread carries on.
+    let result = panic::recover(|| -> i32 { panic!("oh no") });
+    let payload = result.err().unwrap();
+    assert_eq!(*payload.downcast_ref::<&'static str>().unwrap(), "oh no");
+
+    // Shared references to a mutex may cross the boundary, since the mutex
+    // is poisoned by the panic.
+    let lock = Mutex::new(0);
+    let result = panic::recover(|| {
+        *lock.lock().unwrap() += 1;
+        panic!("poison the lock");
+    });
+    assert!(result.is_err());
+    assert!(lock.is_poisoned());
+
+    // Mutable references must be asserted to be safe.
+    let mut count = 0;
+    for _ in 0..3 {
+        let result = panic::recover(AssertRecoverSafe(|| {
+            count += 1;
+            panic!("resume me");
+        }));
+        assert!(result.is_err());
+    }
+    assert_eq!(count, 3);
+}
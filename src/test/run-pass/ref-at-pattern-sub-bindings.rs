@@ -0,0 +1,56 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// `ref x @ subpattern` may contain shared borrows and copies of the parts
+// of `x`'s referent.
+
+#[derive(Debug, PartialEq)]
+struct Pair {
+    name: String,
+    count: u32,
+}
+
+fn describe(opt: Option<Pair>) -> String {
+    match opt {
+        ref whole @ Some(Pair { ref name, count }) => {
+            assert_eq!(whole.as_ref().unwrap().name, *name);
+            format!("{} x{} ({:?})", name, count, whole)
+        }
+        None => "nothing".to_string(),
+    }
+}
+
+fn main() {
+    let pair = Some(Pair { name: "a".to_string(), count: 2 });
+    assert_eq!(describe(pair),
+               "a x2 (Some(Pair { name: \"a\", count: 2 }))");
+    assert_eq!(describe(None), "nothing");
+
+    // Irrefutable patterns, nested `@`s and the original value outliving
+    // all of the borrows.
+    let v = (vec![1, 2, 3], "x".to_string());
+    {
+        let ref outer @ (ref inner @ ref first, ref s) = v;
+        assert_eq!(outer.0.len(), 3);
+        assert_eq!(inner.len(), 3);
+        assert!(first.as_ptr() == inner.as_ptr());
+        assert!(&outer.1 as *const String == s as *const String);
+    }
+    assert_eq!(v.0, vec![1, 2, 3]);
+
+    let b = Box::new(Some(5));
+    match *b {
+        ref x @ Some(y) => {
+            assert_eq!(*x, Some(5));
+            assert_eq!(y, 5);
+        }
+        None => panic!(),
+    }
+}
@@ -0,0 +1,29 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -C debug-assertions=no
+
+// Without debug assertions, over-long shifts are defined to mask the shift
+// amount to the bit width of the shifted type.
+// (compare with test/run-fail/overflowing-{lsh,rsh}*.rs)
+
+// (Work around constant-evaluation)
+fn id<T>(x: T) -> T { x }
+
+fn main() {
+    assert_eq!(1_u8 << id(9), 2);
+    assert_eq!(1_u16 << id(17), 2);
+    assert_eq!(1_u32 << id(33), 2);
+    assert_eq!(1_u64 << id(65), 2);
+    assert_eq!(1_i32 << id(32), 1);
+    assert_eq!(4_u8 >> id(10), 1);
+    assert_eq!(4_u64 >> id(66), 1);
+    assert_eq!(-4_i32 >> id(33), -2);
+}
@@ -0,0 +1,103 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// A comparison that panics part way through any of the slice sorts must leave
+// the slice holding each of its elements exactly once: nothing may be lost,
+// duplicated, or dropped twice.
+
+#![feature(collections, rand)]
+
+use std::cmp::Ordering;
+use std::rand::{thread_rng, Rng};
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+struct DropCounter {
+    key: u32,
+    id: usize,
+    drops: Arc<Vec<AtomicUsize>>,
+}
+
+impl Drop for DropCounter {
+    fn drop(&mut self) {
+        self.drops[self.id].fetch_add(1, AtomicOrdering::Relaxed);
+    }
+}
+
+fn stable(v: &mut [DropCounter], compare: &mut FnMut(&DropCounter, &DropCounter) -> Ordering) {
+    v.sort_by(|a, b| (*compare)(a, b))
+}
+
+fn unstable(v: &mut [DropCounter], compare: &mut FnMut(&DropCounter, &DropCounter) -> Ordering) {
+    v.sort_unstable_by(|a, b| (*compare)(a, b))
+}
+
+fn check(len: usize, keys: &[u32],
+         sort: fn(&mut [DropCounter], &mut FnMut(&DropCounter, &DropCounter) -> Ordering)) {
+    let drops = Arc::new((0..len).map(|_| AtomicUsize::new(0)).collect::<Vec<_>>());
+    let make = || {
+        keys.iter().enumerate().map(|(id, &key)| {
+            DropCounter { key: key, id: id, drops: drops.clone() }
+        }).collect::<Vec<_>>()
+    };
+
+    // count the comparisons a full sort needs...
+    let mut count = 0;
+    let mut v = make();
+    sort(&mut v, &mut |a, b| { count += 1; a.key.cmp(&b.key) });
+    drop(v);
+    for c in drops.iter() {
+        c.store(0, AtomicOrdering::Relaxed);
+    }
+
+    // ... and panic at a random one of them.
+    let panic_at = thread_rng().gen_range(0, count);
+    let v = Arc::new(Mutex::new(make()));
+    let v2 = v.clone();
+    let result = thread::spawn(move|| {
+        let mut v = v2.lock().unwrap();
+        let mut calls = 0;
+        sort(&mut v, &mut |a, b| {
+            if calls == panic_at {
+                panic!("comparison {} of {}", calls, count);
+            }
+            calls += 1;
+            a.key.cmp(&b.key)
+        });
+    }).join();
+    assert!(result.is_err());
+
+    {
+        let v = v.lock().unwrap_or_else(|e| e.into_inner());
+        let mut ids: Vec<_> = v.iter().map(|d| d.id).collect();
+        ids.sort();
+        assert_eq!(ids, (0..len).collect::<Vec<_>>());
+        for c in drops.iter() {
+            assert_eq!(c.load(AtomicOrdering::Relaxed), 0);
+        }
+    }
+    drop(v);
+    for (i, c) in drops.iter().enumerate() {
+        let count = c.load(AtomicOrdering::Relaxed);
+        assert!(count == 1, "found drop count == {} for i == {}, len == {}", count, i, len);
+    }
+}
+
+fn main() {
+    let mut rng = thread_rng();
+    for &len in &[2, 10, 21, 33, 64, 100, 500] {
+        for _ in 0..5 {
+            let keys: Vec<u32> = rng.gen_iter::<u32>().take(len).map(|k| k % 50).collect();
+            check(len, &keys, stable);
+            check(len, &keys, unstable);
+        }
+    }
+}
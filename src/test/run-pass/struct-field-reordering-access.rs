@@ -0,0 +1,77 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Field access, constants, destructuring, functional record update and
+// destructors all agree on where the fields of a reordered struct live.
+
+use std::cell::Cell;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct Reordered { a: u8, b: u64, c: u16, d: bool }
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct Tuple(u8, u32, u8);
+
+struct Unsized<T: ?Sized> { a: u8, b: u32, tail: T }
+
+struct Noisy<'a> { tag: u8, count: &'a Cell<u32>, value: u64 }
+
+impl<'a> Drop for Noisy<'a> {
+    fn drop(&mut self) {
+        assert_eq!(self.tag, 7);
+        assert_eq!(self.value, 0x1234_5678_9abc);
+        self.count.set(self.count.get() + 1);
+    }
+}
+
+static STATIC: Reordered = Reordered { a: 1, b: 2, c: 3, d: true };
+const CONST: Reordered = Reordered { a: 4, b: 5, c: 6, d: false };
+const CONST_B: u64 = CONST.b;
+
+fn sum(r: Reordered) -> u64 {
+    let Reordered { a, b, c, d } = r;
+    a as u64 + b + c as u64 + d as u64
+}
+
+fn main() {
+    assert_eq!(STATIC.a, 1);
+    assert_eq!(STATIC.b, 2);
+    assert_eq!(STATIC.c, 3);
+    assert!(STATIC.d);
+    assert_eq!(CONST_B, 5);
+    assert_eq!(CONST, Reordered { a: 4, b: 5, c: 6, d: false });
+
+    let mut r = Reordered { c: 30, a: 10, d: false, b: 20 };
+    assert_eq!(sum(r), 60);
+    r.b += 1;
+    r.d = true;
+    assert_eq!(r, Reordered { a: 10, b: 21, c: 30, d: true });
+
+    let s = Reordered { c: 4, .. r };
+    assert_eq!(s, Reordered { a: 10, b: 21, c: 4, d: true });
+
+    let t = Tuple(1, 2, 3);
+    let Tuple(x, y, z) = t;
+    assert_eq!((x, y, z), (1, 2, 3));
+    assert_eq!(t.1, 2);
+
+    let sized = Unsized { a: 5, b: 6, tail: [7u16, 8, 9] };
+    let unsized_: &Unsized<[u16]> = &sized;
+    assert_eq!(unsized_.a, 5);
+    assert_eq!(unsized_.b, 6);
+    assert_eq!(&unsized_.tail[..], &[7, 8, 9][..]);
+
+    let count = Cell::new(0);
+    {
+        let _n = Noisy { tag: 7, count: &count, value: 0x1234_5678_9abc };
+        let _m = Noisy { value: 0x1234_5678_9abc, count: &count, tag: 7 };
+    }
+    assert_eq!(count.get(), 2);
+}
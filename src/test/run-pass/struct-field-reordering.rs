@@ -0,0 +1,49 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Structs without `#[repr(C)]` or `#[repr(packed)]` have their fields
+// sorted by decreasing alignment to avoid padding between them.
+
+use std::mem::size_of;
+
+#[allow(dead_code)]
+struct Reordered { a: u8, b: u64, c: u8 }
+
+#[allow(dead_code)]
+#[repr(C)]
+struct Fixed { a: u8, b: u64, c: u8 }
+
+#[allow(dead_code)]
+#[repr(packed)]
+struct Packed { a: u8, b: u64, c: u8 }
+
+#[allow(dead_code)]
+struct Mixed { a: u8, b: u16, c: u8, d: u32, e: u8 }
+
+#[allow(dead_code)]
+struct Nested { x: u8, inner: Reordered, y: u8 }
+
+#[allow(dead_code)]
+struct Tuple(u8, u32, u8);
+
+#[allow(dead_code)]
+struct Generic<T> { a: u8, b: u64, c: T }
+
+fn main() {
+    assert_eq!(size_of::<Reordered>(), 16);
+    assert_eq!(size_of::<Fixed>(), 24);
+    assert_eq!(size_of::<Packed>(), 10);
+    assert_eq!(size_of::<Mixed>(), 12);
+    assert_eq!(size_of::<Nested>(), 24);
+    assert_eq!(size_of::<Tuple>(), 8);
+
+    // The last field of a generic struct keeps its place.
+    assert_eq!(size_of::<Generic<u64>>(), 24);
+}
@@ -10,9 +10,11 @@
 //
 // ignore-lexer-test FIXME #15883
 
+#[repr(C)]
 #[derive(Copy)]
 pub struct Quad { a: u64, b: u64, c: u64, d: u64 }
 
+#[repr(C)]
 #[derive(Copy)]
 pub struct Floats { a: f64, b: u8, c: f64 }
 
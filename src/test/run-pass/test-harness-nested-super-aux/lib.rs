@@ -0,0 +1,50 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Shared by test-harness-nested-super.rs, which builds it with `--test`, and
+// test-harness-nested-super-normal.rs, which builds it without.
+
+fn helper() -> usize { 1 }
+
+pub mod inner {
+    fn double(x: usize) -> usize { x * 2 }
+
+    pub mod leaf {
+        pub fn api() -> usize { super::double(super::super::helper()) }
+
+        #[cfg(test)]
+        mod tests {
+            use super::api;
+            use super::super::double;
+            use super::super::super::helper;
+
+            // Only reachable from `#[test]` functions.
+            fn check(x: usize) {
+                assert_eq!(double(x), x * 2);
+            }
+
+            #[test]
+            fn doubles_helper() {
+                check(helper());
+                assert_eq!(api(), 2);
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::double;
+
+        #[test]
+        fn doubles() {
+            assert_eq!(double(3), 6);
+        }
+    }
+}
@@ -0,0 +1,20 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// The same module tree as test-harness-nested-super.rs, built without `--test`.
+
+#![deny(dead_code, unused_imports)]
+
+#[path = "test-harness-nested-super-aux/lib.rs"]
+pub mod nested;
+
+pub fn main() {
+    assert_eq!(nested::inner::leaf::api(), 2);
+}
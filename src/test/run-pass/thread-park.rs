@@ -0,0 +1,50 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Checks the permit semantics of thread::park, and that park_timeout and
+// sleep wait for roughly the requested time. The timing bounds are loose
+// so that a loaded machine does not make the test fail.
+
+#![feature(std_misc, thread_sleep)]
+
+use std::sync::mpsc::channel;
+use std::thread;
+use std::time::Duration;
+
+fn main() {
+    // An unpark before the park is remembered, so this returns at once.
+    thread::current().unpark();
+    thread::park();
+
+    // Several unparks still leave a single permit behind.
+    thread::current().unpark();
+    thread::current().unpark();
+    thread::park();
+
+    // park_timeout gives up once the timeout expires. It may also wake up
+    // spuriously, so only an upper bound can be checked.
+    let waited = Duration::span(|| thread::park_timeout(Duration::milliseconds(100)));
+    assert!(waited < Duration::seconds(60));
+
+    let waited = Duration::span(|| thread::sleep(Duration::milliseconds(100)));
+    assert!(waited >= Duration::milliseconds(90));
+
+    // Another thread can wake us up.
+    let (tx, rx) = channel();
+    let main = thread::current();
+    let child = thread::spawn(move || {
+        rx.recv().unwrap();
+        main.unpark();
+    });
+    tx.send(()).unwrap();
+    let waited = Duration::span(|| thread::park_timeout(Duration::seconds(600)));
+    assert!(waited < Duration::seconds(300));
+    child.join().unwrap();
+}
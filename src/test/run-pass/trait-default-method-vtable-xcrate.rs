@@ -0,0 +1,49 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:trait_default_method_vtable_xcrate.rs
+
+// Vtables for impls that override only some of a trait's default methods,
+// built both in the crate defining the trait and downstream of it.
+
+extern crate trait_default_method_vtable_xcrate as aux;
+
+use aux::{Steps, Upstream};
+
+struct Downstream;
+
+impl Steps for Downstream {
+    fn second(&self) -> String { format!("downstream({})", self.third()) }
+}
+
+struct Untouched;
+
+impl Steps for Untouched {}
+
+fn run(s: &Steps) -> (String, String, String) {
+    (s.first(), s.second(), s.third())
+}
+
+pub fn main() {
+    let boxes: Vec<Box<Steps>> = vec![Box::new(Upstream), Box::new(Downstream),
+                                      Box::new(Untouched), aux::upstream_boxed()];
+    let results: Vec<_> = boxes.iter().map(|b| run(&**b)).collect();
+
+    assert_eq!(results[0], ("first(upstream(third))".to_string(),
+                            "upstream(third)".to_string(),
+                            "third".to_string()));
+    assert_eq!(results[1], ("first(downstream(third))".to_string(),
+                            "downstream(third)".to_string(),
+                            "third".to_string()));
+    assert_eq!(results[2], ("first(second(third))".to_string(),
+                            "second(third)".to_string(),
+                            "third".to_string()));
+    assert_eq!(results[3], results[0]);
+}
@@ -0,0 +1,63 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// `try!` converts errors with `From::from`, so a single `From` impl lets it
+// propagate `io::Error`s out of a function returning a custom error type.
+
+use std::io::{self, Read};
+use std::num::ParseIntError;
+
+#[derive(Debug)]
+enum MyError {
+    Io(io::Error),
+    Parse(ParseIntError),
+}
+
+impl From<io::Error> for MyError {
+    fn from(err: io::Error) -> MyError { MyError::Io(err) }
+}
+
+impl From<ParseIntError> for MyError {
+    fn from(err: ParseIntError) -> MyError { MyError::Parse(err) }
+}
+
+struct Broken;
+
+impl Read for Broken {
+    fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+        Err(io::Error::new(io::ErrorKind::Other, "broken", None))
+    }
+}
+
+fn read_number<R: Read>(mut r: R) -> Result<u32, MyError> {
+    let mut s = String::new();
+    try!(r.read_to_string(&mut s));
+    let n = try!(s.trim().parse::<u32>());
+    Ok(n)
+}
+
+// Errors of the same type still go through unchanged.
+fn same_type() -> io::Result<usize> {
+    let n = try!(Broken.read(&mut [0; 4]));
+    Ok(n)
+}
+
+fn main() {
+    assert_eq!(read_number(&b"42\n"[..]).unwrap(), 42);
+    match read_number(&b"forty-two"[..]) {
+        Err(MyError::Parse(_)) => {}
+        other => panic!("unexpected {:?}", other),
+    }
+    match read_number(Broken) {
+        Err(MyError::Io(ref e)) => assert_eq!(e.kind(), io::ErrorKind::Other),
+        other => panic!("unexpected {:?}", other),
+    }
+    assert!(same_type().is_err());
+}
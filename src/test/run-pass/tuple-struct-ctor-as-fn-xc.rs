@@ -0,0 +1,45 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:tuple_struct_ctor_xc.rs
+
+// Tuple struct constructors from another crate can be used as ordinary
+// function values, including through a re-export and with generics.
+
+extern crate tuple_struct_ctor_xc;
+
+use tuple_struct_ctor_xc::{Meters, Pair, Feet};
+
+fn apply<A, B, F: Fn(A) -> B>(f: F, a: A) -> B {
+    f(a)
+}
+
+pub fn main() {
+    let lengths: Vec<Meters> = (1..4).map(Meters).collect();
+    let total = lengths.iter().fold(0, |acc, &Meters(m)| acc + m);
+    assert_eq!(total, 6);
+
+    let make: fn(uint) -> Feet = Feet;
+    let Feet(f) = make(12);
+    assert_eq!(f, 12);
+
+    let pairs: Vec<Pair<char>> = vec!['a', 'b'].into_iter()
+                                               .map(|c| apply(|x| Pair(x, x), c))
+                                               .collect();
+    assert_eq!(pairs.len(), 2);
+    let Pair(x, y) = pairs[1];
+    assert_eq!((x, y), ('b', 'b'));
+
+    let make_pair: fn(int, int) -> Pair<int> = Pair;
+    let Pair(a, b) = make_pair(1, 2);
+    assert_eq!(a + b, 3);
+
+    assert_eq!(tuple_struct_ctor_xc::hidden(7), 7);
+}
@@ -0,0 +1,49 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Tests reading and writing values at unaligned addresses inside a byte
+// buffer and a packed struct.
+
+#![feature(core)]
+
+use std::mem;
+use std::ptr;
+
+#[repr(packed)]
+struct Packed {
+    tag: u8,
+    value: u64,
+}
+
+fn main() {
+    let mut buf = [0u8; 16];
+
+    unsafe {
+        for offset in 0..8 {
+            let p = buf.as_mut_ptr().offset(offset) as *mut u32;
+            ptr::write_unaligned(p, 0x01020304);
+            assert_eq!(ptr::read_unaligned(p as *const u32), 0x01020304);
+
+            // The bytes written must be exactly those of the value.
+            let expected: [u8; 4] = mem::transmute(0x01020304u32);
+            assert_eq!(&buf[offset as usize..offset as usize + 4], &expected[..]);
+            buf = [0u8; 16];
+        }
+
+        let p = buf.as_mut_ptr().offset(3) as *mut (u16, u64);
+        ptr::write_unaligned(p, (7, 0xdead_beef_cafe_f00d));
+        assert_eq!(ptr::read_unaligned(p as *const (u16, u64)), (7, 0xdead_beef_cafe_f00d));
+
+        let mut packed = Packed { tag: 1, value: 0 };
+        ptr::write_unaligned(&mut packed.value, 42);
+        assert_eq!(ptr::read_unaligned(&packed.value), 42);
+        assert_eq!(packed.tag, 1);
+    }
+}